@@ -7,13 +7,13 @@ pub mod kvstore;
 
 use tendermint_proto::abci::request::Value;
 use tendermint_proto::abci::{
-    response, Request, RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx,
-    RequestDeliverTx, RequestEcho, RequestEndBlock, RequestInfo, RequestInitChain,
-    RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestQuery, RequestSetOption, Response,
-    ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx, ResponseCommit,
-    ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseFlush, ResponseInfo,
-    ResponseInitChain, ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
-    ResponseQuery, ResponseSetOption,
+    response, response_apply_snapshot_chunk, response_offer_snapshot, Request,
+    RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx, RequestDeliverTx, RequestEcho,
+    RequestEndBlock, RequestInfo, RequestInitChain, RequestLoadSnapshotChunk, RequestOfferSnapshot,
+    RequestQuery, RequestSetOption, Response, ResponseApplySnapshotChunk, ResponseBeginBlock,
+    ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseEcho, ResponseEndBlock,
+    ResponseException, ResponseFlush, ResponseInfo, ResponseInitChain, ResponseListSnapshots,
+    ResponseLoadSnapshotChunk, ResponseOfferSnapshot, ResponseQuery, ResponseSetOption,
 };
 
 /// An ABCI application.
@@ -89,8 +89,13 @@ pub trait Application: Send + Clone + 'static {
     }
 
     /// Called when bootstrapping the node using state sync.
+    ///
+    /// By default, all snapshots are rejected, as applications must
+    /// implement all the snapshot methods to support state sync.
     fn offer_snapshot(&self, _request: RequestOfferSnapshot) -> ResponseOfferSnapshot {
-        Default::default()
+        ResponseOfferSnapshot {
+            result: response_offer_snapshot::Result::Reject as i32,
+        }
     }
 
     /// Used during state sync to retrieve chunks of snapshots from peers.
@@ -99,28 +104,54 @@ pub trait Application: Send + Clone + 'static {
     }
 
     /// Apply the given snapshot chunk to the application's state.
+    ///
+    /// By default, state sync is aborted, since no snapshot should have been
+    /// accepted in the first place.
     fn apply_snapshot_chunk(
         &self,
         _request: RequestApplySnapshotChunk,
     ) -> ResponseApplySnapshotChunk {
-        Default::default()
+        ResponseApplySnapshotChunk {
+            result: response_apply_snapshot_chunk::Result::Abort as i32,
+            ..Default::default()
+        }
     }
 }
 
 /// Provides a mechanism for the [`Server`] to execute incoming requests while
 /// expecting the correct response types.
-pub trait RequestDispatcher {
+///
+/// The request type identifies the version of the ABCI protocol the
+/// application implements, e.g. [`Request`] for an [`Application`].
+///
+/// [`Server`]: crate::Server
+pub trait RequestDispatcher<Req = Request>: Send + Clone + 'static {
+    /// The type of the responses to the requests.
+    type Response;
+
     /// Executes the relevant application method based on the type of the
     /// request, and produces the corresponding response.
-    fn handle(&self, request: Request) -> Response;
+    fn handle(&self, request: Req) -> Self::Response;
 }
 
 // Implement `RequestDispatcher` for all `Application`s.
 impl<A: Application> RequestDispatcher for A {
+    type Response = Response;
+
     fn handle(&self, request: Request) -> Response {
         tracing::debug!("Incoming request: {:?}", request);
+        let value = match request.value {
+            Some(value) => value,
+            None => {
+                return Response {
+                    value: Some(response::Value::Exception(ResponseException {
+                        error: "empty request".to_string(),
+                    })),
+                }
+            }
+        };
         Response {
-            value: Some(match request.value.unwrap() {
+            value: Some(match value {
                 Value::Echo(req) => response::Value::Echo(self.echo(req)),
                 Value::Flush(_) => response::Value::Flush(self.flush()),
                 Value::Info(req) => response::Value::Info(self.info(req)),
@@ -146,3 +177,34 @@ impl<A: Application> RequestDispatcher for A {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct NoopApp;
+
+    impl Application for NoopApp {}
+
+    #[test]
+    fn empty_request_yields_exception() {
+        match NoopApp.handle(Request { value: None }).value {
+            Some(response::Value::Exception(e)) => assert_eq!(e.error, "empty request"),
+            r => panic!("unexpected response: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn snapshots_are_rejected_by_default() {
+        let request = Request {
+            value: Some(Value::OfferSnapshot(RequestOfferSnapshot::default())),
+        };
+        match NoopApp.handle(request).value {
+            Some(response::Value::OfferSnapshot(res)) => {
+                assert_eq!(res.result, response_offer_snapshot::Result::Reject as i32)
+            }
+            r => panic!("unexpected response: {:?}", r),
+        }
+    }
+}
@@ -1,17 +1,26 @@
-//! In-memory key/value store ABCI application.
+//! In-memory, Merkle-ized key/value store ABCI application.
+//!
+//! Transactions are of the form `key=value`, or `key` (which sets the value
+//! of `key` to `key`). The application hash is the root of a simple Merkle
+//! tree built from the sorted key/value pairs, as in Tendermint's
+//! `SimpleMap`, so that query results can be proven against it.
 
-use crate::codec::{encode_varint, MAX_VARINT_LENGTH};
 use crate::{Application, Error, Result};
-use bytes::BytesMut;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::sync::mpsc::{channel, Receiver, Sender};
+use tendermint::merkle::proof::{Proof, ProofOp};
+use tendermint::merkle::{kv_pair_leaf, proofs_from_byte_vectors, simple_hash_from_byte_vectors};
 use tendermint_proto::abci::{
-    Event, EventAttribute, RequestCheckTx, RequestDeliverTx, RequestInfo, RequestQuery,
-    ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseInfo, ResponseQuery,
+    Event, EventAttribute, RequestCheckTx, RequestDeliverTx, RequestInfo, RequestInitChain,
+    RequestQuery, ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseInfo,
+    ResponseInitChain, ResponseQuery,
 };
 use tracing::{debug, info};
 
-/// In-memory, hashmap-backed key/value store ABCI application.
+/// Code of the responses to invalid transactions and queries.
+pub const CODE_INVALID: u32 = 1;
+
+/// In-memory, Merkle-ized key/value store ABCI application.
 ///
 /// This structure effectively just serves as a handle to the actual key/value
 /// store - the [`KeyValueStoreDriver`].
@@ -27,7 +36,10 @@ impl KeyValueStoreApp {
         (Self { cmd_tx }, KeyValueStoreDriver::new(cmd_rx))
     }
 
-    /// Attempt to retrieve the value associated with the given key.
+    /// Attempt to retrieve the value associated with the given key, as of
+    /// the last commit.
+    ///
+    /// Returns the height of the last commit along with the value.
     pub fn get<K: AsRef<str>>(&self, key: K) -> Result<(i64, Option<String>)> {
         let (result_tx, result_rx) = channel();
         channel_send(
@@ -40,10 +52,28 @@ impl KeyValueStoreApp {
         channel_recv(&result_rx)
     }
 
+    /// Attempt to retrieve the value associated with the given key, as of
+    /// the last commit, along with a proof of it against the application
+    /// hash of the last commit.
+    ///
+    /// No proof is returned for keys which don't exist.
+    pub fn get_with_proof<K: AsRef<str>>(&self, key: K) -> Result<(i64, Option<(String, Proof)>)> {
+        let (result_tx, result_rx) = channel();
+        channel_send(
+            &self.cmd_tx,
+            Command::GetWithProof {
+                key: key.as_ref().to_string(),
+                result_tx,
+            },
+        )?;
+        channel_recv(&result_rx)
+    }
+
     /// Attempt to set the value associated with the given key.
     ///
-    /// Optionally returns any pre-existing value associated with the given
-    /// key.
+    /// The new value is only visible to [`KeyValueStoreApp::get`] after the
+    /// next commit. Optionally returns any pre-existing value associated with
+    /// the given key.
     pub fn set<K, V>(&self, key: K, value: V) -> Result<Option<String>>
     where
         K: AsRef<str>,
@@ -82,46 +112,97 @@ impl Application for KeyValueStoreApp {
         }
     }
 
+    fn init_chain(&self, request: RequestInitChain) -> ResponseInitChain {
+        // The genesis application state, if any, is a JSON object mapping
+        // keys to values
+        let state = if request.app_state_bytes.is_empty() {
+            BTreeMap::new()
+        } else {
+            match serde_json::from_slice(&request.app_state_bytes) {
+                Ok(state) => state,
+                Err(e) => panic!("Failed to parse genesis application state: {}", e),
+            }
+        };
+        let (result_tx, result_rx) = channel();
+        channel_send(
+            &self.cmd_tx,
+            Command::InitChain {
+                initial_height: request.initial_height,
+                state,
+                result_tx,
+            },
+        )
+        .unwrap();
+        let app_hash = channel_recv(&result_rx).unwrap();
+        info!("Initialized chain {}", request.chain_id);
+        ResponseInitChain {
+            consensus_params: None,
+            validators: vec![],
+            app_hash,
+        }
+    }
+
     fn query(&self, request: RequestQuery) -> ResponseQuery {
         let key = match String::from_utf8(request.data.clone()) {
             Ok(s) => s,
-            Err(e) => panic!("Failed to intepret key as UTF-8: {}", e),
+            Err(e) => {
+                return ResponseQuery {
+                    code: CODE_INVALID,
+                    log: format!("key is not valid UTF-8: {}", e),
+                    key: request.data,
+                    ..Default::default()
+                }
+            }
         };
         debug!("Attempting to get key: {}", key);
-        match self.get(key.clone()) {
-            Ok((height, value_opt)) => match value_opt {
-                Some(value) => ResponseQuery {
-                    code: 0,
-                    log: "exists".to_string(),
-                    info: "".to_string(),
-                    index: 0,
-                    key: request.data,
-                    value: value.into_bytes(),
-                    proof_ops: None,
-                    height,
-                    codespace: "".to_string(),
-                },
-                None => ResponseQuery {
-                    code: 0,
-                    log: "does not exist".to_string(),
-                    info: "".to_string(),
-                    index: 0,
-                    key: request.data,
-                    value: vec![],
-                    proof_ops: None,
-                    height,
-                    codespace: "".to_string(),
-                },
+        let (height, value_opt) = if request.prove {
+            match self.get_with_proof(&key) {
+                Ok((height, value_opt)) => (height, value_opt.map(|(v, p)| (v, Some(p)))),
+                Err(e) => panic!("Failed to get key \"{}\": {:?}", key, e),
+            }
+        } else {
+            match self.get(&key) {
+                Ok((height, value_opt)) => (height, value_opt.map(|v| (v, None))),
+                Err(e) => panic!("Failed to get key \"{}\": {:?}", key, e),
+            }
+        };
+        match value_opt {
+            Some((value, proof)) => ResponseQuery {
+                code: 0,
+                log: "exists".to_string(),
+                info: "".to_string(),
+                index: 0,
+                key: request.data,
+                value: value.into_bytes(),
+                proof_ops: proof.map(Into::into),
+                height,
+                codespace: "".to_string(),
+            },
+            None => ResponseQuery {
+                code: 0,
+                log: "does not exist".to_string(),
+                info: "".to_string(),
+                index: 0,
+                key: request.data,
+                value: vec![],
+                proof_ops: None,
+                height,
+                codespace: "".to_string(),
             },
-            Err(e) => panic!("Failed to get key \"{}\": {:?}", key, e),
         }
     }
 
-    fn check_tx(&self, _request: RequestCheckTx) -> ResponseCheckTx {
+    fn check_tx(&self, request: RequestCheckTx) -> ResponseCheckTx {
+        // Transactions are only validated statelessly, since any key can be
+        // set to any value
+        let (code, log) = match parse_tx(&request.tx) {
+            Ok(_) => (0, "".to_string()),
+            Err(e) => (CODE_INVALID, e),
+        };
         ResponseCheckTx {
-            code: 0,
+            code,
             data: vec![],
-            log: "".to_string(),
+            log,
             info: "".to_string(),
             gas_wanted: 1,
             gas_used: 0,
@@ -131,12 +212,15 @@ impl Application for KeyValueStoreApp {
     }
 
     fn deliver_tx(&self, request: RequestDeliverTx) -> ResponseDeliverTx {
-        let tx = String::from_utf8(request.tx).unwrap();
-        let tx_parts = tx.split('=').collect::<Vec<&str>>();
-        let (key, value) = if tx_parts.len() == 2 {
-            (tx_parts[0], tx_parts[1])
-        } else {
-            (tx.as_ref(), tx.as_ref())
+        let (key, value) = match parse_tx(&request.tx) {
+            Ok(kv) => kv,
+            Err(log) => {
+                return ResponseDeliverTx {
+                    code: CODE_INVALID,
+                    log,
+                    ..Default::default()
+                }
+            }
         };
         let _ = self.set(key, value).unwrap();
         ResponseDeliverTx {
@@ -182,10 +266,27 @@ impl Application for KeyValueStoreApp {
     }
 }
 
+/// Parse a `key=value` (or `key`) transaction.
+fn parse_tx(tx: &[u8]) -> std::result::Result<(&str, &str), String> {
+    let tx = std::str::from_utf8(tx).map_err(|e| format!("tx is not valid UTF-8: {}", e))?;
+    let (key, value) = match tx.find('=') {
+        Some(i) => (&tx[..i], &tx[i + 1..]),
+        None => (tx, tx),
+    };
+    if key.is_empty() {
+        return Err("key is empty".to_string());
+    }
+    if value.contains('=') {
+        return Err("tx must be of the form key=value".to_string());
+    }
+    Ok((key, value))
+}
+
 /// Manages key/value store state.
 #[derive(Debug)]
 pub struct KeyValueStoreDriver {
-    store: HashMap<String, String>,
+    store: BTreeMap<String, String>,
+    committed: BTreeMap<String, String>,
     height: i64,
     app_hash: Vec<u8>,
     cmd_rx: Receiver<Command>,
@@ -194,9 +295,10 @@ pub struct KeyValueStoreDriver {
 impl KeyValueStoreDriver {
     fn new(cmd_rx: Receiver<Command>) -> Self {
         Self {
-            store: HashMap::new(),
+            store: BTreeMap::new(),
+            committed: BTreeMap::new(),
             height: 0,
-            app_hash: vec![0_u8; MAX_VARINT_LENGTH],
+            app_hash: vec![],
             cmd_rx,
         }
     }
@@ -212,13 +314,29 @@ impl KeyValueStoreDriver {
                 Command::GetInfo { result_tx } => {
                     channel_send(&result_tx, (self.height, self.app_hash.clone()))?
                 }
+                Command::InitChain {
+                    initial_height,
+                    state,
+                    result_tx,
+                } => {
+                    // Commits start at the initial height
+                    self.height = initial_height.max(1) - 1;
+                    self.store = state;
+                    self.committed = self.store.clone();
+                    self.app_hash = self.compute_app_hash();
+                    channel_send(&result_tx, self.app_hash.clone())?;
+                }
                 Command::Get { key, result_tx } => {
                     debug!("Getting value for \"{}\"", key);
                     channel_send(
                         &result_tx,
-                        (self.height, self.store.get(&key).map(Clone::clone)),
+                        (self.height, self.committed.get(&key).cloned()),
                     )?;
                 }
+                Command::GetWithProof { key, result_tx } => {
+                    debug!("Getting value and proof for \"{}\"", key);
+                    channel_send(&result_tx, (self.height, self.prove(&key)))?;
+                }
                 Command::Set {
                     key,
                     value,
@@ -233,25 +351,59 @@ impl KeyValueStoreDriver {
     }
 
     fn commit(&mut self, result_tx: Sender<(i64, Vec<u8>)>) -> Result<()> {
-        // As in the Go-based key/value store, simply encode the number of
-        // items as the "app hash"
-        let mut app_hash = BytesMut::with_capacity(MAX_VARINT_LENGTH);
-        encode_varint(self.store.len() as u64, &mut app_hash);
-        self.app_hash = app_hash.to_vec();
+        self.committed = self.store.clone();
+        self.app_hash = self.compute_app_hash();
         self.height += 1;
         channel_send(&result_tx, (self.height, self.app_hash.clone()))
     }
+
+    // The leaves of the tree are the committed key/value pairs, sorted by key
+    fn leaves(&self) -> Vec<Vec<u8>> {
+        self.committed
+            .iter()
+            .map(|(k, v)| kv_pair_leaf(k.as_bytes(), v.as_bytes()))
+            .collect()
+    }
+
+    fn compute_app_hash(&self) -> Vec<u8> {
+        simple_hash_from_byte_vectors(self.leaves()).to_vec()
+    }
+
+    fn prove(&self, key: &str) -> Option<(String, Proof)> {
+        let index = self.committed.keys().position(|k| k == key)?;
+        let value = self.committed[key].clone();
+        let (_, mut proofs) = proofs_from_byte_vectors(&self.leaves());
+        let proof = Proof {
+            ops: vec![ProofOp::value(
+                key.as_bytes().to_vec(),
+                proofs.swap_remove(index),
+            )],
+        };
+        Some((value, proof))
+    }
 }
 
 #[derive(Debug, Clone)]
 enum Command {
-    /// Get the height of the last commit.
+    /// Get the height and application hash of the last commit.
     GetInfo { result_tx: Sender<(i64, Vec<u8>)> },
-    /// Get the key associated with `key`.
+    /// Initialize the state of the application from the genesis state,
+    /// returning the resulting application hash.
+    InitChain {
+        initial_height: i64,
+        state: BTreeMap<String, String>,
+        result_tx: Sender<Vec<u8>>,
+    },
+    /// Get the committed value associated with `key`.
     Get {
         key: String,
         result_tx: Sender<(i64, Option<String>)>,
     },
+    /// Get the committed value associated with `key`, along with its proof.
+    GetWithProof {
+        key: String,
+        result_tx: Sender<(i64, Option<(String, Proof)>)>,
+    },
     /// Set the value of `key` to to `value`.
     Set {
         key: String,
@@ -1,5 +1,6 @@
 //! In-memory key/value store application for Tendermint.
 
+use std::path::PathBuf;
 use structopt::StructOpt;
 use tendermint_abci::{KeyValueStoreApp, ServerBuilder};
 use tracing_subscriber::filter::LevelFilter;
@@ -14,6 +15,10 @@ struct Opt {
     #[structopt(short, long, default_value = "26658")]
     port: u16,
 
+    /// Bind the server to this Unix domain socket instead of TCP.
+    #[structopt(short, long)]
+    unix: Option<PathBuf>,
+
     /// The default server read buffer size, in bytes, for each incoming client
     /// connection.
     #[structopt(short, long, default_value = "1048576")]
@@ -40,9 +45,12 @@ fn main() {
     tracing_subscriber::fmt().with_max_level(log_level).init();
 
     let (app, driver) = KeyValueStoreApp::new();
-    let server = ServerBuilder::new(opt.read_buf_size)
-        .bind(format!("{}:{}", opt.host, opt.port), app)
-        .unwrap();
+    let builder = ServerBuilder::new(opt.read_buf_size);
+    let server = match opt.unix {
+        Some(path) => builder.bind_unix(path, app),
+        None => builder.bind(format!("{}:{}", opt.host, opt.port), app),
+    }
+    .unwrap();
     std::thread::spawn(move || driver.run());
     server.listen().unwrap();
 }
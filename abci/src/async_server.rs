@@ -0,0 +1,324 @@
+//! Asynchronous ABCI application server, on top of Tokio.
+
+use crate::application::RequestDispatcher;
+use crate::codec::{decode_length_delimited, encode_length_delimited};
+use crate::net::Addr;
+use crate::server::DEFAULT_SERVER_READ_BUF_SIZE;
+use crate::{Application, Result};
+use bytes::BytesMut;
+use std::future::Future;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tendermint_proto::abci::{request, Request, Response};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, WriteHalf};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::{mpsc, watch, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+/// The maximum number of `CheckTx` requests of a connection which are
+/// handled concurrently by default.
+pub const DEFAULT_MAX_CONCURRENT_CHECK_TX: usize = 64;
+
+/// Allows us to configure and construct an asynchronous ABCI server.
+pub struct AsyncServerBuilder {
+    read_buf_size: usize,
+    max_concurrent_check_tx: usize,
+}
+
+impl AsyncServerBuilder {
+    /// Builder constructor.
+    ///
+    /// Allows you to specify the read buffer size used when reading chunks of
+    /// incoming data from the client. This needs to be tuned for your
+    /// application.
+    pub fn new(read_buf_size: usize) -> Self {
+        Self {
+            read_buf_size,
+            max_concurrent_check_tx: DEFAULT_MAX_CONCURRENT_CHECK_TX,
+        }
+    }
+
+    /// Set the maximum number of `CheckTx` requests of a connection which
+    /// are handled concurrently (at least 1).
+    pub fn max_concurrent_check_tx(mut self, max_concurrent_check_tx: usize) -> Self {
+        self.max_concurrent_check_tx = max_concurrent_check_tx.max(1);
+        self
+    }
+
+    /// Constructor for an asynchronous ABCI server.
+    ///
+    /// Binds the server to the given address. You must subsequently call
+    /// [`AsyncServer::listen`] or [`AsyncServer::listen_until`] in order for
+    /// incoming connections' requests to be routed to the specified ABCI
+    /// application.
+    pub async fn bind<Addr, App>(self, addr: Addr, app: App) -> Result<AsyncServer<App>>
+    where
+        Addr: ToSocketAddrs,
+        App: Application,
+    {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(Listener::Tcp(listener), app)
+    }
+
+    /// Constructor for an asynchronous ABCI server listening on a Unix
+    /// domain socket at the given path.
+    ///
+    /// See [`AsyncServerBuilder::bind`].
+    #[cfg(unix)]
+    pub async fn bind_unix<P, App>(self, path: P, app: App) -> Result<AsyncServer<App>>
+    where
+        P: AsRef<Path>,
+        App: Application,
+    {
+        let path = path.as_ref().to_path_buf();
+        let listener = UnixListener::bind(&path)?;
+        self.serve(Listener::Unix(listener, path), app)
+    }
+
+    /// Constructor for an asynchronous ABCI server listening at the given
+    /// address, which may be a TCP or (on Unix) a Unix domain socket
+    /// address.
+    ///
+    /// See [`AsyncServerBuilder::bind`].
+    pub async fn bind_addr<App>(self, addr: &Addr, app: App) -> Result<AsyncServer<App>>
+    where
+        App: Application,
+    {
+        match addr {
+            Addr::Tcp(host_port) => self.bind(host_port.as_str(), app).await,
+            #[cfg(unix)]
+            Addr::Unix(path) => self.bind_unix(path, app).await,
+        }
+    }
+
+    fn serve<App: Application>(self, listener: Listener, app: App) -> Result<AsyncServer<App>> {
+        let local_addr = match &listener {
+            Listener::Tcp(listener) => listener.local_addr()?.to_string(),
+            #[cfg(unix)]
+            Listener::Unix(_, path) => Addr::Unix(path.clone()).to_string(),
+        };
+        info!("Async ABCI server running at {}", local_addr);
+        Ok(AsyncServer {
+            app,
+            listener,
+            local_addr,
+            read_buf_size: self.read_buf_size,
+            max_concurrent_check_tx: self.max_concurrent_check_tx,
+        })
+    }
+}
+
+impl Default for AsyncServerBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_SERVER_READ_BUF_SIZE)
+    }
+}
+
+/// An asynchronous, TCP- or Unix domain socket-based server for serving a
+/// specific ABCI application.
+///
+/// Each incoming connection is handled in a separate task, with its own
+/// clone of the ABCI application, whose methods are run on Tokio's blocking
+/// thread pool. Responses are always sent in the order of the requests, as
+/// the protocol requires, but while every other request of a connection is
+/// handled on its own, after all the previous ones and before any of the
+/// next ones, consecutive `CheckTx` requests are handled concurrently. This
+/// serializes the consensus connection, while allowing the application to
+/// check the transactions of the mempool connection in parallel.
+pub struct AsyncServer<App> {
+    app: App,
+    listener: Listener,
+    local_addr: String,
+    read_buf_size: usize,
+    max_concurrent_check_tx: usize,
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+impl<App: Application> AsyncServer<App> {
+    /// Listen for incoming connections, forever.
+    pub async fn listen(self) -> Result<()> {
+        self.listen_until(std::future::pending()).await
+    }
+
+    /// Listen for incoming connections until the given future completes, at
+    /// which point the server shuts down gracefully: it stops accepting
+    /// connections and reading requests, and waits for the responses to the
+    /// requests it already read to be sent before closing its connections.
+    pub async fn listen_until<F>(self, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()>,
+    {
+        let (shutdown_tx, shutdown_rx) = watch::channel(());
+        // Each connection holds a clone of the sender, so that the receiver
+        // only completes once all the connections are closed.
+        let (closed_tx, mut closed_rx) = mpsc::channel::<()>(1);
+        let mut next_conn_id = 0_u64;
+        tokio::pin!(shutdown);
+        loop {
+            let mut conn = |addr: String| {
+                // Identify connections by their ID, since Tendermint opens
+                // several of them from the same address
+                let addr = format!("{} (connection {})", addr, next_conn_id);
+                next_conn_id += 1;
+                info!("Incoming connection from: {}", addr);
+                Connection {
+                    app: self.app.clone(),
+                    addr,
+                    read_buf_size: self.read_buf_size,
+                    max_concurrent_check_tx: self.max_concurrent_check_tx,
+                    shutdown: shutdown_rx.clone(),
+                    _closed: closed_tx.clone(),
+                }
+            };
+            match &self.listener {
+                Listener::Tcp(listener) => {
+                    let (stream, addr) = tokio::select! {
+                        _ = &mut shutdown => break,
+                        accepted = listener.accept() => accepted?,
+                    };
+                    tokio::spawn(conn(addr.to_string()).handle(stream));
+                }
+                #[cfg(unix)]
+                Listener::Unix(listener, path) => {
+                    let (stream, _) = tokio::select! {
+                        _ = &mut shutdown => break,
+                        accepted = listener.accept() => accepted?,
+                    };
+                    tokio::spawn(conn(path.display().to_string()).handle(stream));
+                }
+            }
+        }
+
+        info!("Shutting down ABCI server at {}", self.local_addr);
+        let _ = shutdown_tx.send(());
+        drop(closed_tx);
+        let _ = closed_rx.recv().await;
+        Ok(())
+    }
+
+    /// Getter for this server's local address.
+    pub fn local_addr(&self) -> String {
+        self.local_addr.clone()
+    }
+}
+
+struct Connection<App> {
+    app: App,
+    addr: String,
+    read_buf_size: usize,
+    max_concurrent_check_tx: usize,
+    shutdown: watch::Receiver<()>,
+    _closed: mpsc::Sender<()>,
+}
+
+impl<App: Application> Connection<App> {
+    async fn handle<S>(mut self, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (mut reader, writer) = tokio::io::split(stream);
+        // Responses are sent in order by a separate task, so that reading
+        // requests isn't held up by the handling of the previous ones.
+        let (response_tx, response_rx) = mpsc::channel(self.max_concurrent_check_tx);
+        let writer = tokio::spawn(send_responses(writer, response_rx, self.addr.clone()));
+        // `CheckTx` requests are handled with a shared lock, and all others
+        // with an exclusive one.
+        let lock = Arc::new(RwLock::new(()));
+        let mut read_buf = BytesMut::new();
+        let mut read_window = vec![0_u8; self.read_buf_size];
+
+        info!("Listening for incoming requests from {}", self.addr);
+        loop {
+            let request = match decode_length_delimited::<Request>(&mut read_buf) {
+                Ok(Some(request)) => request,
+                Ok(None) => {
+                    // Not enough data to decode a request, let's read more
+                    let bytes_read = tokio::select! {
+                        _ = self.shutdown.changed() => break,
+                        result = reader.read(&mut read_window) => match result {
+                            Ok(0) => {
+                                info!("Client {} terminated stream", self.addr);
+                                break;
+                            }
+                            Ok(bytes_read) => bytes_read,
+                            Err(e) => {
+                                error!(
+                                    "Failed to read incoming request from client {}: {:?}",
+                                    self.addr, e
+                                );
+                                break;
+                            }
+                        },
+                    };
+                    read_buf.extend_from_slice(&read_window[..bytes_read]);
+                    continue;
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to decode incoming request from client {}: {:?}",
+                        self.addr, e
+                    );
+                    break;
+                }
+            };
+
+            let app = self.app.clone();
+            let task = if let Some(request::Value::CheckTx(_)) = request.value {
+                let guard = lock.clone().read_owned().await;
+                tokio::task::spawn_blocking(move || {
+                    let _guard = guard;
+                    app.handle(request)
+                })
+            } else {
+                let guard = lock.clone().write_owned().await;
+                tokio::task::spawn_blocking(move || {
+                    let _guard = guard;
+                    app.handle(request)
+                })
+            };
+            if response_tx.send(task).await.is_err() {
+                // The writer failed, and already logged why
+                break;
+            }
+        }
+
+        // Let the writer send the responses to the requests already read
+        drop(response_tx);
+        let _ = writer.await;
+    }
+}
+
+async fn send_responses<S: AsyncWrite>(
+    mut writer: WriteHalf<S>,
+    mut responses: mpsc::Receiver<JoinHandle<Response>>,
+    addr: String,
+) {
+    let mut write_buf = BytesMut::new();
+    while let Some(task) = responses.recv().await {
+        let response = match task.await {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Failed handling request from client {}: {:?}", addr, e);
+                return;
+            }
+        };
+        let result = match encode_length_delimited(response, &mut write_buf) {
+            Ok(()) => writer.write_all(&write_buf).await.map_err(Into::into),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            error!("Failed sending response to client {}: {:?}", addr, e);
+            return;
+        }
+        write_buf.clear();
+    }
+}
@@ -1,8 +1,14 @@
 //! Blocking ABCI client.
 
 use crate::codec::ClientCodec;
+use crate::net::{Addr, Stream};
 use crate::{Error, Result};
 use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+use std::time::Duration;
 use tendermint_proto::abci::{
     request, response, RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx, RequestCommit,
     RequestDeliverTx, RequestEndBlock, RequestFlush, RequestInfo, RequestInitChain,
@@ -21,18 +27,52 @@ pub const DEFAULT_CLIENT_READ_BUF_SIZE: usize = 1024;
 /// Builder for a blocking ABCI client.
 pub struct ClientBuilder {
     read_buf_size: usize,
+    timeout: Option<Duration>,
 }
 
 impl ClientBuilder {
     /// Builder constructor.
     pub fn new(read_buf_size: usize) -> Self {
-        Self { read_buf_size }
+        Self {
+            read_buf_size,
+            timeout: None,
+        }
+    }
+
+    /// Fail requests whose sending, or whose response, takes longer than the
+    /// given duration, instead of blocking indefinitely (the default).
+    ///
+    /// This is mostly useful when testing applications, which may well not
+    /// respond at all.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
     }
 
     /// Client constructor that attempts to connect to the given network
     /// address.
     pub fn connect<A: ToSocketAddrs>(self, addr: A) -> Result<Client> {
         let stream = TcpStream::connect(addr)?;
+        self.client(Stream::Tcp(stream))
+    }
+
+    /// Client constructor that attempts to connect to the Unix domain socket
+    /// at the given path.
+    #[cfg(unix)]
+    pub fn connect_unix<P: AsRef<Path>>(self, path: P) -> Result<Client> {
+        let stream = UnixStream::connect(path)?;
+        self.client(Stream::Unix(stream))
+    }
+
+    /// Client constructor that attempts to connect to the given address,
+    /// which may be a TCP or (on Unix) a Unix domain socket address.
+    pub fn connect_addr(self, addr: &Addr) -> Result<Client> {
+        let stream = Stream::connect(addr)?;
+        self.client(stream)
+    }
+
+    fn client(self, stream: Stream) -> Result<Client> {
+        stream.set_timeout(self.timeout)?;
         Ok(Client {
             codec: ClientCodec::new(stream, self.read_buf_size),
         })
@@ -41,15 +81,17 @@ impl ClientBuilder {
 
 impl Default for ClientBuilder {
     fn default() -> Self {
-        Self {
-            read_buf_size: DEFAULT_CLIENT_READ_BUF_SIZE,
-        }
+        Self::new(DEFAULT_CLIENT_READ_BUF_SIZE)
     }
 }
 
 /// Blocking ABCI client.
+///
+/// Besides allowing to drive an application as Tendermint would, e.g. to test
+/// it, the client can forward requests verbatim with [`Client::perform`], as
+/// an ABCI proxy would.
 pub struct Client {
-    codec: ClientCodec<TcpStream>,
+    codec: ClientCodec<Stream>,
 }
 
 macro_rules! perform {
@@ -102,6 +144,8 @@ impl Client {
         perform!(self, EndBlock, req)
     }
 
+    /// Signal that messages queued on the client should be flushed to the
+    /// server.
     pub fn flush(&mut self) -> Result<ResponseFlush> {
         perform!(self, Flush, RequestFlush {})
     }
@@ -142,13 +186,19 @@ impl Client {
         perform!(self, ApplySnapshotChunk, req)
     }
 
-    fn perform(&mut self, req: request::Value) -> Result<response::Value> {
+    /// Send the given request, and wait for the server's response to it.
+    ///
+    /// Exceptions reported by the server are returned as
+    /// [`Error::ServerException`], but the response isn't otherwise checked
+    /// to match the request.
+    pub fn perform(&mut self, req: request::Value) -> Result<response::Value> {
         self.codec.send(Request { value: Some(req) })?;
         let res = self
             .codec
             .next()
             .ok_or(Error::ServerConnectionTerminated)??;
         match res.value {
+            Some(response::Value::Exception(e)) => Err(Error::ServerException(e.error).into()),
             Some(value) => Ok(value),
             None => Err(Error::MalformedServerResponse.into()),
         }
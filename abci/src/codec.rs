@@ -15,8 +15,22 @@ use tendermint_proto::abci::{Request, Response};
 /// we're encountering a decoding error for a varint.
 pub const MAX_VARINT_LENGTH: usize = 16;
 
-/// The server receives incoming requests, and sends outgoing responses.
-pub type ServerCodec<S> = Codec<S, Request, Response>;
+/// A message of a version of the ABCI protocol, which determines how the
+/// length of the messages is encoded on the wire.
+pub trait AbciMessage: Message + Default {
+    /// Whether the length prefix of messages is a signed varint, as up to
+    /// Tendermint v0.34, rather than an unsigned one, as from CometBFT v0.37
+    /// on.
+    const SIGNED_LENGTH: bool = true;
+}
+
+impl AbciMessage for Request {}
+
+impl AbciMessage for Response {}
+
+/// The server receives incoming requests, and sends outgoing responses (of
+/// the ABCI protocol of Tendermint v0.34, unless specified otherwise).
+pub type ServerCodec<S, Req = Request, Res = Response> = Codec<S, Req, Res>;
 
 #[cfg(feature = "client")]
 /// The client sends outgoing requests, and receives incoming responses.
@@ -38,8 +52,8 @@ pub struct Codec<S, I, O> {
 impl<S, I, O> Codec<S, I, O>
 where
     S: Read + Write,
-    I: Message + Default,
-    O: Message,
+    I: AbciMessage,
+    O: AbciMessage,
 {
     /// Constructor.
     pub fn new(stream: S, read_buf_size: usize) -> Self {
@@ -58,7 +72,7 @@ where
 impl<S, I, O> Iterator for Codec<S, I, O>
 where
     S: Read,
-    I: Message + Default,
+    I: AbciMessage,
 {
     type Item = Result<I>;
 
@@ -90,7 +104,7 @@ where
 impl<S, I, O> Codec<S, I, O>
 where
     S: Write,
-    O: Message,
+    O: AbciMessage,
 {
     /// Send a message using this codec.
     pub fn send(&mut self, message: O) -> Result<()> {
@@ -113,13 +127,17 @@ where
 /// Encode the given message with a length prefix.
 pub fn encode_length_delimited<M, B>(message: M, mut dst: &mut B) -> Result<()>
 where
-    M: Message,
+    M: AbciMessage,
     B: BufMut,
 {
     let mut buf = BytesMut::new();
     message.encode(&mut buf)?;
     let buf = buf.freeze();
-    encode_varint(buf.len() as u64, &mut dst);
+    if M::SIGNED_LENGTH {
+        encode_varint(buf.len() as u64, &mut dst);
+    } else {
+        prost::encoding::encode_varint(buf.len() as u64, &mut dst);
+    }
     dst.put(buf);
     Ok(())
 }
@@ -127,11 +145,16 @@ where
 /// Attempt to decode a message of type `M` from the given source buffer.
 pub fn decode_length_delimited<M>(src: &mut BytesMut) -> Result<Option<M>>
 where
-    M: Message + Default,
+    M: AbciMessage,
 {
     let src_len = src.len();
     let mut tmp = src.clone().freeze();
-    let encoded_len = match decode_varint(&mut tmp) {
+    let encoded_len = if M::SIGNED_LENGTH {
+        decode_varint(&mut tmp)
+    } else {
+        prost::encoding::decode_varint(&mut tmp).map_err(Into::into)
+    };
+    let encoded_len = match encoded_len {
         Ok(len) => len,
         // We've potentially only received a partial length delimiter
         Err(_) if src_len <= MAX_VARINT_LENGTH => return Ok(None),
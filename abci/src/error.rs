@@ -11,6 +11,9 @@ pub enum Error {
     #[error("malformed server response")]
     MalformedServerResponse,
 
+    #[error("server exception: {0}")]
+    ServerException(String),
+
     #[error("unexpected server response type: expected {0}, but got {1:?}")]
     UnexpectedServerResponseType(String, tendermint_proto::abci::response::Value),
 
@@ -19,4 +22,7 @@ pub enum Error {
 
     #[error("channel receive error: {0}")]
     ChannelRecv(String),
+
+    #[error("invalid ABCI server address: {0}")]
+    InvalidAddress(String),
 }
@@ -54,20 +54,32 @@
 //! ```
 
 mod application;
+#[cfg(feature = "async-server")]
+mod async_server;
 #[cfg(feature = "client")]
 mod client;
 mod codec;
 mod error;
+#[cfg(feature = "client")]
+mod local_client;
+mod net;
 mod server;
+#[cfg(feature = "v0_38")]
+pub mod v0_38;
 
 // Re-exported
 pub use eyre::Result;
 
 // Common exports
-pub use application::Application;
+pub use application::{Application, RequestDispatcher};
+#[cfg(feature = "async-server")]
+pub use async_server::{AsyncServer, AsyncServerBuilder};
 #[cfg(feature = "client")]
 pub use client::{Client, ClientBuilder};
 pub use error::Error;
+#[cfg(feature = "client")]
+pub use local_client::LocalClient;
+pub use net::Addr;
 pub use server::{Server, ServerBuilder};
 
 // Example applications
@@ -0,0 +1,144 @@
+//! In-process ABCI client.
+
+use crate::application::RequestDispatcher;
+use crate::{Application, Error, Result};
+use std::sync::{Arc, Mutex, PoisonError};
+use tendermint_proto::abci::{
+    request, response, Request, RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx,
+    RequestDeliverTx, RequestEcho, RequestEndBlock, RequestInfo, RequestInitChain,
+    RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestQuery, RequestSetOption,
+    ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx, ResponseCommit,
+    ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseFlush, ResponseInfo,
+    ResponseInitChain, ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
+    ResponseQuery, ResponseSetOption,
+};
+
+/// ABCI client which calls an application directly, in the same process,
+/// without any socket or serialization in between.
+///
+/// This is useful to test applications, or to embed one in a node. As with
+/// Tendermint's local client, the calls of a client and of all its clones
+/// are serialized, so that e.g. the consensus and mempool connections can
+/// be given clones of the same client, and the application doesn't see
+/// concurrent calls.
+#[derive(Clone)]
+pub struct LocalClient<App> {
+    app: App,
+    lock: Arc<Mutex<()>>,
+}
+
+impl<App: Application> LocalClient<App> {
+    /// Create a client calling the given application.
+    pub fn new(app: App) -> Self {
+        Self {
+            app,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Ask the application to echo back a message.
+    pub fn echo(&self, req: RequestEcho) -> ResponseEcho {
+        self.call(|app| app.echo(req))
+    }
+
+    /// Request information about the ABCI application.
+    pub fn info(&self, req: RequestInfo) -> ResponseInfo {
+        self.call(|app| app.info(req))
+    }
+
+    /// To be called once upon genesis.
+    pub fn init_chain(&self, req: RequestInitChain) -> ResponseInitChain {
+        self.call(|app| app.init_chain(req))
+    }
+
+    /// Query the application for data at the current or past height.
+    pub fn query(&self, req: RequestQuery) -> ResponseQuery {
+        self.call(|app| app.query(req))
+    }
+
+    /// Check the given transaction before putting it into the local mempool.
+    pub fn check_tx(&self, req: RequestCheckTx) -> ResponseCheckTx {
+        self.call(|app| app.check_tx(req))
+    }
+
+    /// Signal the beginning of a new block, prior to any `DeliverTx` calls.
+    pub fn begin_block(&self, req: RequestBeginBlock) -> ResponseBeginBlock {
+        self.call(|app| app.begin_block(req))
+    }
+
+    /// Apply a transaction to the application's state.
+    pub fn deliver_tx(&self, req: RequestDeliverTx) -> ResponseDeliverTx {
+        self.call(|app| app.deliver_tx(req))
+    }
+
+    /// Signal the end of a block.
+    pub fn end_block(&self, req: RequestEndBlock) -> ResponseEndBlock {
+        self.call(|app| app.end_block(req))
+    }
+
+    /// Signal that messages queued on the client should be flushed to the
+    /// application, which is a no-op for the application itself.
+    pub fn flush(&self) -> ResponseFlush {
+        self.call(|app| app.flush())
+    }
+
+    /// Commit the current state at the current height.
+    pub fn commit(&self) -> ResponseCommit {
+        self.call(|app| app.commit())
+    }
+
+    /// Request that the application set an option to a particular value.
+    pub fn set_option(&self, req: RequestSetOption) -> ResponseSetOption {
+        self.call(|app| app.set_option(req))
+    }
+
+    /// Used during state sync to discover available snapshots on peers.
+    pub fn list_snapshots(&self) -> ResponseListSnapshots {
+        self.call(|app| app.list_snapshots())
+    }
+
+    /// Called when bootstrapping the node using state sync.
+    pub fn offer_snapshot(&self, req: RequestOfferSnapshot) -> ResponseOfferSnapshot {
+        self.call(|app| app.offer_snapshot(req))
+    }
+
+    /// Used during state sync to retrieve chunks of snapshots from peers.
+    pub fn load_snapshot_chunk(&self, req: RequestLoadSnapshotChunk) -> ResponseLoadSnapshotChunk {
+        self.call(|app| app.load_snapshot_chunk(req))
+    }
+
+    /// Apply the given snapshot chunk to the application's state.
+    pub fn apply_snapshot_chunk(
+        &self,
+        req: RequestApplySnapshotChunk,
+    ) -> ResponseApplySnapshotChunk {
+        self.call(|app| app.apply_snapshot_chunk(req))
+    }
+
+    /// Dispatch the given request to the application, as a server would.
+    ///
+    /// As with [`Client::perform`], exceptions are returned as
+    /// [`Error::ServerException`].
+    ///
+    /// [`Client::perform`]: crate::Client::perform
+    pub fn perform(&self, req: request::Value) -> Result<response::Value> {
+        let res = self.call(|app| app.handle(Request { value: Some(req) }));
+        match res.value {
+            Some(response::Value::Exception(e)) => Err(Error::ServerException(e.error).into()),
+            Some(value) => Ok(value),
+            None => Err(Error::MalformedServerResponse.into()),
+        }
+    }
+
+    /// Get the application this client calls.
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    fn call<R>(&self, f: impl FnOnce(&App) -> R) -> R {
+        // A panic in the application doesn't leave the lock in an
+        // inconsistent state, since it protects nothing but the call itself
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&self.app)
+    }
+}
@@ -0,0 +1,191 @@
+//! Addresses of, and connections to, ABCI servers.
+
+use crate::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::str::FromStr;
+#[cfg(feature = "client")]
+use std::time::Duration;
+
+const TCP_PREFIX: &str = "tcp://";
+#[cfg(unix)]
+const UNIX_PREFIX: &str = "unix://";
+
+/// The address of an ABCI server, as in Tendermint's `proxy_app` setting:
+/// either `tcp://host:port` (or just `host:port`), or `unix:///path/to/socket`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Addr {
+    /// TCP `host:port` address
+    Tcp(String),
+
+    /// Path of a Unix domain socket
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for Addr {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[cfg(unix)]
+        {
+            if let Some(path) = s.strip_prefix(UNIX_PREFIX) {
+                if path.is_empty() {
+                    return Err(Error::InvalidAddress(s.to_string()));
+                }
+                return Ok(Addr::Unix(PathBuf::from(path)));
+            }
+        }
+        let host_port = s.strip_prefix(TCP_PREFIX).unwrap_or(s);
+        if host_port.is_empty() || host_port.contains("://") {
+            return Err(Error::InvalidAddress(s.to_string()));
+        }
+        Ok(Addr::Tcp(host_port.to_string()))
+    }
+}
+
+impl fmt::Display for Addr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Addr::Tcp(host_port) => write!(f, "{}{}", TCP_PREFIX, host_port),
+            #[cfg(unix)]
+            Addr::Unix(path) => write!(f, "{}{}", UNIX_PREFIX, path.display()),
+        }
+    }
+}
+
+/// A connection between an ABCI client and server.
+pub(crate) enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    #[cfg(feature = "client")]
+    pub(crate) fn connect(addr: &Addr) -> io::Result<Self> {
+        match addr {
+            Addr::Tcp(host_port) => TcpStream::connect(host_port).map(Stream::Tcp),
+            #[cfg(unix)]
+            Addr::Unix(path) => UnixStream::connect(path).map(Stream::Unix),
+        }
+    }
+
+    /// Set the read and write timeouts of the underlying socket
+    #[cfg(feature = "client")]
+    pub(crate) fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+            #[cfg(unix)]
+            Stream::Unix(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Listener for incoming connections to an ABCI server.
+pub(crate) enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+impl Listener {
+    pub(crate) fn bind(addr: &Addr) -> io::Result<Self> {
+        match addr {
+            Addr::Tcp(host_port) => TcpListener::bind(host_port).map(Listener::Tcp),
+            #[cfg(unix)]
+            Addr::Unix(path) => {
+                UnixListener::bind(path).map(|listener| Listener::Unix(listener, path.clone()))
+            }
+        }
+    }
+
+    /// The address this listener is bound to, e.g. with the actual port
+    /// number if it was bound to port 0.
+    pub(crate) fn local_addr(&self) -> io::Result<Addr> {
+        match self {
+            Listener::Tcp(listener) => Ok(Addr::Tcp(listener.local_addr()?.to_string())),
+            #[cfg(unix)]
+            Listener::Unix(_, path) => Ok(Addr::Unix(path.clone())),
+        }
+    }
+
+    /// Accept an incoming connection, along with a description of its peer
+    /// for logging purposes.
+    pub(crate) fn accept(&self) -> io::Result<(Stream, String)> {
+        match self {
+            Listener::Tcp(listener) => {
+                let (stream, addr) = listener.accept()?;
+                Ok((Stream::Tcp(stream), addr.to_string()))
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener, path) => {
+                let (stream, _) = listener.accept()?;
+                Ok((Stream::Unix(stream), path.display().to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_addresses() {
+        let addr: Addr = "tcp://127.0.0.1:26658".parse().unwrap();
+        assert_eq!(addr, Addr::Tcp("127.0.0.1:26658".to_string()));
+        assert_eq!(addr.to_string(), "tcp://127.0.0.1:26658");
+        assert_eq!("127.0.0.1:26658".parse::<Addr>().unwrap(), addr);
+
+        #[cfg(unix)]
+        {
+            let addr: Addr = "unix:///tmp/app.sock".parse().unwrap();
+            assert_eq!(addr, Addr::Unix(PathBuf::from("/tmp/app.sock")));
+            assert_eq!(addr.to_string(), "unix:///tmp/app.sock");
+            assert!("unix://".parse::<Addr>().is_err());
+        }
+
+        assert!("".parse::<Addr>().is_err());
+        assert!("http://127.0.0.1:26658".parse::<Addr>().is_err());
+    }
+}
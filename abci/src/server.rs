@@ -1,10 +1,15 @@
 //! ABCI application server interface.
 
 use crate::application::RequestDispatcher;
-use crate::codec::ServerCodec;
-use crate::{Application, Result};
-use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use crate::codec::{AbciMessage, ServerCodec};
+use crate::net::{self, Listener, Stream};
+use crate::Result;
+use std::marker::PhantomData;
+use std::net::{TcpListener, ToSocketAddrs};
+#[cfg(unix)]
+use std::path::Path;
 use std::thread;
+use tendermint_proto::abci::Request;
 use tracing::{error, info};
 
 /// The size of the read buffer for each incoming connection to the ABCI
@@ -31,19 +36,62 @@ impl ServerBuilder {
     /// Binds the server to the given address. You must subsequently call the
     /// [`Server::listen`] method in order for incoming connections' requests
     /// to be routed to the specified ABCI application.
-    pub fn bind<Addr, App>(self, addr: Addr, app: App) -> Result<Server<App>>
+    ///
+    /// The application may implement either the [`Application`] trait or,
+    /// with the `v0_38` feature, the [`v0_38::Application`] trait.
+    ///
+    /// [`Application`]: crate::Application
+    /// [`v0_38::Application`]: crate::v0_38::Application
+    pub fn bind<Addr, App, Req>(self, addr: Addr, app: App) -> Result<Server<App, Req>>
     where
         Addr: ToSocketAddrs,
-        App: Application,
+        App: RequestDispatcher<Req>,
     {
-        let listener = TcpListener::bind(addr)?;
-        let local_addr = listener.local_addr()?.to_string();
+        self.serve(Listener::Tcp(TcpListener::bind(addr)?), app)
+    }
+
+    /// Constructor for an ABCI server listening on a Unix domain socket at
+    /// the given path.
+    ///
+    /// See [`ServerBuilder::bind`].
+    #[cfg(unix)]
+    pub fn bind_unix<P, App, Req>(self, path: P, app: App) -> Result<Server<App, Req>>
+    where
+        P: AsRef<Path>,
+        App: RequestDispatcher<Req>,
+    {
+        let addr = net::Addr::Unix(path.as_ref().to_path_buf());
+        self.serve(Listener::bind(&addr)?, app)
+    }
+
+    /// Constructor for an ABCI server listening at the given address, which
+    /// may be a TCP or (on Unix) a Unix domain socket address.
+    ///
+    /// See [`ServerBuilder::bind`].
+    pub fn bind_addr<App, Req>(self, addr: &net::Addr, app: App) -> Result<Server<App, Req>>
+    where
+        App: RequestDispatcher<Req>,
+    {
+        self.serve(Listener::bind(addr)?, app)
+    }
+
+    fn serve<App, Req>(self, listener: Listener, app: App) -> Result<Server<App, Req>>
+    where
+        App: RequestDispatcher<Req>,
+    {
+        let local_addr = match listener.local_addr()? {
+            net::Addr::Tcp(host_port) => host_port,
+            #[cfg(unix)]
+            addr => addr.to_string(),
+        };
         info!("ABCI server running at {}", local_addr);
         Ok(Server {
             app,
             listener,
             local_addr,
             read_buf_size: self.read_buf_size,
+            next_conn_id: 0,
+            _request: PhantomData,
         })
     }
 }
@@ -56,43 +104,60 @@ impl Default for ServerBuilder {
     }
 }
 
-/// A TCP-based server for serving a specific ABCI application.
+/// A TCP- or Unix domain socket-based server for serving a specific ABCI
+/// application.
+///
+/// Tendermint opens four connections to its application (consensus, mempool,
+/// query and snapshot), over which requests are sent and responses are
+/// expected in order.
 ///
 /// Each incoming connection is handled in a separate thread. The ABCI
 /// application is cloned for access in each thread. It is up to the
 /// application developer to manage shared state across these different
 /// threads.
-pub struct Server<App> {
+pub struct Server<App, Req = Request> {
     app: App,
-    listener: TcpListener,
+    listener: Listener,
     local_addr: String,
     read_buf_size: usize,
+    next_conn_id: u64,
+    _request: PhantomData<fn(Req)>,
 }
 
-impl<App: Application> Server<App> {
+impl<App, Req> Server<App, Req>
+where
+    App: RequestDispatcher<Req>,
+    App::Response: AbciMessage,
+    Req: AbciMessage + 'static,
+{
     /// Initiate a blocking listener for incoming connections.
-    pub fn listen(self) -> Result<()> {
+    pub fn listen(mut self) -> Result<()> {
         loop {
             let (stream, addr) = self.listener.accept()?;
-            let addr = addr.to_string();
+            let conn_id = self.next_conn_id;
+            self.next_conn_id += 1;
+            // Identify connections by their ID, since Tendermint opens
+            // several of them from the same address
+            let addr = format!("{} (connection {})", addr, conn_id);
             info!("Incoming connection from: {}", addr);
             self.spawn_client_handler(stream, addr);
         }
     }
 
-    /// Getter for this server's local address.
+    /// Getter for this server's local address: `host:port` for a TCP server,
+    /// or `unix://` followed by the socket's path.
     pub fn local_addr(&self) -> String {
         self.local_addr.clone()
     }
 
-    fn spawn_client_handler(&self, stream: TcpStream, addr: String) {
+    fn spawn_client_handler(&self, stream: Stream, addr: String) {
         let app = self.app.clone();
         let read_buf_size = self.read_buf_size;
         let _ = thread::spawn(move || Self::handle_client(stream, addr, app, read_buf_size));
     }
 
-    fn handle_client(stream: TcpStream, addr: String, app: App, read_buf_size: usize) {
-        let mut codec = ServerCodec::new(stream, read_buf_size);
+    fn handle_client(stream: Stream, addr: String, app: App, read_buf_size: usize) {
+        let mut codec = ServerCodec::<_, Req, App::Response>::new(stream, read_buf_size);
         info!("Listening for incoming requests from {}", addr);
         loop {
             let request = match codec.next() {
@@ -0,0 +1,295 @@
+//! ABCI 2.0 application interface, as spoken by CometBFT v0.38.
+//!
+//! ABCI 2.0 replaces `BeginBlock`, `DeliverTx` and `EndBlock` with a single
+//! `FinalizeBlock` method, and lets applications take part in consensus with
+//! `PrepareProposal`, `ProcessProposal`, `ExtendVote` and
+//! `VerifyVoteExtension`. `SetOption` is gone.
+//!
+//! Applications implementing [`Application`] are served by the same
+//! [`Server`] as those implementing [`crate::Application`].
+//!
+//! [`Server`]: crate::Server
+
+pub mod proto;
+
+use crate::application::RequestDispatcher;
+use crate::codec::AbciMessage;
+use proto::request::Value;
+use proto::{
+    response, response_apply_snapshot_chunk, response_offer_snapshot, response_process_proposal,
+    response_verify_vote_extension, ExecTxResult, Request, RequestApplySnapshotChunk,
+    RequestCheckTx, RequestEcho, RequestExtendVote, RequestFinalizeBlock, RequestInfo,
+    RequestInitChain, RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestPrepareProposal,
+    RequestProcessProposal, RequestQuery, RequestVerifyVoteExtension, Response,
+    ResponseApplySnapshotChunk, ResponseCheckTx, ResponseCommit, ResponseEcho, ResponseException,
+    ResponseExtendVote, ResponseFinalizeBlock, ResponseFlush, ResponseInfo, ResponseInitChain,
+    ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
+    ResponsePrepareProposal, ResponseProcessProposal, ResponseQuery, ResponseVerifyVoteExtension,
+};
+
+/// An ABCI 2.0 application.
+///
+/// Applications are `Send` + `Clone` + `'static` because they are cloned for
+/// each incoming connection to the ABCI [`Server`]. It is up to the
+/// application developer to manage shared state between these clones of their
+/// application.
+///
+/// [`Server`]: crate::Server
+pub trait Application: Send + Clone + 'static {
+    /// Echo back the same message as provided in the request.
+    fn echo(&self, request: RequestEcho) -> ResponseEcho {
+        ResponseEcho {
+            message: request.message,
+        }
+    }
+
+    /// Provide information about the ABCI application.
+    fn info(&self, _request: RequestInfo) -> ResponseInfo {
+        Default::default()
+    }
+
+    /// Called once upon genesis.
+    fn init_chain(&self, _request: RequestInitChain) -> ResponseInitChain {
+        Default::default()
+    }
+
+    /// Query the application for data at the current or past height.
+    fn query(&self, _request: RequestQuery) -> ResponseQuery {
+        Default::default()
+    }
+
+    /// Check the given transaction before putting it into the local mempool.
+    fn check_tx(&self, _request: RequestCheckTx) -> ResponseCheckTx {
+        Default::default()
+    }
+
+    /// Signals that messages queued on the client should be flushed to the
+    /// server.
+    fn flush(&self) -> ResponseFlush {
+        ResponseFlush {}
+    }
+
+    /// Build the proposal of a block, when the node is its proposer.
+    ///
+    /// By default, the transactions of the mempool are proposed in order, up
+    /// to the maximum size of the block's transactions.
+    fn prepare_proposal(&self, request: RequestPrepareProposal) -> ResponsePrepareProposal {
+        let max_tx_bytes = request.max_tx_bytes;
+        let mut total_bytes = 0_i64;
+        let txs = request
+            .txs
+            .into_iter()
+            .take_while(|tx| {
+                total_bytes += tx.len() as i64;
+                total_bytes <= max_tx_bytes
+            })
+            .collect();
+        ResponsePrepareProposal { txs }
+    }
+
+    /// Accept or reject the proposal of a block, before voting for it.
+    ///
+    /// By default, all proposals are accepted.
+    fn process_proposal(&self, _request: RequestProcessProposal) -> ResponseProcessProposal {
+        ResponseProcessProposal {
+            status: response_process_proposal::ProposalStatus::Accept as i32,
+        }
+    }
+
+    /// Extend the precommit vote of the node for a block with
+    /// application-specific data.
+    ///
+    /// Only called when vote extensions are enabled. By default, the vote
+    /// extension is empty.
+    fn extend_vote(&self, _request: RequestExtendVote) -> ResponseExtendVote {
+        Default::default()
+    }
+
+    /// Verify the vote extension of another validator's precommit vote.
+    ///
+    /// By default, all vote extensions are accepted.
+    fn verify_vote_extension(
+        &self,
+        _request: RequestVerifyVoteExtension,
+    ) -> ResponseVerifyVoteExtension {
+        ResponseVerifyVoteExtension {
+            status: response_verify_vote_extension::VerifyStatus::Accept as i32,
+        }
+    }
+
+    /// Execute a decided block, and all its transactions.
+    ///
+    /// The resulting state must not be persisted until [`Application::commit`]
+    /// is called. By default, every transaction succeeds without any effect.
+    fn finalize_block(&self, request: RequestFinalizeBlock) -> ResponseFinalizeBlock {
+        ResponseFinalizeBlock {
+            tx_results: vec![ExecTxResult::default(); request.txs.len()],
+            ..Default::default()
+        }
+    }
+
+    /// Persist the application state resulting from the last finalized block.
+    fn commit(&self) -> ResponseCommit {
+        Default::default()
+    }
+
+    /// Used during state sync to discover available snapshots on peers.
+    fn list_snapshots(&self) -> ResponseListSnapshots {
+        Default::default()
+    }
+
+    /// Called when bootstrapping the node using state sync.
+    ///
+    /// By default, all snapshots are rejected, as applications must
+    /// implement all the snapshot methods to support state sync.
+    fn offer_snapshot(&self, _request: RequestOfferSnapshot) -> ResponseOfferSnapshot {
+        ResponseOfferSnapshot {
+            result: response_offer_snapshot::Result::Reject as i32,
+        }
+    }
+
+    /// Used during state sync to retrieve chunks of snapshots from peers.
+    fn load_snapshot_chunk(&self, _request: RequestLoadSnapshotChunk) -> ResponseLoadSnapshotChunk {
+        Default::default()
+    }
+
+    /// Apply the given snapshot chunk to the application's state.
+    ///
+    /// By default, state sync is aborted, since no snapshot should have been
+    /// accepted in the first place.
+    fn apply_snapshot_chunk(
+        &self,
+        _request: RequestApplySnapshotChunk,
+    ) -> ResponseApplySnapshotChunk {
+        ResponseApplySnapshotChunk {
+            result: response_apply_snapshot_chunk::Result::Abort as i32,
+            ..Default::default()
+        }
+    }
+}
+
+// CometBFT prefixes messages with their length as an unsigned varint.
+impl AbciMessage for Request {
+    const SIGNED_LENGTH: bool = false;
+}
+
+impl AbciMessage for Response {
+    const SIGNED_LENGTH: bool = false;
+}
+
+// Implement `RequestDispatcher` for all ABCI 2.0 `Application`s.
+impl<A: Application> RequestDispatcher<Request> for A {
+    type Response = Response;
+
+    fn handle(&self, request: Request) -> Response {
+        tracing::debug!("Incoming request: {:?}", request);
+        let value = match request.value {
+            Some(value) => value,
+            None => {
+                return Response {
+                    value: Some(response::Value::Exception(ResponseException {
+                        error: "empty request".to_string(),
+                    })),
+                }
+            }
+        };
+        Response {
+            value: Some(match value {
+                Value::Echo(req) => response::Value::Echo(self.echo(req)),
+                Value::Flush(_) => response::Value::Flush(self.flush()),
+                Value::Info(req) => response::Value::Info(self.info(req)),
+                Value::InitChain(req) => response::Value::InitChain(self.init_chain(req)),
+                Value::Query(req) => response::Value::Query(self.query(req)),
+                Value::CheckTx(req) => response::Value::CheckTx(self.check_tx(req)),
+                Value::Commit(_) => response::Value::Commit(self.commit()),
+                Value::ListSnapshots(_) => response::Value::ListSnapshots(self.list_snapshots()),
+                Value::OfferSnapshot(req) => {
+                    response::Value::OfferSnapshot(self.offer_snapshot(req))
+                }
+                Value::LoadSnapshotChunk(req) => {
+                    response::Value::LoadSnapshotChunk(self.load_snapshot_chunk(req))
+                }
+                Value::ApplySnapshotChunk(req) => {
+                    response::Value::ApplySnapshotChunk(self.apply_snapshot_chunk(req))
+                }
+                Value::PrepareProposal(req) => {
+                    response::Value::PrepareProposal(self.prepare_proposal(req))
+                }
+                Value::ProcessProposal(req) => {
+                    response::Value::ProcessProposal(self.process_proposal(req))
+                }
+                Value::ExtendVote(req) => response::Value::ExtendVote(self.extend_vote(req)),
+                Value::VerifyVoteExtension(req) => {
+                    response::Value::VerifyVoteExtension(self.verify_vote_extension(req))
+                }
+                Value::FinalizeBlock(req) => {
+                    response::Value::FinalizeBlock(self.finalize_block(req))
+                }
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[derive(Clone)]
+    struct NoopApp;
+
+    impl Application for NoopApp {}
+
+    #[test]
+    fn prepare_proposal_respects_max_tx_bytes() {
+        let request = Request {
+            value: Some(Value::PrepareProposal(RequestPrepareProposal {
+                max_tx_bytes: 5,
+                txs: vec![b"abc".to_vec(), b"de".to_vec(), b"f".to_vec()],
+                ..Default::default()
+            })),
+        };
+        match NoopApp.handle(request).value {
+            Some(response::Value::PrepareProposal(res)) => {
+                assert_eq!(res.txs, vec![b"abc".to_vec(), b"de".to_vec()])
+            }
+            r => panic!("unexpected response: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn finalize_block_yields_a_result_per_tx() {
+        let request = Request {
+            value: Some(Value::FinalizeBlock(RequestFinalizeBlock {
+                txs: vec![b"a".to_vec(), b"b".to_vec()],
+                ..Default::default()
+            })),
+        };
+        match NoopApp.handle(request).value {
+            Some(response::Value::FinalizeBlock(res)) => assert_eq!(res.tx_results.len(), 2),
+            r => panic!("unexpected response: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn unchanged_messages_are_wire_compatible() {
+        // Messages which didn't change since v0.34 keep their field tags, so
+        // that they decode as their v0.34 counterparts
+        let request = Request {
+            value: Some(Value::CheckTx(RequestCheckTx {
+                tx: b"tx".to_vec(),
+                r#type: 1,
+            })),
+        };
+        let mut buf = Vec::new();
+        request.encode(&mut buf).unwrap();
+        let decoded = tendermint_proto::abci::Request::decode(buf.as_slice()).unwrap();
+        match decoded.value {
+            Some(tendermint_proto::abci::request::Value::CheckTx(req)) => {
+                assert_eq!(req.tx, b"tx".to_vec());
+                assert_eq!(req.r#type, 1);
+            }
+            r => panic!("unexpected request: {:?}", r),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Protobuf messages of the ABCI 2.0 protocol, as spoken by CometBFT v0.38.
+//!
+//! These are re-exported from [`tendermint_proto::v0_38::abci`].
+
+pub use tendermint_proto::v0_38::abci::*;
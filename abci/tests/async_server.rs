@@ -0,0 +1,58 @@
+//! Integration tests for the asynchronous ABCI server.
+
+#[cfg(all(feature = "async-server", feature = "client", feature = "kvstore-app"))]
+mod async_server_integration {
+    use std::thread;
+    use tendermint_abci::{AsyncServerBuilder, ClientBuilder, KeyValueStoreApp};
+    use tendermint_proto::abci::{RequestCheckTx, RequestDeliverTx, RequestQuery};
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn happy_path_and_shutdown() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = AsyncServerBuilder::default()
+            .max_concurrent_check_tx(4)
+            .bind("127.0.0.1:0", app)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(server.listen_until(async move {
+            let _ = shutdown_rx.await;
+        }));
+
+        tokio::task::spawn_blocking(move || {
+            let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+            for i in 0..10 {
+                let res = client
+                    .check_tx(RequestCheckTx {
+                        tx: format!("key-{}=value", i).into_bytes(),
+                        r#type: 0,
+                    })
+                    .unwrap();
+                assert_eq!(res.code, 0);
+            }
+            client
+                .deliver_tx(RequestDeliverTx {
+                    tx: "test-key=test-value".as_bytes().to_owned(),
+                })
+                .unwrap();
+            client.commit().unwrap();
+            let res = client
+                .query(RequestQuery {
+                    data: "test-key".as_bytes().to_owned(),
+                    path: "".to_string(),
+                    height: 0,
+                    prove: false,
+                })
+                .unwrap();
+            assert_eq!(res.value, "test-value".as_bytes().to_owned());
+        })
+        .await
+        .unwrap();
+
+        shutdown_tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+}
@@ -2,7 +2,7 @@
 
 #[cfg(all(feature = "client", feature = "echo-app"))]
 mod echo_app_integration {
-    use tendermint_abci::{ClientBuilder, EchoApp, ServerBuilder};
+    use tendermint_abci::{Addr, ClientBuilder, EchoApp, ServerBuilder};
     use tendermint_proto::abci::RequestEcho;
 
     #[test]
@@ -21,4 +21,26 @@ mod echo_app_integration {
             .unwrap();
         assert_eq!(response.message, "Hello ABCI!");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn echo_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("abci-echo-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let addr: Addr = format!("unix://{}", path.display()).parse().unwrap();
+        let server = ServerBuilder::default()
+            .bind_addr(&addr, EchoApp::default())
+            .unwrap();
+        assert_eq!(server.local_addr(), addr.to_string());
+        let _ = std::thread::spawn(move || server.listen());
+        let mut client = ClientBuilder::default().connect_addr(&addr).unwrap();
+
+        let response = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .unwrap();
+        assert_eq!(response.message, "Hello ABCI!");
+        std::fs::remove_file(&path).unwrap();
+    }
 }
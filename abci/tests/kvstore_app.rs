@@ -2,9 +2,15 @@
 
 #[cfg(all(feature = "client", feature = "kvstore-app"))]
 mod kvstore_app_integration {
+    use std::convert::TryFrom;
     use std::thread;
-    use tendermint_abci::{ClientBuilder, KeyValueStoreApp, ServerBuilder};
-    use tendermint_proto::abci::{RequestDeliverTx, RequestEcho, RequestQuery};
+    use std::time::Duration;
+    use tendermint::merkle::proof::Proof;
+    use tendermint_abci::{ClientBuilder, KeyValueStoreApp, LocalClient, ServerBuilder};
+    use tendermint_proto::abci::{
+        request, response, RequestBeginBlock, RequestCheckTx, RequestDeliverTx, RequestEcho,
+        RequestEndBlock, RequestInfo, RequestInitChain, RequestQuery,
+    };
 
     #[test]
     fn happy_path() {
@@ -39,4 +45,161 @@ mod kvstore_app_integration {
             .unwrap();
         assert_eq!(res.value, "test-value".as_bytes().to_owned());
     }
+    #[test]
+    fn block_lifecycle() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = ServerBuilder::default().bind("127.0.0.1:0", app).unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default()
+            .timeout(Duration::from_secs(5))
+            .connect(server_addr)
+            .unwrap();
+        let info = client.info(RequestInfo::default()).unwrap();
+        assert_eq!(info.last_block_height, 0);
+
+        client.begin_block(RequestBeginBlock::default()).unwrap();
+        client
+            .deliver_tx(RequestDeliverTx {
+                tx: "test-key=test-value".as_bytes().to_owned(),
+            })
+            .unwrap();
+        client.end_block(RequestEndBlock { height: 1 }).unwrap();
+        let commit = client.commit().unwrap();
+
+        let info = client.info(RequestInfo::default()).unwrap();
+        assert_eq!(info.last_block_height, 1);
+        assert_eq!(info.last_block_app_hash, commit.data);
+
+        // Requests can also be forwarded verbatim, as by a proxy
+        let res = client
+            .perform(request::Value::Query(RequestQuery {
+                data: "test-key".as_bytes().to_owned(),
+                path: "".to_string(),
+                height: 0,
+                prove: false,
+            }))
+            .unwrap();
+        match res {
+            response::Value::Query(res) => {
+                assert_eq!(res.value, "test-value".as_bytes().to_owned());
+                assert_eq!(res.height, 1);
+            }
+            res => panic!("unexpected response: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn local_client() {
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let client = LocalClient::new(app);
+        let mempool = client.clone();
+
+        client.deliver_tx(RequestDeliverTx {
+            tx: "local-key=local-value".as_bytes().to_owned(),
+        });
+        client.commit();
+
+        let res = mempool.query(RequestQuery {
+            data: "local-key".as_bytes().to_owned(),
+            path: "".to_string(),
+            height: 0,
+            prove: false,
+        });
+        assert_eq!(res.value, "local-value".as_bytes().to_owned());
+
+        match client
+            .perform(request::Value::Info(RequestInfo::default()))
+            .unwrap()
+        {
+            response::Value::Info(res) => assert_eq!(res.last_block_height, 1),
+            res => panic!("unexpected response: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn check_tx_validation() {
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let client = LocalClient::new(app);
+
+        for tx in &[&b"key=value"[..], b"key", b"key="] {
+            let res = client.check_tx(RequestCheckTx {
+                tx: tx.to_vec(),
+                r#type: 0,
+            });
+            assert_eq!(res.code, 0, "{:?}", tx);
+        }
+        for tx in &[&b"=value"[..], b"", b"a=b=c", &[0xff, b'=', b'a']] {
+            let res = client.check_tx(RequestCheckTx {
+                tx: tx.to_vec(),
+                r#type: 0,
+            });
+            assert_ne!(res.code, 0, "{:?}", tx);
+            // Invalid transactions are rejected, rather than crashing the app,
+            // if they make it into a block anyway
+            let res = client.deliver_tx(RequestDeliverTx { tx: tx.to_vec() });
+            assert_ne!(res.code, 0, "{:?}", tx);
+        }
+    }
+
+    #[test]
+    fn query_proof() {
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let client = LocalClient::new(app);
+
+        let res = client.init_chain(RequestInitChain {
+            initial_height: 10,
+            app_state_bytes: br#"{"genesis-key":"genesis-value"}"#.to_vec(),
+            ..Default::default()
+        });
+        let genesis_app_hash = res.app_hash;
+
+        client.deliver_tx(RequestDeliverTx {
+            tx: b"a=1".to_vec(),
+        });
+        client.deliver_tx(RequestDeliverTx {
+            tx: b"b=2".to_vec(),
+        });
+        // Uncommitted state is not visible to queries
+        let res = client.query(RequestQuery {
+            data: b"a".to_vec(),
+            prove: true,
+            ..Default::default()
+        });
+        assert!(res.value.is_empty());
+        assert_eq!(res.height, 9);
+
+        let commit = client.commit();
+        assert_ne!(commit.data, genesis_app_hash);
+        let app_hash = <[u8; 32]>::try_from(commit.data.as_slice()).unwrap();
+
+        for (key, value) in &[("genesis-key", "genesis-value"), ("a", "1"), ("b", "2")] {
+            let res = client.query(RequestQuery {
+                data: key.as_bytes().to_vec(),
+                prove: true,
+                ..Default::default()
+            });
+            assert_eq!(res.value, value.as_bytes());
+            assert_eq!(res.height, 10);
+            let proof = Proof::try_from(res.proof_ops.unwrap()).unwrap();
+            proof
+                .verify_value(&app_hash, key.as_bytes(), value.as_bytes())
+                .unwrap();
+            assert!(proof
+                .verify_value(&app_hash, key.as_bytes(), b"forged")
+                .is_err());
+        }
+
+        // Proofs are only given when asked for
+        let res = client.query(RequestQuery {
+            data: b"a".to_vec(),
+            ..Default::default()
+        });
+        assert!(res.proof_ops.is_none());
+    }
 }
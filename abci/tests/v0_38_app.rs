@@ -0,0 +1,111 @@
+//! Integration tests for serving ABCI 2.0 applications.
+
+#[cfg(feature = "v0_38")]
+mod v0_38_integration {
+    use prost::Message;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use tendermint_abci::v0_38::proto::{
+        request, response, response_process_proposal::ProposalStatus,
+        response_verify_vote_extension::VerifyStatus, Request, RequestProcessProposal,
+        RequestVerifyVoteExtension, Response, ResponseProcessProposal, ResponseVerifyVoteExtension,
+    };
+    use tendermint_abci::v0_38::Application;
+    use tendermint_abci::ServerBuilder;
+
+    /// Rejects the proposals with an empty transaction, and accepts the vote
+    /// extensions which match the height.
+    #[derive(Clone)]
+    struct StrictApp;
+
+    impl Application for StrictApp {
+        fn process_proposal(&self, request: RequestProcessProposal) -> ResponseProcessProposal {
+            let status = if request.txs.iter().any(Vec::is_empty) {
+                ProposalStatus::Reject
+            } else {
+                ProposalStatus::Accept
+            };
+            ResponseProcessProposal {
+                status: status as i32,
+            }
+        }
+
+        fn verify_vote_extension(
+            &self,
+            request: RequestVerifyVoteExtension,
+        ) -> ResponseVerifyVoteExtension {
+            let status = if request.vote_extension == request.height.to_be_bytes() {
+                VerifyStatus::Accept
+            } else {
+                VerifyStatus::Reject
+            };
+            ResponseVerifyVoteExtension {
+                status: status as i32,
+            }
+        }
+    }
+
+    // Requests and responses are prefixed with their length as an unsigned
+    // varint
+    fn perform(stream: &mut TcpStream, value: request::Value) -> response::Value {
+        let mut buf = Vec::new();
+        Request { value: Some(value) }
+            .encode_length_delimited(&mut buf)
+            .unwrap();
+        stream.write_all(&buf).unwrap();
+
+        let mut len = 0_usize;
+        let mut shift = 0;
+        loop {
+            let mut byte = [0_u8];
+            stream.read_exact(&mut byte).unwrap();
+            len |= usize::from(byte[0] & 0x7F) << shift;
+            if byte[0] & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        let mut buf = vec![0_u8; len];
+        stream.read_exact(&mut buf).unwrap();
+        Response::decode(buf.as_slice()).unwrap().value.unwrap()
+    }
+
+    #[test]
+    fn consensus_methods() {
+        let server = ServerBuilder::default()
+            .bind("127.0.0.1:0", StrictApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        let _ = std::thread::spawn(move || server.listen());
+        let mut stream = TcpStream::connect(server_addr).unwrap();
+
+        let res = perform(
+            &mut stream,
+            request::Value::ProcessProposal(RequestProcessProposal {
+                txs: vec![b"tx".to_vec(), vec![]],
+                ..Default::default()
+            }),
+        );
+        match res {
+            response::Value::ProcessProposal(res) => {
+                assert_eq!(res.status, ProposalStatus::Reject as i32)
+            }
+            res => panic!("unexpected response: {:?}", res),
+        }
+
+        let res = perform(
+            &mut stream,
+            request::Value::VerifyVoteExtension(RequestVerifyVoteExtension {
+                height: 5,
+                vote_extension: 5_i64.to_be_bytes().to_vec(),
+                ..Default::default()
+            }),
+        );
+        match res {
+            response::Value::VerifyVoteExtension(res) => {
+                assert_eq!(res.status, VerifyStatus::Accept as i32)
+            }
+            res => panic!("unexpected response: {:?}", res),
+        }
+    }
+}
@@ -7,9 +7,9 @@ use crate::builder::error::{self, Error};
 use crate::components::clock::Clock;
 use crate::components::io::{AtHeight, Io};
 use crate::components::scheduler::Scheduler;
-use crate::components::verifier::Verifier;
+use crate::components::verifier::{ProdVerifier, Verifier};
 use crate::light_client::{LightClient, Options};
-use crate::operations::Hasher;
+use crate::operations::{Hasher, ProdCommitValidator, ProdHasher, VotingPowerCalculator};
 use crate::predicates::VerificationPredicates;
 use crate::state::{State, VerificationTrace};
 use crate::store::LightStore;
@@ -19,8 +19,7 @@ use crate::types::{LightBlock, PeerId, Status};
 #[cfg(feature = "rpc-client")]
 use {
     crate::components::clock::SystemClock, crate::components::io::ProdIo,
-    crate::components::scheduler, crate::components::verifier::ProdVerifier,
-    crate::operations::ProdHasher, crate::predicates::ProdPredicates, std::time::Duration,
+    crate::components::scheduler, crate::predicates::ProdPredicates, std::time::Duration,
     tendermint_rpc as rpc,
 };
 
@@ -66,11 +65,12 @@ impl<Current> LightClientBuilder<Current> {
 }
 
 impl LightClientBuilder<NoTrustedState> {
-    /// Initialize a builder for a production (non-mock) light client.
+    /// Initialize a builder for a production (non-mock) light client, which
+    /// fetches light blocks with the given RPC client.
     #[cfg(feature = "rpc-client")]
     pub fn prod(
         peer_id: PeerId,
-        rpc_client: rpc::HttpClient,
+        rpc_client: impl rpc::Client + Send + Sync + 'static,
         light_store: Box<dyn LightStore>,
         options: Options,
         timeout: Option<Duration>,
@@ -88,6 +88,36 @@ impl LightClientBuilder<NoTrustedState> {
         )
     }
 
+    /// Initialize a builder for a production (non-mock) light client, which
+    /// fetches light blocks over HTTP from the node at the given address.
+    ///
+    /// The node is queried with the RPC serialization format of the given
+    /// Tendermint/CometBFT version, or of the version it reports if none is
+    /// given, so that instances can follow chains running different releases.
+    #[cfg(feature = "rpc-client")]
+    pub fn prod_http(
+        peer_id: PeerId,
+        address: rpc::Url,
+        compat_mode: Option<rpc::CompatMode>,
+        light_store: Box<dyn LightStore>,
+        options: Options,
+        timeout: Option<Duration>,
+    ) -> Result<Self, Error> {
+        let io = ProdIo::http(peer_id, address, compat_mode, timeout).map_err(error::Kind::Io)?;
+
+        Ok(Self::custom(
+            peer_id,
+            options,
+            light_store,
+            Box::new(io),
+            Box::new(ProdHasher),
+            Box::new(SystemClock),
+            Box::new(ProdVerifier::default()),
+            Box::new(scheduler::basic_bisecting_schedule),
+            Box::new(ProdPredicates),
+        ))
+    }
+
     /// Initialize a builder for a custom light client, by providing all dependencies upfront.
     #[allow(clippy::too_many_arguments)]
     pub fn custom(
@@ -115,6 +145,28 @@ impl LightClientBuilder<NoTrustedState> {
         }
     }
 
+    /// Use the given verification predicates and voting power calculator
+    /// instead of the production ones, e.g. for chains with modified header
+    /// rules or alternative thresholds.
+    ///
+    /// The predicates validate the initial trusted state, and the verifier is
+    /// replaced by a [`ProdVerifier`] which verifies light blocks with both,
+    /// along with the production commit validator and header hasher.
+    pub fn verification_predicates(
+        mut self,
+        predicates: impl VerificationPredicates + Clone + 'static,
+        voting_power_calculator: impl VotingPowerCalculator + 'static,
+    ) -> Self {
+        self.verifier = Box::new(ProdVerifier::new(
+            predicates.clone(),
+            voting_power_calculator,
+            ProdCommitValidator::default(),
+            ProdHasher,
+        ));
+        self.predicates = Box::new(predicates);
+        self
+    }
+
     /// Set the given light block as the initial trusted state.
     fn trust_light_block(
         mut self,
@@ -215,3 +267,137 @@ impl LightClientBuilder<HasTrustedState> {
         Instance::new(light_client, state)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::scheduler;
+    use crate::operations::voting_power::VotingPowerTally;
+    use crate::predicates::{errors::VerificationError, ProdPredicates};
+    use crate::store::memory::MemoryStore;
+    use crate::tests::{MockClock, MockIo};
+    use crate::types::{SignedHeader, Time, TrustThreshold, ValidatorSet};
+    use std::convert::TryFrom;
+    use std::time::Duration;
+    use tendermint_testgen::{helpers::get_time, Generator, LightChain};
+
+    /// Predicates which never deem a header to be within the trusting period
+    #[derive(Clone)]
+    struct ExpiredPredicates;
+
+    impl VerificationPredicates for ExpiredPredicates {
+        fn is_within_trust_period(
+            &self,
+            _trusted_header: &tendermint::block::Header,
+            _trusting_period: Duration,
+            now: Time,
+        ) -> Result<(), VerificationError> {
+            Err(VerificationError::NotWithinTrustPeriod {
+                expires_at: now,
+                now,
+            })
+        }
+    }
+
+    /// Predicates which accept any header
+    #[derive(Clone)]
+    struct AcceptingPredicates;
+
+    impl VerificationPredicates for AcceptingPredicates {}
+
+    /// Voting power calculator for which nobody ever signs
+    struct NoSignersCalculator;
+
+    impl VotingPowerCalculator for NoSignersCalculator {
+        fn voting_power_in(
+            &self,
+            _signed_header: &SignedHeader,
+            validator_set: &ValidatorSet,
+            trust_threshold: TrustThreshold,
+        ) -> Result<VotingPowerTally, VerificationError> {
+            Ok(VotingPowerTally {
+                total: self.total_power_of(validator_set),
+                tallied: 0,
+                trust_threshold,
+            })
+        }
+    }
+
+    fn make_builder() -> (LightClientBuilder<NoTrustedState>, Vec<LightBlock>) {
+        let light_blocks = LightChain::default_with_length(3)
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let options = Options {
+            trust_threshold: TrustThreshold::default(),
+            trusting_period: Duration::from_secs(60 * 60),
+            clock_drift: Duration::from_secs(10),
+        };
+        let io = MockIo::new(
+            light_blocks[0].signed_header.header.chain_id.to_string(),
+            light_blocks.clone(),
+        );
+
+        let builder = LightClientBuilder::custom(
+            light_blocks[0].provider,
+            options,
+            Box::new(MemoryStore::new()),
+            Box::new(io),
+            Box::new(ProdHasher),
+            Box::new(MockClock { now: get_time(4) }),
+            Box::new(ProdVerifier::default()),
+            Box::new(scheduler::basic_bisecting_schedule),
+            Box::new(ProdPredicates),
+        );
+
+        (builder, light_blocks)
+    }
+
+    #[test]
+    fn custom_predicates_validate_trusted_state() {
+        let (builder, light_blocks) = make_builder();
+        let trusted = &light_blocks[0];
+
+        let result = builder
+            .verification_predicates(ExpiredPredicates, NoSignersCalculator)
+            .trust_primary_at(trusted.height(), trusted.signed_header.header.hash());
+
+        assert!(matches!(
+            result.err().map(|e| e.kind().clone()),
+            Some(error::Kind::InvalidLightBlock)
+        ));
+    }
+
+    #[test]
+    fn custom_voting_power_calculator_verifies_light_blocks() {
+        let (builder, light_blocks) = make_builder();
+        let trusted = &light_blocks[0];
+        let target = Height::try_from(3_u64).unwrap();
+
+        let mut instance = builder
+            .verification_predicates(AcceptingPredicates, NoSignersCalculator)
+            .trust_primary_at(trusted.height(), trusted.signed_header.header.hash())
+            .unwrap()
+            .build();
+
+        let result = instance
+            .light_client
+            .verify_to_target(target, &mut instance.state);
+
+        assert!(result.is_err());
+
+        let (builder, _) = make_builder();
+        let mut instance = builder
+            .trust_primary_at(trusted.height(), trusted.signed_header.header.hash())
+            .unwrap()
+            .build();
+
+        let result = instance
+            .light_client
+            .verify_to_target(target, &mut instance.state);
+
+        assert_eq!(result.unwrap().height(), target);
+    }
+}
@@ -82,6 +82,7 @@ pub use self::prod::ProdIo;
 mod prod {
     use super::*;
 
+    use std::sync::Arc;
     use std::time::Duration;
 
     use crate::bail;
@@ -95,14 +96,31 @@ mod prod {
 
     /// Production implementation of the Io component, which fetches
     /// light blocks from full nodes via RPC.
-    #[derive(Clone, Debug)]
-    pub struct ProdIo {
+    ///
+    /// Any RPC client can be used to reach the full node, e.g. an `HttpClient`,
+    /// a `WebSocketClient`, a `MockClient` in tests, or a client wrapping
+    /// another one, such as a `CachingClient`.
+    #[derive(Debug)]
+    pub struct ProdIo<C = rpc::HttpClient> {
         peer_id: PeerId,
-        rpc_client: rpc::HttpClient,
+        rpc_client: Arc<C>,
         timeout: Option<Duration>,
     }
 
-    impl Io for ProdIo {
+    impl<C> Clone for ProdIo<C> {
+        fn clone(&self) -> Self {
+            Self {
+                peer_id: self.peer_id,
+                rpc_client: self.rpc_client.clone(),
+                timeout: self.timeout,
+            }
+        }
+    }
+
+    impl<C> Io for ProdIo<C>
+    where
+        C: Client + Send + Sync + 'static,
+    {
         fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
             let signed_header = self.fetch_signed_header(height)?;
             let height = signed_header.header.height;
@@ -122,19 +140,16 @@ mod prod {
         }
     }
 
-    impl ProdIo {
-        /// Constructs a new ProdIo component.
-        ///
-        /// A peer map which maps peer IDS to their network address must be supplied.
-        pub fn new(
-            peer_id: PeerId,
-            rpc_client: rpc::HttpClient, /* TODO(thane): Generalize over client transport
-                                          * (instead of using HttpClient directly) */
-            timeout: Option<Duration>,
-        ) -> Self {
+    impl<C> ProdIo<C>
+    where
+        C: Client + Send + Sync + 'static,
+    {
+        /// Constructs a new ProdIo component, which fetches the light blocks
+        /// of the given peer with the given RPC client.
+        pub fn new(peer_id: PeerId, rpc_client: C, timeout: Option<Duration>) -> Self {
             Self {
                 peer_id,
-                rpc_client,
+                rpc_client: Arc::new(rpc_client),
                 timeout,
             }
         }
@@ -183,4 +198,84 @@ mod prod {
             Ok(validator_set)
         }
     }
+
+    impl ProdIo<rpc::HttpClient> {
+        /// Constructs a new ProdIo component, which fetches the light blocks
+        /// of the given peer over HTTP from the node at the given address.
+        ///
+        /// The blocks are fetched with the RPC serialization format of the
+        /// given Tendermint/CometBFT version, or of the version reported by
+        /// the node's `/status` endpoint if none is given.
+        pub fn http(
+            peer_id: PeerId,
+            address: rpc::Url,
+            compat_mode: Option<rpc::CompatMode>,
+            timeout: Option<Duration>,
+        ) -> Result<Self, IoError> {
+            let mut client = rpc::HttpClient::new(address)?;
+            let compat_mode = match compat_mode {
+                Some(compat_mode) => compat_mode,
+                None => {
+                    let client = client.clone();
+                    block_on(
+                        timeout,
+                        async move { rpc::CompatMode::detect(&client).await },
+                    )??
+                }
+            };
+            client.set_compat_mode(compat_mode);
+
+            Ok(Self::new(peer_id, client, timeout))
+        }
+
+        /// The RPC serialization format used to fetch light blocks.
+        pub fn compat_mode(&self) -> rpc::CompatMode {
+            self.rpc_client.compat_mode()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use tendermint_rpc::{Method, MockClient, MockRequestMethodMatcher};
+        use tendermint_testgen::{rpc::RpcMethod, Generator, RpcResponse};
+
+        fn response(method: RpcMethod) -> String {
+            RpcResponse::new(method)
+                .length(3)
+                .height(2)
+                .encode()
+                .unwrap()
+        }
+
+        #[test]
+        fn fetch_light_block_with_mock_client() {
+            let matcher = MockRequestMethodMatcher::default()
+                .map(Method::Commit, Ok(response(RpcMethod::Commit)))
+                .map(Method::Validators, Ok(response(RpcMethod::Validators)));
+            let (client, _driver) = MockClient::new(matcher);
+            let peer_id = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse().unwrap();
+
+            let io = ProdIo::new(peer_id, client, None);
+            let light_block = io.fetch_light_block(AtHeight::Highest).unwrap();
+
+            assert_eq!(light_block.height().value(), 2);
+            assert_eq!(light_block.provider, peer_id);
+            assert_eq!(
+                light_block.signed_header.header.validators_hash,
+                light_block.validators.hash()
+            );
+        }
+
+        #[test]
+        fn http_io_with_given_compat_mode() {
+            let peer_id = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse().unwrap();
+            let address = "http://127.0.0.1:26657".parse().unwrap();
+
+            // The node isn't queried when the version is given
+            let io = ProdIo::http(peer_id, address, Some(rpc::CompatMode::V0_37), None).unwrap();
+            assert_eq!(io.compat_mode(), rpc::CompatMode::V0_37);
+        }
+    }
 }
@@ -12,6 +12,7 @@ use crate::{
 };
 use preds::{errors::VerificationError, ProdPredicates, VerificationPredicates};
 use serde::{Deserialize, Serialize};
+use tracing::{debug, debug_span};
 
 /// Represents the result of the verification performed by the
 /// verifier component.
@@ -108,7 +109,14 @@ impl Verifier for ProdVerifier {
         options: &Options,
         now: Time,
     ) -> Verdict {
-        preds::verify(
+        let _span = debug_span!(
+            "verify",
+            untrusted_height = %untrusted.height(),
+            trusted_height = %trusted.height()
+        )
+        .entered();
+
+        let verdict = preds::verify(
             &*self.predicates,
             &*self.voting_power_calculator,
             &*self.commit_validator,
@@ -118,6 +126,10 @@ impl Verifier for ProdVerifier {
             options,
             now,
         )
-        .into()
+        .into();
+
+        debug!(?verdict, "verification outcome");
+
+        verdict
     }
 }
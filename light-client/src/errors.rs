@@ -28,6 +28,15 @@ pub enum ErrorKind {
     #[error("store error")]
     Store,
 
+    /// The schema of the store is incompatible with this version of the library
+    #[error("incompatible store schema version {found}, expected version {expected}")]
+    IncompatibleSchema {
+        /// Schema version of the store
+        found: u64,
+        /// Schema version expected by this version of the library
+        expected: u64,
+    },
+
     /// No primary
     #[error("no primary")]
     NoPrimary,
@@ -52,6 +61,10 @@ pub enum ErrorKind {
     #[error("no trusted state")]
     NoTrustedState(Status),
 
+    /// No verified light block at the given height
+    #[error("no verified light block at height {0}")]
+    NotVerified(Height),
+
     /// Target height for the light client lower than latest trusted state height
     #[error("target height ({target_height}) is lower than trusted state ({trusted_height})")]
     TargetLowerThanTrustedState {
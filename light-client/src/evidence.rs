@@ -1,8 +1,13 @@
 //! Fork evidence data structures and interfaces.
 
-use crate::{components::io::IoError, types::PeerId};
+use crate::{
+    components::io::IoError,
+    types::{LightBlock, PeerId},
+};
 
-use tendermint::abci::transaction::Hash;
+use tendermint::block::CommitSig;
+use tendermint::evidence::{ConflictingBlock, LightClientAttackEvidence};
+use tendermint::Hash;
 
 use contracts::contract_trait;
 
@@ -16,6 +21,37 @@ pub trait EvidenceReporter: Send + Sync {
     fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError>;
 }
 
+/// Build evidence of a light client attack, given the conflicting block and
+/// the last block on which the conflicting peers agreed.
+///
+/// The validators of the conflicting block which signed its commit are
+/// considered byzantine.
+pub fn light_client_attack_evidence(conflicting: &LightBlock, common: &LightBlock) -> Evidence {
+    let byzantine_validators = conflicting
+        .signed_header
+        .commit
+        .signatures
+        .iter()
+        .filter_map(|sig| match sig {
+            CommitSig::BlockIdFlagCommit {
+                validator_address, ..
+            } => conflicting.validators.validator(*validator_address),
+            _ => None,
+        })
+        .collect();
+
+    Evidence::LightClientAttack(Box::new(LightClientAttackEvidence {
+        conflicting_block: ConflictingBlock {
+            signed_header: conflicting.signed_header.clone(),
+            validator_set: conflicting.validators.clone(),
+        },
+        common_height: common.height(),
+        byzantine_validators,
+        total_voting_power: common.validators.total_voting_power(),
+        timestamp: common.signed_header.header.time,
+    }))
+}
+
 #[cfg(feature = "rpc-client")]
 pub use self::prod::ProdEvidenceReporter;
 
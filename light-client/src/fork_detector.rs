@@ -1,6 +1,7 @@
 //! Fork detection data structures and implementation.
 
 use serde::{Deserialize, Serialize};
+use tracing::{debug, info_span, warn};
 
 use crate::{
     errors::{Error, ErrorExt, ErrorKind},
@@ -85,6 +86,14 @@ impl ForkDetector for ProdForkDetector {
         trusted_block: &LightBlock,
         witnesses: Vec<&Instance>,
     ) -> Result<ForkDetection, Error> {
+        let _span = info_span!(
+            "detect_forks",
+            height = %verified_block.height(),
+            trusted_height = %trusted_block.height(),
+            witnesses = witnesses.len()
+        )
+        .entered();
+
         let primary_hash = self
             .hasher
             .hash_header(&verified_block.signed_header.header);
@@ -102,9 +111,17 @@ impl ForkDetector for ProdForkDetector {
 
             if primary_hash == witness_hash {
                 // Hashes match, continue with next witness, if any.
+                debug!(witness = %witness.light_client.peer, "witness agrees with primary");
                 continue;
             }
 
+            warn!(
+                witness = %witness.light_client.peer,
+                %primary_hash,
+                %witness_hash,
+                "witness diverges from primary, verifying its light block"
+            );
+
             state
                 .light_store
                 .insert(trusted_block.clone(), Status::Verified);
@@ -118,24 +135,33 @@ impl ForkDetector for ProdForkDetector {
                 .verify_to_target(verified_block.height(), &mut state);
 
             match result {
-                Ok(_) => forks.push(Fork::Forked {
-                    primary: verified_block.clone(),
-                    witness: witness_block,
-                }),
+                Ok(_) => {
+                    warn!(witness = %witness.light_client.peer, "fork detected");
+                    forks.push(Fork::Forked {
+                        primary: verified_block.clone(),
+                        witness: witness_block,
+                    })
+                }
                 Err(e) if e.kind().has_expired() => {
+                    warn!(witness = %witness.light_client.peer, error = %e, "fork detected");
                     forks.push(Fork::Forked {
                         primary: verified_block.clone(),
                         witness: witness_block,
                     });
                 }
                 Err(e) if e.kind().is_timeout() => {
+                    warn!(witness = %witness.light_client.peer, error = %e, "witness timed out");
                     forks.push(Fork::Timeout(witness_block.provider, e.kind().clone()))
                 }
-                Err(e) => forks.push(Fork::Faulty(witness_block, e.kind().clone())),
+                Err(e) => {
+                    warn!(witness = %witness.light_client.peer, error = %e, "witness is faulty");
+                    forks.push(Fork::Faulty(witness_block, e.kind().clone()))
+                }
             }
         }
 
         if forks.is_empty() {
+            debug!("no fork detected");
             Ok(ForkDetection::NotDetected)
         } else {
             Ok(ForkDetection::Detected(forks))
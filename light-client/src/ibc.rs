@@ -0,0 +1,259 @@
+//! Conversion of the forks detected by the light client into the
+//! misbehaviour of IBC's Tendermint light client
+//! (`ibc.lightclients.tendermint.v1`), so that relayers can submit light
+//! client attacks to the counterparty chains tracking the attacked chain.
+//!
+//! The IBC messages are defined here as they are on the wire, with the
+//! Tendermint structures they embed taken from `tendermint-proto`.
+
+use prost::Message;
+use prost_types::Any;
+use thiserror::Error;
+
+use tendermint::block::Height;
+use tendermint::chain;
+use tendermint_proto::types::{SignedHeader as RawSignedHeader, ValidatorSet as RawValidatorSet};
+
+use crate::types::LightBlock;
+
+/// Type URL of [`RawMisbehaviour`]
+pub const MISBEHAVIOUR_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.Misbehaviour";
+
+/// Type URL of [`RawHeader`]
+pub const HEADER_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.Header";
+
+/// Height of a chain, as seen by IBC (`ibc.core.client.v1.Height`)
+#[derive(Clone, PartialEq, Message)]
+pub struct RawHeight {
+    /// Revision of the chain, incremented when its height is reset
+    #[prost(uint64, tag = "1")]
+    pub revision_number: u64,
+
+    /// Height of the chain within its revision
+    #[prost(uint64, tag = "2")]
+    pub revision_height: u64,
+}
+
+/// Header submitted to an IBC Tendermint client
+/// (`ibc.lightclients.tendermint.v1.Header`)
+#[derive(Clone, PartialEq, Message)]
+pub struct RawHeader {
+    /// The header and the commit for it
+    #[prost(message, optional, tag = "1")]
+    pub signed_header: Option<RawSignedHeader>,
+
+    /// The validators which signed the commit
+    #[prost(message, optional, tag = "2")]
+    pub validator_set: Option<RawValidatorSet>,
+
+    /// Height of the consensus state of the client from which the header
+    /// is verified
+    #[prost(message, optional, tag = "3")]
+    pub trusted_height: Option<RawHeight>,
+
+    /// The validators of the block following the one at the trusted height
+    #[prost(message, optional, tag = "4")]
+    pub trusted_validators: Option<RawValidatorSet>,
+}
+
+/// Misbehaviour submitted to an IBC Tendermint client
+/// (`ibc.lightclients.tendermint.v1.Misbehaviour`)
+#[derive(Clone, PartialEq, Message)]
+pub struct RawMisbehaviour {
+    /// ID of the client
+    #[prost(string, tag = "1")]
+    pub client_id: String,
+
+    /// First of the conflicting headers
+    #[prost(message, optional, tag = "2")]
+    pub header_1: Option<RawHeader>,
+
+    /// Second of the conflicting headers
+    #[prost(message, optional, tag = "3")]
+    pub header_2: Option<RawHeader>,
+}
+
+impl RawHeader {
+    /// Wrap the header in an `Any`, as expected by the `MsgUpdateClient`
+    /// message of IBC.
+    pub fn to_any(&self) -> Any {
+        to_any(HEADER_TYPE_URL, self)
+    }
+}
+
+impl RawMisbehaviour {
+    /// Wrap the misbehaviour in an `Any`, as expected by the
+    /// `MsgSubmitMisbehaviour` message of IBC.
+    pub fn to_any(&self) -> Any {
+        to_any(MISBEHAVIOUR_TYPE_URL, self)
+    }
+}
+
+fn to_any(type_url: &str, message: &impl Message) -> Any {
+    let mut value = Vec::with_capacity(message.encoded_len());
+    message
+        .encode(&mut value)
+        .expect("buffer grows to fit the message");
+    Any {
+        type_url: type_url.to_string(),
+        value,
+    }
+}
+
+/// Errors raised when converting a fork into misbehaviour
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum MisbehaviourError {
+    /// The conflicting blocks are at different heights
+    #[error("conflicting blocks at different heights: {0} and {1}")]
+    HeightMismatch(Height, Height),
+
+    /// The conflicting blocks are the same
+    #[error("blocks at height {0} do not conflict")]
+    NotConflicting(Height),
+
+    /// The trusted block isn't below the conflicting blocks
+    #[error(
+        "trusted block at height {trusted} is not below the conflicting blocks at height {height}"
+    )]
+    UntrustedHeight {
+        /// Height of the trusted block
+        trusted: Height,
+        /// Height of the conflicting blocks
+        height: Height,
+    },
+}
+
+/// Convert the conflicting blocks of a fork, e.g. the primary and witness
+/// blocks of a [`Fork::Forked`], into misbehaviour of the IBC client with the
+/// given ID.
+///
+/// The conflicting blocks are verified by the client from `trusted`, which
+/// must be a block at the height of one of its consensus states.
+///
+/// [`Fork::Forked`]: crate::fork_detector::Fork::Forked
+pub fn misbehaviour(
+    client_id: impl Into<String>,
+    trusted: &LightBlock,
+    block_1: &LightBlock,
+    block_2: &LightBlock,
+) -> Result<RawMisbehaviour, MisbehaviourError> {
+    let height = block_1.height();
+    if block_2.height() != height {
+        return Err(MisbehaviourError::HeightMismatch(height, block_2.height()));
+    }
+    if block_1.signed_header.header.hash() == block_2.signed_header.header.hash() {
+        return Err(MisbehaviourError::NotConflicting(height));
+    }
+    if trusted.height() >= height {
+        return Err(MisbehaviourError::UntrustedHeight {
+            trusted: trusted.height(),
+            height,
+        });
+    }
+
+    Ok(RawMisbehaviour {
+        client_id: client_id.into(),
+        header_1: Some(header(trusted, block_1)),
+        header_2: Some(header(trusted, block_2)),
+    })
+}
+
+/// Convert a block into a header to submit to an IBC client, to be verified
+/// from the given trusted block.
+pub fn header(trusted: &LightBlock, block: &LightBlock) -> RawHeader {
+    RawHeader {
+        signed_header: Some(block.signed_header.clone().into()),
+        validator_set: Some(block.validators.clone().into()),
+        trusted_height: Some(height(trusted)),
+        trusted_validators: Some(trusted.next_validators.clone().into()),
+    }
+}
+
+/// The height of a block, as seen by IBC.
+pub fn height(block: &LightBlock) -> RawHeight {
+    RawHeight {
+        revision_number: revision_number(&block.signed_header.header.chain_id),
+        revision_height: block.height().value(),
+    }
+}
+
+/// The revision of a chain, which IBC takes from the suffix of its ID, as
+/// in `cosmoshub-4`, or zero if it has no such suffix.
+pub fn revision_number(chain_id: &chain::Id) -> u64 {
+    let chain_id = chain_id.as_str();
+    let (name, revision) = match chain_id.rfind('-') {
+        Some(i) => (&chain_id[..i], &chain_id[i + 1..]),
+        None => return 0,
+    };
+    if name.is_empty() || name.ends_with('-') || revision.starts_with('0') {
+        return 0;
+    }
+    revision.parse().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tendermint::Hash;
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+
+    fn light_block(height: u64) -> LightBlock {
+        TestgenLightBlock::new_default(height)
+            .generate()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn converts_forks_into_misbehaviour() {
+        let trusted = light_block(1);
+        let primary = light_block(3);
+        let mut witness = light_block(3);
+        witness.signed_header.header.last_results_hash = Some(Hash::Sha256([1; 32]));
+
+        let misbehaviour = misbehaviour("07-tendermint-0", &trusted, &primary, &witness).unwrap();
+        let header_2 = misbehaviour.header_2.as_ref().unwrap();
+        assert_eq!(
+            header_2.trusted_height,
+            Some(RawHeight {
+                revision_number: 0,
+                revision_height: 1
+            })
+        );
+        assert_eq!(
+            header_2.signed_header,
+            Some(witness.signed_header.clone().into())
+        );
+
+        assert_eq!(header_2.to_any().type_url, HEADER_TYPE_URL);
+        let any = misbehaviour.to_any();
+        assert_eq!(any.type_url, MISBEHAVIOUR_TYPE_URL);
+        assert_eq!(
+            RawMisbehaviour::decode(any.value.as_slice()).unwrap(),
+            misbehaviour
+        );
+
+        assert_eq!(
+            super::misbehaviour("07-tendermint-0", &trusted, &primary, &primary),
+            Err(MisbehaviourError::NotConflicting(primary.height()))
+        );
+        assert!(super::misbehaviour("07-tendermint-0", &primary, &primary, &witness).is_err());
+        assert!(super::misbehaviour("07-tendermint-0", &trusted, &trusted, &witness).is_err());
+    }
+
+    #[test]
+    fn revision_numbers() {
+        for (chain_id, revision) in &[
+            ("cosmoshub-4", 4),
+            ("test-chain", 0),
+            ("osmosis-1-12", 12),
+            ("chain-01", 0),
+            ("chain--1", 0),
+            ("-1", 0),
+            ("chain", 0),
+        ] {
+            assert_eq!(revision_number(&chain_id.parse().unwrap()), *revision);
+        }
+    }
+}
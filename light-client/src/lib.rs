@@ -23,6 +23,7 @@ pub mod contracts;
 pub mod errors;
 pub mod evidence;
 pub mod fork_detector;
+pub mod ibc;
 pub mod light_client;
 pub mod operations;
 pub mod peer_list;
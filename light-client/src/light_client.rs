@@ -7,6 +7,7 @@ use std::{fmt, time::Duration};
 use contracts::*;
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
+use tracing::{debug, debug_span, info_span, warn};
 
 use crate::{
     bail,
@@ -169,9 +170,12 @@ impl LightClient {
         target_height: Height,
         state: &mut State,
     ) -> Result<LightBlock, Error> {
+        let _span = info_span!("verify_to_target", peer = %self.peer, %target_height).entered();
+
         // Let's first look in the store to see whether
         // we have already successfully verified this block.
         if let Some(light_block) = state.light_store.get_trusted_or_verified(target_height) {
+            debug!("light block already verified");
             return Ok(light_block);
         }
 
@@ -222,12 +226,20 @@ impl LightClient {
                 });
             }
 
+            let _span = debug_span!(
+                "bisection_step",
+                %current_height,
+                trusted_height = %trusted_state.height()
+            )
+            .entered();
+
             // Log the current height as a dependency of the block at the target height
             state.trace_block(target_height, current_height);
 
             // If the trusted state is now at a height equal to the target height, we are done.
             // [LCV-DIST-LIFE.1]
             if target_height == trusted_state.height() {
+                debug!("reached target height");
                 return Ok(trusted_state);
             }
 
@@ -242,19 +254,25 @@ impl LightClient {
 
             match verdict {
                 Verdict::Success => {
+                    debug!("light block verified");
+
                     // Verification succeeded, add the block to the light store with
                     // the `Verified` status or higher if already trusted.
                     let new_status = Status::most_trusted(Status::Verified, status);
                     state.light_store.update(&current_block, new_status);
                 }
                 Verdict::Invalid(e) => {
+                    warn!(error = %e, "light block is invalid");
+
                     // Verification failed, add the block to the light store with `Failed` status,
                     // and abort.
                     state.light_store.update(&current_block, Status::Failed);
 
                     bail!(ErrorKind::InvalidLightBlock(e))
                 }
-                Verdict::NotEnoughTrust(_) => {
+                Verdict::NotEnoughTrust(e) => {
+                    debug!(reason = %e, "not enough trust in light block, bisecting");
+
                     // The current block cannot be trusted because of a missing overlap in the
                     // validator sets. Add the block to the light store with
                     // the `Unverified` status. This will engage bisection in an
@@ -268,6 +286,8 @@ impl LightClient {
             current_height =
                 self.scheduler
                     .schedule(state.light_store.as_ref(), current_height, target_height);
+
+            debug!(next_height = %current_height, "scheduled next height");
         }
     }
 
@@ -350,6 +370,12 @@ impl LightClient {
             let current_hash = self.hasher.hash_header(&current.signed_header.header);
 
             if current_hash != latest_last_block_id.hash {
+                warn!(
+                    %height,
+                    hash = %current_hash,
+                    last_block_id_hash = %latest_last_block_id.hash,
+                    "adjacent headers are not linked"
+                );
                 bail!(ErrorKind::InvalidAdjacentHeaders {
                     h1: current_hash,
                     h2: latest_last_block_id.hash
@@ -13,6 +13,7 @@ use std::fmt;
 use std::convert::TryFrom;
 use tendermint::block::CommitSig;
 use tendermint::trust_threshold::TrustThreshold as _;
+use tendermint::validator;
 use tendermint::vote::{SignedVote, ValidatorIndex, Vote};
 
 /// Tally for the voting power computed by the `VotingPowerCalculator`
@@ -124,6 +125,7 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
             .map(|vote| (signature, vote))
         });
 
+        let mut votes = Vec::with_capacity(signatures.len());
         for (signature, vote) in non_absent_votes {
             // Ensure we only count a validator's power once
             if seen_validators.contains(&vote.validator_address) {
@@ -145,20 +147,35 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
                 vote.validator_address,
                 vote.signature,
             );
+            votes.push((
+                signature,
+                validator,
+                signed_vote.sign_bytes(),
+                vote.signature,
+            ));
+        }
 
-            // Check vote is valid
-            let sign_bytes = signed_vote.sign_bytes();
-            if validator
-                .verify_signature(&sign_bytes, signed_vote.signature())
-                .is_err()
-            {
-                bail!(VerificationError::InvalidSignature {
-                    signature: signed_vote.signature().to_bytes(),
-                    validator: Box::new(validator),
-                    sign_bytes,
-                });
+        // Check the votes are valid, all at once so that their signatures can be
+        // verified as a batch, and then one by one to find the invalid one if any
+        let batch: Vec<_> = votes
+            .iter()
+            .map(|(_, validator, sign_bytes, signature)| {
+                (validator, sign_bytes.as_slice(), signature)
+            })
+            .collect();
+        if validator::verify_signatures(&batch).is_err() {
+            for (validator, sign_bytes, signature) in batch {
+                if validator.verify_signature(sign_bytes, signature).is_err() {
+                    bail!(VerificationError::InvalidSignature {
+                        signature: signature.to_bytes(),
+                        validator: Box::new(*validator),
+                        sign_bytes: sign_bytes.to_vec(),
+                    });
+                }
             }
+        }
 
+        for (signature, validator, _, _) in &votes {
             // If the vote is neither absent nor nil, tally its power
             if signature.is_commit() {
                 tallied_voting_power += validator.power();
@@ -214,6 +231,8 @@ fn non_absent_vote(
         validator_address,
         validator_index,
         signature: *signature,
+        extension: vec![],
+        extension_signature: None,
     })
 }
 
@@ -7,15 +7,18 @@
 
 use std::fmt::Debug;
 
-use crate::types::{Height, LightBlock, Status};
+use crate::types::{Hash, Height, LightBlock, Status};
 use crate::utils::std_ext;
 
+mod integrity;
 pub mod memory;
 
 #[cfg(feature = "lightstore-sled")]
 #[cfg_attr(docsrs, doc(cfg(feature = "lightstore-sled")))]
 pub mod sled;
 
+pub use integrity::{IntegrityIssue, IntegrityReport};
+
 /// Store for light blocks.
 ///
 /// The light store records light blocks received from peers, and their verification status.
@@ -89,4 +92,34 @@ pub trait LightStore: Debug + Send + Sync {
         self.get(height, Status::Trusted)
             .or_else(|| self.get(height, Status::Verified))
     }
+
+    /// Get the hash of the header of the light block at the given height with the
+    /// trusted or verified status.
+    ///
+    /// Stores should maintain an index of these hashes, updated as light blocks are
+    /// inserted and removed, so that checking whether a height was already verified
+    /// doesn't require reading back the light block. The default implementation
+    /// reads the light block and hashes its header.
+    fn verified_hash(&self, height: Height) -> Option<Hash> {
+        self.get_trusted_or_verified(height)
+            .map(|lb| lb.signed_header.header.hash())
+    }
+
+    /// Whether the light block at the given height, whose header has the given hash,
+    /// has the trusted or verified status.
+    fn is_verified(&self, height: Height, hash: Hash) -> bool {
+        self.verified_hash(height) == Some(hash)
+    }
+
+    /// Check that the light blocks in the store are consistent with each other.
+    ///
+    /// This re-hashes the headers of the trusted and verified light blocks and
+    /// compares them with the hashes indexed by the store and with their commits,
+    /// checks that consecutive trusted or verified headers form a hash chain, and
+    /// flags heights with conflicting statuses, e.g. a light block which both
+    /// failed and passed verification. This reads back the whole store, and is
+    /// meant to be run on startup rather than in the course of verification.
+    fn verify_integrity(&self) -> IntegrityReport {
+        integrity::verify_integrity(self)
+    }
 }
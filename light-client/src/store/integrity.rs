@@ -0,0 +1,240 @@
+//! Self-check of the integrity of a light store, see [`LightStore::verify_integrity`].
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::store::LightStore;
+use crate::types::{Hash, Height, LightBlock, Status};
+
+/// Inconsistency found in a light store by [`LightStore::verify_integrity`]
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum IntegrityIssue {
+    /// The hash of a trusted or verified header doesn't match the hash the
+    /// store indexed for its height
+    #[error(
+        "hash of the verified header at height {height} is {computed}, but {indexed:?} is indexed"
+    )]
+    HashMismatch {
+        /// Height of the light block
+        height: Height,
+        /// Hash indexed by the store, if any
+        indexed: Option<Hash>,
+        /// Hash of the stored header
+        computed: Hash,
+    },
+
+    /// The commit of a light block isn't for its header
+    #[error("commit of the {status:?} light block at height {height} is for another header")]
+    CommitMismatch {
+        /// Height of the light block
+        height: Height,
+        /// Status of the light block
+        status: Status,
+    },
+
+    /// The header of a trusted or verified light block doesn't point to the
+    /// trusted or verified light block right below it
+    #[error("verified header at height {height} does not follow the verified header below it")]
+    BrokenChain {
+        /// Height of the upper light block
+        height: Height,
+    },
+
+    /// Light blocks at the same height have statuses which can't both hold,
+    /// e.g. one failed verification while the other passed it, or both
+    /// passed it but have different headers
+    #[error("conflicting light blocks at height {height} with statuses {statuses:?}")]
+    ConflictingStatus {
+        /// Height of the light blocks
+        height: Height,
+        /// Statuses of the conflicting light blocks
+        statuses: Vec<Status>,
+    },
+}
+
+/// Outcome of [`LightStore::verify_integrity`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntegrityReport {
+    /// Number of light blocks checked
+    pub checked: usize,
+    /// Inconsistencies found, by increasing height
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// Whether the store was found consistent
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+pub(super) fn verify_integrity<S: LightStore + ?Sized>(store: &S) -> IntegrityReport {
+    let mut heights: BTreeMap<Height, Vec<(Status, LightBlock)>> = BTreeMap::new();
+    for status in Status::iter() {
+        for light_block in store.all(*status) {
+            heights
+                .entry(light_block.height())
+                .or_default()
+                .push((*status, light_block));
+        }
+    }
+
+    let mut report = IntegrityReport::default();
+    // Hash of the verified header right below the current height, if any
+    let mut previous: Option<(Height, Hash)> = None;
+
+    for (height, entries) in heights {
+        report.checked += entries.len();
+
+        let mut hashes = Vec::with_capacity(entries.len());
+        for (status, light_block) in &entries {
+            let hash = light_block.signed_header.header.hash();
+            if light_block.signed_header.commit.block_id.hash != hash {
+                report.issues.push(IntegrityIssue::CommitMismatch {
+                    height,
+                    status: *status,
+                });
+            }
+            hashes.push((*status, hash, light_block));
+        }
+
+        let mut statuses: Vec<Status> = entries.iter().map(|(status, _)| *status).collect();
+        statuses.sort_unstable_by(|a, b| b.cmp(a));
+
+        let verified: Vec<_> = hashes
+            .iter()
+            .filter(|(status, _, _)| status.is_verified())
+            .collect();
+        let failed = statuses.contains(&Status::Failed);
+        let diverging = verified.windows(2).any(|pair| pair[0].1 != pair[1].1);
+        if diverging || (failed && !verified.is_empty()) {
+            report
+                .issues
+                .push(IntegrityIssue::ConflictingStatus { height, statuses });
+        }
+
+        // The trusted light block, if any, is the one the store serves as verified
+        let (hash, light_block) = match verified.iter().max_by_key(|(status, _, _)| *status) {
+            Some((_, hash, light_block)) => (*hash, *light_block),
+            None => {
+                previous = None;
+                continue;
+            }
+        };
+
+        let indexed = store.verified_hash(height);
+        if indexed != Some(hash) {
+            report.issues.push(IntegrityIssue::HashMismatch {
+                height,
+                indexed,
+                computed: hash,
+            });
+        }
+
+        if let Some((previous_height, previous_hash)) = previous {
+            let last_block_hash = light_block
+                .signed_header
+                .header
+                .last_block_id
+                .map(|id| id.hash);
+            if previous_height.increment() == height && last_block_hash != Some(previous_hash) {
+                report.issues.push(IntegrityIssue::BrokenChain { height });
+            }
+        }
+        previous = Some((height, hash));
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::store::memory::MemoryStore;
+    use tendermint_testgen::{Generator, LightChain};
+
+    fn blocks(height: u64) -> Vec<LightBlock> {
+        LightChain::default_with_length(height)
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect()
+    }
+
+    fn store(blocks: &[LightBlock], status: Status) -> MemoryStore {
+        let mut store = MemoryStore::new();
+        for block in blocks {
+            store.insert(block.clone(), status);
+        }
+        store
+    }
+
+    #[test]
+    fn consistent_store_is_clean() {
+        let blocks = blocks(5);
+        let mut store = store(&blocks[..4], Status::Verified);
+        store.update(&blocks[0], Status::Trusted);
+        store.insert(blocks[4].clone(), Status::Failed);
+
+        let report = store.verify_integrity();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!(report.checked, 5);
+    }
+
+    #[test]
+    fn detects_tampered_headers() {
+        let mut blocks = blocks(4);
+        blocks[2].signed_header.header.last_results_hash = Some(Hash::Sha256([1; 32]));
+        let store = store(&blocks, Status::Verified);
+
+        let issues = store.verify_integrity().issues;
+        assert_eq!(
+            issues,
+            vec![
+                IntegrityIssue::CommitMismatch {
+                    height: blocks[2].height(),
+                    status: Status::Verified,
+                },
+                IntegrityIssue::BrokenChain {
+                    height: blocks[3].height(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skipped_heights_do_not_break_the_chain() {
+        let blocks = blocks(5);
+        let mut store = store(&blocks, Status::Verified);
+        store.remove(blocks[2].height(), Status::Verified);
+        store.insert(blocks[2].clone(), Status::Unverified);
+
+        assert!(store.verify_integrity().is_clean());
+    }
+
+    #[cfg(feature = "lightstore-sled")]
+    #[test]
+    fn detects_conflicting_statuses() {
+        use crate::store::sled::SledStore;
+        use tempdir::TempDir;
+
+        let blocks = blocks(3);
+        let tmp_dir = TempDir::new("tendermint_light_client_integrity_test").unwrap();
+        let mut store = SledStore::open(tmp_dir).unwrap();
+        for block in &blocks {
+            store.insert(block.clone(), Status::Trusted);
+        }
+        store.insert(blocks[1].clone(), Status::Failed);
+
+        let issues = store.verify_integrity().issues;
+        assert_eq!(
+            issues,
+            vec![IntegrityIssue::ConflictingStatus {
+                height: blocks[1].height(),
+                statuses: vec![Status::Trusted, Status::Failed],
+            }]
+        );
+    }
+}
@@ -2,11 +2,11 @@
 
 use crate::{
     store::{LightStore, Status},
-    types::{Height, LightBlock},
+    types::{Hash, Height, LightBlock},
 };
 
 use std::collections::btree_map::Entry::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Internal entry for the memory store
 #[derive(Clone, Debug, PartialEq)]
@@ -28,6 +28,8 @@ impl StoreEntry {
 #[derive(Debug, Clone, Default)]
 pub struct MemoryStore {
     store: BTreeMap<Height, StoreEntry>,
+    /// Hashes of the headers of the trusted or verified light blocks
+    verified: HashMap<Height, Hash>,
 }
 
 impl MemoryStore {
@@ -35,6 +37,7 @@ impl MemoryStore {
     pub fn new() -> Self {
         Self {
             store: BTreeMap::new(),
+            verified: HashMap::new(),
         }
     }
 }
@@ -49,14 +52,24 @@ impl LightStore for MemoryStore {
     }
 
     fn insert(&mut self, light_block: LightBlock, status: Status) {
+        let height = light_block.height();
+
+        if status.is_verified() {
+            let hash = light_block.signed_header.header.hash();
+            self.verified.insert(height, hash);
+        } else {
+            self.verified.remove(&height);
+        }
+
         self.store
-            .insert(light_block.height(), StoreEntry::new(light_block, status));
+            .insert(height, StoreEntry::new(light_block, status));
     }
 
     fn remove(&mut self, height: Height, status: Status) {
         if let Occupied(e) = self.store.entry(height) {
             if e.get().status == status {
                 e.remove_entry();
+                self.verified.remove(&height);
             }
         }
     }
@@ -91,4 +104,8 @@ impl LightStore for MemoryStore {
 
         Box::new(light_blocks.into_iter())
     }
+
+    fn verified_hash(&self, height: Height) -> Option<Hash> {
+        self.verified.get(&height).copied()
+    }
 }
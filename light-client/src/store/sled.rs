@@ -2,11 +2,14 @@
 
 pub mod utils;
 
+use std::convert::TryInto;
 use std::path::Path;
 
 use crate::{
+    bail,
+    errors::{Error, ErrorKind},
     store::sled::utils::HeightIndexedDb,
-    types::{Height, LightBlock},
+    types::{Hash, Height, LightBlock},
 };
 
 use super::{LightStore, Status};
@@ -16,6 +19,83 @@ const VERIFIED: &str = "verified";
 const TRUSTED: &str = "trusted";
 const FAILED: &str = "failed";
 
+/// Tree indexing the hashes of the trusted or verified light blocks by height
+const VERIFIED_INDEX: &str = "verified_index";
+
+/// Tree holding the entries set aside by [`SledStore::repair`]
+const QUARANTINE: &str = "quarantine";
+
+/// Key of the schema version, in the default tree of the database
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Version of the schema of the databases written by this version of the store.
+///
+/// Databases written before the schema was versioned have version 0.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// A migration of a database from a schema version to the next one.
+#[derive(Clone, Copy)]
+pub struct Migration {
+    /// Schema version of the databases the migration applies to
+    pub from_version: u64,
+    /// Upgrade the database, e.g. by re-encoding the light blocks it contains
+    pub migrate: fn(&sled::Db) -> Result<(), Error>,
+}
+
+impl std::fmt::Debug for Migration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Migration")
+            .field("from_version", &self.from_version)
+            .finish()
+    }
+}
+
+/// Migrations of the databases written by the previous versions of the store.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from_version: 0,
+        // The encoding of the light blocks didn't change when the schema was versioned
+        migrate: |_| Ok(()),
+    },
+    Migration {
+        from_version: 1,
+        migrate: SledStore::rebuild_verified_index,
+    },
+];
+
+/// When the writes to the store are made durable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Durability {
+    /// Writes are flushed to disk in the background by sled, every few
+    /// milliseconds. The latest writes may be lost on power loss.
+    Buffered,
+    /// Every write is flushed to disk before returning.
+    Sync,
+}
+
+impl Default for Durability {
+    fn default() -> Self {
+        Self::Buffered
+    }
+}
+
+/// Outcome of [`SledStore::repair`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Number of entries which could not be read back and were moved to the
+    /// quarantine
+    pub quarantined: usize,
+    /// Number of entries which could not be read back and were removed
+    pub removed: usize,
+}
+
+impl RepairReport {
+    /// Whether the store was found intact
+    pub fn is_clean(&self) -> bool {
+        self.quarantined == 0 && self.removed == 0
+    }
+}
+
 /// Persistent store backed by an on-disk `sled` database.
 #[derive(Debug, Clone)]
 pub struct SledStore {
@@ -23,24 +103,231 @@ pub struct SledStore {
     verified_db: HeightIndexedDb<LightBlock>,
     trusted_db: HeightIndexedDb<LightBlock>,
     failed_db: HeightIndexedDb<LightBlock>,
+    verified_index: HeightIndexedDb<Hash>,
     db: sled::Db,
+    durability: Durability,
 }
 
 impl SledStore {
     /// Open a sled database and create a new persistent store from it.
-    pub fn open(db: impl AsRef<Path>) -> Result<Self, sled::Error> {
-        Ok(Self::new(sled::open(db)?))
+    ///
+    /// Fails with [`ErrorKind::IncompatibleSchema`] if the database was written
+    /// with another schema, see [`SledStore::open_and_migrate`].
+    pub fn open(db: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::new(sled::open(db).map_err(|e| ErrorKind::Store.context(e))?)
+    }
+
+    /// Open a sled database, upgrade it to the current schema with the given
+    /// migrations (e.g. [`MIGRATIONS`]), and create a new persistent store from it.
+    ///
+    /// Fails with [`ErrorKind::IncompatibleSchema`] if there is no migration
+    /// for the schema of the database, in which case it must be re-initialized.
+    pub fn open_and_migrate(db: impl AsRef<Path>, migrations: &[Migration]) -> Result<Self, Error> {
+        let db = sled::open(db).map_err(|e| ErrorKind::Store.context(e))?;
+        Self::migrate(&db, migrations)?;
+        Self::new(db)
     }
 
     /// Create a new persistent store from a sled database that is already open.
-    pub fn new(db: sled::Db) -> Self {
-        Self {
-            unverified_db: HeightIndexedDb::new(db.open_tree(UNVERIFIED).unwrap()),
-            verified_db: HeightIndexedDb::new(db.open_tree(VERIFIED).unwrap()),
-            trusted_db: HeightIndexedDb::new(db.open_tree(TRUSTED).unwrap()),
-            failed_db: HeightIndexedDb::new(db.open_tree(FAILED).unwrap()),
+    ///
+    /// An empty database is stamped with the current schema version, otherwise
+    /// this fails with [`ErrorKind::IncompatibleSchema`] if the database was
+    /// written with another schema.
+    pub fn new(db: sled::Db) -> Result<Self, Error> {
+        let store = Self {
+            unverified_db: Self::open_tree(&db, UNVERIFIED)?,
+            verified_db: Self::open_tree(&db, VERIFIED)?,
+            trusted_db: Self::open_tree(&db, TRUSTED)?,
+            failed_db: Self::open_tree(&db, FAILED)?,
+            verified_index: Self::open_tree(&db, VERIFIED_INDEX)?,
             db,
+            durability: Durability::default(),
+        };
+
+        if Self::is_empty(&store.db)? {
+            Self::set_schema_version(&store.db, SCHEMA_VERSION)?;
+        }
+
+        let found = Self::schema_version(&store.db)?;
+        if found != SCHEMA_VERSION {
+            bail!(ErrorKind::IncompatibleSchema {
+                found,
+                expected: SCHEMA_VERSION,
+            });
+        }
+
+        Ok(store)
+    }
+
+    /// Set when the writes to the store are made durable.
+    pub fn with_durability(mut self, durability: Durability) -> Self {
+        self.durability = durability;
+        self
+    }
+
+    /// Scan the store for entries which were partially written, e.g. on power
+    /// loss, or which can't be deserialized, and set them aside so that they
+    /// don't surface in the middle of a sync.
+    ///
+    /// If `quarantine` is true, the entries are moved to a separate tree of the
+    /// database for later inspection, otherwise they are removed.
+    pub fn repair(&self, quarantine: bool) -> Result<RepairReport, Error> {
+        let quarantine = if quarantine {
+            Some(
+                self.db
+                    .open_tree(QUARANTINE)
+                    .map_err(|e| ErrorKind::Store.context(e))?,
+            )
+        } else {
+            None
+        };
+
+        let mut report = RepairReport::default();
+
+        for status in Status::iter() {
+            let db = self.db(*status);
+            for key in db.corrupted_keys()? {
+                if let Some(quarantine) = &quarantine {
+                    db.quarantine(&key, quarantine)?;
+                    report.quarantined += 1;
+                } else {
+                    db.remove_key(&key)?;
+                    report.removed += 1;
+                }
+            }
+        }
+
+        if !report.is_clean() {
+            Self::rebuild_verified_index(&self.db)?;
+            self.flush()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Number of entries moved to the quarantine by [`SledStore::repair`].
+    pub fn quarantined(&self) -> Result<usize, Error> {
+        let quarantine = self
+            .db
+            .open_tree(QUARANTINE)
+            .map_err(|e| ErrorKind::Store.context(e))?;
+
+        Ok(quarantine.len())
+    }
+
+    /// Flush the pending writes to disk.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.db.flush().map_err(|e| ErrorKind::Store.context(e))?;
+        Ok(())
+    }
+
+    /// Flush the pending writes to disk if every write must be durable
+    fn sync(&self) {
+        if self.durability == Durability::Sync {
+            self.flush().ok();
+        }
+    }
+
+    /// Get the schema version of the given database.
+    pub fn schema_version(db: &sled::Db) -> Result<u64, Error> {
+        let version = db
+            .get(SCHEMA_VERSION_KEY)
+            .map_err(|e| ErrorKind::Store.context(e))?;
+
+        match version {
+            None => Ok(0),
+            Some(bytes) => {
+                let bytes = bytes
+                    .as_ref()
+                    .try_into()
+                    .map_err(|e| ErrorKind::Store.context(e))?;
+                Ok(u64::from_be_bytes(bytes))
+            }
+        }
+    }
+
+    /// Upgrade the given database to the current schema, by applying the given
+    /// migrations in turn from its schema version on.
+    pub fn migrate(db: &sled::Db, migrations: &[Migration]) -> Result<(), Error> {
+        if Self::is_empty(db)? {
+            return Ok(());
+        }
+
+        let mut version = Self::schema_version(db)?;
+        while version < SCHEMA_VERSION {
+            let migration = migrations
+                .iter()
+                .find(|m| m.from_version == version)
+                .ok_or(ErrorKind::IncompatibleSchema {
+                    found: version,
+                    expected: SCHEMA_VERSION,
+                })?;
+
+            (migration.migrate)(db)?;
+
+            version += 1;
+            Self::set_schema_version(db, version)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuild the index of the hashes of the trusted or verified light blocks
+    /// of the given database from scratch.
+    fn rebuild_verified_index(db: &sled::Db) -> Result<(), Error> {
+        let index: HeightIndexedDb<Hash> = Self::open_tree(db, VERIFIED_INDEX)?;
+        index.clear()?;
+
+        for name in &[VERIFIED, TRUSTED] {
+            let light_blocks: HeightIndexedDb<LightBlock> = Self::open_tree(db, name)?;
+            for light_block in light_blocks.iter() {
+                index.insert(
+                    light_block.height(),
+                    &light_block.signed_header.header.hash(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update the index entry of the given height after its light blocks changed
+    fn update_verified_index(&self, height: Height) -> Result<(), Error> {
+        match self.get_trusted_or_verified(height) {
+            Some(light_block) => self
+                .verified_index
+                .insert(height, &light_block.signed_header.header.hash()),
+            None => self.verified_index.remove(height),
+        }
+    }
+
+    fn open_tree<V>(db: &sled::Db, name: &str) -> Result<HeightIndexedDb<V>, Error> {
+        db.open_tree(name)
+            .map(HeightIndexedDb::new)
+            .map_err(|e| ErrorKind::Store.context(e).into())
+    }
+
+    fn set_schema_version(db: &sled::Db, version: u64) -> Result<(), Error> {
+        db.insert(SCHEMA_VERSION_KEY, &version.to_be_bytes())
+            .map_err(|e| ErrorKind::Store.context(e))?;
+
+        db.flush().map_err(|e| ErrorKind::Store.context(e))?;
+
+        Ok(())
+    }
+
+    /// Whether the database holds neither light blocks nor a schema version
+    fn is_empty(db: &sled::Db) -> Result<bool, Error> {
+        for name in &[UNVERIFIED, VERIFIED, TRUSTED, FAILED] {
+            let tree = db
+                .open_tree(name)
+                .map_err(|e| ErrorKind::Store.context(e))?;
+            if !tree.is_empty() {
+                return Ok(false);
+            }
         }
+
+        Ok(db.is_empty())
     }
 
     fn db(&self, status: Status) -> &HeightIndexedDb<LightBlock> {
@@ -68,16 +355,26 @@ impl LightStore for SledStore {
         }
 
         self.db(status).insert(height, light_block).ok();
+        self.update_verified_index(height).ok();
+        self.sync();
     }
 
     fn insert(&mut self, light_block: LightBlock, status: Status) {
         self.db(status)
             .insert(light_block.height(), &light_block)
             .ok();
+        if status.is_verified() {
+            self.update_verified_index(light_block.height()).ok();
+        }
+        self.sync();
     }
 
     fn remove(&mut self, height: Height, status: Status) {
         self.db(status).remove(height).ok();
+        if status.is_verified() {
+            self.update_verified_index(height).ok();
+        }
+        self.sync();
     }
 
     fn highest(&self, status: Status) -> Option<LightBlock> {
@@ -91,6 +388,10 @@ impl LightStore for SledStore {
     fn all(&self, status: Status) -> Box<dyn Iterator<Item = LightBlock>> {
         Box::new(self.db(status).iter())
     }
+
+    fn verified_hash(&self, height: Height) -> Option<Hash> {
+        self.verified_index.get(height).ok().flatten()
+    }
 }
 
 #[cfg(test)]
@@ -123,19 +424,185 @@ mod tests {
         })
     }
 
-    fn with_blocks(height: u64, f: impl FnOnce(SledStore, Vec<LightBlock>)) {
+    #[test]
+    fn new_database_has_current_schema() {
         let tmp_dir = TempDir::new("tendermint_light_client_sled_test").unwrap();
-        let db = SledStore::open(tmp_dir).unwrap();
+        let db = sled::open(tmp_dir.path()).unwrap();
+        let store = SledStore::new(db.clone()).unwrap();
 
-        let chain = LightChain::default_with_length(height);
-        let blocks = chain
+        assert_eq!(SledStore::schema_version(&db).unwrap(), SCHEMA_VERSION);
+        drop(store);
+
+        assert!(SledStore::new(db).is_ok());
+    }
+
+    #[test]
+    fn unversioned_database_is_incompatible_until_migrated() {
+        let tmp_dir = TempDir::new("tendermint_light_client_sled_test").unwrap();
+        let block = blocks(1).remove(0);
+
+        // A database written before the schema was versioned. It is kept open
+        // throughout, as sled may not release its lock right away when closed.
+        let db = sled::open(tmp_dir.path()).unwrap();
+        HeightIndexedDb::new(db.open_tree(TRUSTED).unwrap())
+            .insert(block.height(), &block)
+            .unwrap();
+
+        let err = SledStore::new(db.clone()).err().unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleSchema {
+                found: 0,
+                expected: SCHEMA_VERSION
+            }
+        );
+
+        let err = SledStore::migrate(&db, &[]).err().unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleSchema {
+                found: 0,
+                expected: SCHEMA_VERSION
+            }
+        );
+
+        SledStore::migrate(&db, MIGRATIONS).unwrap();
+        let store = SledStore::new(db).unwrap();
+        assert_eq!(
+            store.verified_hash(block.height()),
+            Some(block.signed_header.header.hash())
+        );
+        assert_eq!(store.highest(Status::Trusted), Some(block));
+    }
+
+    #[test]
+    fn verified_index_follows_light_blocks() {
+        with_blocks(3, |mut db, blocks| {
+            let hash = |lb: &LightBlock| lb.signed_header.header.hash();
+            let (b1, b2, b3) = (&blocks[0], &blocks[1], &blocks[2]);
+
+            db.insert(b1.clone(), Status::Trusted);
+            db.insert(b2.clone(), Status::Unverified);
+            db.insert(b3.clone(), Status::Verified);
+            assert!(db.is_verified(b1.height(), hash(b1)));
+            assert!(!db.is_verified(b1.height(), hash(b2)));
+            assert_eq!(db.verified_hash(b2.height()), None);
+            assert_eq!(db.verified_hash(b3.height()), Some(hash(b3)));
+
+            db.update(b2, Status::Verified);
+            assert_eq!(db.verified_hash(b2.height()), Some(hash(b2)));
+            db.update(b3, Status::Failed);
+            assert_eq!(db.verified_hash(b3.height()), None);
+
+            // Pruning a light block evicts it from the index
+            db.remove(b1.height(), Status::Unverified);
+            assert_eq!(db.verified_hash(b1.height()), Some(hash(b1)));
+            db.remove(b1.height(), Status::Trusted);
+            assert_eq!(db.verified_hash(b1.height()), None);
+        })
+    }
+
+    #[test]
+    fn newer_database_is_incompatible() {
+        let tmp_dir = TempDir::new("tendermint_light_client_sled_test").unwrap();
+        let db = sled::open(tmp_dir.path()).unwrap();
+        SledStore::set_schema_version(&db, SCHEMA_VERSION + 1).unwrap();
+
+        let err = SledStore::new(db).err().unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleSchema {
+                found: SCHEMA_VERSION + 1,
+                expected: SCHEMA_VERSION
+            }
+        );
+    }
+
+    #[test]
+    fn repair_quarantines_corrupted_entries() {
+        let tmp_dir = TempDir::new("tendermint_light_client_sled_test").unwrap();
+        let db = sled::open(tmp_dir.path()).unwrap();
+        let mut store = SledStore::new(db.clone()).unwrap();
+        let blocks = blocks(3);
+
+        for block in &blocks {
+            store.insert(block.clone(), Status::Verified);
+        }
+        assert!(store.repair(true).unwrap().is_clean());
+
+        // A partially written light block, and a key which isn't a height
+        let verified = db.open_tree(VERIFIED).unwrap();
+        verified
+            .insert(4_u64.to_be_bytes(), &[0xa4, 0x01][..])
+            .unwrap();
+        verified.insert(b"junk", &b"junk"[..]).unwrap();
+
+        let report = store.repair(true).unwrap();
+        assert_eq!(
+            report,
+            RepairReport {
+                quarantined: 2,
+                removed: 0
+            }
+        );
+        assert_eq!(store.quarantined().unwrap(), 2);
+        assert_eq!(store.all(Status::Verified).collect::<Vec<_>>(), blocks);
+        assert!(store.repair(true).unwrap().is_clean());
+    }
+
+    #[test]
+    fn repair_removes_corrupted_entries() {
+        let tmp_dir = TempDir::new("tendermint_light_client_sled_test").unwrap();
+        let db = sled::open(tmp_dir.path()).unwrap();
+        let store = SledStore::new(db.clone()).unwrap();
+
+        db.open_tree(TRUSTED)
+            .unwrap()
+            .insert(1_u64.to_be_bytes(), &[0xff][..])
+            .unwrap();
+
+        let report = store.repair(false).unwrap();
+        assert_eq!(
+            report,
+            RepairReport {
+                quarantined: 0,
+                removed: 1
+            }
+        );
+        assert_eq!(store.quarantined().unwrap(), 0);
+        assert_eq!(store.get(1_u32.into(), Status::Trusted), None);
+    }
+
+    #[test]
+    fn sync_writes_are_flushed() {
+        let tmp_dir = TempDir::new("tendermint_light_client_sled_test").unwrap();
+        let db = sled::open(tmp_dir.path()).unwrap();
+        let block = blocks(1).remove(0);
+
+        let mut store = SledStore::new(db.clone())
+            .unwrap()
+            .with_durability(Durability::Sync);
+        store.insert(block.clone(), Status::Trusted);
+
+        // Nothing is left to flush
+        assert_eq!(db.flush().unwrap(), 0);
+        assert_eq!(store.highest(Status::Trusted), Some(block));
+    }
+
+    fn blocks(height: u64) -> Vec<LightBlock> {
+        LightChain::default_with_length(height)
             .light_blocks
             .into_iter()
             .map(|lb| lb.generate().unwrap())
             .map(testgen_to_lb)
-            .collect::<Vec<_>>();
+            .collect()
+    }
+
+    fn with_blocks(height: u64, f: impl FnOnce(SledStore, Vec<LightBlock>)) {
+        let tmp_dir = TempDir::new("tendermint_light_client_sled_test").unwrap();
+        let db = SledStore::open(tmp_dir).unwrap();
 
-        f(db, blocks)
+        f(db, blocks(height))
     }
 
     fn testgen_to_lb(tm_lb: TGLightBlock) -> LightBlock {
@@ -90,6 +90,58 @@ where
         Ok(())
     }
 
+    /// Remove all the values within this tree
+    pub fn clear(&self) -> Result<(), Error> {
+        self.tree.clear().map_err(|e| ErrorKind::Store.context(e))?;
+
+        Ok(())
+    }
+
+    /// Return the keys of the entries within this tree which can't be read back,
+    /// either because their key isn't a height or their value can't be deserialized
+    pub fn corrupted_keys(&self) -> Result<Vec<sled::IVec>, Error> {
+        let mut keys = Vec::new();
+
+        for entry in self.tree.iter() {
+            let (key, value) = entry.map_err(|e| ErrorKind::Store.context(e))?;
+            if key.len() != 8 || serde_cbor::from_slice::<V>(&value).is_err() {
+                keys.push(key);
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Move the entry with the given raw key to the `quarantine` tree, under a key
+    /// prefixed with the name of this tree
+    pub fn quarantine(&self, key: &[u8], quarantine: &sled::Tree) -> Result<(), Error> {
+        let value = self
+            .tree
+            .remove(key)
+            .map_err(|e| ErrorKind::Store.context(e))?;
+
+        if let Some(value) = value {
+            let mut quarantine_key = self.tree.name().to_vec();
+            quarantine_key.push(b'/');
+            quarantine_key.extend_from_slice(key);
+
+            quarantine
+                .insert(quarantine_key, value)
+                .map_err(|e| ErrorKind::Store.context(e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the entry with the given raw key within this tree
+    pub fn remove_key(&self, key: &[u8]) -> Result<(), Error> {
+        self.tree
+            .remove(key)
+            .map_err(|e| ErrorKind::Store.context(e))?;
+
+        Ok(())
+    }
+
     /// Return an iterator over all values within this tree
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = V> {
         self.tree
@@ -1,12 +1,11 @@
 //! Supervisor and Handle implementation.
 
 use crossbeam_channel as channel;
-
-use tendermint::evidence::{ConflictingHeadersEvidence, Evidence};
+use tracing::warn;
 
 use crate::bail;
 use crate::errors::{Error, ErrorKind};
-use crate::evidence::EvidenceReporter;
+use crate::evidence::{light_client_attack_evidence, EvidenceReporter};
 use crate::fork_detector::{Fork, ForkDetection, ForkDetector};
 use crate::light_client::LightClient;
 use crate::peer_list::PeerList;
@@ -21,12 +20,20 @@ pub trait Handle: Send + Sync {
     /// Get the latest status.
     fn latest_status(&self) -> Result<LatestStatus, Error>;
 
+    /// Get the block at the given height, if it was already trusted or
+    /// verified, without verifying it otherwise.
+    fn verified_block(&self, _height: Height) -> Result<Option<LightBlock>, Error>;
+
     /// Verify to the highest block.
     fn verify_to_highest(&self) -> Result<LightBlock, Error>;
 
     /// Verify to the block at the given height.
     fn verify_to_target(&self, _height: Height) -> Result<LightBlock, Error>;
 
+    /// Detect forks at the given height, which must have been verified, by
+    /// comparing the block of the primary with the ones of all witnesses.
+    fn detect_forks_at(&self, _height: Height) -> Result<ForkDetection, Error>;
+
     /// Terminate the underlying [`Supervisor`].
     fn terminate(&self) -> Result<(), Error>;
 }
@@ -44,9 +51,15 @@ enum HandleInput {
     /// Verify to the given height, call the provided callback with result
     VerifyToTarget(Height, channel::Sender<Result<LightBlock, Error>>),
 
+    /// Detect forks at the given height, call the provided callback with result
+    DetectForksAt(Height, channel::Sender<Result<ForkDetection, Error>>),
+
     /// Get the latest trusted block.
     LatestTrusted(channel::Sender<Option<LightBlock>>),
 
+    /// Get the trusted or verified block at the given height, if any.
+    VerifiedBlock(Height, channel::Sender<Option<LightBlock>>),
+
     /// Get the current status of the LightClient
     GetStatus(channel::Sender<LatestStatus>),
 }
@@ -169,6 +182,16 @@ impl Supervisor {
         self.peers.primary().latest_trusted()
     }
 
+    /// Get the block at the given height, if the primary already trusted or
+    /// verified it.
+    pub fn verified_block(&self, height: Height) -> Option<LightBlock> {
+        self.peers
+            .primary()
+            .state
+            .light_store
+            .get_trusted_or_verified(height)
+    }
+
     /// Verify to the highest block.
     pub fn verify_to_highest(&mut self) -> Result<LightBlock, Error> {
         self.verify(None)
@@ -197,6 +220,29 @@ impl Supervisor {
         self.verify(Some(height))
     }
 
+    /// Detect forks at the given height, by comparing the block verified by
+    /// the primary at that height with the ones of all witnesses. The blocks
+    /// of the witnesses are verified from the highest block trusted by the
+    /// primary below that height.
+    ///
+    /// Unlike verification, the outcome is only reported: no evidence is
+    /// submitted, and no witness is removed.
+    pub fn detect_forks_at(&self, height: Height) -> Result<ForkDetection, Error> {
+        let light_store = &self.peers.primary().state.light_store;
+
+        let verified_block = light_store
+            .get_trusted_or_verified(height)
+            .ok_or(ErrorKind::NotVerified(height))?;
+
+        let trusted_block = light_store
+            .all(Status::Trusted)
+            .filter(|lb| lb.height() < height)
+            .max_by_key(|lb| lb.height())
+            .ok_or(ErrorKind::NoTrustedState(Status::Trusted))?;
+
+        self.detect_forks(&verified_block, &trusted_block)
+    }
+
     /// Verify either to the latest block (if `height == None`) or to a given block (if `height ==
     /// Some(height)`).
     fn verify(&mut self, height: Option<Height>) -> Result<LightBlock, Error> {
@@ -223,7 +269,7 @@ impl Supervisor {
                 match outcome {
                     // There was a fork or a faulty peer
                     ForkDetection::Detected(forks) => {
-                        let forked = self.process_forks(forks)?;
+                        let forked = self.process_forks(forks, &trusted_block)?;
                         if !forked.is_empty() {
                             // Fork detected, exiting
                             bail!(ErrorKind::ForkDetected(forked))
@@ -254,7 +300,11 @@ impl Supervisor {
         }
     }
 
-    fn process_forks(&mut self, forks: Vec<Fork>) -> Result<Vec<PeerId>, Error> {
+    fn process_forks(
+        &mut self,
+        forks: Vec<Fork>,
+        trusted_block: &LightBlock,
+    ) -> Result<Vec<PeerId>, Error> {
         let mut forked = Vec::with_capacity(forks.len());
 
         for fork in forks {
@@ -263,19 +313,24 @@ impl Supervisor {
                 // TODO: also report to primary
                 Fork::Forked { primary, witness } => {
                     let provider = witness.provider;
-                    self.report_evidence(provider, &primary, &witness)?;
+                    warn!(
+                        witness = %provider,
+                        height = %primary.height(),
+                        "fork detected, reporting evidence to witness"
+                    );
+                    self.report_evidence(provider, &primary, trusted_block)?;
 
                     forked.push(provider);
                 }
                 // A witness has timed out, remove it from the peer list.
-                Fork::Timeout(provider, _error) => {
+                Fork::Timeout(provider, error) => {
+                    warn!(witness = %provider, %error, "witness timed out, replacing it");
                     self.peers.replace_faulty_witness(provider);
-                    // TODO: Log/record the error
                 }
                 // A witness has been deemed faulty, remove it from the peer list.
-                Fork::Faulty(block, _error) => {
+                Fork::Faulty(block, error) => {
+                    warn!(witness = %block.provider, %error, "witness is faulty, replacing it");
                     self.peers.replace_faulty_witness(block.provider);
-                    // TODO: Log/record the error
                 }
             }
         }
@@ -283,20 +338,18 @@ impl Supervisor {
         Ok(forked)
     }
 
-    /// Report the given evidence of a fork.
+    /// Report evidence of an attack by the primary, which forked from the
+    /// witness after the given trusted block, to the witness.
     fn report_evidence(
         &mut self,
         provider: PeerId,
         primary: &LightBlock,
-        witness: &LightBlock,
+        trusted_block: &LightBlock,
     ) -> Result<(), Error> {
-        let evidence = ConflictingHeadersEvidence::new(
-            primary.signed_header.clone(),
-            witness.signed_header.clone(),
-        );
+        let evidence = light_client_attack_evidence(primary, trusted_block);
 
         self.evidence_reporter
-            .report(Evidence::ConflictingHeaders(Box::new(evidence)), provider)
+            .report(evidence, provider)
             .map_err(ErrorKind::Io)?;
 
         Ok(())
@@ -335,6 +388,10 @@ impl Supervisor {
                     let outcome = self.latest_trusted();
                     sender.send(outcome).map_err(ErrorKind::from)?;
                 }
+                HandleInput::VerifiedBlock(height, sender) => {
+                    let outcome = self.verified_block(height);
+                    sender.send(outcome).map_err(ErrorKind::from)?;
+                }
                 HandleInput::Terminate(sender) => {
                     sender.send(()).map_err(ErrorKind::from)?;
                     return Ok(());
@@ -343,6 +400,10 @@ impl Supervisor {
                     let outcome = self.verify_to_target(height);
                     sender.send(outcome).map_err(ErrorKind::from)?;
                 }
+                HandleInput::DetectForksAt(height, sender) => {
+                    let outcome = self.detect_forks_at(height);
+                    sender.send(outcome).map_err(ErrorKind::from)?;
+                }
                 HandleInput::VerifyToHighest(sender) => {
                     let outcome = self.verify_to_highest();
                     sender.send(outcome).map_err(ErrorKind::from)?;
@@ -402,6 +463,14 @@ impl Handle for SupervisorHandle {
         Ok(receiver.recv().map_err(ErrorKind::from)?)
     }
 
+    fn verified_block(&self, height: Height) -> Result<Option<LightBlock>, Error> {
+        let (sender, receiver) = channel::bounded::<Option<LightBlock>>(1);
+        self.sender
+            .send(HandleInput::VerifiedBlock(height, sender))
+            .map_err(ErrorKind::from)?;
+        Ok(receiver.recv().map_err(ErrorKind::from)?)
+    }
+
     fn verify_to_highest(&self) -> Result<LightBlock, Error> {
         self.verify(HandleInput::VerifyToHighest)
     }
@@ -410,6 +479,16 @@ impl Handle for SupervisorHandle {
         self.verify(|sender| HandleInput::VerifyToTarget(height, sender))
     }
 
+    fn detect_forks_at(&self, height: Height) -> Result<ForkDetection, Error> {
+        let (sender, receiver) = channel::bounded::<Result<ForkDetection, Error>>(1);
+
+        self.sender
+            .send(HandleInput::DetectForksAt(height, sender))
+            .map_err(ErrorKind::from)?;
+
+        receiver.recv().map_err(ErrorKind::from)?
+    }
+
     fn terminate(&self) -> Result<(), Error> {
         let (sender, receiver) = channel::bounded::<()>(1);
 
@@ -709,6 +788,65 @@ mod tests {
         assert_eq!(&expected_err, got_err.kind());
     }
 
+    #[test]
+    fn test_detect_forks_at() {
+        let mut chain = LightChain::default_with_length(5);
+        let primary = chain
+            .light_blocks
+            .clone()
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let mut header = chain.light_blocks[4].header.clone().unwrap();
+        header.time = Some(header.time.unwrap() + 3);
+        chain.light_blocks[4].header = Some(header.clone());
+        chain.light_blocks[4].commit = Some(Commit::new(header, 1));
+
+        let witness = change_provider(
+            chain
+                .light_blocks
+                .into_iter()
+                .map(|lb| lb.generate().unwrap().into())
+                .collect::<Vec<LightBlock>>(),
+            None,
+        );
+
+        let peer_list = make_peer_list(
+            Some(primary.clone()),
+            Some(vec![witness.clone()]),
+            get_time(11),
+        );
+        let supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+        let handle = supervisor.handle();
+        std::thread::spawn(|| supervisor.run());
+
+        let height = |h: u64| Height::try_from(h).unwrap();
+
+        let got_err = handle.detect_forks_at(height(4)).err().unwrap();
+        assert_eq!(&ErrorKind::NotVerified(height(4)), got_err.kind());
+
+        handle.verify_to_target(height(4)).unwrap();
+        assert_eq!(
+            handle.detect_forks_at(height(4)).unwrap(),
+            ForkDetection::NotDetected
+        );
+
+        // The primary verified the block at height 5 before detecting the fork
+        assert!(handle.verify_to_target(height(5)).is_err());
+        assert_eq!(
+            handle.detect_forks_at(height(5)).unwrap(),
+            ForkDetection::Detected(vec![Fork::Forked {
+                primary: primary[4].clone(),
+                witness: witness[4].clone(),
+            }])
+        );
+    }
+
     #[test]
     fn test_bisection_no_initial_trusted_state() {
         let chain = LightChain::default_with_length(10);
@@ -3,7 +3,7 @@
 use crate::types::{Height, LightBlock, PeerId, SignedHeader, Time, TrustThreshold, ValidatorSet};
 
 use serde::{Deserialize, Serialize};
-use tendermint::abci::transaction::Hash;
+use tendermint::Hash;
 use tendermint_rpc as rpc;
 
 use crate::components::clock::Clock;
@@ -126,7 +126,7 @@ pub struct MockEvidenceReporter;
 #[contract_trait]
 impl EvidenceReporter for MockEvidenceReporter {
     fn report(&self, _e: Evidence, _peer: PeerId) -> Result<Hash, IoError> {
-        Ok(Hash::new([0; 32]))
+        Ok(Hash::Sha256([0; 32]))
     }
 }
 
@@ -75,6 +75,12 @@ impl Status {
     pub fn most_trusted(a: Self, b: Self) -> Self {
         std::cmp::max(a, b)
     }
+
+    /// Whether a light block with this status was successfully verified,
+    /// ie. its status is either `Verified` or `Trusted`.
+    pub fn is_verified(self) -> bool {
+        self >= Status::Verified
+    }
 }
 
 /// A light block is the core data structure used by the light client.
@@ -17,7 +17,7 @@ use tendermint_light_client::{
 };
 
 use tendermint_testgen::light_block::default_peer_id;
-use tendermint_testgen::Tester;
+use tendermint_testgen::{Generator, Tester, Validator};
 
 // Link to JSON test files repo:
 // https://github.com/informalsystems/conformance-tests
@@ -119,3 +119,52 @@ fn run_tests() {
     tester.run_foreach_in_dir("bisection/single_peer");
     tester.finalize();
 }
+
+fn generate(test: tendermint_testgen::LightClientTest) -> LightClientTest<LightBlock> {
+    let test = test.generate().expect("failed to generate test case");
+    serde_json::from_value(serde_json::to_value(test).unwrap()).unwrap()
+}
+
+#[test]
+fn run_generated_tests() {
+    let vals = |ids: &[&str]| -> Vec<Validator> {
+        ids.iter()
+            .map(|id| Validator::new(id).voting_power(50))
+            .collect()
+    };
+    let validators = [
+        vals(&["a", "b", "c"]),
+        vals(&["a", "b", "c"]),
+        vals(&["b", "c", "d"]),
+        vals(&["c", "d", "e"]),
+        vals(&["d", "e", "f"]),
+    ];
+
+    forward_test(generate(
+        tendermint_testgen::LightClientTest::new(8).validators(&validators),
+    ));
+    forward_test(generate(
+        tendermint_testgen::LightClientTest::new(8)
+            .validators(&validators)
+            .trusting_period(5),
+    ));
+
+    // Trusting period and clock drift
+    let times = [100, 110, 120, 130, 140, 150, 160, 170];
+    for now in &[171, 161, 160] {
+        forward_test(generate(
+            tendermint_testgen::LightClientTest::new(8)
+                .validators(&validators)
+                .times(&times)
+                .now(*now),
+        ));
+    }
+    forward_test(generate(
+        tendermint_testgen::LightClientTest::new(8)
+            .block_interval(60)
+            .trusting_period(300),
+    ));
+    forward_test(generate(
+        tendermint_testgen::LightClientTest::new(8).times(&[100, 110, 105]),
+    ));
+}
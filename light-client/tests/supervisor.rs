@@ -22,7 +22,7 @@ use tendermint_light_client::tests::{
     LightClientTest, MockClock, MockEvidenceReporter, MockIo, TrustOptions,
 };
 
-use tendermint_testgen::Tester;
+use tendermint_testgen::{Generator, Tester};
 
 const TEST_FILES_PATH: &str = "./tests/support/";
 
@@ -127,3 +127,27 @@ fn run_multipeer_tests() {
     tester.run_foreach_in_dir("bisection/multi_peer");
     tester.finalize();
 }
+
+#[test]
+fn run_generated_multipeer_tests() {
+    let generate = |test: tendermint_testgen::LightClientTest| -> LightClientTest<LightBlock> {
+        let test = test.generate().expect("failed to generate test case");
+        serde_json::from_value(serde_json::to_value(test).unwrap()).unwrap()
+    };
+
+    run_multipeer_test(generate(
+        tendermint_testgen::LightClientTest::new(5).witnesses(2),
+    ));
+    run_multipeer_test(generate(
+        tendermint_testgen::LightClientTest::new(5)
+            .witnesses(2)
+            .faulty_witnesses(&[1])
+            .fork_height(3),
+    ));
+    run_multipeer_test(generate(
+        tendermint_testgen::LightClientTest::new(5)
+            .length(6)
+            .faulty_witnesses(&[0])
+            .fork_height(6),
+    ));
+}
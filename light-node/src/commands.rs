@@ -1,6 +1,7 @@
 //! LightNode Subcommands
 //!
 //! The light client supports the following subcommands:
+//! - `evidence`: submits evidence of misbehavior to the configured peers
 //! - `initialize`: subjectively initializes the light node with a given height and hash
 //! - `start`: launches the light client
 //! - `version`: print application version
@@ -8,17 +9,20 @@
 //! See the `impl Configurable` below for how to specify the path to the
 //! application's configuration file.
 
+mod evidence;
 mod initialize;
 mod start;
 mod version;
 
-use self::{start::StartCmd, version::VersionCmd};
+use self::{evidence::EvidenceCmd, start::StartCmd, version::VersionCmd};
 use crate::commands::initialize::InitCmd;
-use crate::config::LightNodeConfig;
+use crate::config::{LightClientConfig, LightNodeConfig};
 use abscissa_core::{
-    config::Override, Command, Configurable, FrameworkError, Help, Options, Runnable,
+    config::Override, status_warn, Command, Configurable, FrameworkError, Help, Options, Runnable,
 };
 use std::path::PathBuf;
+use tendermint_light_client::errors::ErrorKind;
+use tendermint_light_client::store::sled::{Durability, SledStore, MIGRATIONS};
 
 /// LightNode Configuration Filename
 pub const CONFIG_FILE: &str = "light_node.toml";
@@ -30,6 +34,12 @@ pub enum LightNodeCmd {
     #[options(help = "get usage information")]
     Help(Help<Self>),
 
+    /// submit `evidence` of misbehavior
+    #[options(
+        help = "verify evidence of misbehavior read from a file or STDIN, and submit it to the configured peers"
+    )]
+    Evidence(EvidenceCmd),
+
     /// `intialize` the light node
     #[options(
         help = "subjectively initialize the light client with given subjective height and validator set hash"
@@ -73,3 +83,37 @@ impl Configurable<LightNodeConfig> for LightNodeCmd {
         }
     }
 }
+
+/// Open the light store of the given light client, migrating it to the current
+/// schema if it was written by a previous version of the light node, and
+/// quarantining the entries which were corrupted, e.g. on power loss.
+fn open_store(config: &LightClientConfig) -> Result<SledStore, String> {
+    let db_path = &config.db_path;
+    let store = SledStore::open_and_migrate(db_path, MIGRATIONS).map_err(|e| match e.kind() {
+        ErrorKind::IncompatibleSchema { .. } => format!(
+            "could not open database {:?}: {}, please remove it and initialize the light node \
+             again with the `initialize` subcommand",
+            db_path, e
+        ),
+        _ => format!("could not open database: {}", e),
+    })?;
+
+    let report = store
+        .repair(true)
+        .map_err(|e| format!("could not repair database {:?}: {}", db_path, e))?;
+    if !report.is_clean() {
+        status_warn!(
+            "quarantined {} corrupted entries of database {:?}",
+            report.quarantined,
+            db_path
+        );
+    }
+
+    let durability = if config.sync_writes {
+        Durability::Sync
+    } else {
+        Durability::Buffered
+    };
+
+    Ok(store.with_durability(durability))
+}
@@ -0,0 +1,189 @@
+//! `evidence` subcommand - submit evidence of misbehavior to the configured peers.
+
+use crate::application::app_config;
+use crate::commands::open_store;
+use crate::config::LightNodeConfig;
+
+use abscissa_core::{status_err, status_info, status_warn, Command, Options, Runnable};
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use tendermint::evidence::{Duration, Params};
+use tendermint_light_client::components::io::IoError;
+use tendermint_light_client::evidence::{Evidence, EvidenceReporter, ProdEvidenceReporter};
+use tendermint_light_client::store::LightStore;
+use tendermint_rpc::error::EvidenceRejection;
+
+/// Default maximum age of evidence, in blocks, as in Tendermint's default
+/// consensus parameters.
+const DEFAULT_MAX_AGE_NUM_BLOCKS: u64 = 100_000;
+
+/// `evidence` subcommand
+#[derive(Command, Debug, Default, Options)]
+pub struct EvidenceCmd {
+    /// Path to the evidence file
+    #[options(
+        short = "f",
+        long = "file",
+        help = "path to the JSON-encoded evidence (default: read from STDIN)"
+    )]
+    pub file: Option<PathBuf>,
+
+    /// Maximum age of the evidence, in blocks
+    #[options(
+        long = "max-age-num-blocks",
+        help = "maximum age of the evidence in blocks, past which it is rejected if it is also \
+                older than the trusting period (default: 100000)"
+    )]
+    pub max_age_num_blocks: Option<u64>,
+
+    /// Submit the evidence without verifying it first
+    #[options(
+        long = "skip-verification",
+        help = "submit the evidence without verifying it against the light store of the primary"
+    )]
+    pub skip_verification: bool,
+}
+
+impl Runnable for EvidenceCmd {
+    fn run(&self) {
+        let evidence = match self.read_evidence() {
+            Ok(evidence) => evidence,
+            Err(e) => {
+                status_err!("could not read evidence: {}", e);
+                return;
+            }
+        };
+
+        status_info!(
+            "evidence",
+            "evidence {} at height {}",
+            evidence.hash(),
+            evidence.height()
+        );
+
+        if self.skip_verification {
+            status_warn!("skipping verification of the evidence");
+        } else {
+            if let Err(e) = self.verify(&evidence) {
+                status_err!("invalid evidence: {}", e);
+                return;
+            }
+            status_info!("evidence", "evidence is valid");
+        }
+
+        submit(&app_config(), evidence);
+    }
+}
+
+impl EvidenceCmd {
+    fn read_evidence(&self) -> Result<Evidence, String> {
+        let json = match &self.file {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e))?,
+            None => {
+                let mut json = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut json)
+                    .map_err(|e| format!("could not read STDIN: {}", e))?;
+                json
+            }
+        };
+
+        serde_json::from_str(&json).map_err(|e| format!("could not parse evidence: {}", e))
+    }
+
+    /// Verify the evidence against the light blocks verified by the primary:
+    /// the validator set at the height of the evidence, and the latest block,
+    /// which determines whether the evidence has expired.
+    fn verify(&self, evidence: &Evidence) -> Result<(), String> {
+        let config = app_config();
+        let light_store = open_store(config.light_clients.first().unwrap())?;
+
+        let block = light_store
+            .get_trusted_or_verified(evidence.height())
+            .ok_or_else(|| {
+                format!(
+                    "no trusted or verified light block at height {} in the store of the \
+                     primary, verify it first or pass --skip-verification",
+                    evidence.height()
+                )
+            })?;
+
+        let latest = light_store
+            .highest_trusted_or_verified()
+            .ok_or("no trusted or verified state in store for primary")?;
+
+        let params = Params {
+            max_age_num_blocks: self
+                .max_age_num_blocks
+                .unwrap_or(DEFAULT_MAX_AGE_NUM_BLOCKS),
+            max_age_duration: Duration(config.trusting_period),
+            max_bytes: 0,
+        };
+
+        evidence
+            .verify(
+                &block.validators,
+                &block.signed_header.header.chain_id,
+                &params,
+                latest.height(),
+                latest.signed_header.header.time,
+            )
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Submit the evidence to all the configured peers, reporting the outcome
+/// for each of them.
+fn submit(config: &LightNodeConfig, evidence: Evidence) {
+    let peer_map = config
+        .light_clients
+        .iter()
+        .map(|lc| (lc.peer_id, lc.address.clone()))
+        .collect();
+
+    let reporter = ProdEvidenceReporter::new(peer_map, Some(config.rpc_config.request_timeout));
+
+    let mut submitted = 0;
+    for light_client in &config.light_clients {
+        match reporter.report(evidence.clone(), light_client.peer_id) {
+            Ok(hash) => {
+                submitted += 1;
+                status_info!(
+                    "evidence",
+                    "submitted to {} @ {}: {}",
+                    light_client.peer_id,
+                    light_client.address,
+                    hash
+                );
+            }
+            // The peer already has the evidence, which is what submitting it was meant for
+            Err(IoError::RpcError(e))
+                if e.evidence_rejection() == Some(EvidenceRejection::AlreadyCommitted) =>
+            {
+                submitted += 1;
+                status_info!(
+                    "evidence",
+                    "{} @ {} already has the evidence",
+                    light_client.peer_id,
+                    light_client.address
+                );
+            }
+            Err(e) => status_err!(
+                "could not submit evidence to {} @ {}: {}",
+                light_client.peer_id,
+                light_client.address,
+                e
+            ),
+        }
+    }
+
+    status_info!(
+        "evidence",
+        "submitted to {} of {} peer(s)",
+        submitted,
+        config.light_clients.len()
+    );
+}
@@ -4,6 +4,7 @@ use std::ops::Deref;
 use std::time::Duration;
 
 use crate::application::app_config;
+use crate::commands::open_store;
 use crate::config::{LightClientConfig, LightNodeConfig};
 
 use abscissa_core::{status_err, status_info, status_warn, Command, Options, Runnable};
@@ -12,7 +13,6 @@ use tendermint::{hash, Hash};
 
 use std::convert::TryInto;
 use tendermint_light_client::builder::LightClientBuilder;
-use tendermint_light_client::store::sled::SledStore;
 use tendermint_light_client::store::LightStore;
 use tendermint_light_client::supervisor::Instance;
 use tendermint_light_client::types::Height;
@@ -79,8 +79,7 @@ fn initialize_subjectively(
             .to_str()
             .ok_or("unable to obtain sled db path")?
     );
-    let light_store =
-        SledStore::open(&config.db_path).map_err(|e| format!("could not open database: {}", e))?;
+    let light_store = open_store(config)?;
 
     if let Some(trusted_state) = light_store.highest_trusted_or_verified() {
         status_warn!(
@@ -1,12 +1,16 @@
 //! `start` subcommand - start the light node.
 
 use crate::application::app_config;
+use crate::commands::open_store;
 use crate::config::{LightClientConfig, LightNodeConfig};
+use crate::health::Health;
 use crate::rpc;
 use crate::rpc::Server;
 
 use abscissa_core::path::PathBuf;
-use abscissa_core::{config, status_err, status_info, Command, FrameworkError, Options, Runnable};
+use abscissa_core::{
+    config, status_err, status_info, status_warn, Command, FrameworkError, Options, Runnable,
+};
 
 use std::net::SocketAddr;
 use std::ops::Deref;
@@ -14,7 +18,7 @@ use std::time::Duration;
 
 use tendermint_light_client::builder::{LightClientBuilder, SupervisorBuilder};
 use tendermint_light_client::light_client;
-use tendermint_light_client::store::{sled::SledStore, LightStore};
+use tendermint_light_client::store::LightStore;
 use tendermint_light_client::supervisor::{Handle, Instance, Supervisor};
 
 /// `start` subcommand
@@ -31,6 +35,13 @@ pub struct StartCmd {
     /// Path to configuration file
     #[options(short = "c", long = "config", help = "path to light_node.toml")]
     pub config: Option<PathBuf>,
+
+    /// Check the integrity of the light stores before starting
+    #[options(
+        long = "check-store",
+        help = "check the integrity of the light stores and refuse to start if they are inconsistent"
+    )]
+    pub check_store: bool,
 }
 
 impl Runnable for StartCmd {
@@ -49,8 +60,10 @@ impl Runnable for StartCmd {
             }
         };
 
+        let health = Health::new(app_config().rpc_config.health_staleness);
+
         let rpc_handler = supervisor.handle();
-        StartCmd::start_rpc_server(rpc_handler);
+        StartCmd::start_rpc_server(rpc_handler, health.clone());
 
         let handle = supervisor.handle();
         std::thread::spawn(|| supervisor.run());
@@ -58,9 +71,11 @@ impl Runnable for StartCmd {
         loop {
             match handle.verify_to_highest() {
                 Ok(light_block) => {
+                    health.record_success();
                     status_info!("synced to block:", light_block.height().to_string());
                 }
                 Err(err) => {
+                    health.record_failure(&err);
                     status_err!("sync failed: {}", err);
                 }
             }
@@ -91,9 +106,7 @@ impl config::Override<LightNodeConfig> for StartCmd {
 impl StartCmd {
     fn assert_init_was_run() -> Result<(), String> {
         let config = app_config();
-        let db_path = &config.light_clients.first().unwrap().db_path;
-        let primary_store =
-            SledStore::open(db_path).map_err(|e| format!("could not open database: {}", e))?;
+        let primary_store = open_store(config.light_clients.first().unwrap())?;
 
         if primary_store.highest_trusted_or_verified().is_none() {
             return Err("no trusted or verified state in store for primary, please initialize with the `initialize` subcommand first".to_string());
@@ -102,14 +115,14 @@ impl StartCmd {
         Ok(())
     }
 
-    fn start_rpc_server<H>(h: H)
+    fn start_rpc_server<H>(h: H, health: Health)
     where
         H: Handle + Send + Sync + 'static,
     {
         let server = Server::new(h);
         let laddr = app_config().rpc_config.listen_addr;
         // TODO(liamsi): figure out how to handle the potential error on run
-        std::thread::spawn(move || rpc::run(server, &laddr.to_string()));
+        std::thread::spawn(move || rpc::run(server, &laddr.to_string(), health));
         status_info!("started RPC server:", laddr.to_string());
     }
 
@@ -128,8 +141,10 @@ impl StartCmd {
         let rpc_client = tendermint_rpc::HttpClient::new(light_config.address.clone())
             .map_err(|e| format!("failed to create HTTP client: {}", e))?;
 
-        let light_store = SledStore::open(&light_config.db_path)
-            .map_err(|e| format!("could not open database: {}", e))?;
+        let light_store = open_store(light_config)?;
+        if self.check_store {
+            check_store(&light_store, light_config)?;
+        }
 
         status_info!(
             "start",
@@ -197,3 +212,36 @@ impl StartCmd {
         Ok(builder.build_prod())
     }
 }
+
+/// Check the integrity of the light store of the given light client, reporting
+/// each inconsistency found.
+fn check_store(
+    light_store: &dyn LightStore,
+    light_config: &LightClientConfig,
+) -> Result<(), String> {
+    let report = light_store.verify_integrity();
+    for issue in &report.issues {
+        status_warn!(
+            "inconsistent database {:?}: {}",
+            light_config.db_path,
+            issue
+        );
+    }
+    if !report.is_clean() {
+        return Err(format!(
+            "found {} inconsistencies among the {} light blocks of database {:?}, please remove \
+             it and initialize the light node again with the `initialize` subcommand",
+            report.issues.len(),
+            report.checked,
+            light_config.db_path
+        ));
+    }
+
+    status_info!(
+        "start",
+        "checked {} light blocks of database {:?}",
+        report.checked,
+        light_config.db_path
+    );
+    Ok(())
+}
@@ -46,6 +46,10 @@ pub struct LightClientConfig {
     pub peer_id: PeerId,
     /// The data base folder for this instance's store.
     pub db_path: PathBuf,
+    /// Whether every write to the store is flushed to disk before the light
+    /// client goes on, so that no verified state is lost on power loss.
+    #[serde(default)]
+    pub sync_writes: bool,
 }
 
 /// RpcConfig contains for the RPC server of the light node as
@@ -57,6 +61,14 @@ pub struct RpcConfig {
     pub listen_addr: SocketAddr,
     /// The duration after which any RPC request to tendermint node will time out.
     pub request_timeout: Duration,
+    /// The duration since the last successful verification after which the
+    /// light node is reported as unhealthy by the `/health` endpoint.
+    #[serde(default = "default_health_staleness")]
+    pub health_staleness: Duration,
+}
+
+fn default_health_staleness() -> Duration {
+    Duration::from_secs(30)
 }
 
 /// Default light client config settings.
@@ -68,6 +80,7 @@ impl Default for LightClientConfig {
             db_path: "./lightstore/BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE"
                 .parse()
                 .unwrap(),
+            sync_writes: false,
         }
     }
 }
@@ -85,6 +98,7 @@ impl Default for LightNodeConfig {
             rpc_config: RpcConfig {
                 listen_addr: "127.0.0.1:8888".parse().unwrap(),
                 request_timeout: Duration::from_secs(60),
+                health_staleness: default_health_staleness(),
             },
             // TODO(ismail): need at least 2 peers for a proper init
             // otherwise the light node will complain on `start` with `no witness left`
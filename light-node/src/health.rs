@@ -0,0 +1,122 @@
+//! Health of the light node, as reported by the `/health` HTTP endpoint.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tendermint_light_client::errors::{Error, ErrorKind};
+
+/// Path of the health endpoint.
+pub const HEALTH_PATH: &str = "/health";
+
+/// Tracks the outcome of the verifications performed by the light node, to
+/// tell whether it is healthy, ie. whether it verified a block recently and
+/// its primary is reachable.
+///
+/// Clones share the same state, so that the sync loop can record outcomes
+/// which the RPC server reports.
+#[derive(Clone, Debug)]
+pub struct Health {
+    state: Arc<Mutex<State>>,
+    staleness: Duration,
+}
+
+#[derive(Debug)]
+struct State {
+    started: Instant,
+    last_verified: Option<Instant>,
+    primary_reachable: bool,
+}
+
+impl Health {
+    /// Track the health of a light node which must have verified a block
+    /// within the given staleness window to be healthy.
+    pub fn new(staleness: Duration) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                started: Instant::now(),
+                last_verified: None,
+                primary_reachable: true,
+            })),
+            staleness,
+        }
+    }
+
+    /// Record a successful verification.
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.last_verified = Some(Instant::now());
+        state.primary_reachable = true;
+    }
+
+    /// Record a failed verification. The primary is deemed unreachable if
+    /// the verification failed because of an I/O error.
+    pub fn record_failure(&self, error: &Error) {
+        let mut state = self.state.lock().unwrap();
+        state.primary_reachable = !matches!(error.kind(), ErrorKind::Io(_));
+    }
+
+    /// Check whether the light node is healthy, returning the reason why it
+    /// isn't otherwise.
+    pub fn check(&self) -> Result<(), String> {
+        self.check_at(Instant::now())
+    }
+
+    fn check_at(&self, now: Instant) -> Result<(), String> {
+        let state = self.state.lock().unwrap();
+
+        if !state.primary_reachable {
+            return Err("primary is unreachable".to_string());
+        }
+
+        // Before the first verification, the node is given one staleness
+        // window to catch up.
+        let last_verified = state.last_verified.unwrap_or(state.started);
+        let elapsed = now.saturating_duration_since(last_verified);
+        if elapsed > self.staleness {
+            return Err(format!(
+                "last successful verification was {} s ago, more than the allowed {} s",
+                elapsed.as_secs(),
+                self.staleness.as_secs()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use tendermint_light_client::components::io::IoError;
+
+    #[test]
+    fn healthy_within_staleness_window() {
+        let health = Health::new(Duration::from_secs(10));
+        let now = Instant::now();
+        assert!(health.check_at(now).is_ok());
+        assert!(health.check_at(now + Duration::from_secs(11)).is_err());
+
+        health.record_success();
+        let now = Instant::now();
+        assert!(health.check_at(now + Duration::from_secs(5)).is_ok());
+        assert!(health.check_at(now + Duration::from_secs(11)).is_err());
+    }
+
+    #[test]
+    fn unhealthy_when_primary_unreachable() {
+        let health = Health::new(Duration::from_secs(10));
+        health.record_success();
+
+        let timeout = IoError::Timeout(Duration::from_secs(1));
+        health.record_failure(&ErrorKind::Io(timeout).into());
+        assert_eq!(health.check(), Err("primary is unreachable".to_string()));
+
+        // Other failures, eg. invalid light blocks, don't mean that the primary is unreachable
+        health.record_failure(&ErrorKind::NoWitnesses.into());
+        assert!(health.check().is_ok());
+
+        health.record_success();
+        assert!(health.check().is_ok());
+    }
+}
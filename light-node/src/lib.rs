@@ -23,5 +23,6 @@ pub mod application;
 pub mod commands;
 pub mod config;
 pub mod error;
+pub mod health;
 pub mod prelude;
 pub mod rpc;
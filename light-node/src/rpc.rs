@@ -1,23 +1,43 @@
 //! JSON-RPC Server and Client for the light-node RPC endpoint.
 use jsonrpc_core::IoHandler;
-use jsonrpc_http_server::{AccessControlAllowOrigin, DomainsValidation, ServerBuilder};
-
+use jsonrpc_http_server::hyper::header::HeaderValue;
+use jsonrpc_http_server::hyper::{Body, Method, Request, StatusCode};
+use jsonrpc_http_server::{
+    AccessControlAllowOrigin, DomainsValidation, RequestMiddlewareAction, Response, ServerBuilder,
+};
+use serde::Serialize;
+
+use tendermint::block::Height;
 use tendermint_light_client::supervisor::Handle;
 
 use crate::error;
+use crate::health::{Health, HEALTH_PATH};
 
 pub use sealed::{Client, Rpc, Server};
 
+/// Path of the REST endpoint returning the latest status of the light node.
+pub const STATUS_PATH: &str = "/status";
+
+/// Prefix of the path of the REST endpoint returning the verified header at
+/// the height which follows it, e.g. `/header/42`.
+pub const HEADER_PATH: &str = "/header/";
+
 /// Run the given [`Server`] on the given address and blocks until closed.
 ///
+/// Besides the JSON-RPC endpoint, the server answers `GET /health` requests
+/// with a `200 OK` status if the light node is healthy according to `health`,
+/// and with a `503 Service Unavailable` status otherwise. It also answers the
+/// `GET` requests of the REST endpoints (see [`rest`]).
+///
 /// n.b. The underlying server has semantics to close on drop. Also it does not offer any way
 /// to get the underlying Future to await, so we are left with this rather rudimentary way to
 /// control the lifecycle. Should we be interested in a more controlled way to close the server we
 /// can expose a handle in the future.
-pub fn run<H>(server: Server<H>, addr: &str) -> Result<(), error::Error>
+pub fn run<H>(server: Server<H>, addr: &str, health: Health) -> Result<(), error::Error>
 where
     H: Handle + Send + Sync + 'static,
 {
+    let handle = server.handle();
     let mut io = IoHandler::new();
     io.extend_with(server.to_delegate());
 
@@ -25,6 +45,7 @@ where
         .cors(DomainsValidation::AllowOnly(vec![
             AccessControlAllowOrigin::Any,
         ]))
+        .request_middleware(move |request: Request<Body>| route(&health, handle.as_ref(), request))
         .start_http(&addr.parse().map_err(error::Kind::from)?)
         .map_err(|e| error::Kind::Io.context(e))?;
 
@@ -33,12 +54,95 @@ where
     Ok(())
 }
 
+/// Answer the requests to the health and REST endpoints, and pass the others
+/// through.
+fn route<H>(health: &Health, handle: &H, request: Request<Body>) -> RequestMiddlewareAction
+where
+    H: Handle,
+{
+    if request.method() != Method::GET {
+        return request.into();
+    }
+
+    let response = match request.uri().path() {
+        HEALTH_PATH => Some(health_check(health)),
+        path => rest(handle, path),
+    };
+    match response {
+        Some(response) => response.into(),
+        None => request.into(),
+    }
+}
+
+fn health_check(health: &Health) -> Response {
+    match health.check() {
+        Ok(()) => Response::ok("OK\n"),
+        Err(reason) => Response::service_unavailable(format!("{}\n", reason)),
+    }
+}
+
+/// Answer a `GET` request to a REST endpoint of the light node, for clients
+/// which can't easily speak JSON-RPC, or return `None` if the path isn't one
+/// of theirs:
+///
+/// - `/status` returns the same status as the `status` JSON-RPC method;
+/// - `/header/:height` returns the header at the given height if the light
+///   node already trusted or verified it, and `404 Not Found` otherwise:
+///   requests don't make it verify other heights.
+pub fn rest<H>(handle: &H, path: &str) -> Option<Response>
+where
+    H: Handle,
+{
+    if path == STATUS_PATH {
+        return Some(match handle.latest_status() {
+            Ok(status) => json(&status),
+            Err(e) => text(StatusCode::INTERNAL_SERVER_ERROR, e),
+        });
+    }
+
+    let height = path.strip_prefix(HEADER_PATH)?;
+    let height = match height.parse::<Height>() {
+        Ok(height) if height.value() > 0 => height,
+        _ => {
+            return Some(text(
+                StatusCode::BAD_REQUEST,
+                format!("invalid height: {}", height),
+            ))
+        }
+    };
+    Some(match handle.verified_block(height) {
+        Ok(Some(light_block)) => json(&light_block.signed_header.header),
+        Ok(None) => text(
+            StatusCode::NOT_FOUND,
+            format!("no verified header at height {}", height),
+        ),
+        Err(e) => text(StatusCode::INTERNAL_SERVER_ERROR, e),
+    })
+}
+
+fn json(value: &impl Serialize) -> Response {
+    match serde_json::to_string_pretty(value) {
+        Ok(body) => Response::ok(body + "\n"),
+        Err(e) => text(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
+}
+
+fn text(code: StatusCode, message: impl ToString) -> Response {
+    Response {
+        code,
+        content_type: HeaderValue::from_static("text/plain; charset=utf-8"),
+        content: format!("{}\n", message.to_string()),
+    }
+}
+
 mod sealed {
     use jsonrpc_core::futures;
     use jsonrpc_core::types::Error;
     use jsonrpc_core::{BoxFuture, Result};
     use jsonrpc_derive::rpc;
 
+    use std::sync::Arc;
+
     use tendermint_light_client::supervisor::Handle;
     use tendermint_light_client::types::LatestStatus;
     use tendermint_light_client::types::LightBlock;
@@ -60,7 +164,7 @@ mod sealed {
     where
         H: Handle + Send + Sync,
     {
-        handle: H,
+        handle: Arc<H>,
     }
 
     impl<H> Server<H>
@@ -68,7 +172,14 @@ mod sealed {
         H: Handle + Send + Sync,
     {
         pub fn new(handle: H) -> Self {
-            Self { handle }
+            Self {
+                handle: Arc::new(handle),
+            }
+        }
+
+        /// The handle of the supervisor queried by the server.
+        pub(crate) fn handle(&self) -> Arc<H> {
+            self.handle.clone()
         }
     }
 
@@ -104,9 +215,12 @@ mod sealed {
 mod test {
     use jsonrpc_core::IoHandler;
     use jsonrpc_core_client::transports::local;
+    use jsonrpc_http_server::hyper::StatusCode;
     use pretty_assertions::assert_eq;
 
+    use tendermint::block::Header;
     use tendermint_light_client::errors::Error;
+    use tendermint_light_client::fork_detector::ForkDetection;
     use tendermint_light_client::supervisor::Handle;
     use tendermint_light_client::types::LatestStatus;
     use tendermint_light_client::types::LightBlock;
@@ -147,6 +261,30 @@ mod test {
         assert_eq!(have, want);
     }
 
+    #[test]
+    fn rest() {
+        let status = super::rest(&MockHandle {}, "/status").unwrap();
+        assert_eq!(status.code, StatusCode::OK);
+        let have: LatestStatus = serde_json::from_str(&status.content).unwrap();
+        let want: LatestStatus = serde_json::from_str(STATUS_JSON).unwrap();
+        assert_eq!(have, want);
+
+        let header = super::rest(&MockHandle {}, "/header/1").unwrap();
+        assert_eq!(header.code, StatusCode::OK);
+        let have: Header = serde_json::from_str(&header.content).unwrap();
+        let want: LightBlock = serde_json::from_str(LIGHTBLOCK_JSON).unwrap();
+        assert_eq!(have, want.signed_header.header);
+
+        let response = super::rest(&MockHandle {}, "/header/2").unwrap();
+        assert_eq!(response.code, StatusCode::NOT_FOUND);
+
+        for path in &["/header/0", "/header/one", "/header/"] {
+            let response = super::rest(&MockHandle {}, path).unwrap();
+            assert_eq!(response.code, StatusCode::BAD_REQUEST);
+        }
+        assert!(super::rest(&MockHandle {}, "/headers").is_none());
+    }
+
     struct MockHandle;
 
     impl Handle for MockHandle {
@@ -162,6 +300,15 @@ mod test {
             Ok(status)
         }
 
+        fn verified_block(
+            &self,
+            height: tendermint::block::Height,
+        ) -> Result<Option<LightBlock>, Error> {
+            let block: LightBlock = serde_json::from_str(LIGHTBLOCK_JSON).unwrap();
+
+            Ok(Some(block).filter(|block| block.height() == height))
+        }
+
         fn verify_to_highest(&self) -> Result<LightBlock, Error> {
             todo!()
         }
@@ -170,6 +317,15 @@ mod test {
             &self,
             _height: tendermint::block::Height,
         ) -> Result<LightBlock, Error> {
+            let block: LightBlock = serde_json::from_str(LIGHTBLOCK_JSON).unwrap();
+
+            Ok(block)
+        }
+
+        fn detect_forks_at(
+            &self,
+            _height: tendermint::block::Height,
+        ) -> Result<ForkDetection, Error> {
             todo!()
         }
 
@@ -0,0 +1,305 @@
+//! Channels multiplexed over a connection, and the scheduling of their
+//! outgoing messages.
+//!
+//! As in Tendermint's `MConnection`, each channel has a priority, and the
+//! next packet sent over a connection is taken from the channel with pending
+//! messages which recently sent the least data relative to its priority. A
+//! channel flooded with messages thus gets no more than its share of the
+//! connection, and cannot starve the others.
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use tendermint::channel::Channel;
+use tendermint_proto::p2p::PacketMsg;
+
+use crate::error::Error;
+
+/// Channel IDs
+pub type Id = u8;
+
+/// Default capacity of the send queue of a channel, as in Tendermint
+pub const DEFAULT_SEND_QUEUE_CAPACITY: usize = 1;
+
+/// Default maximum size of the data of a packet, as in Tendermint
+pub const DEFAULT_MAX_PACKET_PAYLOAD_SIZE: usize = 1024;
+
+/// Interval at which [`Scheduler::decay`] should be called, as in Tendermint
+pub const DECAY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Descriptors of the channels of Tendermint's reactors, with their
+/// priorities.
+pub mod reactors {
+    use super::Descriptor;
+
+    /// PEX (peer exchange) channel
+    pub const PEX: Descriptor = Descriptor::new(0x00, 1);
+
+    /// Consensus state channel
+    pub const CONSENSUS_STATE: Descriptor = Descriptor::new(0x20, 6).send_queue_capacity(100);
+
+    /// Consensus data (proposals and block parts) channel
+    pub const CONSENSUS_DATA: Descriptor = Descriptor::new(0x21, 10).send_queue_capacity(100);
+
+    /// Consensus votes channel
+    pub const CONSENSUS_VOTE: Descriptor = Descriptor::new(0x22, 7).send_queue_capacity(100);
+
+    /// Consensus vote set bits channel
+    pub const CONSENSUS_VOTE_SET_BITS: Descriptor = Descriptor::new(0x23, 1).send_queue_capacity(2);
+
+    /// Mempool channel
+    pub const MEMPOOL: Descriptor = Descriptor::new(0x30, 5);
+}
+
+/// Description of a channel registered on a connection
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Descriptor {
+    /// Channel ID
+    pub id: Id,
+
+    /// Priority of the channel, relative to the others: a channel with twice
+    /// the priority of another is allowed to send twice as much data.
+    pub priority: u32,
+
+    /// Maximum number of messages waiting to be sent on the channel
+    pub send_queue_capacity: usize,
+}
+
+impl Descriptor {
+    /// Describe a channel with the given ID and priority, and the default
+    /// send queue capacity.
+    pub const fn new(id: Id, priority: u32) -> Self {
+        Self {
+            id,
+            priority,
+            send_queue_capacity: DEFAULT_SEND_QUEUE_CAPACITY,
+        }
+    }
+
+    /// Set the capacity of the send queue of the channel.
+    pub const fn send_queue_capacity(mut self, send_queue_capacity: usize) -> Self {
+        self.send_queue_capacity = send_queue_capacity;
+        self
+    }
+}
+
+/// A channel's queue of messages to send
+#[derive(Debug)]
+struct SendQueue {
+    descriptor: Descriptor,
+    messages: VecDeque<Vec<u8>>,
+    // Number of bytes of the first message which were sent already
+    sent: usize,
+    // Number of bytes sent recently, decayed over time
+    recently_sent: u64,
+}
+
+impl SendQueue {
+    /// Whether this queue recently sent less data than `other`, relative to
+    /// their priorities.
+    fn is_behind(&self, other: &SendQueue) -> bool {
+        u128::from(self.recently_sent) * u128::from(other.descriptor.priority)
+            < u128::from(other.recently_sent) * u128::from(self.descriptor.priority)
+    }
+}
+
+/// Schedules the messages sent over a connection on its channels.
+///
+/// Messages are queued with [`Scheduler::try_send`], then split into the
+/// packets to send with [`Scheduler::next_packet`].
+#[derive(Debug)]
+pub struct Scheduler {
+    queues: Vec<SendQueue>,
+    max_packet_payload_size: usize,
+}
+
+impl Scheduler {
+    /// Register the given channels. Ties between channels which recently
+    /// sent as much data relative to their priorities are broken in favor of
+    /// the channel registered first.
+    ///
+    /// Fails if two channels have the same ID, or if a channel has a zero
+    /// priority or send queue capacity.
+    pub fn new(descriptors: impl IntoIterator<Item = Descriptor>) -> Result<Self, Error> {
+        let mut queues: Vec<SendQueue> = Vec::new();
+        for descriptor in descriptors {
+            if descriptor.priority == 0
+                || descriptor.send_queue_capacity == 0
+                || queues.iter().any(|q| q.descriptor.id == descriptor.id)
+            {
+                return Err(Error::InvalidChannelDescriptor);
+            }
+            queues.push(SendQueue {
+                descriptor,
+                messages: VecDeque::with_capacity(descriptor.send_queue_capacity),
+                sent: 0,
+                recently_sent: 0,
+            });
+        }
+        Ok(Self {
+            queues,
+            max_packet_payload_size: DEFAULT_MAX_PACKET_PAYLOAD_SIZE,
+        })
+    }
+
+    /// Set the maximum size of the data of a packet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is zero.
+    pub fn max_packet_payload_size(mut self, max_packet_payload_size: usize) -> Self {
+        assert!(max_packet_payload_size > 0, "zero packet payload size");
+        self.max_packet_payload_size = max_packet_payload_size;
+        self
+    }
+
+    /// Queue a message to send on the given channel.
+    ///
+    /// Fails, dropping the message, if the channel isn't registered or its
+    /// send queue is full.
+    pub fn try_send(&mut self, channel_id: Id, message: Vec<u8>) -> Result<(), Error> {
+        let queue = self
+            .queues
+            .iter_mut()
+            .find(|q| q.descriptor.id == channel_id)
+            .ok_or(Error::UnknownChannel)?;
+        if queue.messages.len() >= queue.descriptor.send_queue_capacity {
+            return Err(Error::SendQueueFull);
+        }
+        queue.messages.push_back(message);
+        Ok(())
+    }
+
+    /// Whether messages are waiting to be sent.
+    pub fn is_pending(&self) -> bool {
+        self.queues.iter().any(|q| !q.messages.is_empty())
+    }
+
+    /// Take the next packet to send, from the channel with pending messages
+    /// which recently sent the least data relative to its priority, or
+    /// `None` if no message is waiting to be sent.
+    ///
+    /// Messages are split into packets of at most the maximum payload size,
+    /// the last of which is marked with `eof`.
+    pub fn next_packet(&mut self) -> Option<PacketMsg> {
+        let queue = self
+            .queues
+            .iter_mut()
+            .filter(|q| !q.messages.is_empty())
+            .fold(None, |least: Option<&mut SendQueue>, q| match least {
+                Some(least) if !q.is_behind(least) => Some(least),
+                _ => Some(q),
+            })?;
+
+        let message = queue.messages.front()?;
+        let end = message.len().min(queue.sent + self.max_packet_payload_size);
+        let data = message[queue.sent..end].to_vec();
+        let eof = end == message.len();
+        if eof {
+            queue.messages.pop_front();
+            queue.sent = 0;
+        } else {
+            queue.sent = end;
+        }
+        queue.recently_sent = queue.recently_sent.saturating_add(data.len() as u64);
+
+        Some(PacketMsg {
+            channel_id: i32::from(queue.descriptor.id),
+            eof,
+            data,
+        })
+    }
+
+    /// Decay the amount of data recently sent by each channel, so that only
+    /// recent activity weighs on the scheduling. To be called every
+    /// [`DECAY_INTERVAL`].
+    pub fn decay(&mut self) {
+        for queue in &mut self.queues {
+            queue.recently_sent = (u128::from(queue.recently_sent) * 4 / 5) as u64;
+        }
+    }
+
+    /// The status of each channel, as reported by Tendermint's `/net_info`
+    /// RPC endpoint.
+    pub fn status(&self) -> Vec<Channel> {
+        self.queues
+            .iter()
+            .map(|q| Channel {
+                id: u64::from(q.descriptor.id).into(),
+                send_queue_capacity: u64::try_from(q.descriptor.send_queue_capacity)
+                    .unwrap_or(u64::MAX),
+                send_queue_size: u64::try_from(q.messages.len()).unwrap_or(u64::MAX),
+                priority: u64::from(q.descriptor.priority),
+                recently_sent: q.recently_sent,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channels(packets: &[PacketMsg]) -> Vec<i32> {
+        packets.iter().map(|p| p.channel_id).collect()
+    }
+
+    #[test]
+    fn registration() {
+        let channel = Descriptor::new(1, 1);
+        assert!(Scheduler::new(vec![channel, channel]).is_err());
+        assert!(Scheduler::new(vec![Descriptor::new(1, 0)]).is_err());
+        assert!(Scheduler::new(vec![channel.send_queue_capacity(0)]).is_err());
+
+        let mut scheduler = Scheduler::new(vec![channel]).unwrap();
+        assert_eq!(scheduler.try_send(2, vec![1]), Err(Error::UnknownChannel));
+        scheduler.try_send(1, vec![1]).unwrap();
+        assert_eq!(scheduler.try_send(1, vec![2]), Err(Error::SendQueueFull));
+        assert_eq!(scheduler.status()[0].send_queue_size, 1);
+    }
+
+    #[test]
+    fn splits_messages_into_packets() {
+        let mut scheduler = Scheduler::new(vec![Descriptor::new(1, 1)])
+            .unwrap()
+            .max_packet_payload_size(2);
+        assert_eq!(scheduler.next_packet(), None);
+
+        scheduler.try_send(1, vec![1, 2, 3]).unwrap();
+        let first = scheduler.next_packet().unwrap();
+        assert_eq!((first.data, first.eof), (vec![1, 2], false));
+        let last = scheduler.next_packet().unwrap();
+        assert_eq!((last.data, last.eof), (vec![3], true));
+        assert!(!scheduler.is_pending());
+        assert_eq!(scheduler.status()[0].recently_sent, 3);
+    }
+
+    #[test]
+    fn shares_the_connection_by_priority() {
+        let high = Descriptor::new(1, 3).send_queue_capacity(100);
+        let low = Descriptor::new(2, 1).send_queue_capacity(100);
+        let mut scheduler = Scheduler::new(vec![low, high])
+            .unwrap()
+            .max_packet_payload_size(10);
+        for _ in 0..100 {
+            scheduler.try_send(low.id, vec![0; 10]).unwrap();
+            scheduler.try_send(high.id, vec![0; 10]).unwrap();
+        }
+
+        // The low priority channel is flooded, but gets a quarter of the
+        // packets, while the high priority one gets the rest.
+        let packets: Vec<_> = (0..8).filter_map(|_| scheduler.next_packet()).collect();
+        assert_eq!(channels(&packets), vec![2, 1, 1, 1, 2, 1, 1, 1]);
+
+        // A channel left idle doesn't get to catch up forever after its
+        // activity decayed.
+        let mut scheduler = Scheduler::new(vec![low, high]).unwrap();
+        scheduler.try_send(low.id, vec![0; 100]).unwrap();
+        scheduler.next_packet().unwrap();
+        for _ in 0..20 {
+            scheduler.decay();
+        }
+        assert_eq!(scheduler.status()[0].recently_sent, 0);
+    }
+}
@@ -1,7 +1,10 @@
 //! Error types
 
+use tendermint::node;
 use thiserror::Error;
 
+use crate::peer;
+
 /// Kinds of errors
 #[derive(Copy, Clone, Debug, Error, Eq, PartialEq)]
 pub enum Error {
@@ -16,4 +19,35 @@ pub enum Error {
     /// Network protocol-related errors
     #[error("protocol error")]
     ProtocolError,
+
+    /// Channel with a duplicate ID, or a zero priority or queue capacity
+    #[error("invalid channel descriptor")]
+    InvalidChannelDescriptor,
+
+    /// Message sent on a channel which isn't registered
+    #[error("unknown channel")]
+    UnknownChannel,
+
+    /// Message sent on a channel whose send queue is full
+    #[error("send queue full")]
+    SendQueueFull,
+
+    /// Connection submitted to an upgrade pool whose queue is full
+    #[error("upgrade queue full")]
+    UpgradeQueueFull,
+
+    /// Transition of a peer which isn't known
+    #[error("unknown peer")]
+    UnknownPeer,
+
+    /// Transition of a peer to a state it can't move to from its current one
+    #[error("peer {peer} cannot move from {from} to {to}")]
+    InvalidPeerTransition {
+        /// ID of the peer
+        peer: node::Id,
+        /// Current state of the peer
+        from: peer::State,
+        /// Requested state of the peer
+        to: peer::State,
+    },
 }
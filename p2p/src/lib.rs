@@ -14,5 +14,9 @@
     html_logo_url = "https://raw.githubusercontent.com/informalsystems/tendermint-rs/master/img/logo-tendermint-rs_3961x4001.png"
 )]
 
+pub mod channel;
 pub mod error;
+pub mod peer;
 pub mod secret_connection;
+pub mod transport;
+pub mod upgrade;
@@ -0,0 +1,244 @@
+//! Lifecycle of the peers of a node.
+//!
+//! Each peer goes through the states of [`State`], in order: its connection
+//! is established, then upgraded (e.g. by a `SecretConnection` handshake),
+//! after which the peer runs until it is stopped. [`Peers`] holds the state
+//! of every peer in one place, and reports each transition, including the
+//! invalid ones it rejects, as an [`Event`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::vec;
+
+use tendermint::node;
+
+use crate::error::Error;
+
+/// State of a peer
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum State {
+    /// The connection to the peer is established
+    Connected,
+
+    /// The connection is being upgraded
+    Upgrading,
+
+    /// The peer is running
+    Running,
+
+    /// The peer is being stopped
+    Stopping,
+
+    /// The peer is stopped
+    Stopped,
+}
+
+impl State {
+    /// Whether a peer in this state can move to the given state.
+    ///
+    /// Peers move through the states in order, except that they can be
+    /// stopped from any state until they are.
+    pub fn can_transition_to(self, next: State) -> bool {
+        matches!(
+            (self, next),
+            (State::Connected, State::Upgrading)
+                | (State::Upgrading, State::Running)
+                | (State::Connected, State::Stopping)
+                | (State::Upgrading, State::Stopping)
+                | (State::Running, State::Stopping)
+                | (State::Stopping, State::Stopped)
+        )
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            State::Connected => "connected",
+            State::Upgrading => "upgrading",
+            State::Running => "running",
+            State::Stopping => "stopping",
+            State::Stopped => "stopped",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Change of the state of a peer
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A peer moved to a new state.
+    Transitioned {
+        /// ID of the peer
+        peer: node::Id,
+        /// Previous state of the peer, `None` if it wasn't known before
+        from: Option<State>,
+        /// New state of the peer
+        to: State,
+    },
+
+    /// A transition of a peer was rejected.
+    Rejected {
+        /// ID of the peer
+        peer: node::Id,
+        /// Why the transition was rejected
+        error: Error,
+    },
+}
+
+/// The state of each peer of a node
+#[derive(Debug, Default)]
+pub struct Peers {
+    states: HashMap<node::Id, State>,
+    events: Vec<Event>,
+}
+
+impl Peers {
+    /// No peers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a connection to the given peer was established.
+    ///
+    /// Fails if the peer is known and not stopped yet.
+    pub fn connect(&mut self, peer: node::Id) -> Result<(), Error> {
+        let from = self.states.get(&peer).copied();
+        match from {
+            Some(from) if from != State::Stopped => {
+                return self.reject(
+                    peer,
+                    Error::InvalidPeerTransition {
+                        peer,
+                        from,
+                        to: State::Connected,
+                    },
+                )
+            }
+            _ => {}
+        }
+
+        self.states.insert(peer, State::Connected);
+        self.events.push(Event::Transitioned {
+            peer,
+            from,
+            to: State::Connected,
+        });
+        Ok(())
+    }
+
+    /// Move the given peer to the given state, returning its previous state.
+    ///
+    /// Fails if the peer is unknown or can't move to that state, in which
+    /// case its state is left unchanged.
+    pub fn transition(&mut self, peer: node::Id, to: State) -> Result<State, Error> {
+        let from = match self.states.get(&peer) {
+            Some(from) => *from,
+            None => return self.reject(peer, Error::UnknownPeer),
+        };
+        if !from.can_transition_to(to) {
+            return self.reject(peer, Error::InvalidPeerTransition { peer, from, to });
+        }
+
+        self.states.insert(peer, to);
+        self.events.push(Event::Transitioned {
+            peer,
+            from: Some(from),
+            to,
+        });
+        Ok(from)
+    }
+
+    /// The state of the given peer, if known.
+    pub fn state(&self, peer: &node::Id) -> Option<State> {
+        self.states.get(peer).copied()
+    }
+
+    /// The IDs of the peers in the given state.
+    pub fn in_state(&self, state: State) -> impl Iterator<Item = node::Id> + '_ {
+        self.states
+            .iter()
+            .filter(move |(_, s)| **s == state)
+            .map(|(peer, _)| *peer)
+    }
+
+    /// Forget the peers which are stopped.
+    pub fn remove_stopped(&mut self) {
+        self.states.retain(|_, state| *state != State::Stopped);
+    }
+
+    /// Take the events which occurred since the last call, in order.
+    pub fn events(&mut self) -> vec::Drain<'_, Event> {
+        self.events.drain(..)
+    }
+
+    fn reject<T>(&mut self, peer: node::Id, error: Error) -> Result<T, Error> {
+        self.events.push(Event::Rejected { peer, error });
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lifecycle() {
+        let peer = node::Id::new([1; 20]);
+        let mut peers = Peers::new();
+        assert_eq!(
+            peers.transition(peer, State::Running),
+            Err(Error::UnknownPeer)
+        );
+
+        peers.connect(peer).unwrap();
+        assert_eq!(
+            peers.transition(peer, State::Upgrading),
+            Ok(State::Connected)
+        );
+        assert_eq!(
+            peers.transition(peer, State::Stopped),
+            Err(Error::InvalidPeerTransition {
+                peer,
+                from: State::Upgrading,
+                to: State::Stopped,
+            })
+        );
+        assert!(peers.connect(peer).is_err());
+        assert_eq!(peers.state(&peer), Some(State::Upgrading));
+
+        peers.transition(peer, State::Running).unwrap();
+        assert_eq!(
+            peers.in_state(State::Running).collect::<Vec<_>>(),
+            vec![peer]
+        );
+        peers.transition(peer, State::Stopping).unwrap();
+        peers.transition(peer, State::Stopped).unwrap();
+
+        let events: Vec<_> = peers.events().collect();
+        assert_eq!(events.len(), 8);
+        assert_eq!(
+            events[0],
+            Event::Rejected {
+                peer,
+                error: Error::UnknownPeer
+            }
+        );
+        assert_eq!(
+            events[7],
+            Event::Transitioned {
+                peer,
+                from: Some(State::Stopping),
+                to: State::Stopped,
+            }
+        );
+        assert_eq!(peers.events().count(), 0);
+
+        // A stopped peer can reconnect
+        peers.connect(peer).unwrap();
+        peers.transition(peer, State::Stopping).unwrap();
+        peers.transition(peer, State::Stopped).unwrap();
+        peers.remove_stopped();
+        assert_eq!(peers.state(&peer), None);
+    }
+}
@@ -17,16 +17,21 @@ use eyre::{eyre, Result, WrapErr};
 use merlin::Transcript;
 use rand_core::OsRng;
 use subtle::ConstantTimeEq;
-use x25519_dalek::{EphemeralSecret, PublicKey as EphemeralPublic};
+use x25519_dalek::{PublicKey as EphemeralPublic, StaticSecret};
 
+use tendermint::secret::Secret;
 use tendermint_proto as proto;
 
-pub use self::{kdf::Kdf, nonce::Nonce, protocol::Version, public_key::PublicKey};
+pub use self::{
+    async_connection::AsyncSecretConnection, kdf::Kdf, nonce::Nonce, protocol::Version,
+    public_key::PublicKey,
+};
 use crate::error::Error;
 
 #[cfg(feature = "amino")]
 mod amino_types;
 
+mod async_connection;
 mod kdf;
 mod nonce;
 mod protocol;
@@ -45,6 +50,9 @@ pub const DATA_MAX_SIZE: usize = 1024;
 const DATA_LEN_SIZE: usize = 4;
 const TOTAL_FRAME_SIZE: usize = DATA_MAX_SIZE + DATA_LEN_SIZE;
 
+/// Size of an encrypted frame, including its MAC tag
+const SEALED_FRAME_SIZE: usize = TAG_SIZE + TOTAL_FRAME_SIZE;
+
 /// Handshake is a process of establishing the SecretConnection between two peers.
 /// Specification: https://github.com/tendermint/spec/blob/master/spec/p2p/peer.md#authenticated-encryption-handshake
 struct Handshake<S> {
@@ -57,7 +65,7 @@ struct Handshake<S> {
 /// AwaitingEphKey means we're waiting for the remote ephemeral pubkey.
 struct AwaitingEphKey {
     local_privkey: ed25519::Keypair,
-    local_eph_privkey: Option<EphemeralSecret>,
+    local_eph_privkey: Option<Secret<StaticSecret>>,
 }
 
 /// AwaitingAuthSig means we're waiting for the remote authenticated signature.
@@ -75,9 +83,10 @@ impl Handshake<AwaitingEphKey> {
         local_privkey: ed25519::Keypair,
         protocol_version: Version,
     ) -> (Self, EphemeralPublic) {
-        // Generate an ephemeral key for perfect forward secrecy.
-        let local_eph_privkey = EphemeralSecret::new(&mut OsRng);
-        let local_eph_pubkey = EphemeralPublic::from(&local_eph_privkey);
+        // Generate an ephemeral key for perfect forward secrecy. It is only
+        // used for a single key exchange, after which it is zeroized.
+        let local_eph_privkey = Secret::new(StaticSecret::new(&mut OsRng));
+        let local_eph_pubkey = EphemeralPublic::from(local_eph_privkey.expose_secret());
 
         (
             Handshake {
@@ -101,10 +110,12 @@ impl Handshake<AwaitingEphKey> {
             Some(key) => key,
             None => return Err(eyre!("forgot to call Handshake::new?")),
         };
-        let local_eph_pubkey = EphemeralPublic::from(&local_eph_privkey);
+        let local_eph_pubkey = EphemeralPublic::from(local_eph_privkey.expose_secret());
 
         // Compute common shared secret.
-        let shared_secret = EphemeralSecret::diffie_hellman(local_eph_privkey, &remote_eph_pubkey);
+        let shared_secret = local_eph_privkey
+            .expose_secret()
+            .diffie_hellman(&remote_eph_pubkey);
 
         let mut transcript = Transcript::new(b"TENDERMINT_SECRET_CONNECTION_TRANSCRIPT_HASH");
 
@@ -142,15 +153,15 @@ impl Handshake<AwaitingEphKey> {
         let local_signature = if self.protocol_version.has_transcript() {
             sign_challenge(&sc_mac, &self.state.local_privkey)?
         } else {
-            sign_challenge(&kdf.challenge, &self.state.local_privkey)?
+            sign_challenge(kdf.challenge.expose_secret(), &self.state.local_privkey)?
         };
 
         Ok(Handshake {
             protocol_version: self.protocol_version,
             state: AwaitingAuthSig {
                 sc_mac,
-                recv_cipher: ChaCha20Poly1305::new(&kdf.recv_secret.into()),
-                send_cipher: ChaCha20Poly1305::new(&kdf.send_secret.into()),
+                recv_cipher: ChaCha20Poly1305::new(&(*kdf.recv_secret.expose_secret()).into()),
+                send_cipher: ChaCha20Poly1305::new(&(*kdf.send_secret.expose_secret()).into()),
                 kdf,
                 local_signature,
             },
@@ -167,7 +178,7 @@ impl Handshake<AwaitingAuthSig> {
                 proto::crypto::public_key::Sum::Ed25519(ref bytes) => {
                     ed25519::PublicKey::from_bytes(bytes).ok()
                 }
-                proto::crypto::public_key::Sum::Secp256k1(_) => None,
+                _ => None,
             })
             .ok_or(Error::CryptoError)?;
 
@@ -180,7 +191,7 @@ impl Handshake<AwaitingAuthSig> {
                 .map_err(|_| Error::CryptoError)?;
         } else {
             remote_pubkey
-                .verify(&self.state.kdf.challenge, &remote_sig)
+                .verify(self.state.kdf.challenge.expose_secret(), &remote_sig)
                 .map_err(|_| Error::CryptoError)?;
         }
 
@@ -193,10 +204,7 @@ impl Handshake<AwaitingAuthSig> {
 pub struct SecretConnection<IoHandler: Read + Write + Send + Sync> {
     io_handler: IoHandler,
     protocol_version: Version,
-    recv_nonce: Nonce,
-    send_nonce: Nonce,
-    recv_cipher: ChaCha20Poly1305,
-    send_cipher: ChaCha20Poly1305,
+    codec: Codec,
     remote_pubkey: Option<PublicKey>,
     recv_buffer: Vec<u8>,
 }
@@ -207,20 +215,66 @@ impl<IoHandler: Read + Write + Send + Sync> SecretConnection<IoHandler> {
         self.remote_pubkey.expect("remote_pubkey uninitialized")
     }
 
+    /// Returns the protocol version used by this connection.
+    pub fn protocol_version(&self) -> Version {
+        self.protocol_version
+    }
+
     /// Performs a handshake and returns a new SecretConnection.
     pub fn new(
         mut io_handler: IoHandler,
         local_privkey: ed25519::Keypair,
         protocol_version: Version,
     ) -> Result<SecretConnection<IoHandler>> {
+        check_supported(protocol_version)?;
+
         // Start a handshake process.
-        let local_pubkey = PublicKey::from(&local_privkey);
-        let (mut h, local_eph_pubkey) = Handshake::new(local_privkey, protocol_version);
+        let (h, local_eph_pubkey) = Handshake::new(local_privkey, protocol_version);
 
         // Write local ephemeral pubkey and receive one too.
         let remote_eph_pubkey =
             share_eph_pubkey(&mut io_handler, &local_eph_pubkey, protocol_version)?;
 
+        Self::authenticate(io_handler, h, remote_eph_pubkey)
+    }
+
+    /// Performs a handshake using whichever of the `supported_versions` the
+    /// peer is using (see [`Version::negotiate`]), and returns a new
+    /// SecretConnection.
+    ///
+    /// The protocol version is detected from the peer's initial handshake
+    /// message, which is awaited before sending ours. This works with peers
+    /// which send their ephemeral public key straight away (as Go Tendermint
+    /// and [`SecretConnection::new`] do), but not with another negotiating
+    /// peer.
+    pub fn new_negotiated(
+        mut io_handler: IoHandler,
+        local_privkey: ed25519::Keypair,
+        supported_versions: &[Version],
+    ) -> Result<SecretConnection<IoHandler>> {
+        let remote_initial_handshake = read_initial_handshake(&mut io_handler)?;
+        let protocol_version = Version::negotiate(&remote_initial_handshake, supported_versions)?;
+        let remote_eph_pubkey =
+            protocol_version.decode_initial_handshake(&remote_initial_handshake)?;
+
+        // Start a handshake process.
+        let (h, local_eph_pubkey) = Handshake::new(local_privkey, protocol_version);
+        io_handler.write_all(&protocol_version.encode_initial_handshake(&local_eph_pubkey))?;
+        io_handler.flush()?;
+
+        Self::authenticate(io_handler, h, remote_eph_pubkey)
+    }
+
+    /// Completes the handshake once the ephemeral public keys have been
+    /// exchanged.
+    fn authenticate(
+        io_handler: IoHandler,
+        mut h: Handshake<AwaitingEphKey>,
+        remote_eph_pubkey: EphemeralPublic,
+    ) -> Result<SecretConnection<IoHandler>> {
+        let protocol_version = h.protocol_version;
+        let local_pubkey = PublicKey::from(&h.state.local_privkey);
+
         // Compute a local signature (also recv_cipher & send_cipher)
         let mut h = h.got_key(remote_eph_pubkey)?;
 
@@ -228,10 +282,7 @@ impl<IoHandler: Read + Write + Send + Sync> SecretConnection<IoHandler> {
             io_handler,
             protocol_version,
             recv_buffer: vec![],
-            recv_nonce: Nonce::default(),
-            send_nonce: Nonce::default(),
-            recv_cipher: h.state.recv_cipher.clone(),
-            send_cipher: h.state.send_cipher.clone(),
+            codec: Codec::new(&h.state),
             remote_pubkey: None,
         };
 
@@ -250,13 +301,97 @@ impl<IoHandler: Read + Write + Send + Sync> SecretConnection<IoHandler> {
         sc.remote_pubkey = Some(remote_pubkey);
         Ok(sc)
     }
+}
+
+impl<IoHandler> Read for SecretConnection<IoHandler>
+where
+    IoHandler: Read + Write + Send + Sync,
+{
+    // CONTRACT: data smaller than DATA_MAX_SIZE is read atomically.
+    fn read(&mut self, data: &mut [u8]) -> io::Result<usize> {
+        if !self.recv_buffer.is_empty() {
+            return Ok(read_buffered(&mut self.recv_buffer, data));
+        }
+
+        let mut sealed_frame = [0u8; SEALED_FRAME_SIZE];
+        self.io_handler.read_exact(&mut sealed_frame)?;
+
+        let mut chunk = self.codec.open(&sealed_frame).map_err(io_error)?;
+        let n = read_buffered(&mut chunk, data);
+        self.recv_buffer = chunk;
+
+        Ok(n)
+    }
+}
+
+impl<IoHandler> Write for SecretConnection<IoHandler>
+where
+    IoHandler: Read + Write + Send + Sync,
+{
+    // Writes encrypted frames of `TAG_SIZE` + `TOTAL_FRAME_SIZE`
+    // CONTRACT: data smaller than DATA_MAX_SIZE is read atomically.
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        for chunk in data.chunks(DATA_MAX_SIZE) {
+            let sealed_frame = self.codec.seal(chunk).map_err(io_error)?;
+            self.io_handler.write_all(&sealed_frame[..])?;
+        }
+
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.io_handler.flush()
+    }
+}
+
+/// Encryption state of an established connection, shared by
+/// [`SecretConnection`] and [`AsyncSecretConnection`]: seals outgoing frames
+/// and opens incoming ones.
+struct Codec {
+    recv_nonce: Nonce,
+    send_nonce: Nonce,
+    recv_cipher: ChaCha20Poly1305,
+    send_cipher: ChaCha20Poly1305,
+}
+
+impl Codec {
+    fn new(state: &AwaitingAuthSig) -> Self {
+        Codec {
+            recv_nonce: Nonce::default(),
+            send_nonce: Nonce::default(),
+            recv_cipher: state.recv_cipher.clone(),
+            send_cipher: state.send_cipher.clone(),
+        }
+    }
+
+    /// Encrypt a chunk of at most `DATA_MAX_SIZE` bytes into a sealed frame
+    fn seal(&mut self, chunk: &[u8]) -> Result<[u8; SEALED_FRAME_SIZE]> {
+        let mut sealed_frame = [0u8; SEALED_FRAME_SIZE];
+        self.encrypt(chunk, &mut sealed_frame)?;
+        self.send_nonce.increment();
+        Ok(sealed_frame)
+    }
+
+    /// Decrypt a sealed frame, returning the chunk of data it contains
+    fn open(&mut self, sealed_frame: &[u8; SEALED_FRAME_SIZE]) -> Result<Vec<u8>> {
+        let mut frame = [0u8; TOTAL_FRAME_SIZE];
+        self.decrypt(sealed_frame, &mut frame)?;
+        self.recv_nonce.increment();
+
+        let chunk_length = u32::from_le_bytes(frame[..DATA_LEN_SIZE].try_into().unwrap());
+        if chunk_length as usize > DATA_MAX_SIZE {
+            return Err(eyre!(
+                "chunk is too big: {}! max: {}",
+                chunk_length,
+                DATA_MAX_SIZE
+            ));
+        }
+
+        Ok(frame[DATA_LEN_SIZE..DATA_LEN_SIZE + chunk_length as usize].to_vec())
+    }
 
     /// Encrypt AEAD authenticated data
-    fn encrypt(
-        &self,
-        chunk: &[u8],
-        sealed_frame: &mut [u8; TAG_SIZE + TOTAL_FRAME_SIZE],
-    ) -> Result<()> {
+    fn encrypt(&self, chunk: &[u8], sealed_frame: &mut [u8; SEALED_FRAME_SIZE]) -> Result<()> {
         debug_assert!(!chunk.is_empty(), "chunk is empty");
         debug_assert!(
             chunk.len() <= TOTAL_FRAME_SIZE - DATA_LEN_SIZE,
@@ -315,100 +450,17 @@ impl<IoHandler: Read + Write + Send + Sync> SecretConnection<IoHandler> {
     }
 }
 
-impl<IoHandler> Read for SecretConnection<IoHandler>
-where
-    IoHandler: Read + Write + Send + Sync,
-{
-    // CONTRACT: data smaller than DATA_MAX_SIZE is read atomically.
-    fn read(&mut self, data: &mut [u8]) -> io::Result<usize> {
-        if !self.recv_buffer.is_empty() {
-            let n = cmp::min(data.len(), self.recv_buffer.len());
-            data.copy_from_slice(&self.recv_buffer[..n]);
-            let mut leftover_portion = vec![0; self.recv_buffer.len().checked_sub(n).unwrap()];
-            leftover_portion.clone_from_slice(&self.recv_buffer[n..]);
-            self.recv_buffer = leftover_portion;
-
-            return Ok(n);
-        }
-
-        let mut sealed_frame = [0u8; TAG_SIZE + TOTAL_FRAME_SIZE];
-        self.io_handler.read_exact(&mut sealed_frame)?;
-
-        // decrypt the frame
-        let mut frame = [0u8; TOTAL_FRAME_SIZE];
-        let res = self.decrypt(&sealed_frame, &mut frame);
-
-        if res.is_err() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                res.err().unwrap().to_string(),
-            ));
-        }
-
-        self.recv_nonce.increment();
-        // end decryption
-
-        let chunk_length = u32::from_le_bytes(frame[..4].try_into().unwrap());
-
-        if chunk_length as usize > DATA_MAX_SIZE {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                format!("chunk is too big: {}! max: {}", chunk_length, DATA_MAX_SIZE),
-            ));
-        }
-
-        let mut chunk = vec![0; chunk_length as usize];
-        chunk.clone_from_slice(
-            &frame[DATA_LEN_SIZE..(DATA_LEN_SIZE.checked_add(chunk_length as usize).unwrap())],
-        );
-
-        let n = cmp::min(data.len(), chunk.len());
-        data[..n].copy_from_slice(&chunk[..n]);
-        self.recv_buffer.copy_from_slice(&chunk[n..]);
-
-        Ok(n)
-    }
+/// Move as much decrypted data as fits from `buffer` into `data`, returning
+/// the number of bytes moved
+fn read_buffered(buffer: &mut Vec<u8>, data: &mut [u8]) -> usize {
+    let n = cmp::min(data.len(), buffer.len());
+    data[..n].copy_from_slice(&buffer[..n]);
+    buffer.drain(..n);
+    n
 }
 
-impl<IoHandler> Write for SecretConnection<IoHandler>
-where
-    IoHandler: Read + Write + Send + Sync,
-{
-    // Writes encrypted frames of `TAG_SIZE` + `TOTAL_FRAME_SIZE`
-    // CONTRACT: data smaller than DATA_MAX_SIZE is read atomically.
-    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
-        let mut n = 0usize;
-        let mut data_copy = data;
-        while !data_copy.is_empty() {
-            let chunk: &[u8];
-            if DATA_MAX_SIZE < data.len() {
-                chunk = &data[..DATA_MAX_SIZE];
-                data_copy = &data_copy[DATA_MAX_SIZE..];
-            } else {
-                chunk = data_copy;
-                data_copy = &[0u8; 0];
-            }
-            let sealed_frame = &mut [0u8; TAG_SIZE + TOTAL_FRAME_SIZE];
-            let res = self.encrypt(chunk, sealed_frame);
-            if res.is_err() {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    res.err().unwrap().to_string(),
-                ));
-            }
-            self.send_nonce.increment();
-            // end encryption
-
-            self.io_handler.write_all(&sealed_frame[..])?;
-            n = n.checked_add(chunk.len()).unwrap();
-        }
-
-        Ok(n)
-    }
-
-    fn flush(&mut self) -> io::Result<()> {
-        self.io_handler.flush()
-    }
+fn io_error(e: eyre::Report) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
 }
 
 /// Returns remote_eph_pubkey
@@ -423,12 +475,33 @@ fn share_eph_pubkey<IoHandler: Read + Write + Send + Sync>(
     // Should still work though.
     handler.write_all(&protocol_version.encode_initial_handshake(&local_eph_pubkey))?;
 
+    let buf = read_initial_handshake(handler)?;
+    protocol_version.decode_initial_handshake(&buf)
+}
+
+/// Returns the (length-prefixed) initial handshake message sent by the peer,
+/// without its length prefix
+fn read_initial_handshake<IoHandler: Read>(handler: &mut IoHandler) -> Result<Vec<u8>> {
     let mut response_len = 0u8;
     handler.read_exact(slice::from_mut(&mut response_len))?;
 
     let mut buf = vec![0; response_len as usize];
     handler.read_exact(&mut buf)?;
-    protocol_version.decode_initial_handshake(&buf)
+    Ok(buf)
+}
+
+/// Returns an error if the given protocol version isn't supported
+fn check_supported(protocol_version: Version) -> Result<()> {
+    if protocol_version.is_supported() {
+        Ok(())
+    } else {
+        Err(Error::ProtocolError).wrap_err_with(|| {
+            format!(
+                "protocol version {:?} requires the 'amino' feature",
+                protocol_version
+            )
+        })
+    }
 }
 
 /// Return is of the form lo, hi
@@ -597,4 +670,41 @@ mod test {
         });
         assert_eq!(res.is_err(), true);
     }
+
+    #[test]
+    fn test_negotiated_handshake() {
+        let (pipe1, pipe2) = pipe::async_bipipe_buffered();
+
+        let peer1 = thread::spawn(|| {
+            let privkey1 = ed25519::Keypair::generate(&mut OsRng {});
+            let conn1 = SecretConnection::new(pipe2, privkey1, Version::V0_34)
+                .expect("handshake to succeed");
+            conn1.remote_pubkey()
+        });
+
+        let peer2 = thread::spawn(|| {
+            let privkey2 = ed25519::Keypair::generate(&mut OsRng {});
+            let public2 = PublicKey::from(&privkey2);
+            let conn2 = SecretConnection::new_negotiated(
+                pipe1,
+                privkey2,
+                &[Version::V0_33, Version::V0_34],
+            )
+            .expect("handshake to succeed");
+            assert_eq!(conn2.protocol_version(), Version::V0_34);
+            public2
+        });
+
+        let remote2 = peer1.join().expect("peer1 thread has panicked");
+        let public2 = peer2.join().expect("peer2 thread has panicked");
+        assert_eq!(remote2, public2);
+    }
+
+    #[cfg(not(feature = "amino"))]
+    #[test]
+    fn test_amino_requires_feature() {
+        let (pipe1, _pipe2) = pipe::async_bipipe_buffered();
+        let privkey = ed25519::Keypair::generate(&mut OsRng {});
+        assert!(SecretConnection::new(pipe1, privkey, Version::V0_33).is_err());
+    }
 }
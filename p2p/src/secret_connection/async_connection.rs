@@ -0,0 +1,359 @@
+//! Asynchronous counterpart to `SecretConnection`.
+
+use std::{
+    cmp, io,
+    pin::Pin,
+    slice,
+    task::{Context, Poll},
+};
+
+use ed25519_dalek as ed25519;
+use eyre::Result;
+use futures::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    ready,
+};
+
+use x25519_dalek::PublicKey as EphemeralPublic;
+
+use super::{
+    check_supported, io_error, read_buffered, AwaitingEphKey, Codec, Handshake, PublicKey, Version,
+    DATA_MAX_SIZE, SEALED_FRAME_SIZE,
+};
+
+/// Encrypted connection between peers in a Tendermint network, over an
+/// asynchronous transport.
+///
+/// This performs the same handshake and uses the same framing as
+/// [`SecretConnection`](super::SecretConnection), so either can be used on
+/// each end of a connection.
+pub struct AsyncSecretConnection<IoHandler: AsyncRead + AsyncWrite + Unpin> {
+    io_handler: IoHandler,
+    protocol_version: Version,
+    codec: Codec,
+    remote_pubkey: Option<PublicKey>,
+    recv_buffer: Vec<u8>,
+    // Sealed frame being read from the transport, of which `read_pos`
+    // bytes have been read so far.
+    read_frame: [u8; SEALED_FRAME_SIZE],
+    read_pos: usize,
+    // Sealed frame being written to the transport: the bytes in
+    // `write_pos..write_end` have yet to be written.
+    write_frame: [u8; SEALED_FRAME_SIZE],
+    write_pos: usize,
+    write_end: usize,
+}
+
+impl<IoHandler: AsyncRead + AsyncWrite + Unpin> AsyncSecretConnection<IoHandler> {
+    /// Returns the remote pubkey. Panics if there's no key.
+    pub fn remote_pubkey(&self) -> PublicKey {
+        self.remote_pubkey.expect("remote_pubkey uninitialized")
+    }
+
+    /// Returns the protocol version used by this connection.
+    pub fn protocol_version(&self) -> Version {
+        self.protocol_version
+    }
+
+    /// Performs a handshake and returns a new AsyncSecretConnection.
+    pub async fn new(
+        mut io_handler: IoHandler,
+        local_privkey: ed25519::Keypair,
+        protocol_version: Version,
+    ) -> Result<AsyncSecretConnection<IoHandler>> {
+        check_supported(protocol_version)?;
+
+        // Start a handshake process.
+        let (h, local_eph_pubkey) = Handshake::new(local_privkey, protocol_version);
+
+        // Write local ephemeral pubkey and receive one too.
+        io_handler
+            .write_all(&protocol_version.encode_initial_handshake(&local_eph_pubkey))
+            .await?;
+        io_handler.flush().await?;
+
+        let buf = read_initial_handshake(&mut io_handler).await?;
+        let remote_eph_pubkey = protocol_version.decode_initial_handshake(&buf)?;
+
+        Self::authenticate(io_handler, h, remote_eph_pubkey).await
+    }
+
+    /// Performs a handshake using whichever of the `supported_versions` the
+    /// peer is using, and returns a new AsyncSecretConnection.
+    ///
+    /// See [`SecretConnection::new_negotiated`](super::SecretConnection::new_negotiated).
+    pub async fn new_negotiated(
+        mut io_handler: IoHandler,
+        local_privkey: ed25519::Keypair,
+        supported_versions: &[Version],
+    ) -> Result<AsyncSecretConnection<IoHandler>> {
+        let remote_initial_handshake = read_initial_handshake(&mut io_handler).await?;
+        let protocol_version = Version::negotiate(&remote_initial_handshake, supported_versions)?;
+        let remote_eph_pubkey =
+            protocol_version.decode_initial_handshake(&remote_initial_handshake)?;
+
+        // Start a handshake process.
+        let (h, local_eph_pubkey) = Handshake::new(local_privkey, protocol_version);
+        io_handler
+            .write_all(&protocol_version.encode_initial_handshake(&local_eph_pubkey))
+            .await?;
+        io_handler.flush().await?;
+
+        Self::authenticate(io_handler, h, remote_eph_pubkey).await
+    }
+
+    /// Completes the handshake once the ephemeral public keys have been
+    /// exchanged.
+    async fn authenticate(
+        io_handler: IoHandler,
+        mut h: Handshake<AwaitingEphKey>,
+        remote_eph_pubkey: EphemeralPublic,
+    ) -> Result<AsyncSecretConnection<IoHandler>> {
+        let protocol_version = h.protocol_version;
+        let local_pubkey = PublicKey::from(&h.state.local_privkey);
+
+        // Compute a local signature (also recv_cipher & send_cipher)
+        let mut h = h.got_key(remote_eph_pubkey)?;
+
+        let mut sc = AsyncSecretConnection {
+            io_handler,
+            protocol_version,
+            codec: Codec::new(&h.state),
+            remote_pubkey: None,
+            recv_buffer: vec![],
+            read_frame: [0u8; SEALED_FRAME_SIZE],
+            read_pos: 0,
+            write_frame: [0u8; SEALED_FRAME_SIZE],
+            write_pos: 0,
+            write_end: 0,
+        };
+
+        // Share each other's pubkey & challenge signature.
+        // NOTE: the data must be encrypted/decrypted using ciphers.
+        let auth_sig_msg = match local_pubkey {
+            PublicKey::Ed25519(ref pk) => {
+                let buf = protocol_version.encode_auth_signature(pk, &h.state.local_signature);
+                sc.write_all(&buf).await?;
+                sc.flush().await?;
+
+                let mut buf = vec![0; protocol_version.auth_sig_msg_response_len()];
+                sc.read_exact(&mut buf).await?;
+                protocol_version.decode_auth_signature(&buf)?
+            }
+        };
+
+        // Authenticate remote pubkey.
+        let remote_pubkey = h.got_signature(auth_sig_msg)?;
+
+        // All good!
+        sc.remote_pubkey = Some(remote_pubkey);
+        Ok(sc)
+    }
+
+    /// Write out the remainder of the pending sealed frame, if any
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.write_pos < self.write_end {
+            let n = ready!(Pin::new(&mut self.io_handler)
+                .poll_write(cx, &self.write_frame[self.write_pos..self.write_end]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.write_pos += n;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Returns the (length-prefixed) initial handshake message sent by the peer,
+/// without its length prefix
+async fn read_initial_handshake<IoHandler>(handler: &mut IoHandler) -> Result<Vec<u8>>
+where
+    IoHandler: AsyncRead + Unpin,
+{
+    let mut response_len = 0u8;
+    handler
+        .read_exact(slice::from_mut(&mut response_len))
+        .await?;
+
+    let mut buf = vec![0; response_len as usize];
+    handler.read_exact(&mut buf).await?;
+    Ok(buf)
+}
+
+impl<IoHandler> AsyncRead for AsyncSecretConnection<IoHandler>
+where
+    IoHandler: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if data.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        while this.recv_buffer.is_empty() {
+            while this.read_pos < SEALED_FRAME_SIZE {
+                let n = ready!(Pin::new(&mut this.io_handler)
+                    .poll_read(cx, &mut this.read_frame[this.read_pos..]))?;
+                if n == 0 {
+                    return Poll::Ready(if this.read_pos == 0 {
+                        Ok(0)
+                    } else {
+                        Err(io::ErrorKind::UnexpectedEof.into())
+                    });
+                }
+                this.read_pos += n;
+            }
+            this.read_pos = 0;
+            this.recv_buffer = this.codec.open(&this.read_frame).map_err(io_error)?;
+        }
+
+        Poll::Ready(Ok(read_buffered(&mut this.recv_buffer, data)))
+    }
+}
+
+impl<IoHandler> AsyncWrite for AsyncSecretConnection<IoHandler>
+where
+    IoHandler: AsyncRead + AsyncWrite + Unpin,
+{
+    // Seals at most one frame's worth of data per call. The frame is only
+    // written out to the transport on the next write, flush or close.
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        if data.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let chunk = &data[..cmp::min(data.len(), DATA_MAX_SIZE)];
+        this.write_frame = this.codec.seal(chunk).map_err(io_error)?;
+        this.write_pos = 0;
+        this.write_end = SEALED_FRAME_SIZE;
+
+        Poll::Ready(Ok(chunk.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.io_handler).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.io_handler).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        thread,
+    };
+
+    use futures::{executor::block_on, io::AllowStdIo};
+    use rand_core::OsRng;
+
+    use super::super::{pipe, SecretConnection};
+    use super::*;
+
+    const MESSAGE: &str = "The Queen's Gambit";
+
+    #[test]
+    fn test_async_read_write() {
+        let (pipe1, pipe2) = pipe::async_bipipe_buffered();
+
+        let sender = thread::spawn(move || {
+            block_on(async {
+                let privkey1 = ed25519::Keypair::generate(&mut OsRng {});
+                let mut conn1 =
+                    AsyncSecretConnection::new(AllowStdIo::new(pipe2), privkey1, Version::V0_34)
+                        .await
+                        .expect("handshake to succeed");
+
+                // Spans several frames
+                let message = MESSAGE.repeat(100);
+                conn1
+                    .write_all(message.as_bytes())
+                    .await
+                    .expect("expected to write message");
+                conn1.flush().await.expect("expected to flush");
+            })
+        });
+
+        let receiver = thread::spawn(move || {
+            block_on(async {
+                let privkey2 = ed25519::Keypair::generate(&mut OsRng {});
+                let mut conn2 =
+                    AsyncSecretConnection::new(AllowStdIo::new(pipe1), privkey2, Version::V0_34)
+                        .await
+                        .expect("handshake to succeed");
+
+                let mut buf = vec![0; MESSAGE.len() * 100];
+                conn2
+                    .read_exact(&mut buf)
+                    .await
+                    .expect("expected to read message");
+                assert_eq!(MESSAGE.repeat(100).as_bytes(), &buf[..]);
+            })
+        });
+
+        sender.join().expect("sender thread has panicked");
+        receiver.join().expect("receiver thread has panicked");
+    }
+
+    #[test]
+    fn test_interop_with_sync_connection() {
+        let (pipe1, pipe2) = pipe::async_bipipe_buffered();
+
+        let sync_peer = thread::spawn(move || {
+            let privkey1 = ed25519::Keypair::generate(&mut OsRng {});
+            let public1 = PublicKey::from(&privkey1);
+            let mut conn1 = SecretConnection::new(pipe2, privkey1, Version::V0_34)
+                .expect("handshake to succeed");
+            conn1
+                .write_all(MESSAGE.as_bytes())
+                .expect("expected to write message");
+            conn1.flush().expect("expected to flush");
+
+            let mut buf = [0; MESSAGE.len()];
+            conn1
+                .read_exact(&mut buf)
+                .expect("expected to read message");
+            assert_eq!(MESSAGE.as_bytes(), &buf);
+            public1
+        });
+
+        let async_peer = thread::spawn(move || {
+            block_on(async {
+                let privkey2 = ed25519::Keypair::generate(&mut OsRng {});
+                let mut conn2 =
+                    AsyncSecretConnection::new(AllowStdIo::new(pipe1), privkey2, Version::V0_34)
+                        .await
+                        .expect("handshake to succeed");
+
+                let mut buf = [0; MESSAGE.len()];
+                conn2
+                    .read_exact(&mut buf)
+                    .await
+                    .expect("expected to read message");
+                conn2.write_all(&buf).await.expect("expected to echo");
+                conn2.flush().await.expect("expected to flush");
+                conn2.remote_pubkey()
+            })
+        });
+
+        let public1 = sync_peer.join().expect("sync peer thread has panicked");
+        let remote1 = async_peer.join().expect("async peer thread has panicked");
+        assert_eq!(public1, remote1);
+    }
+}
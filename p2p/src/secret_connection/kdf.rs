@@ -1,5 +1,6 @@
 use hkdf::Hkdf;
 use sha2::Sha256;
+use tendermint::secret::Secret;
 use zeroize::Zeroize;
 
 /// "Info" parameter to HKDF we use to personalize the derivation
@@ -8,13 +9,13 @@ const HKDF_INFO: &[u8] = b"TENDERMINT_SECRET_CONNECTION_KEY_AND_CHALLENGE_GEN";
 /// Key Derivation Function for `SecretConnection` (HKDF)
 pub struct Kdf {
     /// Receiver's secret
-    pub recv_secret: [u8; 32],
+    pub recv_secret: Secret<[u8; 32]>,
 
     /// Sender's secret
-    pub send_secret: [u8; 32],
+    pub send_secret: Secret<[u8; 32]>,
 
     /// Challenge to be signed by peer
-    pub challenge: [u8; 32],
+    pub challenge: Secret<[u8; 32]>,
 }
 
 impl Kdf {
@@ -26,31 +27,28 @@ impl Kdf {
             .expand(HKDF_INFO, &mut key_material)
             .unwrap();
 
-        let [mut recv_secret, mut send_secret, mut challenge] = [[0u8; 32]; 3];
+        let mut kdf = Kdf {
+            recv_secret: Secret::default(),
+            send_secret: Secret::default(),
+            challenge: Secret::default(),
+        };
 
-        if loc_is_lo {
-            recv_secret.copy_from_slice(&key_material[0..32]);
-            send_secret.copy_from_slice(&key_material[32..64]);
+        let (recv_secret, send_secret) = if loc_is_lo {
+            (&key_material[0..32], &key_material[32..64])
         } else {
-            send_secret.copy_from_slice(&key_material[0..32]);
-            recv_secret.copy_from_slice(&key_material[32..64]);
-        }
-
-        challenge.copy_from_slice(&key_material[64..96]);
+            (&key_material[32..64], &key_material[0..32])
+        };
+        kdf.recv_secret
+            .expose_secret_mut()
+            .copy_from_slice(recv_secret);
+        kdf.send_secret
+            .expose_secret_mut()
+            .copy_from_slice(send_secret);
+        kdf.challenge
+            .expose_secret_mut()
+            .copy_from_slice(&key_material[64..96]);
         key_material.as_mut().zeroize();
 
-        Kdf {
-            recv_secret,
-            send_secret,
-            challenge,
-        }
-    }
-}
-
-impl Drop for Kdf {
-    fn drop(&mut self) {
-        self.recv_secret.zeroize();
-        self.send_secret.zeroize();
-        self.challenge.zeroize();
+        kdf
     }
 }
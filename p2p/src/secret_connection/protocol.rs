@@ -49,6 +49,41 @@ impl Version {
         }
     }
 
+    /// Can this version be used? Versions using Amino encoding are only
+    /// available with the `amino` feature.
+    pub fn is_supported(self) -> bool {
+        self.is_protobuf() || cfg!(feature = "amino")
+    }
+
+    /// Pick the first of the `supported` versions which matches the encoding
+    /// of the initial handshake message received from a peer.
+    ///
+    /// Tendermint v0.34 peers use Protocol Buffers, whereas older peers use
+    /// Amino. As v0.33 and pre-v0.33 peers can't be told apart from this
+    /// message, the order of `supported` determines which one is preferred.
+    pub fn negotiate(initial_handshake: &[u8], supported: &[Version]) -> Result<Self> {
+        let is_protobuf = match initial_handshake {
+            [0x0a, 0x20, ..] if initial_handshake.len() == 34 => true,
+            [0x20, ..] if initial_handshake.len() == 33 => false,
+            _ => {
+                return Err(Error::ProtocolError)
+                    .wrap_err("malformed handshake message (unknown protocol version)")
+            }
+        };
+
+        supported
+            .iter()
+            .copied()
+            .find(|version| version.is_supported() && version.is_protobuf() == is_protobuf)
+            .ok_or(Error::ProtocolError)
+            .wrap_err_with(|| {
+                format!(
+                    "no supported protocol version for {} peer",
+                    if is_protobuf { "protobuf" } else { "amino" }
+                )
+            })
+    }
+
     /// Encode the initial handshake message (i.e. first one sent by both peers)
     pub fn encode_initial_handshake(self, eph_pubkey: &EphemeralPublic) -> Vec<u8> {
         if self.is_protobuf() {
@@ -258,3 +293,37 @@ fn is_low_order_point(point: &EphemeralPublic) -> bool {
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPPORTED: &[Version] = &[Version::V0_34, Version::V0_33, Version::Legacy];
+
+    #[test]
+    fn negotiates_version_from_initial_handshake() {
+        let eph_pubkey = EphemeralPublic::from(x25519_dalek::X25519_BASEPOINT_BYTES);
+        for version in SUPPORTED {
+            // Strip the length prefix, as it is read separately
+            let message = version.encode_initial_handshake(&eph_pubkey);
+            let negotiated = Version::negotiate(&message[1..], SUPPORTED);
+            if version.is_supported() {
+                let negotiated = negotiated.unwrap();
+                assert_eq!(negotiated.is_protobuf(), version.is_protobuf());
+                assert_eq!(
+                    negotiated.decode_initial_handshake(&message[1..]).unwrap(),
+                    eph_pubkey
+                );
+            } else {
+                assert!(negotiated.is_err());
+            }
+        }
+
+        let amino_message = Version::V0_33.encode_initial_handshake(&eph_pubkey);
+        assert_eq!(
+            Version::negotiate(&amino_message[1..], &[Version::V0_34]).is_err(),
+            true
+        );
+        assert_eq!(Version::negotiate(&[0x0a, 0x20], SUPPORTED).is_err(), true);
+    }
+}
@@ -0,0 +1,365 @@
+//! TCP endpoint on which a node accepts the connections of its peers.
+//!
+//! The endpoint runs its accept loop on a thread of its own, and reports
+//! the address it is bound to, as well as each accepted connection, as an
+//! [`Event`]. The accepted connections are then typically submitted to an
+//! [`UpgradePool`](crate::upgrade::UpgradePool).
+//!
+//! The connections to peers, accepted or opened with [`connect`], are tuned
+//! with [`SocketOptions`]: e.g. links between validators favour the latency
+//! of consensus messages, while links used to sync blocks favour throughput.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use socket2::{Domain, Protocol, SockRef, Socket, TcpKeepalive, Type};
+
+/// Backlog of connections waiting to be accepted, as used by the standard
+/// library
+const LISTEN_BACKLOG: i32 = 128;
+
+/// Number of events buffered by an endpoint, beyond which its accept loop
+/// waits for them to be received
+const EVENT_CAPACITY: usize = 64;
+
+/// Delays before accepting again after a failure, doubling from the first to
+/// the last with each consecutive failure, as done by Go's `net/http`
+const MIN_ACCEPT_BACKOFF: Duration = Duration::from_millis(5);
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How long dropping an endpoint waits to wake its accept loop up
+const WAKE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// TCP options of the connections to peers
+///
+/// The default options are those of the OS.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SocketOptions {
+    /// Send small messages right away, instead of coalescing them
+    /// (`TCP_NODELAY`)
+    pub nodelay: bool,
+
+    /// Idle time after which the connection is probed, if at all
+    /// (`SO_KEEPALIVE`)
+    pub keepalive: Option<Duration>,
+
+    /// Size of the receive buffer (`SO_RCVBUF`), if not the OS default
+    pub recv_buffer_size: Option<usize>,
+
+    /// Size of the send buffer (`SO_SNDBUF`), if not the OS default
+    pub send_buffer_size: Option<usize>,
+
+    /// How long closing the connection waits for the data still to be sent,
+    /// if at all (`SO_LINGER`)
+    pub linger: Option<Duration>,
+}
+
+impl SocketOptions {
+    fn apply(&self, socket: &Socket) -> io::Result<()> {
+        socket.set_nodelay(self.nodelay)?;
+        match self.keepalive {
+            Some(time) => socket.set_tcp_keepalive(&TcpKeepalive::new().with_time(time))?,
+            None => socket.set_keepalive(false)?,
+        }
+        if let Some(size) = self.recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+        if let Some(size) = self.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+        socket.set_linger(self.linger)
+    }
+}
+
+/// Where to bind an endpoint
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BindInfo {
+    /// Address to listen on, whose port may be 0 to let the OS pick one
+    pub addr: SocketAddr,
+
+    /// Options of the accepted connections
+    pub options: SocketOptions,
+}
+
+/// Where an endpoint is bound
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BoundInfo {
+    /// Address the endpoint listens on, with the port picked by the OS if
+    /// port 0 was requested
+    pub local_addr: SocketAddr,
+}
+
+/// Peer to connect to
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConnectInfo {
+    /// Address of the peer
+    pub addr: SocketAddr,
+
+    /// How long to wait for the connection to be established, if not until
+    /// the OS gives up
+    pub timeout: Option<Duration>,
+
+    /// Options of the connection
+    pub options: SocketOptions,
+}
+
+/// Connect to a peer.
+pub fn connect(info: &ConnectInfo) -> io::Result<TcpStream> {
+    let socket = socket(info.addr, &info.options)?;
+    let addr = info.addr.into();
+    match info.timeout {
+        Some(timeout) => socket.connect_timeout(&addr, timeout)?,
+        None => socket.connect(&addr)?,
+    }
+    Ok(socket.into())
+}
+
+fn socket(addr: SocketAddr, options: &SocketOptions) -> io::Result<Socket> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    options.apply(&socket)?;
+    Ok(socket)
+}
+
+/// Event of the accept loop of an endpoint
+#[derive(Debug)]
+pub enum Event {
+    /// The accept loop is running, and accepting connections on the given
+    /// address. This is always the first event of an endpoint.
+    Listening(SocketAddr),
+
+    /// A peer connected from the given address.
+    Accepted(SocketAddr, TcpStream),
+
+    /// Accepting a connection, or setting its options, failed, e.g. because
+    /// the process ran out of file descriptors. The accept loop keeps running,
+    /// but waits longer before accepting again after each consecutive failure.
+    AcceptFailed(io::Error),
+}
+
+/// Endpoint accepting the connections of peers
+///
+/// A bounded number of events is buffered: once it is reached, the accept
+/// loop waits for them to be received, and new connections wait in the
+/// listen backlog.
+///
+/// Dropping the endpoint stops its accept loop and closes its listener.
+pub struct Endpoint {
+    events: flume::Receiver<Event>,
+    stopped: Arc<AtomicBool>,
+    local_addr: SocketAddr,
+}
+
+impl Endpoint {
+    /// Bind an endpoint and start its accept loop, returning the address it
+    /// is actually bound to.
+    pub fn run(info: BindInfo) -> io::Result<(Self, BoundInfo)> {
+        // Buffer sizes must be set before listening to apply to the accepted
+        // connections, the other options are set on each of them
+        let socket = socket(info.addr, &info.options)?;
+        #[cfg(unix)]
+        socket.set_reuse_address(true)?;
+        socket.bind(&info.addr.into())?;
+        socket.listen(LISTEN_BACKLOG)?;
+        let listener = TcpListener::from(socket);
+
+        let bound = BoundInfo {
+            local_addr: listener.local_addr()?,
+        };
+
+        let (sender, events) = flume::bounded(EVENT_CAPACITY);
+        let stopped = Arc::new(AtomicBool::new(false));
+        let options = info.options;
+        {
+            let stopped = stopped.clone();
+            thread::spawn(move || accept_loop(listener, bound, options, sender, &stopped));
+        }
+
+        let endpoint = Self {
+            events,
+            stopped,
+            local_addr: bound.local_addr,
+        };
+        Ok((endpoint, bound))
+    }
+
+    /// Wait for the next event of the accept loop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the accept loop panicked.
+    pub fn recv(&self) -> Event {
+        self.events.recv().expect("accept loop exited")
+    }
+
+    /// Take the next event of the accept loop, if it occurred already.
+    pub fn try_recv(&self) -> Option<Event> {
+        self.events.try_recv().ok()
+    }
+
+    /// Asynchronously wait for the next event of the accept loop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the accept loop panicked.
+    pub async fn recv_async(&self) -> Event {
+        self.events.recv_async().await.expect("accept loop exited")
+    }
+}
+
+impl Drop for Endpoint {
+    fn drop(&mut self) {
+        // Accepting can't be interrupted, so wake the accept loop up with a
+        // connection of our own, after which it sees it must stop
+        self.stopped.store(true, Ordering::SeqCst);
+        let ip = match self.local_addr.ip() {
+            IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ip => ip,
+        };
+        let _ =
+            TcpStream::connect_timeout(&SocketAddr::new(ip, self.local_addr.port()), WAKE_TIMEOUT);
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    bound: BoundInfo,
+    options: SocketOptions,
+    events: flume::Sender<Event>,
+    stopped: &AtomicBool,
+) {
+    if events.send(Event::Listening(bound.local_addr)).is_err() {
+        return;
+    }
+    let mut backoff = None;
+    loop {
+        let accepted = accept(&listener, &options);
+        if stopped.load(Ordering::SeqCst) {
+            break;
+        }
+        let event = match accepted {
+            Ok((stream, addr)) => {
+                backoff = None;
+                Event::Accepted(addr, stream)
+            }
+            Err(e) => {
+                backoff = Some(backoff.map_or(MIN_ACCEPT_BACKOFF, |delay: Duration| {
+                    (delay * 2).min(MAX_ACCEPT_BACKOFF)
+                }));
+                Event::AcceptFailed(e)
+            }
+        };
+        // The endpoint may have been dropped in the meantime
+        if events.send(event).is_err() {
+            break;
+        }
+        if let Some(delay) = backoff {
+            thread::sleep(delay);
+        }
+    }
+}
+
+fn accept(listener: &TcpListener, options: &SocketOptions) -> io::Result<(TcpStream, SocketAddr)> {
+    let (stream, addr) = listener.accept()?;
+    options.apply(&SockRef::from(&stream))?;
+    Ok((stream, addr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_bound_address_and_accepted_connections() {
+        let info = BindInfo {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            options: SocketOptions::default(),
+        };
+        let (endpoint, bound) = Endpoint::run(info).unwrap();
+        assert_ne!(bound.local_addr.port(), 0);
+        match endpoint.recv() {
+            Event::Listening(addr) => assert_eq!(addr, bound.local_addr),
+            event => panic!("unexpected event: {:?}", event),
+        }
+
+        let client = connect(&ConnectInfo {
+            addr: bound.local_addr,
+            timeout: None,
+            options: SocketOptions::default(),
+        })
+        .unwrap();
+        match endpoint.recv() {
+            Event::Accepted(addr, stream) => {
+                assert_eq!(addr, client.local_addr().unwrap());
+                assert_eq!(stream.peer_addr().unwrap(), client.local_addr().unwrap());
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+        assert!(endpoint.try_recv().is_none());
+    }
+
+    #[test]
+    fn closes_listener_when_dropped() {
+        let (endpoint, bound) = Endpoint::run(BindInfo {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            options: SocketOptions::default(),
+        })
+        .unwrap();
+        assert!(matches!(endpoint.recv(), Event::Listening(_)));
+        drop(endpoint);
+
+        // The accept loop closes the listener shortly after being woken up
+        let info = ConnectInfo {
+            addr: bound.local_addr,
+            timeout: Some(Duration::from_secs(1)),
+            options: SocketOptions::default(),
+        };
+        for _ in 0..100 {
+            if connect(&info).is_err() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("listener still accepting connections");
+    }
+
+    #[test]
+    fn sets_socket_options() {
+        let options = SocketOptions {
+            nodelay: true,
+            keepalive: Some(Duration::from_secs(30)),
+            recv_buffer_size: Some(1 << 16),
+            send_buffer_size: Some(1 << 16),
+            linger: Some(Duration::from_secs(1)),
+        };
+        let (endpoint, bound) = Endpoint::run(BindInfo {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            options,
+        })
+        .unwrap();
+
+        let client = connect(&ConnectInfo {
+            addr: bound.local_addr,
+            timeout: Some(Duration::from_secs(5)),
+            options,
+        })
+        .unwrap();
+        let server = loop {
+            if let Event::Accepted(_, stream) = endpoint.recv() {
+                break stream;
+            }
+        };
+
+        for stream in &[client, server] {
+            let socket = SockRef::from(stream);
+            assert!(socket.nodelay().unwrap());
+            assert!(socket.keepalive().unwrap());
+            assert_eq!(socket.linger().unwrap(), Some(Duration::from_secs(1)));
+            assert!(socket.recv_buffer_size().unwrap() >= 1 << 16);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Bounded pool of worker threads upgrading connections, e.g. performing
+//! their `SecretConnection` handshake, concurrently.
+//!
+//! Handshakes take a few round trips with each peer: performing them one at
+//! a time, or while holding state shared with the rest of the node, lets a
+//! few slow peers hold up all the others.
+
+use std::sync::Arc;
+use std::thread;
+
+use crate::error::Error;
+
+/// Upgrades connections on a fixed number of worker threads.
+///
+/// Connections are submitted to a bounded queue, from which each worker
+/// takes the next one as soon as it is done upgrading the previous one, and
+/// the outcomes are received in the order in which upgrades complete.
+///
+/// Dropping the pool drops the connections still in the queue, but doesn't
+/// wait for the upgrades in progress: their workers exit once done with them.
+/// A peer can stall a handshake indefinitely, so `upgrade` should bound the
+/// time it spends on each connection, e.g. with socket timeouts.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::net::{SocketAddr, TcpListener, TcpStream};
+/// use std::time::Duration;
+///
+/// use ed25519_dalek::Keypair;
+/// use tendermint_p2p::secret_connection::{SecretConnection, Version};
+/// use tendermint_p2p::upgrade::UpgradePool;
+///
+/// # fn local_key() -> Keypair { unimplemented!() }
+/// let pool = UpgradePool::new(8, 64, |(addr, stream): (SocketAddr, TcpStream)| {
+///     // Don't let the peer stall the handshake
+///     let timeout = Some(Duration::from_secs(10));
+///     let _ = stream.set_read_timeout(timeout).and(stream.set_write_timeout(timeout));
+///     (addr, SecretConnection::new(stream, local_key(), Version::V0_34))
+/// });
+///
+/// let listener = TcpListener::bind("127.0.0.1:26656").unwrap();
+/// for stream in listener.incoming() {
+///     let stream = stream.unwrap();
+///     // Reject the connection if too many handshakes are pending
+///     let _ = pool.submit((stream.peer_addr().unwrap(), stream));
+///
+///     while let Some((addr, connection)) = pool.try_recv() {
+///         println!("{}: {:?}", addr, connection.map(|c| c.remote_pubkey()));
+///     }
+/// }
+/// ```
+pub struct UpgradePool<I, O> {
+    jobs: Option<flume::Sender<I>>,
+    queue: flume::Receiver<I>,
+    results: flume::Receiver<O>,
+}
+
+impl<I, O> UpgradePool<I, O>
+where
+    I: Send + 'static,
+    O: Send + 'static,
+{
+    /// Start `workers` threads applying `upgrade` to the connections
+    /// submitted to the pool, at most `queue_capacity` of which can wait for
+    /// a worker.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `workers` is zero.
+    pub fn new<F>(workers: usize, queue_capacity: usize, upgrade: F) -> Self
+    where
+        F: Fn(I) -> O + Send + Sync + 'static,
+    {
+        assert!(workers > 0, "upgrade pool without workers");
+
+        let (jobs, pending) = flume::bounded::<I>(queue_capacity);
+        let (completed, results) = flume::unbounded();
+        let upgrade = Arc::new(upgrade);
+        for _ in 0..workers {
+            let pending = pending.clone();
+            let completed = completed.clone();
+            let upgrade = upgrade.clone();
+            thread::spawn(move || {
+                for input in pending.iter() {
+                    // The pool may have been dropped in the meantime
+                    if completed.send(upgrade(input)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        Self {
+            jobs: Some(jobs),
+            queue: pending,
+            results,
+        }
+    }
+
+    /// Submit a connection to upgrade, without blocking.
+    ///
+    /// Fails, dropping the connection, if the queue is full.
+    pub fn submit(&self, input: I) -> Result<(), Error> {
+        self.jobs
+            .as_ref()
+            .expect("upgrade pool dropped")
+            .try_send(input)
+            .map_err(|_| Error::UpgradeQueueFull)
+    }
+
+    /// Number of connections waiting for a worker.
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Wait for the next upgrade to complete.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every worker panicked.
+    pub fn recv(&self) -> O {
+        self.results.recv().expect("upgrade pool workers exited")
+    }
+
+    /// Take the outcome of an upgrade which completed already, if any.
+    pub fn try_recv(&self) -> Option<O> {
+        self.results.try_recv().ok()
+    }
+
+    /// Asynchronously wait for the next upgrade to complete.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every worker panicked.
+    pub async fn recv_async(&self) -> O {
+        self.results
+            .recv_async()
+            .await
+            .expect("upgrade pool workers exited")
+    }
+}
+
+impl<I, O> Drop for UpgradePool<I, O> {
+    fn drop(&mut self) {
+        // Disconnect and drain the queue, so that the workers exit once done
+        // with their current upgrade, without waiting for them
+        self.jobs = None;
+        self.queue.drain().for_each(drop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+    use std::time::Duration;
+
+    #[test]
+    fn upgrades_concurrently() {
+        // Each upgrade waits for the others, so they only complete if they
+        // run concurrently.
+        let barrier = Arc::new(Barrier::new(4));
+        let pool = UpgradePool::new(4, 4, move |n: u32| {
+            barrier.wait();
+            n * 2
+        });
+        for n in 0..4 {
+            pool.submit(n).unwrap();
+        }
+
+        let mut results: Vec<_> = (0..4).map(|_| pool.recv()).collect();
+        results.sort_unstable();
+        assert_eq!(results, vec![0, 2, 4, 6]);
+        assert_eq!(pool.try_recv(), None);
+    }
+
+    #[test]
+    fn bounds_pending_upgrades() {
+        let (release, released) = flume::unbounded::<()>();
+        let pool = UpgradePool::new(1, 1, move |n: u32| {
+            released.recv().unwrap();
+            n
+        });
+
+        pool.submit(1).unwrap();
+        // Wait for the worker to take the first upgrade
+        while pool.pending() > 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        pool.submit(2).unwrap();
+        assert_eq!(pool.submit(3), Err(Error::UpgradeQueueFull));
+
+        release.send(()).unwrap();
+        assert_eq!(pool.recv(), 1);
+        release.send(()).unwrap();
+        assert_eq!(pool.recv(), 2);
+    }
+
+    #[test]
+    fn drop_does_not_wait_for_stalled_upgrades() {
+        let (release, released) = flume::unbounded::<()>();
+        let (started, start) = flume::unbounded::<()>();
+        let pool = UpgradePool::new(1, 1, move |n: u32| {
+            started.send(()).unwrap();
+            released.recv().unwrap();
+            n
+        });
+
+        pool.submit(1).unwrap();
+        start.recv().unwrap();
+        drop(pool);
+
+        // The worker only exits once its upgrade completes
+        release.send(()).unwrap();
+    }
+}
@@ -156,7 +156,9 @@ prop_compose! {
 }
 
 prop_compose! {
-    fn arb_rfc3339_full_date()(year in 0..9999i32, month in 1..12u32)
+    // Years are kept between 2 and 9998 so that applying any offset still
+    // yields a timestamp in the range valid for Tendermint (years 1 to 9999).
+    fn arb_rfc3339_full_date()(year in 2..9999i32, month in 1..12u32)
         (
             day in arb_rfc3339_day_of_year_and_month(year, month),
             year in Just(year),
@@ -0,0 +1,72 @@
+//! Canonical protobuf encoding of the messages, on which signatures depend.
+//!
+//! Signatures, e.g. of votes and proposals, are computed over the protobuf
+//! encoding of their sign bytes structures (`CanonicalVote`,
+//! `CanonicalProposal`, ...), so that they only verify if Rust and Go encode
+//! these structures byte for byte the same way. As Go does, prost writes the
+//! fields in the order of their tags and omits the scalar fields which have
+//! their default value, so that its encoding is canonical.
+//!
+//! Decoding is lenient, though: fields in any order, default values, unknown
+//! fields and non-minimal varints are all accepted, and lost when encoding
+//! the message again. The decoding functions of this module reject such
+//! encodings, so that e.g. sign bytes which don't encode the message which
+//! they decode to are caught.
+//!
+//! ```
+//! use tendermint_proto::canonical;
+//! use tendermint_proto::types::CanonicalPartSetHeader;
+//!
+//! let header = CanonicalPartSetHeader {
+//!     total: 1,
+//!     hash: vec![0xAB; 2],
+//! };
+//! let encoded = canonical::encode(&header);
+//! assert_eq!(encoded, vec![0x08, 0x01, 0x12, 0x02, 0xAB, 0xAB]);
+//! assert_eq!(canonical::decode::<CanonicalPartSetHeader>(&encoded).unwrap(), header);
+//!
+//! // The same header, with its fields in the wrong order
+//! let reordered = [0x12, 0x02, 0xAB, 0xAB, 0x08, 0x01];
+//! assert!(canonical::decode::<CanonicalPartSetHeader>(&reordered).is_err());
+//! ```
+
+use crate::prelude::*;
+use crate::{Error, Kind};
+use prost::Message;
+
+/// Encode a message canonically.
+pub fn encode<M: Message>(message: &M) -> Vec<u8> {
+    let mut wire = Vec::with_capacity(message.encoded_len());
+    // Encoding into a `Vec` can't run out of space
+    message.encode(&mut wire).unwrap();
+    wire
+}
+
+/// Encode a message canonically, prefixed with its length as a varint, as
+/// for sign bytes.
+pub fn encode_length_delimited<M: Message>(message: &M) -> Vec<u8> {
+    let len = message.encoded_len();
+    let mut wire = Vec::with_capacity(len + prost::length_delimiter_len(len));
+    // Encoding into a `Vec` can't run out of space
+    message.encode_length_delimited(&mut wire).unwrap();
+    wire
+}
+
+/// Decode a message, failing unless it is canonically encoded.
+pub fn decode<M: Message + Default>(wire: &[u8]) -> Result<M, Error> {
+    let message = M::decode(wire).map_err(|e| Kind::DecodeMessage.context(e))?;
+    if encode(&message) != wire {
+        return Err(Kind::NonCanonicalEncoding.into());
+    }
+    Ok(message)
+}
+
+/// Decode a message prefixed with its length, failing unless it is
+/// canonically encoded, or followed by other bytes.
+pub fn decode_length_delimited<M: Message + Default>(wire: &[u8]) -> Result<M, Error> {
+    let message = M::decode_length_delimited(wire).map_err(|e| Kind::DecodeMessage.context(e))?;
+    if encode_length_delimited(&message) != wire {
+        return Err(Kind::NonCanonicalEncoding.into());
+    }
+    Ok(message)
+}
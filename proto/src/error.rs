@@ -1,27 +1,63 @@
 //! This module defines the various errors that be raised during Protobuf conversions.
 
-use anomaly::{BoxError, Context};
-use thiserror::Error;
+use core::fmt;
+
+#[cfg(feature = "std")]
+pub use anomaly::{BoxError, Context};
+
+#[cfg(not(feature = "std"))]
+mod context;
+
+#[cfg(not(feature = "std"))]
+pub use self::context::{BoxError, Context};
 
 /// An error that can be raised by the Protobuf conversions.
+#[cfg(feature = "std")]
 pub type Error = anomaly::Error<Kind>;
 
+/// An error that can be raised by the Protobuf conversions.
+#[cfg(not(feature = "std"))]
+pub type Error = self::context::Error<Kind>;
+
 /// Various kinds of errors that can be raised.
-#[derive(Clone, Debug, Error)]
+#[derive(Clone, Debug)]
 pub enum Kind {
     /// TryFrom Prost Message failed during decoding
-    #[error("error converting message type into domain type")]
     TryFromProtobuf,
 
     /// encoding prost Message into buffer failed
-    #[error("error encoding message into buffer")]
     EncodeMessage,
 
     /// decoding buffer into prost Message failed
-    #[error("error decoding buffer into message")]
     DecodeMessage,
+
+    /// encoding message into proto3 JSON failed
+    EncodeJson,
+
+    /// decoding proto3 JSON into message failed
+    DecodeJson,
+
+    /// message is not canonically encoded
+    NonCanonicalEncoding,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Kind::TryFromProtobuf => "error converting message type into domain type",
+            Kind::EncodeMessage => "error encoding message into buffer",
+            Kind::DecodeMessage => "error decoding buffer into message",
+            Kind::EncodeJson => "error encoding message into JSON",
+            Kind::DecodeJson => "error decoding JSON into message",
+            Kind::NonCanonicalEncoding => "message is not canonically encoded",
+        };
+        f.write_str(description)
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Kind {}
+
 impl Kind {
     /// Add a given source error as context for this error kind
     ///
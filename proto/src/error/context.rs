@@ -0,0 +1,170 @@
+//! Stand-ins for the error types of `anomaly`, which requires `std`.
+//!
+//! They mirror its API, minus backtraces and `std::error::Error`, so that
+//! errors are created and propagated the same way with and without `std`.
+
+use crate::prelude::*;
+use alloc::boxed::Box;
+use core::{fmt, ops::Deref};
+
+/// Boxed error, holding the description of its source
+pub struct BoxError(Box<dyn fmt::Display + Send + Sync + 'static>);
+
+impl BoxError {
+    /// Box the given error, e.g. to implement `From` for the error types of
+    /// other crates
+    pub fn new(err: impl fmt::Display + Send + Sync + 'static) -> Self {
+        BoxError(Box::new(err))
+    }
+}
+
+impl fmt::Debug for BoxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for BoxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+// Without `std::error::Error` to tell errors apart from `BoxError` itself, the
+// types errors can be boxed from are listed explicitly.
+macro_rules! impl_from {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl From<$ty> for BoxError {
+                fn from(err: $ty) -> Self {
+                    BoxError(Box::new(err))
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(
+    &'static str,
+    String,
+    crate::error::Kind,
+    Context<crate::error::Kind>,
+    Error<crate::error::Kind>,
+    fmt::Error,
+    core::num::TryFromIntError,
+    prost::DecodeError,
+    prost::EncodeError,
+    serde_json::Error,
+);
+
+impl From<core::convert::Infallible> for BoxError {
+    fn from(err: core::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
+/// Error context: the kind of an error, along with its source (if any)
+#[derive(Debug)]
+pub struct Context<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    kind: Kind,
+    source: Option<BoxError>,
+}
+
+impl<Kind> Context<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    /// Create a new error context
+    pub fn new(kind: Kind, source: Option<BoxError>) -> Self {
+        Context { kind, source }
+    }
+
+    /// Get the kind of error
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+}
+
+impl<Kind> fmt::Display for Context<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(source) = &self.source {
+            write!(f, ": {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Kind> From<Kind> for Context<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    fn from(kind: Kind) -> Self {
+        Context::new(kind, None)
+    }
+}
+
+/// Error type, boxing its [`Context`] so that results stay small
+#[derive(Debug)]
+pub struct Error<Kind>(Box<Context<Kind>>)
+where
+    Kind: Clone + fmt::Debug + fmt::Display;
+
+impl<Kind> Error<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    /// Create a new error of the given kind, with the given source (if any)
+    pub fn new(kind: Kind, source: Option<BoxError>) -> Self {
+        Error(Box::new(Context::new(kind, source)))
+    }
+
+    /// Get the kind of error
+    pub fn kind(&self) -> &Kind {
+        self.0.kind()
+    }
+}
+
+impl<Kind> Deref for Error<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    type Target = Context<Kind>;
+
+    fn deref(&self) -> &Context<Kind> {
+        &self.0
+    }
+}
+
+impl<Kind> fmt::Display for Error<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<Kind> From<Kind> for Error<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    fn from(kind: Kind) -> Self {
+        Error::new(kind, None)
+    }
+}
+
+impl<Kind> From<Context<Kind>> for Error<Kind>
+where
+    Kind: Clone + fmt::Debug + fmt::Display,
+{
+    fn from(context: Context<Kind>) -> Self {
+        Error(Box::new(context))
+    }
+}
@@ -0,0 +1,613 @@
+//! Canonical proto3 JSON encoding of the protobuf messages.
+//!
+//! The `Serialize` and `Deserialize` implementations of the generated types
+//! follow the JSON format of the Tendermint RPC, which differs from the
+//! [proto3 JSON mapping]. This module implements the latter, as produced by
+//! Go's `jsonpb`, so that fixtures and payloads round-trip identically
+//! between Rust and Go:
+//!
+//! * fields are named in lowerCamelCase, are written in the order of their
+//!   field numbers, and are omitted when they have their default value;
+//! * `int64` and `uint64` values are strings, `bytes` are base64 strings
+//!   and enumerations are the names of their values;
+//! * timestamps are RFC 3339 strings and durations are strings of seconds,
+//!   e.g. `"1.5s"`, both with 0, 3, 6 or 9 fractional digits.
+//!
+//! When parsing, the original snake_case field names are accepted too, as
+//! well as numbers for 64-bit integers and enumerations.
+//!
+//! ```
+//! use tendermint_proto::json;
+//! use tendermint_proto::types::PartSetHeader;
+//!
+//! let header = PartSetHeader {
+//!     total: 1,
+//!     hash: vec![0xAB; 4],
+//! };
+//! let encoded = json::to_string(&header).unwrap();
+//! assert_eq!(encoded, r#"{"total":1,"hash":"q6urqw=="}"#);
+//! assert_eq!(json::from_str::<PartSetHeader>(&encoded).unwrap(), header);
+//! ```
+//!
+//! [proto3 JSON mapping]: https://developers.google.com/protocol-buffers/docs/proto3#json
+
+use crate::google::protobuf::{Duration, Timestamp};
+use crate::prelude::*;
+use crate::{Error, Kind};
+use chrono::{DateTime, LocalResult, TimeZone, Utc};
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Error as _, Serialize, Serializer};
+use serde_json::Value;
+
+/// A protobuf message with a canonical proto3 JSON encoding.
+pub trait Proto3Json: Sized {
+    /// Serialize this message in its proto3 JSON representation.
+    fn serialize_proto3<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    /// Parse a message from its proto3 JSON representation.
+    fn from_proto3(value: Value) -> Result<Self, Error>;
+}
+
+/// A protobuf enumeration, whose values are encoded by name in proto3 JSON.
+pub trait Proto3Enum {
+    /// The name of the given value, if it is known.
+    fn proto3_name(value: i32) -> Option<&'static str>;
+
+    /// The value of the given name, if it is known.
+    fn from_proto3_name(name: &str) -> Option<i32>;
+}
+
+/// Wrapper which serializes and deserializes a message with serde in its
+/// proto3 JSON representation, e.g. to embed it in another serde type.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Proto3<T>(pub T);
+
+impl<T: Proto3Json> Serialize for Proto3<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_proto3(serializer)
+    }
+}
+
+impl<'de, T: Proto3Json> Deserialize<'de> for Proto3<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        T::from_proto3(value)
+            .map(Proto3)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Encode a message as a proto3 JSON string.
+pub fn to_string<T: Proto3Json>(message: &T) -> Result<String, Error> {
+    serde_json::to_string(&Proto3Ref(message)).map_err(|e| Kind::EncodeJson.context(e).into())
+}
+
+/// Encode a message as proto3 JSON bytes.
+pub fn to_vec<T: Proto3Json>(message: &T) -> Result<Vec<u8>, Error> {
+    serde_json::to_vec(&Proto3Ref(message)).map_err(|e| Kind::EncodeJson.context(e).into())
+}
+
+/// Decode a message from a proto3 JSON string.
+pub fn from_str<T: Proto3Json>(s: &str) -> Result<T, Error> {
+    let value = serde_json::from_str(s).map_err(|e| Kind::DecodeJson.context(e))?;
+    T::from_proto3(value)
+}
+
+/// Decode a message from proto3 JSON bytes.
+pub fn from_slice<T: Proto3Json>(v: &[u8]) -> Result<T, Error> {
+    let value = serde_json::from_slice(v).map_err(|e| Kind::DecodeJson.context(e))?;
+    T::from_proto3(value)
+}
+
+struct Proto3Ref<'a, T>(&'a T);
+
+impl<T: Proto3Json> Serialize for Proto3Ref<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_proto3(serializer)
+    }
+}
+
+fn decode_error(message: impl Into<String>) -> Error {
+    Kind::DecodeJson.context(message.into()).into()
+}
+
+/// Formats the fractional digits of a second as Go does, i.e. with 0, 3, 6
+/// or 9 digits.
+fn format_nanos(nanos: u32) -> String {
+    let nanos = format!(".{:09}", nanos);
+    let nanos = nanos.strip_suffix("000").unwrap_or(&nanos);
+    let nanos = nanos.strip_suffix("000").unwrap_or(nanos);
+    nanos.strip_suffix(".000").unwrap_or(nanos).to_string()
+}
+
+impl Proto3Json for Timestamp {
+    fn serialize_proto3<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.nanos < 0 || self.nanos >= 1_000_000_000 {
+            return Err(S::Error::custom("invalid nanoseconds in timestamp"));
+        }
+        let time = match Utc.timestamp_opt(self.seconds, self.nanos as u32) {
+            LocalResult::Single(time) => time,
+            _ => return Err(S::Error::custom("invalid timestamp")),
+        };
+        serializer.serialize_str(&format!(
+            "{}{}Z",
+            time.format("%Y-%m-%dT%H:%M:%S"),
+            format_nanos(self.nanos as u32)
+        ))
+    }
+
+    fn from_proto3(value: Value) -> Result<Self, Error> {
+        let s = match value {
+            Value::String(s) => s,
+            v => return Err(decode_error(format!("invalid timestamp: {}", v))),
+        };
+        let time = DateTime::parse_from_rfc3339(&s)
+            .map_err(|e| decode_error(format!("invalid timestamp {:?}: {}", s, e)))?;
+        Ok(Timestamp {
+            seconds: time.timestamp(),
+            nanos: time.timestamp_subsec_nanos() as i32,
+        })
+    }
+}
+
+impl Proto3Json for Duration {
+    fn serialize_proto3<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.nanos <= -1_000_000_000
+            || self.nanos >= 1_000_000_000
+            || (self.seconds > 0 && self.nanos < 0)
+            || (self.seconds < 0 && self.nanos > 0)
+        {
+            return Err(S::Error::custom("invalid nanoseconds in duration"));
+        }
+        let sign = if self.seconds < 0 || self.nanos < 0 {
+            "-"
+        } else {
+            ""
+        };
+        serializer.serialize_str(&format!(
+            "{}{}{}s",
+            sign,
+            self.seconds.unsigned_abs(),
+            format_nanos(self.nanos.unsigned_abs())
+        ))
+    }
+
+    fn from_proto3(value: Value) -> Result<Self, Error> {
+        let s = match value {
+            Value::String(s) => s,
+            v => return Err(decode_error(format!("invalid duration: {}", v))),
+        };
+        let invalid = || decode_error(format!("invalid duration: {:?}", s));
+        let unsigned = s.strip_suffix('s').ok_or_else(invalid)?;
+        let (negative, unsigned) = match unsigned.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, unsigned),
+        };
+        let (seconds, fraction) = match unsigned.find('.') {
+            Some(i) => (&unsigned[..i], &unsigned[i + 1..]),
+            None => (unsigned, ""),
+        };
+        if seconds.is_empty()
+            || fraction.len() > 9
+            || !seconds
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let seconds: i64 = seconds.parse().map_err(|_| invalid())?;
+        let nanos: i32 = if fraction.is_empty() {
+            0
+        } else {
+            format!("{:0<9}", fraction).parse().map_err(|_| invalid())?
+        };
+        Ok(if negative {
+            Duration {
+                seconds: -seconds,
+                nanos: -nanos,
+            }
+        } else {
+            Duration { seconds, nanos }
+        })
+    }
+}
+
+/// Building blocks of the generated `Proto3Json` implementations.
+///
+/// Each field is encoded according to its kind, e.g. `Int64` for the
+/// `int64` fields, which are strings in JSON, or `Optional<Message>` for
+/// the message fields.
+pub(crate) mod fields {
+    use super::{decode_error, Proto3Enum, Proto3Json};
+    use crate::prelude::*;
+    use crate::Error;
+    use core::marker::PhantomData;
+    use serde::de::DeserializeOwned;
+    use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Value};
+    use subtle_encoding::base64;
+
+    /// The proto3 JSON encoding of a kind of field.
+    pub trait FieldKind<T> {
+        /// Is this the default value, which is omitted?
+        fn is_default(value: &T) -> bool;
+
+        /// Serialize a value.
+        fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error>;
+
+        /// Parse a value, which isn't null.
+        fn parse(value: Value) -> Result<T, Error>;
+    }
+
+    /// Strings, booleans and floating-point numbers, which are encoded as is.
+    pub struct Plain;
+
+    /// 32-bit integers, which are numbers.
+    pub struct Int32;
+
+    /// 64-bit integers, which are strings.
+    pub struct Int64;
+
+    /// Bytes, which are base64 strings.
+    pub struct Bytes;
+
+    /// Enumerations, which are the names of their values.
+    pub struct Enum<E>(PhantomData<E>);
+
+    /// Messages.
+    pub struct Message;
+
+    /// Optional values, i.e. message fields, which are encoded when set.
+    pub struct Optional<K>(PhantomData<K>);
+
+    /// Repeated values, which are arrays.
+    pub struct Repeated<K>(PhantomData<K>);
+
+    /// Values of the `Plain` kind.
+    pub trait PlainValue: Serialize + DeserializeOwned + Default + PartialEq {}
+
+    impl PlainValue for String {}
+    impl PlainValue for bool {}
+    impl PlainValue for f64 {}
+
+    impl<T: PlainValue> FieldKind<T> for Plain {
+        fn is_default(value: &T) -> bool {
+            *value == T::default()
+        }
+
+        fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+            value.serialize(serializer)
+        }
+
+        fn parse(value: Value) -> Result<T, Error> {
+            T::deserialize(value).map_err(|e| decode_error(e.to_string()))
+        }
+    }
+
+    /// Values of the `Int32` and `Int64` kinds.
+    pub trait IntValue:
+        Serialize + DeserializeOwned + core::str::FromStr + Default + PartialEq
+    {
+    }
+
+    impl IntValue for i32 {}
+    impl IntValue for u32 {}
+    impl IntValue for i64 {}
+    impl IntValue for u64 {}
+
+    // Both kinds of integers may be numbers or strings.
+    fn parse_int<T: IntValue>(value: Value) -> Result<T, Error> {
+        match value {
+            Value::String(s) => s
+                .parse()
+                .map_err(|_| decode_error(format!("invalid integer: {:?}", s))),
+            value => T::deserialize(value).map_err(|e| decode_error(e.to_string())),
+        }
+    }
+
+    impl<T: IntValue> FieldKind<T> for Int32 {
+        fn is_default(value: &T) -> bool {
+            *value == T::default()
+        }
+
+        fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+            value.serialize(serializer)
+        }
+
+        fn parse(value: Value) -> Result<T, Error> {
+            parse_int(value)
+        }
+    }
+
+    impl<T: IntValue + ToString> FieldKind<T> for Int64 {
+        fn is_default(value: &T) -> bool {
+            *value == T::default()
+        }
+
+        fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&value.to_string())
+        }
+
+        fn parse(value: Value) -> Result<T, Error> {
+            parse_int(value)
+        }
+    }
+
+    impl FieldKind<Vec<u8>> for Bytes {
+        fn is_default(value: &Vec<u8>) -> bool {
+            value.is_empty()
+        }
+
+        fn serialize<S: Serializer>(value: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+            // base64 is ASCII
+            serializer.serialize_str(&String::from_utf8(base64::encode(value)).unwrap())
+        }
+
+        fn parse(value: Value) -> Result<Vec<u8>, Error> {
+            let s = match value {
+                Value::String(s) => s,
+                v => return Err(decode_error(format!("invalid bytes: {}", v))),
+            };
+            // Go also accepts the URL-safe alphabet, and unpadded strings
+            let mut s = s.replace('-', "+").replace('_', "/");
+            while s.len() % 4 != 0 {
+                s.push('=');
+            }
+            base64::decode(&s).map_err(|e| decode_error(e.to_string()))
+        }
+    }
+
+    impl<E: Proto3Enum> FieldKind<i32> for Enum<E> {
+        fn is_default(value: &i32) -> bool {
+            *value == 0
+        }
+
+        fn serialize<S: Serializer>(value: &i32, serializer: S) -> Result<S::Ok, S::Error> {
+            // Unknown values are numbers, as in Go
+            match E::proto3_name(*value) {
+                Some(name) => serializer.serialize_str(name),
+                None => serializer.serialize_i32(*value),
+            }
+        }
+
+        fn parse(value: Value) -> Result<i32, Error> {
+            match value {
+                Value::String(s) => E::from_proto3_name(&s)
+                    .ok_or_else(|| decode_error(format!("unknown enum value: {:?}", s))),
+                value => i32::deserialize(value).map_err(|e| decode_error(e.to_string())),
+            }
+        }
+    }
+
+    impl<T: Proto3Json + Default + PartialEq> FieldKind<T> for Message {
+        fn is_default(value: &T) -> bool {
+            *value == T::default()
+        }
+
+        fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+            value.serialize_proto3(serializer)
+        }
+
+        fn parse(value: Value) -> Result<T, Error> {
+            T::from_proto3(value)
+        }
+    }
+
+    impl<T, K: FieldKind<T>> FieldKind<Option<T>> for Optional<K> {
+        fn is_default(value: &Option<T>) -> bool {
+            value.is_none()
+        }
+
+        fn serialize<S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => K::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        fn parse(value: Value) -> Result<Option<T>, Error> {
+            K::parse(value).map(Some)
+        }
+    }
+
+    impl<T: Default, K: FieldKind<T>> FieldKind<Vec<T>> for Repeated<K> {
+        fn is_default(value: &Vec<T>) -> bool {
+            value.is_empty()
+        }
+
+        fn serialize<S: Serializer>(value: &Vec<T>, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(value.len()))?;
+            for element in value {
+                seq.serialize_element(&With::<K, T>(element, PhantomData))?;
+            }
+            seq.end()
+        }
+
+        fn parse(value: Value) -> Result<Vec<T>, Error> {
+            match value {
+                Value::Array(values) => values.into_iter().map(parse::<K, T>).collect(),
+                v => Err(decode_error(format!("invalid array: {}", v))),
+            }
+        }
+    }
+
+    struct With<'a, K, T>(&'a T, PhantomData<K>);
+
+    impl<K: FieldKind<T>, T> Serialize for With<'_, K, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            K::serialize(self.0, serializer)
+        }
+    }
+
+    /// Write a field, unless it has its default value.
+    pub fn field<K: FieldKind<T>, T, M: SerializeMap>(
+        map: &mut M,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        if K::is_default(value) {
+            return Ok(());
+        }
+        entry::<K, T, M>(map, name, value)
+    }
+
+    /// Write a field, even if it has its default value, as for the members
+    /// of a oneof.
+    pub fn entry<K: FieldKind<T>, T, M: SerializeMap>(
+        map: &mut M,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        map.serialize_entry(name, &With::<K, T>(value, PhantomData))
+    }
+
+    /// Parse a value, null being the default value.
+    pub fn parse<K: FieldKind<T>, T: Default>(value: Value) -> Result<T, Error> {
+        match value {
+            Value::Null => Ok(T::default()),
+            value => K::parse(value),
+        }
+    }
+
+    /// The fields of a message.
+    pub fn object(value: Value) -> Result<Map<String, Value>, Error> {
+        match value {
+            Value::Object(map) => Ok(map),
+            v => Err(decode_error(format!("invalid message: {}", v))),
+        }
+    }
+
+    /// Take the value of a field, if it is the one with the given JSON or
+    /// protobuf name.
+    pub fn take(name: &str, json: &str, proto: &str, value: &mut Option<Value>) -> Option<Value> {
+        if name == json || name == proto {
+            value.take()
+        } else {
+            None
+        }
+    }
+
+    /// Error for a field which the message doesn't have.
+    pub fn unknown_field(name: &str) -> Error {
+        decode_error(format!("unknown field {:?}", name))
+    }
+}
+
+/// Implement [`Proto3Json`] for messages, given the kinds and the JSON and
+/// protobuf names of their fields, in order.
+///
+/// ```ignore
+/// proto3_json! {
+///     PublicKey {
+///         (oneof sum: public_key::Sum {
+///             Ed25519(Bytes), "ed25519", "ed25519";
+///         })
+///     }
+///     ValidatorUpdate {
+///         (pub_key: Optional<Message>, "pubKey", "pub_key")
+///         (power: Int64, "power", "power")
+///     }
+/// }
+/// ```
+macro_rules! proto3_json {
+    (@serialize $message:ident, $map:ident,
+        oneof $field:ident: $oneof:ty { $($variant:ident($kind:ty), $json:literal, $proto:literal;)* }
+    ) => {{
+        type Oneof = $oneof;
+        match &$message.$field {
+            $(Some(Oneof::$variant(value)) => entry::<$kind, _, _>(&mut $map, $json, value)?,)*
+            None => {}
+        }
+    }};
+    (@serialize $message:ident, $map:ident,
+        $field:ident: $kind:ty, $json:literal, $proto:literal
+    ) => {
+        field::<$kind, _, _>(&mut $map, $json, &$message.$field)?
+    };
+    (@parse $message:ident, $name:ident, $value:ident,
+        oneof $field:ident: $oneof:ty { $($variant:ident($kind:ty), $json:literal, $proto:literal;)* }
+    ) => {{
+        type Oneof = $oneof;
+        $(
+            if let Some(value) = take(&$name, $json, $proto, &mut $value) {
+                if !value.is_null() {
+                    $message.$field = Some(Oneof::$variant(<$kind as FieldKind<_>>::parse(value)?));
+                }
+            }
+        )*
+    }};
+    (@parse $message:ident, $name:ident, $value:ident,
+        $field:ident: $kind:ty, $json:literal, $proto:literal
+    ) => {
+        if let Some(value) = take(&$name, $json, $proto, &mut $value) {
+            $message.$field = parse::<$kind, _>(value)?;
+        }
+    };
+    ($($name:ty { $(($($field:tt)*))* })*) => {
+        $(
+            impl $crate::json::Proto3Json for $name {
+                fn serialize_proto3<S: ::serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> ::core::result::Result<S::Ok, S::Error> {
+                    #[allow(unused_imports)]
+                    use $crate::json::fields::*;
+                    use ::serde::ser::SerializeMap;
+                    #[allow(unused_mut)]
+                    let mut map = serializer.serialize_map(None)?;
+                    let message = self;
+                    $(proto3_json!(@serialize message, map, $($field)*);)*
+                    map.end()
+                }
+
+                fn from_proto3(
+                    value: ::serde_json::Value,
+                ) -> ::core::result::Result<Self, $crate::Error> {
+                    #[allow(unused_imports)]
+                    use $crate::json::fields::*;
+                    #[allow(unused_mut)]
+                    let mut message = Self::default();
+                    for (name, value) in object(value)? {
+                        #[allow(unused_mut)]
+                        let mut value = Some(value);
+                        $(proto3_json!(@parse message, name, value, $($field)*);)*
+                        if value.is_some() {
+                            return Err(unknown_field(&name));
+                        }
+                    }
+                    Ok(message)
+                }
+            }
+        )*
+    };
+}
+
+/// Implement [`Proto3Enum`] for enumerations, given the protobuf names of
+/// their values.
+macro_rules! proto3_enum {
+    ($($name:ty { $($variant:ident = $proto:literal,)* })*) => {
+        $(
+            impl $crate::json::Proto3Enum for $name {
+                fn proto3_name(value: i32) -> ::core::option::Option<&'static str> {
+                    type Enum = $name;
+                    $(
+                        if value == Enum::$variant as i32 {
+                            return Some($proto);
+                        }
+                    )*
+                    None
+                }
+
+                fn from_proto3_name(name: &str) -> ::core::option::Option<i32> {
+                    type Enum = $name;
+                    match name {
+                        $($proto => Some(Enum::$variant as i32),)*
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
@@ -1,10 +1,43 @@
 //! tendermint-proto library gives the developer access to the Tendermint proto-defined structs.
+//!
+//! The protos of each supported version live side by side in their own
+//! module: [`v0_34`] for Tendermint v0.34, and `v0_37` and `v0_38` for
+//! CometBFT v0.37 and v0.38, behind the features of the same names. The
+//! Tendermint v0.34 protos are also re-exported at the root of the crate.
+//!
+//! With the `grpc` feature, the gRPC services (`BroadcastAPI` and the ABCI
+//! application) also come with [tonic] client and server stubs, e.g.
+//! `abci::abci_application_client::AbciApplicationClient` and
+//! `abci::abci_application_server::AbciApplicationServer`.
+//!
+//! The messages can also be encoded in the canonical proto3 JSON format of
+//! Go's `jsonpb`, with the [`json`] module. Their `Serialize` and
+//! `Deserialize` implementations follow the JSON format of the Tendermint RPC
+//! instead.
+//!
+//! ## `no_std` support
+//!
+//! The protobuf messages, their conversions and serializers only require
+//! `alloc`, so that e.g. headers and commits can be decoded on targets
+//! without the standard library, such as `wasm32` and on-chain verifiers.
+//! Building without the `std` feature, which is enabled by default, drops
+//! error backtraces and the `std::error::Error` implementations, as well as
+//! the gRPC stubs.
+//!
+//! [tonic]: https://docs.rs/tonic
 
+// The gRPC stubs, which require `std`, rely on its prelude
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings, trivial_casts, trivial_numeric_casts, unused_import_braces)]
 #![allow(clippy::large_enum_variant)]
 #![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc(html_root_url = "https://docs.rs/tendermint-proto/0.19.0")]
 
+extern crate alloc;
+
+mod prelude;
+
 /// Built-in prost_types with slight customization to enable JSON-encoding
 #[allow(warnings)]
 pub mod google {
@@ -15,18 +48,24 @@ pub mod google {
     }
 }
 
+#[macro_use]
+pub mod json;
+
 #[allow(warnings)]
 mod tendermint;
 pub use tendermint::*;
+// The Tendermint v0.34 protos are also available at the root of the crate.
+pub use tendermint::v0_34::*;
 
 mod error;
-use anomaly::BoxError;
 use bytes::{Buf, BufMut};
-pub use error::{Error, Kind};
+use core::convert::{TryFrom, TryInto};
+pub use error::{BoxError, Error, Kind};
+use prelude::*;
 use prost::encoding::encoded_len_varint;
 use prost::Message;
-use std::convert::{TryFrom, TryInto};
 
+pub mod canonical;
 pub mod serializers;
 
 /// Allows for easy Google Protocol Buffers encoding and decoding of domain
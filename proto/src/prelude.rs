@@ -0,0 +1,7 @@
+//! Re-exports of the items of the standard prelude which live in `alloc`,
+//! since the crate is `no_std` without the `std` feature
+
+pub use alloc::string::{String, ToString};
+pub use alloc::vec::Vec;
+
+pub use alloc::format;
@@ -14,7 +14,7 @@ pub struct NoBlockResponse {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BlockResponse {
     #[prost(message, optional, tag="1")]
-    pub block: ::std::option::Option<super::types::Block>,
+    pub block: ::core::option::Option<super::types::Block>,
 }
 /// StatusRequest requests the status of a peer.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -31,7 +31,7 @@ pub struct StatusResponse {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Message {
     #[prost(oneof="message::Sum", tags="1, 2, 3, 4, 5")]
-    pub sum: ::std::option::Option<message::Sum>,
+    pub sum: ::core::option::Option<message::Sum>,
 }
 pub mod message {
     #[derive(Clone, PartialEq, ::prost::Oneof)]
@@ -48,3 +48,30 @@ pub mod message {
         StatusResponse(super::StatusResponse),
     }
 }
+
+proto3_json! {
+    BlockRequest {
+        (height: Int64, "height", "height")
+    }
+    NoBlockResponse {
+        (height: Int64, "height", "height")
+    }
+    BlockResponse {
+        (block: Optional<Message>, "block", "block")
+    }
+    StatusRequest {
+    }
+    StatusResponse {
+        (height: Int64, "height", "height")
+        (base: Int64, "base", "base")
+    }
+    Message {
+        (oneof sum: message::Sum {
+            BlockRequest(Message), "blockRequest", "block_request";
+            NoBlockResponse(Message), "noBlockResponse", "no_block_response";
+            BlockResponse(Message), "blockResponse", "block_response";
+            StatusRequest(Message), "statusRequest", "status_request";
+            StatusResponse(Message), "statusResponse", "status_response";
+        })
+    }
+}
@@ -4,5 +4,12 @@ pub struct BitArray {
     #[prost(int64, tag="1")]
     pub bits: i64,
     #[prost(uint64, repeated, tag="2")]
-    pub elems: ::std::vec::Vec<u64>,
+    pub elems: ::prost::alloc::vec::Vec<u64>,
+}
+
+proto3_json! {
+    BitArray {
+        (bits: Int64, "bits", "bits")
+        (elems: Repeated<Int64>, "elems", "elems")
+    }
 }
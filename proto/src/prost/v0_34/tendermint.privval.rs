@@ -3,53 +3,53 @@ pub struct RemoteSignerError {
     #[prost(int32, tag="1")]
     pub code: i32,
     #[prost(string, tag="2")]
-    pub description: std::string::String,
+    pub description: ::prost::alloc::string::String,
 }
 /// PubKeyRequest requests the consensus public key from the remote signer.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PubKeyRequest {
     #[prost(string, tag="1")]
-    pub chain_id: std::string::String,
+    pub chain_id: ::prost::alloc::string::String,
 }
 /// PubKeyResponse is a response message containing the public key.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PubKeyResponse {
     #[prost(message, optional, tag="1")]
-    pub pub_key: ::std::option::Option<super::crypto::PublicKey>,
+    pub pub_key: ::core::option::Option<super::crypto::PublicKey>,
     #[prost(message, optional, tag="2")]
-    pub error: ::std::option::Option<RemoteSignerError>,
+    pub error: ::core::option::Option<RemoteSignerError>,
 }
 /// SignVoteRequest is a request to sign a vote
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignVoteRequest {
     #[prost(message, optional, tag="1")]
-    pub vote: ::std::option::Option<super::types::Vote>,
+    pub vote: ::core::option::Option<super::types::Vote>,
     #[prost(string, tag="2")]
-    pub chain_id: std::string::String,
+    pub chain_id: ::prost::alloc::string::String,
 }
 /// SignedVoteResponse is a response containing a signed vote or an error
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignedVoteResponse {
     #[prost(message, optional, tag="1")]
-    pub vote: ::std::option::Option<super::types::Vote>,
+    pub vote: ::core::option::Option<super::types::Vote>,
     #[prost(message, optional, tag="2")]
-    pub error: ::std::option::Option<RemoteSignerError>,
+    pub error: ::core::option::Option<RemoteSignerError>,
 }
 /// SignProposalRequest is a request to sign a proposal
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignProposalRequest {
     #[prost(message, optional, tag="1")]
-    pub proposal: ::std::option::Option<super::types::Proposal>,
+    pub proposal: ::core::option::Option<super::types::Proposal>,
     #[prost(string, tag="2")]
-    pub chain_id: std::string::String,
+    pub chain_id: ::prost::alloc::string::String,
 }
 /// SignedProposalResponse is response containing a signed proposal or an error
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignedProposalResponse {
     #[prost(message, optional, tag="1")]
-    pub proposal: ::std::option::Option<super::types::Proposal>,
+    pub proposal: ::core::option::Option<super::types::Proposal>,
     #[prost(message, optional, tag="2")]
-    pub error: ::std::option::Option<RemoteSignerError>,
+    pub error: ::core::option::Option<RemoteSignerError>,
 }
 /// PingRequest is a request to confirm that the connection is alive.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -62,7 +62,7 @@ pub struct PingResponse {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Message {
     #[prost(oneof="message::Sum", tags="1, 2, 3, 4, 5, 6, 7, 8")]
-    pub sum: ::std::option::Option<message::Sum>,
+    pub sum: ::core::option::Option<message::Sum>,
 }
 pub mod message {
     #[derive(Clone, PartialEq, ::prost::Oneof)]
@@ -95,3 +95,60 @@ pub enum Errors {
     ReadTimeout = 4,
     WriteTimeout = 5,
 }
+
+proto3_json! {
+    RemoteSignerError {
+        (code: Int32, "code", "code")
+        (description: Plain, "description", "description")
+    }
+    PubKeyRequest {
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    PubKeyResponse {
+        (pub_key: Optional<Message>, "pubKey", "pub_key")
+        (error: Optional<Message>, "error", "error")
+    }
+    SignVoteRequest {
+        (vote: Optional<Message>, "vote", "vote")
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    SignedVoteResponse {
+        (vote: Optional<Message>, "vote", "vote")
+        (error: Optional<Message>, "error", "error")
+    }
+    SignProposalRequest {
+        (proposal: Optional<Message>, "proposal", "proposal")
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    SignedProposalResponse {
+        (proposal: Optional<Message>, "proposal", "proposal")
+        (error: Optional<Message>, "error", "error")
+    }
+    PingRequest {
+    }
+    PingResponse {
+    }
+    Message {
+        (oneof sum: message::Sum {
+            PubKeyRequest(Message), "pubKeyRequest", "pub_key_request";
+            PubKeyResponse(Message), "pubKeyResponse", "pub_key_response";
+            SignVoteRequest(Message), "signVoteRequest", "sign_vote_request";
+            SignedVoteResponse(Message), "signedVoteResponse", "signed_vote_response";
+            SignProposalRequest(Message), "signProposalRequest", "sign_proposal_request";
+            SignedProposalResponse(Message), "signedProposalResponse", "signed_proposal_response";
+            PingRequest(Message), "pingRequest", "ping_request";
+            PingResponse(Message), "pingResponse", "ping_response";
+        })
+    }
+}
+
+proto3_enum! {
+    Errors {
+        Unknown = "ERRORS_UNKNOWN",
+        UnexpectedResponse = "ERRORS_UNEXPECTED_RESPONSE",
+        NoConnection = "ERRORS_NO_CONNECTION",
+        ConnectionTimeout = "ERRORS_CONNECTION_TIMEOUT",
+        ReadTimeout = "ERRORS_READ_TIMEOUT",
+        WriteTimeout = "ERRORS_WRITE_TIMEOUT",
+    }
+}
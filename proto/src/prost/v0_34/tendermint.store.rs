@@ -5,3 +5,10 @@ pub struct BlockStoreState {
     #[prost(int64, tag="2")]
     pub height: i64,
 }
+
+proto3_json! {
+    BlockStoreState {
+        (base: Int64, "base", "base")
+        (height: Int64, "height", "height")
+    }
+}
@@ -6,7 +6,7 @@ pub struct App {
     #[prost(uint64, tag="1")]
     pub protocol: u64,
     #[prost(string, tag="2")]
-    pub software: std::string::String,
+    pub software: ::prost::alloc::string::String,
 }
 /// Consensus captures the consensus rules for processing a block in the blockchain,
 /// including all blockchain data structures and the rules of the application's
@@ -21,3 +21,14 @@ pub struct Consensus {
     #[serde(with = "crate::serializers::from_str", default)]
     pub app: u64,
 }
+
+proto3_json! {
+    App {
+        (protocol: Int64, "protocol", "protocol")
+        (software: Plain, "software", "software")
+    }
+    Consensus {
+        (block: Int64, "block", "block")
+        (app: Int64, "app", "app")
+    }
+}
@@ -0,0 +1,154 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoteSignerError {
+    #[prost(int32, tag="1")]
+    pub code: i32,
+    #[prost(string, tag="2")]
+    pub description: ::prost::alloc::string::String,
+}
+/// PubKeyRequest requests the consensus public key from the remote signer.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PubKeyRequest {
+    #[prost(string, tag="1")]
+    pub chain_id: ::prost::alloc::string::String,
+}
+/// PubKeyResponse is a response message containing the public key.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PubKeyResponse {
+    #[prost(message, optional, tag="1")]
+    pub pub_key: ::core::option::Option<super::crypto::PublicKey>,
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<RemoteSignerError>,
+}
+/// SignVoteRequest is a request to sign a vote
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignVoteRequest {
+    #[prost(message, optional, tag="1")]
+    pub vote: ::core::option::Option<super::types::Vote>,
+    #[prost(string, tag="2")]
+    pub chain_id: ::prost::alloc::string::String,
+}
+/// SignedVoteResponse is a response containing a signed vote or an error
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedVoteResponse {
+    #[prost(message, optional, tag="1")]
+    pub vote: ::core::option::Option<super::types::Vote>,
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<RemoteSignerError>,
+}
+/// SignProposalRequest is a request to sign a proposal
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignProposalRequest {
+    #[prost(message, optional, tag="1")]
+    pub proposal: ::core::option::Option<super::types::Proposal>,
+    #[prost(string, tag="2")]
+    pub chain_id: ::prost::alloc::string::String,
+}
+/// SignedProposalResponse is response containing a signed proposal or an error
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedProposalResponse {
+    #[prost(message, optional, tag="1")]
+    pub proposal: ::core::option::Option<super::types::Proposal>,
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<RemoteSignerError>,
+}
+/// PingRequest is a request to confirm that the connection is alive.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingRequest {
+}
+/// PingResponse is a response to confirm that the connection is alive.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingResponse {
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Message {
+    #[prost(oneof="message::Sum", tags="1, 2, 3, 4, 5, 6, 7, 8")]
+    pub sum: ::core::option::Option<message::Sum>,
+}
+pub mod message {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Sum {
+        #[prost(message, tag="1")]
+        PubKeyRequest(super::PubKeyRequest),
+        #[prost(message, tag="2")]
+        PubKeyResponse(super::PubKeyResponse),
+        #[prost(message, tag="3")]
+        SignVoteRequest(super::SignVoteRequest),
+        #[prost(message, tag="4")]
+        SignedVoteResponse(super::SignedVoteResponse),
+        #[prost(message, tag="5")]
+        SignProposalRequest(super::SignProposalRequest),
+        #[prost(message, tag="6")]
+        SignedProposalResponse(super::SignedProposalResponse),
+        #[prost(message, tag="7")]
+        PingRequest(super::PingRequest),
+        #[prost(message, tag="8")]
+        PingResponse(super::PingResponse),
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Errors {
+    Unknown = 0,
+    UnexpectedResponse = 1,
+    NoConnection = 2,
+    ConnectionTimeout = 3,
+    ReadTimeout = 4,
+    WriteTimeout = 5,
+}
+
+proto3_json! {
+    RemoteSignerError {
+        (code: Int32, "code", "code")
+        (description: Plain, "description", "description")
+    }
+    PubKeyRequest {
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    PubKeyResponse {
+        (pub_key: Optional<Message>, "pubKey", "pub_key")
+        (error: Optional<Message>, "error", "error")
+    }
+    SignVoteRequest {
+        (vote: Optional<Message>, "vote", "vote")
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    SignedVoteResponse {
+        (vote: Optional<Message>, "vote", "vote")
+        (error: Optional<Message>, "error", "error")
+    }
+    SignProposalRequest {
+        (proposal: Optional<Message>, "proposal", "proposal")
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    SignedProposalResponse {
+        (proposal: Optional<Message>, "proposal", "proposal")
+        (error: Optional<Message>, "error", "error")
+    }
+    PingRequest {
+    }
+    PingResponse {
+    }
+    Message {
+        (oneof sum: message::Sum {
+            PubKeyRequest(Message), "pubKeyRequest", "pub_key_request";
+            PubKeyResponse(Message), "pubKeyResponse", "pub_key_response";
+            SignVoteRequest(Message), "signVoteRequest", "sign_vote_request";
+            SignedVoteResponse(Message), "signedVoteResponse", "signed_vote_response";
+            SignProposalRequest(Message), "signProposalRequest", "sign_proposal_request";
+            SignedProposalResponse(Message), "signedProposalResponse", "signed_proposal_response";
+            PingRequest(Message), "pingRequest", "ping_request";
+            PingResponse(Message), "pingResponse", "ping_response";
+        })
+    }
+}
+
+proto3_enum! {
+    Errors {
+        Unknown = "ERRORS_UNKNOWN",
+        UnexpectedResponse = "ERRORS_UNEXPECTED_RESPONSE",
+        NoConnection = "ERRORS_NO_CONNECTION",
+        ConnectionTimeout = "ERRORS_CONNECTION_TIMEOUT",
+        ReadTimeout = "ERRORS_READ_TIMEOUT",
+        WriteTimeout = "ERRORS_WRITE_TIMEOUT",
+    }
+}
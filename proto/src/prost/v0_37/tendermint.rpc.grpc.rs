@@ -0,0 +1,261 @@
+//----------------------------------------
+// Request types
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestPing {
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestBroadcastTx {
+    #[prost(bytes, tag="1")]
+    pub tx: ::prost::alloc::vec::Vec<u8>,
+}
+//----------------------------------------
+// Response types
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponsePing {
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseBroadcastTx {
+    #[prost(message, optional, tag="1")]
+    pub check_tx: ::core::option::Option<super::super::abci::ResponseCheckTx>,
+    #[prost(message, optional, tag="2")]
+    pub deliver_tx: ::core::option::Option<super::super::abci::ResponseDeliverTx>,
+}
+
+proto3_json! {
+    RequestPing {
+    }
+    RequestBroadcastTx {
+        (tx: Bytes, "tx", "tx")
+    }
+    ResponsePing {
+    }
+    ResponseBroadcastTx {
+        (check_tx: Optional<Message>, "checkTx", "check_tx")
+        (deliver_tx: Optional<Message>, "deliverTx", "deliver_tx")
+    }
+}
+#[cfg(feature = "grpc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc")))]
+#[doc = r" Generated client implementations."]
+pub mod broadcast_api_client {
+    #![allow(unused_variables, dead_code, missing_docs)]
+    use tonic::codegen::*;
+    pub struct BroadcastApiClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl BroadcastApiClient<tonic::transport::Channel> {
+        #[doc = r" Attempt to create a new client by connecting to a given endpoint."]
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> BroadcastApiClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::ResponseBody: Body + HttpBody + Send + 'static,
+        T::Error: Into<StdError>,
+        <T::ResponseBody as HttpBody>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_interceptor(inner: T, interceptor: impl Into<tonic::Interceptor>) -> Self {
+            let inner = tonic::client::Grpc::with_interceptor(inner, interceptor);
+            Self { inner }
+        }
+        pub async fn ping(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestPing>,
+        ) -> Result<tonic::Response<super::ResponsePing>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/tendermint.rpc.grpc.BroadcastAPI/Ping");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn broadcast_tx(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestBroadcastTx>,
+        ) -> Result<tonic::Response<super::ResponseBroadcastTx>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/tendermint.rpc.grpc.BroadcastAPI/BroadcastTx",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+    impl<T: Clone> Clone for BroadcastApiClient<T> {
+        fn clone(&self) -> Self {
+            Self {
+                inner: self.inner.clone(),
+            }
+        }
+    }
+    impl<T> std::fmt::Debug for BroadcastApiClient<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "BroadcastApiClient {{ ... }}")
+        }
+    }
+}
+#[cfg(feature = "grpc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc")))]
+#[doc = r" Generated server implementations."]
+pub mod broadcast_api_server {
+    #![allow(unused_variables, dead_code, missing_docs)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with BroadcastApiServer."]
+    #[async_trait]
+    pub trait BroadcastApi: Send + Sync + 'static {
+        async fn ping(
+            &self,
+            request: tonic::Request<super::RequestPing>,
+        ) -> Result<tonic::Response<super::ResponsePing>, tonic::Status>;
+        async fn broadcast_tx(
+            &self,
+            request: tonic::Request<super::RequestBroadcastTx>,
+        ) -> Result<tonic::Response<super::ResponseBroadcastTx>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct BroadcastApiServer<T: BroadcastApi> {
+        inner: _Inner<T>,
+    }
+    struct _Inner<T>(Arc<T>, Option<tonic::Interceptor>);
+    impl<T: BroadcastApi> BroadcastApiServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner, None);
+            Self { inner }
+        }
+        pub fn with_interceptor(inner: T, interceptor: impl Into<tonic::Interceptor>) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner, Some(interceptor.into()));
+            Self { inner }
+        }
+    }
+    impl<T, B> Service<http::Request<B>> for BroadcastApiServer<T>
+    where
+        T: BroadcastApi,
+        B: HttpBody + Send + Sync + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/tendermint.rpc.grpc.BroadcastAPI/Ping" => {
+                    #[allow(non_camel_case_types)]
+                    struct PingSvc<T: BroadcastApi>(pub Arc<T>);
+                    impl<T: BroadcastApi> tonic::server::UnaryService<super::RequestPing> for PingSvc<T> {
+                        type Response = super::ResponsePing;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestPing>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).ping(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = PingSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.rpc.grpc.BroadcastAPI/BroadcastTx" => {
+                    #[allow(non_camel_case_types)]
+                    struct BroadcastTxSvc<T: BroadcastApi>(pub Arc<T>);
+                    impl<T: BroadcastApi> tonic::server::UnaryService<super::RequestBroadcastTx> for BroadcastTxSvc<T> {
+                        type Response = super::ResponseBroadcastTx;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestBroadcastTx>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).broadcast_tx(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = BroadcastTxSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(tonic::body::BoxBody::empty())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: BroadcastApi> Clone for BroadcastApiServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self { inner }
+        }
+    }
+    impl<T: BroadcastApi> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone(), self.1.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: BroadcastApi> tonic::transport::NamedService for BroadcastApiServer<T> {
+        const NAME: &'static str = "tendermint.rpc.grpc.BroadcastAPI";
+    }
+}
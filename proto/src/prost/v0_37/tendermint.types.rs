@@ -2,9 +2,9 @@
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct ValidatorSet {
     #[prost(message, repeated, tag="1")]
-    pub validators: ::std::vec::Vec<Validator>,
+    pub validators: ::prost::alloc::vec::Vec<Validator>,
     #[prost(message, optional, tag="2")]
-    pub proposer: ::std::option::Option<Validator>,
+    pub proposer: ::core::option::Option<Validator>,
     #[prost(int64, tag="3")]
     pub total_voting_power: i64,
 }
@@ -13,9 +13,9 @@ pub struct ValidatorSet {
 pub struct Validator {
     #[prost(bytes, tag="1")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub address: std::vec::Vec<u8>,
+    pub address: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag="2")]
-    pub pub_key: ::std::option::Option<super::crypto::PublicKey>,
+    pub pub_key: ::core::option::Option<super::crypto::PublicKey>,
     #[prost(int64, tag="3")]
     #[serde(alias = "power", with = "crate::serializers::from_str")]
     pub voting_power: i64,
@@ -26,7 +26,7 @@ pub struct Validator {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SimpleValidator {
     #[prost(message, optional, tag="1")]
-    pub pub_key: ::std::option::Option<super::crypto::PublicKey>,
+    pub pub_key: ::core::option::Option<super::crypto::PublicKey>,
     #[prost(int64, tag="2")]
     pub voting_power: i64,
 }
@@ -39,16 +39,18 @@ pub struct PartSetHeader {
     pub total: u32,
     #[prost(bytes, tag="2")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub hash: std::vec::Vec<u8>,
+    pub hash: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct Part {
     #[prost(uint32, tag="1")]
     pub index: u32,
     #[prost(bytes, tag="2")]
-    pub bytes: std::vec::Vec<u8>,
+    #[serde(with = "crate::serializers::bytes::hexstring")]
+    pub bytes: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag="3")]
-    pub proof: ::std::option::Option<super::crypto::Proof>,
+    pub proof: ::core::option::Option<super::crypto::Proof>,
 }
 /// BlockID
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -56,10 +58,10 @@ pub struct Part {
 pub struct BlockId {
     #[prost(bytes, tag="1")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub hash: std::vec::Vec<u8>,
+    pub hash: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag="2")]
     #[serde(alias = "parts")]
-    pub part_set_header: ::std::option::Option<PartSetHeader>,
+    pub part_set_header: ::core::option::Option<PartSetHeader>,
 }
 // --------------------------------
 
@@ -69,60 +71,60 @@ pub struct BlockId {
 pub struct Header {
     /// basic block info
     #[prost(message, optional, tag="1")]
-    pub version: ::std::option::Option<super::version::Consensus>,
+    pub version: ::core::option::Option<super::version::Consensus>,
     #[prost(string, tag="2")]
-    pub chain_id: std::string::String,
+    pub chain_id: ::prost::alloc::string::String,
     #[prost(int64, tag="3")]
     #[serde(with = "crate::serializers::from_str")]
     pub height: i64,
     #[prost(message, optional, tag="4")]
     #[serde(with = "crate::serializers::optional")]
-    pub time: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub time: ::core::option::Option<crate::google::protobuf::Timestamp>,
     /// prev block info
     #[prost(message, optional, tag="5")]
-    pub last_block_id: ::std::option::Option<BlockId>,
+    pub last_block_id: ::core::option::Option<BlockId>,
     /// hashes of block data
     ///
     /// commit from validators from the last block
     #[prost(bytes, tag="6")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub last_commit_hash: std::vec::Vec<u8>,
+    pub last_commit_hash: ::prost::alloc::vec::Vec<u8>,
     /// transactions
     #[prost(bytes, tag="7")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub data_hash: std::vec::Vec<u8>,
+    pub data_hash: ::prost::alloc::vec::Vec<u8>,
     /// hashes from the app output from the prev block
     ///
     /// validators for the current block
     #[prost(bytes, tag="8")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub validators_hash: std::vec::Vec<u8>,
+    pub validators_hash: ::prost::alloc::vec::Vec<u8>,
     /// validators for the next block
     #[prost(bytes, tag="9")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub next_validators_hash: std::vec::Vec<u8>,
+    pub next_validators_hash: ::prost::alloc::vec::Vec<u8>,
     /// consensus params for current block
     #[prost(bytes, tag="10")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub consensus_hash: std::vec::Vec<u8>,
+    pub consensus_hash: ::prost::alloc::vec::Vec<u8>,
     /// state after txs from the previous block
     #[prost(bytes, tag="11")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub app_hash: std::vec::Vec<u8>,
+    pub app_hash: ::prost::alloc::vec::Vec<u8>,
     /// root hash of all results from the txs from the previous block
     #[prost(bytes, tag="12")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub last_results_hash: std::vec::Vec<u8>,
+    pub last_results_hash: ::prost::alloc::vec::Vec<u8>,
     /// consensus info
     ///
     /// evidence included in the block
     #[prost(bytes, tag="13")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub evidence_hash: std::vec::Vec<u8>,
+    pub evidence_hash: ::prost::alloc::vec::Vec<u8>,
     /// original proposer of the block
     #[prost(bytes, tag="14")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub proposer_address: std::vec::Vec<u8>,
+    pub proposer_address: ::prost::alloc::vec::Vec<u8>,
 }
 /// Data contains the set of transactions included in the block
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -133,7 +135,7 @@ pub struct Data {
     /// This means that block.AppHash does not include these txs.
     #[prost(bytes, repeated, tag="1")]
     #[serde(with = "crate::serializers::txs")]
-    pub txs: ::std::vec::Vec<std::vec::Vec<u8>>,
+    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
 /// Vote represents a prevote, precommit, or commit vote from validators for
 /// consensus.
@@ -150,19 +152,19 @@ pub struct Vote {
     pub round: i32,
     /// zero if vote is nil.
     #[prost(message, optional, tag="4")]
-    pub block_id: ::std::option::Option<BlockId>,
+    pub block_id: ::core::option::Option<BlockId>,
     #[prost(message, optional, tag="5")]
     #[serde(with = "crate::serializers::optional")]
-    pub timestamp: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
     #[prost(bytes, tag="6")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub validator_address: std::vec::Vec<u8>,
+    pub validator_address: ::prost::alloc::vec::Vec<u8>,
     #[prost(int32, tag="7")]
     #[serde(with = "crate::serializers::from_str")]
     pub validator_index: i32,
     #[prost(bytes, tag="8")]
     #[serde(with = "crate::serializers::bytes::base64string")]
-    pub signature: std::vec::Vec<u8>,
+    pub signature: ::prost::alloc::vec::Vec<u8>,
 }
 /// Commit contains the evidence that a block was committed by a set of validators.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -174,10 +176,10 @@ pub struct Commit {
     #[prost(int32, tag="2")]
     pub round: i32,
     #[prost(message, optional, tag="3")]
-    pub block_id: ::std::option::Option<BlockId>,
+    pub block_id: ::core::option::Option<BlockId>,
     #[prost(message, repeated, tag="4")]
     #[serde(with = "crate::serializers::nullable")]
-    pub signatures: ::std::vec::Vec<CommitSig>,
+    pub signatures: ::prost::alloc::vec::Vec<CommitSig>,
 }
 /// CommitSig is a part of the Vote included in a Commit.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -187,13 +189,13 @@ pub struct CommitSig {
     pub block_id_flag: i32,
     #[prost(bytes, tag="2")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub validator_address: std::vec::Vec<u8>,
+    pub validator_address: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag="3")]
     #[serde(with = "crate::serializers::optional")]
-    pub timestamp: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
     #[prost(bytes, tag="4")]
     #[serde(with = "crate::serializers::bytes::base64string")]
-    pub signature: std::vec::Vec<u8>,
+    pub signature: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Proposal {
@@ -206,38 +208,38 @@ pub struct Proposal {
     #[prost(int32, tag="4")]
     pub pol_round: i32,
     #[prost(message, optional, tag="5")]
-    pub block_id: ::std::option::Option<BlockId>,
+    pub block_id: ::core::option::Option<BlockId>,
     #[prost(message, optional, tag="6")]
-    pub timestamp: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
     #[prost(bytes, tag="7")]
-    pub signature: std::vec::Vec<u8>,
+    pub signature: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct SignedHeader {
     #[prost(message, optional, tag="1")]
-    pub header: ::std::option::Option<Header>,
+    pub header: ::core::option::Option<Header>,
     #[prost(message, optional, tag="2")]
-    pub commit: ::std::option::Option<Commit>,
+    pub commit: ::core::option::Option<Commit>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct LightBlock {
     #[prost(message, optional, tag="1")]
-    pub signed_header: ::std::option::Option<SignedHeader>,
+    pub signed_header: ::core::option::Option<SignedHeader>,
     #[prost(message, optional, tag="2")]
-    pub validator_set: ::std::option::Option<ValidatorSet>,
+    pub validator_set: ::core::option::Option<ValidatorSet>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct BlockMeta {
     #[prost(message, optional, tag="1")]
-    pub block_id: ::std::option::Option<BlockId>,
+    pub block_id: ::core::option::Option<BlockId>,
     #[prost(int64, tag="2")]
     #[serde(with = "crate::serializers::from_str")]
     pub block_size: i64,
     #[prost(message, optional, tag="3")]
-    pub header: ::std::option::Option<Header>,
+    pub header: ::core::option::Option<Header>,
     #[prost(int64, tag="4")]
     #[serde(with = "crate::serializers::from_str")]
     pub num_txs: i64,
@@ -248,12 +250,12 @@ pub struct BlockMeta {
 pub struct TxProof {
     #[prost(bytes, tag="1")]
     #[serde(with = "crate::serializers::bytes::hexstring")]
-    pub root_hash: std::vec::Vec<u8>,
+    pub root_hash: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes, tag="2")]
     #[serde(with = "crate::serializers::bytes::base64string")]
-    pub data: std::vec::Vec<u8>,
+    pub data: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag="3")]
-    pub proof: ::std::option::Option<super::crypto::Proof>,
+    pub proof: ::core::option::Option<super::crypto::Proof>,
 }
 /// BlockIdFlag indicates which BlcokID the signature is for
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -283,20 +285,20 @@ pub struct EventDataRoundState {
     #[prost(int32, tag="2")]
     pub round: i32,
     #[prost(string, tag="3")]
-    pub step: std::string::String,
+    pub step: ::prost::alloc::string::String,
 }
 /// ConsensusParams contains consensus critical parameters that determine the
 /// validity of blocks.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ConsensusParams {
     #[prost(message, optional, tag="1")]
-    pub block: ::std::option::Option<BlockParams>,
+    pub block: ::core::option::Option<BlockParams>,
     #[prost(message, optional, tag="2")]
-    pub evidence: ::std::option::Option<EvidenceParams>,
+    pub evidence: ::core::option::Option<EvidenceParams>,
     #[prost(message, optional, tag="3")]
-    pub validator: ::std::option::Option<ValidatorParams>,
+    pub validator: ::core::option::Option<ValidatorParams>,
     #[prost(message, optional, tag="4")]
-    pub version: ::std::option::Option<VersionParams>,
+    pub version: ::core::option::Option<VersionParams>,
 }
 /// BlockParams contains limits on the block size.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -309,12 +311,6 @@ pub struct BlockParams {
     /// Note: must be greater or equal to -1
     #[prost(int64, tag="2")]
     pub max_gas: i64,
-    /// Minimum time increment between consecutive blocks (in milliseconds) If the
-    /// block header timestamp is ahead of the system clock, decrease this value.
-    ///
-    /// Not exposed to the application.
-    #[prost(int64, tag="3")]
-    pub time_iota_ms: i64,
 }
 /// EvidenceParams determine how we handle evidence of malfeasance.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -332,7 +328,7 @@ pub struct EvidenceParams {
     /// mechanism for handling [Nothing-At-Stake
     /// attacks](https://github.com/ethereum/wiki/wiki/Proof-of-Stake-FAQ#what-is-the-nothing-at-stake-problem-and-how-can-it-be-fixed).
     #[prost(message, optional, tag="2")]
-    pub max_age_duration: ::std::option::Option<super::super::google::protobuf::Duration>,
+    pub max_age_duration: ::core::option::Option<crate::google::protobuf::Duration>,
     /// This sets the maximum size of total evidence in bytes that can be committed in a single block.
     /// and should fall comfortably under the max block bytes.
     /// Default is 1048576 or 1MB
@@ -345,13 +341,13 @@ pub struct EvidenceParams {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ValidatorParams {
     #[prost(string, repeated, tag="1")]
-    pub pub_key_types: ::std::vec::Vec<std::string::String>,
+    pub pub_key_types: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 /// VersionParams contains the ABCI application version.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct VersionParams {
     #[prost(uint64, tag="1")]
-    pub app_version: u64,
+    pub app: u64,
 }
 /// HashedParams is a subset of ConsensusParams.
 ///
@@ -365,16 +361,16 @@ pub struct HashedParams {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
-#[serde(from = "crate::serializers::evidence::EvidenceVariant", into = "crate::serializers::evidence::EvidenceVariant")]
+#[serde(from = "crate::serializers::evidence::v0_37::EvidenceVariant", into = "crate::serializers::evidence::v0_37::EvidenceVariant")]
 pub struct Evidence {
     #[prost(oneof="evidence::Sum", tags="1, 2")]
-    pub sum: ::std::option::Option<evidence::Sum>,
+    pub sum: ::core::option::Option<evidence::Sum>,
 }
 pub mod evidence {
     #[derive(Clone, PartialEq, ::prost::Oneof)]
     #[derive(::serde::Deserialize, ::serde::Serialize)]
     #[serde(tag = "type", content = "value")]
-    #[serde(from = "crate::serializers::evidence::EvidenceVariant", into = "crate::serializers::evidence::EvidenceVariant")]
+    #[serde(from = "crate::serializers::evidence::v0_37::EvidenceVariant", into = "crate::serializers::evidence::v0_37::EvidenceVariant")]
     pub enum Sum {
         #[prost(message, tag="1")]
         #[serde(rename = "tendermint/DuplicateVoteEvidence")]
@@ -389,46 +385,48 @@ pub mod evidence {
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct DuplicateVoteEvidence {
     #[prost(message, optional, tag="1")]
-    pub vote_a: ::std::option::Option<Vote>,
+    pub vote_a: ::core::option::Option<Vote>,
     #[prost(message, optional, tag="2")]
-    pub vote_b: ::std::option::Option<Vote>,
+    pub vote_b: ::core::option::Option<Vote>,
     #[prost(int64, tag="3")]
     pub total_voting_power: i64,
     #[prost(int64, tag="4")]
     pub validator_power: i64,
     #[prost(message, optional, tag="5")]
-    pub timestamp: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
 }
 /// LightClientAttackEvidence contains evidence of a set of validators attempting to mislead a light client.
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct LightClientAttackEvidence {
     #[prost(message, optional, tag="1")]
-    pub conflicting_block: ::std::option::Option<LightBlock>,
+    pub conflicting_block: ::core::option::Option<LightBlock>,
     #[prost(int64, tag="2")]
+    #[serde(with = "crate::serializers::from_str")]
     pub common_height: i64,
     #[prost(message, repeated, tag="3")]
-    pub byzantine_validators: ::std::vec::Vec<Validator>,
+    pub byzantine_validators: ::prost::alloc::vec::Vec<Validator>,
     #[prost(int64, tag="4")]
+    #[serde(with = "crate::serializers::from_str")]
     pub total_voting_power: i64,
     #[prost(message, optional, tag="5")]
-    pub timestamp: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct EvidenceList {
     #[prost(message, repeated, tag="1")]
     #[serde(with = "crate::serializers::nullable")]
-    pub evidence: ::std::vec::Vec<Evidence>,
+    pub evidence: ::prost::alloc::vec::Vec<Evidence>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct CanonicalBlockId {
     #[prost(bytes, tag="1")]
-    pub hash: std::vec::Vec<u8>,
+    pub hash: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag="2")]
     #[serde(alias = "parts")]
-    pub part_set_header: ::std::option::Option<CanonicalPartSetHeader>,
+    pub part_set_header: ::core::option::Option<CanonicalPartSetHeader>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
@@ -436,7 +434,7 @@ pub struct CanonicalPartSetHeader {
     #[prost(uint32, tag="1")]
     pub total: u32,
     #[prost(bytes, tag="2")]
-    pub hash: std::vec::Vec<u8>,
+    pub hash: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CanonicalProposal {
@@ -452,11 +450,11 @@ pub struct CanonicalProposal {
     #[prost(int64, tag="4")]
     pub pol_round: i64,
     #[prost(message, optional, tag="5")]
-    pub block_id: ::std::option::Option<CanonicalBlockId>,
+    pub block_id: ::core::option::Option<CanonicalBlockId>,
     #[prost(message, optional, tag="6")]
-    pub timestamp: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
     #[prost(string, tag="7")]
-    pub chain_id: std::string::String,
+    pub chain_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
@@ -471,21 +469,220 @@ pub struct CanonicalVote {
     #[prost(sfixed64, tag="3")]
     pub round: i64,
     #[prost(message, optional, tag="4")]
-    pub block_id: ::std::option::Option<CanonicalBlockId>,
+    pub block_id: ::core::option::Option<CanonicalBlockId>,
     #[prost(message, optional, tag="5")]
-    pub timestamp: ::std::option::Option<super::super::google::protobuf::Timestamp>,
+    pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
     #[prost(string, tag="6")]
-    pub chain_id: std::string::String,
+    pub chain_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 #[derive(::serde::Deserialize, ::serde::Serialize)]
 pub struct Block {
     #[prost(message, optional, tag="1")]
-    pub header: ::std::option::Option<Header>,
+    pub header: ::core::option::Option<Header>,
     #[prost(message, optional, tag="2")]
-    pub data: ::std::option::Option<Data>,
+    pub data: ::core::option::Option<Data>,
     #[prost(message, optional, tag="3")]
-    pub evidence: ::std::option::Option<EvidenceList>,
+    pub evidence: ::core::option::Option<EvidenceList>,
     #[prost(message, optional, tag="4")]
-    pub last_commit: ::std::option::Option<Commit>,
+    pub last_commit: ::core::option::Option<Commit>,
+}
+
+proto3_json! {
+    ValidatorSet {
+        (validators: Repeated<Message>, "validators", "validators")
+        (proposer: Optional<Message>, "proposer", "proposer")
+        (total_voting_power: Int64, "totalVotingPower", "total_voting_power")
+    }
+    Validator {
+        (address: Bytes, "address", "address")
+        (pub_key: Optional<Message>, "pubKey", "pub_key")
+        (voting_power: Int64, "votingPower", "voting_power")
+        (proposer_priority: Int64, "proposerPriority", "proposer_priority")
+    }
+    SimpleValidator {
+        (pub_key: Optional<Message>, "pubKey", "pub_key")
+        (voting_power: Int64, "votingPower", "voting_power")
+    }
+    PartSetHeader {
+        (total: Int32, "total", "total")
+        (hash: Bytes, "hash", "hash")
+    }
+    Part {
+        (index: Int32, "index", "index")
+        (bytes: Bytes, "bytes", "bytes")
+        (proof: Optional<Message>, "proof", "proof")
+    }
+    BlockId {
+        (hash: Bytes, "hash", "hash")
+        (part_set_header: Optional<Message>, "partSetHeader", "part_set_header")
+    }
+    Header {
+        (version: Optional<Message>, "version", "version")
+        (chain_id: Plain, "chainId", "chain_id")
+        (height: Int64, "height", "height")
+        (time: Optional<Message>, "time", "time")
+        (last_block_id: Optional<Message>, "lastBlockId", "last_block_id")
+        (last_commit_hash: Bytes, "lastCommitHash", "last_commit_hash")
+        (data_hash: Bytes, "dataHash", "data_hash")
+        (validators_hash: Bytes, "validatorsHash", "validators_hash")
+        (next_validators_hash: Bytes, "nextValidatorsHash", "next_validators_hash")
+        (consensus_hash: Bytes, "consensusHash", "consensus_hash")
+        (app_hash: Bytes, "appHash", "app_hash")
+        (last_results_hash: Bytes, "lastResultsHash", "last_results_hash")
+        (evidence_hash: Bytes, "evidenceHash", "evidence_hash")
+        (proposer_address: Bytes, "proposerAddress", "proposer_address")
+    }
+    Data {
+        (txs: Repeated<Bytes>, "txs", "txs")
+    }
+    Vote {
+        (r#type: Enum<SignedMsgType>, "type", "type")
+        (height: Int64, "height", "height")
+        (round: Int32, "round", "round")
+        (block_id: Optional<Message>, "blockId", "block_id")
+        (timestamp: Optional<Message>, "timestamp", "timestamp")
+        (validator_address: Bytes, "validatorAddress", "validator_address")
+        (validator_index: Int32, "validatorIndex", "validator_index")
+        (signature: Bytes, "signature", "signature")
+    }
+    Commit {
+        (height: Int64, "height", "height")
+        (round: Int32, "round", "round")
+        (block_id: Optional<Message>, "blockId", "block_id")
+        (signatures: Repeated<Message>, "signatures", "signatures")
+    }
+    CommitSig {
+        (block_id_flag: Enum<BlockIdFlag>, "blockIdFlag", "block_id_flag")
+        (validator_address: Bytes, "validatorAddress", "validator_address")
+        (timestamp: Optional<Message>, "timestamp", "timestamp")
+        (signature: Bytes, "signature", "signature")
+    }
+    Proposal {
+        (r#type: Enum<SignedMsgType>, "type", "type")
+        (height: Int64, "height", "height")
+        (round: Int32, "round", "round")
+        (pol_round: Int32, "polRound", "pol_round")
+        (block_id: Optional<Message>, "blockId", "block_id")
+        (timestamp: Optional<Message>, "timestamp", "timestamp")
+        (signature: Bytes, "signature", "signature")
+    }
+    SignedHeader {
+        (header: Optional<Message>, "header", "header")
+        (commit: Optional<Message>, "commit", "commit")
+    }
+    LightBlock {
+        (signed_header: Optional<Message>, "signedHeader", "signed_header")
+        (validator_set: Optional<Message>, "validatorSet", "validator_set")
+    }
+    BlockMeta {
+        (block_id: Optional<Message>, "blockId", "block_id")
+        (block_size: Int64, "blockSize", "block_size")
+        (header: Optional<Message>, "header", "header")
+        (num_txs: Int64, "numTxs", "num_txs")
+    }
+    TxProof {
+        (root_hash: Bytes, "rootHash", "root_hash")
+        (data: Bytes, "data", "data")
+        (proof: Optional<Message>, "proof", "proof")
+    }
+    EventDataRoundState {
+        (height: Int64, "height", "height")
+        (round: Int32, "round", "round")
+        (step: Plain, "step", "step")
+    }
+    ConsensusParams {
+        (block: Optional<Message>, "block", "block")
+        (evidence: Optional<Message>, "evidence", "evidence")
+        (validator: Optional<Message>, "validator", "validator")
+        (version: Optional<Message>, "version", "version")
+    }
+    BlockParams {
+        (max_bytes: Int64, "maxBytes", "max_bytes")
+        (max_gas: Int64, "maxGas", "max_gas")
+    }
+    EvidenceParams {
+        (max_age_num_blocks: Int64, "maxAgeNumBlocks", "max_age_num_blocks")
+        (max_age_duration: Optional<Message>, "maxAgeDuration", "max_age_duration")
+        (max_bytes: Int64, "maxBytes", "max_bytes")
+    }
+    ValidatorParams {
+        (pub_key_types: Repeated<Plain>, "pubKeyTypes", "pub_key_types")
+    }
+    VersionParams {
+        (app: Int64, "app", "app")
+    }
+    HashedParams {
+        (block_max_bytes: Int64, "blockMaxBytes", "block_max_bytes")
+        (block_max_gas: Int64, "blockMaxGas", "block_max_gas")
+    }
+    Evidence {
+        (oneof sum: evidence::Sum {
+            DuplicateVoteEvidence(Message), "duplicateVoteEvidence", "duplicate_vote_evidence";
+            LightClientAttackEvidence(Message), "lightClientAttackEvidence", "light_client_attack_evidence";
+        })
+    }
+    DuplicateVoteEvidence {
+        (vote_a: Optional<Message>, "voteA", "vote_a")
+        (vote_b: Optional<Message>, "voteB", "vote_b")
+        (total_voting_power: Int64, "totalVotingPower", "total_voting_power")
+        (validator_power: Int64, "validatorPower", "validator_power")
+        (timestamp: Optional<Message>, "timestamp", "timestamp")
+    }
+    LightClientAttackEvidence {
+        (conflicting_block: Optional<Message>, "conflictingBlock", "conflicting_block")
+        (common_height: Int64, "commonHeight", "common_height")
+        (byzantine_validators: Repeated<Message>, "byzantineValidators", "byzantine_validators")
+        (total_voting_power: Int64, "totalVotingPower", "total_voting_power")
+        (timestamp: Optional<Message>, "timestamp", "timestamp")
+    }
+    EvidenceList {
+        (evidence: Repeated<Message>, "evidence", "evidence")
+    }
+    CanonicalBlockId {
+        (hash: Bytes, "hash", "hash")
+        (part_set_header: Optional<Message>, "partSetHeader", "part_set_header")
+    }
+    CanonicalPartSetHeader {
+        (total: Int32, "total", "total")
+        (hash: Bytes, "hash", "hash")
+    }
+    CanonicalProposal {
+        (r#type: Enum<SignedMsgType>, "type", "type")
+        (height: Int64, "height", "height")
+        (round: Int64, "round", "round")
+        (pol_round: Int64, "polRound", "pol_round")
+        (block_id: Optional<Message>, "blockId", "block_id")
+        (timestamp: Optional<Message>, "timestamp", "timestamp")
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    CanonicalVote {
+        (r#type: Enum<SignedMsgType>, "type", "type")
+        (height: Int64, "height", "height")
+        (round: Int64, "round", "round")
+        (block_id: Optional<Message>, "blockId", "block_id")
+        (timestamp: Optional<Message>, "timestamp", "timestamp")
+        (chain_id: Plain, "chainId", "chain_id")
+    }
+    Block {
+        (header: Optional<Message>, "header", "header")
+        (data: Optional<Message>, "data", "data")
+        (evidence: Optional<Message>, "evidence", "evidence")
+        (last_commit: Optional<Message>, "lastCommit", "last_commit")
+    }
+}
+
+proto3_enum! {
+    BlockIdFlag {
+        Unknown = "BLOCK_ID_FLAG_UNKNOWN",
+        Absent = "BLOCK_ID_FLAG_ABSENT",
+        Commit = "BLOCK_ID_FLAG_COMMIT",
+        Nil = "BLOCK_ID_FLAG_NIL",
+    }
+    SignedMsgType {
+        Unknown = "SIGNED_MSG_TYPE_UNKNOWN",
+        Prevote = "SIGNED_MSG_TYPE_PREVOTE",
+        Precommit = "SIGNED_MSG_TYPE_PRECOMMIT",
+        Proposal = "SIGNED_MSG_TYPE_PROPOSAL",
+    }
 }
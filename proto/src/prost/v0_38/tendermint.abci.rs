@@ -0,0 +1,1920 @@
+// This file is copied from http://github.com/tendermint/abci
+// NOTE: When using custom types, mind the warnings.
+// https://github.com/gogo/protobuf/blob/master/custom_types.md#warnings-and-issues
+
+//----------------------------------------
+// Request types
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Request {
+    #[prost(oneof="request::Value", tags="1, 2, 3, 5, 6, 8, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20")]
+    pub value: ::core::option::Option<request::Value>,
+}
+pub mod request {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag="1")]
+        Echo(super::RequestEcho),
+        #[prost(message, tag="2")]
+        Flush(super::RequestFlush),
+        #[prost(message, tag="3")]
+        Info(super::RequestInfo),
+        #[prost(message, tag="5")]
+        InitChain(super::RequestInitChain),
+        #[prost(message, tag="6")]
+        Query(super::RequestQuery),
+        #[prost(message, tag="8")]
+        CheckTx(super::RequestCheckTx),
+        #[prost(message, tag="11")]
+        Commit(super::RequestCommit),
+        #[prost(message, tag="12")]
+        ListSnapshots(super::RequestListSnapshots),
+        #[prost(message, tag="13")]
+        OfferSnapshot(super::RequestOfferSnapshot),
+        #[prost(message, tag="14")]
+        LoadSnapshotChunk(super::RequestLoadSnapshotChunk),
+        #[prost(message, tag="15")]
+        ApplySnapshotChunk(super::RequestApplySnapshotChunk),
+        #[prost(message, tag="16")]
+        PrepareProposal(super::RequestPrepareProposal),
+        #[prost(message, tag="17")]
+        ProcessProposal(super::RequestProcessProposal),
+        #[prost(message, tag="18")]
+        ExtendVote(super::RequestExtendVote),
+        #[prost(message, tag="19")]
+        VerifyVoteExtension(super::RequestVerifyVoteExtension),
+        #[prost(message, tag="20")]
+        FinalizeBlock(super::RequestFinalizeBlock),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestEcho {
+    #[prost(string, tag="1")]
+    pub message: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestFlush {
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestInfo {
+    #[prost(string, tag="1")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub block_version: u64,
+    #[prost(uint64, tag="3")]
+    pub p2p_version: u64,
+    #[prost(string, tag="4")]
+    pub abci_version: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestInitChain {
+    #[prost(message, optional, tag="1")]
+    pub time: ::core::option::Option<crate::google::protobuf::Timestamp>,
+    #[prost(string, tag="2")]
+    pub chain_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub consensus_params: ::core::option::Option<super::types::ConsensusParams>,
+    #[prost(message, repeated, tag="4")]
+    pub validators: ::prost::alloc::vec::Vec<ValidatorUpdate>,
+    #[prost(bytes, tag="5")]
+    pub app_state_bytes: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag="6")]
+    pub initial_height: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestQuery {
+    #[prost(bytes, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(int64, tag="3")]
+    pub height: i64,
+    #[prost(bool, tag="4")]
+    pub prove: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestCheckTx {
+    #[prost(bytes, tag="1")]
+    pub tx: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="CheckTxType", tag="2")]
+    pub r#type: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestCommit {
+}
+/// lists available snapshots
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestListSnapshots {
+}
+/// offers a snapshot to the application
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestOfferSnapshot {
+    /// snapshot offered by peers
+    #[prost(message, optional, tag="1")]
+    pub snapshot: ::core::option::Option<Snapshot>,
+    /// light client-verified app hash for snapshot height
+    #[prost(bytes, tag="2")]
+    pub app_hash: ::prost::alloc::vec::Vec<u8>,
+}
+/// loads a snapshot chunk
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestLoadSnapshotChunk {
+    #[prost(uint64, tag="1")]
+    pub height: u64,
+    #[prost(uint32, tag="2")]
+    pub format: u32,
+    #[prost(uint32, tag="3")]
+    pub chunk: u32,
+}
+/// Applies a snapshot chunk
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestApplySnapshotChunk {
+    #[prost(uint32, tag="1")]
+    pub index: u32,
+    #[prost(bytes, tag="2")]
+    pub chunk: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="3")]
+    pub sender: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestPrepareProposal {
+    /// the modified transactions cannot exceed this size.
+    #[prost(int64, tag="1")]
+    pub max_tx_bytes: i64,
+    /// txs is an array of transactions that will be included in a block,
+    /// sent to the app for possible modifications.
+    #[prost(bytes, repeated, tag="2")]
+    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag="3")]
+    pub local_last_commit: ::core::option::Option<ExtendedCommitInfo>,
+    #[prost(message, repeated, tag="4")]
+    pub misbehavior: ::prost::alloc::vec::Vec<Misbehavior>,
+    #[prost(int64, tag="5")]
+    pub height: i64,
+    #[prost(message, optional, tag="6")]
+    pub time: ::core::option::Option<crate::google::protobuf::Timestamp>,
+    #[prost(bytes, tag="7")]
+    pub next_validators_hash: ::prost::alloc::vec::Vec<u8>,
+    /// address of the public key of the validator proposing the block.
+    #[prost(bytes, tag="8")]
+    pub proposer_address: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestProcessProposal {
+    #[prost(bytes, repeated, tag="1")]
+    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag="2")]
+    pub proposed_last_commit: ::core::option::Option<CommitInfo>,
+    #[prost(message, repeated, tag="3")]
+    pub misbehavior: ::prost::alloc::vec::Vec<Misbehavior>,
+    /// hash is the merkle root hash of the fields of the proposed block.
+    #[prost(bytes, tag="4")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag="5")]
+    pub height: i64,
+    #[prost(message, optional, tag="6")]
+    pub time: ::core::option::Option<crate::google::protobuf::Timestamp>,
+    #[prost(bytes, tag="7")]
+    pub next_validators_hash: ::prost::alloc::vec::Vec<u8>,
+    /// address of the public key of the original proposer of the block.
+    #[prost(bytes, tag="8")]
+    pub proposer_address: ::prost::alloc::vec::Vec<u8>,
+}
+/// Extends a vote with application-injected data
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestExtendVote {
+    /// the hash of the block that this vote may be referring to
+    #[prost(bytes, tag="1")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    /// the height of the extended vote
+    #[prost(int64, tag="2")]
+    pub height: i64,
+    /// info of the block that this vote may be referring to
+    #[prost(message, optional, tag="3")]
+    pub time: ::core::option::Option<crate::google::protobuf::Timestamp>,
+    #[prost(bytes, repeated, tag="4")]
+    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag="5")]
+    pub proposed_last_commit: ::core::option::Option<CommitInfo>,
+    #[prost(message, repeated, tag="6")]
+    pub misbehavior: ::prost::alloc::vec::Vec<Misbehavior>,
+    #[prost(bytes, tag="7")]
+    pub next_validators_hash: ::prost::alloc::vec::Vec<u8>,
+    /// address of the public key of the original proposer of the block.
+    #[prost(bytes, tag="8")]
+    pub proposer_address: ::prost::alloc::vec::Vec<u8>,
+}
+/// Verify the vote extension
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestVerifyVoteExtension {
+    /// the hash of the block that this received vote corresponds to
+    #[prost(bytes, tag="1")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    /// the validator that signed the vote extension
+    #[prost(bytes, tag="2")]
+    pub validator_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag="3")]
+    pub height: i64,
+    #[prost(bytes, tag="4")]
+    pub vote_extension: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestFinalizeBlock {
+    #[prost(bytes, repeated, tag="1")]
+    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag="2")]
+    pub decided_last_commit: ::core::option::Option<CommitInfo>,
+    #[prost(message, repeated, tag="3")]
+    pub misbehavior: ::prost::alloc::vec::Vec<Misbehavior>,
+    /// hash is the merkle root hash of the fields of the decided block.
+    #[prost(bytes, tag="4")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag="5")]
+    pub height: i64,
+    #[prost(message, optional, tag="6")]
+    pub time: ::core::option::Option<crate::google::protobuf::Timestamp>,
+    #[prost(bytes, tag="7")]
+    pub next_validators_hash: ::prost::alloc::vec::Vec<u8>,
+    /// proposer_address is the address of the public key of the original proposer of the block.
+    #[prost(bytes, tag="8")]
+    pub proposer_address: ::prost::alloc::vec::Vec<u8>,
+}
+//----------------------------------------
+// Response types
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Response {
+    #[prost(oneof="response::Value", tags="1, 2, 3, 4, 6, 7, 9, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21")]
+    pub value: ::core::option::Option<response::Value>,
+}
+pub mod response {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag="1")]
+        Exception(super::ResponseException),
+        #[prost(message, tag="2")]
+        Echo(super::ResponseEcho),
+        #[prost(message, tag="3")]
+        Flush(super::ResponseFlush),
+        #[prost(message, tag="4")]
+        Info(super::ResponseInfo),
+        #[prost(message, tag="6")]
+        InitChain(super::ResponseInitChain),
+        #[prost(message, tag="7")]
+        Query(super::ResponseQuery),
+        #[prost(message, tag="9")]
+        CheckTx(super::ResponseCheckTx),
+        #[prost(message, tag="12")]
+        Commit(super::ResponseCommit),
+        #[prost(message, tag="13")]
+        ListSnapshots(super::ResponseListSnapshots),
+        #[prost(message, tag="14")]
+        OfferSnapshot(super::ResponseOfferSnapshot),
+        #[prost(message, tag="15")]
+        LoadSnapshotChunk(super::ResponseLoadSnapshotChunk),
+        #[prost(message, tag="16")]
+        ApplySnapshotChunk(super::ResponseApplySnapshotChunk),
+        #[prost(message, tag="17")]
+        PrepareProposal(super::ResponsePrepareProposal),
+        #[prost(message, tag="18")]
+        ProcessProposal(super::ResponseProcessProposal),
+        #[prost(message, tag="19")]
+        ExtendVote(super::ResponseExtendVote),
+        #[prost(message, tag="20")]
+        VerifyVoteExtension(super::ResponseVerifyVoteExtension),
+        #[prost(message, tag="21")]
+        FinalizeBlock(super::ResponseFinalizeBlock),
+    }
+}
+/// nondeterministic
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseException {
+    #[prost(string, tag="1")]
+    pub error: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseEcho {
+    #[prost(string, tag="1")]
+    pub message: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseFlush {
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(::serde::Deserialize, ::serde::Serialize)]
+pub struct ResponseInfo {
+    #[prost(string, tag="1")]
+    pub data: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    #[serde(with = "crate::serializers::from_str")]
+    pub app_version: u64,
+    #[prost(int64, tag="4")]
+    #[serde(with = "crate::serializers::from_str")]
+    pub last_block_height: i64,
+    #[prost(bytes, tag="5")]
+    #[serde(skip_serializing_if = "::prost::alloc::vec::Vec::is_empty", with = "serde_bytes")]
+    pub last_block_app_hash: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseInitChain {
+    #[prost(message, optional, tag="1")]
+    pub consensus_params: ::core::option::Option<super::types::ConsensusParams>,
+    #[prost(message, repeated, tag="2")]
+    pub validators: ::prost::alloc::vec::Vec<ValidatorUpdate>,
+    #[prost(bytes, tag="3")]
+    pub app_hash: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseQuery {
+    #[prost(uint32, tag="1")]
+    pub code: u32,
+    /// bytes data = 2; // use "value" instead.
+    ///
+    /// nondeterministic
+    #[prost(string, tag="3")]
+    pub log: ::prost::alloc::string::String,
+    /// nondeterministic
+    #[prost(string, tag="4")]
+    pub info: ::prost::alloc::string::String,
+    #[prost(int64, tag="5")]
+    pub index: i64,
+    #[prost(bytes, tag="6")]
+    pub key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes, tag="7")]
+    pub value: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="8")]
+    pub proof_ops: ::core::option::Option<super::crypto::ProofOps>,
+    #[prost(int64, tag="9")]
+    pub height: i64,
+    #[prost(string, tag="10")]
+    pub codespace: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseCheckTx {
+    #[prost(uint32, tag="1")]
+    pub code: u32,
+    #[prost(bytes, tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// nondeterministic
+    #[prost(string, tag="3")]
+    pub log: ::prost::alloc::string::String,
+    /// nondeterministic
+    #[prost(string, tag="4")]
+    pub info: ::prost::alloc::string::String,
+    #[prost(int64, tag="5")]
+    pub gas_wanted: i64,
+    #[prost(int64, tag="6")]
+    pub gas_used: i64,
+    #[prost(message, repeated, tag="7")]
+    pub events: ::prost::alloc::vec::Vec<Event>,
+    #[prost(string, tag="8")]
+    pub codespace: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseCommit {
+    #[prost(int64, tag="3")]
+    pub retain_height: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseListSnapshots {
+    #[prost(message, repeated, tag="1")]
+    pub snapshots: ::prost::alloc::vec::Vec<Snapshot>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseOfferSnapshot {
+    #[prost(enumeration="response_offer_snapshot::Result", tag="1")]
+    pub result: i32,
+}
+pub mod response_offer_snapshot {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Result {
+        /// Unknown result, abort all snapshot restoration
+        Unknown = 0,
+        /// Snapshot accepted, apply chunks
+        Accept = 1,
+        /// Abort all snapshot restoration
+        Abort = 2,
+        /// Reject this specific snapshot, try others
+        Reject = 3,
+        /// Reject all snapshots of this format, try others
+        RejectFormat = 4,
+        /// Reject all snapshots from the sender(s), try others
+        RejectSender = 5,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseLoadSnapshotChunk {
+    #[prost(bytes, tag="1")]
+    pub chunk: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseApplySnapshotChunk {
+    #[prost(enumeration="response_apply_snapshot_chunk::Result", tag="1")]
+    pub result: i32,
+    /// Chunks to refetch and reapply
+    #[prost(uint32, repeated, tag="2")]
+    pub refetch_chunks: ::prost::alloc::vec::Vec<u32>,
+    /// Chunk senders to reject and ban
+    #[prost(string, repeated, tag="3")]
+    pub reject_senders: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+pub mod response_apply_snapshot_chunk {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Result {
+        /// Unknown result, abort all snapshot restoration
+        Unknown = 0,
+        /// Chunk successfully accepted
+        Accept = 1,
+        /// Abort all snapshot restoration
+        Abort = 2,
+        /// Retry chunk (combine with refetch and reject)
+        Retry = 3,
+        /// Retry snapshot (combine with refetch and reject)
+        RetrySnapshot = 4,
+        /// Reject this snapshot, try others
+        RejectSnapshot = 5,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponsePrepareProposal {
+    #[prost(bytes, repeated, tag="1")]
+    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseProcessProposal {
+    #[prost(enumeration="response_process_proposal::ProposalStatus", tag="1")]
+    pub status: i32,
+}
+pub mod response_process_proposal {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum ProposalStatus {
+        Unknown = 0,
+        Accept = 1,
+        Reject = 2,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseExtendVote {
+    #[prost(bytes, tag="1")]
+    pub vote_extension: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseVerifyVoteExtension {
+    #[prost(enumeration="response_verify_vote_extension::VerifyStatus", tag="1")]
+    pub status: i32,
+}
+pub mod response_verify_vote_extension {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum VerifyStatus {
+        Unknown = 0,
+        Accept = 1,
+        /// Rejecting the vote extension will reject the entire precommit by the sender.
+        /// Incorrectly implementing this thus has liveness implications as it may affect
+        /// CometBFT's ability to receive 2/3+ valid votes to finalize the block.
+        /// Honest nodes should never be rejected.
+        Reject = 2,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseFinalizeBlock {
+    /// set of block events emmitted as part of executing the block
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<Event>,
+    /// the result of executing each transaction including the events
+    /// the particular transction emitted. This should match the order
+    /// of the transactions delivered in the block itself
+    #[prost(message, repeated, tag="2")]
+    pub tx_results: ::prost::alloc::vec::Vec<ExecTxResult>,
+    /// a list of updates to the validator set. These will reflect the validator set at current height + 2.
+    #[prost(message, repeated, tag="3")]
+    pub validator_updates: ::prost::alloc::vec::Vec<ValidatorUpdate>,
+    /// updates to the consensus params, if any.
+    #[prost(message, optional, tag="4")]
+    pub consensus_param_updates: ::core::option::Option<super::types::ConsensusParams>,
+    /// app_hash is the hash of the applications' state which is used to confirm that execution of the transactions was
+    /// deterministic. It is up to the application to decide which algorithm to use.
+    #[prost(bytes, tag="5")]
+    pub app_hash: ::prost::alloc::vec::Vec<u8>,
+}
+//----------------------------------------
+// Misc.
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommitInfo {
+    #[prost(int32, tag="1")]
+    pub round: i32,
+    #[prost(message, repeated, tag="2")]
+    pub votes: ::prost::alloc::vec::Vec<VoteInfo>,
+}
+/// ExtendedCommitInfo is similar to CommitInfo except that it is only used in
+/// the PrepareProposal request such that CometBFT can provide vote extensions
+/// to the application.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExtendedCommitInfo {
+    /// The round at which the block proposer decided in the previous height.
+    #[prost(int32, tag="1")]
+    pub round: i32,
+    /// List of validators' addresses in the last validator set with their voting
+    /// information, including vote extensions.
+    #[prost(message, repeated, tag="2")]
+    pub votes: ::prost::alloc::vec::Vec<ExtendedVoteInfo>,
+}
+/// Event allows application developers to attach additional information to
+/// ResponseFinalizeBlock and ResponseCheckTx.
+/// Later, transactions may be queried using these events.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Event {
+    #[prost(string, tag="1")]
+    pub r#type: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="2")]
+    pub attributes: ::prost::alloc::vec::Vec<EventAttribute>,
+}
+/// EventAttribute is a single key-value pair, associated with an event.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventAttribute {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+    /// nondeterministic
+    #[prost(bool, tag="3")]
+    pub index: bool,
+}
+/// ExecTxResult contains results of executing one individual transaction.
+///
+/// * Its structure is equivalent to #ResponseDeliverTx which will be deprecated/deleted
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecTxResult {
+    #[prost(uint32, tag="1")]
+    pub code: u32,
+    #[prost(bytes, tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// nondeterministic
+    #[prost(string, tag="3")]
+    pub log: ::prost::alloc::string::String,
+    /// nondeterministic
+    #[prost(string, tag="4")]
+    pub info: ::prost::alloc::string::String,
+    #[prost(int64, tag="5")]
+    pub gas_wanted: i64,
+    #[prost(int64, tag="6")]
+    pub gas_used: i64,
+    /// nondeterministic
+    #[prost(message, repeated, tag="7")]
+    pub events: ::prost::alloc::vec::Vec<Event>,
+    #[prost(string, tag="8")]
+    pub codespace: ::prost::alloc::string::String,
+}
+/// TxResult contains results of executing the transaction.
+///
+/// One usage is indexing transaction results.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TxResult {
+    #[prost(int64, tag="1")]
+    pub height: i64,
+    #[prost(uint32, tag="2")]
+    pub index: u32,
+    #[prost(bytes, tag="3")]
+    pub tx: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="4")]
+    pub result: ::core::option::Option<ExecTxResult>,
+}
+//----------------------------------------
+// Blockchain Types
+
+/// Validator
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Validator {
+    /// The first 20 bytes of SHA256(public key)
+    #[prost(bytes, tag="1")]
+    pub address: ::prost::alloc::vec::Vec<u8>,
+    /// PubKey pub_key = 2 [(gogoproto.nullable)=false];
+    ///
+    /// The voting power
+    #[prost(int64, tag="3")]
+    pub power: i64,
+}
+/// ValidatorUpdate
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidatorUpdate {
+    #[prost(message, optional, tag="1")]
+    pub pub_key: ::core::option::Option<super::crypto::PublicKey>,
+    #[prost(int64, tag="2")]
+    pub power: i64,
+}
+/// VoteInfo
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VoteInfo {
+    #[prost(message, optional, tag="1")]
+    pub validator: ::core::option::Option<Validator>,
+    #[prost(enumeration="super::types::BlockIdFlag", tag="3")]
+    pub block_id_flag: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExtendedVoteInfo {
+    /// The validator that sent the vote.
+    #[prost(message, optional, tag="1")]
+    pub validator: ::core::option::Option<Validator>,
+    /// Non-deterministic extension provided by the sending validator's application.
+    #[prost(bytes, tag="3")]
+    pub vote_extension: ::prost::alloc::vec::Vec<u8>,
+    /// Vote extension signature created by CometBFT
+    #[prost(bytes, tag="4")]
+    pub extension_signature: ::prost::alloc::vec::Vec<u8>,
+    /// block_id_flag indicates whether the validator voted for a block, nil, or did not vote at all
+    #[prost(enumeration="super::types::BlockIdFlag", tag="5")]
+    pub block_id_flag: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Misbehavior {
+    #[prost(enumeration="MisbehaviorType", tag="1")]
+    pub r#type: i32,
+    /// The offending validator
+    #[prost(message, optional, tag="2")]
+    pub validator: ::core::option::Option<Validator>,
+    /// The height when the offense occurred
+    #[prost(int64, tag="3")]
+    pub height: i64,
+    /// The corresponding time where the offense occurred
+    #[prost(message, optional, tag="4")]
+    pub time: ::core::option::Option<crate::google::protobuf::Timestamp>,
+    /// Total voting power of the validator set in case the ABCI application does
+    /// not store historical validators.
+    /// https://github.com/tendermint/tendermint/issues/4581
+    #[prost(int64, tag="5")]
+    pub total_voting_power: i64,
+}
+//----------------------------------------
+// State Sync Types
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Snapshot {
+    /// The height at which the snapshot was taken
+    #[prost(uint64, tag="1")]
+    pub height: u64,
+    /// The application-specific snapshot format
+    #[prost(uint32, tag="2")]
+    pub format: u32,
+    /// Number of chunks in the snapshot
+    #[prost(uint32, tag="3")]
+    pub chunks: u32,
+    /// Arbitrary snapshot hash, equal only if identical
+    #[prost(bytes, tag="4")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    /// Arbitrary application metadata
+    #[prost(bytes, tag="5")]
+    pub metadata: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CheckTxType {
+    New = 0,
+    Recheck = 1,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MisbehaviorType {
+    Unknown = 0,
+    DuplicateVote = 1,
+    LightClientAttack = 2,
+}
+
+proto3_json! {
+    Request {
+        (oneof value: request::Value {
+            Echo(Message), "echo", "echo";
+            Flush(Message), "flush", "flush";
+            Info(Message), "info", "info";
+            InitChain(Message), "initChain", "init_chain";
+            Query(Message), "query", "query";
+            CheckTx(Message), "checkTx", "check_tx";
+            Commit(Message), "commit", "commit";
+            ListSnapshots(Message), "listSnapshots", "list_snapshots";
+            OfferSnapshot(Message), "offerSnapshot", "offer_snapshot";
+            LoadSnapshotChunk(Message), "loadSnapshotChunk", "load_snapshot_chunk";
+            ApplySnapshotChunk(Message), "applySnapshotChunk", "apply_snapshot_chunk";
+            PrepareProposal(Message), "prepareProposal", "prepare_proposal";
+            ProcessProposal(Message), "processProposal", "process_proposal";
+            ExtendVote(Message), "extendVote", "extend_vote";
+            VerifyVoteExtension(Message), "verifyVoteExtension", "verify_vote_extension";
+            FinalizeBlock(Message), "finalizeBlock", "finalize_block";
+        })
+    }
+    RequestEcho {
+        (message: Plain, "message", "message")
+    }
+    RequestFlush {
+    }
+    RequestInfo {
+        (version: Plain, "version", "version")
+        (block_version: Int64, "blockVersion", "block_version")
+        (p2p_version: Int64, "p2pVersion", "p2p_version")
+        (abci_version: Plain, "abciVersion", "abci_version")
+    }
+    RequestInitChain {
+        (time: Optional<Message>, "time", "time")
+        (chain_id: Plain, "chainId", "chain_id")
+        (consensus_params: Optional<Message>, "consensusParams", "consensus_params")
+        (validators: Repeated<Message>, "validators", "validators")
+        (app_state_bytes: Bytes, "appStateBytes", "app_state_bytes")
+        (initial_height: Int64, "initialHeight", "initial_height")
+    }
+    RequestQuery {
+        (data: Bytes, "data", "data")
+        (path: Plain, "path", "path")
+        (height: Int64, "height", "height")
+        (prove: Plain, "prove", "prove")
+    }
+    RequestCheckTx {
+        (tx: Bytes, "tx", "tx")
+        (r#type: Enum<CheckTxType>, "type", "type")
+    }
+    RequestCommit {
+    }
+    RequestListSnapshots {
+    }
+    RequestOfferSnapshot {
+        (snapshot: Optional<Message>, "snapshot", "snapshot")
+        (app_hash: Bytes, "appHash", "app_hash")
+    }
+    RequestLoadSnapshotChunk {
+        (height: Int64, "height", "height")
+        (format: Int32, "format", "format")
+        (chunk: Int32, "chunk", "chunk")
+    }
+    RequestApplySnapshotChunk {
+        (index: Int32, "index", "index")
+        (chunk: Bytes, "chunk", "chunk")
+        (sender: Plain, "sender", "sender")
+    }
+    RequestPrepareProposal {
+        (max_tx_bytes: Int64, "maxTxBytes", "max_tx_bytes")
+        (txs: Repeated<Bytes>, "txs", "txs")
+        (local_last_commit: Optional<Message>, "localLastCommit", "local_last_commit")
+        (misbehavior: Repeated<Message>, "misbehavior", "misbehavior")
+        (height: Int64, "height", "height")
+        (time: Optional<Message>, "time", "time")
+        (next_validators_hash: Bytes, "nextValidatorsHash", "next_validators_hash")
+        (proposer_address: Bytes, "proposerAddress", "proposer_address")
+    }
+    RequestProcessProposal {
+        (txs: Repeated<Bytes>, "txs", "txs")
+        (proposed_last_commit: Optional<Message>, "proposedLastCommit", "proposed_last_commit")
+        (misbehavior: Repeated<Message>, "misbehavior", "misbehavior")
+        (hash: Bytes, "hash", "hash")
+        (height: Int64, "height", "height")
+        (time: Optional<Message>, "time", "time")
+        (next_validators_hash: Bytes, "nextValidatorsHash", "next_validators_hash")
+        (proposer_address: Bytes, "proposerAddress", "proposer_address")
+    }
+    RequestExtendVote {
+        (hash: Bytes, "hash", "hash")
+        (height: Int64, "height", "height")
+        (time: Optional<Message>, "time", "time")
+        (txs: Repeated<Bytes>, "txs", "txs")
+        (proposed_last_commit: Optional<Message>, "proposedLastCommit", "proposed_last_commit")
+        (misbehavior: Repeated<Message>, "misbehavior", "misbehavior")
+        (next_validators_hash: Bytes, "nextValidatorsHash", "next_validators_hash")
+        (proposer_address: Bytes, "proposerAddress", "proposer_address")
+    }
+    RequestVerifyVoteExtension {
+        (hash: Bytes, "hash", "hash")
+        (validator_address: Bytes, "validatorAddress", "validator_address")
+        (height: Int64, "height", "height")
+        (vote_extension: Bytes, "voteExtension", "vote_extension")
+    }
+    RequestFinalizeBlock {
+        (txs: Repeated<Bytes>, "txs", "txs")
+        (decided_last_commit: Optional<Message>, "decidedLastCommit", "decided_last_commit")
+        (misbehavior: Repeated<Message>, "misbehavior", "misbehavior")
+        (hash: Bytes, "hash", "hash")
+        (height: Int64, "height", "height")
+        (time: Optional<Message>, "time", "time")
+        (next_validators_hash: Bytes, "nextValidatorsHash", "next_validators_hash")
+        (proposer_address: Bytes, "proposerAddress", "proposer_address")
+    }
+    Response {
+        (oneof value: response::Value {
+            Exception(Message), "exception", "exception";
+            Echo(Message), "echo", "echo";
+            Flush(Message), "flush", "flush";
+            Info(Message), "info", "info";
+            InitChain(Message), "initChain", "init_chain";
+            Query(Message), "query", "query";
+            CheckTx(Message), "checkTx", "check_tx";
+            Commit(Message), "commit", "commit";
+            ListSnapshots(Message), "listSnapshots", "list_snapshots";
+            OfferSnapshot(Message), "offerSnapshot", "offer_snapshot";
+            LoadSnapshotChunk(Message), "loadSnapshotChunk", "load_snapshot_chunk";
+            ApplySnapshotChunk(Message), "applySnapshotChunk", "apply_snapshot_chunk";
+            PrepareProposal(Message), "prepareProposal", "prepare_proposal";
+            ProcessProposal(Message), "processProposal", "process_proposal";
+            ExtendVote(Message), "extendVote", "extend_vote";
+            VerifyVoteExtension(Message), "verifyVoteExtension", "verify_vote_extension";
+            FinalizeBlock(Message), "finalizeBlock", "finalize_block";
+        })
+    }
+    ResponseException {
+        (error: Plain, "error", "error")
+    }
+    ResponseEcho {
+        (message: Plain, "message", "message")
+    }
+    ResponseFlush {
+    }
+    ResponseInfo {
+        (data: Plain, "data", "data")
+        (version: Plain, "version", "version")
+        (app_version: Int64, "appVersion", "app_version")
+        (last_block_height: Int64, "lastBlockHeight", "last_block_height")
+        (last_block_app_hash: Bytes, "lastBlockAppHash", "last_block_app_hash")
+    }
+    ResponseInitChain {
+        (consensus_params: Optional<Message>, "consensusParams", "consensus_params")
+        (validators: Repeated<Message>, "validators", "validators")
+        (app_hash: Bytes, "appHash", "app_hash")
+    }
+    ResponseQuery {
+        (code: Int32, "code", "code")
+        (log: Plain, "log", "log")
+        (info: Plain, "info", "info")
+        (index: Int64, "index", "index")
+        (key: Bytes, "key", "key")
+        (value: Bytes, "value", "value")
+        (proof_ops: Optional<Message>, "proofOps", "proof_ops")
+        (height: Int64, "height", "height")
+        (codespace: Plain, "codespace", "codespace")
+    }
+    ResponseCheckTx {
+        (code: Int32, "code", "code")
+        (data: Bytes, "data", "data")
+        (log: Plain, "log", "log")
+        (info: Plain, "info", "info")
+        (gas_wanted: Int64, "gasWanted", "gas_wanted")
+        (gas_used: Int64, "gasUsed", "gas_used")
+        (events: Repeated<Message>, "events", "events")
+        (codespace: Plain, "codespace", "codespace")
+    }
+    ResponseCommit {
+        (retain_height: Int64, "retainHeight", "retain_height")
+    }
+    ResponseListSnapshots {
+        (snapshots: Repeated<Message>, "snapshots", "snapshots")
+    }
+    ResponseOfferSnapshot {
+        (result: Enum<response_offer_snapshot::Result>, "result", "result")
+    }
+    ResponseLoadSnapshotChunk {
+        (chunk: Bytes, "chunk", "chunk")
+    }
+    ResponseApplySnapshotChunk {
+        (result: Enum<response_apply_snapshot_chunk::Result>, "result", "result")
+        (refetch_chunks: Repeated<Int32>, "refetchChunks", "refetch_chunks")
+        (reject_senders: Repeated<Plain>, "rejectSenders", "reject_senders")
+    }
+    ResponsePrepareProposal {
+        (txs: Repeated<Bytes>, "txs", "txs")
+    }
+    ResponseProcessProposal {
+        (status: Enum<response_process_proposal::ProposalStatus>, "status", "status")
+    }
+    ResponseExtendVote {
+        (vote_extension: Bytes, "voteExtension", "vote_extension")
+    }
+    ResponseVerifyVoteExtension {
+        (status: Enum<response_verify_vote_extension::VerifyStatus>, "status", "status")
+    }
+    ResponseFinalizeBlock {
+        (events: Repeated<Message>, "events", "events")
+        (tx_results: Repeated<Message>, "txResults", "tx_results")
+        (validator_updates: Repeated<Message>, "validatorUpdates", "validator_updates")
+        (consensus_param_updates: Optional<Message>, "consensusParamUpdates", "consensus_param_updates")
+        (app_hash: Bytes, "appHash", "app_hash")
+    }
+    CommitInfo {
+        (round: Int32, "round", "round")
+        (votes: Repeated<Message>, "votes", "votes")
+    }
+    ExtendedCommitInfo {
+        (round: Int32, "round", "round")
+        (votes: Repeated<Message>, "votes", "votes")
+    }
+    Event {
+        (r#type: Plain, "type", "type")
+        (attributes: Repeated<Message>, "attributes", "attributes")
+    }
+    EventAttribute {
+        (key: Plain, "key", "key")
+        (value: Plain, "value", "value")
+        (index: Plain, "index", "index")
+    }
+    ExecTxResult {
+        (code: Int32, "code", "code")
+        (data: Bytes, "data", "data")
+        (log: Plain, "log", "log")
+        (info: Plain, "info", "info")
+        (gas_wanted: Int64, "gasWanted", "gas_wanted")
+        (gas_used: Int64, "gasUsed", "gas_used")
+        (events: Repeated<Message>, "events", "events")
+        (codespace: Plain, "codespace", "codespace")
+    }
+    TxResult {
+        (height: Int64, "height", "height")
+        (index: Int32, "index", "index")
+        (tx: Bytes, "tx", "tx")
+        (result: Optional<Message>, "result", "result")
+    }
+    Validator {
+        (address: Bytes, "address", "address")
+        (power: Int64, "power", "power")
+    }
+    ValidatorUpdate {
+        (pub_key: Optional<Message>, "pubKey", "pub_key")
+        (power: Int64, "power", "power")
+    }
+    VoteInfo {
+        (validator: Optional<Message>, "validator", "validator")
+        (block_id_flag: Enum<super::types::BlockIdFlag>, "blockIdFlag", "block_id_flag")
+    }
+    ExtendedVoteInfo {
+        (validator: Optional<Message>, "validator", "validator")
+        (vote_extension: Bytes, "voteExtension", "vote_extension")
+        (extension_signature: Bytes, "extensionSignature", "extension_signature")
+        (block_id_flag: Enum<super::types::BlockIdFlag>, "blockIdFlag", "block_id_flag")
+    }
+    Misbehavior {
+        (r#type: Enum<MisbehaviorType>, "type", "type")
+        (validator: Optional<Message>, "validator", "validator")
+        (height: Int64, "height", "height")
+        (time: Optional<Message>, "time", "time")
+        (total_voting_power: Int64, "totalVotingPower", "total_voting_power")
+    }
+    Snapshot {
+        (height: Int64, "height", "height")
+        (format: Int32, "format", "format")
+        (chunks: Int32, "chunks", "chunks")
+        (hash: Bytes, "hash", "hash")
+        (metadata: Bytes, "metadata", "metadata")
+    }
+}
+
+proto3_enum! {
+    response_offer_snapshot::Result {
+        Unknown = "UNKNOWN",
+        Accept = "ACCEPT",
+        Abort = "ABORT",
+        Reject = "REJECT",
+        RejectFormat = "REJECT_FORMAT",
+        RejectSender = "REJECT_SENDER",
+    }
+    response_apply_snapshot_chunk::Result {
+        Unknown = "UNKNOWN",
+        Accept = "ACCEPT",
+        Abort = "ABORT",
+        Retry = "RETRY",
+        RetrySnapshot = "RETRY_SNAPSHOT",
+        RejectSnapshot = "REJECT_SNAPSHOT",
+    }
+    response_process_proposal::ProposalStatus {
+        Unknown = "UNKNOWN",
+        Accept = "ACCEPT",
+        Reject = "REJECT",
+    }
+    response_verify_vote_extension::VerifyStatus {
+        Unknown = "UNKNOWN",
+        Accept = "ACCEPT",
+        Reject = "REJECT",
+    }
+    CheckTxType {
+        New = "NEW",
+        Recheck = "RECHECK",
+    }
+    MisbehaviorType {
+        Unknown = "UNKNOWN",
+        DuplicateVote = "DUPLICATE_VOTE",
+        LightClientAttack = "LIGHT_CLIENT_ATTACK",
+    }
+}
+#[cfg(feature = "grpc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc")))]
+#[doc = r" Generated client implementations."]
+pub mod abci_client {
+    #![allow(unused_variables, dead_code, missing_docs)]
+    use tonic::codegen::*;
+    pub struct AbciClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl AbciClient<tonic::transport::Channel> {
+        #[doc = r" Attempt to create a new client by connecting to a given endpoint."]
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> AbciClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::ResponseBody: Body + HttpBody + Send + 'static,
+        T::Error: Into<StdError>,
+        <T::ResponseBody as HttpBody>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_interceptor(inner: T, interceptor: impl Into<tonic::Interceptor>) -> Self {
+            let inner = tonic::client::Grpc::with_interceptor(inner, interceptor);
+            Self { inner }
+        }
+        pub async fn echo(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestEcho>,
+        ) -> Result<tonic::Response<super::ResponseEcho>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/Echo");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn flush(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestFlush>,
+        ) -> Result<tonic::Response<super::ResponseFlush>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/Flush");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestInfo>,
+        ) -> Result<tonic::Response<super::ResponseInfo>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/Info");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn check_tx(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestCheckTx>,
+        ) -> Result<tonic::Response<super::ResponseCheckTx>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/CheckTx");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestQuery>,
+        ) -> Result<tonic::Response<super::ResponseQuery>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/Query");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn commit(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestCommit>,
+        ) -> Result<tonic::Response<super::ResponseCommit>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/Commit");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn init_chain(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestInitChain>,
+        ) -> Result<tonic::Response<super::ResponseInitChain>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/InitChain");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_snapshots(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestListSnapshots>,
+        ) -> Result<tonic::Response<super::ResponseListSnapshots>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/ListSnapshots");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn offer_snapshot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestOfferSnapshot>,
+        ) -> Result<tonic::Response<super::ResponseOfferSnapshot>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/OfferSnapshot");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn load_snapshot_chunk(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestLoadSnapshotChunk>,
+        ) -> Result<tonic::Response<super::ResponseLoadSnapshotChunk>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/LoadSnapshotChunk");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn apply_snapshot_chunk(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestApplySnapshotChunk>,
+        ) -> Result<tonic::Response<super::ResponseApplySnapshotChunk>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/ApplySnapshotChunk");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn prepare_proposal(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestPrepareProposal>,
+        ) -> Result<tonic::Response<super::ResponsePrepareProposal>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/PrepareProposal");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn process_proposal(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestProcessProposal>,
+        ) -> Result<tonic::Response<super::ResponseProcessProposal>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/ProcessProposal");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn extend_vote(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestExtendVote>,
+        ) -> Result<tonic::Response<super::ResponseExtendVote>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/ExtendVote");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn verify_vote_extension(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestVerifyVoteExtension>,
+        ) -> Result<tonic::Response<super::ResponseVerifyVoteExtension>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/VerifyVoteExtension");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn finalize_block(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RequestFinalizeBlock>,
+        ) -> Result<tonic::Response<super::ResponseFinalizeBlock>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/tendermint.abci.ABCI/FinalizeBlock");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+    impl<T: Clone> Clone for AbciClient<T> {
+        fn clone(&self) -> Self {
+            Self {
+                inner: self.inner.clone(),
+            }
+        }
+    }
+    impl<T> std::fmt::Debug for AbciClient<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "AbciClient {{ ... }}")
+        }
+    }
+}
+#[cfg(feature = "grpc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc")))]
+#[doc = r" Generated server implementations."]
+pub mod abci_server {
+    #![allow(unused_variables, dead_code, missing_docs)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with AbciServer."]
+    #[async_trait]
+    pub trait Abci: Send + Sync + 'static {
+        async fn echo(
+            &self,
+            request: tonic::Request<super::RequestEcho>,
+        ) -> Result<tonic::Response<super::ResponseEcho>, tonic::Status>;
+        async fn flush(
+            &self,
+            request: tonic::Request<super::RequestFlush>,
+        ) -> Result<tonic::Response<super::ResponseFlush>, tonic::Status>;
+        async fn info(
+            &self,
+            request: tonic::Request<super::RequestInfo>,
+        ) -> Result<tonic::Response<super::ResponseInfo>, tonic::Status>;
+        async fn check_tx(
+            &self,
+            request: tonic::Request<super::RequestCheckTx>,
+        ) -> Result<tonic::Response<super::ResponseCheckTx>, tonic::Status>;
+        async fn query(
+            &self,
+            request: tonic::Request<super::RequestQuery>,
+        ) -> Result<tonic::Response<super::ResponseQuery>, tonic::Status>;
+        async fn commit(
+            &self,
+            request: tonic::Request<super::RequestCommit>,
+        ) -> Result<tonic::Response<super::ResponseCommit>, tonic::Status>;
+        async fn init_chain(
+            &self,
+            request: tonic::Request<super::RequestInitChain>,
+        ) -> Result<tonic::Response<super::ResponseInitChain>, tonic::Status>;
+        async fn list_snapshots(
+            &self,
+            request: tonic::Request<super::RequestListSnapshots>,
+        ) -> Result<tonic::Response<super::ResponseListSnapshots>, tonic::Status>;
+        async fn offer_snapshot(
+            &self,
+            request: tonic::Request<super::RequestOfferSnapshot>,
+        ) -> Result<tonic::Response<super::ResponseOfferSnapshot>, tonic::Status>;
+        async fn load_snapshot_chunk(
+            &self,
+            request: tonic::Request<super::RequestLoadSnapshotChunk>,
+        ) -> Result<tonic::Response<super::ResponseLoadSnapshotChunk>, tonic::Status>;
+        async fn apply_snapshot_chunk(
+            &self,
+            request: tonic::Request<super::RequestApplySnapshotChunk>,
+        ) -> Result<tonic::Response<super::ResponseApplySnapshotChunk>, tonic::Status>;
+        async fn prepare_proposal(
+            &self,
+            request: tonic::Request<super::RequestPrepareProposal>,
+        ) -> Result<tonic::Response<super::ResponsePrepareProposal>, tonic::Status>;
+        async fn process_proposal(
+            &self,
+            request: tonic::Request<super::RequestProcessProposal>,
+        ) -> Result<tonic::Response<super::ResponseProcessProposal>, tonic::Status>;
+        async fn extend_vote(
+            &self,
+            request: tonic::Request<super::RequestExtendVote>,
+        ) -> Result<tonic::Response<super::ResponseExtendVote>, tonic::Status>;
+        async fn verify_vote_extension(
+            &self,
+            request: tonic::Request<super::RequestVerifyVoteExtension>,
+        ) -> Result<tonic::Response<super::ResponseVerifyVoteExtension>, tonic::Status>;
+        async fn finalize_block(
+            &self,
+            request: tonic::Request<super::RequestFinalizeBlock>,
+        ) -> Result<tonic::Response<super::ResponseFinalizeBlock>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct AbciServer<T: Abci> {
+        inner: _Inner<T>,
+    }
+    struct _Inner<T>(Arc<T>, Option<tonic::Interceptor>);
+    impl<T: Abci> AbciServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner, None);
+            Self { inner }
+        }
+        pub fn with_interceptor(inner: T, interceptor: impl Into<tonic::Interceptor>) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner, Some(interceptor.into()));
+            Self { inner }
+        }
+    }
+    impl<T, B> Service<http::Request<B>> for AbciServer<T>
+    where
+        T: Abci,
+        B: HttpBody + Send + Sync + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/tendermint.abci.ABCI/Echo" => {
+                    #[allow(non_camel_case_types)]
+                    struct EchoSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestEcho> for EchoSvc<T> {
+                        type Response = super::ResponseEcho;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestEcho>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).echo(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = EchoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/Flush" => {
+                    #[allow(non_camel_case_types)]
+                    struct FlushSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestFlush> for FlushSvc<T> {
+                        type Response = super::ResponseFlush;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestFlush>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).flush(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = FlushSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/Info" => {
+                    #[allow(non_camel_case_types)]
+                    struct InfoSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestInfo> for InfoSvc<T> {
+                        type Response = super::ResponseInfo;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestInfo>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).info(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = InfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/CheckTx" => {
+                    #[allow(non_camel_case_types)]
+                    struct CheckTxSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestCheckTx> for CheckTxSvc<T> {
+                        type Response = super::ResponseCheckTx;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestCheckTx>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).check_tx(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = CheckTxSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/Query" => {
+                    #[allow(non_camel_case_types)]
+                    struct QuerySvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestQuery> for QuerySvc<T> {
+                        type Response = super::ResponseQuery;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestQuery>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).query(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = QuerySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/Commit" => {
+                    #[allow(non_camel_case_types)]
+                    struct CommitSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestCommit> for CommitSvc<T> {
+                        type Response = super::ResponseCommit;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestCommit>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).commit(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = CommitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/InitChain" => {
+                    #[allow(non_camel_case_types)]
+                    struct InitChainSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestInitChain> for InitChainSvc<T> {
+                        type Response = super::ResponseInitChain;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestInitChain>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).init_chain(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = InitChainSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/ListSnapshots" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListSnapshotsSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestListSnapshots> for ListSnapshotsSvc<T> {
+                        type Response = super::ResponseListSnapshots;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestListSnapshots>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_snapshots(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = ListSnapshotsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/OfferSnapshot" => {
+                    #[allow(non_camel_case_types)]
+                    struct OfferSnapshotSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestOfferSnapshot> for OfferSnapshotSvc<T> {
+                        type Response = super::ResponseOfferSnapshot;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestOfferSnapshot>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).offer_snapshot(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = OfferSnapshotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/LoadSnapshotChunk" => {
+                    #[allow(non_camel_case_types)]
+                    struct LoadSnapshotChunkSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestLoadSnapshotChunk>
+                        for LoadSnapshotChunkSvc<T>
+                    {
+                        type Response = super::ResponseLoadSnapshotChunk;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestLoadSnapshotChunk>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).load_snapshot_chunk(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = LoadSnapshotChunkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/ApplySnapshotChunk" => {
+                    #[allow(non_camel_case_types)]
+                    struct ApplySnapshotChunkSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestApplySnapshotChunk>
+                        for ApplySnapshotChunkSvc<T>
+                    {
+                        type Response = super::ResponseApplySnapshotChunk;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestApplySnapshotChunk>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).apply_snapshot_chunk(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = ApplySnapshotChunkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/PrepareProposal" => {
+                    #[allow(non_camel_case_types)]
+                    struct PrepareProposalSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestPrepareProposal> for PrepareProposalSvc<T> {
+                        type Response = super::ResponsePrepareProposal;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestPrepareProposal>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).prepare_proposal(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = PrepareProposalSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/ProcessProposal" => {
+                    #[allow(non_camel_case_types)]
+                    struct ProcessProposalSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestProcessProposal> for ProcessProposalSvc<T> {
+                        type Response = super::ResponseProcessProposal;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestProcessProposal>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).process_proposal(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = ProcessProposalSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/ExtendVote" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExtendVoteSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestExtendVote> for ExtendVoteSvc<T> {
+                        type Response = super::ResponseExtendVote;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestExtendVote>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).extend_vote(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = ExtendVoteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/VerifyVoteExtension" => {
+                    #[allow(non_camel_case_types)]
+                    struct VerifyVoteExtensionSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestVerifyVoteExtension>
+                        for VerifyVoteExtensionSvc<T>
+                    {
+                        type Response = super::ResponseVerifyVoteExtension;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestVerifyVoteExtension>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).verify_vote_extension(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = VerifyVoteExtensionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/tendermint.abci.ABCI/FinalizeBlock" => {
+                    #[allow(non_camel_case_types)]
+                    struct FinalizeBlockSvc<T: Abci>(pub Arc<T>);
+                    impl<T: Abci> tonic::server::UnaryService<super::RequestFinalizeBlock> for FinalizeBlockSvc<T> {
+                        type Response = super::ResponseFinalizeBlock;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestFinalizeBlock>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).finalize_block(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let interceptor = inner.1.clone();
+                        let inner = inner.0;
+                        let method = FinalizeBlockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = if let Some(interceptor) = interceptor {
+                            tonic::server::Grpc::with_interceptor(codec, interceptor)
+                        } else {
+                            tonic::server::Grpc::new(codec)
+                        };
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(tonic::body::BoxBody::empty())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: Abci> Clone for AbciServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self { inner }
+        }
+    }
+    impl<T: Abci> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone(), self.1.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Abci> tonic::transport::NamedService for AbciServer<T> {
+        const NAME: &'static str = "tendermint.abci.ABCI";
+    }
+}
@@ -0,0 +1,15 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(::serde::Deserialize, ::serde::Serialize)]
+pub struct BitArray {
+    #[prost(int64, tag="1")]
+    pub bits: i64,
+    #[prost(uint64, repeated, tag="2")]
+    pub elems: ::prost::alloc::vec::Vec<u64>,
+}
+
+proto3_json! {
+    BitArray {
+        (bits: Int64, "bits", "bits")
+        (elems: Repeated<Int64>, "elems", "elems")
+    }
+}
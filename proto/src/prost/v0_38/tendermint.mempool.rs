@@ -0,0 +1,28 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Txs {
+    #[prost(bytes, repeated, tag="1")]
+    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Message {
+    #[prost(oneof="message::Sum", tags="1")]
+    pub sum: ::core::option::Option<message::Sum>,
+}
+pub mod message {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Sum {
+        #[prost(message, tag="1")]
+        Txs(super::Txs),
+    }
+}
+
+proto3_json! {
+    Txs {
+        (txs: Repeated<Bytes>, "txs", "txs")
+    }
+    Message {
+        (oneof sum: message::Sum {
+            Txs(Message), "txs", "txs";
+        })
+    }
+}
@@ -0,0 +1,154 @@
+/// LegacyABCIResponses retains the responses
+/// of the legacy ABCI calls during block processing.
+/// Note ReponseDeliverTx is renamed to ExecTxResult but they are semantically the same
+/// Kept for backwards compatibility for versions prior to v0.38
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LegacyAbciResponses {
+    #[prost(message, repeated, tag="1")]
+    pub deliver_txs: ::prost::alloc::vec::Vec<super::abci::ExecTxResult>,
+    #[prost(message, optional, tag="2")]
+    pub end_block: ::core::option::Option<ResponseEndBlock>,
+    #[prost(message, optional, tag="3")]
+    pub begin_block: ::core::option::Option<ResponseBeginBlock>,
+}
+/// ResponseBeginBlock is kept for backwards compatibility for versions prior to v0.38
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseBeginBlock {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<super::abci::Event>,
+}
+/// ResponseEndBlock is kept for backwards compatibility for versions prior to v0.38
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResponseEndBlock {
+    #[prost(message, repeated, tag="1")]
+    pub validator_updates: ::prost::alloc::vec::Vec<super::abci::ValidatorUpdate>,
+    #[prost(message, optional, tag="2")]
+    pub consensus_param_updates: ::core::option::Option<super::types::ConsensusParams>,
+    #[prost(message, repeated, tag="3")]
+    pub events: ::prost::alloc::vec::Vec<super::abci::Event>,
+}
+/// ValidatorsInfo represents the latest validator set, or the last height it changed
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidatorsInfo {
+    #[prost(message, optional, tag="1")]
+    pub validator_set: ::core::option::Option<super::types::ValidatorSet>,
+    #[prost(int64, tag="2")]
+    pub last_height_changed: i64,
+}
+/// ConsensusParamsInfo represents the latest consensus params, or the last height it changed
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConsensusParamsInfo {
+    #[prost(message, optional, tag="1")]
+    pub consensus_params: ::core::option::Option<super::types::ConsensusParams>,
+    #[prost(int64, tag="2")]
+    pub last_height_changed: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbciResponsesInfo {
+    /// Retains the responses of the legacy ABCI calls during block processing.
+    #[prost(message, optional, tag="1")]
+    pub legacy_abci_responses: ::core::option::Option<LegacyAbciResponses>,
+    #[prost(int64, tag="2")]
+    pub height: i64,
+    #[prost(message, optional, tag="3")]
+    pub response_finalize_block: ::core::option::Option<super::abci::ResponseFinalizeBlock>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Version {
+    #[prost(message, optional, tag="1")]
+    pub consensus: ::core::option::Option<super::version::Consensus>,
+    #[prost(string, tag="2")]
+    pub software: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct State {
+    #[prost(message, optional, tag="1")]
+    pub version: ::core::option::Option<Version>,
+    /// immutable
+    #[prost(string, tag="2")]
+    pub chain_id: ::prost::alloc::string::String,
+    #[prost(int64, tag="14")]
+    pub initial_height: i64,
+    /// LastBlockHeight=0 at genesis (ie. block(H=0) does not exist)
+    #[prost(int64, tag="3")]
+    pub last_block_height: i64,
+    #[prost(message, optional, tag="4")]
+    pub last_block_id: ::core::option::Option<super::types::BlockId>,
+    #[prost(message, optional, tag="5")]
+    pub last_block_time: ::core::option::Option<crate::google::protobuf::Timestamp>,
+    /// LastValidators is used to validate block.LastCommit.
+    /// Validators are persisted to the database separately every time they change,
+    /// so we can query for historical validator sets.
+    /// Note that if s.LastBlockHeight causes a valset change,
+    /// we set s.LastHeightValidatorsChanged = s.LastBlockHeight + 1 + 1
+    /// Extra +1 due to nextValSet delay.
+    #[prost(message, optional, tag="6")]
+    pub next_validators: ::core::option::Option<super::types::ValidatorSet>,
+    #[prost(message, optional, tag="7")]
+    pub validators: ::core::option::Option<super::types::ValidatorSet>,
+    #[prost(message, optional, tag="8")]
+    pub last_validators: ::core::option::Option<super::types::ValidatorSet>,
+    #[prost(int64, tag="9")]
+    pub last_height_validators_changed: i64,
+    /// Consensus parameters used for validating blocks.
+    /// Changes returned by EndBlock and updated after Commit.
+    #[prost(message, optional, tag="10")]
+    pub consensus_params: ::core::option::Option<super::types::ConsensusParams>,
+    #[prost(int64, tag="11")]
+    pub last_height_consensus_params_changed: i64,
+    /// Merkle root of the results from executing prev block
+    #[prost(bytes, tag="12")]
+    pub last_results_hash: ::prost::alloc::vec::Vec<u8>,
+    /// the latest AppHash we've received from calling abci.Commit()
+    #[prost(bytes, tag="13")]
+    pub app_hash: ::prost::alloc::vec::Vec<u8>,
+}
+
+proto3_json! {
+    LegacyAbciResponses {
+        (deliver_txs: Repeated<Message>, "deliverTxs", "deliver_txs")
+        (end_block: Optional<Message>, "endBlock", "end_block")
+        (begin_block: Optional<Message>, "beginBlock", "begin_block")
+    }
+    ResponseBeginBlock {
+        (events: Repeated<Message>, "events", "events")
+    }
+    ResponseEndBlock {
+        (validator_updates: Repeated<Message>, "validatorUpdates", "validator_updates")
+        (consensus_param_updates: Optional<Message>, "consensusParamUpdates", "consensus_param_updates")
+        (events: Repeated<Message>, "events", "events")
+    }
+    ValidatorsInfo {
+        (validator_set: Optional<Message>, "validatorSet", "validator_set")
+        (last_height_changed: Int64, "lastHeightChanged", "last_height_changed")
+    }
+    ConsensusParamsInfo {
+        (consensus_params: Optional<Message>, "consensusParams", "consensus_params")
+        (last_height_changed: Int64, "lastHeightChanged", "last_height_changed")
+    }
+    AbciResponsesInfo {
+        (legacy_abci_responses: Optional<Message>, "legacyAbciResponses", "legacy_abci_responses")
+        (height: Int64, "height", "height")
+        (response_finalize_block: Optional<Message>, "responseFinalizeBlock", "response_finalize_block")
+    }
+    Version {
+        (consensus: Optional<Message>, "consensus", "consensus")
+        (software: Plain, "software", "software")
+    }
+    State {
+        (version: Optional<Message>, "version", "version")
+        (chain_id: Plain, "chainId", "chain_id")
+        (initial_height: Int64, "initialHeight", "initial_height")
+        (last_block_height: Int64, "lastBlockHeight", "last_block_height")
+        (last_block_id: Optional<Message>, "lastBlockId", "last_block_id")
+        (last_block_time: Optional<Message>, "lastBlockTime", "last_block_time")
+        (next_validators: Optional<Message>, "nextValidators", "next_validators")
+        (validators: Optional<Message>, "validators", "validators")
+        (last_validators: Optional<Message>, "lastValidators", "last_validators")
+        (last_height_validators_changed: Int64, "lastHeightValidatorsChanged", "last_height_validators_changed")
+        (consensus_params: Optional<Message>, "consensusParams", "consensus_params")
+        (last_height_consensus_params_changed: Int64, "lastHeightConsensusParamsChanged", "last_height_consensus_params_changed")
+        (last_results_hash: Bytes, "lastResultsHash", "last_results_hash")
+        (app_hash: Bytes, "appHash", "app_hash")
+    }
+}
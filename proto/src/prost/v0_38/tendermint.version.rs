@@ -0,0 +1,34 @@
+/// App includes the protocol and software version for the application.
+/// This information is included in ResponseInfo. The App.Protocol can be
+/// updated in ResponseEndBlock.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct App {
+    #[prost(uint64, tag="1")]
+    pub protocol: u64,
+    #[prost(string, tag="2")]
+    pub software: ::prost::alloc::string::String,
+}
+/// Consensus captures the consensus rules for processing a block in the blockchain,
+/// including all blockchain data structures and the rules of the application's
+/// state transition machine.
+#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(::serde::Deserialize, ::serde::Serialize)]
+pub struct Consensus {
+    #[prost(uint64, tag="1")]
+    #[serde(with = "crate::serializers::from_str")]
+    pub block: u64,
+    #[prost(uint64, tag="2")]
+    #[serde(with = "crate::serializers::from_str", default)]
+    pub app: u64,
+}
+
+proto3_json! {
+    App {
+        (protocol: Int64, "protocol", "protocol")
+        (software: Plain, "software", "software")
+    }
+    Consensus {
+        (block: Int64, "block", "block")
+        (app: Int64, "app", "app")
+    }
+}
@@ -2,6 +2,7 @@
 
 /// Serialize into hexstring, deserialize from hexstring
 pub mod hexstring {
+    use crate::prelude::*;
     use serde::{Deserialize, Deserializer, Serializer};
     use subtle_encoding::hex;
 
@@ -30,6 +31,7 @@ pub mod hexstring {
 
 /// Serialize into base64string, deserialize from base64string
 pub mod base64string {
+    use crate::prelude::*;
     use serde::{Deserialize, Deserializer, Serializer};
     use subtle_encoding::base64;
 
@@ -66,6 +68,7 @@ pub mod base64string {
 
 /// Serialize into Vec<base64string>, deserialize from Vec<base64string>
 pub mod vec_base64string {
+    use crate::prelude::*;
     use serde::{Deserialize, Deserializer, Serializer};
     use subtle_encoding::base64;
 
@@ -99,6 +102,7 @@ pub mod vec_base64string {
 
 /// Serialize into Option<base64string>, deserialize from Option<base64string>
 pub mod option_base64string {
+    use crate::prelude::*;
     use serde::{Deserialize, Deserializer, Serializer};
     use subtle_encoding::base64;
 
@@ -125,6 +129,7 @@ pub mod option_base64string {
 
 /// Serialize into string, deserialize from string
 pub mod string {
+    use crate::prelude::*;
     use serde::{Deserialize, Deserializer, Serializer};
 
     /// Deserialize string into Vec<u8>
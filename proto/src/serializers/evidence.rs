@@ -1,67 +1,85 @@
-use crate::tendermint::types::evidence::Sum;
-use crate::tendermint::types::Evidence;
+//! EvidenceVariant helper structs for evidence serialization, one for each
+//! version of the protos.
 
-/// EvidenceVariant helper struct for evidence serialization
-/// This is a workaround until we figure a better way of JSON serializing evidence.
-/// It is a modified copy of the crate::tendermint::types::evidence::Sum struct.
-#[allow(clippy::large_enum_variant)]
-#[derive(Clone, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
-#[serde(tag = "type", content = "value")]
-pub enum EvidenceVariant {
-    /// Provided for when the evidence struct's optional `sum` field is `None`.
-    None,
-    #[serde(rename = "tendermint/DuplicateVoteEvidence")]
-    DuplicateVoteEvidence(crate::tendermint::types::DuplicateVoteEvidence),
-    #[serde(rename = "tendermint/LightClientAttackEvidence")]
-    LightClientAttackEvidence(crate::tendermint::types::LightClientAttackEvidence),
-}
+macro_rules! evidence_variant {
+    ($version:ident) => {
+        /// EvidenceVariant helper struct for evidence serialization
+        pub mod $version {
+            use crate::$version::types::evidence::Sum;
+            use crate::$version::types::Evidence;
 
-impl From<EvidenceVariant> for Evidence {
-    fn from(value: EvidenceVariant) -> Self {
-        match value {
-            EvidenceVariant::None => Evidence { sum: None },
-            _ => Evidence {
-                sum: Some(value.into()),
-            },
-        }
-    }
-}
+            /// EvidenceVariant helper struct for evidence serialization
+            /// This is a workaround until we figure a better way of JSON serializing evidence.
+            /// It is a modified copy of the crate::types::evidence::Sum struct.
+            #[allow(clippy::large_enum_variant)]
+            #[derive(Clone, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
+            #[serde(tag = "type", content = "value")]
+            pub enum EvidenceVariant {
+                /// Provided for when the evidence struct's optional `sum` field is `None`.
+                None,
+                #[serde(rename = "tendermint/DuplicateVoteEvidence")]
+                DuplicateVoteEvidence(crate::$version::types::DuplicateVoteEvidence),
+                #[serde(rename = "tendermint/LightClientAttackEvidence")]
+                LightClientAttackEvidence(crate::$version::types::LightClientAttackEvidence),
+            }
 
-impl From<Evidence> for EvidenceVariant {
-    fn from(value: Evidence) -> Self {
-        match value.sum {
-            Some(sum) => sum.into(),
-            None => Self::None,
-        }
-    }
-}
+            impl From<EvidenceVariant> for Evidence {
+                fn from(value: EvidenceVariant) -> Self {
+                    match value {
+                        EvidenceVariant::None => Evidence { sum: None },
+                        _ => Evidence {
+                            sum: Some(value.into()),
+                        },
+                    }
+                }
+            }
 
-impl From<Sum> for EvidenceVariant {
-    fn from(value: Sum) -> Self {
-        match value {
-            Sum::DuplicateVoteEvidence(d) => Self::DuplicateVoteEvidence(d),
-            Sum::LightClientAttackEvidence(l) => Self::LightClientAttackEvidence(l),
-        }
-    }
-}
+            impl From<Evidence> for EvidenceVariant {
+                fn from(value: Evidence) -> Self {
+                    match value.sum {
+                        Some(sum) => sum.into(),
+                        None => Self::None,
+                    }
+                }
+            }
 
-impl From<EvidenceVariant> for Sum {
-    fn from(value: EvidenceVariant) -> Self {
-        match value {
-            // This should never be called - should be handled instead in the
-            // `impl From<EvidenceVariant> for Evidence` above.
-            EvidenceVariant::None => {
-                panic!("non-existent evidence cannot be converted into its protobuf representation")
+            impl From<Sum> for EvidenceVariant {
+                fn from(value: Sum) -> Self {
+                    match value {
+                        Sum::DuplicateVoteEvidence(d) => Self::DuplicateVoteEvidence(d),
+                        Sum::LightClientAttackEvidence(l) => Self::LightClientAttackEvidence(l),
+                    }
+                }
+            }
+
+            impl From<EvidenceVariant> for Sum {
+                fn from(value: EvidenceVariant) -> Self {
+                    match value {
+                        // This should never be called - should be handled instead in the
+                        // `impl From<EvidenceVariant> for Evidence` above.
+                        EvidenceVariant::None => {
+                            panic!("non-existent evidence cannot be converted into its protobuf representation")
+                        }
+                        EvidenceVariant::DuplicateVoteEvidence(d) => Self::DuplicateVoteEvidence(d),
+                        EvidenceVariant::LightClientAttackEvidence(l) => {
+                            Self::LightClientAttackEvidence(l)
+                        }
+                    }
+                }
             }
-            EvidenceVariant::DuplicateVoteEvidence(d) => Self::DuplicateVoteEvidence(d),
-            EvidenceVariant::LightClientAttackEvidence(l) => Self::LightClientAttackEvidence(l),
         }
-    }
+    };
 }
 
+evidence_variant!(v0_34);
+#[cfg(feature = "v0_37")]
+evidence_variant!(v0_37);
+#[cfg(feature = "v0_38")]
+evidence_variant!(v0_38);
+
 #[cfg(test)]
 mod test {
-    use super::*;
+    use crate::types::Evidence;
 
     // Minimally reproduce https://github.com/informalsystems/tendermint-rs/issues/782
     #[test]
@@ -1,8 +1,9 @@
 //! De/serialize an optional type that must be converted from/to a string.
 
+use crate::prelude::*;
+use core::str::FromStr;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serializer};
-use std::str::FromStr;
 
 pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -19,7 +20,7 @@ pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
     T: FromStr,
-    T::Err: std::error::Error,
+    T::Err: core::fmt::Display,
 {
     let s = match Option::<String>::deserialize(deserializer)? {
         Some(s) => s,
@@ -2,6 +2,7 @@
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::google::protobuf::Timestamp;
+use crate::prelude::*;
 use chrono::{DateTime, LocalResult, TimeZone, Utc};
 use serde::ser::Error;
 
@@ -94,6 +95,7 @@ pub fn to_rfc3339_nanos(t: &DateTime<Utc>) -> String {
 mod test {
     use super::*;
     use crate::google::protobuf::Timestamp;
+    use alloc::vec;
     use serde::{Deserialize, Serialize};
 
     // The Go code with which the following timestamps
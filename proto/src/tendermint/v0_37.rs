@@ -0,0 +1,66 @@
+//! Tendermint-proto auto-generated sub-modules for CometBFT v0.37
+
+pub mod statesync {
+    include!("../prost/v0_37/tendermint.statesync.rs");
+}
+
+pub mod abci {
+    include!("../prost/v0_37/tendermint.abci.rs");
+}
+
+pub mod store {
+    include!("../prost/v0_37/tendermint.store.rs");
+}
+
+pub mod version {
+    include!("../prost/v0_37/tendermint.version.rs");
+}
+
+pub mod types {
+    include!("../prost/v0_37/tendermint.types.rs");
+}
+
+pub mod consensus {
+    include!("../prost/v0_37/tendermint.consensus.rs");
+}
+
+pub mod p2p {
+    include!("../prost/v0_37/tendermint.p2p.rs");
+}
+
+pub mod privval {
+    include!("../prost/v0_37/tendermint.privval.rs");
+}
+
+pub mod blocksync {
+    include!("../prost/v0_37/tendermint.blocksync.rs");
+}
+
+pub mod crypto {
+    include!("../prost/v0_37/tendermint.crypto.rs");
+}
+
+pub mod mempool {
+    include!("../prost/v0_37/tendermint.mempool.rs");
+}
+
+pub mod state {
+    include!("../prost/v0_37/tendermint.state.rs");
+}
+
+pub mod libs {
+    pub mod bits {
+        include!("../prost/v0_37/tendermint.libs.bits.rs");
+    }
+}
+
+pub mod rpc {
+    pub mod grpc {
+        include!("../prost/v0_37/tendermint.rpc.grpc.rs");
+    }
+}
+
+pub mod meta {
+    pub const REPOSITORY: &str = "https://github.com/cometbft/cometbft";
+    pub const COMMITISH: &str = "v0.37.2";
+}
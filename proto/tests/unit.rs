@@ -109,3 +109,257 @@ pub fn protobuf_struct_conveniences_example() {
     let new_domain_type = BlockId::decode_length_delimited_vec(&wire).unwrap();
     assert_eq!(my_domain_type, new_domain_type);
 }
+
+#[cfg(all(feature = "v0_37", feature = "v0_38"))]
+#[test]
+pub fn protobuf_versions_side_by_side() {
+    use prost::Message;
+    use tendermint_proto::{v0_34, v0_37, v0_38};
+
+    // The root of the crate re-exports the Tendermint v0.34 protos
+    let header = RawPartSetHeader {
+        total: 1,
+        hash: vec![42; 32],
+    };
+    let mut wire = Vec::new();
+    header.encode(&mut wire).unwrap();
+    assert_eq!(
+        v0_34::types::PartSetHeader::decode(wire.as_slice()).unwrap(),
+        header
+    );
+
+    // Messages which didn't change between versions are wire-compatible
+    let header = v0_37::types::PartSetHeader::decode(wire.as_slice()).unwrap();
+    assert_eq!(header.total, 1);
+    let header = v0_38::types::PartSetHeader::decode(wire.as_slice()).unwrap();
+    assert_eq!(header.hash, vec![42; 32]);
+
+    assert_eq!(v0_34::meta::COMMITISH, "v0.34.0");
+    assert_eq!(v0_37::meta::COMMITISH, "v0.37.2");
+    assert_eq!(v0_38::meta::COMMITISH, "v0.38.0");
+}
+
+#[cfg(feature = "grpc")]
+#[test]
+pub fn grpc_service_stubs() {
+    use tendermint_proto::rpc::grpc::broadcast_api_server::{BroadcastApi, BroadcastApiServer};
+    use tendermint_proto::rpc::grpc::{
+        RequestBroadcastTx, RequestPing, ResponseBroadcastTx, ResponsePing,
+    };
+    use tonic::codegen::async_trait;
+    use tonic::transport::NamedService;
+    use tonic::{Request, Response, Status};
+
+    struct Node;
+
+    #[async_trait]
+    impl BroadcastApi for Node {
+        async fn ping(
+            &self,
+            _request: Request<RequestPing>,
+        ) -> Result<Response<ResponsePing>, Status> {
+            Ok(Response::new(ResponsePing {}))
+        }
+
+        async fn broadcast_tx(
+            &self,
+            _request: Request<RequestBroadcastTx>,
+        ) -> Result<Response<ResponseBroadcastTx>, Status> {
+            Err(Status::unimplemented("broadcast_tx"))
+        }
+    }
+
+    fn service_name<S: NamedService>(_service: &S) -> &'static str {
+        S::NAME
+    }
+
+    let server = BroadcastApiServer::new(Node);
+    assert_eq!(service_name(&server), "tendermint.rpc.grpc.BroadcastAPI");
+}
+
+#[test]
+pub fn proto3_json_matches_go() {
+    use tendermint_proto::google::protobuf::Timestamp;
+    use tendermint_proto::json;
+    use tendermint_proto::types::{SignedMsgType, Vote};
+
+    // As encoded by Go's jsonpb
+    let go = r#"{"type":"SIGNED_MSG_TYPE_PRECOMMIT","height":"12","round":2,"blockId":{"hash":"AQID","partSetHeader":{"total":1,"hash":"BAU="}},"timestamp":"2021-01-02T03:04:05.123Z","validatorAddress":"q6s=","validatorIndex":3,"signature":"/w=="}"#;
+    let vote = Vote {
+        r#type: SignedMsgType::Precommit as i32,
+        height: 12,
+        round: 2,
+        block_id: Some(RawBlockId {
+            hash: vec![1, 2, 3],
+            part_set_header: Some(RawPartSetHeader {
+                total: 1,
+                hash: vec![4, 5],
+            }),
+        }),
+        timestamp: Some(Timestamp {
+            seconds: 1_609_556_645,
+            nanos: 123_000_000,
+        }),
+        validator_address: vec![0xAB, 0xAB],
+        validator_index: 3,
+        signature: vec![0xFF],
+        ..Default::default()
+    };
+    assert_eq!(json::to_string(&vote).unwrap(), go);
+    assert_eq!(json::from_str::<Vote>(go).unwrap(), vote);
+}
+
+#[test]
+pub fn proto3_json_accepts_go_alternatives() {
+    use tendermint_proto::google::protobuf::Duration;
+    use tendermint_proto::json;
+    use tendermint_proto::types::{SignedMsgType, Vote};
+
+    // Go also accepts the protobuf names of the fields, numbers for 64-bit
+    // integers and enumerations, unpadded base64 and nulls
+    let vote: Vote = json::from_str(
+        r#"{"type":1,"height":12,"round":"2","block_id":null,"validator_address":"q6s","signature":null}"#,
+    )
+    .unwrap();
+    assert_eq!(vote.r#type, SignedMsgType::Prevote as i32);
+    assert_eq!(vote.height, 12);
+    assert_eq!(vote.round, 2);
+    assert_eq!(vote.block_id, None);
+    assert_eq!(vote.validator_address, vec![0xAB, 0xAB]);
+
+    assert!(json::from_str::<Vote>(r#"{"votingPower":"1"}"#).is_err());
+    assert!(json::from_str::<Vote>(r#"{"type":"PRECOMMIT"}"#).is_err());
+
+    for (duration, go) in &[
+        ((1, 500_000_000), "1.500s"),
+        ((0, -1_000), "-0.000001s"),
+        ((3, 0), "3s"),
+    ] {
+        let duration = Duration {
+            seconds: duration.0,
+            nanos: duration.1,
+        };
+        let encoded = serde_json::to_string(&json::Proto3(duration.clone())).unwrap();
+        assert_eq!(encoded, format!("{:?}", go));
+        assert_eq!(
+            serde_json::from_str::<json::Proto3<Duration>>(&encoded).unwrap(),
+            json::Proto3(duration)
+        );
+    }
+}
+
+#[test]
+pub fn proto3_json_oneofs() {
+    use tendermint_proto::crypto::{public_key, PublicKey};
+    use tendermint_proto::json;
+
+    let key = PublicKey {
+        sum: Some(public_key::Sum::Ed25519(vec![0; 3])),
+    };
+    let encoded = json::to_string(&key).unwrap();
+    assert_eq!(encoded, r#"{"ed25519":"AAAA"}"#);
+    assert_eq!(json::from_str::<PublicKey>(&encoded).unwrap(), key);
+    assert_eq!(json::to_string(&PublicKey::default()).unwrap(), "{}");
+}
+
+/// Sign bytes of a vote, as produced by Go (see `TestVoteSignBytesTestVectors`)
+const GO_CANONICAL_VOTE: [u8; 125] = [
+    0x7c, // length
+    0x8, 0x1, // type
+    0x11, 0x39, 0x30, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, // height
+    0x19, 0x2, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, // round
+    0x22, 0x4a, // block_id
+    0xa, 0x20, 0x44, 0x45, 0x41, 0x44, 0x42, 0x45, 0x45, 0x46, 0x44, 0x45, 0x41, 0x44, 0x42, 0x45,
+    0x45, 0x46, 0x42, 0x41, 0x46, 0x42, 0x41, 0x46, 0x42, 0x41, 0x46, 0x42, 0x41, 0x46, 0x42, 0x41,
+    0x46, 0x41, // block_id.hash
+    0x12, 0x26, 0x8, 0xc0, 0x84, 0x3d, 0x12, 0x20, 0x30, 0x30, 0x32, 0x32, 0x34, 0x34, 0x36, 0x36,
+    0x38, 0x38, 0x41, 0x41, 0x43, 0x43, 0x45, 0x45, 0x31, 0x31, 0x33, 0x33, 0x35, 0x35, 0x37, 0x37,
+    0x39, 0x39, 0x42, 0x42, 0x44, 0x44, 0x46, 0x46, // block_id.part_set_header
+    0x2a, 0xb, 0x8, 0xb1, 0xd3, 0x81, 0xd2, 0x5, 0x10, 0x80, 0x9d, 0xca, 0x6f, // timestamp
+    0x32, 0xd, 0x74, 0x65, 0x73, 0x74, 0x5f, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x5f, 0x69,
+    0x64, // chain_id
+];
+
+fn go_canonical_vote() -> tendermint_proto::types::CanonicalVote {
+    use tendermint_proto::google::protobuf::Timestamp;
+    use tendermint_proto::types::{CanonicalBlockId, CanonicalPartSetHeader, CanonicalVote};
+
+    CanonicalVote {
+        r#type: 1,
+        height: 12345,
+        round: 2,
+        block_id: Some(CanonicalBlockId {
+            hash: b"DEADBEEFDEADBEEFBAFBAFBAFBAFBAFA".to_vec(),
+            part_set_header: Some(CanonicalPartSetHeader {
+                total: 1_000_000,
+                hash: b"0022446688AACCEE1133557799BBDDFF".to_vec(),
+            }),
+        }),
+        timestamp: Some(Timestamp {
+            seconds: 1_514_170_801,
+            nanos: 234_000_000,
+        }),
+        chain_id: "test_chain_id".to_string(),
+    }
+}
+
+#[test]
+pub fn canonical_encoding_matches_go() {
+    use tendermint_proto::canonical;
+    use tendermint_proto::types::CanonicalVote;
+
+    let vote = go_canonical_vote();
+    assert_eq!(
+        canonical::encode_length_delimited(&vote),
+        GO_CANONICAL_VOTE.to_vec()
+    );
+    assert_eq!(canonical::encode(&vote), GO_CANONICAL_VOTE[1..].to_vec());
+    assert_eq!(
+        canonical::decode_length_delimited::<CanonicalVote>(&GO_CANONICAL_VOTE).unwrap(),
+        vote
+    );
+    assert_eq!(
+        canonical::decode::<CanonicalVote>(&GO_CANONICAL_VOTE[1..]).unwrap(),
+        vote
+    );
+}
+
+#[test]
+pub fn canonical_decoding_rejects_other_encodings() {
+    use tendermint_proto::canonical;
+    use tendermint_proto::types::CanonicalVote;
+
+    let canonical = &GO_CANONICAL_VOTE[1..];
+    let expect_non_canonical = |wire: &[u8]| {
+        // prost accepts these encodings, which decode to the same vote
+        assert_eq!(
+            <CanonicalVote as prost::Message>::decode(wire).unwrap(),
+            go_canonical_vote()
+        );
+        let error = canonical::decode::<CanonicalVote>(wire).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            tendermint_proto::Kind::NonCanonicalEncoding
+        ));
+    };
+
+    // Unknown field 7
+    expect_non_canonical(&[canonical, &[0x38, 0x01]].concat());
+    // Default value of an unset field, e.g. the round of the vote set to 0
+    // before it is set to 2
+    expect_non_canonical(
+        &[
+            &canonical[..2],
+            &[0x19, 0, 0, 0, 0, 0, 0, 0, 0],
+            &canonical[2..],
+        ]
+        .concat(),
+    );
+    // Fields out of order: the chain ID first
+    expect_non_canonical(&[&canonical[109..], &canonical[..109]].concat());
+    // Trailing bytes after a length delimited message
+    assert!(canonical::decode_length_delimited::<CanonicalVote>(
+        &[&GO_CANONICAL_VOTE[..], &[0]].concat()
+    )
+    .is_err());
+}
@@ -0,0 +1,282 @@
+//! Typed, decoded ABCI events as returned in `/block_results` and `/tx`
+//! responses, as well as in transaction subscription events.
+
+use crate::event::TmEvent;
+use crate::{Error, Result};
+use std::fmt;
+use subtle_encoding::base64;
+use tendermint::abci;
+
+/// Determines how event attribute keys and values that are not valid UTF-8
+/// are handled when decoding events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeDecoding {
+    /// Fail to decode the event if any of its attributes are not valid UTF-8.
+    Utf8Only,
+    /// Keep the raw bytes of any attribute keys or values that are not valid
+    /// UTF-8.
+    RawFallback,
+}
+
+impl Default for AttributeDecoding {
+    fn default() -> Self {
+        AttributeDecoding::Utf8Only
+    }
+}
+
+/// The decoded key or value of an event attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AttributeData {
+    /// A valid UTF-8 string.
+    Utf8(String),
+    /// Raw bytes that are not valid UTF-8.
+    Raw(Vec<u8>),
+}
+
+impl AttributeData {
+    fn decode(data: Vec<u8>, decoding: AttributeDecoding) -> Result<Self> {
+        match String::from_utf8(data) {
+            Ok(s) => Ok(AttributeData::Utf8(s)),
+            Err(e) => match decoding {
+                AttributeDecoding::RawFallback => Ok(AttributeData::Raw(e.into_bytes())),
+                AttributeDecoding::Utf8Only => Err(Error::parse_error(format!(
+                    "event attribute is not valid UTF-8: {}",
+                    e
+                ))),
+            },
+        }
+    }
+
+    /// The attribute data as a string, if it is valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttributeData::Utf8(s) => Some(s),
+            AttributeData::Raw(_) => None,
+        }
+    }
+
+    /// The attribute data as raw bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            AttributeData::Utf8(s) => s.as_bytes(),
+            AttributeData::Raw(b) => b,
+        }
+    }
+}
+
+impl fmt::Display for AttributeData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeData::Utf8(s) => write!(f, "{}", s),
+            AttributeData::Raw(b) => write!(f, "{}", String::from_utf8_lossy(b)),
+        }
+    }
+}
+
+/// A single decoded event attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventAttribute {
+    /// The attribute's key.
+    pub key: AttributeData,
+    /// The attribute's value.
+    pub value: AttributeData,
+}
+
+/// A decoded ABCI event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// The type of the event (e.g. `transfer`).
+    pub kind: String,
+    /// The event's attributes, in the order in which they were emitted.
+    pub attributes: Vec<EventAttribute>,
+}
+
+impl Event {
+    /// The values of all attributes with the given key that are valid UTF-8.
+    pub fn values<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.attributes
+            .iter()
+            .filter(move |attr| attr.key.as_str() == Some(key))
+            .filter_map(|attr| attr.value.as_str())
+    }
+
+    /// The value of the first attribute with the given key, if it is valid
+    /// UTF-8.
+    pub fn value<'a>(&'a self, key: &'a str) -> Option<&'a str> {
+        self.values(key).next()
+    }
+}
+
+impl From<&abci::Event> for Event {
+    fn from(event: &abci::Event) -> Self {
+        Self {
+            kind: event.type_str.clone(),
+            attributes: event
+                .attributes
+                .iter()
+                .map(|attr| EventAttribute {
+                    key: AttributeData::Utf8(attr.key.clone()),
+                    value: AttributeData::Utf8(attr.value.clone()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A collection of decoded ABCI events.
+///
+/// ## Examples
+///
+/// ```rust
+/// use tendermint_rpc::abci_event::{Event, EventAttribute, AttributeData, Events};
+///
+/// let events = Events::from(vec![Event {
+///     kind: "transfer".to_string(),
+///     attributes: vec![EventAttribute {
+///         key: AttributeData::Utf8("amount".to_string()),
+///         value: AttributeData::Utf8("100stake".to_string()),
+///     }],
+/// }]);
+/// assert_eq!(Some("100stake"), events.find("transfer", "amount"));
+/// assert_eq!(None, events.find("transfer", "recipient"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Events(Vec<Event>);
+
+impl Events {
+    /// Decode the given events, whose attribute keys and values are
+    /// base64-encoded (as in transaction subscription events).
+    pub fn decode(events: &[TmEvent], decoding: AttributeDecoding) -> Result<Self> {
+        events
+            .iter()
+            .map(|event| {
+                Ok(Event {
+                    kind: event.event_type.clone(),
+                    attributes: event
+                        .attributes
+                        .iter()
+                        .map(|attr| {
+                            Ok(EventAttribute {
+                                key: AttributeData::decode(decode_base64(&attr.key)?, decoding)?,
+                                value: AttributeData::decode(
+                                    decode_base64(&attr.value)?,
+                                    decoding,
+                                )?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+
+    /// The value of the first attribute with the given key in the first event
+    /// of the given type that has such an attribute.
+    pub fn find<'a>(&'a self, kind: &'a str, key: &'a str) -> Option<&'a str> {
+        self.find_all(kind, key).next()
+    }
+
+    /// The values of all attributes with the given key in all events of the
+    /// given type.
+    pub fn find_all<'a>(&'a self, kind: &'a str, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.of_kind(kind).flat_map(move |event| event.values(key))
+    }
+
+    /// All events of the given type.
+    pub fn of_kind<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a Event> {
+        self.0.iter().filter(move |event| event.kind == kind)
+    }
+
+    /// Iterate over all of the events.
+    pub fn iter(&self) -> impl Iterator<Item = &Event> {
+        self.0.iter()
+    }
+
+    /// The number of events in this collection.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether this collection contains no events.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<Event>> for Events {
+    fn from(events: Vec<Event>) -> Self {
+        Self(events)
+    }
+}
+
+impl From<&[abci::Event]> for Events {
+    fn from(events: &[abci::Event]) -> Self {
+        events.iter().map(Event::from).collect()
+    }
+}
+
+impl std::iter::FromIterator<Event> for Events {
+    fn from_iter<I: IntoIterator<Item = Event>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Events {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>> {
+    base64::decode(s).map_err(Error::parse_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Attribute;
+
+    fn tm_event(kind: &str, attributes: &[(&str, &str)]) -> TmEvent {
+        TmEvent {
+            event_type: kind.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| Attribute {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn decodes_base64_attributes() {
+        // "amount" => "100stake"
+        let events = Events::decode(
+            &[tm_event("transfer", &[("YW1vdW50", "MTAwc3Rha2U=")])],
+            AttributeDecoding::Utf8Only,
+        )
+        .unwrap();
+        assert_eq!(Some("100stake"), events.find("transfer", "amount"));
+        assert_eq!(None, events.find("message", "amount"));
+    }
+
+    #[test]
+    fn falls_back_to_raw_bytes() {
+        // "key" => [0xff, 0xfe]
+        let raw = [tm_event("raw", &[("a2V5", "//4=")])];
+        assert!(Events::decode(&raw, AttributeDecoding::Utf8Only).is_err());
+
+        let events = Events::decode(&raw, AttributeDecoding::RawFallback).unwrap();
+        let event = events.of_kind("raw").next().unwrap();
+        assert_eq!(
+            AttributeData::Raw(vec![0xff, 0xfe]),
+            event.attributes[0].value
+        );
+        assert_eq!(None, event.value("key"));
+    }
+}
@@ -1,16 +1,36 @@
 //! Tendermint RPC client.
 
+mod cache;
+pub use cache::{CacheConfig, CachingClient};
+mod compat;
+pub use compat::CompatMode;
+mod dynamic;
+pub use dynamic::DynClient;
+mod height;
+pub use height::HeightStream;
+mod instrument;
+mod replay;
+#[cfg(feature = "prometheus")]
+pub use instrument::PrometheusInterceptor;
+pub use instrument::{InstrumentedClient, Interceptor, Outcome};
+pub use replay::{BlockStream, BlockWithResults};
 mod subscription;
-pub use subscription::{Subscription, SubscriptionClient};
+pub use subscription::{BackpressurePolicy, Subscription, SubscriptionClient};
 pub mod sync;
+mod wire_log;
+pub use wire_log::{WireLog, WireLogConfig, WIRE_LOG_TARGET};
 
 mod transport;
 pub use transport::mock::{MockClient, MockRequestMatcher, MockRequestMethodMatcher};
 
+#[cfg(feature = "grpc")]
+pub use transport::grpc::{BroadcastTxResponse as GrpcBroadcastTxResponse, GrpcClient};
 #[cfg(feature = "http-client")]
 pub use transport::http::{HttpClient, HttpClientUrl};
 #[cfg(feature = "websocket-client")]
-pub use transport::websocket::{WebSocketClient, WebSocketClientDriver, WebSocketClientUrl};
+pub use transport::websocket::{
+    WebSocketClient, WebSocketClientConfig, WebSocketClientDriver, WebSocketClientUrl,
+};
 
 use crate::endpoint::validators::DEFAULT_VALIDATORS_PER_PAGE;
 use crate::endpoint::*;
@@ -19,11 +39,14 @@ use crate::paging::Paging;
 use crate::query::Query;
 use crate::{Order, Result, SimpleRequest};
 use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt;
 use std::time::Duration;
 use tendermint::abci::{self, Transaction};
 use tendermint::block::Height;
 use tendermint::evidence::Evidence;
-use tendermint::Genesis;
+use tendermint::{Genesis, Hash};
 use tokio::time;
 
 /// Provides lightweight access to the Tendermint RPC. It gives access to all
@@ -123,6 +146,30 @@ pub trait Client {
         self.perform(commit::Request::new(height.into())).await
     }
 
+    /// `/header`: get the header of the block at a given height.
+    ///
+    /// Only supported by nodes running CometBFT v0.38 and later.
+    async fn header<H>(&self, height: H) -> Result<header::Response>
+    where
+        H: Into<Height> + Send,
+    {
+        self.perform(header::Request::new(height.into())).await
+    }
+
+    /// `/header`: get the header of the latest block.
+    ///
+    /// Only supported by nodes running CometBFT v0.38 and later.
+    async fn latest_header(&self) -> Result<header::Response> {
+        self.perform(header::Request::default()).await
+    }
+
+    /// `/header_by_hash`: get the header of the block with the given hash.
+    ///
+    /// Only supported by nodes running CometBFT v0.38 and later.
+    async fn header_by_hash(&self, hash: Hash) -> Result<header_by_hash::Response> {
+        self.perform(header_by_hash::Request::new(hash)).await
+    }
+
     /// `/consensus_state`: get current consensus state
     async fn consensus_state(&self) -> Result<consensus_state::Response> {
         self.perform(consensus_state::Request::new()).await
@@ -254,8 +301,91 @@ pub trait Client {
         Ok(())
     }
 
+    /// Stream the latest height of the chain, checking for new blocks every
+    /// `poll_interval`.
+    ///
+    /// The stream starts with the current height, and only produces heights
+    /// greater than the previous ones. Clients which support subscriptions,
+    /// like the [`WebSocketClient`], produce the heights of `NewBlock` events
+    /// instead, falling back to polling if the subscription fails.
+    ///
+    /// [`WebSocketClient`]: struct.WebSocketClient.html
+    fn latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_>
+    where
+        Self: Sync,
+    {
+        height::poll(self, poll_interval)
+    }
+
+    /// Stream the blocks of the chain with their results, starting at the
+    /// given height and checking for new blocks every `poll_interval`.
+    ///
+    /// The blocks up to the current height are fetched first, after which
+    /// the stream follows the [`latest_height_stream`], so that each block
+    /// is produced exactly once and in order, without gaps, e.g. to index
+    /// the events emitted by the chain.
+    ///
+    /// [`latest_height_stream`]: Client::latest_height_stream
+    fn block_stream(&self, from: Height, poll_interval: Duration) -> BlockStream<'_>
+    where
+        Self: Sync,
+    {
+        replay::blocks(self, from, poll_interval)
+    }
+
+    /// Call the endpoint with the given name, which this crate doesn't model
+    /// (e.g. one added by a fork of Tendermint), with the given parameters,
+    /// returning its result as is.
+    async fn perform_raw(&self, method: impl Into<String> + Send, params: Value) -> Result<Value> {
+        self.perform_custom(method, params).await
+    }
+
+    /// Call the endpoint with the given name, which this crate doesn't model,
+    /// with the given parameters, parsing its result as a `T`.
+    async fn perform_custom<P, T>(&self, method: impl Into<String> + Send, params: P) -> Result<T>
+    where
+        P: fmt::Debug + DeserializeOwned + Serialize + Send,
+        T: fmt::Debug + DeserializeOwned + Serialize,
+    {
+        Ok(self
+            .perform(custom::Request::<P, T>::new(method, params))
+            .await?
+            .0)
+    }
+
     /// Perform a request against the RPC endpoint
     async fn perform<R>(&self, request: R) -> Result<R::Response>
     where
         R: SimpleRequest;
 }
+
+/// Provides access to Tendermint's "unsafe" RPC endpoints, which are only
+/// available on nodes whose RPC server has been configured with
+/// `unsafe = true`.
+///
+/// These endpoints allow for manipulation of a node's peers and are intended
+/// for use with local and development networks. This trait is implemented for
+/// all [`Client`]s, but needs to be explicitly imported in order to use them.
+#[async_trait]
+pub trait UnsafeClient: Client {
+    /// `/dial_peers`: dial the given peers (of the form
+    /// `<node ID>@<host>:<port>`).
+    async fn unsafe_dial_peers(
+        &self,
+        request: dial_peers::Request,
+    ) -> Result<dial_peers::Response> {
+        self.perform(request).await
+    }
+
+    /// `/dial_seeds`: dial the given seed nodes (of the form
+    /// `<node ID>@<host>:<port>`).
+    async fn unsafe_dial_seeds<I>(&self, seeds: I) -> Result<dial_seeds::Response>
+    where
+        I: IntoIterator<Item = String> + Send,
+    {
+        self.perform(dial_seeds::Request::new(seeds.into_iter().collect()))
+            .await
+    }
+}
+
+impl<C> UnsafeClient for C where C: Client + Sync {}
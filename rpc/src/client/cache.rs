@@ -0,0 +1,341 @@
+//! Response caching for immutable queries.
+
+use crate::client::height::HeightStream;
+use crate::client::subscription::{Subscription, SubscriptionClient};
+use crate::query::Query;
+use crate::{Client, Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Configuration for a [`CachingClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// The maximum number of responses to keep in the cache. Once this limit
+    /// is reached, the least recently used response is evicted.
+    pub capacity: usize,
+    /// How long responses are kept in the cache. If `None`, responses are
+    /// kept until they are evicted.
+    pub ttl: Option<Duration>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            ttl: None,
+        }
+    }
+}
+
+/// A client that wraps another client, caching the responses to queries
+/// whose results cannot change once available.
+///
+/// Only the following queries are cached:
+///
+/// * `/block`, `/block_results`, `/header` and `/validators` for an explicit
+///   height.
+/// * `/commit` for an explicit height, once the commit is canonical.
+/// * `/header_by_hash`, once a header has been found.
+///
+/// All other requests are passed straight through to the wrapped client.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{CacheConfig, CachingClient, Client, HttpClient};
+///
+/// let client = CachingClient::new(
+///     HttpClient::new("http://127.0.0.1:26657").unwrap(),
+///     CacheConfig::default(),
+/// );
+/// // Only the first request is sent to the node.
+/// let block = client.block(10_u32).await.unwrap();
+/// let block = client.block(10_u32).await.unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachingClient<C> {
+    inner: C,
+    cache: Arc<Mutex<LruCache>>,
+}
+
+impl<C> CachingClient<C> {
+    /// Wrap the given client with a cache configured as specified.
+    pub fn new(inner: C, config: CacheConfig) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(LruCache::new(config))),
+        }
+    }
+
+    /// The wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The number of responses presently cached.
+    pub fn cached_responses(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
+    /// Remove all cached responses.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear()
+    }
+}
+
+#[async_trait]
+impl<C> Client for CachingClient<C>
+where
+    C: Client + Sync + Send,
+{
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let method = request.method();
+        if !is_cacheable(&method) {
+            return self.inner.perform(request).await;
+        }
+        let params = serde_json::to_value(&request)
+            .map_err(|e| Error::client_internal_error(e.to_string()))?;
+        if method != Method::HeaderByHash && params.get("height").map_or(true, Value::is_null) {
+            // Queries for the latest height are not immutable.
+            return self.inner.perform(request).await;
+        }
+
+        let key = format!("{}:{}", method, params);
+        let cached = self.cache.lock().unwrap().get(&key);
+        if let Some(response) = cached.and_then(|value| serde_json::from_value(value).ok()) {
+            return Ok(response);
+        }
+
+        let response = self.inner.perform(request).await?;
+        if let Ok(value) = serde_json::to_value(&response) {
+            if is_final(&method, &value) {
+                self.cache.lock().unwrap().insert(key, value);
+            }
+        }
+        Ok(response)
+    }
+
+    fn latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+        self.inner.latest_height_stream(poll_interval)
+    }
+}
+
+#[async_trait]
+impl<C> SubscriptionClient for CachingClient<C>
+where
+    C: SubscriptionClient + Sync + Send,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<()> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+fn is_cacheable(method: &Method) -> bool {
+    matches!(
+        method,
+        Method::Block
+            | Method::BlockResults
+            | Method::Commit
+            | Method::Header
+            | Method::HeaderByHash
+            | Method::Validators
+    )
+}
+
+// Whether the given response can no longer change.
+fn is_final(method: &Method, response: &Value) -> bool {
+    match method {
+        Method::Commit => response.get("canonical") == Some(&Value::Bool(true)),
+        Method::HeaderByHash => response.get("header").map_or(false, |h| !h.is_null()),
+        _ => true,
+    }
+}
+
+#[derive(Debug)]
+struct LruCache {
+    config: CacheConfig,
+    entries: HashMap<String, (Value, Instant)>,
+    // Keys ordered from least to most recently used.
+    recency: VecDeque<String>,
+}
+
+impl LruCache {
+    fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            recency: VecDeque::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    fn get(&mut self, key: &str) -> Option<Value> {
+        let expired = match (self.entries.get(key), self.config.ttl) {
+            (None, _) => return None,
+            (Some((_, inserted)), Some(ttl)) => inserted.elapsed() > ttl,
+            (Some(_), None) => false,
+        };
+        self.touch(key);
+        if expired {
+            self.recency.pop_back();
+            self.entries.remove(key);
+            return None;
+        }
+        self.entries.get(key).map(|(value, _)| value.clone())
+    }
+
+    fn insert(&mut self, key: String, value: Value) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        if self
+            .entries
+            .insert(key.clone(), (value, Instant::now()))
+            .is_some()
+        {
+            self.touch(&key);
+            return;
+        }
+        self.recency.push_back(key);
+        while self.entries.len() > self.config.capacity {
+            match self.recency.pop_front() {
+                Some(evicted) => self.entries.remove(&evicted),
+                None => break,
+            };
+        }
+    }
+
+    // Marks the given key as the most recently used.
+    fn touch(&mut self, key: &str) {
+        if let Some(pos) = self.recency.iter().position(|k| k == key) {
+            if let Some(k) = self.recency.remove(pos) {
+                self.recency.push_back(k);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::instrument::{InstrumentedClient, Interceptor, Outcome};
+    use crate::event::Event;
+    use crate::{MockClient, MockRequestMethodMatcher, Response};
+    use futures::StreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tendermint::block::Height;
+
+    #[derive(Default)]
+    struct CountingInterceptor(AtomicUsize);
+
+    impl Interceptor for CountingInterceptor {
+        fn on_response(&self, _method: &Method, _duration: Duration, _outcome: Outcome<'_>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_height_pinned_queries() {
+        let fixture = std::fs::read_to_string("./tests/support/block.json").unwrap();
+        let matcher = MockRequestMethodMatcher::default().map(Method::Block, Ok(fixture));
+        let (mock, _driver) = MockClient::new(matcher);
+        let client = CachingClient::new(
+            InstrumentedClient::new(mock, CountingInterceptor::default()),
+            CacheConfig::default(),
+        );
+        let requests = || client.inner().interceptor().0.load(Ordering::SeqCst);
+
+        let first = client.block(Height::from(10_u32)).await.unwrap();
+        let second = client.block(Height::from(10_u32)).await.unwrap();
+        assert_eq!(first.block.header.height, second.block.header.height);
+        assert_eq!(1, requests());
+        assert_eq!(1, client.cached_responses());
+
+        // Queries for the latest block are never cached.
+        client.latest_block().await.unwrap();
+        client.latest_block().await.unwrap();
+        assert_eq!(3, requests());
+        assert_eq!(1, client.cached_responses());
+    }
+
+    #[tokio::test]
+    async fn wrappers_forward_latest_height_stream() {
+        let fixture = std::fs::read_to_string("./tests/support/status.json")
+            .unwrap()
+            .replace("\"410744\"", "\"1607\"");
+        let matcher = MockRequestMethodMatcher::default().map(Method::Status, Ok(fixture));
+        let (mock, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        let client = CachingClient::new(
+            InstrumentedClient::new(mock, CountingInterceptor::default()),
+            CacheConfig::default(),
+        );
+
+        // Only the mock client follows new block events; polling every minute
+        // wouldn't produce the next height in time.
+        let mut heights = client.latest_height_stream(Duration::from_secs(60));
+        assert_eq!(
+            heights.next().await.unwrap().unwrap(),
+            Height::from(1607_u32)
+        );
+        let event = std::fs::read_to_string("./tests/support/event_new_block_1.json").unwrap();
+        client
+            .inner()
+            .inner()
+            .publish(&Event::from_string(&event).unwrap());
+        let next = tokio::time::timeout(Duration::from_secs(5), heights.next())
+            .await
+            .expect("new block event not followed");
+        assert_eq!(next.unwrap().unwrap(), Height::from(1608_u32));
+
+        drop(heights);
+        client.inner.into_inner().close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn lru_eviction_and_expiry() {
+        let mut cache = LruCache::new(CacheConfig {
+            capacity: 2,
+            ttl: None,
+        });
+        cache.insert("a".to_string(), Value::from(1));
+        cache.insert("b".to_string(), Value::from(2));
+        assert_eq!(Some(Value::from(1)), cache.get("a"));
+        cache.insert("c".to_string(), Value::from(3));
+        assert_eq!(None, cache.get("b"));
+        assert_eq!(Some(Value::from(1)), cache.get("a"));
+        assert_eq!(Some(Value::from(3)), cache.get("c"));
+
+        let mut cache = LruCache::new(CacheConfig {
+            capacity: 2,
+            ttl: Some(Duration::from_secs(0)),
+        });
+        cache.insert("a".to_string(), Value::from(1));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(None, cache.get("a"));
+        assert_eq!(0, cache.len());
+    }
+}
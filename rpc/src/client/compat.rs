@@ -0,0 +1,228 @@
+//! Compatibility with the RPC serialization formats of different versions of
+//! Tendermint/CometBFT.
+
+use crate::{Client, Error, Result};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use subtle_encoding::{base64, hex};
+use tendermint::Version;
+
+/// The version-specific RPC serialization format to use when communicating
+/// with a node.
+///
+/// This crate's response types follow the format of Tendermint v0.34. When
+/// talking to newer nodes, responses are first adapted to that format before
+/// being deserialized, and requests are adapted to the format the node
+/// expects. In particular:
+///
+/// * From v0.37 onwards, ABCI event attribute keys and values are plain
+///   strings instead of base64-encoded strings, and transaction hashes in
+///   `/tx` requests are base64-encoded.
+/// * From v0.38 onwards, `BeginBlock`/`EndBlock` events are replaced by
+///   `FinalizeBlock` events in `/block_results` responses (see
+///   [`crate::endpoint::block_results::Response::finalize_block_events`]).
+///
+/// The compatibility mode of a particular node can be detected using
+/// [`CompatMode::detect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CompatMode {
+    /// Tendermint v0.34 (and earlier).
+    V0_34,
+    /// Tendermint/CometBFT v0.37.
+    V0_37,
+    /// CometBFT v0.38 (and later).
+    V0_38,
+}
+
+impl Default for CompatMode {
+    fn default() -> Self {
+        CompatMode::V0_34
+    }
+}
+
+impl CompatMode {
+    /// The most recent version supported by this crate.
+    pub fn latest() -> Self {
+        CompatMode::V0_38
+    }
+
+    /// Determine the compatibility mode from a node's version, as reported in
+    /// the `node_info.version` field of the `/status` endpoint.
+    pub fn from_version(version: &Version) -> Result<Self> {
+        let version = version.to_string();
+        let mut parts = version.trim_start_matches('v').split('.');
+        let (major, minor) = match (parts.next(), parts.next()) {
+            (Some(major), Some(minor)) => (major.parse::<u64>(), minor.parse::<u64>()),
+            _ => return Err(unsupported_version(&version)),
+        };
+        match (major, minor) {
+            (Ok(0), Ok(minor)) if minor < 37 => Ok(CompatMode::V0_34),
+            (Ok(0), Ok(37)) => Ok(CompatMode::V0_37),
+            (Ok(_), Ok(_)) => Ok(CompatMode::V0_38),
+            _ => Err(unsupported_version(&version)),
+        }
+    }
+
+    /// Detect the compatibility mode of the node to which the given client is
+    /// connected by querying its `/status` endpoint.
+    pub async fn detect<C>(client: &C) -> Result<Self>
+    where
+        C: Client + Sync,
+    {
+        let status = client.status().await?;
+        Self::from_version(&status.node_info.version)
+    }
+
+    /// Adapt the given serialized JSON-RPC request to the format expected by
+    /// nodes of this version.
+    pub(crate) fn adapt_request(self, request: String) -> String {
+        if self < CompatMode::V0_37 {
+            return request;
+        }
+        let mut json = match serde_json::from_str::<Value>(&request) {
+            Ok(json) => json,
+            Err(_) => return request,
+        };
+        if json.get("method").and_then(Value::as_str) == Some("tx") {
+            if let Some(hash) = json.pointer_mut("/params/hash") {
+                hex_to_base64(hash);
+            }
+        }
+        json.to_string()
+    }
+
+    /// Adapt the given serialized JSON-RPC response (or event) from a node of
+    /// this version to the format expected by this crate's response types.
+    pub(crate) fn adapt_response(self, response: String) -> String {
+        if self < CompatMode::V0_37 {
+            return response;
+        }
+        let mut json = match serde_json::from_str::<Value>(&response) {
+            Ok(json) => json,
+            Err(_) => return response,
+        };
+        encode_event_attributes(&mut json);
+        json.to_string()
+    }
+}
+
+impl fmt::Display for CompatMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatMode::V0_34 => write!(f, "v0.34"),
+            CompatMode::V0_37 => write!(f, "v0.37"),
+            CompatMode::V0_38 => write!(f, "v0.38"),
+        }
+    }
+}
+
+impl FromStr for CompatMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "v0.34" => Ok(CompatMode::V0_34),
+            "v0.37" => Ok(CompatMode::V0_37),
+            "v0.38" => Ok(CompatMode::V0_38),
+            _ => Err(Error::invalid_params(&format!(
+                "unsupported compatibility mode: {} (must be one of v0.34, v0.37 or v0.38)",
+                s
+            ))),
+        }
+    }
+}
+
+fn unsupported_version(version: &str) -> Error {
+    Error::client_internal_error(format!("unsupported node version: {}", version))
+}
+
+// Re-encodes a "0x"-prefixed hexadecimal string as base64.
+fn hex_to_base64(value: &mut Value) {
+    let encoded = value
+        .as_str()
+        .map(|s| s.trim_start_matches("0x"))
+        .and_then(|s| hex::decode_upper(s).or_else(|_| hex::decode(s)).ok())
+        .map(|bytes| String::from_utf8(base64::encode(bytes)).unwrap());
+    if let Some(encoded) = encoded {
+        *value = Value::String(encoded);
+    }
+}
+
+// Recursively base64-encodes the keys and values of the attributes of all ABCI
+// events within the given JSON value.
+fn encode_event_attributes(json: &mut Value) {
+    match json {
+        Value::Object(map) => {
+            let is_event = map.get("type").map_or(false, Value::is_string);
+            if let (true, Some(Value::Array(attributes))) = (is_event, map.get_mut("attributes")) {
+                for attribute in attributes {
+                    for field in &["key", "value"] {
+                        if let Some(Value::String(s)) = attribute.get_mut(field) {
+                            *s = String::from_utf8(base64::encode(s.as_bytes())).unwrap();
+                        }
+                    }
+                }
+                return;
+            }
+            for value in map.values_mut() {
+                encode_event_attributes(value);
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(encode_event_attributes),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> Version {
+        serde_json::from_str(&format!("\"{}\"", s)).unwrap()
+    }
+
+    #[test]
+    fn compat_mode_from_version() {
+        assert_eq!(
+            CompatMode::V0_34,
+            CompatMode::from_version(&version("0.34.24")).unwrap()
+        );
+        assert_eq!(
+            CompatMode::V0_34,
+            CompatMode::from_version(&version("0.30.1")).unwrap()
+        );
+        assert_eq!(
+            CompatMode::V0_37,
+            CompatMode::from_version(&version("v0.37.0-rc1")).unwrap()
+        );
+        assert_eq!(
+            CompatMode::V0_38,
+            CompatMode::from_version(&version("0.38.2")).unwrap()
+        );
+        assert!(CompatMode::from_version(&version("unknown")).is_err());
+    }
+
+    #[test]
+    fn adapts_event_attributes() {
+        let response = r#"{"result":{"events":[{"type":"transfer","attributes":[{"key":"amount","value":"10","index":true}]}]}}"#;
+        let adapted: Value =
+            serde_json::from_str(&CompatMode::V0_37.adapt_response(response.to_string())).unwrap();
+        assert_eq!(
+            "YW1vdW50",
+            *adapted
+                .pointer("/result/events/0/attributes/0/key")
+                .unwrap()
+        );
+        assert_eq!(
+            "MTA=",
+            *adapted
+                .pointer("/result/events/0/attributes/0/value")
+                .unwrap()
+        );
+        assert_eq!(
+            response,
+            CompatMode::V0_34.adapt_response(response.to_string())
+        );
+    }
+}
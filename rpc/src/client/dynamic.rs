@@ -0,0 +1,201 @@
+//! Object-safe counterpart to the [`Client`] trait.
+
+use crate::client::height::HeightStream;
+use crate::endpoint::*;
+use crate::{Client, Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An object-safe counterpart to the [`Client`] trait, allowing for clients
+/// to be used as trait objects (e.g. `Box<dyn DynClient>`).
+///
+/// Since [`Client::perform`] is generic over the request type, [`Client`]
+/// itself cannot be used as a trait object. Instead, `DynClient` performs
+/// requests whose parameters and results are represented as JSON values.
+///
+/// `DynClient` is implemented for all [`Client`]s, and [`Client`] is in turn
+/// implemented for `Box<dyn DynClient>` and `Arc<dyn DynClient>`, so any
+/// client can be swapped in at runtime without losing access to the
+/// convenience methods provided by [`Client`]:
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, DynClient, HttpClient, WebSocketClient};
+///
+/// let client: Box<dyn DynClient> = if use_websocket {
+///     let (client, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+///         .await
+///         .unwrap();
+///     tokio::spawn(async move { driver.run().await });
+///     Box::new(client)
+/// } else {
+///     Box::new(HttpClient::new("http://127.0.0.1:26657").unwrap())
+/// };
+/// let status = client.status().await.unwrap();
+/// ```
+///
+/// Middleware can be implemented by wrapping another `DynClient` and
+/// intercepting calls to [`DynClient::perform_json`].
+#[async_trait]
+pub trait DynClient: Send + Sync {
+    /// Perform a request for the given method, with the given JSON-encoded
+    /// request parameters, returning the JSON-encoded result.
+    async fn perform_json(&self, method: Method, params: Value) -> Result<Value>;
+
+    /// Stream the latest height of the chain, as done by
+    /// [`Client::latest_height_stream`].
+    ///
+    /// Named differently from its [`Client`] counterpart so that calls to
+    /// either remain unambiguous when both traits are in scope.
+    fn dyn_latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_>;
+}
+
+#[async_trait]
+impl<C> DynClient for C
+where
+    C: Client + Send + Sync,
+{
+    async fn perform_json(&self, method: Method, params: Value) -> Result<Value> {
+        match method {
+            Method::AbciInfo => perform_json::<_, abci_info::Request>(self, params).await,
+            Method::AbciQuery => perform_json::<_, abci_query::Request>(self, params).await,
+            Method::Block => perform_json::<_, block::Request>(self, params).await,
+            Method::BlockResults => perform_json::<_, block_results::Request>(self, params).await,
+            Method::Blockchain => perform_json::<_, blockchain::Request>(self, params).await,
+            Method::BroadcastEvidence => perform_json::<_, evidence::Request>(self, params).await,
+            Method::BroadcastTxAsync => {
+                perform_json::<_, broadcast::tx_async::Request>(self, params).await
+            }
+            Method::BroadcastTxSync => {
+                perform_json::<_, broadcast::tx_sync::Request>(self, params).await
+            }
+            Method::BroadcastTxCommit => {
+                perform_json::<_, broadcast::tx_commit::Request>(self, params).await
+            }
+            Method::Commit => perform_json::<_, commit::Request>(self, params).await,
+            Method::ConsensusState => {
+                perform_json::<_, consensus_state::Request>(self, params).await
+            }
+            Method::DialPeers => perform_json::<_, dial_peers::Request>(self, params).await,
+            Method::DialSeeds => perform_json::<_, dial_seeds::Request>(self, params).await,
+            Method::Genesis => perform_json::<_, genesis::Request>(self, params).await,
+            Method::Header => perform_json::<_, header::Request>(self, params).await,
+            Method::HeaderByHash => perform_json::<_, header_by_hash::Request>(self, params).await,
+            Method::Health => perform_json::<_, health::Request>(self, params).await,
+            Method::NetInfo => perform_json::<_, net_info::Request>(self, params).await,
+            Method::Status => perform_json::<_, status::Request>(self, params).await,
+            Method::Tx => perform_json::<_, tx::Request>(self, params).await,
+            Method::TxSearch => perform_json::<_, tx_search::Request>(self, params).await,
+            Method::Validators => perform_json::<_, validators::Request>(self, params).await,
+            Method::Custom(name) => self.perform_raw(name, params).await,
+            Method::Subscribe | Method::Unsubscribe => {
+                Err(Error::method_not_found(method.as_str()))
+            }
+        }
+    }
+
+    fn dyn_latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+        self.latest_height_stream(poll_interval)
+    }
+}
+
+async fn perform_json<C, R>(client: &C, params: Value) -> Result<Value>
+where
+    C: Client + Sync,
+    R: SimpleRequest,
+{
+    let request: R = serde_json::from_value(params).map_err(Error::parse_error)?;
+    let response = client.perform(request).await?;
+    serde_json::to_value(response).map_err(|e| Error::client_internal_error(e.to_string()))
+}
+
+async fn perform_dyn<R>(client: &dyn DynClient, request: R) -> Result<R::Response>
+where
+    R: SimpleRequest,
+{
+    let method = request.method();
+    let params =
+        serde_json::to_value(request).map_err(|e| Error::client_internal_error(e.to_string()))?;
+    let result = client.perform_json(method, params).await?;
+    serde_json::from_value(result).map_err(Error::parse_error)
+}
+
+#[async_trait]
+impl Client for Box<dyn DynClient> {
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        perform_dyn(self.as_ref(), request).await
+    }
+
+    fn latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+        self.as_ref().dyn_latest_height_stream(poll_interval)
+    }
+}
+
+#[async_trait]
+impl Client for Arc<dyn DynClient> {
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        perform_dyn(self.as_ref(), request).await
+    }
+
+    fn latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+        self.as_ref().dyn_latest_height_stream(poll_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockClient, MockRequestMethodMatcher};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tendermint::block::Height;
+
+    fn read_json_fixture(name: &str) -> String {
+        std::fs::read_to_string(format!("./tests/support/{}.json", name)).unwrap()
+    }
+
+    struct CountingClient {
+        inner: Box<dyn DynClient>,
+        count: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DynClient for CountingClient {
+        async fn perform_json(&self, method: Method, params: Value) -> Result<Value> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.inner.as_ref().perform_json(method, params).await
+        }
+
+        fn dyn_latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+            self.inner.dyn_latest_height_stream(poll_interval)
+        }
+    }
+
+    #[tokio::test]
+    async fn boxed_clients() {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::AbciInfo, Ok(read_json_fixture("abci_info")))
+            .map(Method::Block, Ok(read_json_fixture("block")));
+        let (mock, _driver) = MockClient::new(matcher);
+        let counting = Arc::new(CountingClient {
+            inner: Box::new(mock),
+            count: AtomicUsize::new(0),
+        });
+        let client: Arc<dyn DynClient> = counting.clone();
+
+        let abci_info = client.abci_info().await.unwrap();
+        assert_eq!("GaiaApp".to_string(), abci_info.data);
+
+        let block = client.block(Height::from(10_u32)).await.unwrap().block;
+        assert_eq!(Height::from(10_u32), block.header.height);
+
+        assert!(client.status().await.is_err());
+        assert_eq!(3, counting.count.load(Ordering::SeqCst));
+    }
+}
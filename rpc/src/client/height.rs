@@ -0,0 +1,164 @@
+//! Streams of the latest height of the chain.
+
+use crate::client::subscription::{Subscription, SubscriptionClient};
+use crate::event::{Event, EventData};
+use crate::query::EventType;
+use crate::{Client, Result};
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt};
+use std::time::Duration;
+use tendermint::block::Height;
+use tokio::time;
+
+/// A stream of monotonically increasing heights of the chain, as produced by
+/// [`Client::latest_height_stream`].
+///
+/// Errors encountered while obtaining a height are produced by the stream,
+/// which keeps going afterwards.
+pub type HeightStream<'a> = BoxStream<'a, Result<Height>>;
+
+/// Stream the latest height by polling the `/status` endpoint.
+pub(crate) fn poll<C>(client: &C, poll_interval: Duration) -> HeightStream<'_>
+where
+    C: Client + Sync + ?Sized,
+{
+    Watcher {
+        client,
+        poll_interval,
+        subscribe: None,
+        subscription: None,
+        polled: false,
+        latest: None,
+    }
+    .into_stream()
+}
+
+/// Stream the latest height from `NewBlock` events, falling back to polling
+/// the `/status` endpoint if the subscription fails or terminates.
+pub(crate) fn watch<C>(client: &C, poll_interval: Duration) -> HeightStream<'_>
+where
+    C: Client + SubscriptionClient + Sync,
+{
+    Watcher {
+        client,
+        poll_interval,
+        subscribe: Some(client.subscribe(EventType::NewBlock.into())),
+        subscription: None,
+        polled: false,
+        latest: None,
+    }
+    .into_stream()
+}
+
+struct Watcher<'a, C: ?Sized> {
+    client: &'a C,
+    poll_interval: Duration,
+    /// The pending subscription to `NewBlock` events, if any.
+    subscribe: Option<BoxFuture<'a, Result<Subscription>>>,
+    subscription: Option<Subscription>,
+    /// Whether `/status` was polled already.
+    polled: bool,
+    /// The latest height produced by the stream.
+    latest: Option<Height>,
+}
+
+impl<'a, C> Watcher<'a, C>
+where
+    C: Client + Sync + ?Sized,
+{
+    fn into_stream(self) -> HeightStream<'a> {
+        stream::unfold(self, |mut watcher| async move {
+            let height = watcher.next().await;
+            Some((height, watcher))
+        })
+        .boxed()
+    }
+
+    async fn next(&mut self) -> Result<Height> {
+        // Subscribe before polling the current height, so that no block is
+        // missed in between.
+        if let Some(subscribe) = self.subscribe.take() {
+            self.subscription = subscribe.await.ok();
+        }
+
+        loop {
+            let height = match self.latest.and(self.subscription.as_mut()) {
+                Some(subscription) => match subscription.next().await {
+                    Some(Ok(event)) => match new_block_height(&event) {
+                        Some(height) => height,
+                        None => continue,
+                    },
+                    // Lagging only means that some heights were skipped
+                    Some(Err(e)) if e.missed_events().is_some() => continue,
+                    _ => {
+                        self.subscription = None;
+                        continue;
+                    }
+                },
+                None => {
+                    if self.polled {
+                        time::sleep(self.poll_interval).await;
+                    }
+                    self.polled = true;
+                    self.client.status().await?.sync_info.latest_block_height
+                }
+            };
+
+            if Some(height) > self.latest {
+                self.latest = Some(height);
+                return Ok(height);
+            }
+        }
+    }
+}
+
+fn new_block_height(event: &Event) -> Option<Height> {
+    match &event.data {
+        EventData::NewBlock {
+            block: Some(block), ..
+        } => Some(block.header.height),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, MockClient, MockRequestMethodMatcher};
+
+    #[tokio::test]
+    async fn poll_status() {
+        let status_fixture = std::fs::read_to_string("./tests/support/status.json").unwrap();
+        let matcher = MockRequestMethodMatcher::default().map(Method::Status, Ok(status_fixture));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let mut heights = poll(&client, Duration::from_millis(10));
+        assert_eq!(
+            heights.next().await.unwrap().unwrap(),
+            Height::from(410744_u32)
+        );
+
+        // The height doesn't change, so it is not produced again
+        let next = time::timeout(Duration::from_millis(100), heights.next()).await;
+        assert!(next.is_err());
+
+        drop(heights);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn poll_errors() {
+        let (client, driver) = MockClient::new(MockRequestMethodMatcher::default());
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let mut heights = poll(&client, Duration::from_millis(10));
+        assert!(heights.next().await.unwrap().is_err());
+        assert!(heights.next().await.unwrap().is_err());
+
+        drop(heights);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+}
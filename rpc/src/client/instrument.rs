@@ -0,0 +1,237 @@
+//! Instrumentation of RPC clients, e.g. for monitoring the latency and error
+//! rate of requests to an RPC provider.
+
+use crate::client::height::HeightStream;
+use crate::client::subscription::{Subscription, SubscriptionClient};
+use crate::query::Query;
+use crate::{Client, Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "prometheus")]
+pub use self::metrics::PrometheusInterceptor;
+
+/// The outcome of a single request.
+#[derive(Debug, Clone, Copy)]
+pub enum Outcome<'a> {
+    /// The request succeeded.
+    Success,
+    /// The request failed with the given error.
+    Failure(&'a Error),
+}
+
+impl Outcome<'_> {
+    /// A short label describing this outcome (`"success"` or `"failure"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::Failure(_) => "failure",
+        }
+    }
+}
+
+/// Observes each request performed by an [`InstrumentedClient`].
+pub trait Interceptor: Send + Sync {
+    /// Called before a request for the given method is sent.
+    fn on_request(&self, _method: &Method) {}
+
+    /// Called once a request for the given method has completed, with the
+    /// time it took and its outcome.
+    fn on_response(&self, method: &Method, duration: Duration, outcome: Outcome<'_>);
+}
+
+/// A client that wraps another client, invoking an [`Interceptor`] around
+/// every request.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, HttpClient, InstrumentedClient, PrometheusInterceptor};
+///
+/// let registry = prometheus::Registry::new();
+/// let interceptor = PrometheusInterceptor::new(&registry).unwrap();
+/// let client = InstrumentedClient::new(
+///     HttpClient::new("http://127.0.0.1:26657").unwrap(),
+///     interceptor,
+/// );
+/// let status = client.status().await.unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct InstrumentedClient<C, I> {
+    inner: C,
+    interceptor: Arc<I>,
+}
+
+impl<C, I> InstrumentedClient<C, I>
+where
+    I: Interceptor,
+{
+    /// Wrap the given client such that the given interceptor observes all of
+    /// its requests.
+    pub fn new(inner: C, interceptor: I) -> Self {
+        Self {
+            inner,
+            interceptor: Arc::new(interceptor),
+        }
+    }
+
+    /// The wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The interceptor observing this client's requests.
+    pub fn interceptor(&self) -> &I {
+        &self.interceptor
+    }
+
+    /// Unwrap the underlying client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+#[async_trait]
+impl<C, I> Client for InstrumentedClient<C, I>
+where
+    C: Client + Sync,
+    I: Interceptor,
+{
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let method = request.method();
+        self.interceptor.on_request(&method);
+        let started = Instant::now();
+        let result = self.inner.perform(request).await;
+        let outcome = match &result {
+            Ok(_) => Outcome::Success,
+            Err(e) => Outcome::Failure(e),
+        };
+        self.interceptor
+            .on_response(&method, started.elapsed(), outcome);
+        result
+    }
+
+    // Heights are streamed by the wrapped client, e.g. from new block events,
+    // rather than by polling `/status` through the interceptor.
+    fn latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+        self.inner.latest_height_stream(poll_interval)
+    }
+}
+
+#[async_trait]
+impl<C, I> SubscriptionClient for InstrumentedClient<C, I>
+where
+    C: SubscriptionClient + Sync,
+    I: Interceptor,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<()> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+#[cfg(feature = "prometheus")]
+mod metrics {
+    use super::{Interceptor, Outcome};
+    use crate::Method;
+    use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+    use std::time::Duration;
+
+    /// An [`Interceptor`] that records Prometheus metrics for each request:
+    ///
+    /// * `tendermint_rpc_requests_total` (labels `method` and `outcome`): the
+    ///   number of completed requests.
+    /// * `tendermint_rpc_request_duration_seconds` (label `method`): the
+    ///   latency of requests.
+    #[derive(Debug, Clone)]
+    pub struct PrometheusInterceptor {
+        requests: IntCounterVec,
+        durations: HistogramVec,
+    }
+
+    impl PrometheusInterceptor {
+        /// Create the interceptor's metrics and register them with the given
+        /// registry.
+        pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+            let requests = IntCounterVec::new(
+                Opts::new(
+                    "tendermint_rpc_requests_total",
+                    "Number of completed Tendermint RPC requests",
+                ),
+                &["method", "outcome"],
+            )?;
+            let durations = HistogramVec::new(
+                HistogramOpts::new(
+                    "tendermint_rpc_request_duration_seconds",
+                    "Latency of Tendermint RPC requests",
+                ),
+                &["method"],
+            )?;
+            registry.register(Box::new(requests.clone()))?;
+            registry.register(Box::new(durations.clone()))?;
+            Ok(Self {
+                requests,
+                durations,
+            })
+        }
+    }
+
+    impl Interceptor for PrometheusInterceptor {
+        fn on_response(&self, method: &Method, duration: Duration, outcome: Outcome<'_>) {
+            self.requests
+                .with_label_values(&[method.as_str(), outcome.as_str()])
+                .inc();
+            self.durations
+                .with_label_values(&[method.as_str()])
+                .observe(duration.as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockClient, MockRequestMethodMatcher};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingInterceptor {
+        responses: Mutex<Vec<(Method, bool)>>,
+    }
+
+    impl Interceptor for RecordingInterceptor {
+        fn on_response(&self, method: &Method, _duration: Duration, outcome: Outcome<'_>) {
+            self.responses
+                .lock()
+                .unwrap()
+                .push((method.clone(), matches!(outcome, Outcome::Success)));
+        }
+    }
+
+    #[tokio::test]
+    async fn interceptor_observes_requests() {
+        let fixture = std::fs::read_to_string("./tests/support/health.json").unwrap();
+        let matcher = MockRequestMethodMatcher::default().map(Method::Health, Ok(fixture));
+        let (mock, _driver) = MockClient::new(matcher);
+        let client = InstrumentedClient::new(mock, RecordingInterceptor::default());
+
+        client.health().await.unwrap();
+        assert!(client.status().await.is_err());
+
+        assert_eq!(
+            vec![(Method::Health, true), (Method::Status, false)],
+            *client.interceptor().responses.lock().unwrap()
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Gap-free streams of the blocks of the chain, from a given height.
+
+use crate::client::height::HeightStream;
+use crate::endpoint::block_results;
+use crate::{Client, Result};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::time::Duration;
+use tendermint::block::{Block, Height};
+
+/// A stream of consecutive blocks of the chain with their results, as
+/// produced by [`Client::block_stream`].
+///
+/// Errors encountered while fetching a block are produced by the stream,
+/// which tries to fetch the same block again afterwards. The stream ends if
+/// the [`HeightStream`] it follows does.
+pub type BlockStream<'a> = BoxStream<'a, Result<BlockWithResults>>;
+
+/// A block of the chain, with the results of its execution, which include
+/// the events it emitted.
+#[derive(Clone, Debug)]
+pub struct BlockWithResults {
+    /// The block
+    pub block: Block,
+    /// The results of the execution of the block
+    pub results: block_results::Response,
+}
+
+/// Stream the blocks from the given height, fetching the past ones first,
+/// then each new one as the latest height of the chain increases.
+pub(crate) fn blocks<C>(client: &C, from: Height, poll_interval: Duration) -> BlockStream<'_>
+where
+    C: Client + Sync + ?Sized,
+{
+    Replay {
+        client,
+        heights: client.latest_height_stream(poll_interval),
+        next: from,
+        latest: None,
+    }
+    .into_stream()
+}
+
+struct Replay<'a, C: ?Sized> {
+    client: &'a C,
+    heights: HeightStream<'a>,
+    /// The height of the next block to produce.
+    next: Height,
+    /// The latest height of the chain, as far as we know.
+    latest: Option<Height>,
+}
+
+impl<'a, C> Replay<'a, C>
+where
+    C: Client + Sync + ?Sized,
+{
+    fn into_stream(self) -> BlockStream<'a> {
+        stream::unfold(self, |mut replay| async move {
+            let block = replay.next().await?;
+            Some((block, replay))
+        })
+        .boxed()
+    }
+
+    /// Fetch the next block, once the chain reaches its height. Returns
+    /// `None` if the stream of heights ends before that.
+    async fn next(&mut self) -> Option<Result<BlockWithResults>> {
+        // Heights are only produced once they increase, so that blocks
+        // announced both by the poll and by the subscription, or again by
+        // the subscription after a lag, are not fetched twice.
+        while self.latest < Some(self.next) {
+            match self.heights.next().await? {
+                Ok(height) => self.latest = Some(height),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        Some(self.fetch().await)
+    }
+
+    async fn fetch(&mut self) -> Result<BlockWithResults> {
+        let height = self.next;
+        let block = self.client.block(height).await?.block;
+        let results = self.client.block_results(height).await?;
+        self.next = height.increment();
+        Ok(BlockWithResults { block, results })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use crate::{Method, MockClient, MockRequestMatcher, Request, Response};
+    use futures::stream;
+    use tokio::time;
+
+    fn read_json_fixture(name: &str) -> String {
+        std::fs::read_to_string(format!("./tests/support/{}.json", name)).unwrap()
+    }
+
+    // Serves the block and results of the requested height, at a chain
+    // whose latest height is 11.
+    struct ChainMatcher;
+
+    impl MockRequestMatcher for ChainMatcher {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response>>
+        where
+            R: Request,
+        {
+            let method = request.method();
+            let params = serde_json::to_value(&request).unwrap();
+            let height = params["height"].as_str().map(ToOwned::to_owned);
+            let json = match method {
+                Method::Status => read_json_fixture("status").replace("\"410744\"", "\"11\""),
+                Method::Block => read_json_fixture("block").replacen(
+                    "\"height\": \"10\"",
+                    &format!("\"height\": \"{}\"", height?),
+                    1,
+                ),
+                Method::BlockResults => read_json_fixture("block_results").replacen(
+                    "\"height\": \"1814\"",
+                    &format!("\"height\": \"{}\"", height?),
+                    1,
+                ),
+                _ => return None,
+            };
+            Some(R::Response::from_string(json))
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_past_blocks_then_follows_new_ones() {
+        let (client, driver) = MockClient::new(ChainMatcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let mut blocks = client.block_stream(Height::from(10_u32), Duration::from_secs(60));
+        for height in 10..=11 {
+            let block = blocks.next().await.unwrap().unwrap();
+            assert_eq!(block.block.header.height.value(), height);
+            assert_eq!(block.results.height.value(), height);
+        }
+
+        // The next block is only fetched once the chain reaches its height
+        let next = time::timeout(Duration::from_millis(100), blocks.next()).await;
+        assert!(next.is_err());
+        let event = Event::from_string(read_json_fixture("event_new_block_1")).unwrap();
+        client.publish(&event);
+        let block = blocks.next().await.unwrap().unwrap();
+        assert_eq!(block.block.header.height.value(), 12);
+
+        drop(blocks);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn ends_with_the_height_stream() {
+        let (client, driver) = MockClient::new(ChainMatcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let heights = stream::iter(vec![Ok(Height::from(10_u32))]).boxed();
+        let mut blocks = Replay {
+            client: &client,
+            heights,
+            next: Height::from(10_u32),
+            latest: None,
+        }
+        .into_stream();
+        assert!(blocks.next().await.unwrap().is_ok());
+        assert!(blocks.next().await.is_none());
+
+        drop(blocks);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+}
@@ -1,9 +1,11 @@
 //! Subscription- and subscription management-related functionality.
 
-use crate::client::sync::{ChannelRx, ChannelTx};
+use crate::client::sync::{
+    bounded, unbounded, BoundedRx, BoundedTx, ChannelRx, ChannelTx, Lagged, OverflowPolicy,
+};
 use crate::event::Event;
 use crate::query::Query;
-use crate::Result;
+use crate::{Error, Result};
 use async_trait::async_trait;
 use futures::task::{Context, Poll};
 use futures::Stream;
@@ -33,8 +35,90 @@ pub trait SubscriptionClient {
     fn close(self) -> Result<()>;
 }
 
-pub(crate) type SubscriptionTx = ChannelTx<Result<Event>>;
-pub(crate) type SubscriptionRx = ChannelRx<Result<Event>>;
+/// Determines how a [`Subscription`] behaves when its consumer receives
+/// events more slowly than the remote endpoint produces them.
+///
+/// Similar to the semantics of Tokio's [`broadcast`] channel, lagging is
+/// reported to the consumer as an [`Error`] whose [`Error::missed_events`]
+/// method provides the number of events that were dropped.
+///
+/// [`broadcast`]: https://docs.rs/tokio/*/tokio/sync/broadcast/index.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Buffer an unlimited number of events for each subscription.
+    Unbounded,
+    /// Buffer at most `capacity` events, and at least one. When the buffer
+    /// is full, the oldest event is discarded and the subscription produces
+    /// a lag error before continuing with the remaining events.
+    DropOldest { capacity: usize },
+    /// Buffer at most `capacity` events, and at least one. When the buffer
+    /// is full, the subscription is closed: it produces the buffered events,
+    /// followed by a lag error, and then terminates.
+    Close { capacity: usize },
+}
+
+impl Default for BackpressurePolicy {
+    fn default() -> Self {
+        BackpressurePolicy::Unbounded
+    }
+}
+
+/// Sending half of a subscription's event buffer.
+#[derive(Debug, Clone)]
+pub(crate) enum SubscriptionTx {
+    Unbounded(ChannelTx<Result<Event>>),
+    Bounded(BoundedTx<Result<Event>>),
+}
+
+impl SubscriptionTx {
+    pub fn send(&self, value: Result<Event>) -> Result<()> {
+        match self {
+            SubscriptionTx::Unbounded(tx) => tx.send(value),
+            SubscriptionTx::Bounded(tx) => tx.send(value),
+        }
+    }
+}
+
+/// Receiving half of a subscription's event buffer.
+#[derive(Debug)]
+pub(crate) enum SubscriptionRx {
+    Unbounded(ChannelRx<Result<Event>>),
+    Bounded(BoundedRx<Result<Event>>),
+}
+
+impl Stream for SubscriptionRx {
+    type Item = Result<Event>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut() {
+            SubscriptionRx::Unbounded(rx) => Pin::new(rx).poll_next(cx),
+            SubscriptionRx::Bounded(rx) => Pin::new(rx).poll_next(cx).map(|item| {
+                item.map(|res| {
+                    res.unwrap_or_else(|Lagged(missed)| Err(Error::subscription_lagged(missed)))
+                })
+            }),
+        }
+    }
+}
+
+/// Constructs the event buffer for a single subscription according to the
+/// given policy.
+pub(crate) fn subscription_channel(policy: BackpressurePolicy) -> (SubscriptionTx, SubscriptionRx) {
+    match policy {
+        BackpressurePolicy::Unbounded => {
+            let (tx, rx) = unbounded();
+            (SubscriptionTx::Unbounded(tx), SubscriptionRx::Unbounded(rx))
+        }
+        BackpressurePolicy::DropOldest { capacity } => {
+            let (tx, rx) = bounded(capacity, OverflowPolicy::DropOldest);
+            (SubscriptionTx::Bounded(tx), SubscriptionRx::Bounded(rx))
+        }
+        BackpressurePolicy::Close { capacity } => {
+            let (tx, rx) = bounded(capacity, OverflowPolicy::Close);
+            (SubscriptionTx::Bounded(tx), SubscriptionRx::Bounded(rx))
+        }
+    }
+}
 
 /// An interface that can be used to asynchronously receive [`Event`]s for a
 /// particular subscription.
@@ -71,6 +155,10 @@ pub struct Subscription {
     // Our internal result event receiver for this subscription.
     #[pin]
     rx: SubscriptionRx,
+    // Notifies the client's driver when this subscription is dropped (if the
+    // client needs to know).
+    #[cfg(feature = "websocket-client")]
+    release: Option<ReleaseGuard>,
 }
 
 impl Stream for Subscription {
@@ -83,7 +171,38 @@ impl Stream for Subscription {
 
 impl Subscription {
     pub(crate) fn new(id: String, query: Query, rx: SubscriptionRx) -> Self {
-        Self { id, query, rx }
+        Self {
+            id,
+            query,
+            rx,
+            #[cfg(feature = "websocket-client")]
+            release: None,
+        }
+    }
+
+    /// Construct a subscription that notifies the given channel when it is
+    /// dropped, allowing the client to keep track of how many local
+    /// subscribers remain for a particular query.
+    #[cfg(feature = "websocket-client")]
+    pub(crate) fn new_with_release(
+        id: String,
+        query: Query,
+        rx: SubscriptionRx,
+        release_tx: ChannelTx<ReleasedSubscription>,
+    ) -> Self {
+        let release = Some(ReleaseGuard {
+            subscription: Some(ReleasedSubscription {
+                id: id.clone(),
+                query: query.to_string(),
+            }),
+            release_tx,
+        });
+        Self {
+            id,
+            query,
+            rx,
+            release,
+        }
     }
 
     /// Return this subscription's ID for informational purposes.
@@ -95,3 +214,30 @@ impl Subscription {
         &self.query
     }
 }
+
+/// Identifies a subscription that has been dropped by its owner.
+#[cfg(feature = "websocket-client")]
+#[derive(Debug, Clone)]
+pub(crate) struct ReleasedSubscription {
+    pub id: String,
+    pub query: String,
+}
+
+// Sends a notification to the client's driver when dropped.
+#[cfg(feature = "websocket-client")]
+#[derive(Debug)]
+struct ReleaseGuard {
+    subscription: Option<ReleasedSubscription>,
+    release_tx: ChannelTx<ReleasedSubscription>,
+}
+
+#[cfg(feature = "websocket-client")]
+impl Drop for ReleaseGuard {
+    fn drop(&mut self) {
+        if let Some(subscription) = self.subscription.take() {
+            // If the driver has already terminated, there's nothing left to
+            // release.
+            let _ = self.release_tx.send(subscription);
+        }
+    }
+}
@@ -1,12 +1,16 @@
 //! Synchronization primitives specific to the Tendermint RPC client.
 //!
 //! At present, this wraps Tokio's synchronization primitives and provides some
-//! convenience methods. We also only implement unbounded channels at present.
-//! In future, if RPC consumers need it, we will implement bounded channels.
+//! convenience methods. Unbounded channels are used for internal
+//! communication, while bounded channels (with a configurable
+//! [`OverflowPolicy`]) are available for consumer-facing buffers that must not
+//! grow without limit.
 
+use std::collections::VecDeque;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
-use futures::task::{Context, Poll};
+use futures::task::{AtomicWaker, Context, Poll};
 use futures::Stream;
 use pin_project::pin_project;
 use tokio::sync::mpsc;
@@ -58,3 +62,206 @@ impl<T> Stream for ChannelRx<T> {
         self.project().0.poll_recv(cx)
     }
 }
+
+/// What a bounded channel does when a value is sent while its buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discard the oldest buffered value to make room for the new one. The
+    /// receiver is informed of the number of discarded values via [`Lagged`]
+    /// before receiving the next buffered value.
+    DropOldest,
+    /// Discard the new value and close the channel. The receiver is informed
+    /// of the number of discarded values via [`Lagged`] once it has drained
+    /// the buffer, after which the channel terminates.
+    Close,
+}
+
+/// Produced by a [`BoundedRx`] when values have been discarded due to the
+/// receiver not keeping up with the sender(s). Contains the number of
+/// discarded values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+/// Constructor for a bounded channel that can buffer at most `capacity`
+/// values, handling overflow according to the given `policy`.
+///
+/// Unlike Tokio's bounded channels, sending never blocks. A `capacity` of 0
+/// is treated as 1, since such a channel could never deliver a value.
+pub fn bounded<T>(capacity: usize, policy: OverflowPolicy) -> (BoundedTx<T>, BoundedRx<T>) {
+    let capacity = capacity.max(1);
+    let shared = Arc::new(BoundedShared {
+        state: Mutex::new(BoundedState {
+            buffer: VecDeque::with_capacity(capacity),
+            missed: 0,
+            overflowed: false,
+            senders: 1,
+            receiver_dropped: false,
+        }),
+        waker: AtomicWaker::new(),
+        capacity,
+        policy,
+    });
+    (BoundedTx(shared.clone()), BoundedRx(shared))
+}
+
+#[derive(Debug)]
+struct BoundedState<T> {
+    buffer: VecDeque<T>,
+    // The number of values discarded since the receiver was last told about
+    // them.
+    missed: u64,
+    // Set when the channel has been closed as a result of the
+    // `OverflowPolicy::Close` policy.
+    overflowed: bool,
+    senders: usize,
+    receiver_dropped: bool,
+}
+
+#[derive(Debug)]
+struct BoundedShared<T> {
+    state: Mutex<BoundedState<T>>,
+    waker: AtomicWaker,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+/// Sender interface for a bounded channel.
+#[derive(Debug)]
+pub struct BoundedTx<T>(Arc<BoundedShared<T>>);
+
+impl<T> BoundedTx<T> {
+    /// Attempt to send the given value, applying the channel's overflow policy
+    /// if its buffer is full.
+    ///
+    /// Fails if the receiver has been dropped, or if the channel has been
+    /// closed due to overflow.
+    pub fn send(&self, value: T) -> Result<()> {
+        {
+            let mut state = self.0.state.lock().unwrap();
+            if state.receiver_dropped || state.overflowed {
+                return Err(Error::client_internal_error(
+                    "failed to send message to internal channel: channel closed",
+                ));
+            }
+            if state.buffer.len() >= self.0.capacity {
+                state.missed += 1;
+                match self.0.policy {
+                    OverflowPolicy::DropOldest => {
+                        state.buffer.pop_front();
+                        state.buffer.push_back(value);
+                    }
+                    OverflowPolicy::Close => state.overflowed = true,
+                }
+            } else {
+                state.buffer.push_back(value);
+            }
+        }
+        self.0.waker.wake();
+        Ok(())
+    }
+}
+
+impl<T> Clone for BoundedTx<T> {
+    fn clone(&self) -> Self {
+        self.0.state.lock().unwrap().senders += 1;
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Drop for BoundedTx<T> {
+    fn drop(&mut self) {
+        let remaining = {
+            let mut state = self.0.state.lock().unwrap();
+            state.senders -= 1;
+            state.senders
+        };
+        if remaining == 0 {
+            self.0.waker.wake();
+        }
+    }
+}
+
+/// Receiver interface for a bounded channel.
+///
+/// Produces `Err(Lagged)` whenever values have been discarded as a result of
+/// the channel's overflow policy.
+#[derive(Debug)]
+pub struct BoundedRx<T>(Arc<BoundedShared<T>>);
+
+impl<T> Stream for BoundedRx<T> {
+    type Item = std::result::Result<T, Lagged>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Register first so we don't miss a wakeup between checking the state
+        // and returning `Poll::Pending`.
+        self.0.waker.register(cx.waker());
+        let mut state = self.0.state.lock().unwrap();
+        // Values discarded from the front of the buffer are reported before
+        // any of the values that replaced them.
+        if state.missed > 0 && !state.overflowed {
+            return Poll::Ready(Some(Err(Lagged(std::mem::take(&mut state.missed)))));
+        }
+        if let Some(value) = state.buffer.pop_front() {
+            return Poll::Ready(Some(Ok(value)));
+        }
+        if state.overflowed {
+            if state.missed > 0 {
+                return Poll::Ready(Some(Err(Lagged(std::mem::take(&mut state.missed)))));
+            }
+            return Poll::Ready(None);
+        }
+        if state.senders == 0 {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for BoundedRx<T> {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock().unwrap();
+        state.receiver_dropped = true;
+        state.buffer.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn bounded_drop_oldest_reports_lag() {
+        let (tx, mut rx) = bounded(2, OverflowPolicy::DropOldest);
+        for i in 0..5 {
+            tx.send(i).unwrap();
+        }
+        assert_eq!(Some(Err(Lagged(3))), rx.next().await);
+        assert_eq!(Some(Ok(3)), rx.next().await);
+        assert_eq!(Some(Ok(4)), rx.next().await);
+        drop(tx);
+        assert_eq!(None, rx.next().await);
+    }
+
+    #[tokio::test]
+    async fn bounded_close_terminates_after_draining() {
+        let (tx, mut rx) = bounded(2, OverflowPolicy::Close);
+        tx.send(0).unwrap();
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert!(tx.send(3).is_err());
+        assert_eq!(Some(Ok(0)), rx.next().await);
+        assert_eq!(Some(Ok(1)), rx.next().await);
+        assert_eq!(Some(Err(Lagged(1))), rx.next().await);
+        assert_eq!(None, rx.next().await);
+    }
+
+    #[tokio::test]
+    async fn bounded_zero_capacity_holds_one_value() {
+        let (tx, mut rx) = bounded(0, OverflowPolicy::DropOldest);
+        tx.send(0).unwrap();
+        tx.send(1).unwrap();
+        assert_eq!(Some(Err(Lagged(1))), rx.next().await);
+        assert_eq!(Some(Ok(1)), rx.next().await);
+    }
+}
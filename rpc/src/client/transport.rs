@@ -3,6 +3,8 @@
 pub mod mock;
 mod router;
 
+#[cfg(feature = "grpc")]
+pub mod grpc;
 #[cfg(feature = "http-client")]
 pub mod http;
 #[cfg(feature = "websocket-client")]
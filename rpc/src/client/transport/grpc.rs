@@ -0,0 +1,225 @@
+//! gRPC-based transport for Tendermint's broadcast API.
+
+use crate::endpoint::broadcast::tx_commit::TxResult;
+use crate::{Error, Result};
+use http::uri::PathAndQuery;
+use std::convert::TryFrom;
+use tendermint::abci::{responses::Codespace, Code, Data, Event, Gas, Info, Log, Transaction};
+use tendermint_proto::abci::{Event as RawEvent, ResponseCheckTx, ResponseDeliverTx};
+use tendermint_proto::rpc::grpc::{
+    RequestBroadcastTx, RequestPing, ResponseBroadcastTx, ResponsePing,
+};
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::transport::{Channel, Endpoint};
+
+const PING_PATH: &str = "/tendermint.rpc.grpc.BroadcastAPI/Ping";
+const BROADCAST_TX_PATH: &str = "/tendermint.rpc.grpc.BroadcastAPI/BroadcastTx";
+
+/// A client for Tendermint's gRPC broadcast API, which is served by nodes
+/// configured with a `grpc_laddr` in their RPC configuration.
+///
+/// This API only provides a small subset of the functionality of the
+/// JSON-RPC API: checking that the node is reachable, and broadcasting
+/// transactions (equivalent to `/broadcast_tx_commit`).
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::GrpcClient;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = GrpcClient::new("http://127.0.0.1:26658")
+///         .await
+///         .unwrap();
+///
+///     client.ping().await.unwrap();
+///     let response = client.broadcast_tx(b"key=value".to_vec().into())
+///         .await
+///         .unwrap();
+///
+///     println!("Got DeliverTx result: {:?}", response.deliver_tx);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrpcClient {
+    inner: Grpc<Channel>,
+}
+
+impl GrpcClient {
+    /// Connect to the gRPC broadcast API at the given URL.
+    pub async fn new<U>(url: U) -> Result<Self>
+    where
+        U: AsRef<str>,
+    {
+        let channel = Endpoint::from_shared(url.as_ref().to_string())
+            .map_err(|e| Error::grpc_error(e.to_string()))?
+            .connect()
+            .await
+            .map_err(|e| Error::grpc_error(e.to_string()))?;
+        Ok(Self {
+            inner: Grpc::new(channel),
+        })
+    }
+
+    /// `Ping`: check that the node's gRPC server is reachable.
+    pub async fn ping(&self) -> Result<()> {
+        let mut grpc = self.ready().await?;
+        let _: ResponsePing = grpc
+            .unary(
+                tonic::Request::new(RequestPing {}),
+                PathAndQuery::from_static(PING_PATH),
+                ProstCodec::default(),
+            )
+            .await
+            .map_err(|status| Error::grpc_error(status.to_string()))?
+            .into_inner();
+        Ok(())
+    }
+
+    /// `BroadcastTx`: broadcast a transaction, waiting for it to be
+    /// committed.
+    pub async fn broadcast_tx(&self, tx: Transaction) -> Result<BroadcastTxResponse> {
+        let mut grpc = self.ready().await?;
+        let response: ResponseBroadcastTx = grpc
+            .unary(
+                tonic::Request::new(RequestBroadcastTx { tx: tx.into() }),
+                PathAndQuery::from_static(BROADCAST_TX_PATH),
+                ProstCodec::default(),
+            )
+            .await
+            .map_err(|status| Error::grpc_error(status.to_string()))?
+            .into_inner();
+        Ok(response.into())
+    }
+
+    async fn ready(&self) -> Result<Grpc<Channel>> {
+        let mut grpc = self.inner.clone();
+        grpc.ready()
+            .await
+            .map_err(|e| Error::grpc_error(format!("service was not ready: {}", e)))?;
+        Ok(grpc)
+    }
+}
+
+/// Response from a gRPC `BroadcastTx` request.
+#[derive(Clone, Debug)]
+pub struct BroadcastTxResponse {
+    /// `CheckTx` result
+    pub check_tx: TxResult,
+
+    /// `DeliverTx` result
+    pub deliver_tx: TxResult,
+}
+
+impl From<ResponseBroadcastTx> for BroadcastTxResponse {
+    fn from(response: ResponseBroadcastTx) -> Self {
+        Self {
+            check_tx: response.check_tx.map(check_tx_result).unwrap_or_default(),
+            deliver_tx: response
+                .deliver_tx
+                .map(deliver_tx_result)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn check_tx_result(r: ResponseCheckTx) -> TxResult {
+    tx_result(
+        r.code,
+        r.data,
+        &r.log,
+        &r.info,
+        r.gas_wanted,
+        r.gas_used,
+        r.events,
+        &r.codespace,
+    )
+}
+
+fn deliver_tx_result(r: ResponseDeliverTx) -> TxResult {
+    tx_result(
+        r.code,
+        r.data,
+        &r.log,
+        &r.info,
+        r.gas_wanted,
+        r.gas_used,
+        r.events,
+        &r.codespace,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn tx_result(
+    code: u32,
+    data: Vec<u8>,
+    log: &str,
+    info: &str,
+    gas_wanted: i64,
+    gas_used: i64,
+    events: Vec<RawEvent>,
+    codespace: &str,
+) -> TxResult {
+    TxResult {
+        code: Code::from(code),
+        data: if data.is_empty() {
+            None
+        } else {
+            Some(Data::from(data))
+        },
+        log: Log::from(log),
+        info: Info::from(info),
+        gas_wanted: gas(gas_wanted),
+        gas_used: gas(gas_used),
+        events: events.into_iter().map(Event::from).collect(),
+        codespace: Codespace::from(codespace),
+    }
+}
+
+fn gas(value: i64) -> Gas {
+    Gas::from(u64::try_from(value).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint_proto::abci::EventAttribute;
+
+    #[test]
+    fn converts_broadcast_tx_response() {
+        let response = BroadcastTxResponse::from(ResponseBroadcastTx {
+            check_tx: Some(ResponseCheckTx {
+                gas_wanted: 1,
+                ..Default::default()
+            }),
+            deliver_tx: Some(ResponseDeliverTx {
+                code: 1,
+                data: b"result".to_vec(),
+                log: "failed".to_string(),
+                events: vec![RawEvent {
+                    r#type: "app".to_string(),
+                    attributes: vec![EventAttribute {
+                        key: b"key".to_vec(),
+                        value: b"value".to_vec(),
+                        index: true,
+                    }],
+                }],
+                ..Default::default()
+            }),
+        });
+
+        assert_eq!(Code::Ok, response.check_tx.code);
+        assert_eq!(1, response.check_tx.gas_wanted.value());
+        assert!(response.check_tx.data.is_none());
+        assert_eq!(Code::Err(1), response.deliver_tx.code);
+        assert_eq!(
+            b"result".to_vec(),
+            response.deliver_tx.data.unwrap().value().to_vec()
+        );
+        assert_eq!("failed", response.deliver_tx.log.value());
+        assert_eq!("key", response.deliver_tx.events[0].attributes[0].key);
+        assert_eq!("value", response.deliver_tx.events[0].attributes[0].value);
+    }
+}
@@ -1,6 +1,6 @@
 //! HTTP-based transport for Tendermint RPC Client.
 
-use crate::client::Client;
+use crate::client::{Client, CompatMode, WireLog};
 use crate::{Error, Result, Scheme, SimpleRequest, Url};
 use async_trait::async_trait;
 use std::convert::{TryFrom, TryInto};
@@ -36,6 +36,8 @@ use tendermint::net;
 #[derive(Debug, Clone)]
 pub struct HttpClient {
     inner: sealed::HttpClient,
+    compat: CompatMode,
+    wire_log: Option<WireLog>,
 }
 
 impl HttpClient {
@@ -52,6 +54,8 @@ impl HttpClient {
             } else {
                 sealed::HttpClient::new_http(url.try_into()?)
             },
+            compat: CompatMode::default(),
+            wire_log: None,
         })
     }
 
@@ -75,8 +79,31 @@ impl HttpClient {
             } else {
                 sealed::HttpClient::new_http_proxy(url.try_into()?, proxy_url.try_into()?)?
             },
+            compat: CompatMode::default(),
+            wire_log: None,
         })
     }
+
+    /// The RPC serialization format this client uses to communicate with the
+    /// node (see [`CompatMode`]).
+    pub fn compat_mode(&self) -> CompatMode {
+        self.compat
+    }
+
+    /// Set the RPC serialization format this client uses to communicate with
+    /// the node.
+    ///
+    /// Use [`CompatMode::detect`] to determine the appropriate mode for the
+    /// node to which this client is connected.
+    pub fn set_compat_mode(&mut self, compat: CompatMode) {
+        self.compat = compat;
+    }
+
+    /// Log the requests this client sends and the raw responses it receives
+    /// to the given wire log, while it is enabled.
+    pub fn set_wire_log(&mut self, wire_log: WireLog) {
+        self.wire_log = Some(wire_log);
+    }
 }
 
 #[async_trait]
@@ -85,7 +112,9 @@ impl Client for HttpClient {
     where
         R: SimpleRequest,
     {
-        self.inner.perform(request).await
+        self.inner
+            .perform(request, self.compat, self.wire_log.as_ref())
+            .await
     }
 }
 
@@ -152,6 +181,7 @@ impl TryFrom<HttpClientUrl> for hyper::Uri {
 }
 
 mod sealed {
+    use crate::client::{CompatMode, WireLog};
     use crate::{Error, Response, Result, SimpleRequest};
     use hyper::body::Buf;
     use hyper::client::connect::Connect;
@@ -178,15 +208,23 @@ mod sealed {
     where
         C: Connect + Clone + Send + Sync + 'static,
     {
-        pub async fn perform<R>(&self, request: R) -> Result<R::Response>
+        pub async fn perform<R>(
+            &self,
+            request: R,
+            compat: CompatMode,
+            wire_log: Option<&WireLog>,
+        ) -> Result<R::Response>
         where
             R: SimpleRequest,
         {
-            let request = self.build_request(request)?;
+            let method = request.method();
+            let request = self.build_request(request, compat, wire_log)?;
             let response = self.inner.request(request).await?;
             let response_body = response_to_string(response).await?;
-            tracing::debug!("Incoming response: {}", response_body);
-            R::Response::from_string(&response_body)
+            if let Some(wire_log) = wire_log {
+                wire_log.response(&method, &response_body);
+            }
+            R::Response::from_string(compat.adapt_response(response_body))
         }
     }
 
@@ -195,8 +233,14 @@ mod sealed {
         pub fn build_request<R: SimpleRequest>(
             &self,
             request: R,
+            compat: CompatMode,
+            wire_log: Option<&WireLog>,
         ) -> Result<hyper::Request<hyper::Body>> {
-            let request_body = request.into_json();
+            let method = request.method();
+            let request_body = compat.adapt_request(request.into_json());
+            if let Some(wire_log) = wire_log {
+                wire_log.request(&method, &request_body);
+            }
 
             let mut request = hyper::Request::builder()
                 .method("POST")
@@ -262,15 +306,20 @@ mod sealed {
             )))
         }
 
-        pub async fn perform<R>(&self, request: R) -> Result<R::Response>
+        pub async fn perform<R>(
+            &self,
+            request: R,
+            compat: CompatMode,
+            wire_log: Option<&WireLog>,
+        ) -> Result<R::Response>
         where
             R: SimpleRequest,
         {
             match self {
-                HttpClient::Http(c) => c.perform(request).await,
-                HttpClient::Https(c) => c.perform(request).await,
-                HttpClient::HttpProxy(c) => c.perform(request).await,
-                HttpClient::HttpsProxy(c) => c.perform(request).await,
+                HttpClient::Http(c) => c.perform(request, compat, wire_log).await,
+                HttpClient::Https(c) => c.perform(request, compat, wire_log).await,
+                HttpClient::HttpProxy(c) => c.perform(request, compat, wire_log).await,
+                HttpClient::HttpsProxy(c) => c.perform(request, compat, wire_log).await,
             }
         }
     }
@@ -285,3 +334,86 @@ mod sealed {
         Ok(response_body)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::WireLogConfig;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    // Records the fields of every event logged while it is the default
+    // subscriber.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    struct Fields<'a>(&'a mut String);
+
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, _field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!("{:?} ", value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = String::new();
+            event.record(&mut Fields(&mut fields));
+            self.0.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[tokio::test]
+    async fn responses_are_only_logged_redacted() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 4096];
+            let _ = stream.read(&mut request).unwrap();
+            let body = r#"{"jsonrpc":"2.0","id":"1","result":{"tx":"c2VjcmV0"}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        });
+
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+        let wire_log = WireLog::new(WireLogConfig::default());
+        wire_log.enable();
+        let mut client = HttpClient::new(url.as_str()).unwrap();
+        client.set_wire_log(wire_log);
+        // Only the logging of the response matters, not whether it parses
+        let _ = client.abci_info().await;
+        server.join().unwrap();
+
+        let logged = recorder.0.lock().unwrap();
+        assert!(logged.iter().any(|fields| fields.contains("<redacted>")));
+        assert!(logged.iter().all(|fields| !fields.contains("c2VjcmV0")));
+    }
+}
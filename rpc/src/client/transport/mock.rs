@@ -1,6 +1,7 @@
 //! Mock client implementation for use in testing.
 
-use crate::client::subscription::SubscriptionTx;
+use crate::client::height::{self, HeightStream};
+use crate::client::subscription::{subscription_channel, BackpressurePolicy, SubscriptionTx};
 use crate::client::sync::{unbounded, ChannelRx, ChannelTx};
 use crate::client::transport::router::SubscriptionRouter;
 use crate::event::Event;
@@ -9,6 +10,7 @@ use crate::utils::uuid_str;
 use crate::{Client, Error, Method, Request, Response, Result, Subscription, SubscriptionClient};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// A mock client implementation for use in testing.
 ///
@@ -62,6 +64,10 @@ impl<M: MockRequestMatcher> Client for MockClient<M> {
             Error::client_internal_error("no matching response for incoming request")
         })?
     }
+
+    fn latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+        height::watch(self, poll_interval)
+    }
 }
 
 impl<M: MockRequestMatcher> MockClient<M> {
@@ -92,7 +98,7 @@ impl<M: MockRequestMatcher> MockClient<M> {
 impl<M: MockRequestMatcher> SubscriptionClient for MockClient<M> {
     async fn subscribe(&self, query: Query) -> Result<Subscription> {
         let id = uuid_str();
-        let (subs_tx, subs_rx) = unbounded();
+        let (subs_tx, subs_rx) = subscription_channel(BackpressurePolicy::default());
         let (result_tx, mut result_rx) = unbounded();
         self.driver_tx.send(DriverCommand::Subscribe {
             id: id.clone(),
@@ -314,4 +320,47 @@ mod test {
         client.close();
         driver_hdl.await.unwrap().unwrap();
     }
+
+    #[tokio::test]
+    async fn mock_latest_height_stream() {
+        // The current height precedes those of the new block events
+        let status_fixture = read_json_fixture("status")
+            .await
+            .replace("\"410744\"", "\"1607\"");
+        let matcher = MockRequestMethodMatcher::default().map(Method::Status, Ok(status_fixture));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let mut heights = client.latest_height_stream(Duration::from_secs(60));
+        assert_eq!(
+            heights.next().await.unwrap().unwrap(),
+            Height::from(1607_u32)
+        );
+
+        // Heights which aren't greater than the latest one are skipped
+        for name in &[
+            "event_new_block_1",
+            "event_new_block_2",
+            "event_new_block_1",
+            "event_new_block_3",
+        ] {
+            client.publish(&read_event(name).await);
+        }
+        let heights = heights
+            .take(3)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            heights,
+            vec![
+                Height::from(1608_u32),
+                Height::from(1609_u32),
+                Height::from(1610_u32)
+            ]
+        );
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
 }
@@ -84,6 +84,22 @@ impl SubscriptionRouter {
             .map(|subs_for_query| subs_for_query.len())
             .unwrap_or(0)
     }
+
+    /// Removes the subscription with the given ID for the given query.
+    ///
+    /// Returns whether the router was tracking the subscription.
+    pub fn remove(&mut self, id: impl ToString, query: impl ToString) -> bool {
+        let query = query.to_string();
+        let subs_for_query = match self.subscriptions.get_mut(&query) {
+            Some(s) => s,
+            None => return false,
+        };
+        let removed = subs_for_query.remove(&id.to_string()).is_some();
+        if subs_for_query.is_empty() {
+            self.subscriptions.remove(&query);
+        }
+        removed
+    }
 }
 
 impl Default for SubscriptionRouter {
@@ -153,10 +169,22 @@ mod test {
         let (subs3_event_tx, mut subs3_event_rx) = unbounded();
 
         // Two subscriptions with the same query
-        router.add(subs1_id, "query1", subs1_event_tx);
-        router.add(subs2_id, "query1", subs2_event_tx);
+        router.add(
+            subs1_id,
+            "query1",
+            SubscriptionTx::Unbounded(subs1_event_tx),
+        );
+        router.add(
+            subs2_id,
+            "query1",
+            SubscriptionTx::Unbounded(subs2_event_tx),
+        );
         // Another subscription with a different query
-        router.add(subs3_id, "query2", subs3_event_tx);
+        router.add(
+            subs3_id,
+            "query2",
+            SubscriptionTx::Unbounded(subs3_event_tx),
+        );
 
         let mut ev = read_event("event_new_block_1").await;
         ev.query = "query1".into();
@@ -176,4 +204,29 @@ mod test {
         let subs3_ev = must_recv(&mut subs3_event_rx, 500).await.unwrap();
         assert_eq!(ev, subs3_ev);
     }
+
+    #[cfg(feature = "websocket-client")]
+    #[test]
+    fn router_remove_by_id() {
+        let mut router = SubscriptionRouter::default();
+        let (subs1_id, subs2_id) = (uuid_str(), uuid_str());
+        let (subs1_event_tx, _subs1_event_rx) = unbounded();
+        let (subs2_event_tx, _subs2_event_rx) = unbounded();
+        router.add(
+            &subs1_id,
+            "query1",
+            SubscriptionTx::Unbounded(subs1_event_tx),
+        );
+        router.add(
+            &subs2_id,
+            "query1",
+            SubscriptionTx::Unbounded(subs2_event_tx),
+        );
+
+        assert!(router.remove(&subs1_id, "query1"));
+        assert!(!router.remove(&subs1_id, "query1"));
+        assert_eq!(1, router.num_subscriptions_for_query("query1"));
+        assert!(router.remove(&subs2_id, "query1"));
+        assert_eq!(0, router.num_subscriptions_for_query("query1"));
+    }
 }
@@ -1,8 +1,10 @@
 //! WebSocket-based clients for accessing Tendermint RPC functionality.
 
-use crate::client::subscription::SubscriptionTx;
+use crate::client::height::{self, HeightStream};
+use crate::client::subscription::{BackpressurePolicy, ReleasedSubscription, SubscriptionTx};
 use crate::client::sync::{ChannelRx, ChannelTx};
 use crate::client::transport::router::{PublishResult, SubscriptionRouter};
+use crate::client::{CompatMode, WireLog};
 use crate::endpoint::{subscribe, unsubscribe};
 use crate::event::Event;
 use crate::query::Query;
@@ -53,11 +55,17 @@ const PING_INTERVAL: Duration = Duration::from_secs((RECV_TIMEOUT_SECONDS * 9) /
 /// It is the caller's responsibility to spawn an asynchronous task in which to
 /// execute the [`WebSocketClientDriver::run`] method. See the example below.
 ///
-/// Dropping [`Subscription`]s will automatically terminate them (the
-/// `WebSocketClientDriver` detects a disconnected channel and removes the
-/// subscription from its internal routing table). When all subscriptions to a
-/// particular query have disconnected, the driver will automatically issue an
-/// unsubscribe request to the remote RPC endpoint.
+/// Requests of all the methods of [`Client`], such as `status`, `block` or
+/// `abci_query`, are served over the same connection as the subscriptions,
+/// their responses being matched to them by ID, so that long-lived clients
+/// only need a single connection to the node.
+///
+/// Multiple [`Subscription`]s to the same query share a single subscription on
+/// the remote RPC endpoint, with incoming events being fanned out to each
+/// local subscriber. Dropping a `Subscription` will automatically terminate
+/// it, and when the last local subscription to a particular query has been
+/// dropped, the driver will automatically issue an unsubscribe request to the
+/// remote RPC endpoint.
 ///
 /// ### Timeouts
 ///
@@ -76,6 +84,13 @@ const PING_INTERVAL: Duration = Duration::from_secs((RECV_TIMEOUT_SECONDS * 9) /
 ///
 /// This is not configurable at present.
 ///
+/// ### Backpressure
+///
+/// By default, each subscription buffers an unlimited number of incoming
+/// events. If your application may consume events more slowly than the node
+/// produces them, use [`WebSocketClient::new_with_config`] to configure a
+/// bounded [`BackpressurePolicy`].
+///
 /// ## Examples
 ///
 /// ```rust,ignore
@@ -131,19 +146,44 @@ impl WebSocketClient {
     ///
     /// Supports both `ws://` and `wss://` protocols.
     pub async fn new<U>(url: U) -> Result<(Self, WebSocketClientDriver)>
+    where
+        U: TryInto<WebSocketClientUrl, Error = Error>,
+    {
+        Self::new_with_config(url, WebSocketClientConfig::default()).await
+    }
+
+    /// Construct a new WebSocket-based client connecting to the given
+    /// Tendermint node's RPC endpoint, using the given configuration.
+    pub async fn new_with_config<U>(
+        url: U,
+        config: WebSocketClientConfig,
+    ) -> Result<(Self, WebSocketClientDriver)>
     where
         U: TryInto<WebSocketClientUrl, Error = Error>,
     {
         let url = url.try_into()?;
         let (inner, driver) = if url.0.is_secure() {
-            sealed::WebSocketClient::new_secure(url.0).await?
+            sealed::WebSocketClient::new_secure(url.0, config).await?
         } else {
-            sealed::WebSocketClient::new_unsecure(url.0).await?
+            sealed::WebSocketClient::new_unsecure(url.0, config).await?
         };
         Ok((Self { inner }, driver))
     }
 }
 
+/// Configuration options for a [`WebSocketClient`].
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketClientConfig {
+    /// How each subscription buffers events that have not yet been consumed.
+    pub backpressure: BackpressurePolicy,
+    /// The RPC serialization format to use when communicating with the node
+    /// (see [`CompatMode`]).
+    pub compat: CompatMode,
+    /// Where to log the requests sent by the client and the raw responses it
+    /// receives, while enabled (see [`WireLog`]).
+    pub wire_log: Option<WireLog>,
+}
+
 #[async_trait]
 impl Client for WebSocketClient {
     async fn perform<R>(&self, request: R) -> Result<<R as Request>::Response>
@@ -152,6 +192,10 @@ impl Client for WebSocketClient {
     {
         self.inner.perform(request).await
     }
+
+    fn latest_height_stream(&self, poll_interval: Duration) -> HeightStream<'_> {
+        height::watch(self, poll_interval)
+    }
 }
 
 #[async_trait]
@@ -226,8 +270,9 @@ impl TryFrom<net::Address> for WebSocketClientUrl {
 mod sealed {
     use super::{
         DriverCommand, SimpleRequestCommand, SubscribeCommand, UnsubscribeCommand,
-        WebSocketClientDriver,
+        WebSocketClientConfig, WebSocketClientDriver,
     };
+    use crate::client::subscription::{subscription_channel, ReleasedSubscription};
     use crate::client::sync::{unbounded, ChannelTx};
     use crate::query::Query;
     use crate::request::Wrapper;
@@ -255,6 +300,8 @@ mod sealed {
     #[derive(Debug, Clone)]
     pub struct AsyncTungsteniteClient<C> {
         cmd_tx: ChannelTx<DriverCommand>,
+        release_tx: ChannelTx<ReleasedSubscription>,
+        config: WebSocketClientConfig,
         _client_type: std::marker::PhantomData<C>,
     }
 
@@ -267,15 +314,21 @@ mod sealed {
         /// this driver becomes the responsibility of the client owner, and must be
         /// executed in a separate asynchronous context to the client to ensure it
         /// doesn't block the client.
-        pub async fn new(url: Url) -> Result<(Self, WebSocketClientDriver)> {
+        pub async fn new(
+            url: Url,
+            config: WebSocketClientConfig,
+        ) -> Result<(Self, WebSocketClientDriver)> {
             let url = url.to_string();
             debug!("Connecting to unsecure WebSocket endpoint: {}", url);
             let (stream, _response) = connect_async(url).await?;
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx);
+            let (release_tx, release_rx) = unbounded();
+            let driver = WebSocketClientDriver::new(stream, cmd_rx, release_rx, config.compat);
             Ok((
                 Self {
                     cmd_tx,
+                    release_tx,
+                    config,
                     _client_type: Default::default(),
                 },
                 driver,
@@ -293,17 +346,23 @@ mod sealed {
         /// this driver becomes the responsibility of the client owner, and must be
         /// executed in a separate asynchronous context to the client to ensure it
         /// doesn't block the client.
-        pub async fn new(url: Url) -> Result<(Self, WebSocketClientDriver)> {
+        pub async fn new(
+            url: Url,
+            config: WebSocketClientConfig,
+        ) -> Result<(Self, WebSocketClientDriver)> {
             let url = url.to_string();
             debug!("Connecting to secure WebSocket endpoint: {}", url);
             // Not supplying a connector means async_tungstenite will create the
             // connector for us.
             let (stream, _response) = connect_async_with_tls_connector(url, None).await?;
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx);
+            let (release_tx, release_rx) = unbounded();
+            let driver = WebSocketClientDriver::new(stream, cmd_rx, release_rx, config.compat);
             Ok((
                 Self {
                     cmd_tx,
+                    release_tx,
+                    config,
                     _client_type: Default::default(),
                 },
                 driver,
@@ -325,9 +384,13 @@ mod sealed {
         where
             R: SimpleRequest,
         {
+            let method = request.method();
             let wrapper = Wrapper::new(request);
             let id = wrapper.id().clone().to_string();
-            let wrapped_request = wrapper.into_json();
+            let wrapped_request = self.config.compat.adapt_request(wrapper.into_json());
+            if let Some(wire_log) = &self.config.wire_log {
+                wire_log.request(&method, &wrapped_request);
+            }
             let (response_tx, mut response_rx) = unbounded();
             self.send_cmd(DriverCommand::SimpleRequest(SimpleRequestCommand {
                 id,
@@ -339,12 +402,14 @@ mod sealed {
                     "failed to hear back from WebSocket driver".to_string(),
                 )
             })??;
-            tracing::debug!("Incoming response: {}", response);
+            if let Some(wire_log) = &self.config.wire_log {
+                wire_log.response(&method, &response);
+            }
             R::Response::from_string(response)
         }
 
         pub async fn subscribe(&self, query: Query) -> Result<Subscription> {
-            let (subscription_tx, subscription_rx) = unbounded();
+            let (subscription_tx, subscription_rx) = subscription_channel(self.config.backpressure);
             let (response_tx, mut response_rx) = unbounded();
             // By default we use UUIDs to differentiate subscriptions
             let id = uuid_str();
@@ -360,7 +425,12 @@ mod sealed {
                     "failed to hear back from WebSocket driver".to_string(),
                 )
             })??;
-            Ok(Subscription::new(id, query, subscription_rx))
+            Ok(Subscription::new_with_release(
+                id,
+                query,
+                subscription_rx,
+                self.release_tx.clone(),
+            ))
         }
 
         pub async fn unsubscribe(&self, query: Query) -> Result<()> {
@@ -392,13 +462,19 @@ mod sealed {
     }
 
     impl WebSocketClient {
-        pub async fn new_unsecure(url: Url) -> Result<(Self, WebSocketClientDriver)> {
-            let (client, driver) = AsyncTungsteniteClient::<Unsecure>::new(url).await?;
+        pub async fn new_unsecure(
+            url: Url,
+            config: WebSocketClientConfig,
+        ) -> Result<(Self, WebSocketClientDriver)> {
+            let (client, driver) = AsyncTungsteniteClient::<Unsecure>::new(url, config).await?;
             Ok((Self::Unsecure(client), driver))
         }
 
-        pub async fn new_secure(url: Url) -> Result<(Self, WebSocketClientDriver)> {
-            let (client, driver) = AsyncTungsteniteClient::<Secure>::new(url).await?;
+        pub async fn new_secure(
+            url: Url,
+            config: WebSocketClientConfig,
+        ) -> Result<(Self, WebSocketClientDriver)> {
+            let (client, driver) = AsyncTungsteniteClient::<Secure>::new(url, config).await?;
             Ok((Self::Secure(client), driver))
         }
 
@@ -494,20 +570,36 @@ pub struct WebSocketClientDriver {
     stream: WebSocketStream<ConnectStream>,
     // Facilitates routing of events to their respective subscriptions.
     router: SubscriptionRouter,
+    // Used to adapt incoming messages to the format of our response types.
+    compat: CompatMode,
     // How we receive incoming commands from the WebSocketClient.
     cmd_rx: ChannelRx<DriverCommand>,
+    // How we are notified of Subscriptions that have been dropped.
+    release_rx: ChannelRx<ReleasedSubscription>,
     // Commands we've received but have not yet completed, indexed by their ID.
     // A Terminate command is executed immediately.
     pending_commands: HashMap<String, DriverCommand>,
+    // Subscribe commands for queries for which a subscription request is
+    // already in flight, indexed by query. These are added to the router once
+    // the in-flight request has been confirmed by the remote endpoint.
+    waiting_subscriptions: HashMap<String, Vec<SubscribeCommand>>,
 }
 
 impl WebSocketClientDriver {
-    fn new(stream: WebSocketStream<ConnectStream>, cmd_rx: ChannelRx<DriverCommand>) -> Self {
+    fn new(
+        stream: WebSocketStream<ConnectStream>,
+        cmd_rx: ChannelRx<DriverCommand>,
+        release_rx: ChannelRx<ReleasedSubscription>,
+        compat: CompatMode,
+    ) -> Self {
         Self {
             stream,
             router: SubscriptionRouter::default(),
+            compat,
             cmd_rx,
+            release_rx,
             pending_commands: HashMap::new(),
+            waiting_subscriptions: HashMap::new(),
         }
     }
 
@@ -541,6 +633,7 @@ impl WebSocketClientDriver {
                     DriverCommand::SimpleRequest(req_cmd) => self.simple_request(req_cmd).await?,
                     DriverCommand::Terminate => return self.close().await,
                 },
+                Some(released) = self.release_rx.recv() => self.release(released).await,
                 _ = ping_interval.tick() => self.ping().await?,
                 _ = &mut recv_timeout => {
                     return Err(Error::websocket_error(format!(
@@ -579,6 +672,14 @@ impl WebSocketClientDriver {
             return response_tx.send(Ok(()));
         }
 
+        // If a subscription request for this query is already in flight, this
+        // subscription will be added to the router along with the in-flight
+        // one once the remote endpoint has confirmed it.
+        if let Some(waiting) = self.waiting_subscriptions.get_mut(&cmd.query) {
+            waiting.push(cmd);
+            return Ok(());
+        }
+
         // Otherwise, we need to initiate a subscription request.
         let wrapper = Wrapper::new_with_id(
             Id::Str(cmd.id.clone()),
@@ -588,11 +689,36 @@ impl WebSocketClientDriver {
             cmd.response_tx.send(Err(e.clone()))?;
             return Err(e);
         }
+        self.waiting_subscriptions
+            .insert(cmd.query.clone(), Vec::new());
         self.pending_commands
             .insert(cmd.id.clone(), DriverCommand::Subscribe(cmd));
         Ok(())
     }
 
+    async fn release(&mut self, released: ReleasedSubscription) {
+        // The subscription may have already been removed from the router,
+        // e.g. via an explicit unsubscribe request or because we failed to
+        // publish an event to it.
+        if !self.router.remove(&released.id, &released.query)
+            || self.router.num_subscriptions_for_query(&released.query) > 0
+        {
+            return;
+        }
+        debug!(
+            "Last subscriber for query \"{}\" has been dropped. Unsubscribing from query...",
+            released.query
+        );
+        // As with disconnected subscribers, we issue a fire-and-forget
+        // unsubscribe message.
+        if let Err(e) = self
+            .send_request(Wrapper::new(unsubscribe::Request::new(released.query)))
+            .await
+        {
+            error!("Failed to send unsubscribe request: {}", e);
+        }
+    }
+
     async fn unsubscribe(&mut self, cmd: UnsubscribeCommand) -> Result<()> {
         // Terminate all subscriptions for this query immediately. This
         // prioritizes acknowledgement of the caller's wishes over networking
@@ -639,6 +765,7 @@ impl WebSocketClientDriver {
     }
 
     async fn handle_text_msg(&mut self, msg: String) -> Result<()> {
+        let msg = self.compat.adapt_response(msg);
         if let Ok(ev) = Event::from_string(&msg) {
             self.publish_event(ev).await;
             return Ok(());
@@ -690,10 +817,18 @@ impl WebSocketClientDriver {
     ) -> Result<()> {
         match pending_cmd {
             DriverCommand::Subscribe(cmd) => {
-                let (id, query, subscription_tx, response_tx) =
-                    (cmd.id, cmd.query, cmd.subscription_tx, cmd.response_tx);
-                self.router.add(id, query, subscription_tx);
-                response_tx.send(Ok(()))
+                let waiting = self
+                    .waiting_subscriptions
+                    .remove(&cmd.query)
+                    .unwrap_or_default();
+                let result = subscribe::Response::from_string(&response).map(|_| ());
+                for cmd in std::iter::once(cmd).chain(waiting) {
+                    if result.is_ok() {
+                        self.router.add(cmd.id, cmd.query, cmd.subscription_tx);
+                    }
+                    cmd.response_tx.send(result.clone())?;
+                }
+                Ok(())
             }
             DriverCommand::Unsubscribe(cmd) => cmd.response_tx.send(Ok(())),
             DriverCommand::SimpleRequest(cmd) => cmd.response_tx.send(Ok(response)),
@@ -965,8 +1100,9 @@ mod test {
                                     self.remove_subscription(req.params().query.clone());
                                     self.send(req.id().clone(), unsubscribe::Response {}).await;
                                 }
-                                _ => {
-                                    println!("Unsupported method in incoming request: {}", &method);
+                                method => {
+                                    let id = json_msg.get("id").cloned().unwrap_or_default();
+                                    self.send_fixture(method, id).await;
                                 }
                             },
                             Err(e) => {
@@ -1009,6 +1145,17 @@ mod test {
                 .unwrap();
         }
 
+        // Answers a request with the fixture named after its method.
+        async fn send_fixture(&mut self, method: Method, id: serde_json::Value) {
+            let mut res: serde_json::Value =
+                serde_json::from_str(&read_json_fixture(method.as_str()).await).unwrap();
+            res["id"] = id;
+            self.conn
+                .send(Message::Text(res.to_string()))
+                .await
+                .unwrap();
+        }
+
         async fn terminate(&mut self) {
             let _ = self
                 .conn
@@ -1082,4 +1229,62 @@ mod test {
             );
         }
     }
+
+    #[tokio::test]
+    async fn websocket_client_shares_subscriptions_per_query() {
+        let event1 = read_event("event_new_block_1").await;
+        let event2 = read_event("event_new_block_2").await;
+
+        let mut server = TestServer::new("127.0.0.1:0").await;
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        let (subs1, subs2) = tokio::join!(
+            client.subscribe(EventType::NewBlock.into()),
+            client.subscribe(EventType::NewBlock.into()),
+        );
+        let (subs1, mut subs2) = (subs1.unwrap(), subs2.unwrap());
+        assert_ne!(subs1.id(), subs2.id());
+
+        server.publish_event(event1.clone()).unwrap();
+        assert_eq!(event1, subs2.next().await.unwrap().unwrap());
+
+        // Dropping one of the subscribers must not affect the other.
+        drop(subs1);
+        server.publish_event(event2.clone()).unwrap();
+        assert_eq!(event2, subs2.next().await.unwrap().unwrap());
+
+        client.close().unwrap();
+        server.terminate().await.unwrap();
+        let _ = driver_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_client_queries_alongside_subscriptions() {
+        let event = read_event("event_new_block_1").await;
+
+        let mut server = TestServer::new("127.0.0.1:0").await;
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+
+        // Queries are answered over the same connection as the events.
+        let (status, abci_info, block) =
+            tokio::join!(client.status(), client.abci_info(), client.latest_block());
+        assert_eq!(status.unwrap().node_info.network.as_str(), "cosmoshub-2");
+        assert_eq!(abci_info.unwrap().data, "GaiaApp");
+        assert_eq!(block.unwrap().block.header.height.value(), 10);
+
+        server.publish_event(event.clone()).unwrap();
+        assert_eq!(event, subs.next().await.unwrap().unwrap());
+
+        client.close().unwrap();
+        server.terminate().await.unwrap();
+        let _ = driver_handle.await.unwrap();
+    }
 }
@@ -0,0 +1,229 @@
+//! Logging of the raw JSON-RPC messages exchanged with a node, to diagnose
+//! serialization mismatches with specific node versions.
+
+use crate::Method;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// The `tracing` target of the messages logged by a [`WireLog`].
+pub const WIRE_LOG_TARGET: &str = "tendermint_rpc::wire";
+
+/// The placeholder replacing the values of redacted keys.
+const REDACTED: &str = "<redacted>";
+
+/// Configuration for a [`WireLog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireLogConfig {
+    /// The maximum length of a logged message, in bytes. Longer messages are
+    /// truncated.
+    pub max_len: usize,
+    /// The keys of the JSON objects whose values are redacted, wherever they
+    /// appear in a message.
+    pub redacted_keys: Vec<String>,
+    /// The methods whose requests and responses are logged. If `None`, those
+    /// of all methods are logged.
+    pub methods: Option<Vec<Method>>,
+}
+
+impl Default for WireLogConfig {
+    fn default() -> Self {
+        Self {
+            max_len: 4096,
+            redacted_keys: vec!["tx".to_string(), "priv_key".to_string()],
+            methods: None,
+        }
+    }
+}
+
+/// Logs every request sent by a client and the raw response it receives, at
+/// the `DEBUG` level and with the [`WIRE_LOG_TARGET`] target.
+///
+/// Logging can be toggled at runtime: clones of a `WireLog` share whether it
+/// is enabled, so that a clone can be kept to toggle the logging of the
+/// client it was given to.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{HttpClient, WireLog, WireLogConfig};
+///
+/// let wire_log = WireLog::new(WireLogConfig::default());
+/// let mut client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+/// client.set_wire_log(wire_log.clone());
+///
+/// wire_log.enable();
+/// let status = client.status().await.unwrap();
+/// wire_log.disable();
+/// ```
+#[derive(Debug, Clone)]
+pub struct WireLog {
+    enabled: Arc<AtomicBool>,
+    config: Arc<WireLogConfig>,
+}
+
+impl WireLog {
+    /// Create a wire log with the given configuration, which is disabled
+    /// until [`WireLog::enable`] is called.
+    pub fn new(config: WireLogConfig) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(false)),
+            config: Arc::new(config),
+        }
+    }
+
+    /// Start logging messages.
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop logging messages.
+    pub fn disable(&self) {
+        self.enabled.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether messages are being logged.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// The configuration of this wire log.
+    pub fn config(&self) -> &WireLogConfig {
+        &self.config
+    }
+
+    /// Log a request for the given method.
+    pub(crate) fn request(&self, method: &Method, body: &str) {
+        if self.logs(method) {
+            tracing::debug!(
+                target: WIRE_LOG_TARGET,
+                "{} request: {}",
+                method.as_str(),
+                self.format(body)
+            );
+        }
+    }
+
+    /// Log the raw response to a request for the given method.
+    pub(crate) fn response(&self, method: &Method, body: &str) {
+        if self.logs(method) {
+            tracing::debug!(
+                target: WIRE_LOG_TARGET,
+                "{} response: {}",
+                method.as_str(),
+                self.format(body)
+            );
+        }
+    }
+
+    fn logs(&self, method: &Method) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+        match &self.config.methods {
+            Some(methods) => methods.contains(method),
+            None => true,
+        }
+    }
+
+    /// Redact and truncate the given message. Messages which aren't valid
+    /// JSON are only truncated.
+    fn format(&self, body: &str) -> String {
+        let redacted = match serde_json::from_str::<Value>(body) {
+            Ok(mut value) => {
+                self.redact(&mut value);
+                value.to_string()
+            }
+            Err(_) => body.to_string(),
+        };
+        truncate(redacted, self.config.max_len)
+    }
+
+    fn redact(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                for (key, value) in object.iter_mut() {
+                    if self.config.redacted_keys.iter().any(|k| k == key) {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.redact(value)),
+            _ => {}
+        }
+    }
+}
+
+fn truncate(mut message: String, max_len: usize) -> String {
+    if message.len() <= max_len {
+        return message;
+    }
+
+    let mut len = max_len;
+    while !message.is_char_boundary(len) {
+        len -= 1;
+    }
+    let truncated = message.len() - len;
+    message.truncate(len);
+    message.push_str(&format!("... ({} bytes truncated)", truncated));
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(wire_log: &WireLog, message: &str) -> Value {
+        serde_json::from_str(&wire_log.format(message)).unwrap()
+    }
+
+    #[test]
+    fn redacts_and_truncates() {
+        let wire_log = WireLog::new(WireLogConfig::default());
+        assert_eq!(
+            format(
+                &wire_log,
+                r#"{"jsonrpc":"2.0","id":"1","method":"broadcast_tx_sync","params":{"tx":"c2VjcmV0"}}"#
+            ),
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "1",
+                "method": "broadcast_tx_sync",
+                "params": { "tx": "<redacted>" },
+            })
+        );
+        assert_eq!(
+            format(
+                &wire_log,
+                r#"{"result":{"txs":[{"tx":"c2VjcmV0","height":"1"}]}}"#
+            ),
+            serde_json::json!({ "result": { "txs": [{ "tx": "<redacted>", "height": "1" }] } })
+        );
+
+        let wire_log = WireLog::new(WireLogConfig {
+            max_len: 4,
+            ..WireLogConfig::default()
+        });
+        assert_eq!(wire_log.format("not json"), "not ... (4 bytes truncated)");
+        assert_eq!(wire_log.format("é-é"), "é-... (2 bytes truncated)");
+    }
+
+    #[test]
+    fn toggles_and_filters_methods() {
+        let wire_log = WireLog::new(WireLogConfig {
+            methods: Some(vec![Method::Status]),
+            ..WireLogConfig::default()
+        });
+        assert!(!wire_log.logs(&Method::Status));
+
+        let toggle = wire_log.clone();
+        toggle.enable();
+        assert!(wire_log.logs(&Method::Status));
+        assert!(!wire_log.logs(&Method::Block));
+
+        toggle.disable();
+        assert!(!wire_log.is_enabled());
+    }
+}
@@ -8,8 +8,13 @@ pub mod blockchain;
 pub mod broadcast;
 pub mod commit;
 pub mod consensus_state;
+pub mod custom;
+pub mod dial_peers;
+pub mod dial_seeds;
 pub mod evidence;
 pub mod genesis;
+pub mod header;
+pub mod header_by_hash;
 pub mod health;
 pub mod net_info;
 pub mod status;
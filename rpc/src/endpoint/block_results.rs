@@ -1,5 +1,6 @@
 //! `/block_results` endpoint JSON-RPC wrapper
 
+use crate::abci_event::Events;
 use serde::{Deserialize, Serialize};
 
 use tendermint::{abci, block, consensus, validator};
@@ -47,6 +48,11 @@ pub struct Response {
     /// End block events (might be explicit null)
     pub end_block_events: Option<Vec<abci::Event>>,
 
+    /// Finalize block events (only produced by nodes running CometBFT v0.38
+    /// and later, which replace begin/end block events)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalize_block_events: Option<Vec<abci::Event>>,
+
     /// Validator updates (might be explicit null)
     #[serde(deserialize_with = "abci::responses::deserialize_validator_updates")]
     pub validator_updates: Vec<validator::Update>,
@@ -55,4 +61,35 @@ pub struct Response {
     pub consensus_param_updates: Option<consensus::Params>,
 }
 
+impl Response {
+    /// All of the events emitted while processing this block, decoded into
+    /// UTF-8 attributes: begin block events first, followed by the events of
+    /// each transaction in order, the end block events and finally any
+    /// finalize block events.
+    pub fn events(&self) -> Events {
+        let txs_events = self
+            .txs_results
+            .iter()
+            .flatten()
+            .flat_map(|result| result.events.iter());
+        self.begin_block_events
+            .iter()
+            .flatten()
+            .chain(txs_events)
+            .chain(self.end_block_events.iter().flatten())
+            .chain(self.finalize_block_events.iter().flatten())
+            .map(Into::into)
+            .collect()
+    }
+
+    /// The decoded events emitted by the transaction at the given index within
+    /// the block, if any.
+    pub fn tx_events(&self, index: usize) -> Option<Events> {
+        self.txs_results
+            .as_ref()
+            .and_then(|results| results.get(index))
+            .map(|result| result.events.as_slice().into())
+    }
+}
+
 impl crate::Response for Response {}
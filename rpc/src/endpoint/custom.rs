@@ -0,0 +1,102 @@
+//! Endpoints which this crate doesn't model, e.g. those added by forks of
+//! Tendermint, with their parameters and results in any serde-compatible
+//! type, such as `serde_json::Value`.
+
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Request to the endpoint with the given name, whose result is a `T`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Request<P, T> {
+    #[serde(skip)]
+    method: String,
+
+    /// Parameters of the request
+    pub params: P,
+
+    #[serde(skip)]
+    response: PhantomData<fn() -> T>,
+}
+
+impl<P, T> Request<P, T> {
+    /// Create a new request to the endpoint with the given name.
+    pub fn new(method: impl Into<String>, params: P) -> Self {
+        Self {
+            method: method.into(),
+            params,
+            response: PhantomData,
+        }
+    }
+}
+
+impl<P, T> crate::Request for Request<P, T>
+where
+    P: Debug + DeserializeOwned + Serialize + Send,
+    T: Debug + DeserializeOwned + Serialize,
+{
+    type Response = Response<T>;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::Custom(self.method.clone())
+    }
+}
+
+impl<P, T> crate::SimpleRequest for Request<P, T>
+where
+    P: Debug + DeserializeOwned + Serialize + Send,
+    T: Debug + DeserializeOwned + Serialize,
+{
+}
+
+/// Result of a request to an endpoint which this crate doesn't model
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Response<T>(pub T);
+
+impl<T> crate::Response for Response<T> where T: DeserializeOwned + Serialize {}
+
+#[cfg(all(test, any(feature = "http-client", feature = "websocket-client")))]
+mod tests {
+    use super::*;
+    use crate::{Client, Method, MockClient, MockRequestMethodMatcher};
+    use serde_json::{json, Value};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct ForkInfo {
+        name: String,
+        version: u64,
+    }
+
+    #[tokio::test]
+    async fn raw_and_custom_requests() {
+        let request = Request::<Value, Value>::new("fork_info", json!({ "verbose": true }));
+        assert_eq!(
+            crate::Request::method(&request),
+            Method::Custom("fork_info".to_owned())
+        );
+        let json: Value = serde_json::from_str(&crate::Request::into_json(request)).unwrap();
+        assert_eq!(json["method"], "fork_info");
+        assert_eq!(json["params"], json!({ "verbose": true }));
+
+        let response = r#"{"jsonrpc":"2.0","id":"","result":{"name":"fork","version":2}}"#;
+        let matcher = MockRequestMethodMatcher::default().map(
+            Method::Custom("fork_info".to_owned()),
+            Ok(response.to_string()),
+        );
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let raw = client.perform_raw("fork_info", json!({})).await.unwrap();
+        assert_eq!(raw, json!({ "name": "fork", "version": 2 }));
+        let info: ForkInfo = client.perform_custom("fork_info", ()).await.unwrap();
+        assert_eq!((info.name.as_str(), info.version), ("fork", 2));
+        assert!(client.perform_raw("other", json!({})).await.is_err());
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+//! `/dial_peers` endpoint JSON-RPC wrapper
+//!
+//! This endpoint is only available on nodes whose RPC server has been
+//! configured with `unsafe = true`.
+
+use crate::Method;
+use serde::{Deserialize, Serialize};
+
+/// Dial the given peers.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Peer addresses, of the form `<node ID>@<host>:<port>`.
+    pub peers: Vec<String>,
+
+    /// Add the peers to the node's persistent peers.
+    pub persistent: bool,
+
+    /// Add the peers to the node's unconditional peers, which are dialed
+    /// regardless of the node's maximum peer limits.
+    pub unconditional: bool,
+
+    /// Add the peers to the node's private peers, whose addresses are not
+    /// gossiped to other peers.
+    pub private: bool,
+}
+
+impl Request {
+    /// Create a new request to dial the given peers.
+    pub fn new(peers: Vec<String>) -> Self {
+        Self {
+            peers,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the peers are to be added to the node's persistent peers.
+    pub fn persistent(mut self, persistent: bool) -> Self {
+        self.persistent = persistent;
+        self
+    }
+
+    /// Whether the peers are to be added to the node's unconditional peers.
+    pub fn unconditional(mut self, unconditional: bool) -> Self {
+        self.unconditional = unconditional;
+        self
+    }
+
+    /// Whether the peers are to be added to the node's private peers.
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = private;
+        self
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> Method {
+        Method::DialPeers
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Response from dialing peers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// A description of the outcome of the request.
+    pub log: String,
+}
+
+impl crate::Response for Response {}
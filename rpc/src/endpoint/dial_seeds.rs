@@ -0,0 +1,40 @@
+//! `/dial_seeds` endpoint JSON-RPC wrapper
+//!
+//! This endpoint is only available on nodes whose RPC server has been
+//! configured with `unsafe = true`.
+
+use crate::Method;
+use serde::{Deserialize, Serialize};
+
+/// Dial the given seed nodes.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Seed addresses, of the form `<node ID>@<host>:<port>`.
+    pub seeds: Vec<String>,
+}
+
+impl Request {
+    /// Create a new request to dial the given seed nodes.
+    pub fn new(seeds: Vec<String>) -> Self {
+        Self { seeds }
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> Method {
+        Method::DialSeeds
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Response from dialing seed nodes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// A description of the outcome of the request.
+    pub log: String,
+}
+
+impl crate::Response for Response {}
@@ -3,7 +3,7 @@
 use crate::Method;
 
 use serde::{Deserialize, Serialize};
-use tendermint::{abci::transaction, evidence::Evidence};
+use tendermint::{evidence::Evidence, Hash};
 
 /// `/broadcast_evidence`: broadcast an evidence.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -30,11 +30,52 @@ impl crate::Request for Request {
 impl crate::SimpleRequest for Request {}
 
 /// Response from either an evidence broadcast request.
+///
+/// If the node rejects the evidence, the reason can be obtained from the
+/// returned error with [`Error::evidence_rejection`](crate::Error::evidence_rejection).
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Response {
     /// Evidence hash
-    /// TODO: transaction::Hash should be tmhash (github.com/tendermint/tendermint/crypto/tmhash)
-    pub hash: transaction::Hash,
+    #[serde(with = "evidence_hash")]
+    pub hash: Hash,
 }
 
 impl crate::Response for Response {}
+
+/// Serialization of the evidence hash, which Tendermint encodes as base64.
+///
+/// Hex-encoded hashes are accepted as well when deserializing.
+mod evidence_hash {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+    use subtle_encoding::base64;
+    use tendermint::{
+        hash::{Algorithm, SHA256_HASH_SIZE},
+        Hash,
+    };
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Hash, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+
+        // A hex-encoded SHA256 hash is valid base64 as well, but decodes to
+        // 48 bytes rather than 32.
+        match base64::decode(&encoded) {
+            Ok(bytes) if bytes.len() == SHA256_HASH_SIZE => {
+                Hash::from_bytes(Algorithm::Sha256, &bytes).map_err(D::Error::custom)
+            }
+            _ => Hash::from_hex_upper(Algorithm::Sha256, &encoded.to_uppercase())
+                .map_err(D::Error::custom),
+        }
+    }
+
+    pub fn serialize<S>(value: &Hash, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let encoded = String::from_utf8(base64::encode(value.as_bytes()))
+            .map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&encoded)
+    }
+}
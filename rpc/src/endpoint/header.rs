@@ -0,0 +1,45 @@
+//! `/header` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+
+use tendermint::block::{self, Header};
+
+/// Get the header of a block at a given height.
+///
+/// This endpoint is only available on nodes running CometBFT v0.38 and later.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Height of the block whose header to request.
+    ///
+    /// If no height is provided, it will fetch the header of the latest
+    /// block.
+    pub height: Option<block::Height>,
+}
+
+impl Request {
+    /// Create a new request for the header of the block at the given height.
+    pub fn new(height: block::Height) -> Self {
+        Self {
+            height: Some(height),
+        }
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::Header
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Header response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Block header
+    pub header: Header,
+}
+
+impl crate::Response for Response {}
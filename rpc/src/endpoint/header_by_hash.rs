@@ -0,0 +1,84 @@
+//! `/header_by_hash` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+
+use tendermint::{block::Header, Hash};
+
+/// Get the header of the block with the given hash.
+///
+/// This endpoint is only available on nodes running CometBFT v0.38 and later.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Hash of the block whose header to request.
+    #[serde(with = "base64_hash")]
+    pub hash: Hash,
+}
+
+impl Request {
+    /// Create a new request for the header of the block with the given hash.
+    pub fn new(hash: Hash) -> Self {
+        Self { hash }
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::HeaderByHash
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Header response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Block header, or `None` if no block with the given hash was found.
+    pub header: Option<Header>,
+}
+
+impl crate::Response for Response {}
+
+// Block hashes are base64-encoded in requests to CometBFT v0.38 nodes.
+mod base64_hash {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+    use subtle_encoding::base64;
+    use tendermint::{hash::Algorithm, Hash};
+
+    pub fn serialize<S>(hash: &Hash, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let encoded = String::from_utf8(base64::encode(hash.as_bytes())).unwrap();
+        serializer.serialize_str(&encoded)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Hash, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = base64::decode(&encoded).map_err(D::Error::custom)?;
+        Hash::from_bytes(Algorithm::Sha256, &bytes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn request_hash_is_base64_encoded() {
+        let hash =
+            Hash::from_str("E5A8B4B5C5A0C7E4A9ACD6D0E2A0F2D1C2B3A4958677685940312213F4E5D6C7")
+                .unwrap();
+        let json = serde_json::to_value(Request::new(hash)).unwrap();
+        assert_eq!("5ai0tcWgx+SprNbQ4qDy0cKzpJWGd2hZQDEiE/Tl1sc=", json["hash"]);
+        assert_eq!(
+            Request::new(hash),
+            serde_json::from_value::<Request>(json).unwrap()
+        );
+    }
+}
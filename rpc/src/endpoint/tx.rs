@@ -1,9 +1,13 @@
 //! `/tx` endpoint JSON-RPC wrapper
 
+use crate::abci_event::Events;
 use crate::Method;
 use serde::{Deserialize, Serialize};
-use tendermint::{abci, block};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+use tendermint::{abci, block, merkle, Hash};
 use tendermint_proto::types::TxProof;
+use thiserror::Error;
 
 /// Request for finding a transaction by its hash.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -43,4 +47,77 @@ pub struct Response {
     pub proof: Option<TxProof>,
 }
 
+impl Response {
+    /// The events emitted by the transaction, decoded into UTF-8 attributes.
+    pub fn events(&self) -> Events {
+        self.tx_result.events.as_slice().into()
+    }
+
+    /// Verify the Merkle proof of this transaction's inclusion in a block,
+    /// given the `data_hash` from that block's header.
+    ///
+    /// This requires the transaction to have been requested with
+    /// `prove = true`. Since the block's `data_hash` is obtained
+    /// independently of this response (e.g. from a header verified by a light
+    /// client), successful verification does not rely on trusting the node
+    /// that served the response.
+    pub fn verify_proof(&self, data_hash: &Hash) -> Result<(), ProofError> {
+        let tx_proof = self.proof.as_ref().ok_or(ProofError::MissingProof)?;
+        if tx_proof.root_hash != data_hash.as_bytes() {
+            return Err(ProofError::RootHashMismatch);
+        }
+        if tx_proof.data != self.tx.as_bytes() {
+            return Err(ProofError::TxMismatch);
+        }
+        let proof = tx_proof.proof.as_ref().ok_or(ProofError::MissingProof)?;
+
+        let leaf = Sha256::digest(&tx_proof.data);
+        if proof.leaf_hash != merkle::leaf_hash(&leaf) {
+            return Err(ProofError::LeafHashMismatch);
+        }
+        let root: merkle::Hash = tx_proof
+            .root_hash
+            .as_slice()
+            .try_into()
+            .map_err(|_| ProofError::InvalidProof)?;
+        let aunts = proof
+            .aunts
+            .iter()
+            .map(|aunt| aunt.as_slice().try_into())
+            .collect::<Result<Vec<merkle::Hash>, _>>()
+            .map_err(|_| ProofError::InvalidProof)?;
+        let (index, total) = match (proof.index.try_into(), proof.total.try_into()) {
+            (Ok(index), Ok(total)) => (index, total),
+            _ => return Err(ProofError::InvalidProof),
+        };
+        if merkle::verify_inclusion(&root, &leaf, index, total, &aunts) {
+            Ok(())
+        } else {
+            Err(ProofError::InvalidProof)
+        }
+    }
+}
+
+/// Errors arising when verifying the Merkle proof in a `/tx` response.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum ProofError {
+    /// The response does not contain a proof.
+    #[error(
+        "response does not contain a proof (was the transaction requested with prove = true?)"
+    )]
+    MissingProof,
+    /// The root hash of the proof does not match the block's data hash.
+    #[error("proof root hash does not match the block's data hash")]
+    RootHashMismatch,
+    /// The transaction in the proof does not match the one in the response.
+    #[error("proof data does not match the transaction")]
+    TxMismatch,
+    /// The leaf hash in the proof does not match the transaction's hash.
+    #[error("proof leaf hash does not match the transaction's hash")]
+    LeafHashMismatch,
+    /// The proof does not lead to the expected root hash.
+    #[error("invalid Merkle proof")]
+    InvalidProof,
+}
+
 impl crate::Response for Response {}
@@ -3,7 +3,8 @@
 #[cfg(feature = "websocket-client")]
 use async_tungstenite::tungstenite::Error as WSError;
 
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 use std::fmt::{self, Display};
 use thiserror::Error;
 
@@ -19,6 +20,7 @@ pub struct Error {
     message: String,
 
     /// Additional data about the error
+    #[serde(default, deserialize_with = "deserialize_data")]
     data: Option<String>,
 }
 impl std::error::Error for Error {}
@@ -49,6 +51,11 @@ impl Error {
         Error::new(Code::InvalidParams, Some(data.to_string()))
     }
 
+    /// Create a new gRPC error
+    pub fn grpc_error(cause: impl Into<String>) -> Error {
+        Error::new(Code::GrpcError, Some(cause.into()))
+    }
+
     /// Create a new websocket error
     pub fn websocket_error(cause: impl Into<String>) -> Error {
         Error::new(Code::WebSocketError, Some(cause.into()))
@@ -80,20 +87,75 @@ impl Error {
         Error::new(Code::ClientInternalError, Some(cause.into()))
     }
 
+    /// A subscription's consumer could not keep up with incoming events, and
+    /// `missed` events were discarded as a result.
+    pub fn subscription_lagged(missed: u64) -> Error {
+        Error::new(Code::SubscriptionLagged, Some(missed.to_string()))
+    }
+
+    /// The number of events missed by a lagging subscription, if this is a
+    /// [`Code::SubscriptionLagged`] error.
+    pub fn missed_events(&self) -> Option<u64> {
+        match self.code {
+            Code::SubscriptionLagged => self.data().and_then(|data| data.parse().ok()),
+            _ => None,
+        }
+    }
+
+    /// The reason why the node rejected the evidence submitted to
+    /// `/broadcast_evidence`, if this error is such a rejection.
+    pub fn evidence_rejection(&self) -> Option<EvidenceRejection> {
+        match self.code {
+            Code::InternalError => self.data().and_then(EvidenceRejection::parse),
+            _ => None,
+        }
+    }
+
     /// Obtain the `rpc::error::Code` for this error
     pub fn code(&self) -> Code {
         self.code
     }
 
+    /// Where this error comes from
+    pub fn class(&self) -> Class {
+        self.code.class()
+    }
+
     /// Borrow the error message (if available)
     pub fn message(&self) -> &str {
         &self.message
     }
 
     /// Optional additional error message (if available)
+    ///
+    /// Data sent by the node as another JSON value than a string, e.g. an
+    /// object, is kept as its JSON encoding.
     pub fn data(&self) -> Option<&str> {
         self.data.as_ref().map(AsRef::as_ref)
     }
+
+    /// Parse the additional data of the error (if available) as a `T`, e.g.
+    /// the structured data sent by some nodes, or a `serde_json::Value`.
+    pub fn data_as<T>(&self) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let data = self.data()?;
+        serde_json::from_str(data)
+            .or_else(|_| serde_json::from_value(Value::String(data.to_string())))
+            .ok()
+    }
+}
+
+fn deserialize_data<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => None,
+        Some(Value::String(data)) => Some(data),
+        Some(data) => Some(data.to_string()),
+    })
 }
 
 impl Display for Error {
@@ -188,6 +250,20 @@ pub enum Code {
     #[error("Client internal error")]
     ClientInternalError,
 
+    /// A subscription's consumer did not keep up with incoming events and
+    /// some events were discarded.
+    ///
+    /// This is an error unique to this client, and is not available in the
+    /// [Go client].
+    ///
+    /// [Go client]: https://github.com/tendermint/tendermint/tree/master/rpc/jsonrpc/client
+    #[error("Subscription lagged")]
+    SubscriptionLagged,
+
+    /// Low-level gRPC error
+    #[error("gRPC error")]
+    GrpcError,
+
     /// Parse error i.e. invalid JSON (-32700)
     #[error("Parse error. Invalid JSON")]
     ParseError,
@@ -222,6 +298,38 @@ impl Code {
     pub fn value(self) -> i32 {
         i32::from(self)
     }
+
+    /// Where errors with this code come from
+    pub fn class(self) -> Class {
+        match self {
+            Code::HttpError | Code::WebSocketError | Code::GrpcError => Class::Transport,
+            Code::ClientInternalError | Code::SubscriptionLagged => Class::Client,
+            Code::ParseError
+            | Code::InvalidRequest
+            | Code::MethodNotFound
+            | Code::InvalidParams => Class::Protocol,
+            Code::InternalError | Code::ServerError | Code::Other(_) => Class::Server,
+        }
+    }
+}
+
+/// Classes of errors, by where they come from
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Class {
+    /// The node couldn't be reached, or the connection to it failed. Such
+    /// errors are usually worth retrying.
+    Transport,
+
+    /// The request or the response isn't valid JSON-RPC, or doesn't match the
+    /// endpoint it is for, e.g. because the node runs an unsupported version.
+    Protocol,
+
+    /// The node failed to handle a valid request, e.g. because the requested
+    /// block isn't available, or an ABCI query failed.
+    Server,
+
+    /// The client itself failed, or a subscription lagged.
+    Client,
 }
 
 impl From<i32> for Code {
@@ -230,6 +338,8 @@ impl From<i32> for Code {
             0 => Code::HttpError,
             1 => Code::WebSocketError,
             2 => Code::ClientInternalError,
+            3 => Code::SubscriptionLagged,
+            4 => Code::GrpcError,
             -32700 => Code::ParseError,
             -32600 => Code::InvalidRequest,
             -32601 => Code::MethodNotFound,
@@ -247,6 +357,8 @@ impl From<Code> for i32 {
             Code::HttpError => 0,
             Code::WebSocketError => 1,
             Code::ClientInternalError => 2,
+            Code::SubscriptionLagged => 3,
+            Code::GrpcError => 4,
             Code::ParseError => -32700,
             Code::InvalidRequest => -32600,
             Code::MethodNotFound => -32601,
@@ -270,10 +382,56 @@ impl Serialize for Code {
     }
 }
 
+/// Reasons why a node rejects the evidence submitted to `/broadcast_evidence`.
+///
+/// Tendermint reports them as internal errors, whose data is the message of
+/// the error raised by the evidence pool, e.g.
+/// `failed to add evidence: invalid evidence: evidence from height 1 (created at: ...) is too old; ...`.
+#[derive(Copy, Clone, Debug, Eq, Error, Hash, PartialEq)]
+pub enum EvidenceRejection {
+    /// The evidence is older than allowed by the evidence parameters of the
+    /// chain, both in number of blocks and in duration.
+    #[error("evidence expired")]
+    Expired,
+
+    /// The evidence is malformed, or does not match the state of the chain.
+    #[error("invalid evidence")]
+    Invalid,
+
+    /// The evidence was already committed, or is already pending in the
+    /// evidence pool of the node.
+    #[error("evidence already committed")]
+    AlreadyCommitted,
+}
+
+impl EvidenceRejection {
+    fn parse(data: &str) -> Option<Self> {
+        if !data.starts_with("failed to add evidence")
+            && !data.starts_with("evidence.ValidateBasic failed")
+        {
+            return None;
+        }
+
+        // Expired evidence is reported as invalid, so check for it first
+        if data.contains("is too old") {
+            Some(EvidenceRejection::Expired)
+        } else if data.contains("already committed")
+            || data.contains("already stored")
+            || data.contains("already pending")
+        {
+            Some(EvidenceRejection::AlreadyCommitted)
+        } else {
+            Some(EvidenceRejection::Invalid)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::Class;
     use super::Code;
     use super::Error;
+    use super::EvidenceRejection;
 
     #[test]
     fn test_serialize() {
@@ -287,4 +445,75 @@ mod tests {
         assert_eq!(res.code.value(), -32700);
         assert_eq!(res.data, Some("hello world".to_string()));
     }
+
+    #[test]
+    fn test_structured_data() {
+        let json =
+            r#"{"code":-32603,"message":"Internal error","data":{"height":10,"reason":"pruned"}}"#;
+        let err: Error = serde_json::from_str(json).unwrap();
+        assert_eq!(err.code(), Code::InternalError);
+        assert_eq!(err.class(), Class::Server);
+        let data: serde_json::Value = err.data_as().unwrap();
+        assert_eq!(data["height"], 10);
+        assert_eq!(data["reason"], "pruned");
+
+        // String data is kept as is
+        let err = Error::server_error("height 10 is not available");
+        assert_eq!(
+            err.data_as::<String>().unwrap(),
+            "height 10 is not available"
+        );
+        assert_eq!(
+            serde_json::from_str::<Error>(r#"{"code":-32000,"message":"Server error"}"#)
+                .unwrap()
+                .data(),
+            None
+        );
+
+        assert_eq!(
+            Error::http_error("connection refused").class(),
+            Class::Transport
+        );
+        assert_eq!(
+            Error::parse_error("expected value").class(),
+            Class::Protocol
+        );
+        assert_eq!(Error::subscription_lagged(3).class(), Class::Client);
+    }
+
+    #[test]
+    fn test_evidence_rejection() {
+        let rejection = |data: &str| {
+            Error::new(Code::InternalError, Some(data.to_string())).evidence_rejection()
+        };
+
+        assert_eq!(
+            rejection(
+                "failed to add evidence: invalid evidence: evidence from height 1 \
+                 (created at: 2021-01-01 00:00:00 +0000 UTC) is too old; min height is 100 \
+                 and evidence can not be older than 2021-01-02 00:00:00 +0000 UTC"
+            ),
+            Some(EvidenceRejection::Expired)
+        );
+        assert_eq!(
+            rejection("failed to add evidence: invalid evidence: invalid signature"),
+            Some(EvidenceRejection::Invalid)
+        );
+        assert_eq!(
+            rejection("evidence.ValidateBasic failed: empty vote A"),
+            Some(EvidenceRejection::Invalid)
+        );
+        assert_eq!(
+            rejection("failed to add evidence: evidence was already committed"),
+            Some(EvidenceRejection::AlreadyCommitted)
+        );
+        assert_eq!(
+            rejection("min height 321 can't be greater than max height 123"),
+            None
+        );
+        assert_eq!(
+            Error::server_error("failed to add evidence: invalid evidence").evidence_rejection(),
+            None
+        );
+    }
 }
@@ -1,14 +1,15 @@
 //! RPC subscription event-related data structures.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use tendermint::{
     abci::responses::{BeginBlock, EndBlock},
     Block,
 };
 
+use crate::abci_event::{AttributeDecoding, Events};
 use crate::query::EventType;
-use crate::{response::Wrapper, Response};
+use crate::{response::Wrapper, Response, Result};
 
 /// An incoming event produced by a [`Subscription`].
 ///
@@ -20,7 +21,7 @@ pub struct Event {
     /// The data associated with the event.
     pub data: EventData,
     /// Event type and attributes map.
-    pub events: Option<HashMap<String, Vec<String>>>,
+    pub events: Option<BTreeMap<String, Vec<String>>>,
 }
 impl Response for Event {}
 
@@ -77,6 +78,13 @@ pub struct TxResult {
     pub events: Vec<TmEvent>,
 }
 
+impl TxResult {
+    /// Decode the base64-encoded attributes of this transaction's events.
+    pub fn decode_events(&self, decoding: AttributeDecoding) -> Result<Events> {
+        Events::decode(&self.events, decoding)
+    }
+}
+
 /// Tendermint ABCI Events
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TmEvent {
@@ -16,6 +16,19 @@
 //!   client functionality, including general RPC functionality as well as
 //!   [`event::Event`] subscription functionality. Can be used over secure
 //!   (`wss://`) and unsecure (`ws://`) connections.
+//! * `grpc` - Provides [`GrpcClient`], a client for Tendermint's **gRPC
+//!   broadcast API**, which only supports pinging the node and broadcasting
+//!   transactions.
+//! * `prometheus` - Provides [`PrometheusInterceptor`], which records
+//!   per-endpoint request latency and error rates for clients wrapped in an
+//!   [`InstrumentedClient`].
+//!
+//! ## Server
+//!
+//! * `websocket-server` - Provides [`server::WebSocketServer`], a reusable
+//!   JSON-RPC-over-WebSocket server component that implements Tendermint's
+//!   event subscription protocol, for projects that need to expose
+//!   Tendermint-compatible RPC endpoints.
 //!
 //! ### Mock Clients
 //!
@@ -27,18 +40,31 @@
 //! [Tendermint RPC]: https://docs.tendermint.com/master/rpc/
 //! [`/subscribe` endpoint]: https://docs.tendermint.com/master/rpc/#/Websocket/subscribe
 
+pub mod abci_event;
 #[cfg(any(feature = "http-client", feature = "websocket-client"))]
 mod client;
 #[cfg(any(feature = "http-client", feature = "websocket-client"))]
 pub use client::{
-    Client, MockClient, MockRequestMatcher, MockRequestMethodMatcher, Subscription,
-    SubscriptionClient,
+    BackpressurePolicy, BlockStream, BlockWithResults, CacheConfig, CachingClient, Client,
+    CompatMode, DynClient, HeightStream, InstrumentedClient, Interceptor, MockClient,
+    MockRequestMatcher, MockRequestMethodMatcher, Outcome, Subscription, SubscriptionClient,
+    UnsafeClient, WireLog, WireLogConfig, WIRE_LOG_TARGET,
 };
 
+#[cfg(all(
+    feature = "prometheus",
+    any(feature = "http-client", feature = "websocket-client")
+))]
+pub use client::PrometheusInterceptor;
+
+#[cfg(feature = "grpc")]
+pub use client::{GrpcBroadcastTxResponse, GrpcClient};
 #[cfg(feature = "http-client")]
 pub use client::{HttpClient, HttpClientUrl};
 #[cfg(feature = "websocket-client")]
-pub use client::{WebSocketClient, WebSocketClientDriver, WebSocketClientUrl};
+pub use client::{
+    WebSocketClient, WebSocketClientConfig, WebSocketClientDriver, WebSocketClientUrl,
+};
 
 pub mod endpoint;
 pub mod error;
@@ -52,6 +78,8 @@ pub mod request;
 pub mod response;
 mod result;
 mod rpc_url;
+#[cfg(feature = "websocket-server")]
+pub mod server;
 mod utils;
 mod version;
 
@@ -10,7 +10,7 @@ use std::{
 /// JSON-RPC request methods.
 ///
 /// Serialized as the "method" field of JSON-RPC/HTTP requests.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub enum Method {
     /// Get ABCI info
     AbciInfo,
@@ -42,9 +42,21 @@ pub enum Method {
     /// Get consensus state
     ConsensusState,
 
+    /// Dial peers (unsafe)
+    DialPeers,
+
+    /// Dial seeds (unsafe)
+    DialSeeds,
+
     /// Get genesis file
     Genesis,
 
+    /// Get the header of a block
+    Header,
+
+    /// Get the header of a block by its hash
+    HeaderByHash,
+
     /// Get health info
     Health,
 
@@ -71,11 +83,15 @@ pub enum Method {
 
     /// Broadcast evidence
     BroadcastEvidence,
+
+    /// Method with the given name, which this crate doesn't model, e.g. one
+    /// added by a fork of Tendermint
+    Custom(String),
 }
 
 impl Method {
-    /// Get a static string which represents this method name
-    pub fn as_str(self) -> &'static str {
+    /// Get a string which represents this method name
+    pub fn as_str(&self) -> &str {
         match self {
             Method::AbciInfo => "abci_info",
             Method::AbciQuery => "abci_query",
@@ -88,7 +104,11 @@ impl Method {
             Method::BroadcastTxCommit => "broadcast_tx_commit",
             Method::Commit => "commit",
             Method::ConsensusState => "consensus_state",
+            Method::DialPeers => "dial_peers",
+            Method::DialSeeds => "dial_seeds",
             Method::Genesis => "genesis",
+            Method::Header => "header",
+            Method::HeaderByHash => "header_by_hash",
             Method::Health => "health",
             Method::NetInfo => "net_info",
             Method::Status => "status",
@@ -97,6 +117,7 @@ impl Method {
             Method::TxSearch => "tx_search",
             Method::Unsubscribe => "unsubscribe",
             Method::Validators => "validators",
+            Method::Custom(name) => name,
         }
     }
 }
@@ -117,7 +138,11 @@ impl FromStr for Method {
             "broadcast_tx_commit" => Method::BroadcastTxCommit,
             "commit" => Method::Commit,
             "consensus_state" => Method::ConsensusState,
+            "dial_peers" => Method::DialPeers,
+            "dial_seeds" => Method::DialSeeds,
             "genesis" => Method::Genesis,
+            "header" => Method::Header,
+            "header_by_hash" => Method::HeaderByHash,
             "health" => Method::Health,
             "net_info" => Method::NetInfo,
             "status" => Method::Status,
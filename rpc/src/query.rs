@@ -8,9 +8,12 @@
 #![allow(clippy::redundant_closure_call, clippy::unit_arg)]
 
 use crate::{Error, Result};
-use chrono::{Date, DateTime, FixedOffset, Utc};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::str::FromStr;
+use tendermint::abci::event::query as event_query;
+
+pub use tendermint::abci::event::query::{Condition, Operand};
 
 /// A structured query for use in interacting with the Tendermint RPC event
 /// subscription system.
@@ -152,6 +155,82 @@ impl Query {
         self.conditions.push(Condition::Exists(key.to_string()));
         self
     }
+
+    /// Does this query match the given map from composite event keys to
+    /// their values, as delivered along with subscription events (see
+    /// [`Event::events`])?
+    ///
+    /// This allows for filtering events client-side in the same way as the
+    /// node does for subscriptions.
+    ///
+    /// [`Event::events`]: ../event/struct.Event.html#structfield.events
+    pub fn matches(&self, events: &BTreeMap<String, Vec<String>>) -> bool {
+        let event_type_matches = match &self.event_type {
+            Some(event_type) => {
+                Condition::Eq(EVENT_TYPE_KEY.to_owned(), event_type.to_string().into())
+                    .matches(events)
+            }
+            None => true,
+        };
+        event_type_matches
+            && self
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(events))
+    }
+}
+
+/// The composite key under which nodes report the type of an event
+const EVENT_TYPE_KEY: &str = "tm.event";
+
+/// Parses a query, as understood by Tendermint nodes.
+///
+/// ## Examples
+///
+/// ```rust
+/// use tendermint_rpc::query::{Query, EventType};
+///
+/// let query: Query = "tm.event = 'Tx' AND tx.height >= 100".parse().unwrap();
+/// assert_eq!(query, Query::from(EventType::Tx).and_gte("tx.height", 100_u64));
+/// ```
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut conditions: Vec<Condition> = s
+            .parse::<event_query::Query>()
+            .map_err(|e| Error::invalid_params(&e.to_string()))?
+            .into();
+
+        let event_type = conditions.iter().position(|condition| match condition {
+            Condition::Eq(key, Operand::String(value)) => {
+                key == EVENT_TYPE_KEY && value.parse::<EventType>().is_ok()
+            }
+            _ => false,
+        });
+        let event_type = match event_type.map(|i| conditions.remove(i)) {
+            Some(Condition::Eq(_, Operand::String(value))) => Some(value.parse()?),
+            _ => None,
+        };
+
+        Ok(Self {
+            event_type,
+            conditions,
+        })
+    }
+}
+
+impl From<Query> for event_query::Query {
+    fn from(query: Query) -> Self {
+        let mut conditions = query.conditions;
+        if let Some(event_type) = query.event_type {
+            conditions.insert(
+                0,
+                Condition::Eq(EVENT_TYPE_KEY.to_owned(), event_type.to_string().into()),
+            );
+        }
+        event_query::Query::new(conditions)
+    }
 }
 
 impl Default for Query {
@@ -240,190 +319,10 @@ impl FromStr for EventType {
     }
 }
 
-/// The different types of conditions supported by a [`Query`].
-///
-/// [`Query`]: struct.Query.html
-#[derive(Debug, Clone, PartialEq)]
-pub enum Condition {
-    /// Equals
-    Eq(String, Operand),
-    /// Less than
-    Lt(String, Operand),
-    /// Less than or equal to
-    Lte(String, Operand),
-    /// Greater than
-    Gt(String, Operand),
-    /// Greater than or equal to
-    Gte(String, Operand),
-    /// Contains (to check if a key contains a certain sub-string)
-    Contains(String, String),
-    /// Exists (to check if a key exists)
-    Exists(String),
-}
-
-impl fmt::Display for Condition {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Condition::Eq(key, op) => write!(f, "{} = {}", key, op),
-            Condition::Lt(key, op) => write!(f, "{} < {}", key, op),
-            Condition::Lte(key, op) => write!(f, "{} <= {}", key, op),
-            Condition::Gt(key, op) => write!(f, "{} > {}", key, op),
-            Condition::Gte(key, op) => write!(f, "{} >= {}", key, op),
-            Condition::Contains(key, op) => write!(f, "{} CONTAINS {}", key, escape(op)),
-            Condition::Exists(key) => write!(f, "{} EXISTS", key),
-        }
-    }
-}
-
-/// A typed operand for use in an [`Condition`].
-///
-/// According to the [Tendermint RPC subscribe docs][tm-subscribe],
-/// an operand can be a string, number, date or time. We differentiate here
-/// between integer and floating point numbers.
-///
-/// [`Condition`]: enum.Condition.html
-/// [tm-subscribe]: https://docs.tendermint.com/master/rpc/#/Websocket/subscribe
-#[derive(Debug, Clone, PartialEq)]
-pub enum Operand {
-    String(String),
-    Signed(i64),
-    Unsigned(u64),
-    Float(f64),
-    Date(Date<Utc>),
-    DateTime(DateTime<Utc>),
-}
-
-impl fmt::Display for Operand {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Operand::String(s) => write!(f, "{}", escape(s)),
-            Operand::Signed(i) => write!(f, "{}", i),
-            Operand::Unsigned(u) => write!(f, "{}", u),
-            Operand::Float(h) => write!(f, "{}", h),
-            Operand::Date(d) => write!(f, "DATE {}", d.format("%Y-%m-%d").to_string()),
-            Operand::DateTime(dt) => write!(f, "TIME {}", dt.to_rfc3339()),
-        }
-    }
-}
-
-impl From<String> for Operand {
-    fn from(source: String) -> Self {
-        Operand::String(source)
-    }
-}
-
-impl From<char> for Operand {
-    fn from(source: char) -> Self {
-        Operand::String(source.to_string())
-    }
-}
-
-impl From<&str> for Operand {
-    fn from(source: &str) -> Self {
-        Operand::String(source.to_string())
-    }
-}
-
-impl From<i64> for Operand {
-    fn from(source: i64) -> Self {
-        Operand::Signed(source)
-    }
-}
-
-impl From<i32> for Operand {
-    fn from(source: i32) -> Self {
-        Operand::Signed(source as i64)
-    }
-}
-
-impl From<i16> for Operand {
-    fn from(source: i16) -> Self {
-        Operand::Signed(source as i64)
-    }
-}
-
-impl From<i8> for Operand {
-    fn from(source: i8) -> Self {
-        Operand::Signed(source as i64)
-    }
-}
-
-impl From<u64> for Operand {
-    fn from(source: u64) -> Self {
-        Operand::Unsigned(source)
-    }
-}
-
-impl From<u32> for Operand {
-    fn from(source: u32) -> Self {
-        Operand::Unsigned(source as u64)
-    }
-}
-
-impl From<u16> for Operand {
-    fn from(source: u16) -> Self {
-        Operand::Unsigned(source as u64)
-    }
-}
-
-impl From<u8> for Operand {
-    fn from(source: u8) -> Self {
-        Operand::Unsigned(source as u64)
-    }
-}
-
-impl From<usize> for Operand {
-    fn from(source: usize) -> Self {
-        Operand::Unsigned(source as u64)
-    }
-}
-
-impl From<f64> for Operand {
-    fn from(source: f64) -> Self {
-        Operand::Float(source)
-    }
-}
-
-impl From<f32> for Operand {
-    fn from(source: f32) -> Self {
-        Operand::Float(source as f64)
-    }
-}
-
-impl From<Date<Utc>> for Operand {
-    fn from(source: Date<Utc>) -> Self {
-        Operand::Date(source)
-    }
-}
-
-impl From<DateTime<Utc>> for Operand {
-    fn from(source: DateTime<Utc>) -> Self {
-        Operand::DateTime(source)
-    }
-}
-
-impl From<DateTime<FixedOffset>> for Operand {
-    fn from(source: DateTime<FixedOffset>) -> Self {
-        Operand::DateTime(source.into())
-    }
-}
-
-/// Escape backslashes and single quotes within the given string with a backslash.
-fn escape(s: &str) -> String {
-    let mut result = String::new();
-    for ch in s.chars() {
-        if ch == '\\' || ch == '\'' {
-            result.push('\\');
-        }
-        result.push(ch);
-    }
-    format!("'{}'", result)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
-    use chrono::NaiveDate;
+    use chrono::{Date, DateTime, NaiveDate, Utc};
 
     #[test]
     fn empty_query() {
@@ -500,6 +399,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_query() {
+        let query: Query = "tm.event = 'Tx' AND tx.height <= 100 AND transfer.sender = 'AddrA'"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            query,
+            Query::from(EventType::Tx)
+                .and_lte("tx.height", 100_u64)
+                .and_eq("transfer.sender", "AddrA")
+        );
+
+        let query: Query = "tm.event = 'Unknown'".parse().unwrap();
+        assert_eq!(query, Query::eq("tm.event", "Unknown"));
+
+        assert!("tx.height <= ".parse::<Query>().is_err());
+    }
+
+    #[test]
+    fn matches_events() {
+        let mut events = BTreeMap::new();
+        events.insert("tm.event".to_owned(), vec!["Tx".to_owned()]);
+        events.insert("tx.height".to_owned(), vec!["42".to_owned()]);
+        events.insert(
+            "transfer.sender".to_owned(),
+            vec!["AddrA".to_owned(), "AddrB".to_owned()],
+        );
+
+        assert!(Query::default().matches(&events));
+        assert!(Query::from(EventType::Tx)
+            .and_gt("tx.height", 10_u64)
+            .and_eq("transfer.sender", "AddrB")
+            .matches(&events));
+        assert!(!Query::from(EventType::NewBlock).matches(&events));
+        assert!(!Query::from(EventType::Tx)
+            .and_exists("transfer.recipient")
+            .matches(&events));
+    }
+
     #[test]
     fn complex_query() {
         let query = Query::from(EventType::Tx).and_eq("tx.height", 3_i64);
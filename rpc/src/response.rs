@@ -67,8 +67,8 @@ where
         }
     }
 
-    #[cfg(test)]
-    pub fn new_with_id(id: Id, result: Option<R>, error: Option<Error>) -> Self {
+    #[cfg(any(feature = "websocket-server", all(test, feature = "websocket-client")))]
+    pub(crate) fn new_with_id(id: Id, result: Option<R>, error: Option<Error>) -> Self {
         Self {
             jsonrpc: Version::current(),
             id,
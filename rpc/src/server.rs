@@ -0,0 +1,13 @@
+//! Tendermint RPC server components.
+//!
+//! At present this only provides a JSON-RPC-over-WebSocket server that
+//! implements Tendermint's event subscription protocol, for use by projects
+//! that need to expose Tendermint-compatible RPC endpoints (e.g. light nodes,
+//! simulators and proxies).
+
+mod websocket;
+
+pub use websocket::{
+    NoopRequestHandler, RequestHandler, WebSocketServer, WebSocketServerConfig,
+    WebSocketServerDriver,
+};
@@ -0,0 +1,662 @@
+//! JSON-RPC-over-WebSocket server implementing Tendermint's event
+//! subscription protocol.
+
+use crate::endpoint::{subscribe, unsubscribe};
+use crate::error::Code;
+use crate::event::Event;
+use crate::{response, Error, Id, Method, Response, Result};
+use async_trait::async_trait;
+use async_tungstenite::tokio::{accept_async, TokioAdapter};
+use async_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use async_tungstenite::tungstenite::protocol::CloseFrame;
+use async_tungstenite::tungstenite::Message;
+use async_tungstenite::WebSocketStream;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::ops::Add;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant};
+use tracing::{debug, error, info};
+
+// Matches the default `max_open_connections` setting of the Tendermint RPC
+// server.
+const DEFAULT_MAX_CONNECTIONS: usize = 900;
+
+// Matches the default `max_subscriptions_per_client` setting of the Tendermint
+// RPC server.
+const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 5;
+
+// How frequently we send ping messages to connected clients.
+//
+// Taken from https://github.com/tendermint/tendermint/blob/309e29c245a01825fc9630103311fd04de99fa5e/rpc/jsonrpc/server/ws_handler.go#L28
+const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(27);
+
+// How long a client has to complete the WebSocket handshake.
+//
+// Matches the default `ReadTimeout` of the Tendermint RPC server's HTTP
+// listener.
+const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Delays before accepting again after a failure, doubling from the first to
+// the last with each consecutive failure, as done by Go's `net/http`.
+const MIN_ACCEPT_BACKOFF: Duration = Duration::from_millis(5);
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Handles all incoming JSON-RPC requests that are not related to event
+/// subscription (i.e. everything other than `subscribe` and `unsubscribe`).
+///
+/// Parameters and results are passed through as raw JSON values, leaving it up
+/// to the implementation to decide how to (de)serialize them.
+#[async_trait]
+pub trait RequestHandler: Send + Sync + 'static {
+    /// Produce the JSON result for the given method and its parameters.
+    async fn handle(&self, method: Method, params: serde_json::Value) -> Result<serde_json::Value>;
+}
+
+/// A [`RequestHandler`] that rejects every request it receives, for servers
+/// that exclusively provide event subscription functionality.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopRequestHandler;
+
+#[async_trait]
+impl RequestHandler for NoopRequestHandler {
+    async fn handle(
+        &self,
+        method: Method,
+        _params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        Err(Error::method_not_found(method.as_str()))
+    }
+}
+
+/// Configuration parameters for a [`WebSocketServer`].
+#[derive(Debug, Clone)]
+pub struct WebSocketServerConfig {
+    /// The maximum number of simultaneously connected clients. Connections
+    /// beyond this limit are dropped immediately.
+    pub max_connections: usize,
+    /// The maximum number of subscriptions a single client connection may
+    /// hold.
+    pub max_subscriptions_per_connection: usize,
+    /// How frequently to send PING messages to connected clients.
+    pub ping_interval: Duration,
+    /// How long a client has to complete the WebSocket handshake before its
+    /// connection is dropped.
+    pub handshake_timeout: Duration,
+}
+
+impl Default for WebSocketServerConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            max_subscriptions_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            ping_interval: DEFAULT_PING_INTERVAL,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+        }
+    }
+}
+
+/// A JSON-RPC-over-WebSocket server that speaks Tendermint's event
+/// subscription protocol.
+///
+/// Much like the [`crate::WebSocketClient`], the `WebSocketServer` is just a
+/// handle to its driver, which is responsible for accepting incoming
+/// connections, tracking each connection's subscriptions and routing
+/// published [`Event`]s to the relevant subscribers. It is the caller's
+/// responsibility to spawn an asynchronous task in which to execute the
+/// [`WebSocketServerDriver::run`] method.
+///
+/// Events are routed to subscribers whose subscription query exactly matches
+/// the event's [`Event::query`] field.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::server::WebSocketServer;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (server, driver) = WebSocketServer::new("127.0.0.1:26657").await.unwrap();
+///     let driver_hdl = tokio::spawn(async move { driver.run().await });
+///
+///     // Publish events as they are produced by your application, e.g.
+///     // server.publish(event).unwrap();
+///
+///     server.terminate().unwrap();
+///     driver_hdl.await.unwrap().unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WebSocketServer {
+    local_addr: SocketAddr,
+    cmd_tx: mpsc::UnboundedSender<ServerCommand>,
+}
+
+impl WebSocketServer {
+    /// Bind a subscription-only WebSocket server to the given address, using
+    /// the default configuration.
+    pub async fn new<A>(addr: A) -> Result<(Self, WebSocketServerDriver)>
+    where
+        A: ToSocketAddrs,
+    {
+        Self::new_with_handler(addr, WebSocketServerConfig::default(), NoopRequestHandler).await
+    }
+
+    /// Bind a WebSocket server to the given address, delegating all
+    /// non-subscription requests to the given handler.
+    pub async fn new_with_handler<A, H>(
+        addr: A,
+        config: WebSocketServerConfig,
+        handler: H,
+    ) -> Result<(Self, WebSocketServerDriver)>
+    where
+        A: ToSocketAddrs,
+        H: RequestHandler,
+    {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        debug!("WebSocket server listening on {}", local_addr);
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let driver = WebSocketServerDriver {
+            listener,
+            config,
+            handler: Arc::new(handler),
+            cmd_rx,
+            connections: Vec::new(),
+        };
+        Ok((Self { local_addr, cmd_tx }, driver))
+    }
+
+    /// The address to which the server is bound.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Publish the given event to all clients subscribed to the event's query.
+    pub fn publish(&self, ev: Event) -> Result<()> {
+        self.send_cmd(ServerCommand::Publish(Box::new(ev)))
+    }
+
+    /// Signals to the driver that it must close all client connections and
+    /// terminate.
+    pub fn terminate(self) -> Result<()> {
+        self.send_cmd(ServerCommand::Terminate)
+    }
+
+    fn send_cmd(&self, cmd: ServerCommand) -> Result<()> {
+        self.cmd_tx.send(cmd).map_err(|e| {
+            Error::client_internal_error(format!("failed to send command to server driver: {}", e))
+        })
+    }
+}
+
+#[derive(Debug)]
+enum ServerCommand {
+    Publish(Box<Event>),
+    Terminate,
+}
+
+/// Drives a [`WebSocketServer`], accepting incoming connections and routing
+/// events to them.
+pub struct WebSocketServerDriver {
+    listener: TcpListener,
+    config: WebSocketServerConfig,
+    handler: Arc<dyn RequestHandler>,
+    cmd_rx: mpsc::UnboundedReceiver<ServerCommand>,
+    connections: Vec<ConnectionHandle>,
+}
+
+impl WebSocketServerDriver {
+    /// Executes the server driver until the server is terminated.
+    pub async fn run(mut self) -> Result<()> {
+        // Failing to accept a connection (e.g. because we have run out of file
+        // descriptors) usually persists for a while, so we back off instead
+        // of retrying straight away.
+        let mut backoff: Option<Duration> = None;
+        let mut accept_at = Instant::now();
+        loop {
+            let listener = &self.listener;
+            tokio::select! {
+                res = async move {
+                    tokio::time::sleep_until(accept_at).await;
+                    listener.accept().await
+                } => match res {
+                    Ok((stream, peer_addr)) => {
+                        backoff = None;
+                        self.accept(stream, peer_addr);
+                    }
+                    Err(e) => {
+                        let delay = backoff
+                            .map_or(MIN_ACCEPT_BACKOFF, |delay| (delay * 2).min(MAX_ACCEPT_BACKOFF));
+                        error!(
+                            "Failed to accept incoming connection, retrying in {:?}: {}",
+                            delay, e
+                        );
+                        backoff = Some(delay);
+                        accept_at = Instant::now().add(delay);
+                    }
+                },
+                cmd = self.cmd_rx.recv() => match cmd {
+                    Some(ServerCommand::Publish(ev)) => self.publish(*ev),
+                    Some(ServerCommand::Terminate) | None => return self.terminate().await,
+                },
+            }
+        }
+    }
+
+    fn accept(&mut self, stream: TcpStream, peer_addr: SocketAddr) {
+        // Forget about connections that have already been closed.
+        self.connections.retain(|conn| !conn.is_closed());
+        if self.connections.len() >= self.config.max_connections {
+            info!(
+                "Rejecting connection from {}: maximum of {} connections reached",
+                peer_addr, self.config.max_connections
+            );
+            return;
+        }
+        debug!("Accepted connection from {}", peer_addr);
+        self.connections.push(ConnectionHandle::spawn(
+            stream,
+            peer_addr,
+            self.config.clone(),
+            self.handler.clone(),
+        ));
+    }
+
+    fn publish(&mut self, ev: Event) {
+        // We assume here that any failure to publish an event is an indication
+        // that the connection has been closed.
+        self.connections
+            .retain(|conn| conn.event_tx.send(ev.clone()).is_ok());
+    }
+
+    async fn terminate(self) -> Result<()> {
+        for conn in self.connections {
+            let _ = conn.terminate_tx.send(());
+            match conn.driver_hdl.await {
+                Ok(Err(e)) => debug!(
+                    "Connection to {} terminated with error: {}",
+                    conn.peer_addr, e
+                ),
+                Err(e) => error!(
+                    "Failed to join connection task for {}: {}",
+                    conn.peer_addr, e
+                ),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+// Interface to the task managing a single client connection.
+struct ConnectionHandle {
+    peer_addr: SocketAddr,
+    event_tx: mpsc::UnboundedSender<Event>,
+    terminate_tx: mpsc::UnboundedSender<()>,
+    driver_hdl: JoinHandle<Result<()>>,
+}
+
+impl ConnectionHandle {
+    fn spawn(
+        stream: TcpStream,
+        peer_addr: SocketAddr,
+        config: WebSocketServerConfig,
+        handler: Arc<dyn RequestHandler>,
+    ) -> Self {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let (terminate_tx, mut terminate_rx) = mpsc::unbounded_channel();
+        let driver_hdl = tokio::spawn(async move {
+            // The server must be able to terminate while clients are still
+            // (or never stop) performing their handshake.
+            let conn = tokio::select! {
+                res = tokio::time::timeout(config.handshake_timeout, accept_async(stream)) => {
+                    match res {
+                        Ok(res) => res.map_err(|e| {
+                            Error::websocket_error(format!(
+                                "WebSocket handshake with {} failed: {}",
+                                peer_addr, e
+                            ))
+                        })?,
+                        Err(_) => {
+                            return Err(Error::websocket_error(format!(
+                                "WebSocket handshake with {} timed out after {:?}",
+                                peer_addr, config.handshake_timeout
+                            )))
+                        }
+                    }
+                }
+                _ = terminate_rx.recv() => return Ok(()),
+            };
+            ConnectionDriver {
+                conn,
+                config,
+                handler,
+                event_rx,
+                terminate_rx,
+                subscriptions: HashMap::new(),
+            }
+            .run()
+            .await
+        });
+        Self {
+            peer_addr,
+            event_tx,
+            terminate_tx,
+            driver_hdl,
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.event_tx.is_closed()
+    }
+}
+
+// Manages interaction with a single client connection.
+struct ConnectionDriver {
+    conn: WebSocketStream<TokioAdapter<TcpStream>>,
+    config: WebSocketServerConfig,
+    handler: Arc<dyn RequestHandler>,
+    event_rx: mpsc::UnboundedReceiver<Event>,
+    terminate_rx: mpsc::UnboundedReceiver<()>,
+    // Subscription queries mapped to the JSON-RPC ID of the request that
+    // initiated the subscription. Events are sent using this ID.
+    subscriptions: HashMap<String, Id>,
+}
+
+impl ConnectionDriver {
+    async fn run(mut self) -> Result<()> {
+        let ping_interval = self.config.ping_interval;
+        let mut ping_interval =
+            tokio::time::interval_at(Instant::now().add(ping_interval), ping_interval);
+
+        loop {
+            tokio::select! {
+                msg = self.conn.next() => match msg {
+                    Some(Ok(msg)) => {
+                        if self.handle_incoming_msg(msg).await? {
+                            return Ok(());
+                        }
+                    }
+                    Some(Err(e)) => return Err(Error::websocket_error(
+                        format!("failed to read from WebSocket connection: {}", e),
+                    )),
+                    None => return Ok(()),
+                },
+                Some(ev) = self.event_rx.recv() => self.publish_event(ev).await?,
+                _ = self.terminate_rx.recv() => return self.close().await,
+                _ = ping_interval.tick() => self.send_msg(Message::Ping(Vec::new())).await?,
+            }
+        }
+    }
+
+    // Returns `true` if the remote endpoint closed the connection.
+    async fn handle_incoming_msg(&mut self, msg: Message) -> Result<bool> {
+        match msg {
+            Message::Text(s) => self.handle_text_msg(s).await.map(|_| false),
+            Message::Ping(v) => self.send_msg(Message::Pong(v)).await.map(|_| false),
+            Message::Close(_) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    async fn handle_text_msg(&mut self, msg: String) -> Result<()> {
+        let req = match serde_json::from_str::<IncomingRequest>(&msg) {
+            Ok(req) => req,
+            Err(e) => return self.send_error(Id::None, Error::parse_error(e)).await,
+        };
+        let id = req.id.unwrap_or(Id::None);
+        let method = match Method::from_str(&req.method) {
+            Ok(method) => method,
+            Err(e) => return self.send_error(id, e).await,
+        };
+        match method {
+            Method::Subscribe => self.subscribe(id, req.params).await,
+            Method::Unsubscribe => self.unsubscribe(id, req.params).await,
+            _ => match self.handler.handle(method, req.params).await {
+                Ok(result) => self.send_result(id, JsonResult(result)).await,
+                Err(e) => self.send_error(id, e).await,
+            },
+        }
+    }
+
+    async fn subscribe(&mut self, id: Id, params: serde_json::Value) -> Result<()> {
+        let params = match serde_json::from_value::<subscribe::Request>(params) {
+            Ok(params) => params,
+            Err(e) => {
+                return self
+                    .send_error(id, Error::invalid_params(&e.to_string()))
+                    .await
+            }
+        };
+        if self.subscriptions.contains_key(&params.query) {
+            return self
+                .send_error(
+                    id,
+                    Error::new(Code::InternalError, Some("already subscribed".into())),
+                )
+                .await;
+        }
+        if self.subscriptions.len() >= self.config.max_subscriptions_per_connection {
+            return self
+                .send_error(
+                    id,
+                    Error::new(
+                        Code::InternalError,
+                        Some(format!(
+                            "max_subscriptions_per_client {} reached",
+                            self.config.max_subscriptions_per_connection
+                        )),
+                    ),
+                )
+                .await;
+        }
+        debug!(
+            "Adding subscription with ID {} for query: {}",
+            id, params.query
+        );
+        self.subscriptions.insert(params.query, id.clone());
+        self.send_result(id, subscribe::Response {}).await
+    }
+
+    async fn unsubscribe(&mut self, id: Id, params: serde_json::Value) -> Result<()> {
+        let params = match serde_json::from_value::<unsubscribe::Request>(params) {
+            Ok(params) => params,
+            Err(e) => {
+                return self
+                    .send_error(id, Error::invalid_params(&e.to_string()))
+                    .await
+            }
+        };
+        if self.subscriptions.remove(&params.query).is_none() {
+            return self
+                .send_error(
+                    id,
+                    Error::new(Code::InternalError, Some("subscription not found".into())),
+                )
+                .await;
+        }
+        debug!("Removed subscription for query: {}", params.query);
+        self.send_result(id, unsubscribe::Response {}).await
+    }
+
+    async fn publish_event(&mut self, ev: Event) -> Result<()> {
+        match self.subscriptions.get(&ev.query) {
+            Some(id) => {
+                let id = id.clone();
+                self.send_result(id, ev).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn send_result<R>(&mut self, id: Id, result: R) -> Result<()>
+    where
+        R: Response,
+    {
+        self.send_wrapper(response::Wrapper::new_with_id(id, Some(result), None))
+            .await
+    }
+
+    async fn send_error(&mut self, id: Id, error: Error) -> Result<()> {
+        self.send_wrapper(response::Wrapper::<JsonResult>::new_with_id(
+            id,
+            None,
+            Some(error),
+        ))
+        .await
+    }
+
+    async fn send_wrapper<R>(&mut self, wrapper: response::Wrapper<R>) -> Result<()>
+    where
+        R: Response,
+    {
+        let msg = serde_json::to_string(&wrapper).map_err(|e| {
+            Error::client_internal_error(format!("failed to serialize response: {}", e))
+        })?;
+        self.send_msg(Message::Text(msg)).await
+    }
+
+    async fn send_msg(&mut self, msg: Message) -> Result<()> {
+        self.conn.send(msg).await.map_err(|e| {
+            Error::websocket_error(format!("failed to write to WebSocket connection: {}", e))
+        })
+    }
+
+    async fn close(mut self) -> Result<()> {
+        self.send_msg(Message::Close(Some(CloseFrame {
+            code: CloseCode::Normal,
+            reason: Cow::from("server closed WebSocket connection"),
+        })))
+        .await
+    }
+}
+
+// The generic form of an incoming JSON-RPC request.
+#[derive(Deserialize)]
+struct IncomingRequest {
+    id: Option<Id>,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+struct JsonResult(serde_json::Value);
+
+impl Response for JsonResult {}
+
+#[cfg(all(test, feature = "websocket-client"))]
+mod test {
+    use super::*;
+    use crate::query::EventType;
+    use crate::{Client, SubscriptionClient, WebSocketClient};
+    use std::path::PathBuf;
+    use tokio::fs;
+
+    struct HealthHandler;
+
+    #[async_trait]
+    impl RequestHandler for HealthHandler {
+        async fn handle(
+            &self,
+            method: Method,
+            _params: serde_json::Value,
+        ) -> Result<serde_json::Value> {
+            match method {
+                Method::Health => Ok(serde_json::json!({})),
+                _ => Err(Error::method_not_found(method.as_str())),
+            }
+        }
+    }
+
+    async fn read_event(name: &str) -> Event {
+        let fixture =
+            fs::read_to_string(PathBuf::from("./tests/support/").join(name.to_owned() + ".json"))
+                .await
+                .unwrap();
+        Event::from_string(&fixture).unwrap()
+    }
+
+    #[tokio::test]
+    async fn websocket_server_routes_events_and_requests() {
+        let (server, driver) = WebSocketServer::new_with_handler(
+            "127.0.0.1:0",
+            WebSocketServerConfig::default(),
+            HealthHandler,
+        )
+        .await
+        .unwrap();
+        let server_hdl = tokio::spawn(async move { driver.run().await });
+
+        let url = format!("ws://{}/websocket", server.local_addr());
+        let (client, driver) = WebSocketClient::new(url.as_str()).await.unwrap();
+        let client_hdl = tokio::spawn(async move { driver.run().await });
+
+        client.health().await.unwrap();
+        assert!(client.status().await.is_err());
+
+        let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+        let ev = read_event("event_new_block_1").await;
+        server.publish(ev.clone()).unwrap();
+        assert_eq!(ev, subs.next().await.unwrap().unwrap());
+
+        client.close().unwrap();
+        let _ = client_hdl.await.unwrap();
+        server.terminate().unwrap();
+        server_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_server_terminates_during_handshake() {
+        let (server, driver) = WebSocketServer::new("127.0.0.1:0").await.unwrap();
+        let server_hdl = tokio::spawn(async move { driver.run().await });
+
+        // Connect without ever sending the handshake request.
+        let _stream = TcpStream::connect(server.local_addr()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        server.terminate().unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server_hdl)
+            .await
+            .expect("server did not terminate")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_server_drops_stalled_handshakes() {
+        let config = WebSocketServerConfig {
+            max_connections: 1,
+            handshake_timeout: Duration::from_millis(100),
+            ..WebSocketServerConfig::default()
+        };
+        let (server, driver) =
+            WebSocketServer::new_with_handler("127.0.0.1:0", config, HealthHandler)
+                .await
+                .unwrap();
+        let server_hdl = tokio::spawn(async move { driver.run().await });
+
+        // The stalled connection must not keep holding the only slot.
+        let _stream = TcpStream::connect(server.local_addr()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let url = format!("ws://{}/websocket", server.local_addr());
+        let (client, driver) = WebSocketClient::new(url.as_str()).await.unwrap();
+        let client_hdl = tokio::spawn(async move { driver.run().await });
+        client.health().await.unwrap();
+
+        client.close().unwrap();
+        let _ = client_hdl.await.unwrap();
+        server.terminate().unwrap();
+        server_hdl.await.unwrap().unwrap();
+    }
+}
@@ -123,11 +123,8 @@ fn block_results() {
     assert_eq!(deliver_tx[0].gas_used.value(), 105_662);
     assert_eq!(deliver_tx[0].events.len(), 1);
     assert_eq!(deliver_tx[0].events[0].attributes.len(), 3);
-    assert_eq!(deliver_tx[0].events[0].attributes[0].key.as_ref(), "action");
-    assert_eq!(
-        deliver_tx[0].events[0].attributes[0].value.as_ref(),
-        "delegate"
-    );
+    assert_eq!(deliver_tx[0].events[0].attributes[0].key, "action");
+    assert_eq!(deliver_tx[0].events[0].attributes[0].value, "delegate");
 
     assert_eq!(validator_updates[0].power.value(), 1_233_243);
 }
@@ -242,6 +239,26 @@ fn commit_height_1() {
     assert_eq!(header.hash(), block_id.hash);
 }
 
+#[test]
+fn dial_peers() {
+    let response =
+        endpoint::dial_peers::Response::from_string(&read_json_fixture("dial_peers")).unwrap();
+
+    assert_eq!(
+        "Dialing peers in progress. See /net_info for details",
+        response.log
+    );
+}
+
+#[test]
+fn header() {
+    let response = endpoint::header::Response::from_string(&read_json_fixture("header")).unwrap();
+
+    assert_eq!(response.header.version.block, 10);
+    assert_eq!(response.header.chain_id.as_str(), EXAMPLE_CHAIN);
+    assert_eq!(response.header.height.value(), 10);
+}
+
 #[test]
 fn genesis() {
     let response = endpoint::genesis::Response::from_string(&read_json_fixture("genesis")).unwrap();
@@ -290,6 +307,34 @@ fn validators() {
     assert_eq!(validators.len(), 65);
 }
 
+#[test]
+fn broadcast_evidence() {
+    let response =
+        endpoint::evidence::Response::from_string(&read_json_fixture("broadcast_evidence"))
+            .unwrap();
+
+    assert_eq!(
+        "8C0C0F5B3E1A2F2C6F8E2A7B4D9E1F3A5C6B7D8E9F0A1B2C3D4E5F60718293A4",
+        response.hash.to_string()
+    );
+}
+
+#[test]
+fn broadcast_evidence_expired() {
+    let result =
+        endpoint::evidence::Response::from_string(&read_json_fixture("broadcast_evidence_expired"));
+
+    if let Err(err) = result {
+        assert_eq!(err.code(), rpc::error::Code::InternalError);
+        assert_eq!(
+            err.evidence_rejection(),
+            Some(rpc::error::EvidenceRejection::Expired)
+        );
+    } else {
+        panic!("expected error, got {:?}", result)
+    }
+}
+
 #[test]
 fn jsonrpc_error() {
     let result = endpoint::blockchain::Response::from_string(&read_json_fixture("error"));
@@ -319,9 +364,9 @@ fn tx_no_prove() {
     let events = &tx.tx_result.events;
     assert_eq!(events.len(), 6);
     assert_eq!(events[0].attributes.len(), 3);
-    assert_eq!(events[0].attributes[0].key.as_ref(), "recipient");
+    assert_eq!(events[0].attributes[0].key, "recipient");
     assert_eq!(
-        events[0].attributes[0].value.as_ref(),
+        events[0].attributes[0].value,
         "cosmos17xpfvakm2amg962yls6f84z3kell8c5lserqta"
     );
 
@@ -341,9 +386,9 @@ fn tx_with_prove() {
     let events = &tx.tx_result.events;
     assert_eq!(events.len(), 6);
     assert_eq!(events[0].attributes.len(), 3);
-    assert_eq!(events[0].attributes[0].key.as_ref(), "recipient");
+    assert_eq!(events[0].attributes[0].key, "recipient");
     assert_eq!(
-        events[0].attributes[0].value.as_ref(),
+        events[0].attributes[0].value,
         "cosmos17xpfvakm2amg962yls6f84z3kell8c5lserqta"
     );
 
@@ -380,6 +425,50 @@ fn tx_with_prove() {
     );
 }
 
+#[test]
+fn tx_with_prove_verify_proof() {
+    use tendermint::hash::{Algorithm, Hash};
+    use tendermint_rpc::endpoint::tx::ProofError;
+
+    let tx = endpoint::tx::Response::from_string(&read_json_fixture("tx_with_prove")).unwrap();
+    let data_hash = Hash::from_hex_upper(
+        Algorithm::Sha256,
+        "69C402D84BC672506F1B3611046B8B25289C2600FD7A007689C5949A33206557",
+    )
+    .unwrap();
+    tx.verify_proof(&data_hash).unwrap();
+
+    let other_hash = Hash::from_hex_upper(
+        Algorithm::Sha256,
+        "291B44C883803751917D547238EAC419E968C0171A3154D777B2EA8EA5039C57",
+    )
+    .unwrap();
+    assert_eq!(
+        Err(ProofError::RootHashMismatch),
+        tx.verify_proof(&other_hash)
+    );
+
+    let mut tampered = tx.clone();
+    tampered
+        .proof
+        .as_mut()
+        .unwrap()
+        .proof
+        .as_mut()
+        .unwrap()
+        .index = 1;
+    assert_eq!(
+        Err(ProofError::InvalidProof),
+        tampered.verify_proof(&data_hash)
+    );
+
+    let no_prove = endpoint::tx::Response::from_string(&read_json_fixture("tx_no_prove")).unwrap();
+    assert_eq!(
+        Err(ProofError::MissingProof),
+        no_prove.verify_proof(&data_hash)
+    );
+}
+
 #[test]
 fn tx_search_no_prove() {
     let response =
@@ -398,8 +487,8 @@ fn tx_search_no_prove() {
     let events = &response.txs[0].tx_result.events;
     assert_eq!(events.len(), 1);
     assert_eq!(events[0].attributes.len(), 4);
-    assert_eq!(events[0].attributes[0].key.as_ref(), "creator");
-    assert_eq!(events[0].attributes[0].value.as_ref(), "Cosmoshi Netowoko");
+    assert_eq!(events[0].attributes[0].key, "creator");
+    assert_eq!(events[0].attributes[0].value, "Cosmoshi Netowoko");
 }
 
 #[test]
@@ -431,8 +520,8 @@ fn tx_search_with_prove() {
     let events = &response.txs[0].tx_result.events;
     assert_eq!(events.len(), 1);
     assert_eq!(events[0].attributes.len(), 4);
-    assert_eq!(events[0].attributes[0].key.as_ref(), "creator");
-    assert_eq!(events[0].attributes[0].value.as_ref(), "Cosmoshi Netowoko");
+    assert_eq!(events[0].attributes[0].key, "creator");
+    assert_eq!(events[0].attributes[0].value, "Cosmoshi Netowoko");
 }
 
 #[test]
@@ -504,3 +593,39 @@ fn consensus_state() {
     }
     assert_eq!(hvs[0].precommits[1], RoundVote::Nil);
 }
+
+#[test]
+fn generated_fixtures() {
+    use tendermint_testgen::rpc::RpcMethod;
+    use tendermint_testgen::{Generator, RpcResponse};
+
+    let fixture = |method| {
+        RpcResponse::new(method)
+            .chain_id(EXAMPLE_CHAIN)
+            .length(5)
+            .height(3)
+            .encode()
+            .unwrap()
+    };
+
+    let block = endpoint::block::Response::from_string(fixture(RpcMethod::Block)).unwrap();
+    let commit = endpoint::commit::Response::from_string(fixture(RpcMethod::Commit)).unwrap();
+    let validators =
+        endpoint::validators::Response::from_string(fixture(RpcMethod::Validators)).unwrap();
+    let status = endpoint::status::Response::from_string(fixture(RpcMethod::Status)).unwrap();
+
+    assert_eq!(block.block.header.chain_id.as_str(), EXAMPLE_CHAIN);
+    assert_eq!(block.block.header.height.value(), 3);
+    assert_eq!(block.block.header, commit.signed_header.header);
+    assert_eq!(block.block_id, commit.signed_header.commit.block_id);
+    assert!(commit.canonical);
+    assert_eq!(validators.block_height.value(), 3);
+    assert_eq!(validators.total, validators.validators.len() as i32);
+    assert_eq!(
+        tendermint::validator::Set::without_proposer(validators.validators).hash(),
+        block.block.header.validators_hash
+    );
+    assert_eq!(status.node_info.network.as_str(), EXAMPLE_CHAIN);
+    assert_eq!(status.sync_info.latest_block_height.value(), 5);
+    assert!(!status.sync_info.catching_up);
+}
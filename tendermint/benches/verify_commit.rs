@@ -0,0 +1,59 @@
+//! Benchmarks for commit verification, the hot path of the light client.
+//!
+//! Run with `cargo bench --features batch-verify` to compare against
+//! verifying signatures one by one (the default).
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use tendermint::block::{self, Commit};
+use tendermint::{chain, private_key, validator, vote, Hash, Time};
+
+fn signing_key(idx: usize) -> private_key::Ed25519 {
+    let mut seed = [0_u8; 32];
+    seed[..8].copy_from_slice(&(idx as u64).to_le_bytes());
+    let secret = ed25519_dalek::SecretKey::from_bytes(&seed).unwrap();
+    private_key::Ed25519 {
+        public: ed25519_dalek::PublicKey::from(&secret),
+        secret,
+    }
+}
+
+// A validator set of the given size, and a commit signed by all of its
+// validators.
+fn signed_commit(chain_id: &chain::Id, size: usize) -> (validator::Set, Commit) {
+    let keys: Vec<_> = (0..size).map(signing_key).collect();
+    let val_set = validator::Set::without_proposer(
+        keys.iter()
+            .map(|key| validator::Info::new(key.public.into(), vote::Power::from(10_u32)))
+            .collect(),
+    );
+
+    let commit = Commit::builder()
+        .height(block::Height::from(5_u32))
+        .block_id(block::Id {
+            hash: Hash::Sha256([1; 32]),
+            part_set_header: block::parts::Header::new(1, Hash::Sha256([2; 32])).unwrap(),
+        })
+        .timestamp(Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap())
+        .sign(chain_id.clone(), &val_set, &keys)
+        .unwrap();
+    (val_set, commit)
+}
+
+fn verify_commit(c: &mut Criterion) {
+    let chain_id: chain::Id = "bench-chain".parse().unwrap();
+    let mut group = c.benchmark_group("verify_commit");
+    for size in [10, 50, 150].iter() {
+        let (val_set, commit) = signed_commit(&chain_id, *size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &commit, |b, commit| {
+            b.iter(|| {
+                val_set
+                    .verify_commit(&chain_id, &commit.block_id, commit.height, commit)
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, verify_commit);
+criterion_main!(benches);
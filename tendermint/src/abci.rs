@@ -1,29 +1,32 @@
 //! Application BlockChain Interface (ABCI)
 //!
-//! NOTE: This module contains types for ABCI responses as consumed from RPC
-//! endpoints. It does not contain an ABCI protocol implementation.
-//!
-//! For that, see:
-//!
-//! <https://github.com/tendermint/rust-abci>
+//! This module contains domain types for the ABCI protocol's requests and
+//! responses (see [`request`] and [`response`]), as well as types for ABCI
+//! responses as consumed from RPC endpoints. It does not contain an ABCI
+//! protocol implementation: for that, see the `tendermint-abci` crate.
 
 mod code;
 mod data;
+pub mod event;
 mod gas;
 mod info;
 mod log;
 mod path;
+pub mod request;
+pub mod response;
 pub mod responses;
 pub mod tag;
 pub mod transaction;
+pub mod types;
 
 pub use self::{
     code::Code,
     data::Data,
+    event::{Event, EventAttribute},
     gas::Gas,
     info::Info,
     log::Log,
     path::Path,
-    responses::{DeliverTx, Event, Responses},
+    responses::{DeliverTx, ExecTxResult, Responses, TxResult},
     transaction::Transaction,
 };
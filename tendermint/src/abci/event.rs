@@ -0,0 +1,182 @@
+//! Events emitted by ABCI applications
+
+pub mod query;
+
+pub use self::query::Query;
+use crate::prelude::*;
+
+use alloc::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::abci::{Event as RawEvent, EventAttribute as RawEventAttribute};
+use tendermint_proto::serializers::bytes::base64string;
+
+/// An event emitted by an ABCI application, e.g. while delivering a
+/// transaction.
+///
+/// Events can be queried by their composite `<type>.<key>` attribute keys.
+/// See [`Query`] for details.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Event {
+    /// Event type
+    #[serde(rename = "type")]
+    pub type_str: String,
+
+    /// Attributes
+    pub attributes: Vec<EventAttribute>,
+}
+
+impl Event {
+    /// Create a new event of the given type
+    pub fn new(type_str: impl Into<String>, attributes: Vec<EventAttribute>) -> Self {
+        Event {
+            type_str: type_str.into(),
+            attributes,
+        }
+    }
+
+    /// The composite `<type>.<key>` keys of this event's attributes, along
+    /// with their values.
+    pub fn composite_attributes(&self) -> impl Iterator<Item = (String, &str)> {
+        self.attributes.iter().map(move |attr| {
+            (
+                format!("{}.{}", self.type_str, attr.key),
+                attr.value.as_str(),
+            )
+        })
+    }
+}
+
+/// Converts the keys and values of the event attributes lossily, since they
+/// are expected to be UTF-8 strings.
+impl From<RawEvent> for Event {
+    fn from(value: RawEvent) -> Self {
+        Event {
+            type_str: value.r#type,
+            attributes: value.attributes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Event> for RawEvent {
+    fn from(value: Event) -> Self {
+        RawEvent {
+            r#type: value.type_str,
+            attributes: value.attributes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A key/value attribute of an [`Event`]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct EventAttribute {
+    /// Key
+    #[serde(
+        serialize_with = "base64string::serialize",
+        deserialize_with = "base64string::deserialize_to_string"
+    )]
+    pub key: String,
+
+    /// Value
+    #[serde(
+        serialize_with = "base64string::serialize",
+        deserialize_with = "base64string::deserialize_to_string"
+    )]
+    pub value: String,
+
+    /// Whether the node should index this attribute
+    #[serde(default)]
+    pub index: bool,
+}
+
+impl EventAttribute {
+    /// Create a new event attribute
+    pub fn new(key: impl Into<String>, value: impl Into<String>, index: bool) -> Self {
+        EventAttribute {
+            key: key.into(),
+            value: value.into(),
+            index,
+        }
+    }
+}
+
+impl From<RawEventAttribute> for EventAttribute {
+    fn from(value: RawEventAttribute) -> Self {
+        EventAttribute {
+            key: String::from_utf8_lossy(&value.key).into_owned(),
+            value: String::from_utf8_lossy(&value.value).into_owned(),
+            index: value.index,
+        }
+    }
+}
+
+impl From<EventAttribute> for RawEventAttribute {
+    fn from(value: EventAttribute) -> Self {
+        RawEventAttribute {
+            key: value.key.into_bytes(),
+            value: value.value.into_bytes(),
+            index: value.index,
+        }
+    }
+}
+
+/// Collect the composite `<type>.<key>` keys of the attributes of the given
+/// events into a map from each key to all of its values, in order.
+///
+/// This is the form in which a node matches events against a [`Query`], and
+/// in which it reports them along with subscription events.
+pub fn composite_map(events: &[Event]) -> BTreeMap<String, Vec<String>> {
+    let mut map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (key, value) in events.iter().flat_map(Event::composite_attributes) {
+        map.entry(key).or_default().push(value.to_string());
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attribute_serde() {
+        let json = r#"{"key": "cGFja2V0X3RpbWVvdXRfaGVpZ2h0", "value": "MC00ODQw", "index": true}"#;
+        let attr: EventAttribute = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            attr,
+            EventAttribute::new("packet_timeout_height", "0-4840", true)
+        );
+
+        // `index` is absent from events returned by older nodes
+        let json = r#"{"key": "a2V5", "value": null}"#;
+        let attr: EventAttribute = serde_json::from_str(json).unwrap();
+        assert_eq!(attr, EventAttribute::new("key", "", false));
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        let event = Event::new(
+            "transfer",
+            vec![
+                EventAttribute::new("sender", "alice", true),
+                EventAttribute::new("amount", "100stake", false),
+            ],
+        );
+        let raw = RawEvent::from(event.clone());
+        assert_eq!(raw.attributes[0].key, b"sender".to_vec());
+        assert!(!raw.attributes[1].index);
+        assert_eq!(Event::from(raw), event);
+    }
+
+    #[test]
+    fn composite_keys() {
+        let events = [
+            Event::new(
+                "transfer",
+                vec![EventAttribute::new("sender", "alice", true)],
+            ),
+            Event::new("transfer", vec![EventAttribute::new("sender", "bob", true)]),
+        ];
+        let map = composite_map(&events);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map["transfer.sender"], vec!["alice", "bob"]);
+    }
+}
@@ -0,0 +1,709 @@
+//! The event query language, as used by the Tendermint RPC event subscription
+//! system and transaction search.
+//!
+//! Queries are conjunctions of conditions on composite `<type>.<key>` event
+//! attribute keys, e.g.:
+//!
+//! ```text
+//! transfer.sender = 'alice' AND tx.height > 5
+//! ```
+//!
+//! See [`Query`] for parsing queries and matching them against events.
+
+use super::{composite_map, Event};
+use crate::prelude::*;
+use crate::{Error, Kind};
+use alloc::collections::BTreeMap;
+use chrono::{Date, DateTime, FixedOffset, NaiveDate, Utc};
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+/// A parsed event query: a conjunction of zero or more [`Condition`]s.
+///
+/// An empty query matches any set of events.
+///
+/// ## Examples
+///
+/// ```rust
+/// use tendermint::abci::event::{Event, EventAttribute, Query};
+///
+/// let query: Query = "transfer.sender = 'alice' AND transfer.amount > 5"
+///     .parse()
+///     .unwrap();
+///
+/// let events = [Event::new(
+///     "transfer",
+///     vec![
+///         EventAttribute::new("sender", "alice", true),
+///         EventAttribute::new("amount", "10stake", true),
+///     ],
+/// )];
+/// assert!(query.matches(&events));
+///
+/// let query: Query = "transfer.sender = 'bob'".parse().unwrap();
+/// assert!(!query.matches(&events));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    conditions: Vec<Condition>,
+}
+
+impl Query {
+    /// Create a query from the given conditions
+    pub fn new(conditions: Vec<Condition>) -> Self {
+        Query { conditions }
+    }
+
+    /// The conditions of this query
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+
+    /// Does this query match the given events?
+    pub fn matches(&self, events: &[Event]) -> bool {
+        self.matches_composite(&composite_map(events))
+    }
+
+    /// Does this query match the given map from composite `<type>.<key>`
+    /// keys to their values?
+    ///
+    /// Nodes match subscriptions against such a map, which in addition to the
+    /// attributes of the ABCI events includes keys such as `tm.event`,
+    /// `tx.hash` and `tx.height`.
+    pub fn matches_composite(&self, events: &BTreeMap<String, Vec<String>>) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(events))
+    }
+}
+
+impl From<Vec<Condition>> for Query {
+    fn from(conditions: Vec<Condition>) -> Self {
+        Query::new(conditions)
+    }
+}
+
+impl From<Query> for Vec<Condition> {
+    fn from(query: Query) -> Self {
+        query.conditions
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, condition) in self.conditions.iter().enumerate() {
+            if i > 0 {
+                write!(f, " AND ")?;
+            }
+            write!(f, "{}", condition)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the query grammar understood by Tendermint nodes.
+///
+/// Numbers without a decimal point are parsed as [`Operand::Unsigned`], or as
+/// [`Operand::Signed`] when negative.
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let s = s.trim();
+        // Nodes accept queries wrapped in double quotes
+        let s = s
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .unwrap_or(s);
+
+        let mut parser = Parser { input: s, pos: 0 };
+        let mut conditions = Vec::new();
+
+        parser.skip_whitespace();
+        while !parser.is_done() {
+            if !conditions.is_empty() && !parser.keyword("AND") {
+                return Err(parser.error("expected AND"));
+            }
+            conditions.push(parser.condition()?);
+            parser.skip_whitespace();
+        }
+
+        Ok(Query::new(conditions))
+    }
+}
+
+/// The different types of conditions supported by a [`Query`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// Equals
+    Eq(String, Operand),
+    /// Less than
+    Lt(String, Operand),
+    /// Less than or equal to
+    Lte(String, Operand),
+    /// Greater than
+    Gt(String, Operand),
+    /// Greater than or equal to
+    Gte(String, Operand),
+    /// Contains (to check if a key contains a certain sub-string)
+    Contains(String, String),
+    /// Exists (to check if a key exists)
+    Exists(String),
+}
+
+impl Condition {
+    /// The composite `<type>.<key>` key this condition applies to
+    pub fn key(&self) -> &str {
+        match self {
+            Condition::Eq(key, _)
+            | Condition::Lt(key, _)
+            | Condition::Lte(key, _)
+            | Condition::Gt(key, _)
+            | Condition::Gte(key, _)
+            | Condition::Contains(key, _)
+            | Condition::Exists(key) => key,
+        }
+    }
+
+    /// Does this condition hold for any of the values of its key in the given
+    /// map from composite keys to values?
+    ///
+    /// As with Tendermint nodes, string operands only support equality, and
+    /// numeric operands are compared against the first number found in each
+    /// value (so that e.g. `transfer.amount > 5` matches `10stake`).
+    pub fn matches(&self, events: &BTreeMap<String, Vec<String>>) -> bool {
+        let values = match events.get(self.key()) {
+            Some(values) => values,
+            None => return false,
+        };
+
+        let compare = |operand: &Operand, expected: fn(Ordering) -> bool| {
+            values
+                .iter()
+                .any(|value| operand.compare(value).map_or(false, expected))
+        };
+
+        match self {
+            Condition::Exists(_) => true,
+            Condition::Contains(_, needle) => {
+                values.iter().any(|value| value.contains(needle.as_str()))
+            }
+            Condition::Eq(_, operand) => compare(operand, |ord| ord == Ordering::Equal),
+            Condition::Lt(_, operand)
+            | Condition::Lte(_, operand)
+            | Condition::Gt(_, operand)
+            | Condition::Gte(_, operand)
+                if operand.is_string() =>
+            {
+                false
+            }
+            Condition::Lt(_, operand) => compare(operand, |ord| ord == Ordering::Less),
+            Condition::Lte(_, operand) => compare(operand, |ord| ord != Ordering::Greater),
+            Condition::Gt(_, operand) => compare(operand, |ord| ord == Ordering::Greater),
+            Condition::Gte(_, operand) => compare(operand, |ord| ord != Ordering::Less),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Eq(key, op) => write!(f, "{} = {}", key, op),
+            Condition::Lt(key, op) => write!(f, "{} < {}", key, op),
+            Condition::Lte(key, op) => write!(f, "{} <= {}", key, op),
+            Condition::Gt(key, op) => write!(f, "{} > {}", key, op),
+            Condition::Gte(key, op) => write!(f, "{} >= {}", key, op),
+            Condition::Contains(key, op) => write!(f, "{} CONTAINS {}", key, escape(op)),
+            Condition::Exists(key) => write!(f, "{} EXISTS", key),
+        }
+    }
+}
+
+/// A typed operand for use in an [`Condition`].
+///
+/// According to the [Tendermint RPC subscribe docs][tm-subscribe],
+/// an operand can be a string, number, date or time. We differentiate here
+/// between integer and floating point numbers.
+///
+/// [tm-subscribe]: https://docs.tendermint.com/master/rpc/#/Websocket/subscribe
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    /// String
+    String(String),
+    /// Signed integer
+    Signed(i64),
+    /// Unsigned integer
+    Unsigned(u64),
+    /// Floating point number
+    Float(f64),
+    /// Date
+    Date(Date<Utc>),
+    /// Date and time
+    DateTime(DateTime<Utc>),
+}
+
+impl Operand {
+    fn is_string(&self) -> bool {
+        matches!(self, Operand::String(_))
+    }
+
+    /// Compare the given attribute value to this operand, if the value can be
+    /// interpreted as the same type as the operand.
+    fn compare(&self, value: &str) -> Option<Ordering> {
+        match self {
+            Operand::String(s) => Some(value.cmp(s.as_str())),
+            Operand::Signed(i) => compare_number(value, *i as i128, *i as f64),
+            Operand::Unsigned(u) => compare_number(value, *u as i128, *u as f64),
+            Operand::Float(f) => extract_number(value)?.parse::<f64>().ok()?.partial_cmp(f),
+            Operand::Date(d) => Some(
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .ok()?
+                    .cmp(&d.naive_utc()),
+            ),
+            Operand::DateTime(dt) => Some(
+                DateTime::parse_from_rfc3339(value)
+                    .ok()?
+                    .with_timezone(&Utc)
+                    .cmp(dt),
+            ),
+        }
+    }
+}
+
+/// Compare the first number in the given value to an integer operand,
+/// falling back to floating point if the number has a fractional part.
+fn compare_number(value: &str, int: i128, float: f64) -> Option<Ordering> {
+    let number = extract_number(value)?;
+    if number.contains('.') {
+        number.parse::<f64>().ok()?.partial_cmp(&float)
+    } else {
+        Some(number.parse::<i128>().ok()?.cmp(&int))
+    }
+}
+
+/// The first (possibly negative) number in the given value, if any.
+fn extract_number(value: &str) -> Option<&str> {
+    let start = value.find(|c: char| c.is_ascii_digit())?;
+    let start = if value[..start].ends_with('-') {
+        start - 1
+    } else {
+        start
+    };
+    let len = value[start + 1..]
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .map_or(value.len() - start, |len| len + 1);
+    Some(&value[start..start + len])
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::String(s) => write!(f, "{}", escape(s)),
+            Operand::Signed(i) => write!(f, "{}", i),
+            Operand::Unsigned(u) => write!(f, "{}", u),
+            Operand::Float(h) => write!(f, "{}", h),
+            Operand::Date(d) => write!(f, "DATE {}", d.format("%Y-%m-%d").to_string()),
+            Operand::DateTime(dt) => write!(f, "TIME {}", dt.to_rfc3339()),
+        }
+    }
+}
+
+impl From<String> for Operand {
+    fn from(source: String) -> Self {
+        Operand::String(source)
+    }
+}
+
+impl From<char> for Operand {
+    fn from(source: char) -> Self {
+        Operand::String(source.to_string())
+    }
+}
+
+impl From<&str> for Operand {
+    fn from(source: &str) -> Self {
+        Operand::String(source.to_string())
+    }
+}
+
+impl From<i64> for Operand {
+    fn from(source: i64) -> Self {
+        Operand::Signed(source)
+    }
+}
+
+impl From<i32> for Operand {
+    fn from(source: i32) -> Self {
+        Operand::Signed(source as i64)
+    }
+}
+
+impl From<i16> for Operand {
+    fn from(source: i16) -> Self {
+        Operand::Signed(source as i64)
+    }
+}
+
+impl From<i8> for Operand {
+    fn from(source: i8) -> Self {
+        Operand::Signed(source as i64)
+    }
+}
+
+impl From<u64> for Operand {
+    fn from(source: u64) -> Self {
+        Operand::Unsigned(source)
+    }
+}
+
+impl From<u32> for Operand {
+    fn from(source: u32) -> Self {
+        Operand::Unsigned(source as u64)
+    }
+}
+
+impl From<u16> for Operand {
+    fn from(source: u16) -> Self {
+        Operand::Unsigned(source as u64)
+    }
+}
+
+impl From<u8> for Operand {
+    fn from(source: u8) -> Self {
+        Operand::Unsigned(source as u64)
+    }
+}
+
+impl From<usize> for Operand {
+    fn from(source: usize) -> Self {
+        Operand::Unsigned(source as u64)
+    }
+}
+
+impl From<f64> for Operand {
+    fn from(source: f64) -> Self {
+        Operand::Float(source)
+    }
+}
+
+impl From<f32> for Operand {
+    fn from(source: f32) -> Self {
+        Operand::Float(source as f64)
+    }
+}
+
+impl From<Date<Utc>> for Operand {
+    fn from(source: Date<Utc>) -> Self {
+        Operand::Date(source)
+    }
+}
+
+impl From<DateTime<Utc>> for Operand {
+    fn from(source: DateTime<Utc>) -> Self {
+        Operand::DateTime(source)
+    }
+}
+
+impl From<DateTime<FixedOffset>> for Operand {
+    fn from(source: DateTime<FixedOffset>) -> Self {
+        Operand::DateTime(source.into())
+    }
+}
+
+/// Escape backslashes and single quotes within the given string with a backslash.
+fn escape(s: &str) -> String {
+    let mut result = String::new();
+    for ch in s.chars() {
+        if ch == '\\' || ch == '\'' {
+            result.push('\\');
+        }
+        result.push(ch);
+    }
+    format!("'{}'", result)
+}
+
+/// Characters which terminate a composite key
+const KEY_DELIMITERS: &str = "\\()\"'=><";
+
+/// Hand-written recursive descent parser for the query grammar
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn is_done(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn error(&self, msg: &str) -> Error {
+        format_err!(Kind::Parse, "{} at position {} of query", msg, self.pos).into()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consume everything up to the first character matching the predicate
+    fn take_until(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(pred).unwrap_or_else(|| rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn symbol(&mut self, symbol: &str) -> bool {
+        if self.rest().starts_with(symbol) {
+            self.pos += symbol.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the given keyword, if it isn't the prefix of a longer word
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let rest = self.rest();
+        let delimited = rest.get(keyword.len()..).map_or(false, |after| {
+            after.is_empty() || after.starts_with(char::is_whitespace) || after.starts_with('\'')
+        });
+        if rest.starts_with(keyword) && delimited {
+            self.pos += keyword.len();
+            self.skip_whitespace();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn condition(&mut self) -> Result<Condition, Error> {
+        let key = self
+            .take_until(|c| c.is_whitespace() || KEY_DELIMITERS.contains(c))
+            .to_owned();
+        if key.is_empty() {
+            return Err(self.error("expected key"));
+        }
+        self.skip_whitespace();
+
+        let condition = if self.symbol("<=") {
+            Condition::Lte(key, self.operand()?)
+        } else if self.symbol(">=") {
+            Condition::Gte(key, self.operand()?)
+        } else if self.symbol("<") {
+            Condition::Lt(key, self.operand()?)
+        } else if self.symbol(">") {
+            Condition::Gt(key, self.operand()?)
+        } else if self.symbol("=") {
+            Condition::Eq(key, self.operand()?)
+        } else if self.keyword("CONTAINS") {
+            Condition::Contains(key, self.quoted()?)
+        } else if self.keyword("EXISTS") {
+            Condition::Exists(key)
+        } else {
+            return Err(self.error("expected operator"));
+        };
+        Ok(condition)
+    }
+
+    fn operand(&mut self) -> Result<Operand, Error> {
+        self.skip_whitespace();
+        if self.rest().starts_with('\'') {
+            return Ok(Operand::String(self.quoted()?));
+        }
+
+        let start = self.pos;
+        if self.keyword("DATE") {
+            let date = self.take_until(char::is_whitespace);
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| self.error("invalid date"))?;
+            return Ok(Operand::Date(Date::from_utc(date, Utc)));
+        }
+        if self.keyword("TIME") {
+            let time = self.take_until(char::is_whitespace);
+            let time =
+                DateTime::parse_from_rfc3339(time).map_err(|_| self.error("invalid time"))?;
+            return Ok(time.into());
+        }
+
+        let number = self.take_until(char::is_whitespace);
+        let operand = if number.contains('.') {
+            number.parse().map(Operand::Float).ok()
+        } else if number.starts_with('-') {
+            number.parse().map(Operand::Signed).ok()
+        } else {
+            number.parse().map(Operand::Unsigned).ok()
+        };
+        operand.ok_or_else(|| {
+            self.pos = start;
+            self.error("expected operand")
+        })
+    }
+
+    /// A single-quoted string, in which backslashes escape the next character
+    fn quoted(&mut self) -> Result<String, Error> {
+        if !self.symbol("'") {
+            return Err(self.error("expected quoted string"));
+        }
+        let mut result = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                '\'' => {
+                    self.pos += i + 1;
+                    return Ok(result);
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => result.push(escaped),
+                    None => break,
+                },
+                _ => result.push(ch),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abci::event::EventAttribute;
+
+    fn parse(query: &str) -> Query {
+        query.parse().unwrap()
+    }
+
+    fn events() -> Vec<Event> {
+        vec![
+            Event::new(
+                "transfer",
+                vec![
+                    EventAttribute::new("sender", "alice", true),
+                    EventAttribute::new("amount", "100stake", true),
+                    EventAttribute::new("memo", "it's a 'gift'", false),
+                ],
+            ),
+            Event::new(
+                "transfer",
+                vec![
+                    EventAttribute::new("sender", "bob", true),
+                    EventAttribute::new("amount", "2.5atom", true),
+                ],
+            ),
+            Event::new(
+                "block",
+                vec![
+                    EventAttribute::new("date", "2020-09-24", true),
+                    EventAttribute::new("time", "2020-09-24T10:17:23-04:00", true),
+                    EventAttribute::new("offset", "-3", true),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn parses_conditions() {
+        let query = parse(
+            "tm.event = 'Tx' AND tx.height>5 AND a.b<=-2 AND c.d >= 1.5 AND e.f < 3 \
+             AND g.h CONTAINS 'x\\'y' AND i.j EXISTS AND k.l = DATE 2020-09-24 \
+             AND m.n > TIME 2020-09-24T10:17:23Z",
+        );
+        assert_eq!(
+            query.conditions(),
+            &[
+                Condition::Eq("tm.event".to_owned(), "Tx".into()),
+                Condition::Gt("tx.height".to_owned(), 5_u64.into()),
+                Condition::Lte("a.b".to_owned(), (-2_i64).into()),
+                Condition::Gte("c.d".to_owned(), 1.5_f64.into()),
+                Condition::Lt("e.f".to_owned(), 3_u64.into()),
+                Condition::Contains("g.h".to_owned(), "x'y".to_owned()),
+                Condition::Exists("i.j".to_owned()),
+                Condition::Eq(
+                    "k.l".to_owned(),
+                    Date::from_utc(NaiveDate::from_ymd(2020, 9, 24), Utc).into()
+                ),
+                Condition::Gt(
+                    "m.n".to_owned(),
+                    DateTime::parse_from_rfc3339("2020-09-24T10:17:23Z")
+                        .unwrap()
+                        .into()
+                ),
+            ]
+        );
+        assert!(parse("").conditions().is_empty());
+        assert_eq!(parse("\"a.b EXISTS\""), parse("a.b EXISTS"));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let query = Query::new(vec![
+            Condition::Eq("tx.hash".to_owned(), "X\\Y'Z".into()),
+            Condition::Lt("tx.height".to_owned(), 10_u64.into()),
+            Condition::Contains("a.b".to_owned(), "'quoted'".to_owned()),
+            Condition::Exists("c.d".to_owned()),
+        ]);
+        assert_eq!(parse(&query.to_string()), query);
+    }
+
+    #[test]
+    fn rejects_invalid_queries() {
+        for query in &[
+            "a.b",
+            "= 'x'",
+            "a.b = x",
+            "a.b = 'x",
+            "a.b = 'x' b.c EXISTS",
+            "a.b = 'x' OR b.c EXISTS",
+            "a.b CONTAINS 5",
+            "a.b EXISTSX",
+            "a.b = DATE 2020-13-01",
+        ] {
+            assert!(query.parse::<Query>().is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn matches_events() {
+        let events = events();
+        for (query, expected) in &[
+            ("", true),
+            ("transfer.sender = 'bob'", true),
+            ("transfer.sender = 'carol'", false),
+            ("transfer.sender = 'alice' AND transfer.amount > 50", true),
+            (
+                "transfer.sender = 'alice' AND transfer.recipient EXISTS",
+                false,
+            ),
+            ("transfer.amount >= 100", true),
+            ("transfer.amount > 100", false),
+            ("transfer.amount < 3", true),
+            ("transfer.amount = 2.5", true),
+            ("transfer.amount > 'a'", false),
+            ("transfer.memo CONTAINS '\\'gift\\''", true),
+            ("transfer.memo CONTAINS 'present'", false),
+            ("block.date = DATE 2020-09-24", true),
+            ("block.date > DATE 2020-09-24", false),
+            ("block.time = TIME 2020-09-24T14:17:23Z", true),
+            ("block.time < TIME 2020-09-24T14:17:23Z", false),
+            ("block.offset < -2", true),
+            ("block.offset = 3", false),
+            ("block.sender EXISTS", false),
+        ] {
+            assert_eq!(parse(query).matches(&events), *expected, "{}", query);
+        }
+    }
+
+    #[test]
+    fn matches_composite_keys() {
+        let mut events = composite_map(&events());
+        assert!(!parse("tm.event = 'Tx'").matches_composite(&events));
+
+        events.insert("tm.event".to_owned(), vec!["Tx".to_owned()]);
+        assert!(parse("tm.event = 'Tx' AND transfer.sender = 'alice'").matches_composite(&events));
+    }
+}
@@ -5,12 +5,13 @@
 //!
 //! <https://tendermint.com/docs/spec/abci/apps.html#gas>
 
+use crate::prelude::*;
 use crate::{Error, Kind};
-use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
-use std::{
+use core::{
     fmt::{self, Display},
     str::FromStr,
 };
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Gas: representation of transaction processing resource costs
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
@@ -1,5 +1,6 @@
+use crate::prelude::*;
+use core::fmt::{self, Display};
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Display};
 
 /// ABCI info
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -22,3 +23,9 @@ impl Default for Info {
         Self(String::new())
     }
 }
+
+impl From<&str> for Info {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
@@ -1,6 +1,7 @@
+use crate::prelude::*;
+use core::fmt;
+use core::fmt::Display;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::fmt::Display;
 
 /// ABCI log data
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -0,0 +1,547 @@
+//! ABCI requests, as sent by Tendermint to applications.
+//!
+//! These are domain types for the messages of the ABCI protocol, which
+//! convert to and from their Protobuf encodings (as defined by
+//! [`tendermint_proto::abci`]).
+
+use super::types::{CheckTxKind, LastCommitInfo, Misbehavior, Snapshot};
+use crate::prelude::*;
+use crate::{block, consensus, validator, Error, Hash, Kind, Time};
+use bytes::Bytes;
+use core::convert::{TryFrom, TryInto};
+use tendermint_proto::abci::request::Value;
+use tendermint_proto::abci::{
+    Request as RawRequest, RequestApplySnapshotChunk as RawApplySnapshotChunk,
+    RequestBeginBlock as RawBeginBlock, RequestCheckTx as RawCheckTx, RequestCommit as RawCommit,
+    RequestDeliverTx as RawDeliverTx, RequestEcho as RawEcho, RequestEndBlock as RawEndBlock,
+    RequestFlush as RawFlush, RequestInfo as RawInfo, RequestInitChain as RawInitChain,
+    RequestListSnapshots as RawListSnapshots, RequestLoadSnapshotChunk as RawLoadSnapshotChunk,
+    RequestOfferSnapshot as RawOfferSnapshot, RequestQuery as RawQuery,
+    RequestSetOption as RawSetOption,
+};
+use tendermint_proto::Protobuf;
+
+/// Echo a message back, to test the connection
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Echo {
+    /// Message to echo back
+    pub message: String,
+}
+
+impl From<RawEcho> for Echo {
+    fn from(value: RawEcho) -> Self {
+        Echo {
+            message: value.message,
+        }
+    }
+}
+
+impl From<Echo> for RawEcho {
+    fn from(value: Echo) -> Self {
+        RawEcho {
+            message: value.message,
+        }
+    }
+}
+
+/// Ask for information about the application, on startup
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Info {
+    /// Tendermint software version
+    pub version: String,
+
+    /// Tendermint block protocol version
+    pub block_version: u64,
+
+    /// Tendermint P2P protocol version
+    pub p2p_version: u64,
+}
+
+impl From<RawInfo> for Info {
+    fn from(value: RawInfo) -> Self {
+        Info {
+            version: value.version,
+            block_version: value.block_version,
+            p2p_version: value.p2p_version,
+        }
+    }
+}
+
+impl From<Info> for RawInfo {
+    fn from(value: Info) -> Self {
+        RawInfo {
+            version: value.version,
+            block_version: value.block_version,
+            p2p_version: value.p2p_version,
+        }
+    }
+}
+
+/// Set an application-specific, non-consensus option
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SetOption {
+    /// Option name
+    pub key: String,
+
+    /// Option value
+    pub value: String,
+}
+
+impl From<RawSetOption> for SetOption {
+    fn from(value: RawSetOption) -> Self {
+        SetOption {
+            key: value.key,
+            value: value.value,
+        }
+    }
+}
+
+impl From<SetOption> for RawSetOption {
+    fn from(value: SetOption) -> Self {
+        RawSetOption {
+            key: value.key,
+            value: value.value,
+        }
+    }
+}
+
+/// Initialize the blockchain, from its genesis document
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitChain {
+    /// Genesis time
+    pub time: Time,
+
+    /// Chain ID
+    pub chain_id: String,
+
+    /// Initial consensus parameters
+    pub consensus_params: consensus::Params,
+
+    /// Initial validator set
+    pub validators: Vec<validator::Update>,
+
+    /// Initial application state, serialized by the application
+    pub app_state_bytes: Bytes,
+
+    /// Height of the first block
+    pub initial_height: block::Height,
+}
+
+impl TryFrom<RawInitChain> for InitChain {
+    type Error = Error;
+
+    fn try_from(value: RawInitChain) -> Result<Self, Self::Error> {
+        Ok(InitChain {
+            time: value.time.ok_or(Kind::MissingTimestamp)?.try_into()?,
+            chain_id: value.chain_id,
+            consensus_params: value
+                .consensus_params
+                .ok_or_else(|| Kind::InvalidAbciMessage.context("missing consensus params"))?
+                .try_into()?,
+            validators: value
+                .validators
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            app_state_bytes: value.app_state_bytes.into(),
+            initial_height: value.initial_height.try_into()?,
+        })
+    }
+}
+
+impl From<InitChain> for RawInitChain {
+    fn from(value: InitChain) -> Self {
+        RawInitChain {
+            time: Some(value.time.into()),
+            chain_id: value.chain_id,
+            consensus_params: Some(value.consensus_params.into()),
+            validators: value.validators.into_iter().map(Into::into).collect(),
+            app_state_bytes: value.app_state_bytes.to_vec(),
+            initial_height: value.initial_height.into(),
+        }
+    }
+}
+
+/// Query the application's state
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Query {
+    /// Query data, e.g. the key to look up
+    pub data: Bytes,
+
+    /// Application-specific path of the query, e.g. `/store/key`
+    pub path: String,
+
+    /// Height of the state to query, or 0 for the latest committed state
+    pub height: block::Height,
+
+    /// Whether to return a Merkle proof of the result
+    pub prove: bool,
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Query {
+            data: Bytes::new(),
+            path: String::new(),
+            height: block::Height::from(0_u32),
+            prove: false,
+        }
+    }
+}
+
+impl TryFrom<RawQuery> for Query {
+    type Error = Error;
+
+    fn try_from(value: RawQuery) -> Result<Self, Self::Error> {
+        Ok(Query {
+            data: value.data.into(),
+            path: value.path,
+            height: value.height.try_into()?,
+            prove: value.prove,
+        })
+    }
+}
+
+impl From<Query> for RawQuery {
+    fn from(value: Query) -> Self {
+        RawQuery {
+            data: value.data.to_vec(),
+            path: value.path,
+            height: value.height.into(),
+            prove: value.prove,
+        }
+    }
+}
+
+/// Begin executing a block
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeginBlock {
+    /// Hash of the block's header
+    pub hash: Hash,
+
+    /// Header of the block
+    pub header: block::Header,
+
+    /// Which validators signed the last block
+    pub last_commit_info: LastCommitInfo,
+
+    /// Misbehavior of validators, as found in the evidence of the block
+    pub byzantine_validators: Vec<Misbehavior>,
+}
+
+impl TryFrom<RawBeginBlock> for BeginBlock {
+    type Error = Error;
+
+    fn try_from(value: RawBeginBlock) -> Result<Self, Self::Error> {
+        Ok(BeginBlock {
+            hash: value.hash.try_into()?,
+            header: value.header.ok_or(Kind::MissingHeader)?.try_into()?,
+            last_commit_info: value
+                .last_commit_info
+                .ok_or_else(|| Kind::InvalidAbciMessage.context("missing last commit info"))?
+                .try_into()?,
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<BeginBlock> for RawBeginBlock {
+    fn from(value: BeginBlock) -> Self {
+        RawBeginBlock {
+            hash: value.hash.into(),
+            header: Some(value.header.into()),
+            last_commit_info: Some(value.last_commit_info.into()),
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+/// Check whether a transaction may be added to the mempool
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CheckTx {
+    /// The transaction
+    pub tx: Bytes,
+
+    /// Whether the transaction is new, or rechecked after a block was
+    /// committed
+    pub kind: CheckTxKind,
+}
+
+impl TryFrom<RawCheckTx> for CheckTx {
+    type Error = Error;
+
+    fn try_from(value: RawCheckTx) -> Result<Self, Self::Error> {
+        Ok(CheckTx {
+            tx: value.tx.into(),
+            kind: value.r#type.try_into()?,
+        })
+    }
+}
+
+impl From<CheckTx> for RawCheckTx {
+    fn from(value: CheckTx) -> Self {
+        RawCheckTx {
+            tx: value.tx.to_vec(),
+            r#type: value.kind.into(),
+        }
+    }
+}
+
+/// Execute a transaction of the current block
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeliverTx {
+    /// The transaction
+    pub tx: Bytes,
+}
+
+impl From<RawDeliverTx> for DeliverTx {
+    fn from(value: RawDeliverTx) -> Self {
+        DeliverTx {
+            tx: value.tx.into(),
+        }
+    }
+}
+
+impl From<DeliverTx> for RawDeliverTx {
+    fn from(value: DeliverTx) -> Self {
+        RawDeliverTx {
+            tx: value.tx.to_vec(),
+        }
+    }
+}
+
+/// Finish executing a block, after all its transactions were delivered
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EndBlock {
+    /// Height of the block
+    pub height: block::Height,
+}
+
+impl TryFrom<RawEndBlock> for EndBlock {
+    type Error = Error;
+
+    fn try_from(value: RawEndBlock) -> Result<Self, Self::Error> {
+        Ok(EndBlock {
+            height: value.height.try_into()?,
+        })
+    }
+}
+
+impl From<EndBlock> for RawEndBlock {
+    fn from(value: EndBlock) -> Self {
+        RawEndBlock {
+            height: value.height.into(),
+        }
+    }
+}
+
+/// Offer a snapshot to the application, when bootstrapping the node with
+/// state sync
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OfferSnapshot {
+    /// The snapshot offered by a peer
+    pub snapshot: Snapshot,
+
+    /// The light client-verified app hash for the height of the snapshot
+    pub app_hash: Bytes,
+}
+
+impl TryFrom<RawOfferSnapshot> for OfferSnapshot {
+    type Error = Error;
+
+    fn try_from(value: RawOfferSnapshot) -> Result<Self, Self::Error> {
+        Ok(OfferSnapshot {
+            snapshot: value
+                .snapshot
+                .ok_or_else(|| Kind::InvalidAbciMessage.context("missing snapshot"))?
+                .try_into()?,
+            app_hash: value.app_hash.into(),
+        })
+    }
+}
+
+impl From<OfferSnapshot> for RawOfferSnapshot {
+    fn from(value: OfferSnapshot) -> Self {
+        RawOfferSnapshot {
+            snapshot: Some(value.snapshot.into()),
+            app_hash: value.app_hash.to_vec(),
+        }
+    }
+}
+
+/// Load a chunk of a local snapshot, to send it to a peer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadSnapshotChunk {
+    /// Height of the snapshot
+    pub height: block::Height,
+
+    /// Format of the snapshot
+    pub format: u32,
+
+    /// Index of the chunk
+    pub chunk: u32,
+}
+
+impl TryFrom<RawLoadSnapshotChunk> for LoadSnapshotChunk {
+    type Error = Error;
+
+    fn try_from(value: RawLoadSnapshotChunk) -> Result<Self, Self::Error> {
+        Ok(LoadSnapshotChunk {
+            height: value.height.try_into()?,
+            format: value.format,
+            chunk: value.chunk,
+        })
+    }
+}
+
+impl From<LoadSnapshotChunk> for RawLoadSnapshotChunk {
+    fn from(value: LoadSnapshotChunk) -> Self {
+        RawLoadSnapshotChunk {
+            height: value.height.into(),
+            format: value.format,
+            chunk: value.chunk,
+        }
+    }
+}
+
+/// Apply a chunk of the snapshot the application accepted, in order
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ApplySnapshotChunk {
+    /// Index of the chunk
+    pub index: u32,
+
+    /// The chunk's contents
+    pub chunk: Bytes,
+
+    /// ID of the peer which sent the chunk
+    pub sender: String,
+}
+
+impl From<RawApplySnapshotChunk> for ApplySnapshotChunk {
+    fn from(value: RawApplySnapshotChunk) -> Self {
+        ApplySnapshotChunk {
+            index: value.index,
+            chunk: value.chunk.into(),
+            sender: value.sender,
+        }
+    }
+}
+
+impl From<ApplySnapshotChunk> for RawApplySnapshotChunk {
+    fn from(value: ApplySnapshotChunk) -> Self {
+        RawApplySnapshotChunk {
+            index: value.index,
+            chunk: value.chunk.to_vec(),
+            sender: value.sender,
+        }
+    }
+}
+
+/// All ABCI requests
+#[derive(Clone, Debug, PartialEq)]
+pub enum Request {
+    /// Echo a message back
+    Echo(Echo),
+
+    /// Flush the responses to all the pending requests
+    Flush,
+
+    /// Ask for information about the application
+    Info(Info),
+
+    /// Set an application option
+    SetOption(SetOption),
+
+    /// Initialize the blockchain
+    InitChain(InitChain),
+
+    /// Query the application's state
+    Query(Query),
+
+    /// Begin executing a block
+    BeginBlock(BeginBlock),
+
+    /// Check a transaction for the mempool
+    CheckTx(CheckTx),
+
+    /// Execute a transaction
+    DeliverTx(DeliverTx),
+
+    /// Finish executing a block
+    EndBlock(EndBlock),
+
+    /// Commit the state resulting from executing a block
+    Commit,
+
+    /// List the application's local snapshots
+    ListSnapshots,
+
+    /// Offer a snapshot to the application
+    OfferSnapshot(OfferSnapshot),
+
+    /// Load a chunk of a local snapshot
+    LoadSnapshotChunk(LoadSnapshotChunk),
+
+    /// Apply a chunk of a snapshot
+    ApplySnapshotChunk(ApplySnapshotChunk),
+}
+
+impl Protobuf<RawRequest> for Request {}
+
+impl TryFrom<RawRequest> for Request {
+    type Error = Error;
+
+    fn try_from(value: RawRequest) -> Result<Self, Self::Error> {
+        let value = value
+            .value
+            .ok_or_else(|| Kind::InvalidAbciMessage.context("empty request"))?;
+        Ok(match value {
+            Value::Echo(req) => Request::Echo(req.into()),
+            Value::Flush(_) => Request::Flush,
+            Value::Info(req) => Request::Info(req.into()),
+            Value::SetOption(req) => Request::SetOption(req.into()),
+            Value::InitChain(req) => Request::InitChain(req.try_into()?),
+            Value::Query(req) => Request::Query(req.try_into()?),
+            Value::BeginBlock(req) => Request::BeginBlock(req.try_into()?),
+            Value::CheckTx(req) => Request::CheckTx(req.try_into()?),
+            Value::DeliverTx(req) => Request::DeliverTx(req.into()),
+            Value::EndBlock(req) => Request::EndBlock(req.try_into()?),
+            Value::Commit(_) => Request::Commit,
+            Value::ListSnapshots(_) => Request::ListSnapshots,
+            Value::OfferSnapshot(req) => Request::OfferSnapshot(req.try_into()?),
+            Value::LoadSnapshotChunk(req) => Request::LoadSnapshotChunk(req.try_into()?),
+            Value::ApplySnapshotChunk(req) => Request::ApplySnapshotChunk(req.into()),
+        })
+    }
+}
+
+impl From<Request> for RawRequest {
+    fn from(value: Request) -> Self {
+        let value = match value {
+            Request::Echo(req) => Value::Echo(req.into()),
+            Request::Flush => Value::Flush(RawFlush {}),
+            Request::Info(req) => Value::Info(req.into()),
+            Request::SetOption(req) => Value::SetOption(req.into()),
+            Request::InitChain(req) => Value::InitChain(req.into()),
+            Request::Query(req) => Value::Query(req.into()),
+            Request::BeginBlock(req) => Value::BeginBlock(req.into()),
+            Request::CheckTx(req) => Value::CheckTx(req.into()),
+            Request::DeliverTx(req) => Value::DeliverTx(req.into()),
+            Request::EndBlock(req) => Value::EndBlock(req.into()),
+            Request::Commit => Value::Commit(RawCommit {}),
+            Request::ListSnapshots => Value::ListSnapshots(RawListSnapshots {}),
+            Request::OfferSnapshot(req) => Value::OfferSnapshot(req.into()),
+            Request::LoadSnapshotChunk(req) => Value::LoadSnapshotChunk(req.into()),
+            Request::ApplySnapshotChunk(req) => Value::ApplySnapshotChunk(req.into()),
+        };
+        RawRequest { value: Some(value) }
+    }
+}
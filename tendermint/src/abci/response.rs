@@ -0,0 +1,956 @@
+//! ABCI responses, as returned by applications to Tendermint.
+//!
+//! These are domain types for the messages of the ABCI protocol, which
+//! convert to and from their Protobuf encodings (as defined by
+//! [`tendermint_proto::abci`]).
+
+use super::types::Snapshot;
+use super::{Code, Event, Gas};
+use crate::merkle::proof::Proof;
+use crate::prelude::*;
+use crate::{block, consensus, validator, Error, Kind};
+use bytes::Bytes;
+use core::convert::{TryFrom, TryInto};
+use tendermint_proto::abci::response::Value;
+use tendermint_proto::abci::response_apply_snapshot_chunk::Result as RawApplySnapshotChunkResult;
+use tendermint_proto::abci::response_offer_snapshot::Result as RawOfferSnapshotResult;
+use tendermint_proto::abci::{
+    Response as RawResponse, ResponseApplySnapshotChunk as RawApplySnapshotChunk,
+    ResponseBeginBlock as RawBeginBlock, ResponseCheckTx as RawCheckTx,
+    ResponseCommit as RawCommit, ResponseDeliverTx as RawDeliverTx, ResponseEcho as RawEcho,
+    ResponseEndBlock as RawEndBlock, ResponseException as RawException, ResponseFlush as RawFlush,
+    ResponseInfo as RawInfo, ResponseInitChain as RawInitChain,
+    ResponseListSnapshots as RawListSnapshots, ResponseLoadSnapshotChunk as RawLoadSnapshotChunk,
+    ResponseOfferSnapshot as RawOfferSnapshot, ResponseQuery as RawQuery,
+    ResponseSetOption as RawSetOption,
+};
+use tendermint_proto::Protobuf;
+
+/// An unexpected error while handling a request
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Exception {
+    /// Error message
+    pub error: String,
+}
+
+impl From<RawException> for Exception {
+    fn from(value: RawException) -> Self {
+        Exception { error: value.error }
+    }
+}
+
+impl From<Exception> for RawException {
+    fn from(value: Exception) -> Self {
+        RawException { error: value.error }
+    }
+}
+
+/// The message of an echo request
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Echo {
+    /// Echoed message
+    pub message: String,
+}
+
+impl From<RawEcho> for Echo {
+    fn from(value: RawEcho) -> Self {
+        Echo {
+            message: value.message,
+        }
+    }
+}
+
+impl From<Echo> for RawEcho {
+    fn from(value: Echo) -> Self {
+        RawEcho {
+            message: value.message,
+        }
+    }
+}
+
+/// Information about the application
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Info {
+    /// Arbitrary information
+    pub data: String,
+
+    /// Application software version
+    pub version: String,
+
+    /// Application protocol version
+    pub app_version: u64,
+
+    /// Height of the last block the application committed, or 0 if none
+    pub last_block_height: block::Height,
+
+    /// Hash of the application's state after the last block it committed
+    pub last_block_app_hash: Bytes,
+}
+
+impl Default for Info {
+    fn default() -> Self {
+        Info {
+            data: String::new(),
+            version: String::new(),
+            app_version: 0,
+            last_block_height: block::Height::from(0_u32),
+            last_block_app_hash: Bytes::new(),
+        }
+    }
+}
+
+impl TryFrom<RawInfo> for Info {
+    type Error = Error;
+
+    fn try_from(value: RawInfo) -> Result<Self, Self::Error> {
+        Ok(Info {
+            data: value.data,
+            version: value.version,
+            app_version: value.app_version,
+            last_block_height: value.last_block_height.try_into()?,
+            last_block_app_hash: value.last_block_app_hash.into(),
+        })
+    }
+}
+
+impl From<Info> for RawInfo {
+    fn from(value: Info) -> Self {
+        RawInfo {
+            data: value.data,
+            version: value.version,
+            app_version: value.app_version,
+            last_block_height: value.last_block_height.into(),
+            last_block_app_hash: value.last_block_app_hash.to_vec(),
+        }
+    }
+}
+
+/// Outcome of setting an application option
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SetOption {
+    /// Response code
+    pub code: Code,
+
+    /// Log output (nondeterministic)
+    pub log: String,
+
+    /// Additional information (nondeterministic)
+    pub info: String,
+}
+
+impl From<RawSetOption> for SetOption {
+    fn from(value: RawSetOption) -> Self {
+        SetOption {
+            code: value.code.into(),
+            log: value.log,
+            info: value.info,
+        }
+    }
+}
+
+impl From<SetOption> for RawSetOption {
+    fn from(value: SetOption) -> Self {
+        RawSetOption {
+            code: value.code.into(),
+            log: value.log,
+            info: value.info,
+        }
+    }
+}
+
+/// Outcome of initializing the blockchain
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InitChain {
+    /// Consensus parameters replacing those of the genesis document, if any
+    pub consensus_params: Option<consensus::Params>,
+
+    /// Validator set replacing that of the genesis document, if not empty
+    pub validators: Vec<validator::Update>,
+
+    /// Initial hash of the application's state
+    pub app_hash: Bytes,
+}
+
+impl TryFrom<RawInitChain> for InitChain {
+    type Error = Error;
+
+    fn try_from(value: RawInitChain) -> Result<Self, Self::Error> {
+        Ok(InitChain {
+            consensus_params: value.consensus_params.map(TryInto::try_into).transpose()?,
+            validators: value
+                .validators
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            app_hash: value.app_hash.into(),
+        })
+    }
+}
+
+impl From<InitChain> for RawInitChain {
+    fn from(value: InitChain) -> Self {
+        RawInitChain {
+            consensus_params: value.consensus_params.map(Into::into),
+            validators: value.validators.into_iter().map(Into::into).collect(),
+            app_hash: value.app_hash.to_vec(),
+        }
+    }
+}
+
+/// Result of a query of the application's state
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Query {
+    /// Response code
+    pub code: Code,
+
+    /// Log output (nondeterministic)
+    pub log: String,
+
+    /// Additional information (nondeterministic)
+    pub info: String,
+
+    /// Index of the key in the tree
+    pub index: i64,
+
+    /// Key of the result
+    pub key: Bytes,
+
+    /// Value of the result
+    pub value: Bytes,
+
+    /// Merkle proof of the result, if requested
+    pub proof: Option<Proof>,
+
+    /// Height of the state which was queried
+    pub height: block::Height,
+
+    /// Namespace of the response code
+    pub codespace: String,
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Query {
+            code: Code::Ok,
+            log: String::new(),
+            info: String::new(),
+            index: 0,
+            key: Bytes::new(),
+            value: Bytes::new(),
+            proof: None,
+            height: block::Height::from(0_u32),
+            codespace: String::new(),
+        }
+    }
+}
+
+impl TryFrom<RawQuery> for Query {
+    type Error = Error;
+
+    fn try_from(value: RawQuery) -> Result<Self, Self::Error> {
+        Ok(Query {
+            code: value.code.into(),
+            log: value.log,
+            info: value.info,
+            index: value.index,
+            key: value.key.into(),
+            value: value.value.into(),
+            proof: value.proof_ops.map(TryInto::try_into).transpose()?,
+            height: value.height.try_into()?,
+            codespace: value.codespace,
+        })
+    }
+}
+
+impl From<Query> for RawQuery {
+    fn from(value: Query) -> Self {
+        RawQuery {
+            code: value.code.into(),
+            log: value.log,
+            info: value.info,
+            index: value.index,
+            key: value.key.to_vec(),
+            value: value.value.to_vec(),
+            proof_ops: value.proof.map(Into::into),
+            height: value.height.into(),
+            codespace: value.codespace,
+        }
+    }
+}
+
+/// Outcome of beginning to execute a block
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BeginBlock {
+    /// Events emitted by the application
+    pub events: Vec<Event>,
+}
+
+impl From<RawBeginBlock> for BeginBlock {
+    fn from(value: RawBeginBlock) -> Self {
+        BeginBlock {
+            events: value.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<BeginBlock> for RawBeginBlock {
+    fn from(value: BeginBlock) -> Self {
+        RawBeginBlock {
+            events: value.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Outcome of checking a transaction for the mempool
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CheckTx {
+    /// Response code: the transaction is only added to the mempool if OK
+    pub code: Code,
+
+    /// Result data
+    pub data: Bytes,
+
+    /// Log output (nondeterministic)
+    pub log: String,
+
+    /// Additional information (nondeterministic)
+    pub info: String,
+
+    /// Amount of gas requested for the transaction
+    pub gas_wanted: Gas,
+
+    /// Amount of gas consumed by the transaction
+    pub gas_used: Gas,
+
+    /// Events emitted by the application
+    pub events: Vec<Event>,
+
+    /// Namespace of the response code
+    pub codespace: String,
+}
+
+impl TryFrom<RawCheckTx> for CheckTx {
+    type Error = Error;
+
+    fn try_from(value: RawCheckTx) -> Result<Self, Self::Error> {
+        Ok(CheckTx {
+            code: value.code.into(),
+            data: value.data.into(),
+            log: value.log,
+            info: value.info,
+            gas_wanted: gas(value.gas_wanted)?,
+            gas_used: gas(value.gas_used)?,
+            events: value.events.into_iter().map(Into::into).collect(),
+            codespace: value.codespace,
+        })
+    }
+}
+
+impl From<CheckTx> for RawCheckTx {
+    fn from(value: CheckTx) -> Self {
+        RawCheckTx {
+            code: value.code.into(),
+            data: value.data.to_vec(),
+            log: value.log,
+            info: value.info,
+            gas_wanted: value.gas_wanted.value() as i64,
+            gas_used: value.gas_used.value() as i64,
+            events: value.events.into_iter().map(Into::into).collect(),
+            codespace: value.codespace,
+        }
+    }
+}
+
+/// Outcome of executing a transaction
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeliverTx {
+    /// Response code: the transaction is only considered successful if OK
+    pub code: Code,
+
+    /// Result data
+    pub data: Bytes,
+
+    /// Log output (nondeterministic)
+    pub log: String,
+
+    /// Additional information (nondeterministic)
+    pub info: String,
+
+    /// Amount of gas requested for the transaction
+    pub gas_wanted: Gas,
+
+    /// Amount of gas consumed by the transaction
+    pub gas_used: Gas,
+
+    /// Events emitted by the application
+    pub events: Vec<Event>,
+
+    /// Namespace of the response code
+    pub codespace: String,
+}
+
+impl TryFrom<RawDeliverTx> for DeliverTx {
+    type Error = Error;
+
+    fn try_from(value: RawDeliverTx) -> Result<Self, Self::Error> {
+        Ok(DeliverTx {
+            code: value.code.into(),
+            data: value.data.into(),
+            log: value.log,
+            info: value.info,
+            gas_wanted: gas(value.gas_wanted)?,
+            gas_used: gas(value.gas_used)?,
+            events: value.events.into_iter().map(Into::into).collect(),
+            codespace: value.codespace,
+        })
+    }
+}
+
+impl From<DeliverTx> for RawDeliverTx {
+    fn from(value: DeliverTx) -> Self {
+        RawDeliverTx {
+            code: value.code.into(),
+            data: value.data.to_vec(),
+            log: value.log,
+            info: value.info,
+            gas_wanted: value.gas_wanted.value() as i64,
+            gas_used: value.gas_used.value() as i64,
+            events: value.events.into_iter().map(Into::into).collect(),
+            codespace: value.codespace,
+        }
+    }
+}
+
+/// Outcome of executing a block
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EndBlock {
+    /// Changes to the validator set
+    pub validator_updates: Vec<validator::Update>,
+
+    /// Changes to the consensus parameters, if any
+    pub consensus_param_updates: Option<consensus::Params>,
+
+    /// Events emitted by the application
+    pub events: Vec<Event>,
+}
+
+impl TryFrom<RawEndBlock> for EndBlock {
+    type Error = Error;
+
+    fn try_from(value: RawEndBlock) -> Result<Self, Self::Error> {
+        Ok(EndBlock {
+            validator_updates: value
+                .validator_updates
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            consensus_param_updates: value
+                .consensus_param_updates
+                .map(TryInto::try_into)
+                .transpose()?,
+            events: value.events.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+impl From<EndBlock> for RawEndBlock {
+    fn from(value: EndBlock) -> Self {
+        RawEndBlock {
+            validator_updates: value
+                .validator_updates
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            consensus_param_updates: value.consensus_param_updates.map(Into::into),
+            events: value.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Outcome of committing the state resulting from executing a block
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Commit {
+    /// Hash of the application's state (the app hash of the next block)
+    pub data: Bytes,
+
+    /// Height of the earliest block to retain, or 0 to retain all blocks
+    pub retain_height: block::Height,
+}
+
+impl Default for Commit {
+    fn default() -> Self {
+        Commit {
+            data: Bytes::new(),
+            retain_height: block::Height::from(0_u32),
+        }
+    }
+}
+
+impl TryFrom<RawCommit> for Commit {
+    type Error = Error;
+
+    fn try_from(value: RawCommit) -> Result<Self, Self::Error> {
+        Ok(Commit {
+            data: value.data.into(),
+            retain_height: value.retain_height.try_into()?,
+        })
+    }
+}
+
+impl From<Commit> for RawCommit {
+    fn from(value: Commit) -> Self {
+        RawCommit {
+            data: value.data.to_vec(),
+            retain_height: value.retain_height.into(),
+        }
+    }
+}
+
+/// The application's local snapshots
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ListSnapshots {
+    /// The snapshots
+    pub snapshots: Vec<Snapshot>,
+}
+
+impl TryFrom<RawListSnapshots> for ListSnapshots {
+    type Error = Error;
+
+    fn try_from(value: RawListSnapshots) -> Result<Self, Self::Error> {
+        Ok(ListSnapshots {
+            snapshots: value
+                .snapshots
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<ListSnapshots> for RawListSnapshots {
+    fn from(value: ListSnapshots) -> Self {
+        RawListSnapshots {
+            snapshots: value.snapshots.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Whether the application accepts a snapshot offered to it
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OfferSnapshot {
+    /// Unknown result, which aborts state sync
+    Unknown,
+
+    /// The snapshot is accepted: start applying its chunks
+    Accept,
+
+    /// Abort state sync altogether
+    Abort,
+
+    /// Reject this snapshot, and try others
+    Reject,
+
+    /// Reject all the snapshots of this format
+    RejectFormat,
+
+    /// Reject all the snapshots of the peer which sent this one
+    RejectSender,
+}
+
+impl Default for OfferSnapshot {
+    fn default() -> Self {
+        OfferSnapshot::Unknown
+    }
+}
+
+impl TryFrom<RawOfferSnapshot> for OfferSnapshot {
+    type Error = Error;
+
+    fn try_from(value: RawOfferSnapshot) -> Result<Self, Self::Error> {
+        Ok(match RawOfferSnapshotResult::from_i32(value.result) {
+            Some(RawOfferSnapshotResult::Unknown) => OfferSnapshot::Unknown,
+            Some(RawOfferSnapshotResult::Accept) => OfferSnapshot::Accept,
+            Some(RawOfferSnapshotResult::Abort) => OfferSnapshot::Abort,
+            Some(RawOfferSnapshotResult::Reject) => OfferSnapshot::Reject,
+            Some(RawOfferSnapshotResult::RejectFormat) => OfferSnapshot::RejectFormat,
+            Some(RawOfferSnapshotResult::RejectSender) => OfferSnapshot::RejectSender,
+            None => fail!(
+                Kind::InvalidAbciMessage,
+                "unknown OfferSnapshot result {}",
+                value.result
+            ),
+        })
+    }
+}
+
+impl From<OfferSnapshot> for RawOfferSnapshot {
+    fn from(value: OfferSnapshot) -> Self {
+        let result = match value {
+            OfferSnapshot::Unknown => RawOfferSnapshotResult::Unknown,
+            OfferSnapshot::Accept => RawOfferSnapshotResult::Accept,
+            OfferSnapshot::Abort => RawOfferSnapshotResult::Abort,
+            OfferSnapshot::Reject => RawOfferSnapshotResult::Reject,
+            OfferSnapshot::RejectFormat => RawOfferSnapshotResult::RejectFormat,
+            OfferSnapshot::RejectSender => RawOfferSnapshotResult::RejectSender,
+        };
+        RawOfferSnapshot {
+            result: result as i32,
+        }
+    }
+}
+
+/// A chunk of a local snapshot
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoadSnapshotChunk {
+    /// The chunk's contents
+    pub chunk: Bytes,
+}
+
+impl From<RawLoadSnapshotChunk> for LoadSnapshotChunk {
+    fn from(value: RawLoadSnapshotChunk) -> Self {
+        LoadSnapshotChunk {
+            chunk: value.chunk.into(),
+        }
+    }
+}
+
+impl From<LoadSnapshotChunk> for RawLoadSnapshotChunk {
+    fn from(value: LoadSnapshotChunk) -> Self {
+        RawLoadSnapshotChunk {
+            chunk: value.chunk.to_vec(),
+        }
+    }
+}
+
+/// Outcome of applying a snapshot chunk
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ApplySnapshotChunkResult {
+    /// Unknown result, which aborts state sync
+    Unknown,
+
+    /// The chunk was applied
+    Accept,
+
+    /// Abort state sync altogether
+    Abort,
+
+    /// Retry applying this chunk
+    Retry,
+
+    /// Retry applying the whole snapshot
+    RetrySnapshot,
+
+    /// Reject this snapshot, and try others
+    RejectSnapshot,
+}
+
+impl Default for ApplySnapshotChunkResult {
+    fn default() -> Self {
+        ApplySnapshotChunkResult::Unknown
+    }
+}
+
+impl TryFrom<i32> for ApplySnapshotChunkResult {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Ok(match RawApplySnapshotChunkResult::from_i32(value) {
+            Some(RawApplySnapshotChunkResult::Unknown) => ApplySnapshotChunkResult::Unknown,
+            Some(RawApplySnapshotChunkResult::Accept) => ApplySnapshotChunkResult::Accept,
+            Some(RawApplySnapshotChunkResult::Abort) => ApplySnapshotChunkResult::Abort,
+            Some(RawApplySnapshotChunkResult::Retry) => ApplySnapshotChunkResult::Retry,
+            Some(RawApplySnapshotChunkResult::RetrySnapshot) => {
+                ApplySnapshotChunkResult::RetrySnapshot
+            }
+            Some(RawApplySnapshotChunkResult::RejectSnapshot) => {
+                ApplySnapshotChunkResult::RejectSnapshot
+            }
+            None => fail!(
+                Kind::InvalidAbciMessage,
+                "unknown ApplySnapshotChunk result {}",
+                value
+            ),
+        })
+    }
+}
+
+impl From<ApplySnapshotChunkResult> for i32 {
+    fn from(value: ApplySnapshotChunkResult) -> Self {
+        let result = match value {
+            ApplySnapshotChunkResult::Unknown => RawApplySnapshotChunkResult::Unknown,
+            ApplySnapshotChunkResult::Accept => RawApplySnapshotChunkResult::Accept,
+            ApplySnapshotChunkResult::Abort => RawApplySnapshotChunkResult::Abort,
+            ApplySnapshotChunkResult::Retry => RawApplySnapshotChunkResult::Retry,
+            ApplySnapshotChunkResult::RetrySnapshot => RawApplySnapshotChunkResult::RetrySnapshot,
+            ApplySnapshotChunkResult::RejectSnapshot => RawApplySnapshotChunkResult::RejectSnapshot,
+        };
+        result as i32
+    }
+}
+
+/// Outcome of applying a snapshot chunk, along with the chunks to fetch
+/// again and the peers to ban, if any
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ApplySnapshotChunk {
+    /// Outcome of applying the chunk
+    pub result: ApplySnapshotChunkResult,
+
+    /// Indexes of the chunks to fetch again (and apply again, if they were
+    /// already applied)
+    pub refetch_chunks: Vec<u32>,
+
+    /// IDs of the peers to reject snapshot chunks from
+    pub reject_senders: Vec<String>,
+}
+
+impl TryFrom<RawApplySnapshotChunk> for ApplySnapshotChunk {
+    type Error = Error;
+
+    fn try_from(value: RawApplySnapshotChunk) -> Result<Self, Self::Error> {
+        Ok(ApplySnapshotChunk {
+            result: value.result.try_into()?,
+            refetch_chunks: value.refetch_chunks,
+            reject_senders: value.reject_senders,
+        })
+    }
+}
+
+impl From<ApplySnapshotChunk> for RawApplySnapshotChunk {
+    fn from(value: ApplySnapshotChunk) -> Self {
+        RawApplySnapshotChunk {
+            result: value.result.into(),
+            refetch_chunks: value.refetch_chunks,
+            reject_senders: value.reject_senders,
+        }
+    }
+}
+
+/// All ABCI responses
+#[derive(Clone, Debug, PartialEq)]
+pub enum Response {
+    /// An unexpected error while handling a request
+    Exception(Exception),
+
+    /// The message of an echo request
+    Echo(Echo),
+
+    /// All the pending responses were flushed
+    Flush,
+
+    /// Information about the application
+    Info(Info),
+
+    /// Outcome of setting an application option
+    SetOption(SetOption),
+
+    /// Outcome of initializing the blockchain
+    InitChain(InitChain),
+
+    /// Result of a query
+    Query(Query),
+
+    /// Outcome of beginning to execute a block
+    BeginBlock(BeginBlock),
+
+    /// Outcome of checking a transaction
+    CheckTx(CheckTx),
+
+    /// Outcome of executing a transaction
+    DeliverTx(DeliverTx),
+
+    /// Outcome of executing a block
+    EndBlock(EndBlock),
+
+    /// Outcome of committing a block
+    Commit(Commit),
+
+    /// The application's local snapshots
+    ListSnapshots(ListSnapshots),
+
+    /// Whether the application accepts a snapshot
+    OfferSnapshot(OfferSnapshot),
+
+    /// A chunk of a local snapshot
+    LoadSnapshotChunk(LoadSnapshotChunk),
+
+    /// Outcome of applying a snapshot chunk
+    ApplySnapshotChunk(ApplySnapshotChunk),
+}
+
+impl Protobuf<RawResponse> for Response {}
+
+impl TryFrom<RawResponse> for Response {
+    type Error = Error;
+
+    fn try_from(value: RawResponse) -> Result<Self, Self::Error> {
+        let value = value
+            .value
+            .ok_or_else(|| Kind::InvalidAbciMessage.context("empty response"))?;
+        Ok(match value {
+            Value::Exception(res) => Response::Exception(res.into()),
+            Value::Echo(res) => Response::Echo(res.into()),
+            Value::Flush(_) => Response::Flush,
+            Value::Info(res) => Response::Info(res.try_into()?),
+            Value::SetOption(res) => Response::SetOption(res.into()),
+            Value::InitChain(res) => Response::InitChain(res.try_into()?),
+            Value::Query(res) => Response::Query(res.try_into()?),
+            Value::BeginBlock(res) => Response::BeginBlock(res.into()),
+            Value::CheckTx(res) => Response::CheckTx(res.try_into()?),
+            Value::DeliverTx(res) => Response::DeliverTx(res.try_into()?),
+            Value::EndBlock(res) => Response::EndBlock(res.try_into()?),
+            Value::Commit(res) => Response::Commit(res.try_into()?),
+            Value::ListSnapshots(res) => Response::ListSnapshots(res.try_into()?),
+            Value::OfferSnapshot(res) => Response::OfferSnapshot(res.try_into()?),
+            Value::LoadSnapshotChunk(res) => Response::LoadSnapshotChunk(res.into()),
+            Value::ApplySnapshotChunk(res) => Response::ApplySnapshotChunk(res.try_into()?),
+        })
+    }
+}
+
+impl From<Response> for RawResponse {
+    fn from(value: Response) -> Self {
+        let value = match value {
+            Response::Exception(res) => Value::Exception(res.into()),
+            Response::Echo(res) => Value::Echo(res.into()),
+            Response::Flush => Value::Flush(RawFlush {}),
+            Response::Info(res) => Value::Info(res.into()),
+            Response::SetOption(res) => Value::SetOption(res.into()),
+            Response::InitChain(res) => Value::InitChain(res.into()),
+            Response::Query(res) => Value::Query(res.into()),
+            Response::BeginBlock(res) => Value::BeginBlock(res.into()),
+            Response::CheckTx(res) => Value::CheckTx(res.into()),
+            Response::DeliverTx(res) => Value::DeliverTx(res.into()),
+            Response::EndBlock(res) => Value::EndBlock(res.into()),
+            Response::Commit(res) => Value::Commit(res.into()),
+            Response::ListSnapshots(res) => Value::ListSnapshots(res.into()),
+            Response::OfferSnapshot(res) => Value::OfferSnapshot(res.into()),
+            Response::LoadSnapshotChunk(res) => Value::LoadSnapshotChunk(res.into()),
+            Response::ApplySnapshotChunk(res) => Value::ApplySnapshotChunk(res.into()),
+        };
+        RawResponse { value: Some(value) }
+    }
+}
+
+// Gas amounts are signed in the proto, but can't sensibly be negative
+fn gas(value: i64) -> Result<Gas, Error> {
+    u64::try_from(value).map(Gas::from).map_err(|_| {
+        format_err!(
+            Kind::InvalidAbciMessage,
+            "gas amounts must not be negative, got {}",
+            value
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abci::request::{self, Request};
+    use crate::abci::EventAttribute;
+    use tendermint_proto::abci::{Request as RawRequest, RequestCheckTx};
+
+    #[test]
+    fn request_round_trip() {
+        let request = Request::CheckTx(request::CheckTx {
+            tx: Bytes::from_static(b"key=value"),
+            kind: crate::abci::types::CheckTxKind::Recheck,
+        });
+        let raw = RawRequest::from(request.clone());
+        match &raw.value {
+            Some(tendermint_proto::abci::request::Value::CheckTx(req)) => {
+                assert_eq!(req.tx, b"key=value".to_vec());
+                assert_eq!(req.r#type, 1);
+            }
+            value => panic!("unexpected request: {:?}", value),
+        }
+        assert_eq!(Request::try_from(raw).unwrap(), request);
+
+        let bytes = Request::Commit.encode_length_delimited_vec().unwrap();
+        assert_eq!(
+            Request::decode_length_delimited_vec(&bytes).unwrap(),
+            Request::Commit
+        );
+
+        let raw = RawRequest {
+            value: Some(tendermint_proto::abci::request::Value::CheckTx(
+                RequestCheckTx {
+                    tx: vec![],
+                    r#type: 7,
+                },
+            )),
+        };
+        assert!(Request::try_from(raw).is_err());
+        assert!(Request::try_from(RawRequest { value: None }).is_err());
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let snapshot = Snapshot {
+            height: block::Height::from(100_u32),
+            format: 1,
+            chunks: 3,
+            hash: Bytes::from_static(&[0xAB; 32]),
+            metadata: Bytes::new(),
+        };
+        let request = Request::OfferSnapshot(request::OfferSnapshot {
+            snapshot: snapshot.clone(),
+            app_hash: Bytes::from_static(&[0xCD; 32]),
+        });
+        let bytes = request.clone().encode_length_delimited_vec().unwrap();
+        assert_eq!(
+            Request::decode_length_delimited_vec(&bytes).unwrap(),
+            request
+        );
+
+        let response = Response::ListSnapshots(ListSnapshots {
+            snapshots: vec![snapshot],
+        });
+        let raw = RawResponse::from(response.clone());
+        assert_eq!(Response::try_from(raw).unwrap(), response);
+
+        let response = Response::ApplySnapshotChunk(ApplySnapshotChunk {
+            result: ApplySnapshotChunkResult::Retry,
+            refetch_chunks: vec![1, 2],
+            reject_senders: vec!["peer".to_string()],
+        });
+        let raw = RawResponse::from(response.clone());
+        assert_eq!(Response::try_from(raw).unwrap(), response);
+    }
+
+    #[test]
+    fn response_round_trip() {
+        let response = Response::DeliverTx(DeliverTx {
+            code: Code::Err(3),
+            data: Bytes::from_static(b"data"),
+            gas_wanted: Gas::from(10),
+            gas_used: Gas::from(5),
+            events: vec![Event::new(
+                "app",
+                vec![EventAttribute::new("key", "value", true)],
+            )],
+            ..Default::default()
+        });
+        let raw = RawResponse::from(response.clone());
+        assert_eq!(Response::try_from(raw).unwrap(), response);
+
+        let mut raw = RawDeliverTx::from(DeliverTx::default());
+        raw.gas_used = -1;
+        assert!(DeliverTx::try_from(raw).is_err());
+
+        let mut raw = RawOfferSnapshot::from(OfferSnapshot::Reject);
+        assert_eq!(raw.result, 3);
+        raw.result = 42;
+        assert!(OfferSnapshot::try_from(raw).is_err());
+
+        let response = Response::Commit(Commit {
+            data: Bytes::from_static(&[1, 2, 3]),
+            ..Default::default()
+        });
+        let bytes = response.encode_length_delimited_vec().unwrap();
+        assert_eq!(
+            Response::decode_length_delimited_vec(&bytes).unwrap(),
+            response
+        );
+    }
+}
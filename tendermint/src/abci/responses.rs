@@ -1,9 +1,14 @@
 //! ABCI response types used by the `/block_results` RPC endpoint.
 
-use super::{code::Code, data::Data, gas::Gas, info::Info, log::Log, tag::Tag};
-use crate::{consensus, serializers, validator};
+use super::{
+    code::Code, data::Data, event::Event, gas::Gas, info::Info, log::Log, tag::Tag, transaction,
+};
+use crate::prelude::*;
+use crate::{block, consensus, serializers, validator, Error, Kind};
+use core::convert::{TryFrom, TryInto};
+use core::fmt::{self, Display};
 use serde::{Deserialize, Deserializer, Serialize};
-use std::fmt::{self, Display};
+use tendermint_proto::abci::{ResponseDeliverTx as RawResponseDeliverTx, TxResult as RawTxResult};
 
 /// Responses for ABCI calls which occur during block processing.
 ///
@@ -74,15 +79,68 @@ pub struct DeliverTx {
     pub codespace: Codespace,
 }
 
-/// Event
+/// Result of executing a transaction, as named by newer Tendermint versions
+pub type ExecTxResult = DeliverTx;
+
+impl From<RawResponseDeliverTx> for DeliverTx {
+    fn from(value: RawResponseDeliverTx) -> Self {
+        DeliverTx {
+            code: value.code.into(),
+            data: value.data.into(),
+            log: Log::from(value.log.as_str()),
+            info: Info::from(value.info.as_str()),
+            gas_wanted: gas(value.gas_wanted),
+            gas_used: gas(value.gas_used),
+            events: value.events.into_iter().map(Into::into).collect(),
+            codespace: Codespace::from(value.codespace.as_str()),
+        }
+    }
+}
+
+// Gas amounts are signed in the proto, but can't sensibly be negative
+fn gas(value: i64) -> Gas {
+    Gas::from(u64::try_from(value).unwrap_or_default())
+}
+
+/// Result of a transaction included in a block, as stored by the transaction
+/// indexer.
+///
+/// This type corresponds to the `TxResult` proto from:
+///
+/// <https://github.com/tendermint/tendermint/blob/master/proto/tendermint/abci/types.proto>
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Event {
-    /// Event type
-    #[serde(rename = "type")]
-    pub type_str: String,
+pub struct TxResult {
+    /// Height of the block containing the transaction
+    pub height: block::Height,
+
+    /// Index of the transaction within the block
+    pub index: u32,
+
+    /// Transaction
+    pub tx: transaction::Transaction,
 
-    /// Attributes
-    pub attributes: Vec<Tag>,
+    /// Result of executing the transaction
+    pub result: ExecTxResult,
+}
+
+impl TxResult {
+    /// Compute the hash of the transaction
+    pub fn hash(&self) -> transaction::Hash {
+        self.tx.hash()
+    }
+}
+
+impl TryFrom<RawTxResult> for TxResult {
+    type Error = Error;
+
+    fn try_from(value: RawTxResult) -> Result<Self, Self::Error> {
+        Ok(TxResult {
+            height: value.height.try_into()?,
+            index: value.index,
+            tx: value.tx.into(),
+            result: value.result.ok_or(Kind::MissingData)?.into(),
+        })
+    }
 }
 
 /// Begin block response.
@@ -149,3 +207,56 @@ impl Default for Codespace {
         Self(String::new())
     }
 }
+
+impl From<&str> for Codespace {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint_proto::abci::{Event as RawEvent, EventAttribute};
+
+    #[test]
+    fn tx_result_from_proto() {
+        let raw = RawTxResult {
+            height: 10,
+            index: 2,
+            tx: b"abc".to_vec(),
+            result: Some(RawResponseDeliverTx {
+                code: 1,
+                log: "failed".to_string(),
+                gas_wanted: 10,
+                gas_used: -1,
+                events: vec![RawEvent {
+                    r#type: "transfer".to_string(),
+                    attributes: vec![EventAttribute {
+                        key: b"amount".to_vec(),
+                        value: b"100".to_vec(),
+                        index: true,
+                    }],
+                }],
+                ..Default::default()
+            }),
+        };
+        let tx_result = TxResult::try_from(raw.clone()).unwrap();
+        assert_eq!(tx_result.height, block::Height::from(10_u32));
+        assert_eq!(
+            tx_result.hash().to_string(),
+            "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+        );
+        assert_eq!(tx_result.result.code, Code::Err(1));
+        assert_eq!(tx_result.result.gas_wanted.value(), 10);
+        assert_eq!(tx_result.result.gas_used.value(), 0);
+        assert_eq!(tx_result.result.events[0].attributes[0].value, "100");
+        assert!(tx_result.result.events[0].attributes[0].index);
+
+        assert!(TxResult::try_from(RawTxResult {
+            result: None,
+            ..raw
+        })
+        .is_err());
+    }
+}
@@ -1,8 +1,9 @@
 //! Tags
 
 use crate::error::Error;
+use crate::prelude::*;
+use core::{fmt, str::FromStr};
 use serde::{Deserialize, Serialize};
-use std::{fmt, str::FromStr};
 use tendermint_proto::serializers::bytes::base64string;
 
 /// Tags
@@ -3,8 +3,10 @@
 mod hash;
 
 pub use self::hash::Hash;
+use crate::prelude::*;
+use core::{fmt, slice};
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
-use std::{fmt, slice};
+use sha2::{Digest, Sha256};
 use subtle_encoding::base64;
 use tendermint_proto::types::Data as RawData;
 
@@ -30,6 +32,14 @@ impl Transaction {
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_slice()
     }
+
+    /// Compute the hash of this transaction (the SHA-256 digest of its
+    /// contents), by which it is identified e.g. by the `/tx` RPC endpoint
+    pub fn hash(&self) -> Hash {
+        let mut hash_bytes = [0u8; hash::LENGTH];
+        hash_bytes.copy_from_slice(&Sha256::digest(self.as_bytes()));
+        Hash::new(hash_bytes)
+    }
 }
 
 impl AsRef<[u8]> for Transaction {
@@ -123,7 +133,8 @@ impl AsRef<[Transaction]> for Data {
 
 #[cfg(test)]
 mod tests {
-    use super::Transaction;
+    use super::{Hash, Transaction};
+    use crate::prelude::*;
 
     #[test]
     fn upper_hex_serialization() {
@@ -131,4 +142,13 @@ mod tests {
         let tx_hex = format!("{:X}", &tx);
         assert_eq!(&tx_hex, "FF01FE02");
     }
+
+    #[test]
+    fn transaction_hash() {
+        let tx = Transaction::from(b"abc".to_vec());
+        let expected: Hash = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+            .parse()
+            .unwrap();
+        assert_eq!(tx.hash(), expected);
+    }
 }
@@ -1,11 +1,12 @@
 //! Transaction hashes
 
 use crate::error::{Error, Kind};
-use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use std::{
+use crate::prelude::*;
+use core::{
     fmt::{self, Debug, Display},
     str::FromStr,
 };
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use subtle::{self, ConstantTimeEq};
 use subtle_encoding::hex;
 
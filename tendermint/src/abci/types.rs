@@ -0,0 +1,297 @@
+//! Types shared by several ABCI requests and responses
+
+use crate::prelude::*;
+use crate::{account, block, vote, Error, Kind, Time};
+use bytes::Bytes;
+use core::convert::{TryFrom, TryInto};
+use tendermint_proto::abci::{
+    CheckTxType as RawCheckTxType, Evidence as RawMisbehavior, EvidenceType as RawMisbehaviorKind,
+    LastCommitInfo as RawLastCommitInfo, Snapshot as RawSnapshot, Validator as RawValidator,
+    VoteInfo as RawVoteInfo,
+};
+
+/// A validator, as identified to the application by its address
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Validator {
+    /// Address of the validator (the first 20 bytes of the SHA-256 digest of
+    /// its public key)
+    pub address: account::Id,
+
+    /// Voting power of the validator
+    pub power: vote::Power,
+}
+
+impl TryFrom<RawValidator> for Validator {
+    type Error = Error;
+
+    fn try_from(value: RawValidator) -> Result<Self, Self::Error> {
+        Ok(Validator {
+            address: value.address.try_into()?,
+            power: value.power.try_into()?,
+        })
+    }
+}
+
+impl From<Validator> for RawValidator {
+    fn from(value: Validator) -> Self {
+        RawValidator {
+            address: value.address.into(),
+            power: value.power.into(),
+        }
+    }
+}
+
+/// Whether a validator signed the last block
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteInfo {
+    /// The validator
+    pub validator: Validator,
+
+    /// Whether the validator signed the last block
+    pub signed_last_block: bool,
+}
+
+impl TryFrom<RawVoteInfo> for VoteInfo {
+    type Error = Error;
+
+    fn try_from(value: RawVoteInfo) -> Result<Self, Self::Error> {
+        Ok(VoteInfo {
+            validator: value
+                .validator
+                .ok_or_else(|| Kind::InvalidAbciMessage.context("missing validator"))?
+                .try_into()?,
+            signed_last_block: value.signed_last_block,
+        })
+    }
+}
+
+impl From<VoteInfo> for RawVoteInfo {
+    fn from(value: VoteInfo) -> Self {
+        RawVoteInfo {
+            validator: Some(value.validator.into()),
+            signed_last_block: value.signed_last_block,
+        }
+    }
+}
+
+/// The votes of the validators of the last block, i.e. which of them signed
+/// its commit
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LastCommitInfo {
+    /// Round in which the last block was committed
+    pub round: block::Round,
+
+    /// Votes of the validators
+    pub votes: Vec<VoteInfo>,
+}
+
+impl TryFrom<RawLastCommitInfo> for LastCommitInfo {
+    type Error = Error;
+
+    fn try_from(value: RawLastCommitInfo) -> Result<Self, Self::Error> {
+        Ok(LastCommitInfo {
+            round: value.round.try_into()?,
+            votes: value
+                .votes
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<LastCommitInfo> for RawLastCommitInfo {
+    fn from(value: LastCommitInfo) -> Self {
+        RawLastCommitInfo {
+            round: value.round.into(),
+            votes: value.votes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Kinds of validator misbehavior
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MisbehaviorKind {
+    /// Unknown kind of misbehavior
+    Unknown,
+
+    /// Voting for two different blocks at the same height and round
+    DuplicateVote,
+
+    /// Signing a conflicting block, so as to attack light clients
+    LightClientAttack,
+}
+
+impl Default for MisbehaviorKind {
+    fn default() -> Self {
+        MisbehaviorKind::Unknown
+    }
+}
+
+impl TryFrom<i32> for MisbehaviorKind {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match RawMisbehaviorKind::from_i32(value) {
+            Some(RawMisbehaviorKind::Unknown) => Ok(MisbehaviorKind::Unknown),
+            Some(RawMisbehaviorKind::DuplicateVote) => Ok(MisbehaviorKind::DuplicateVote),
+            Some(RawMisbehaviorKind::LightClientAttack) => Ok(MisbehaviorKind::LightClientAttack),
+            None => Err(format_err!(
+                Kind::InvalidAbciMessage,
+                "unknown misbehavior kind {}",
+                value
+            )
+            .into()),
+        }
+    }
+}
+
+impl From<MisbehaviorKind> for i32 {
+    fn from(value: MisbehaviorKind) -> Self {
+        let kind = match value {
+            MisbehaviorKind::Unknown => RawMisbehaviorKind::Unknown,
+            MisbehaviorKind::DuplicateVote => RawMisbehaviorKind::DuplicateVote,
+            MisbehaviorKind::LightClientAttack => RawMisbehaviorKind::LightClientAttack,
+        };
+        kind as i32
+    }
+}
+
+/// Misbehavior of a validator, as found in evidence committed to the
+/// blockchain, for the application to punish it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Misbehavior {
+    /// Kind of misbehavior
+    pub kind: MisbehaviorKind,
+
+    /// The misbehaving validator
+    pub validator: Validator,
+
+    /// Height at which the validator misbehaved
+    pub height: block::Height,
+
+    /// Time of the block at which the validator misbehaved
+    pub time: Time,
+
+    /// Total voting power of the validator set at the height of the
+    /// misbehavior
+    pub total_voting_power: vote::Power,
+}
+
+impl TryFrom<RawMisbehavior> for Misbehavior {
+    type Error = Error;
+
+    fn try_from(value: RawMisbehavior) -> Result<Self, Self::Error> {
+        Ok(Misbehavior {
+            kind: value.r#type.try_into()?,
+            validator: value
+                .validator
+                .ok_or_else(|| Kind::InvalidAbciMessage.context("missing validator"))?
+                .try_into()?,
+            height: value.height.try_into()?,
+            time: value.time.ok_or(Kind::MissingTimestamp)?.try_into()?,
+            total_voting_power: value.total_voting_power.try_into()?,
+        })
+    }
+}
+
+impl From<Misbehavior> for RawMisbehavior {
+    fn from(value: Misbehavior) -> Self {
+        RawMisbehavior {
+            r#type: value.kind.into(),
+            validator: Some(value.validator.into()),
+            height: value.height.into(),
+            time: Some(value.time.into()),
+            total_voting_power: value.total_voting_power.into(),
+        }
+    }
+}
+
+/// Whether a transaction is checked for the first time, or rechecked after a
+/// block was committed
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CheckTxKind {
+    /// A new transaction
+    New,
+
+    /// A transaction which remained in the mempool after a block was
+    /// committed
+    Recheck,
+}
+
+impl Default for CheckTxKind {
+    fn default() -> Self {
+        CheckTxKind::New
+    }
+}
+
+impl TryFrom<i32> for CheckTxKind {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match RawCheckTxType::from_i32(value) {
+            Some(RawCheckTxType::New) => Ok(CheckTxKind::New),
+            Some(RawCheckTxType::Recheck) => Ok(CheckTxKind::Recheck),
+            None => {
+                Err(format_err!(Kind::InvalidAbciMessage, "unknown CheckTx type {}", value).into())
+            }
+        }
+    }
+}
+
+impl From<CheckTxKind> for i32 {
+    fn from(value: CheckTxKind) -> Self {
+        let kind = match value {
+            CheckTxKind::New => RawCheckTxType::New,
+            CheckTxKind::Recheck => RawCheckTxType::Recheck,
+        };
+        kind as i32
+    }
+}
+
+/// A snapshot of the application's state, for state sync
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    /// Height at which the snapshot was taken
+    pub height: block::Height,
+
+    /// Application-specific format of the snapshot, which allows the
+    /// application to change its snapshot format while still restoring
+    /// older snapshots
+    pub format: u32,
+
+    /// Number of chunks in the snapshot
+    pub chunks: u32,
+
+    /// Arbitrary snapshot hash, only compared for equality between peers
+    pub hash: Bytes,
+
+    /// Arbitrary application metadata, e.g. the hashes of the chunks
+    pub metadata: Bytes,
+}
+
+impl TryFrom<RawSnapshot> for Snapshot {
+    type Error = Error;
+
+    fn try_from(value: RawSnapshot) -> Result<Self, Self::Error> {
+        Ok(Snapshot {
+            height: value.height.try_into()?,
+            format: value.format,
+            chunks: value.chunks,
+            hash: value.hash.into(),
+            metadata: value.metadata.into(),
+        })
+    }
+}
+
+impl From<Snapshot> for RawSnapshot {
+    fn from(value: Snapshot) -> Self {
+        RawSnapshot {
+            height: value.height.into(),
+            format: value.format,
+            chunks: value.chunks,
+            hash: value.hash.to_vec(),
+            metadata: value.metadata.to_vec(),
+        }
+    }
+}
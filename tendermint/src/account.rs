@@ -1,25 +1,30 @@
 //! Tendermint accounts
 
+use crate::prelude::*;
 use crate::{
     error::{Error, Kind},
     public_key::Ed25519,
 };
 
-use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use sha2::{Digest, Sha256};
-use std::{
+use core::{
     convert::TryInto,
     fmt::{self, Debug, Display},
     str::FromStr,
 };
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
 use subtle::{self, ConstantTimeEq};
-use subtle_encoding::hex;
+use subtle_encoding::{bech32, hex};
 
+#[cfg(feature = "bls12381")]
+use crate::public_key::Bls12381;
 #[cfg(feature = "secp256k1")]
 use crate::public_key::Secp256k1;
+#[cfg(feature = "sr25519")]
+use crate::public_key::Sr25519;
+use core::convert::TryFrom;
 #[cfg(feature = "secp256k1")]
 use ripemd160::Ripemd160;
-use std::convert::TryFrom;
 use tendermint_proto::Protobuf;
 
 /// Size of an  account ID in bytes
@@ -60,6 +65,28 @@ impl Id {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0[..]
     }
+
+    /// Serialize this ID as Bech32 with the given human readable prefix,
+    /// e.g. `cosmosvalcons` for the address of a consensus key (see
+    /// [`bech32::Prefixes`](crate::bech32::Prefixes))
+    pub fn to_bech32(&self, hrp: &str) -> String {
+        bech32::encode(hrp, self.as_bytes())
+    }
+
+    /// Parse an ID serialized as Bech32, checking that it has the given
+    /// human readable prefix
+    pub fn from_bech32(s: &str, hrp: &str) -> Result<Self, Error> {
+        let (actual_hrp, bytes) = bech32::decode(s).map_err(|e| Kind::Parse.context(e))?;
+        if actual_hrp != hrp {
+            fail!(
+                Kind::Parse,
+                "expected bech32 prefix {:?}, got {:?}",
+                hrp,
+                actual_hrp
+            );
+        }
+        bytes.try_into()
+    }
 }
 
 impl AsRef<[u8]> for Id {
@@ -110,6 +137,24 @@ impl From<Ed25519> for Id {
     }
 }
 
+// SHA256(pk)[:20]
+#[cfg(feature = "sr25519")]
+impl From<Sr25519> for Id {
+    fn from(pk: Sr25519) -> Id {
+        let digest = Sha256::digest(&pk.to_bytes());
+        Id(digest[..LENGTH].try_into().unwrap())
+    }
+}
+
+// SHA256(pk)[:20]
+#[cfg(feature = "bls12381")]
+impl From<Bls12381> for Id {
+    fn from(pk: Bls12381) -> Id {
+        let digest = Sha256::digest(pk.as_bytes());
+        Id(digest[..LENGTH].try_into().unwrap())
+    }
+}
+
 /// Decode account ID from hex
 impl FromStr for Id {
     type Err = Error;
@@ -171,6 +216,18 @@ mod tests {
         assert_eq!(id_bytes.ct_eq(&id).unwrap_u8(), 1);
     }
 
+    #[test]
+    fn test_bech32_id() {
+        let id = Id::from_str("E307483A08C3954474A2600F3EC8DC99613AE142").unwrap();
+        let bech32_id = "cosmosvalcons1uvr5swsgcw25ga9zvq8najxun9sn4c2zr5g5yu";
+        assert_eq!(id.to_bech32("cosmosvalcons"), bech32_id);
+        assert_eq!(Id::from_bech32(bech32_id, "cosmosvalcons").unwrap(), id);
+        assert_eq!(
+            Id::from_bech32(bech32_id, "cosmos").unwrap_err().kind(),
+            &Kind::Parse
+        );
+    }
+
     #[test]
     #[cfg(feature = "secp256k1")]
     fn test_secp_id() {
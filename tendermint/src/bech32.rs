@@ -0,0 +1,71 @@
+//! Human-readable parts used to render addresses and public keys as Bech32,
+//! the way the Cosmos ecosystem does.
+//!
+//! See [`account::Id::to_bech32`](crate::account::Id::to_bech32) and
+//! [`PublicKey::to_bech32`](crate::PublicKey::to_bech32).
+
+use crate::prelude::*;
+
+/// The human-readable parts (HRPs) used by a chain for the Bech32 encodings
+/// of its addresses and public keys
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Prefixes {
+    /// Account addresses, e.g. `cosmos`
+    pub account_address: String,
+
+    /// Account public keys, e.g. `cosmospub`
+    pub account_public_key: String,
+
+    /// Validator operator addresses, e.g. `cosmosvaloper`
+    pub validator_address: String,
+
+    /// Validator operator public keys, e.g. `cosmosvaloperpub`
+    pub validator_public_key: String,
+
+    /// Consensus (i.e. validator signing key) addresses, e.g. `cosmosvalcons`
+    pub consensus_address: String,
+
+    /// Consensus public keys, e.g. `cosmosvalconspub`
+    pub consensus_public_key: String,
+}
+
+impl Prefixes {
+    /// Derive all the human-readable parts from the main prefix of a chain,
+    /// as the Cosmos SDK does: `osmo` gives `osmo`, `osmopub`, `osmovaloper`,
+    /// `osmovaloperpub`, `osmovalcons` and `osmovalconspub`.
+    pub fn new(main_prefix: &str) -> Self {
+        Prefixes {
+            account_address: main_prefix.to_string(),
+            account_public_key: format!("{}pub", main_prefix),
+            validator_address: format!("{}valoper", main_prefix),
+            validator_public_key: format!("{}valoperpub", main_prefix),
+            consensus_address: format!("{}valcons", main_prefix),
+            consensus_public_key: format!("{}valconspub", main_prefix),
+        }
+    }
+
+    /// The human-readable parts used by the Cosmos Hub
+    pub fn cosmos() -> Self {
+        Self::new("cosmos")
+    }
+}
+
+impl Default for Prefixes {
+    fn default() -> Self {
+        Self::cosmos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_prefixes() {
+        let prefixes = Prefixes::new("osmo");
+        assert_eq!(prefixes.account_public_key, "osmopub");
+        assert_eq!(prefixes.validator_address, "osmovaloper");
+        assert_eq!(prefixes.consensus_public_key, "osmovalconspub");
+        assert_eq!(Prefixes::default().consensus_address, "cosmosvalcons");
+    }
+}
@@ -22,8 +22,8 @@ pub use self::{
     size::Size,
 };
 use crate::{abci::transaction, evidence, Error, Kind};
+use core::convert::{TryFrom, TryInto};
 use serde::{Deserialize, Serialize};
-use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::Block as RawBlock;
 use tendermint_proto::Protobuf;
 
@@ -74,7 +74,7 @@ impl TryFrom<RawBlock> for Block {
         //}
         Ok(Block {
             header,
-            data: value.data.ok_or(Kind::MissingData)?.try_into()?,
+            data: value.data.ok_or(Kind::MissingData)?.into(),
             evidence: value.evidence.ok_or(Kind::MissingEvidence)?.try_into()?,
             last_commit,
         })
@@ -118,6 +118,15 @@ impl Block {
         })
     }
 
+    /// Split this block into parts of the given size, e.g.
+    /// [`parts::BLOCK_PART_SIZE_BYTES`], for gossiping.
+    pub fn make_part_set(&self, part_size: usize) -> Result<parts::Set, Error> {
+        let bytes = self
+            .encode_vec()
+            .map_err(|e| Kind::InvalidBlock.context(e))?;
+        Ok(parts::Set::from_data(&bytes, part_size))
+    }
+
     /// Get header
     pub fn header(&self) -> &Header {
         &self.header
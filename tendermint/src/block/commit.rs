@@ -1,10 +1,13 @@
 //! Commits to a Tendermint blockchain
 
 use crate::block::commit_sig::CommitSig;
-use crate::block::{Height, Id, Round};
-use crate::{Error, Kind};
+use crate::block::{Header, Height, Id, Round};
+use crate::chain::Id as ChainId;
+use crate::prelude::*;
+use crate::vote::{self, ValidatorIndex, Vote};
+use crate::{account, private_key, validator, Error, Kind, Time};
+use core::convert::TryFrom;
 use serde::{Deserialize, Serialize};
-use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::Commit as RawCommit;
 
 /// Commit contains the justification (ie. a set of signatures) that a block was committed by a set
@@ -28,31 +31,39 @@ pub struct Commit {
     pub signatures: Vec<CommitSig>,
 }
 
-impl TryFrom<RawCommit> for Commit {
-    type Error = Error;
+tendermint_pb_modules! {
+    use super::Commit;
+    use crate::block::commit_sig::CommitSig;
+    use crate::{Error, Kind};
+    use core::convert::{TryFrom, TryInto};
+    use pb::types::Commit as RawCommit;
 
-    fn try_from(value: RawCommit) -> Result<Self, Self::Error> {
-        let signatures: Result<Vec<CommitSig>, Error> = value
-            .signatures
-            .into_iter()
-            .map(TryFrom::try_from)
-            .collect();
-        Ok(Self {
-            height: value.height.try_into()?,
-            round: value.round.try_into()?,
-            block_id: value.block_id.ok_or(Kind::InvalidBlock)?.try_into()?, /* gogoproto.nullable = false */
-            signatures: signatures?,
-        })
+    impl TryFrom<RawCommit> for Commit {
+        type Error = Error;
+
+        fn try_from(value: RawCommit) -> Result<Self, Self::Error> {
+            let signatures: Result<Vec<CommitSig>, Error> = value
+                .signatures
+                .into_iter()
+                .map(TryFrom::try_from)
+                .collect();
+            Ok(Self {
+                height: value.height.try_into()?,
+                round: value.round.try_into()?,
+                block_id: value.block_id.ok_or(Kind::InvalidBlock)?.try_into()?, /* gogoproto.nullable = false */
+                signatures: signatures?,
+            })
+        }
     }
-}
 
-impl From<Commit> for RawCommit {
-    fn from(value: Commit) -> Self {
-        RawCommit {
-            height: value.height.into(),
-            round: value.round.into(),
-            block_id: Some(value.block_id.into()),
-            signatures: value.signatures.into_iter().map(Into::into).collect(),
+    impl From<Commit> for RawCommit {
+        fn from(value: Commit) -> Self {
+            RawCommit {
+                height: value.height.into(),
+                round: value.round.into(),
+                block_id: Some(value.block_id.into()),
+                signatures: value.signatures.into_iter().map(Into::into).collect(),
+            }
         }
     }
 }
@@ -68,3 +79,137 @@ impl Default for Commit {
         }
     }
 }
+
+impl Commit {
+    /// Start building a commit, which can then be signed by the validators
+    /// in one go with [`CommitBuilder::sign`].
+    pub fn builder() -> CommitBuilder {
+        CommitBuilder::default()
+    }
+
+    /// Compute the BFT time of the block following the committed one: the
+    /// median of the timestamps of the votes in this commit, weighted by the
+    /// voting powers of their validators in the given set, as done by
+    /// Tendermint's `MedianTime`.
+    ///
+    /// Votes for nil are counted, while votes of validators which aren't part
+    /// of the set are ignored.
+    pub fn median_time(&self, validators: &validator::Set) -> Result<Time, Error> {
+        let weighted_times: Vec<_> = self
+            .signatures
+            .iter()
+            .filter_map(|sig| {
+                let validator = validators.validator(sig.validator_address()?)?;
+                Some((sig.timestamp()?, validator.power()))
+            })
+            .collect();
+        Time::weighted_median(&weighted_times).ok_or_else(|| {
+            Kind::InvalidCommit
+                .context("no vote of the validator set to compute the median time of")
+                .into()
+        })
+    }
+}
+
+/// Builder for a [`Commit`], as returned by [`Commit::builder`]
+#[derive(Clone, Debug)]
+pub struct CommitBuilder {
+    height: Height,
+    round: Round,
+    block_id: Id,
+    timestamp: Time,
+}
+
+impl Default for CommitBuilder {
+    fn default() -> Self {
+        CommitBuilder {
+            height: Height::default(),
+            round: Round::default(),
+            block_id: Id::default(),
+            timestamp: Time::unix_epoch(),
+        }
+    }
+}
+
+impl CommitBuilder {
+    /// Set the height of the committed block
+    pub fn height(mut self, height: Height) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set the round in which the block was committed
+    pub fn round(mut self, round: Round) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Set the ID of the committed block
+    pub fn block_id(mut self, block_id: Id) -> Self {
+        self.block_id = block_id;
+        self
+    }
+
+    /// Commit the block with the given header, with votes timestamped with
+    /// the header's time.
+    ///
+    /// The part set header of the block ID is left empty.
+    pub fn header(mut self, header: &Header) -> Self {
+        self.height = header.height;
+        self.block_id = Id::new(header.hash(), Default::default());
+        self.timestamp = header.time;
+        self
+    }
+
+    /// Set the timestamp of the votes
+    pub fn timestamp(mut self, timestamp: Time) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Build the commit, with a precommit for the block signed by each
+    /// validator of the set whose key is among the given ones.
+    ///
+    /// The signatures of the other validators are absent.
+    pub fn sign(
+        self,
+        chain_id: ChainId,
+        validators: &validator::Set,
+        signers: &[private_key::Ed25519],
+    ) -> Result<Commit, Error> {
+        let mut signatures = Vec::with_capacity(validators.validators().len());
+        for (index, validator) in validators.validators().iter().enumerate() {
+            let signer = signers
+                .iter()
+                .find(|signer| account::Id::from(signer.public) == validator.address);
+            let signer = match signer {
+                Some(signer) => signer,
+                None => {
+                    signatures.push(CommitSig::BlockIdFlagAbsent);
+                    continue;
+                }
+            };
+            let mut vote = Vote::builder()
+                .vote_type(vote::Type::Precommit)
+                .height(self.height)
+                .round(self.round)
+                .block_id(self.block_id)
+                .timestamp(self.timestamp)
+                .validator(validator.address, ValidatorIndex::try_from(index)?)
+                .build();
+            vote.sign(chain_id.clone(), signer)?;
+            signatures.push(CommitSig::BlockIdFlagCommit {
+                validator_address: validator.address,
+                timestamp: self.timestamp,
+                signature: vote.signature,
+            });
+        }
+
+        Ok(Commit {
+            height: self.height,
+            round: self.round,
+            block_id: self.block_id,
+            signatures,
+        })
+    }
+}
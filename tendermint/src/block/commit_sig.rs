@@ -1,11 +1,6 @@
 //! CommitSig within Commit
 
 use crate::{account, Signature, Time};
-use crate::{Error, Kind};
-use num_traits::ToPrimitive;
-use std::convert::{TryFrom, TryInto};
-use tendermint_proto::types::BlockIdFlag;
-use tendermint_proto::types::CommitSig as RawCommitSig;
 
 /// CommitSig represents a signature of a validator.
 /// It's a part of the Commit and can be used to reconstruct the vote set given the validator set.
@@ -47,6 +42,15 @@ impl CommitSig {
         }
     }
 
+    /// Get the timestamp of the vote of this validator if one was received.
+    pub fn timestamp(&self) -> Option<Time> {
+        match self {
+            Self::BlockIdFlagCommit { timestamp, .. } => Some(*timestamp),
+            Self::BlockIdFlagNil { timestamp, .. } => Some(*timestamp),
+            _ => None,
+        }
+    }
+
     /// Whether this signature is absent (no vote was received from validator)
     pub fn is_absent(&self) -> bool {
         self == &Self::BlockIdFlagAbsent
@@ -63,90 +67,98 @@ impl CommitSig {
     }
 }
 
-// Todo: https://github.com/informalsystems/tendermint-rs/issues/259 - CommitSig Timestamp can be zero time
-// Todo: https://github.com/informalsystems/tendermint-rs/issues/260 - CommitSig validator address missing in Absent vote
-impl TryFrom<RawCommitSig> for CommitSig {
-    type Error = Error;
+tendermint_pb_modules! {
+    use super::CommitSig;
+    use crate::{Error, Kind};
+    use core::convert::{TryFrom, TryInto};
+    use num_traits::ToPrimitive;
+    use pb::types::{BlockIdFlag, CommitSig as RawCommitSig};
 
-    fn try_from(value: RawCommitSig) -> Result<Self, Self::Error> {
-        if value.block_id_flag == BlockIdFlag::Absent.to_i32().unwrap() {
-            if value.timestamp.is_some() {
-                let timestamp = value.timestamp.unwrap();
-                // 0001-01-01T00:00:00.000Z translates to EPOCH-62135596800 seconds
-                if timestamp.nanos != 0 || timestamp.seconds != -62135596800 {
-                    return Err(Kind::InvalidTimestamp
-                        .context("absent commitsig has non-zero timestamp")
-                        .into());
+    // Todo: https://github.com/informalsystems/tendermint-rs/issues/259 - CommitSig Timestamp can be zero time
+    // Todo: https://github.com/informalsystems/tendermint-rs/issues/260 - CommitSig validator address missing in Absent vote
+    impl TryFrom<RawCommitSig> for CommitSig {
+        type Error = Error;
+
+        fn try_from(value: RawCommitSig) -> Result<Self, Self::Error> {
+            if value.block_id_flag == BlockIdFlag::Absent.to_i32().unwrap() {
+                if value.timestamp.is_some() {
+                    let timestamp = value.timestamp.unwrap();
+                    // 0001-01-01T00:00:00.000Z translates to EPOCH-62135596800 seconds
+                    if timestamp.nanos != 0 || timestamp.seconds != -62135596800 {
+                        return Err(Kind::InvalidTimestamp
+                            .context("absent commitsig has non-zero timestamp")
+                            .into());
+                    }
                 }
+                if !value.signature.is_empty() {
+                    return Err(Kind::InvalidSignature.into());
+                }
+                return Ok(CommitSig::BlockIdFlagAbsent);
             }
-            if !value.signature.is_empty() {
-                return Err(Kind::InvalidSignature.into());
-            }
-            return Ok(CommitSig::BlockIdFlagAbsent);
-        }
-        if value.block_id_flag == BlockIdFlag::Commit.to_i32().unwrap() {
-            if value.signature.is_empty() {
-                return Err(Kind::InvalidSignature
-                    .context("regular commitsig has no signature")
-                    .into());
-            }
-            if value.validator_address.is_empty() {
-                return Err(Kind::InvalidValidatorAddress.into());
-            }
-            return Ok(CommitSig::BlockIdFlagCommit {
-                validator_address: value.validator_address.try_into()?,
-                timestamp: value.timestamp.ok_or(Kind::NoTimestamp)?.try_into()?,
-                signature: value.signature.try_into()?,
-            });
-        }
-        if value.block_id_flag == BlockIdFlag::Nil.to_i32().unwrap() {
-            if value.signature.is_empty() {
-                return Err(Kind::InvalidSignature
-                    .context("nil commitsig has no signature")
-                    .into());
+            if value.block_id_flag == BlockIdFlag::Commit.to_i32().unwrap() {
+                if value.signature.is_empty() {
+                    return Err(Kind::InvalidSignature
+                        .context("regular commitsig has no signature")
+                        .into());
+                }
+                if value.validator_address.is_empty() {
+                    return Err(Kind::InvalidValidatorAddress.into());
+                }
+                return Ok(CommitSig::BlockIdFlagCommit {
+                    validator_address: value.validator_address.try_into()?,
+                    timestamp: value.timestamp.ok_or(Kind::NoTimestamp)?.try_into()?,
+                    signature: value.signature.try_into()?,
+                });
             }
-            if value.validator_address.is_empty() {
-                return Err(Kind::InvalidValidatorAddress.into());
+            if value.block_id_flag == BlockIdFlag::Nil.to_i32().unwrap() {
+                if value.signature.is_empty() {
+                    return Err(Kind::InvalidSignature
+                        .context("nil commitsig has no signature")
+                        .into());
+                }
+                if value.validator_address.is_empty() {
+                    return Err(Kind::InvalidValidatorAddress.into());
+                }
+                return Ok(CommitSig::BlockIdFlagNil {
+                    validator_address: value.validator_address.try_into()?,
+                    timestamp: value.timestamp.ok_or(Kind::NoTimestamp)?.try_into()?,
+                    signature: value.signature.try_into()?,
+                });
             }
-            return Ok(CommitSig::BlockIdFlagNil {
-                validator_address: value.validator_address.try_into()?,
-                timestamp: value.timestamp.ok_or(Kind::NoTimestamp)?.try_into()?,
-                signature: value.signature.try_into()?,
-            });
+            Err(Kind::BlockIdFlag.into())
         }
-        Err(Kind::BlockIdFlag.into())
     }
-}
 
-impl From<CommitSig> for RawCommitSig {
-    fn from(commit: CommitSig) -> RawCommitSig {
-        match commit {
-            CommitSig::BlockIdFlagAbsent => RawCommitSig {
-                block_id_flag: BlockIdFlag::Absent.to_i32().unwrap(),
-                validator_address: Vec::new(),
-                timestamp: None,
-                signature: Vec::new(),
-            },
-            CommitSig::BlockIdFlagNil {
-                validator_address,
-                timestamp,
-                signature,
-            } => RawCommitSig {
-                block_id_flag: BlockIdFlag::Nil.to_i32().unwrap(),
-                validator_address: validator_address.into(),
-                timestamp: Some(timestamp.into()),
-                signature: signature.into(),
-            },
-            CommitSig::BlockIdFlagCommit {
-                validator_address,
-                timestamp,
-                signature,
-            } => RawCommitSig {
-                block_id_flag: BlockIdFlag::Commit.to_i32().unwrap(),
-                validator_address: validator_address.into(),
-                timestamp: Some(timestamp.into()),
-                signature: signature.into(),
-            },
+    impl From<CommitSig> for RawCommitSig {
+        fn from(commit: CommitSig) -> RawCommitSig {
+            match commit {
+                CommitSig::BlockIdFlagAbsent => RawCommitSig {
+                    block_id_flag: BlockIdFlag::Absent.to_i32().unwrap(),
+                    validator_address: Vec::new(),
+                    timestamp: None,
+                    signature: Vec::new(),
+                },
+                CommitSig::BlockIdFlagNil {
+                    validator_address,
+                    timestamp,
+                    signature,
+                } => RawCommitSig {
+                    block_id_flag: BlockIdFlag::Nil.to_i32().unwrap(),
+                    validator_address: validator_address.into(),
+                    timestamp: Some(timestamp.into()),
+                    signature: signature.into(),
+                },
+                CommitSig::BlockIdFlagCommit {
+                    validator_address,
+                    timestamp,
+                    signature,
+                } => RawCommitSig {
+                    block_id_flag: BlockIdFlag::Commit.to_i32().unwrap(),
+                    validator_address: validator_address.into(),
+                    timestamp: Some(timestamp.into()),
+                    signature: signature.into(),
+                },
+            }
         }
     }
 }
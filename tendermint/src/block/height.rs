@@ -1,11 +1,12 @@
 use crate::error::{Error, Kind};
-use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
-use std::convert::TryInto;
-use std::{
+use crate::prelude::*;
+use core::convert::TryInto;
+use core::{
     convert::TryFrom,
     fmt::{self, Debug, Display},
     str::FromStr,
 };
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use tendermint_proto::Protobuf;
 
 /// Block height for a particular chain (i.e. number of blocks created since
@@ -73,8 +74,91 @@ impl Height {
     }
 
     /// Increment the block height by 1
+    ///
+    /// # Panics
+    ///
+    /// Panics if the height would exceed `i64::MAX`. See
+    /// [`Height::checked_add`] for a version without panic.
     pub fn increment(self) -> Self {
-        Height::try_from(self.0.checked_add(1).expect("height overflow")).unwrap()
+        self.checked_add(1).expect("height overflow")
+    }
+
+    /// Decrement the block height by 1, returning `None` for height 0
+    pub fn decrement(self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    /// Add the given number of blocks to this height, returning `None` if
+    /// the result would exceed `i64::MAX`
+    pub fn checked_add(self, rhs: u64) -> Option<Self> {
+        self.0
+            .checked_add(rhs)
+            .and_then(|value| Height::try_from(value).ok())
+    }
+
+    /// Subtract the given number of blocks from this height, returning `None`
+    /// if the result would be negative
+    pub fn checked_sub(self, rhs: u64) -> Option<Self> {
+        self.0.checked_sub(rhs).map(Height)
+    }
+
+    /// Is this the height of a chain which has not yet produced a block?
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the heights from this one up to the given one
+    /// (inclusive), e.g. `last_height.increment().up_to(latest)` for the
+    /// blocks to fetch. The iterator is empty if `last` is below this height.
+    pub fn up_to(self, last: Height) -> Heights {
+        Heights {
+            next: self.0,
+            last: last.0,
+        }
+    }
+}
+
+/// Iterator over a range of consecutive block heights, returned by
+/// [`Height::up_to`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Heights {
+    next: u64,
+    last: u64,
+}
+
+impl Iterator for Heights {
+    type Item = Height;
+
+    fn next(&mut self) -> Option<Height> {
+        if self.next > self.last {
+            return None;
+        }
+        let height = Height(self.next);
+        self.next += 1;
+        Some(height)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.last + 1).saturating_sub(self.next);
+        match usize::try_from(len) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Heights {
+    fn next_back(&mut self) -> Option<Height> {
+        if self.next > self.last {
+            return None;
+        }
+        let height = Height(self.last);
+        match self.last.checked_sub(1) {
+            Some(last) => self.last = last,
+            // The range started at height 0, and is now exhausted
+            None => self.next = 1,
+        }
+        Some(height)
     }
 }
 
@@ -135,6 +219,38 @@ mod tests {
         assert_eq!(Height::default().increment().value(), 2);
     }
 
+    #[test]
+    fn checked_arithmetic() {
+        let max = Height::try_from(i64::MAX as u64).unwrap();
+        assert_eq!(max.checked_add(1), None);
+        assert_eq!(Height::from(1_u32).decrement(), Some(Height::from(0_u32)));
+        assert_eq!(Height::from(0_u32).decrement(), None);
+        assert_eq!(Height::from(5_u32).checked_sub(6), None);
+        assert!(Height::from(0_u32).is_zero());
+        assert!(!Height::default().is_zero());
+    }
+
+    #[test]
+    fn height_ranges() {
+        let heights = |from: u32, to: u32| {
+            Height::from(from)
+                .up_to(Height::from(to))
+                .map(|h| h.value())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(heights(3, 5), vec![3, 4, 5]);
+        assert_eq!(heights(5, 5), vec![5]);
+        assert!(heights(6, 5).is_empty());
+
+        let mut range = Height::from(0_u32).up_to(Height::from(2_u32));
+        assert_eq!(range.size_hint(), (3, Some(3)));
+        assert_eq!(range.next_back(), Some(Height::from(2_u32)));
+        assert_eq!(range.next(), Some(Height::from(0_u32)));
+        assert_eq!(range.next_back(), Some(Height::from(1_u32)));
+        assert_eq!(range.next_back(), None);
+        assert_eq!(range.next(), None);
+    }
+
     #[test]
     fn avoid_try_unwrap_dance() {
         assert_eq!(
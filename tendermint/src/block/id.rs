@@ -1,19 +1,15 @@
+use crate::prelude::*;
 use crate::{
     block::parts::Header as PartSetHeader,
     error::{Error, Kind},
     hash::{Algorithm, Hash},
 };
-use serde::{Deserialize, Serialize};
-use std::convert::{TryFrom, TryInto};
-use std::{
+use core::{
     fmt::{self, Display},
     str::{self, FromStr},
 };
-use tendermint_proto::types::{
-    BlockId as RawBlockId, CanonicalBlockId as RawCanonicalBlockId,
-    PartSetHeader as RawPartSetHeader,
-};
-use tendermint_proto::Protobuf;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::types::BlockId as RawBlockId;
 
 /// Length of a block ID prefix displayed for debugging purposes
 pub const PREFIX_LENGTH: usize = 10;
@@ -57,73 +53,91 @@ pub struct Id {
     pub part_set_header: PartSetHeader,
 }
 
-impl Protobuf<RawBlockId> for Id {}
-
-impl TryFrom<RawBlockId> for Id {
-    type Error = Error;
-
-    fn try_from(value: RawBlockId) -> Result<Self, Self::Error> {
-        if value.part_set_header.is_none() {
-            return Err(Kind::InvalidPartSetHeader
-                .context("part_set_header is None")
-                .into());
+tendermint_pb_modules! {
+    use super::Id;
+    use crate::error::{Error, Kind};
+    use core::convert::{TryFrom, TryInto};
+    use pb::types::{
+        BlockId as RawBlockId, CanonicalBlockId as RawCanonicalBlockId,
+        PartSetHeader as RawPartSetHeader,
+    };
+
+    impl Protobuf<RawBlockId> for Id {}
+
+    impl TryFrom<RawBlockId> for Id {
+        type Error = Error;
+
+        fn try_from(value: RawBlockId) -> Result<Self, Self::Error> {
+            if value.part_set_header.is_none() {
+                return Err(Kind::InvalidPartSetHeader
+                    .context("part_set_header is None")
+                    .into());
+            }
+            Ok(Self {
+                hash: value.hash.try_into()?,
+                part_set_header: value.part_set_header.unwrap().try_into()?,
+            })
         }
-        Ok(Self {
-            hash: value.hash.try_into()?,
-            part_set_header: value.part_set_header.unwrap().try_into()?,
-        })
     }
-}
 
-impl From<Id> for RawBlockId {
-    fn from(value: Id) -> Self {
-        // https://github.com/tendermint/tendermint/blob/1635d1339c73ae6a82e062cd2dc7191b029efa14/types/block.go#L1204
-        // The Go implementation encodes a nil value into an empty struct. We try our best to
-        // anticipate an empty struct by using the default implementation which would otherwise be
-        // invalid.
-        if value == Id::default() {
-            RawBlockId {
-                hash: vec![],
-                part_set_header: Some(RawPartSetHeader {
-                    total: 0,
+    impl From<Id> for RawBlockId {
+        fn from(value: Id) -> Self {
+            // https://github.com/tendermint/tendermint/blob/1635d1339c73ae6a82e062cd2dc7191b029efa14/types/block.go#L1204
+            // The Go implementation encodes a nil value into an empty struct. We try our best to
+            // anticipate an empty struct by using the default implementation which would otherwise be
+            // invalid.
+            if value == Id::default() {
+                RawBlockId {
                     hash: vec![],
-                }),
-            }
-        } else {
-            RawBlockId {
-                hash: value.hash.into(),
-                part_set_header: Some(value.part_set_header.into()),
+                    part_set_header: Some(RawPartSetHeader {
+                        total: 0,
+                        hash: vec![],
+                    }),
+                }
+            } else {
+                RawBlockId {
+                    hash: value.hash.into(),
+                    part_set_header: Some(value.part_set_header.into()),
+                }
             }
         }
     }
-}
 
-impl TryFrom<RawCanonicalBlockId> for Id {
-    type Error = Error;
+    impl TryFrom<RawCanonicalBlockId> for Id {
+        type Error = Error;
 
-    fn try_from(value: RawCanonicalBlockId) -> Result<Self, Self::Error> {
-        if value.part_set_header.is_none() {
-            return Err(Kind::InvalidPartSetHeader
-                .context("part_set_header is None")
-                .into());
+        fn try_from(value: RawCanonicalBlockId) -> Result<Self, Self::Error> {
+            if value.part_set_header.is_none() {
+                return Err(Kind::InvalidPartSetHeader
+                    .context("part_set_header is None")
+                    .into());
+            }
+            Ok(Self {
+                hash: value.hash.try_into()?,
+                part_set_header: value.part_set_header.unwrap().try_into()?,
+            })
         }
-        Ok(Self {
-            hash: value.hash.try_into()?,
-            part_set_header: value.part_set_header.unwrap().try_into()?,
-        })
     }
-}
 
-impl From<Id> for RawCanonicalBlockId {
-    fn from(value: Id) -> Self {
-        RawCanonicalBlockId {
-            hash: value.hash.as_bytes().to_vec(),
-            part_set_header: Some(value.part_set_header.into()),
+    impl From<Id> for RawCanonicalBlockId {
+        fn from(value: Id) -> Self {
+            RawCanonicalBlockId {
+                hash: value.hash.as_bytes().to_vec(),
+                part_set_header: Some(value.part_set_header.into()),
+            }
         }
     }
 }
 
 impl Id {
+    /// Create a new block ID from the given block hash and parts header
+    pub const fn new(hash: Hash, part_set_header: PartSetHeader) -> Self {
+        Id {
+            hash,
+            part_set_header,
+        }
+    }
+
     /// Get a shortened 12-character prefix of a block ID (ala git)
     pub fn prefix(&self) -> String {
         let mut result = self.to_string();
@@ -132,21 +146,45 @@ impl Id {
     }
 }
 
-// TODO: match gaia serialization? e.g `D2F5991B98D708FD2C25AA2BEBED9358F24177DE:1:C37A55FB95E9`
+/// Block IDs are displayed as `HASH:TOTAL:PARTS_HASH`, or just as `HASH` if
+/// the parts header is empty.
 impl Display for Id {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", &self.hash)
+        if self.part_set_header == PartSetHeader::default() {
+            write!(f, "{}", &self.hash)
+        } else {
+            write!(
+                f,
+                "{}:{}:{}",
+                &self.hash, self.part_set_header.total, &self.part_set_header.hash
+            )
+        }
     }
 }
 
-// TODO: match gaia serialization?
+/// Parses either of the forms produced by the `Display` implementation, with
+/// hashes in either upper or lower case.
 impl FromStr for Id {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Error> {
+        let fields = s.split(':').collect::<Vec<_>>();
+        let part_set_header = match fields.as_slice() {
+            [_] => PartSetHeader::default(),
+            [_, total, parts_hash] => PartSetHeader::new(
+                total.parse().map_err(|e| Kind::Parse.context(e))?,
+                Hash::from_hex(Algorithm::Sha256, parts_hash)?,
+            )?,
+            _ => fail!(
+                Kind::Parse,
+                "invalid block ID {:?}: expected HASH or HASH:TOTAL:PARTS_HASH",
+                s
+            ),
+        };
+
         Ok(Self {
-            hash: Hash::from_hex_upper(Algorithm::Sha256, s)?,
-            part_set_header: PartSetHeader::default(),
+            hash: Hash::from_hex(Algorithm::Sha256, fields[0])?,
+            part_set_header,
         })
     }
 }
@@ -164,6 +202,9 @@ mod tests {
     const EXAMPLE_SHA256_ID: &str =
         "26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D";
 
+    const EXAMPLE_PARTS_HASH: &str =
+        "C37A55FB95E9A8A79E0D5A7B2F0B7A1B1D7E6F9A1C0B2D3E4F5A6B7C8D9E0F1A";
+
     #[test]
     fn parses_hex_strings() {
         let id = Id::from_str(EXAMPLE_SHA256_ID).unwrap();
@@ -180,4 +221,32 @@ mod tests {
         let id = Id::from_str(EXAMPLE_SHA256_ID).unwrap();
         assert_eq!(&id.to_string(), EXAMPLE_SHA256_ID)
     }
+
+    #[test]
+    fn parses_lower_case_hex_strings() {
+        let id = Id::from_str(&EXAMPLE_SHA256_ID.to_lowercase()).unwrap();
+        assert_eq!(id, Id::from_str(EXAMPLE_SHA256_ID).unwrap());
+    }
+
+    #[test]
+    fn round_trips_parts_header() {
+        let s = format!("{}:1:{}", EXAMPLE_SHA256_ID, EXAMPLE_PARTS_HASH);
+        let id = Id::from_str(&s).unwrap();
+        assert_eq!(id.part_set_header.total, 1);
+        assert_eq!(id.part_set_header.hash.to_string(), EXAMPLE_PARTS_HASH);
+        assert_eq!(id.to_string(), s);
+        assert_eq!(id.prefix(), &EXAMPLE_SHA256_ID[..PREFIX_LENGTH]);
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        for s in &[
+            "26C0A41F",
+            "26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D:1",
+            "26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D:x:26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D",
+            "26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D:0:26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D",
+        ] {
+            assert!(Id::from_str(s).is_err(), "{:?} should not parse", s);
+        }
+    }
 }
@@ -1,16 +1,22 @@
 //! Block parts
 
-use crate::hash::Algorithm;
-use crate::hash::SHA256_HASH_SIZE;
+use crate::consensus::params::MAX_BLOCK_SIZE_BYTES;
+use crate::merkle::{self, SimpleProof};
+use crate::prelude::*;
 use crate::Hash;
 use crate::{Error, Kind};
+use core::convert::{TryFrom, TryInto};
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
-use tendermint_proto::types::{
-    CanonicalPartSetHeader as RawCanonicalPartSetHeader, PartSetHeader as RawPartSetHeader,
-};
+use tendermint_proto::types::{Part as RawPart, PartSetHeader as RawPartSetHeader};
 use tendermint_proto::Protobuf;
 
+/// Size of the parts blocks are split into for gossiping, in bytes
+pub const BLOCK_PART_SIZE_BYTES: usize = 65536;
+
+/// Maximum number of parts of a block, as in Tendermint
+pub const MAX_BLOCK_PARTS_COUNT: u32 =
+    (MAX_BLOCK_SIZE_BYTES / BLOCK_PART_SIZE_BYTES as u64) as u32 + 1;
+
 /// Block parts header
 #[derive(
     Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize,
@@ -25,50 +31,62 @@ pub struct Header {
     pub hash: Hash,
 }
 
-impl Protobuf<RawPartSetHeader> for Header {}
+tendermint_pb_modules! {
+    use super::{check_total, Header};
+    use crate::hash::{Algorithm, SHA256_HASH_SIZE};
+    use crate::{Error, Hash, Kind};
+    use core::convert::TryFrom;
+    use pb::types::{
+        CanonicalPartSetHeader as RawCanonicalPartSetHeader, PartSetHeader as RawPartSetHeader,
+    };
 
-impl TryFrom<RawPartSetHeader> for Header {
-    type Error = Error;
+    impl Protobuf<RawPartSetHeader> for Header {}
 
-    fn try_from(value: RawPartSetHeader) -> Result<Self, Self::Error> {
-        if !value.hash.is_empty() && value.hash.len() != SHA256_HASH_SIZE {
-            return Err(Kind::InvalidHashSize.into());
+    impl TryFrom<RawPartSetHeader> for Header {
+        type Error = Error;
+
+        fn try_from(value: RawPartSetHeader) -> Result<Self, Self::Error> {
+            if !value.hash.is_empty() && value.hash.len() != SHA256_HASH_SIZE {
+                return Err(Kind::InvalidHashSize.into());
+            }
+            check_total(value.total)?;
+            Ok(Self {
+                total: value.total,
+                hash: Hash::from_bytes(Algorithm::Sha256, &value.hash)?,
+            })
         }
-        Ok(Self {
-            total: value.total,
-            hash: Hash::from_bytes(Algorithm::Sha256, &value.hash)?,
-        })
     }
-}
 
-impl From<Header> for RawPartSetHeader {
-    fn from(value: Header) -> Self {
-        RawPartSetHeader {
-            total: value.total,
-            hash: value.hash.into(),
+    impl From<Header> for RawPartSetHeader {
+        fn from(value: Header) -> Self {
+            RawPartSetHeader {
+                total: value.total,
+                hash: value.hash.into(),
+            }
         }
     }
-}
 
-impl TryFrom<RawCanonicalPartSetHeader> for Header {
-    type Error = Error;
+    impl TryFrom<RawCanonicalPartSetHeader> for Header {
+        type Error = Error;
 
-    fn try_from(value: RawCanonicalPartSetHeader) -> Result<Self, Self::Error> {
-        if !value.hash.is_empty() && value.hash.len() != SHA256_HASH_SIZE {
-            return Err(Kind::InvalidHashSize.into());
+        fn try_from(value: RawCanonicalPartSetHeader) -> Result<Self, Self::Error> {
+            if !value.hash.is_empty() && value.hash.len() != SHA256_HASH_SIZE {
+                return Err(Kind::InvalidHashSize.into());
+            }
+            check_total(value.total)?;
+            Ok(Self {
+                total: value.total,
+                hash: Hash::from_bytes(Algorithm::Sha256, &value.hash)?,
+            })
         }
-        Ok(Self {
-            total: value.total,
-            hash: Hash::from_bytes(Algorithm::Sha256, &value.hash)?,
-        })
     }
-}
 
-impl From<Header> for RawCanonicalPartSetHeader {
-    fn from(value: Header) -> Self {
-        RawCanonicalPartSetHeader {
-            total: value.total,
-            hash: value.hash.into(),
+    impl From<Header> for RawCanonicalPartSetHeader {
+        fn from(value: Header) -> Self {
+            RawCanonicalPartSetHeader {
+                total: value.total,
+                hash: value.hash.into(),
+            }
         }
     }
 }
@@ -76,6 +94,7 @@ impl From<Header> for RawCanonicalPartSetHeader {
 impl Header {
     /// constructor
     pub fn new(total: u32, hash: Hash) -> Result<Self, Error> {
+        check_total(total)?;
         if total == 0 && hash != Hash::None {
             return Err(Kind::InvalidPartSetHeader
                 .context("zero total with existing hash")
@@ -89,3 +108,258 @@ impl Header {
         Ok(Header { total, hash })
     }
 }
+
+// Part set headers come from peers: reject those which would make us
+// allocate room for more parts than a block can have
+fn check_total(total: u32) -> Result<(), Error> {
+    if total > MAX_BLOCK_PARTS_COUNT {
+        return Err(format_err!(
+            Kind::InvalidPartSetHeader,
+            "too many parts: {} (max {})",
+            total,
+            MAX_BLOCK_PARTS_COUNT
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// A part of a block, along with the proof of its inclusion in the block's
+/// part set
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "RawPart", into = "RawPart")]
+pub struct Part {
+    /// Index of this part
+    pub index: u32,
+
+    /// Contents of this part
+    pub bytes: Vec<u8>,
+
+    /// Proof of the inclusion of this part in the part set
+    pub proof: SimpleProof,
+}
+
+impl Protobuf<RawPart> for Part {}
+
+impl TryFrom<RawPart> for Part {
+    type Error = Error;
+
+    fn try_from(value: RawPart) -> Result<Self, Self::Error> {
+        Ok(Self {
+            index: value.index,
+            bytes: value.bytes,
+            proof: value
+                .proof
+                .ok_or_else(|| Kind::InvalidPartSetHeader.context("missing part proof"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<Part> for RawPart {
+    fn from(value: Part) -> Self {
+        RawPart {
+            index: value.index,
+            bytes: value.bytes,
+            proof: Some(value.proof.into()),
+        }
+    }
+}
+
+impl Part {
+    /// Verify that this part belongs to the part set with the given header.
+    pub fn verify(&self, header: &Header) -> Result<(), Error> {
+        if self.bytes.len() > BLOCK_PART_SIZE_BYTES {
+            return Err(format_err!(
+                Kind::InvalidPartSetHeader,
+                "part {} is too big: {} bytes",
+                self.index,
+                self.bytes.len()
+            )
+            .into());
+        }
+        if self.index >= header.total
+            || u64::from(self.index) != self.proof.index
+            || u64::from(header.total) != self.proof.total
+        {
+            return Err(format_err!(
+                Kind::InvalidPartSetHeader,
+                "part {} does not match the part set of {} parts",
+                self.index,
+                header.total
+            )
+            .into());
+        }
+        let root_hash = match header.hash {
+            Hash::Sha256(hash) => hash,
+            Hash::None => return Err(Kind::InvalidPartSetHeader.context("missing hash").into()),
+        };
+        self.proof.verify(&root_hash, &self.bytes)
+    }
+}
+
+/// A set of block parts, either built from a serialized block, or being
+/// received part by part.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Set {
+    header: Header,
+    parts: Vec<Option<Part>>,
+    count: u32,
+}
+
+impl Set {
+    /// Split the given data (usually a protobuf-encoded block) into parts of
+    /// the given size.
+    pub fn from_data(data: &[u8], part_size: usize) -> Self {
+        let chunks: Vec<Vec<u8>> = data.chunks(part_size).map(<[u8]>::to_vec).collect();
+        let (root_hash, proofs) = merkle::proofs_from_byte_vectors(&chunks);
+        let parts: Vec<_> = chunks
+            .into_iter()
+            .zip(proofs)
+            .enumerate()
+            .map(|(index, (bytes, proof))| {
+                Some(Part {
+                    index: index as u32,
+                    bytes,
+                    proof,
+                })
+            })
+            .collect();
+        let total = parts.len() as u32;
+        Set {
+            header: Header {
+                total,
+                hash: Hash::Sha256(root_hash),
+            },
+            parts,
+            count: total,
+        }
+    }
+
+    /// An empty set of parts, to be filled with the parts of the given part
+    /// set header.
+    ///
+    /// Fails if the header has more than [`MAX_BLOCK_PARTS_COUNT`] parts.
+    pub fn from_header(header: Header) -> Result<Self, Error> {
+        check_total(header.total)?;
+        Ok(Set {
+            header,
+            parts: vec![None; header.total as usize],
+            count: 0,
+        })
+    }
+
+    /// Header of this part set
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// Total number of parts
+    pub fn total(&self) -> u32 {
+        self.header.total
+    }
+
+    /// Number of parts presently in this set
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Whether all the parts are in this set
+    pub fn is_complete(&self) -> bool {
+        self.count == self.header.total
+    }
+
+    /// Part with the given index, if present
+    pub fn part(&self, index: u32) -> Option<&Part> {
+        self.parts.get(index as usize).and_then(Option::as_ref)
+    }
+
+    /// Add the given part to this set, after verifying it.
+    ///
+    /// Returns `false` if the part was already present.
+    pub fn add_part(&mut self, part: Part) -> Result<bool, Error> {
+        part.verify(&self.header)?;
+        let slot = &mut self.parts[part.index as usize];
+        if slot.is_some() {
+            return Ok(false);
+        }
+        *slot = Some(part);
+        self.count += 1;
+        Ok(true)
+    }
+
+    /// The data these parts were split from, once all of them are present.
+    pub fn assemble(&self) -> Option<Vec<u8>> {
+        if !self.is_complete() {
+            return None;
+        }
+        Some(
+            self.parts
+                .iter()
+                .flatten()
+                .flat_map(|part| part.bytes.iter().copied())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_set_roundtrip() {
+        let data: Vec<u8> = (0..10_000_u32).map(|i| i as u8).collect();
+        let set = Set::from_data(&data, 1024);
+        assert_eq!(10, set.total());
+        assert!(set.is_complete());
+        assert_eq!(Some(data.clone()), set.assemble());
+
+        let mut received = Set::from_header(set.header()).unwrap();
+        assert_eq!(None, received.assemble());
+        for index in (0..set.total()).rev() {
+            let part = set.part(index).unwrap().clone();
+            assert!(received.add_part(part.clone()).unwrap());
+            assert!(!received.add_part(part).unwrap());
+        }
+        assert_eq!(set, received);
+        assert_eq!(Some(data), received.assemble());
+    }
+
+    #[test]
+    fn invalid_parts() {
+        let set = Set::from_data(&[1; 3000], 1024);
+        let mut received = Set::from_header(set.header()).unwrap();
+
+        let mut tampered = set.part(1).unwrap().clone();
+        tampered.bytes[0] = 2;
+        assert!(received.add_part(tampered).is_err());
+
+        let mut misplaced = set.part(1).unwrap().clone();
+        misplaced.index = 2;
+        assert!(received.add_part(misplaced).is_err());
+
+        let other = Set::from_data(&[1; 2048], 1024);
+        assert!(received.add_part(other.part(0).unwrap().clone()).is_err());
+        assert_eq!(0, received.count());
+    }
+
+    #[test]
+    fn bounds_part_count() {
+        assert_eq!(1601, MAX_BLOCK_PARTS_COUNT);
+        let hash = Hash::Sha256([1; 32]);
+        Header::new(MAX_BLOCK_PARTS_COUNT, hash).unwrap();
+        assert!(Header::new(MAX_BLOCK_PARTS_COUNT + 1, hash).is_err());
+        assert!(Header::new(u32::MAX, hash).is_err());
+
+        let raw = RawPartSetHeader {
+            total: u32::MAX,
+            hash: vec![1; 32],
+        };
+        assert!(Header::try_from(raw).is_err());
+
+        let mut header = Set::from_data(&[1; 10], 1024).header();
+        header.total = u32::MAX;
+        assert!(Set::from_header(header).is_err());
+    }
+}
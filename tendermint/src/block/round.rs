@@ -1,11 +1,12 @@
 use crate::error::{Error, Kind};
-use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
-use std::convert::TryInto;
-use std::{
+use crate::prelude::*;
+use core::convert::TryInto;
+use core::{
     convert::TryFrom,
     fmt::{self, Debug, Display},
     str::FromStr,
 };
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Block round for a particular chain
 #[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
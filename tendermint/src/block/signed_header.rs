@@ -1,9 +1,8 @@
 //! SignedHeader contains commit and and block header.
 //! It is what the rpc endpoint /commit returns and hence can be used by a
 //! light client.
-use crate::{block, Error, Kind};
+use crate::{block, chain, validator, Error, Kind};
 use serde::{Deserialize, Serialize};
-use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::SignedHeader as RawSignedHeader;
 
 /// Signed block headers
@@ -17,21 +16,28 @@ pub struct SignedHeader {
     pub commit: block::Commit,
 }
 
-impl TryFrom<RawSignedHeader> for SignedHeader {
-    type Error = Error;
+tendermint_pb_modules! {
+    use super::SignedHeader;
+    use crate::{Error, Kind};
+    use core::convert::{TryFrom, TryInto};
+    use pb::types::SignedHeader as RawSignedHeader;
 
-    fn try_from(value: RawSignedHeader) -> Result<Self, Self::Error> {
-        let header = value.header.ok_or(Kind::InvalidSignedHeader)?.try_into()?;
-        let commit = value.commit.ok_or(Kind::InvalidSignedHeader)?.try_into()?;
-        Self::new(header, commit) // Additional checks
+    impl TryFrom<RawSignedHeader> for SignedHeader {
+        type Error = Error;
+
+        fn try_from(value: RawSignedHeader) -> Result<Self, Self::Error> {
+            let header = value.header.ok_or(Kind::InvalidSignedHeader)?.try_into()?;
+            let commit = value.commit.ok_or(Kind::InvalidSignedHeader)?.try_into()?;
+            Self::new(header, commit) // Additional checks
+        }
     }
-}
 
-impl From<SignedHeader> for RawSignedHeader {
-    fn from(value: SignedHeader) -> Self {
-        RawSignedHeader {
-            header: Some(value.header.into()),
-            commit: Some(value.commit.into()),
+    impl From<SignedHeader> for RawSignedHeader {
+        fn from(value: SignedHeader) -> Self {
+            RawSignedHeader {
+                header: Some(value.header.into()),
+                commit: Some(value.commit.into()),
+            }
         }
     }
 }
@@ -54,4 +60,136 @@ impl SignedHeader {
     pub fn commit(&self) -> &block::Commit {
         &self.commit
     }
+
+    /// Validate the structure of this signed header, as done by Tendermint's
+    /// `SignedHeader.ValidateBasic`: the header must belong to the given
+    /// chain, and the commit must be for the header (same height and block
+    /// hash) and contain signatures.
+    ///
+    /// The signatures themselves are not verified.
+    pub fn validate(&self, chain_id: &chain::Id) -> Result<(), Error> {
+        let (header, commit) = (&self.header, &self.commit);
+        if &header.chain_id != chain_id {
+            return Err(Kind::ChainIdMismatch {
+                expected: chain_id.clone(),
+                got: header.chain_id.clone(),
+            }
+            .into());
+        }
+        if header.height.value() == 0 {
+            return Err(Kind::InvalidSignedHeader
+                .context("header has a height of zero")
+                .into());
+        }
+        if header.height != commit.height {
+            return Err(Kind::HeightMismatch {
+                header: header.height,
+                commit: commit.height,
+            }
+            .into());
+        }
+        if commit.block_id == block::Id::default() {
+            return Err(Kind::InvalidSignedHeader
+                .context("commit is for an empty block ID")
+                .into());
+        }
+        if commit.signatures.is_empty() {
+            return Err(Kind::InvalidSignedHeader
+                .context("commit has no signatures")
+                .into());
+        }
+        let header_hash = header.hash();
+        if commit.block_id.hash != header_hash {
+            return Err(Kind::BlockIdMismatch {
+                expected: block::Id {
+                    hash: header_hash,
+                    ..commit.block_id
+                },
+                got: commit.block_id,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Validate this signed header as [`SignedHeader::validate`] does, and
+    /// additionally check it against the validator set at its height: the
+    /// header's validators hash must match the set, and the commit must
+    /// contain exactly one signature per validator.
+    pub fn validate_with_validators(
+        &self,
+        chain_id: &chain::Id,
+        validators: &validator::Set,
+    ) -> Result<(), Error> {
+        self.validate(chain_id)?;
+        if self.header.validators_hash != validators.hash() {
+            return Err(Kind::ValidatorsHashMismatch {
+                header: self.header.validators_hash,
+                validators: validators.hash(),
+            }
+            .into());
+        }
+        if self.commit.signatures.len() != validators.validators().len() {
+            return Err(Kind::SignatureCountMismatch {
+                validators: validators.validators().len(),
+                signatures: self.commit.signatures.len(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{CommitSig, Header, Id};
+    use crate::prelude::*;
+    use core::convert::TryInto;
+
+    fn signed_header() -> SignedHeader {
+        let header: Header = serde_json::from_str(include_str!(
+            "../../tests/support/serialization/block/header_with_known_hash.json"
+        ))
+        .unwrap();
+        let commit = block::Commit {
+            height: header.height,
+            round: Default::default(),
+            block_id: Id {
+                hash: header.hash(),
+                part_set_header: Default::default(),
+            },
+            signatures: vec![CommitSig::BlockIdFlagAbsent],
+        };
+        SignedHeader::new(header, commit).unwrap()
+    }
+
+    #[test]
+    fn validation() {
+        let chain_id: chain::Id = "dockerchain".parse().unwrap();
+        let signed_header = signed_header();
+        signed_header.validate(&chain_id).unwrap();
+        let err = signed_header
+            .validate(&"otherchain".parse().unwrap())
+            .unwrap_err();
+        assert!(matches!(err.kind(), Kind::ChainIdMismatch { .. }));
+
+        let mut wrong_hash = signed_header.clone();
+        wrong_hash.commit.block_id = Id::default();
+        assert!(wrong_hash.validate(&chain_id).is_err());
+
+        let mut modified = signed_header.clone();
+        modified.header.app_hash = vec![1, 2, 3].try_into().unwrap();
+        let err = modified.validate(&chain_id).unwrap_err();
+        assert!(matches!(err.kind(), Kind::BlockIdMismatch { .. }));
+
+        let mut no_signatures = signed_header.clone();
+        no_signatures.commit.signatures = vec![];
+        assert!(no_signatures.validate(&chain_id).is_err());
+
+        let err = signed_header
+            .validate_with_validators(&chain_id, &validator::Set::without_proposer(vec![]))
+            .unwrap_err();
+        assert!(matches!(err.kind(), Kind::ValidatorsHashMismatch { .. }));
+    }
 }
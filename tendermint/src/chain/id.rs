@@ -1,14 +1,15 @@
 //! Tendermint blockchain identifiers
 
 use crate::error::{Error, Kind};
-use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
-use std::convert::TryFrom;
-use std::{
+use crate::prelude::*;
+use core::convert::TryFrom;
+use core::{
     cmp::Ordering,
     fmt::{self, Debug, Display},
     hash::{Hash, Hasher},
     str::{self, FromStr},
 };
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use tendermint_proto::Protobuf;
 
 /// Maximum length of a `chain::Id` name. Matches `MaxChainIDLen` from:
@@ -26,15 +27,26 @@ impl TryFrom<String> for Id {
     type Error = Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        if value.is_empty() || value.len() > MAX_LENGTH {
-            return Err(Kind::Length.into());
+        if value.is_empty() {
+            return Err(Kind::Length.context("empty chain ID").into());
+        }
+        if value.len() > MAX_LENGTH {
+            return Err(Kind::Length
+                .context(format!(
+                    "chain ID is {} bytes long, the maximum is {}",
+                    value.len(),
+                    MAX_LENGTH
+                ))
+                .into());
         }
 
-        for byte in value.as_bytes() {
-            match byte {
-                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (),
-                _ => return Err(Kind::Parse.context("chain id charset").into()),
-            }
+        if let Some(c) = value
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        {
+            return Err(Kind::Parse
+                .context(format!("invalid character {:?} in chain ID", c))
+                .into());
         }
 
         Ok(Id(value))
@@ -151,18 +163,20 @@ mod tests {
 
     #[test]
     fn rejects_empty_chain_ids() {
-        assert_eq!(
-            *"".parse::<Id>().unwrap_err().to_string(),
-            Kind::Length.to_string()
-        );
+        assert_eq!("".parse::<Id>().unwrap_err().kind(), &Kind::Length);
     }
 
     #[test]
     fn rejects_overlength_chain_ids() {
         let overlong_id = String::from_utf8(vec![b'x'; MAX_LENGTH + 1]).unwrap();
-        assert_eq!(
-            *overlong_id.parse::<Id>().unwrap_err().to_string(),
-            Kind::Length.to_string()
-        );
+        assert_eq!(overlong_id.parse::<Id>().unwrap_err().kind(), &Kind::Length);
+    }
+
+    #[test]
+    fn rejects_invalid_characters() {
+        for id in &["gaia 9000", "gaia/9000", "gaïa-9000"] {
+            let err = id.parse::<Id>().unwrap_err();
+            assert_eq!(err.kind(), &Kind::Parse);
+        }
     }
 }
@@ -3,9 +3,10 @@
 mod id;
 
 pub use self::id::Id;
+use crate::prelude::*;
 use crate::serializers;
+use core::fmt::{self, Display};
 pub use serde::{Deserialize, Serialize};
-use std::fmt::{self, Display};
 
 /// Channels
 #[derive(Clone, Debug, Deserialize, Serialize)]
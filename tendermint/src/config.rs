@@ -3,13 +3,14 @@
 //! This module contains types which correspond to the following config files:
 //!
 //! - `config.toml`: `config::TendermintConfig`
-//! - `node_key.rs`: `config::node_key::NodeKey`
+//! - `node_key.json`: `config::NodeKey` (i.e. `node::Key`)
 //! - `priv_validator_key.rs`: `config::priv_validator_key::PrivValidatorKey`
 
-mod node_key;
 mod priv_validator_key;
 
-pub use self::{node_key::NodeKey, priv_validator_key::PrivValidatorKey};
+pub use self::priv_validator_key::PrivValidatorKey;
+pub use crate::node::Key as NodeKey;
+use crate::prelude::*;
 
 use crate::{
     abci::tag,
@@ -17,7 +18,6 @@ use crate::{
     genesis::Genesis,
     net, node, Moniker, Timeout,
 };
-use anomaly::{fail, format_err};
 use serde::{de, de::Error as _, ser, Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -105,7 +105,7 @@ pub struct TendermintConfig {
 impl TendermintConfig {
     /// Parse Tendermint `config.toml`
     pub fn parse_toml<T: AsRef<str>>(toml_string: T) -> Result<Self, Error> {
-        Ok(toml::from_str(toml_string.as_ref())?)
+        Ok(toml::from_str(toml_string.as_ref()).map_err(|e| Kind::Parse.context(e))?)
     }
 
     /// Load `config.toml` from a file
@@ -131,7 +131,7 @@ impl TendermintConfig {
         let genesis_json = fs::read_to_string(&path)
             .map_err(|e| format_err!(Kind::Parse, "couldn't open {}: {}", path.display(), e))?;
 
-        Ok(serde_json::from_str(genesis_json.as_ref())?)
+        Ok(serde_json::from_str(genesis_json.as_ref()).map_err(|e| Kind::Parse.context(e))?)
     }
 
     /// Load `node_key.json` file from the configured location
@@ -6,8 +6,8 @@ use crate::{
     error::{Error, Kind},
     private_key::PrivateKey,
     public_key::PublicKey,
+    secret::Secret,
 };
-use anomaly::format_err;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 
@@ -21,13 +21,15 @@ pub struct PrivValidatorKey {
     pub pub_key: PublicKey,
 
     /// Private key
+    #[serde(serialize_with = "PrivateKey::serialize_exposed")]
     pub priv_key: PrivateKey,
 }
 
 impl PrivValidatorKey {
     /// Parse `priv_validator_key.json`
     pub fn parse_json<T: AsRef<str>>(json_string: T) -> Result<Self, Error> {
-        let result = serde_json::from_str::<Self>(json_string.as_ref())?;
+        let result = serde_json::from_str::<Self>(json_string.as_ref())
+            .map_err(|e| Kind::Parse.context(e))?;
 
         // Validate that the parsed key type is usable as a consensus key
         TendermintKey::new_consensus_key(result.priv_key.public_key())?;
@@ -35,7 +37,7 @@ impl PrivValidatorKey {
         Ok(result)
     }
 
-    /// Load `node_key.json` from a file
+    /// Load `priv_validator_key.json` from a file
     pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
     where
         P: AsRef<Path>,
@@ -52,6 +54,24 @@ impl PrivValidatorKey {
         Self::parse_json(json_string)
     }
 
+    /// Save `priv_validator_key.json` to a file
+    pub fn save_json_file<P>(&self, path: &P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string =
+            Secret::new(serde_json::to_string_pretty(self).map_err(|e| Kind::Io.context(e))?);
+        fs::write(path, json_string.expose_secret()).map_err(|e| {
+            format_err!(
+                Kind::Io,
+                "couldn't write {}: {}",
+                path.as_ref().display(),
+                e
+            )
+            .into()
+        })
+    }
+
     /// Get the consensus public key for this validator private key
     pub fn consensus_pubkey(&self) -> TendermintKey {
         TendermintKey::new_consensus_key(self.priv_key.public_key()).unwrap()
@@ -1,6 +1,14 @@
 //! Tendermint consensus
 
 pub mod params;
+pub mod round_state;
 pub mod state;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod wal;
 
-pub use self::{params::Params, state::State};
+pub use self::{
+    params::Params,
+    round_state::{RoundState, Step},
+    state::State,
+};
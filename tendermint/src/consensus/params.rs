@@ -1,10 +1,14 @@
 //! Tendermint consensus parameters
 
+use crate::prelude::*;
 use crate::{block, evidence, public_key};
-use crate::{Error, Kind};
+use crate::{Error, Hash, Kind};
+use core::convert::{TryFrom, TryInto};
+use prost::Message;
 use serde::{Deserialize, Serialize};
-use std::convert::{TryFrom, TryInto};
+use sha2::{Digest, Sha256};
 use tendermint_proto::abci::ConsensusParams as RawParams;
+use tendermint_proto::types::HashedParams as RawHashedParams;
 use tendermint_proto::types::ValidatorParams as RawValidatorParams;
 use tendermint_proto::types::VersionParams as RawVersionParams;
 use tendermint_proto::Protobuf;
@@ -26,6 +30,91 @@ pub struct Params {
     pub version: Option<VersionParams>,
 }
 
+/// Maximum size of a block, in bytes, as in Tendermint.
+pub const MAX_BLOCK_SIZE_BYTES: u64 = 104_857_600;
+
+impl Params {
+    /// Check that these parameters are valid, as done by Tendermint's
+    /// `ValidateConsensusParams`.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.block.max_bytes == 0 || self.block.max_bytes > MAX_BLOCK_SIZE_BYTES {
+            return Err(format_err!(
+                Kind::InvalidConsensusParams,
+                "block.max_bytes must be between 1 and {}, got {}",
+                MAX_BLOCK_SIZE_BYTES,
+                self.block.max_bytes
+            )
+            .into());
+        }
+        if self.block.max_gas < -1 {
+            return Err(format_err!(
+                Kind::InvalidConsensusParams,
+                "block.max_gas must be greater or equal to -1, got {}",
+                self.block.max_gas
+            )
+            .into());
+        }
+
+        self.evidence.validate()?;
+        if self.evidence.max_bytes as u64 > self.block.max_bytes {
+            return Err(format_err!(
+                Kind::InvalidConsensusParams,
+                "evidence.max_bytes must be between 0 and block.max_bytes ({}), got {}",
+                self.block.max_bytes,
+                self.evidence.max_bytes
+            )
+            .into());
+        }
+
+        if self.validator.pub_key_types.is_empty() {
+            return Err(Kind::InvalidConsensusParams
+                .context("validator.pub_key_types must not be empty")
+                .into());
+        }
+        Ok(())
+    }
+
+    /// Check that evidence is not kept for longer than the given unbonding
+    /// period of the application, after which misbehaving validators can no
+    /// longer be punished.
+    pub fn validate_unbonding_period(
+        &self,
+        unbonding_period: core::time::Duration,
+    ) -> Result<(), Error> {
+        let max_age_duration = core::time::Duration::from(self.evidence.max_age_duration);
+        if max_age_duration > unbonding_period {
+            return Err(format_err!(
+                Kind::InvalidConsensusParams,
+                "evidence.max_age_duration ({:?}) must not exceed the unbonding period ({:?})",
+                max_age_duration,
+                unbonding_period
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Returns the bytes hashed into a block header's `consensus_hash`: the
+    /// encoding of the subset of these parameters that Tendermint commits to
+    /// (`HashedParams`).
+    pub fn hash_bytes(&self) -> Vec<u8> {
+        let hashed_params = RawHashedParams {
+            block_max_bytes: self.block.max_bytes as i64,
+            block_max_gas: self.block.max_gas,
+        };
+        let mut bytes = Vec::with_capacity(hashed_params.encoded_len());
+        hashed_params.encode(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Compute the hash of these parameters, as found in a block header's
+    /// `consensus_hash`
+    pub fn hash(&self) -> Hash {
+        let digest = Sha256::digest(&self.hash_bytes());
+        Hash::Sha256(digest.into())
+    }
+}
+
 impl Protobuf<RawParams> for Params {}
 
 impl TryFrom<RawParams> for Params {
@@ -86,6 +175,12 @@ fn key_type(s: &str) -> public_key::Algorithm {
     if s == "Secp256k1" || s == "secp256k1" {
         return public_key::Algorithm::Secp256k1;
     }
+    if s == "Sr25519" || s == "sr25519" {
+        return public_key::Algorithm::Sr25519;
+    }
+    if s == "Bls12381" || s == "bls12_381" {
+        return public_key::Algorithm::Bls12381;
+    }
     public_key::Algorithm::Ed25519 // Todo: Shall we error out for invalid key types?
 }
 
@@ -98,6 +193,8 @@ impl From<ValidatorParams> for RawValidatorParams {
                 .map(|k| match k {
                     public_key::Algorithm::Ed25519 => "ed25519".to_string(),
                     public_key::Algorithm::Secp256k1 => "secp256k1".to_string(),
+                    public_key::Algorithm::Sr25519 => "sr25519".to_string(),
+                    public_key::Algorithm::Bls12381 => "bls12_381".to_string(),
                 })
                 .collect(),
         }
@@ -130,3 +227,25 @@ impl From<VersionParams> for RawVersionParams {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_matches_go() {
+        let params: Params = serde_json::from_str(
+            r#"{
+                "block": { "max_bytes": "22020096", "max_gas": "-1", "time_iota_ms": "1000" },
+                "evidence": { "max_age_num_blocks": "100000", "max_age_duration": "172800000000000", "max_bytes": "1048576" },
+                "validator": { "pub_key_types": ["ed25519"] }
+            }"#,
+        )
+        .unwrap();
+        // As in the headers of a node started with the default parameters
+        assert_eq!(
+            params.hash().to_string(),
+            "048091BC7DDC283F77BFBF91D73C44DA58C3DF8A9CBC867405D8B7F3DAADA22F"
+        );
+    }
+}
@@ -0,0 +1,576 @@
+//! Model of the state of a validator going through the rounds of consensus
+//! for a given height
+
+use crate::block::{self, Commit, Round};
+use crate::vote::{self, AddedVote, Vote, VoteSet};
+use crate::{chain, validator, Error, Kind, Proposal};
+use alloc::collections::BTreeMap;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+/// Steps of a consensus round, as Tendermint's `RoundStepType`.
+///
+/// Steps are ordered as they occur within a round.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Step {
+    /// Waiting for the commit timeout of the previous height to elapse
+    NewHeight = 1,
+
+    /// Starting a new round
+    NewRound = 2,
+
+    /// Waiting for the proposal of the round
+    Propose = 3,
+
+    /// Prevoting
+    Prevote = 4,
+
+    /// Waiting for more prevotes, after prevotes from more than 2/3 of the
+    /// voting power were received
+    PrevoteWait = 5,
+
+    /// Precommitting
+    Precommit = 6,
+
+    /// Waiting for more precommits, after precommits from more than 2/3 of
+    /// the voting power were received
+    PrecommitWait = 7,
+
+    /// Committing the block precommitted by more than 2/3 of the voting power
+    Commit = 8,
+}
+
+impl Step {
+    /// The name of this step, as in Tendermint
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Step::NewHeight => "RoundStepNewHeight",
+            Step::NewRound => "RoundStepNewRound",
+            Step::Propose => "RoundStepPropose",
+            Step::Prevote => "RoundStepPrevote",
+            Step::PrevoteWait => "RoundStepPrevoteWait",
+            Step::Precommit => "RoundStepPrecommit",
+            Step::PrecommitWait => "RoundStepPrecommitWait",
+            Step::Commit => "RoundStepCommit",
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<u8> for Step {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Step::NewHeight),
+            2 => Ok(Step::NewRound),
+            3 => Ok(Step::Propose),
+            4 => Ok(Step::Prevote),
+            5 => Ok(Step::PrevoteWait),
+            6 => Ok(Step::Precommit),
+            7 => Ok(Step::PrecommitWait),
+            8 => Ok(Step::Commit),
+            _ => Err(format_err!(Kind::OutOfRange, "invalid round step {}", value).into()),
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        (1..=8)
+            .map(|value| Step::try_from(value).unwrap())
+            .find(|step| step.as_str() == s)
+            .ok_or_else(|| format_err!(Kind::Parse, "invalid round step {}", s).into())
+    }
+}
+
+impl From<Step> for u8 {
+    fn from(step: Step) -> u8 {
+        step as u8
+    }
+}
+
+/// Prevotes and precommits of a round
+#[derive(Clone, Debug)]
+struct RoundVotes {
+    prevotes: VoteSet,
+    precommits: VoteSet,
+}
+
+/// State of a validator going through the rounds of consensus for a given
+/// height, as Tendermint's `RoundState`.
+///
+/// The state only moves forward through legal transitions: within a round,
+/// steps can be skipped but not revisited, a later round can be entered at
+/// any time before committing, and the next height can only be entered once
+/// a block was committed. Proposals and votes for the height are tracked
+/// along the way, which tells when a block can be locked or committed.
+#[derive(Clone, Debug)]
+pub struct RoundState {
+    chain_id: chain::Id,
+    height: block::Height,
+    round: Round,
+    step: Step,
+    validators: validator::Set,
+    // Proposals by round
+    proposals: BTreeMap<Round, Proposal>,
+    votes: BTreeMap<Round, RoundVotes>,
+    locked: Option<(Round, block::Id)>,
+}
+
+impl RoundState {
+    /// State at the start of the given height, which the given validators
+    /// decide on.
+    ///
+    /// The proposer priorities of the validator set must be those of the
+    /// height, so as to determine the proposer of each round.
+    pub fn new(chain_id: chain::Id, height: block::Height, validators: validator::Set) -> Self {
+        RoundState {
+            chain_id,
+            height,
+            round: Round::default(),
+            step: Step::NewHeight,
+            validators,
+            proposals: BTreeMap::new(),
+            votes: BTreeMap::new(),
+            locked: None,
+        }
+    }
+
+    /// Current height
+    pub fn height(&self) -> block::Height {
+        self.height
+    }
+
+    /// Current round
+    pub fn round(&self) -> Round {
+        self.round
+    }
+
+    /// Current step
+    pub fn step(&self) -> Step {
+        self.step
+    }
+
+    /// The validators deciding on the current height
+    pub fn validators(&self) -> &validator::Set {
+        &self.validators
+    }
+
+    /// The proposer of the current round
+    pub fn proposer(&self) -> Option<validator::Info> {
+        self.validators.proposer_for(0, self.round)
+    }
+
+    /// The proposal received for the given round, if any
+    pub fn proposal(&self, round: Round) -> Option<&Proposal> {
+        self.proposals.get(&round)
+    }
+
+    /// The prevotes received for the given round, if any
+    pub fn prevotes(&self, round: Round) -> Option<&VoteSet> {
+        self.votes.get(&round).map(|votes| &votes.prevotes)
+    }
+
+    /// The precommits received for the given round, if any
+    pub fn precommits(&self, round: Round) -> Option<&VoteSet> {
+        self.votes.get(&round).map(|votes| &votes.precommits)
+    }
+
+    /// The block this validator is locked on, with the round it was locked
+    /// in, if any
+    pub fn locked(&self) -> Option<(Round, block::Id)> {
+        self.locked
+    }
+
+    /// The block precommitted by more than 2/3 of the voting power, with the
+    /// round of the precommits, if any
+    pub fn committed_block(&self) -> Option<(Round, block::Id)> {
+        self.votes.iter().find_map(
+            |(round, votes)| match votes.precommits.two_thirds_majority() {
+                Some(Some(block_id)) => Some((*round, block_id)),
+                _ => None,
+            },
+        )
+    }
+
+    /// Whether the state can move to the given step of the given round of
+    /// the current height.
+    pub fn can_enter(&self, round: Round, step: Step) -> bool {
+        if self.step == Step::Commit {
+            return false;
+        }
+        let legal = if round == self.round {
+            step > self.step && step != Step::NewHeight
+        } else {
+            round > self.round && step == Step::NewRound
+        };
+        legal && (step != Step::Commit || self.committed_block().is_some())
+    }
+
+    /// Move to the given step of the given round of the current height.
+    ///
+    /// Fails if the transition is not legal (see [`RoundState::can_enter`]),
+    /// e.g. to go back to an earlier step, or to commit before a block was
+    /// precommitted by more than 2/3 of the voting power.
+    pub fn enter(&mut self, round: Round, step: Step) -> Result<(), Error> {
+        if !self.can_enter(round, step) {
+            fail!(
+                Kind::InvalidStateTransition,
+                "cannot move from {}/{}/{} to {}/{}/{}",
+                self.height,
+                self.round,
+                self.step,
+                self.height,
+                round,
+                step
+            );
+        }
+        self.round = round;
+        self.step = step;
+        Ok(())
+    }
+
+    /// Move to the next height, which the given validators decide on, once
+    /// the current height is committed. Returns the commit for the current
+    /// height.
+    pub fn next_height(&mut self, validators: validator::Set) -> Result<Commit, Error> {
+        if self.step != Step::Commit {
+            fail!(
+                Kind::InvalidStateTransition,
+                "cannot move to the next height from {}/{}/{}",
+                self.height,
+                self.round,
+                self.step
+            );
+        }
+        let commit = match self.committed_block() {
+            Some((round, _)) => self.votes[&round].precommits.make_commit()?,
+            None => fail!(Kind::InvalidStateTransition, "no committed block"),
+        };
+
+        *self = RoundState::new(self.chain_id.clone(), self.height.increment(), validators);
+        Ok(commit)
+    }
+
+    /// Verify the given proposal and record it for its round.
+    ///
+    /// Returns `false` if the same proposal was already recorded. Fails if
+    /// the proposal is not for the current height, was not validly signed by
+    /// the proposer of its round, or conflicts with another proposal for the
+    /// round.
+    pub fn set_proposal(&mut self, proposal: Proposal) -> Result<bool, Error> {
+        if proposal.height != self.height {
+            fail!(
+                Kind::InvalidProposal,
+                "expected proposal for height {}, got {}",
+                self.height,
+                proposal.height
+            );
+        }
+        match proposal.pol_round {
+            Some(pol_round) if pol_round >= proposal.round => fail!(
+                Kind::InvalidProposal,
+                "POL round {} is not before the round {}",
+                pol_round,
+                proposal.round
+            ),
+            _ => {}
+        }
+        let proposer = self
+            .validators
+            .proposer_for(0, proposal.round)
+            .ok_or_else(|| Kind::InvalidProposal.context("empty validator set"))?;
+        proposal.verify(self.chain_id.clone(), &proposer.pub_key)?;
+
+        match self.proposals.get(&proposal.round) {
+            Some(existing) if existing.block_id == proposal.block_id => Ok(false),
+            Some(_) => fail!(
+                Kind::InvalidProposal,
+                "conflicting proposals from {} for round {}",
+                proposer.address,
+                proposal.round
+            ),
+            None => {
+                self.proposals.insert(proposal.round, proposal);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Verify the given vote for the current height and add it to the votes
+    /// of its round (see [`VoteSet::add_vote`]).
+    pub fn add_vote(&mut self, vote: Vote) -> Result<AddedVote, Error> {
+        if vote.height != self.height {
+            fail!(
+                Kind::InvalidVote,
+                "expected vote for height {}, got {}",
+                self.height,
+                vote.height
+            );
+        }
+
+        let (chain_id, height, validators) = (&self.chain_id, self.height, &self.validators);
+        let votes = self.votes.entry(vote.round).or_insert_with(|| {
+            let vote_set = |vote_type| {
+                VoteSet::new(
+                    chain_id.clone(),
+                    height,
+                    vote.round,
+                    vote_type,
+                    validators.clone(),
+                )
+            };
+            RoundVotes {
+                prevotes: vote_set(vote::Type::Prevote),
+                precommits: vote_set(vote::Type::Precommit),
+            }
+        });
+        match vote.vote_type {
+            vote::Type::Prevote => votes.prevotes.add_vote(vote),
+            vote::Type::Precommit => votes.precommits.add_vote(vote),
+        }
+    }
+
+    /// Lock on the given block in the current round, which requires that
+    /// more than 2/3 of the voting power prevoted for it in this round.
+    pub fn lock(&mut self, block_id: block::Id) -> Result<(), Error> {
+        if self.prevote_majority() != Some(Some(block_id)) {
+            fail!(
+                Kind::InvalidStateTransition,
+                "no 2/3 majority of prevotes for {} in round {}",
+                block_id,
+                self.round
+            );
+        }
+        self.locked = Some((self.round, block_id));
+        Ok(())
+    }
+
+    /// Release the lock, which requires that more than 2/3 of the voting
+    /// power prevoted for nil in the current round.
+    pub fn unlock(&mut self) -> Result<(), Error> {
+        if self.prevote_majority() != Some(None) {
+            fail!(
+                Kind::InvalidStateTransition,
+                "no 2/3 majority of prevotes for nil in round {}",
+                self.round
+            );
+        }
+        self.locked = None;
+        Ok(())
+    }
+
+    fn prevote_majority(&self) -> Option<Option<block::Id>> {
+        self.prevotes(self.round)
+            .and_then(VoteSet::two_thirds_majority)
+    }
+}
+
+impl fmt::Display for RoundState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.height, self.round, u8::from(self.step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::proposal::Type as ProposalType;
+    use crate::test::signing_key;
+    use crate::vote::ValidatorIndex;
+    use crate::{account, private_key, Hash, Signature, Time};
+
+    fn block_id(seed: u8) -> block::Id {
+        block::Id {
+            hash: Hash::Sha256([seed; 32]),
+            part_set_header: block::parts::Header::new(1, Hash::Sha256([seed; 32])).unwrap(),
+        }
+    }
+
+    struct Network {
+        chain_id: chain::Id,
+        keys: Vec<private_key::Ed25519>,
+        validators: validator::Set,
+    }
+
+    impl Network {
+        fn new() -> Self {
+            let keys: Vec<_> = (1..=4).map(signing_key).collect();
+            let validators = validator::Set::without_proposer(
+                keys.iter()
+                    .map(|key| validator::Info::new(key.public.into(), vote::Power::from(10_u32)))
+                    .collect(),
+            );
+            Network {
+                chain_id: "test-chain".parse().unwrap(),
+                keys,
+                validators,
+            }
+        }
+
+        fn key(&self, address: account::Id) -> &private_key::Ed25519 {
+            self.keys
+                .iter()
+                .find(|key| account::Id::from(key.public) == address)
+                .unwrap()
+        }
+
+        fn proposal(&self, round: Round, block_id: block::Id) -> Proposal {
+            let proposer = self.validators.proposer_for(0, round).unwrap();
+            let mut proposal = Proposal {
+                msg_type: ProposalType::Proposal,
+                height: block::Height::from(3_u32),
+                round,
+                pol_round: None,
+                block_id: Some(block_id),
+                timestamp: Some(Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap()),
+                signature: Signature::default(),
+            };
+            proposal
+                .sign(self.chain_id.clone(), self.key(proposer.address))
+                .unwrap();
+            proposal
+        }
+
+        fn vote(
+            &self,
+            index: usize,
+            vote_type: vote::Type,
+            round: Round,
+            block_id: Option<block::Id>,
+        ) -> Vote {
+            let validator = self.validators.validators()[index];
+            let mut builder = Vote::builder()
+                .vote_type(vote_type)
+                .height(block::Height::from(3_u32))
+                .round(round)
+                .timestamp(Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap())
+                .validator(
+                    validator.address,
+                    ValidatorIndex::try_from(index as u32).unwrap(),
+                );
+            if let Some(block_id) = block_id {
+                builder = builder.block_id(block_id);
+            }
+            builder
+                .sign(self.chain_id.clone(), self.key(validator.address))
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn step_conversions() {
+        for value in 1..=8_u8 {
+            assert_eq!(u8::from(Step::try_from(value).unwrap()), value);
+        }
+        assert!(Step::try_from(0).is_err());
+        assert!(Step::try_from(9).is_err());
+        assert_eq!(Step::PrevoteWait.to_string(), "RoundStepPrevoteWait");
+        assert_eq!("RoundStepCommit".parse::<Step>().unwrap(), Step::Commit);
+        assert!("Commit".parse::<Step>().is_err());
+    }
+
+    #[test]
+    fn transitions() {
+        let network = Network::new();
+        let mut state = RoundState::new(
+            network.chain_id.clone(),
+            block::Height::from(3_u32),
+            network.validators.clone(),
+        );
+        let round0 = Round::default();
+        let round1 = Round::from(1_u8);
+
+        state.enter(round0, Step::NewRound).unwrap();
+        // Steps can be skipped, but not revisited
+        state.enter(round0, Step::Prevote).unwrap();
+        assert!(state.enter(round0, Step::Propose).is_err());
+        assert!(state.enter(round0, Step::Prevote).is_err());
+        assert!(state.enter(round0, Step::NewHeight).is_err());
+        // Nothing was precommitted yet
+        assert!(state.enter(round0, Step::Commit).is_err());
+        assert!(state.next_height(network.validators.clone()).is_err());
+
+        // Later rounds start with a new round step
+        assert!(state.enter(round1, Step::Propose).is_err());
+        state.enter(round1, Step::NewRound).unwrap();
+        assert!(state.enter(round0, Step::NewRound).is_err());
+        assert_eq!(state.to_string(), "3/1/2");
+    }
+
+    #[test]
+    fn decide_height() {
+        let network = Network::new();
+        let mut state = RoundState::new(
+            network.chain_id.clone(),
+            block::Height::from(3_u32),
+            network.validators.clone(),
+        );
+        let round = Round::default();
+        let block = block_id(1);
+
+        state.enter(round, Step::Propose).unwrap();
+        assert!(state.set_proposal(network.proposal(round, block)).unwrap());
+        assert!(!state.set_proposal(network.proposal(round, block)).unwrap());
+        assert!(state
+            .set_proposal(network.proposal(round, block_id(2)))
+            .is_err());
+        let mut forged = network.proposal(round, block);
+        forged.timestamp = Some(Time::parse_from_rfc3339("2021-01-02T00:00:00Z").unwrap());
+        assert!(state.set_proposal(forged).is_err());
+        assert_eq!(state.proposal(round).unwrap().block_id, Some(block));
+
+        state.enter(round, Step::Prevote).unwrap();
+        for index in 0..2 {
+            state
+                .add_vote(network.vote(index, vote::Type::Prevote, round, Some(block)))
+                .unwrap();
+        }
+        assert!(state.lock(block).is_err());
+        state
+            .add_vote(network.vote(2, vote::Type::Prevote, round, Some(block)))
+            .unwrap();
+        assert!(state.unlock().is_err());
+        state.lock(block).unwrap();
+        assert_eq!(state.locked(), Some((round, block)));
+
+        state.enter(round, Step::Precommit).unwrap();
+        for index in 0..3 {
+            state
+                .add_vote(network.vote(index, vote::Type::Precommit, round, Some(block)))
+                .unwrap();
+        }
+        assert_eq!(state.committed_block(), Some((round, block)));
+        assert!(state
+            .add_vote(network.vote(3, vote::Type::Precommit, round, None))
+            .is_ok());
+
+        state.enter(round, Step::Commit).unwrap();
+        assert!(state.enter(Round::from(1_u8), Step::NewRound).is_err());
+        let commit = state.next_height(network.validators.clone()).unwrap();
+        network
+            .validators
+            .verify_commit(&network.chain_id, &block, commit.height, &commit)
+            .unwrap();
+
+        assert_eq!(state.height(), block::Height::from(4_u32));
+        assert_eq!(state.step(), Step::NewHeight);
+        assert_eq!(state.locked(), None);
+        assert!(state.precommits(round).is_none());
+        // Votes for other heights are rejected
+        assert!(state
+            .add_vote(network.vote(0, vote::Type::Prevote, round, None))
+            .is_err());
+    }
+}
@@ -0,0 +1,447 @@
+//! Tendermint's consensus write-ahead log (WAL)
+//!
+//! The WAL is a sequence of messages, each framed as the CRC-32C checksum
+//! and the length of its data (as big-endian `u32`s), followed by the data:
+//! the protobuf encoding of a `TimedWALMessage`. `EndHeight` messages mark
+//! the end of each height.
+
+use crate::block::{Height, Round};
+use crate::consensus::Step;
+use crate::prelude::*;
+use crate::{Error, Kind, Time};
+use core::convert::{TryFrom, TryInto};
+use core::time::Duration;
+use std::io::{self, Read, Write};
+use tendermint_proto::consensus::{
+    wal_message::Sum, EndHeight as RawEndHeight, MsgInfo as RawMsgInfo,
+    TimedWalMessage as RawTimedWalMessage, TimeoutInfo as RawTimeoutInfo,
+    WalMessage as RawWalMessage,
+};
+use tendermint_proto::google::protobuf::Duration as RawDuration;
+use tendermint_proto::types::EventDataRoundState as RawEventDataRoundState;
+use tendermint_proto::Protobuf;
+
+/// Maximum size of the data of a message, as in Tendermint
+pub const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// Size of the checksum and length preceding the data of a message
+const HEADER_SIZE: usize = 8;
+
+/// A message of the WAL
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    /// The consensus state moved to a new step
+    RoundState {
+        /// Height
+        height: Height,
+        /// Round
+        round: Round,
+        /// Step
+        step: Step,
+    },
+
+    /// A consensus message received from a peer, or sent by the node itself
+    /// if the peer ID is empty
+    MsgInfo(RawMsgInfo),
+
+    /// A timeout elapsed
+    Timeout {
+        /// Duration of the timeout
+        duration: Duration,
+        /// Height
+        height: Height,
+        /// Round
+        round: Round,
+        /// Step at which the timeout was scheduled
+        step: Step,
+    },
+
+    /// The given height was committed
+    EndHeight(Height),
+}
+
+/// A message of the WAL, with the time at which it was written
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimedMessage {
+    /// Time at which the message was written
+    pub time: Time,
+    /// The message
+    pub message: Message,
+}
+
+impl Protobuf<RawTimedWalMessage> for TimedMessage {}
+
+impl TryFrom<RawTimedWalMessage> for TimedMessage {
+    type Error = Error;
+
+    fn try_from(value: RawTimedWalMessage) -> Result<Self, Self::Error> {
+        let time = value.time.ok_or(Kind::MissingTimestamp)?.try_into()?;
+        let message = match value.msg.and_then(|msg| msg.sum) {
+            Some(Sum::EventDataRoundState(state)) => Message::RoundState {
+                height: state.height.try_into()?,
+                round: state.round.try_into()?,
+                step: state.step.parse()?,
+            },
+            Some(Sum::MsgInfo(msg_info)) => Message::MsgInfo(msg_info),
+            Some(Sum::TimeoutInfo(timeout)) => {
+                let duration = timeout.duration.unwrap_or_default();
+                Message::Timeout {
+                    duration: Duration::new(
+                        duration
+                            .seconds
+                            .try_into()
+                            .map_err(|_| Kind::IntegerOverflow)?,
+                        duration
+                            .nanos
+                            .try_into()
+                            .map_err(|_| Kind::IntegerOverflow)?,
+                    ),
+                    height: timeout.height.try_into()?,
+                    round: timeout.round.try_into()?,
+                    step: u8::try_from(timeout.step)
+                        .map_err(|_| Kind::OutOfRange)?
+                        .try_into()?,
+                }
+            }
+            Some(Sum::EndHeight(end_height)) => Message::EndHeight(end_height.height.try_into()?),
+            None => return Err(Kind::MissingData.context("empty WAL message").into()),
+        };
+        Ok(TimedMessage { time, message })
+    }
+}
+
+impl From<TimedMessage> for RawTimedWalMessage {
+    fn from(value: TimedMessage) -> Self {
+        let sum = match value.message {
+            Message::RoundState {
+                height,
+                round,
+                step,
+            } => Sum::EventDataRoundState(RawEventDataRoundState {
+                height: height.into(),
+                round: round.into(),
+                step: step.to_string(),
+            }),
+            Message::MsgInfo(msg_info) => Sum::MsgInfo(msg_info),
+            Message::Timeout {
+                duration,
+                height,
+                round,
+                step,
+            } => Sum::TimeoutInfo(RawTimeoutInfo {
+                duration: Some(RawDuration {
+                    seconds: duration.as_secs() as i64,
+                    nanos: duration.subsec_nanos() as i32,
+                }),
+                height: height.into(),
+                round: round.into(),
+                step: u8::from(step).into(),
+            }),
+            Message::EndHeight(height) => Sum::EndHeight(RawEndHeight {
+                height: height.into(),
+            }),
+        };
+        RawTimedWalMessage {
+            time: Some(value.time.into()),
+            msg: Some(RawWalMessage { sum: Some(sum) }),
+        }
+    }
+}
+
+/// Writes messages to a WAL.
+#[derive(Debug)]
+pub struct Encoder<W> {
+    writer: W,
+}
+
+impl<W: Write> Encoder<W> {
+    /// Write messages to the given writer.
+    pub fn new(writer: W) -> Self {
+        Encoder { writer }
+    }
+
+    /// Write the given message.
+    pub fn encode(&mut self, message: &TimedMessage) -> Result<(), Error> {
+        let data = message
+            .encode_vec()
+            .map_err(|e| Kind::Protocol.context(e))?;
+        if data.len() > MAX_MESSAGE_SIZE {
+            fail!(
+                Kind::Length,
+                "WAL message of {} bytes exceeds the maximum of {} bytes",
+                data.len(),
+                MAX_MESSAGE_SIZE
+            );
+        }
+
+        let mut frame = Vec::with_capacity(HEADER_SIZE + data.len());
+        frame.extend_from_slice(&crc32c(&data).to_be_bytes());
+        frame.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&data);
+        self.writer
+            .write_all(&frame)
+            .map_err(|e| Kind::Io.context(e).into())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.writer.flush().map_err(|e| Kind::Io.context(e).into())
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads messages from a WAL.
+///
+/// Messages which are truncated, oversized, fail their checksum or cannot be
+/// decoded are reported as [`Kind::CorruptedWal`] errors. The decoder can also
+/// be used as an iterator over the messages, which stops after the first
+/// error.
+#[derive(Debug)]
+pub struct Decoder<R> {
+    reader: R,
+    failed: bool,
+}
+
+impl<R: Read> Decoder<R> {
+    /// Read messages from the given reader.
+    pub fn new(reader: R) -> Self {
+        Decoder {
+            reader,
+            failed: false,
+        }
+    }
+
+    /// Read the next message, or `None` at the end of the WAL.
+    pub fn decode(&mut self) -> Result<Option<TimedMessage>, Error> {
+        let mut header = [0; HEADER_SIZE];
+        match read_full(&mut self.reader, &mut header)? {
+            0 => return Ok(None),
+            HEADER_SIZE => {}
+            read => fail!(
+                Kind::CorruptedWal,
+                "truncated message header of {} bytes",
+                read
+            ),
+        }
+
+        let mut checksum = [0; 4];
+        let mut length = [0; 4];
+        checksum.copy_from_slice(&header[..4]);
+        length.copy_from_slice(&header[4..]);
+        let (checksum, length) = (u32::from_be_bytes(checksum), u32::from_be_bytes(length));
+        let length = length as usize;
+        if length > MAX_MESSAGE_SIZE {
+            fail!(
+                Kind::CorruptedWal,
+                "message length {} exceeds the maximum of {} bytes",
+                length,
+                MAX_MESSAGE_SIZE
+            );
+        }
+
+        let mut data = vec![0; length];
+        let read = read_full(&mut self.reader, &mut data)?;
+        if read < length {
+            fail!(
+                Kind::CorruptedWal,
+                "truncated message of {} bytes, expected {}",
+                read,
+                length
+            );
+        }
+        if crc32c(&data) != checksum {
+            fail!(Kind::CorruptedWal, "checksum mismatch");
+        }
+
+        TimedMessage::decode_vec(&data)
+            .map(Some)
+            .map_err(|e| Kind::CorruptedWal.context(e).into())
+    }
+
+    /// Skip the messages up to the `EndHeight` message of the given height,
+    /// so that the next decoded message is the first one of the next height.
+    ///
+    /// Returns `false` if the WAL has no such message.
+    pub fn skip_to_end_height(&mut self, height: Height) -> Result<bool, Error> {
+        while let Some(timed) = self.decode()? {
+            if timed.message == Message::EndHeight(height) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for Decoder<R> {
+    type Item = Result<TimedMessage, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let result = self.decode();
+        self.failed = result.is_err();
+        result.transpose()
+    }
+}
+
+/// Copy the messages of the given WAL up to the first corrupted one, as
+/// Tendermint does to repair its WAL on startup. Returns the number of
+/// messages copied.
+pub fn repair<R: Read, W: Write>(reader: R, writer: W) -> Result<usize, Error> {
+    let mut decoder = Decoder::new(reader);
+    let mut encoder = Encoder::new(writer);
+    let mut copied = 0;
+    loop {
+        match decoder.decode() {
+            Ok(Some(message)) => {
+                encoder.encode(&message)?;
+                copied += 1;
+            }
+            Ok(None) => break,
+            Err(e) if e.kind() == &Kind::CorruptedWal => break,
+            Err(e) => return Err(e),
+        }
+    }
+    encoder.flush()?;
+    Ok(copied)
+}
+
+/// Fill the given buffer, unless the end of the reader is reached first.
+/// Returns the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, Error> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(Kind::Io.context(e).into()),
+        }
+    }
+    Ok(read)
+}
+
+/// CRC-32C (Castagnoli) checksum, as used by Tendermint's WAL
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages() -> Vec<TimedMessage> {
+        let time = Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap();
+        let height = Height::from(3_u32);
+        let round = Round::from(1_u8);
+        vec![
+            Message::RoundState {
+                height,
+                round,
+                step: Step::Propose,
+            },
+            Message::MsgInfo(RawMsgInfo {
+                msg: None,
+                peer_id: "peer".to_string(),
+            }),
+            Message::Timeout {
+                duration: Duration::from_millis(1500),
+                height,
+                round,
+                step: Step::PrevoteWait,
+            },
+            Message::EndHeight(height),
+            Message::EndHeight(height.increment()),
+        ]
+        .into_iter()
+        .map(|message| TimedMessage { time, message })
+        .collect()
+    }
+
+    fn encode(messages: &[TimedMessage]) -> Vec<u8> {
+        let mut encoder = Encoder::new(Vec::new());
+        for message in messages {
+            encoder.encode(message).unwrap();
+        }
+        encoder.into_inner()
+    }
+
+    #[test]
+    fn checksum() {
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+    }
+
+    #[test]
+    fn round_trip() {
+        let messages = messages();
+        let wal = encode(&messages);
+
+        let decoded = Decoder::new(wal.as_slice())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(decoded, messages);
+
+        let mut decoder = Decoder::new(wal.as_slice());
+        assert!(decoder.skip_to_end_height(Height::from(3_u32)).unwrap());
+        assert_eq!(decoder.decode().unwrap(), Some(messages[4].clone()));
+        assert_eq!(decoder.decode().unwrap(), None);
+        let mut decoder = Decoder::new(wal.as_slice());
+        assert!(!decoder.skip_to_end_height(Height::from(5_u32)).unwrap());
+    }
+
+    #[test]
+    fn corruption() {
+        let messages = messages();
+        let wal = encode(&messages);
+        let first_len = encode(&messages[..1]).len();
+
+        let corrupted = |wal: &[u8]| {
+            let mut decoder = Decoder::new(wal);
+            decoder.decode().unwrap();
+            let err = decoder.decode().unwrap_err();
+            assert_eq!(err.kind(), &Kind::CorruptedWal);
+        };
+        // Truncated header and data
+        corrupted(&wal[..first_len + 4]);
+        corrupted(&wal[..first_len + HEADER_SIZE + 1]);
+        // Checksum mismatch
+        let mut flipped = wal.clone();
+        flipped[first_len + HEADER_SIZE] ^= 1;
+        corrupted(&flipped);
+        // Oversized message
+        let mut oversized = wal.clone();
+        oversized[first_len + 4..first_len + 8].copy_from_slice(&u32::MAX.to_be_bytes());
+        corrupted(&oversized);
+
+        // Iterating stops at the first corrupted message
+        assert_eq!(Decoder::new(flipped.as_slice()).count(), 2);
+
+        let mut repaired = Vec::new();
+        assert_eq!(repair(flipped.as_slice(), &mut repaired).unwrap(), 1);
+        assert_eq!(repaired, wal[..first_len].to_vec());
+        let mut repaired = Vec::new();
+        assert_eq!(repair(wal.as_slice(), &mut repaired).unwrap(), 5);
+        assert_eq!(repaired, wal);
+    }
+}
@@ -1,171 +1,146 @@
 //! Error types
 
-use anomaly::{BoxError, Context};
-use thiserror::Error;
+use crate::{account, block, chain, vote, Hash, Time};
+use core::fmt;
 
-use crate::account;
-use crate::vote;
+#[cfg(feature = "std")]
+pub use anomaly::{BoxError, Context};
+
+#[cfg(not(feature = "std"))]
+mod context;
+
+#[cfg(not(feature = "std"))]
+pub use self::context::{BoxError, Context};
+
+/// Error type
+#[cfg(feature = "std")]
+pub type Error = anomaly::Error<Kind>;
 
 /// Error type
-pub type Error = BoxError;
+#[cfg(not(feature = "std"))]
+pub type Error = self::context::Error<Kind>;
 
 /// Kinds of errors
-#[derive(Clone, Eq, PartialEq, Debug, Error)]
+///
+/// Each kind has a stable numeric [`Kind::code`]. New kinds get the next
+/// unused code, so they're added at the end.
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum Kind {
     /// Cryptographic operation failed
-    #[error("cryptographic error")]
     Crypto,
 
     /// Malformatted or otherwise invalid cryptographic key
-    #[error("invalid key")]
     InvalidKey,
 
     /// Input/output error
-    #[error("I/O error")]
     Io,
 
     /// Length incorrect or too long
-    #[error("length error")]
     Length,
 
     /// Parse error
-    #[error("parse error")]
     Parse,
 
     /// Network protocol-related errors
-    #[error("protocol error")]
     Protocol,
 
     /// Value out-of-range
-    #[error("value out of range")]
     OutOfRange,
 
     /// Signature invalid
-    #[error("bad signature")]
     SignatureInvalid,
 
     /// invalid message type
-    #[error("invalid message type")]
     InvalidMessageType,
 
     /// Negative block height
-    #[error("negative height")]
     NegativeHeight,
 
     /// Negative voting round
-    #[error("negative round")]
     NegativeRound,
 
     /// Negative POL round
-    #[error("negative POL round")]
     NegativePolRound,
 
     /// Negative validator index in vote
-    #[error("negative validator index")]
     NegativeValidatorIndex,
 
     /// Invalid hash size in part_set_header
-    #[error("invalid hash: expected hash size to be 32 bytes")]
     InvalidHashSize,
 
     /// No timestamp in vote or block header
-    #[error("no timestamp")]
     NoTimestamp,
 
     /// Invalid timestamp
-    #[error("invalid timestamp")]
     InvalidTimestamp,
 
     /// Invalid account ID length
-    #[error("invalid account ID length")]
     InvalidAccountIdLength,
 
     /// Invalid signature ID length
-    #[error("invalid signature ID length")]
     InvalidSignatureIdLength,
 
     /// Overflow during conversion
-    #[error("integer overflow")]
     IntegerOverflow,
 
     /// No Vote found during conversion
-    #[error("no vote found")]
     NoVoteFound,
 
     /// No Proposal found during conversion
-    #[error("no proposal found")]
     NoProposalFound,
 
     /// Invalid AppHash length found during conversion
-    #[error("invalid app hash Length")]
     InvalidAppHashLength,
 
     /// Invalid PartSetHeader
-    #[error("invalid part set header")]
     InvalidPartSetHeader,
 
     /// Missing Header in Block
-    #[error("missing header field")]
     MissingHeader,
 
     /// Missing Data in Block
-    #[error("missing data field")]
     MissingData,
 
     /// Missing Evidence in Block
-    #[error("missing evidence field")]
     MissingEvidence,
 
     /// Missing Timestamp in Block
-    #[error("missing timestamp field")]
     MissingTimestamp,
 
     /// Invalid Block
-    #[error("invalid block")]
     InvalidBlock,
 
     /// Invalid first Block
-    #[error("invalid first block")]
     InvalidFirstBlock,
 
     /// Missing Version field
-    #[error("missing version")]
     MissingVersion,
 
     /// Invalid Header
-    #[error("invalid header")]
     InvalidHeader,
 
     /// Invalid first Header
-    #[error("invalid first header")]
     InvalidFirstHeader,
 
     /// Invalid signature in CommitSig
-    #[error("invalid signature")]
     InvalidSignature,
 
     /// Invalid validator address in CommitSig
-    #[error("invalid validator address")]
     InvalidValidatorAddress,
 
     /// Invalid Signed Header
-    #[error("invalid signed header")]
     InvalidSignedHeader,
 
     /// Invalid Evidence
-    #[error("invalid evidence")]
     InvalidEvidence,
 
     /// Invalid BlockIdFlag
-    #[error("invalid block id flag")]
     BlockIdFlag,
 
     /// Negative voting power
-    #[error("negative power")]
     NegativePower,
 
     /// Mismatch between raw voting power and computed one in validator set
-    #[error("mismatch between raw voting power ({raw}) and computed one ({computed})")]
     RawVotingPowerMismatch {
         /// raw voting power
         raw: vote::Power,
@@ -174,33 +149,379 @@ pub enum Kind {
     },
 
     /// Missing Public Key
-    #[error("missing public key")]
     MissingPublicKey,
 
     /// Invalid validator parameters
-    #[error("invalid validator parameters")]
     InvalidValidatorParams,
 
     /// Invalid version parameters
-    #[error("invalid version parameters")]
     InvalidVersionParams,
 
     /// Negative max_age_num_blocks in Evidence parameters
-    #[error("negative max_age_num_blocks")]
     NegativeMaxAgeNum,
 
     /// Missing max_age_duration in evidence parameters
-    #[error("missing max_age_duration")]
     MissingMaxAgeDuration,
 
     /// Proposer not found in validator set
-    #[error("proposer with address '{}' not found in validator set", _0)]
     ProposerNotFound(account::Id),
+
+    /// Invalid commit
+    InvalidCommit,
+
+    /// Invalid Merkle proof
+    InvalidMerkleProof,
+
+    /// Invalid validator set update
+    InvalidValidatorUpdate,
+
+    /// Signing would amount to double signing
+    DoubleSign,
+
+    /// Error returned by a remote signer
+    RemoteSigner,
+
+    /// Invalid vote
+    InvalidVote,
+
+    /// Invalid consensus parameters
+    InvalidConsensusParams,
+
+    /// Invalid genesis document
+    InvalidGenesis,
+
+    /// Not enough voting power signed a commit
+    InsufficientVotingPower {
+        /// voting power of the validators which signed
+        tallied: u64,
+        /// total voting power of the validator set
+        total: u64,
+    },
+
+    /// A header belongs to another chain than expected
+    ChainIdMismatch {
+        /// expected chain ID
+        expected: chain::Id,
+        /// chain ID of the header
+        got: chain::Id,
+    },
+
+    /// A header and its commit are for different heights
+    HeightMismatch {
+        /// height of the header
+        header: block::Height,
+        /// height of the commit
+        commit: block::Height,
+    },
+
+    /// A commit signs another block than expected
+    BlockIdMismatch {
+        /// expected block ID
+        expected: block::Id,
+        /// block ID signed by the commit
+        got: block::Id,
+    },
+
+    /// A header's validators hash doesn't match the validator set
+    ValidatorsHashMismatch {
+        /// validators hash of the header
+        header: Hash,
+        /// hash of the validator set
+        validators: Hash,
+    },
+
+    /// A commit doesn't have exactly one signature per validator
+    SignatureCountMismatch {
+        /// number of validators
+        validators: usize,
+        /// number of signatures in the commit
+        signatures: usize,
+    },
+
+    /// A validator voted more than once in a commit
+    DuplicateVote(account::Id),
+
+    /// A validator's signature in a commit failed to verify
+    InvalidCommitSignature(account::Id),
+
+    /// A validator set update refers to a validator more than once
+    DuplicateValidatorUpdate(account::Id),
+
+    /// A validator to remove from a validator set isn't part of it
+    ValidatorNotFound(account::Id),
+
+    /// Invalid ABCI request or response
+    InvalidAbciMessage,
+
+    /// A consensus round state cannot move to the requested height, round
+    /// or step
+    InvalidStateTransition,
+
+    /// Invalid proposal
+    InvalidProposal,
+
+    /// A consensus write-ahead log is corrupted
+    CorruptedWal,
+
+    /// A block or state store holds an entry which cannot be decoded
+    CorruptedStore,
+
+    /// The time of a header isn't the BFT time defined by the previous commit
+    BftTimeMismatch {
+        /// time of the header
+        header: Time,
+        /// median time of the previous commit
+        median: Time,
+    },
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Crypto => f.write_str("cryptographic error"),
+            Kind::InvalidKey => f.write_str("invalid key"),
+            Kind::Io => f.write_str("I/O error"),
+            Kind::Length => f.write_str("length error"),
+            Kind::Parse => f.write_str("parse error"),
+            Kind::Protocol => f.write_str("protocol error"),
+            Kind::OutOfRange => f.write_str("value out of range"),
+            Kind::SignatureInvalid => f.write_str("bad signature"),
+            Kind::InvalidMessageType => f.write_str("invalid message type"),
+            Kind::NegativeHeight => f.write_str("negative height"),
+            Kind::NegativeRound => f.write_str("negative round"),
+            Kind::NegativePolRound => f.write_str("negative POL round"),
+            Kind::NegativeValidatorIndex => f.write_str("negative validator index"),
+            Kind::InvalidHashSize => f.write_str("invalid hash: expected hash size to be 32 bytes"),
+            Kind::NoTimestamp => f.write_str("no timestamp"),
+            Kind::InvalidTimestamp => f.write_str("invalid timestamp"),
+            Kind::InvalidAccountIdLength => f.write_str("invalid account ID length"),
+            Kind::InvalidSignatureIdLength => f.write_str("invalid signature ID length"),
+            Kind::IntegerOverflow => f.write_str("integer overflow"),
+            Kind::NoVoteFound => f.write_str("no vote found"),
+            Kind::NoProposalFound => f.write_str("no proposal found"),
+            Kind::InvalidAppHashLength => f.write_str("invalid app hash Length"),
+            Kind::InvalidPartSetHeader => f.write_str("invalid part set header"),
+            Kind::MissingHeader => f.write_str("missing header field"),
+            Kind::MissingData => f.write_str("missing data field"),
+            Kind::MissingEvidence => f.write_str("missing evidence field"),
+            Kind::MissingTimestamp => f.write_str("missing timestamp field"),
+            Kind::InvalidBlock => f.write_str("invalid block"),
+            Kind::InvalidFirstBlock => f.write_str("invalid first block"),
+            Kind::MissingVersion => f.write_str("missing version"),
+            Kind::InvalidHeader => f.write_str("invalid header"),
+            Kind::InvalidFirstHeader => f.write_str("invalid first header"),
+            Kind::InvalidSignature => f.write_str("invalid signature"),
+            Kind::InvalidValidatorAddress => f.write_str("invalid validator address"),
+            Kind::InvalidSignedHeader => f.write_str("invalid signed header"),
+            Kind::InvalidEvidence => f.write_str("invalid evidence"),
+            Kind::BlockIdFlag => f.write_str("invalid block id flag"),
+            Kind::NegativePower => f.write_str("negative power"),
+            Kind::RawVotingPowerMismatch { raw, computed } => write!(
+                f,
+                "mismatch between raw voting power ({}) and computed one ({})",
+                raw, computed
+            ),
+            Kind::MissingPublicKey => f.write_str("missing public key"),
+            Kind::InvalidValidatorParams => f.write_str("invalid validator parameters"),
+            Kind::InvalidVersionParams => f.write_str("invalid version parameters"),
+            Kind::NegativeMaxAgeNum => f.write_str("negative max_age_num_blocks"),
+            Kind::MissingMaxAgeDuration => f.write_str("missing max_age_duration"),
+            Kind::ProposerNotFound(id) => write!(
+                f,
+                "proposer with address '{}' not found in validator set",
+                id
+            ),
+            Kind::InvalidCommit => f.write_str("invalid commit"),
+            Kind::InvalidMerkleProof => f.write_str("invalid Merkle proof"),
+            Kind::InvalidValidatorUpdate => f.write_str("invalid validator update"),
+            Kind::DoubleSign => f.write_str("double sign attempt"),
+            Kind::RemoteSigner => f.write_str("remote signer error"),
+            Kind::InvalidVote => f.write_str("invalid vote"),
+            Kind::InvalidConsensusParams => f.write_str("invalid consensus parameters"),
+            Kind::InvalidGenesis => f.write_str("invalid genesis"),
+            Kind::InsufficientVotingPower { tallied, total } => write!(
+                f,
+                "insufficient voting power: {} of {} signed",
+                tallied, total
+            ),
+            Kind::ChainIdMismatch { expected, got } => {
+                write!(f, "header belongs to chain {}, expected {}", got, expected)
+            }
+            Kind::HeightMismatch { header, commit } => write!(
+                f,
+                "header height {} does not match commit height {}",
+                header, commit
+            ),
+            Kind::BlockIdMismatch { expected, got } => {
+                write!(f, "commit signs block {}, expected {}", got, expected)
+            }
+            Kind::ValidatorsHashMismatch { header, validators } => write!(
+                f,
+                "header's validators hash {} does not match the validator set's hash {}",
+                header, validators
+            ),
+            Kind::SignatureCountMismatch {
+                validators,
+                signatures,
+            } => write!(
+                f,
+                "commit has {} signatures, but there are {} validators",
+                signatures, validators
+            ),
+            Kind::DuplicateVote(id) => write!(f, "double vote from {}", id),
+            Kind::InvalidCommitSignature(id) => write!(f, "invalid commit signature from {}", id),
+            Kind::DuplicateValidatorUpdate(id) => {
+                write!(f, "duplicate entry for validator {}", id)
+            }
+            Kind::ValidatorNotFound(id) => write!(f, "validator {} not found", id),
+            Kind::InvalidAbciMessage => f.write_str("invalid ABCI message"),
+            Kind::InvalidStateTransition => f.write_str("invalid consensus state transition"),
+            Kind::InvalidProposal => f.write_str("invalid proposal"),
+            Kind::CorruptedWal => f.write_str("corrupted WAL"),
+            Kind::CorruptedStore => f.write_str("corrupted store"),
+            Kind::BftTimeMismatch { header, median } => write!(
+                f,
+                "header time {} does not match the median time {} of the previous commit",
+                header, median
+            ),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Kind {}
+
 impl Kind {
+    /// A numeric code identifying this kind of error.
+    ///
+    /// Codes are stable: they are never changed or reused across releases, so
+    /// they can be persisted or sent over the wire, unlike the variants'
+    /// positions.
+    pub fn code(&self) -> u16 {
+        match self {
+            Kind::Crypto => 1,
+            Kind::InvalidKey => 2,
+            Kind::Io => 3,
+            Kind::Length => 4,
+            Kind::Parse => 5,
+            Kind::Protocol => 6,
+            Kind::OutOfRange => 7,
+            Kind::SignatureInvalid => 8,
+            Kind::InvalidMessageType => 9,
+            Kind::NegativeHeight => 10,
+            Kind::NegativeRound => 11,
+            Kind::NegativePolRound => 12,
+            Kind::NegativeValidatorIndex => 13,
+            Kind::InvalidHashSize => 14,
+            Kind::NoTimestamp => 15,
+            Kind::InvalidTimestamp => 16,
+            Kind::InvalidAccountIdLength => 17,
+            Kind::InvalidSignatureIdLength => 18,
+            Kind::IntegerOverflow => 19,
+            Kind::NoVoteFound => 20,
+            Kind::NoProposalFound => 21,
+            Kind::InvalidAppHashLength => 22,
+            Kind::InvalidPartSetHeader => 23,
+            Kind::MissingHeader => 24,
+            Kind::MissingData => 25,
+            Kind::MissingEvidence => 26,
+            Kind::MissingTimestamp => 27,
+            Kind::InvalidBlock => 28,
+            Kind::InvalidFirstBlock => 29,
+            Kind::MissingVersion => 30,
+            Kind::InvalidHeader => 31,
+            Kind::InvalidFirstHeader => 32,
+            Kind::InvalidSignature => 33,
+            Kind::InvalidValidatorAddress => 34,
+            Kind::InvalidSignedHeader => 35,
+            Kind::InvalidEvidence => 36,
+            Kind::BlockIdFlag => 37,
+            Kind::NegativePower => 38,
+            Kind::RawVotingPowerMismatch { .. } => 39,
+            Kind::MissingPublicKey => 40,
+            Kind::InvalidValidatorParams => 41,
+            Kind::InvalidVersionParams => 42,
+            Kind::NegativeMaxAgeNum => 43,
+            Kind::MissingMaxAgeDuration => 44,
+            Kind::ProposerNotFound(_) => 45,
+            Kind::InvalidCommit => 46,
+            Kind::InvalidMerkleProof => 47,
+            Kind::InvalidValidatorUpdate => 48,
+            Kind::DoubleSign => 49,
+            Kind::RemoteSigner => 50,
+            Kind::InvalidVote => 51,
+            Kind::InvalidConsensusParams => 52,
+            Kind::InvalidGenesis => 53,
+            Kind::InsufficientVotingPower { .. } => 54,
+            Kind::ChainIdMismatch { .. } => 55,
+            Kind::HeightMismatch { .. } => 56,
+            Kind::BlockIdMismatch { .. } => 57,
+            Kind::ValidatorsHashMismatch { .. } => 58,
+            Kind::SignatureCountMismatch { .. } => 59,
+            Kind::DuplicateVote(_) => 60,
+            Kind::InvalidCommitSignature(_) => 61,
+            Kind::DuplicateValidatorUpdate(_) => 62,
+            Kind::ValidatorNotFound(_) => 63,
+            Kind::InvalidAbciMessage => 64,
+            Kind::InvalidStateTransition => 65,
+            Kind::InvalidProposal => 66,
+            Kind::CorruptedWal => 67,
+            Kind::CorruptedStore => 68,
+            Kind::BftTimeMismatch { .. } => 69,
+        }
+    }
+
     /// Add additional context.
     pub fn context(self, source: impl Into<BoxError>) -> Context<Kind> {
         Context::new(self, Some(source.into()))
     }
 }
+
+/// Create a new error (of a given kind) with a formatted message
+macro_rules! format_err {
+    ($kind:path, $msg:expr) => {
+        $crate::error::Context::new(
+            $kind,
+            Some($crate::prelude::ToString::to_string(&$msg).into()),
+        )
+    };
+    ($kind:path, $fmt:expr, $($arg:tt)+) => {
+        format_err!($kind, &$crate::prelude::format!($fmt, $($arg)+))
+    };
+}
+
+/// Create and return an error with a formatted message
+macro_rules! fail {
+    ($kind:path, $msg:expr) => {
+        return Err(format_err!($kind, $msg).into())
+    };
+    ($kind:path, $fmt:expr, $($arg:tt)+) => {
+        fail!($kind, &$crate::prelude::format!($fmt, $($arg)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_stable() {
+        assert_eq!(Kind::Crypto.code(), 1);
+        assert_eq!(Kind::InvalidSignedHeader.code(), 35);
+        assert_eq!(
+            Kind::InsufficientVotingPower {
+                tallied: 1,
+                total: 3
+            }
+            .code(),
+            54
+        );
+        assert_eq!(
+            Kind::ValidatorNotFound(account::Id::new([0; 20])).code(),
+            63
+        );
+        assert_eq!(Kind::InvalidAbciMessage.code(), 64);
+        assert_eq!(Kind::InvalidProposal.code(), 66);
+        assert_eq!(Kind::CorruptedStore.code(), 68);
+    }
+}
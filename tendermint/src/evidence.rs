@@ -1,11 +1,20 @@
 //! Evidence of malfeasance by validators (i.e. signing conflicting votes).
 
+use crate::prelude::*;
 use crate::{
-    block::signed_header::SignedHeader, serializers, vote::Power, Error, Kind, Time, Vote,
+    block::{signed_header::SignedHeader, Height},
+    chain,
+    hash::SHA256_HASH_SIZE,
+    merkle, serializers,
+    trust_threshold::TrustThresholdFraction,
+    validator,
+    vote::Power,
+    Error, Hash, Kind, Time, Vote,
 };
+use core::convert::{TryFrom, TryInto};
+use core::slice;
 use serde::{Deserialize, Serialize};
-use std::convert::{TryFrom, TryInto};
-use std::slice;
+use sha2::{Digest, Sha256};
 use tendermint_proto::google::protobuf::Duration as RawDuration;
 use tendermint_proto::types::evidence::Sum as RawSum;
 use tendermint_proto::types::evidence::Sum;
@@ -13,11 +22,12 @@ use tendermint_proto::types::DuplicateVoteEvidence as RawDuplicateVoteEvidence;
 use tendermint_proto::types::Evidence as RawEvidence;
 use tendermint_proto::types::EvidenceList as RawEvidenceList;
 use tendermint_proto::types::EvidenceParams as RawEvidenceParams;
+use tendermint_proto::types::LightBlock as RawLightBlock;
+use tendermint_proto::types::LightClientAttackEvidence as RawLightClientAttackEvidence;
 use tendermint_proto::Protobuf;
 
-/// Evidence of malfeasance by validators (i.e. signing conflicting votes).
-/// encoded using an Amino prefix. There is currently only a single type of
-/// evidence: `DuplicateVoteEvidence`.
+/// Evidence of malfeasance by validators (i.e. signing conflicting votes or
+/// attempting to mislead light clients).
 ///
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#evidence>
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -28,12 +38,8 @@ pub enum Evidence {
     //#[serde(rename = "tendermint/DuplicateVoteEvidence")]
     DuplicateVote(DuplicateVoteEvidence),
 
-    /// Conflicting headers evidence - Todo: this is not implemented in protobuf, it's ignored now
-    //#[serde(rename = "tendermint/ConflictingHeadersEvidence")]
-    ConflictingHeaders(Box<ConflictingHeadersEvidence>),
-
-    /// LightClient attack evidence - Todo: Implement details
-    LightClientAttackEvidence,
+    /// Light client attack evidence
+    LightClientAttack(Box<LightClientAttackEvidence>),
 }
 
 impl TryFrom<RawEvidence> for Evidence {
@@ -42,7 +48,9 @@ impl TryFrom<RawEvidence> for Evidence {
     fn try_from(value: RawEvidence) -> Result<Self, Self::Error> {
         match value.sum.ok_or(Kind::InvalidEvidence)? {
             Sum::DuplicateVoteEvidence(ev) => Ok(Evidence::DuplicateVote(ev.try_into()?)),
-            Sum::LightClientAttackEvidence(_ev) => Ok(Evidence::LightClientAttackEvidence),
+            Sum::LightClientAttackEvidence(ev) => {
+                Ok(Evidence::LightClientAttack(Box::new(ev.try_into()?)))
+            }
         }
     }
 }
@@ -53,12 +61,99 @@ impl From<Evidence> for RawEvidence {
             Evidence::DuplicateVote(ev) => RawEvidence {
                 sum: Some(RawSum::DuplicateVoteEvidence(ev.into())),
             },
-            Evidence::ConflictingHeaders(_ev) => RawEvidence { sum: None }, // Todo: implement
-            Evidence::LightClientAttackEvidence => RawEvidence { sum: None }, // Todo: implement
+            Evidence::LightClientAttack(ev) => RawEvidence {
+                sum: Some(RawSum::LightClientAttackEvidence((*ev).into())),
+            },
+        }
+    }
+}
+
+impl Evidence {
+    /// Compute the hash of this evidence, as done by Tendermint.
+    pub fn hash(&self) -> Hash {
+        match self {
+            Evidence::DuplicateVote(_) => sha256(&self.bytes()),
+            Evidence::LightClientAttack(ev) => ev.hash(),
+        }
+    }
+
+    // The bytes Tendermint uses to compute the evidence hash and the hash of
+    // the evidence data in a block, i.e. the protobuf encoding of the
+    // evidence itself (not of the `Evidence` wrapper).
+    fn bytes(&self) -> Vec<u8> {
+        match self {
+            // Todo: Implement proper domain types so this becomes infallible
+            Evidence::DuplicateVote(ev) => ev.encode_vec().unwrap(),
+            Evidence::LightClientAttack(ev) => ev.as_ref().encode_vec().unwrap(),
+        }
+    }
+
+    /// Height at which the offence occurred.
+    pub fn height(&self) -> Height {
+        match self {
+            Evidence::DuplicateVote(ev) => ev.vote_a.height,
+            Evidence::LightClientAttack(ev) => ev.common_height,
+        }
+    }
+
+    /// Time of the block at the height of the offence.
+    pub fn time(&self) -> Time {
+        match self {
+            Evidence::DuplicateVote(ev) => ev.timestamp,
+            Evidence::LightClientAttack(ev) => ev.timestamp,
+        }
+    }
+
+    /// Verify this evidence.
+    ///
+    /// `validator_set` must be the validator set at the height of the
+    /// evidence (for light client attacks, the common height), and
+    /// `latest_height` and `latest_time` those of the latest committed block,
+    /// which determine whether the evidence has expired according to
+    /// `params`.
+    pub fn verify(
+        &self,
+        validator_set: &validator::Set,
+        chain_id: &chain::Id,
+        params: &Params,
+        latest_height: Height,
+        latest_time: Time,
+    ) -> Result<(), Error> {
+        verify_age(self, params, latest_height, latest_time)?;
+        match self {
+            Evidence::DuplicateVote(ev) => ev.verify(validator_set, chain_id),
+            Evidence::LightClientAttack(ev) => ev.verify(validator_set, chain_id),
         }
     }
 }
 
+fn verify_age(
+    evidence: &Evidence,
+    params: &Params,
+    latest_height: Height,
+    latest_time: Time,
+) -> Result<(), Error> {
+    if evidence.height() > latest_height {
+        return Err(Kind::InvalidEvidence
+            .context(format!(
+                "evidence height {} is greater than latest height {}",
+                evidence.height(),
+                latest_height
+            ))
+            .into());
+    }
+    if params.is_expired(evidence, latest_height, latest_time) {
+        return Err(Kind::InvalidEvidence
+            .context(format!(
+                "evidence from height {} ({}) is too old",
+                evidence.height(),
+                evidence.time()
+            ))
+            .into());
+    }
+    Ok(())
+}
+
 /// Duplicate vote evidence
 #[derive(Clone, Debug, PartialEq)]
 pub struct DuplicateVoteEvidence {
@@ -69,6 +164,8 @@ pub struct DuplicateVoteEvidence {
     timestamp: Time,
 }
 
+impl Protobuf<RawDuplicateVoteEvidence> for DuplicateVoteEvidence {}
+
 impl TryFrom<RawDuplicateVoteEvidence> for DuplicateVoteEvidence {
     type Error = Error;
 
@@ -89,13 +186,15 @@ impl From<DuplicateVoteEvidence> for RawDuplicateVoteEvidence {
             vote_a: Some(value.vote_a.into()),
             vote_b: Some(value.vote_b.into()),
             total_voting_power: value.total_voting_power.into(),
-            validator_power: value.total_voting_power.into(),
+            validator_power: value.validator_power.into(),
             timestamp: Some(value.timestamp.into()),
         }
     }
 }
 
 impl DuplicateVoteEvidence {
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     /// constructor
     pub fn new(vote_a: Vote, vote_b: Vote) -> Result<Self, Error> {
         if vote_a.height != vote_b.height {
@@ -110,26 +209,305 @@ impl DuplicateVoteEvidence {
             timestamp: Time::now(),
         })
     }
+
+    /// Evidence of the given conflicting votes by a validator of the given
+    /// validator set, recording the voting powers at the time of the votes.
+    ///
+    /// As in Tendermint, the votes are ordered by block ID, so that the same
+    /// pair of votes always yields the same evidence.
+    pub fn from_conflicting_votes(
+        vote_a: Vote,
+        vote_b: Vote,
+        validator_set: &validator::Set,
+        timestamp: Time,
+    ) -> Result<Self, Error> {
+        if vote_a.validator_address != vote_b.validator_address {
+            return Err(Kind::InvalidEvidence
+                .context("votes are from different validators")
+                .into());
+        }
+        let validator = validator_set
+            .validator(vote_a.validator_address)
+            .ok_or_else(|| {
+                Kind::InvalidEvidence.context(format!(
+                    "validator {} not found in validator set",
+                    vote_a.validator_address
+                ))
+            })?;
+        let (vote_a, vote_b) = if vote_a.block_id <= vote_b.block_id {
+            (vote_a, vote_b)
+        } else {
+            (vote_b, vote_a)
+        };
+        Ok(Self {
+            vote_a,
+            vote_b,
+            total_voting_power: validator_set.total_voting_power(),
+            validator_power: validator.voting_power,
+            timestamp,
+        })
+    }
+
     /// Get votes
     pub fn votes(&self) -> (&Vote, &Vote) {
         (&self.vote_a, &self.vote_b)
     }
+
+    /// Verify that both votes were validly signed by the same validator of
+    /// the given validator set, for the same height, round and vote type but
+    /// for different blocks.
+    pub fn verify(
+        &self,
+        validator_set: &validator::Set,
+        chain_id: &chain::Id,
+    ) -> Result<(), Error> {
+        let (a, b) = (&self.vote_a, &self.vote_b);
+        if a.height != b.height || a.round != b.round || a.vote_type != b.vote_type {
+            return Err(Kind::InvalidEvidence
+                .context("votes are for different heights, rounds or types")
+                .into());
+        }
+        if a.validator_address != b.validator_address {
+            return Err(Kind::InvalidEvidence
+                .context("votes are from different validators")
+                .into());
+        }
+        if a.block_id == b.block_id {
+            return Err(Kind::InvalidEvidence
+                .context("votes are for the same block")
+                .into());
+        }
+
+        let validator = validator_set
+            .validator(a.validator_address)
+            .ok_or_else(|| {
+                Kind::InvalidEvidence.context(format!(
+                    "validator {} not found in validator set",
+                    a.validator_address
+                ))
+            })?;
+        if self.validator_power != validator.voting_power {
+            return Err(Kind::InvalidEvidence
+                .context("validator power does not match validator set")
+                .into());
+        }
+        if self.total_voting_power != validator_set.total_voting_power() {
+            return Err(Kind::InvalidEvidence
+                .context("total voting power does not match validator set")
+                .into());
+        }
+
+        for vote in &[a, b] {
+            let sign_bytes = vote
+                .to_signable_vec(chain_id.clone())
+                .map_err(|e| Kind::InvalidEvidence.context(e))?;
+            validator
+                .verify_signature(&sign_bytes, &vote.signature)
+                .map_err(|e| Kind::SignatureInvalid.context(e))?;
+        }
+        Ok(())
+    }
 }
 
-/// Conflicting headers evidence.
-// Todo: This struct doesn't seem to have a protobuf definition.
-#[derive(Clone, Debug, PartialEq)]
-pub struct ConflictingHeadersEvidence {
-    //#[serde(rename = "H1")]
-    h1: SignedHeader,
-    //#[serde(rename = "H2")]
-    h2: SignedHeader,
+/// Evidence that a set of validators attempted to mislead a light client,
+/// i.e. a light block that conflicts with the block at the same height on
+/// the main chain.
+///
+/// <https://github.com/tendermint/spec/blob/master/spec/core/data_structures.md#lightclientattackevidence>
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "RawLightClientAttackEvidence",
+    into = "RawLightClientAttackEvidence"
+)]
+pub struct LightClientAttackEvidence {
+    /// The light block that conflicts with the main chain
+    pub conflicting_block: ConflictingBlock,
+
+    /// The last height at which the primary and witness agreed
+    pub common_height: Height,
+
+    /// The validators that signed the conflicting block
+    pub byzantine_validators: Vec<validator::Info>,
+
+    /// Total voting power of the validator set at the common height
+    pub total_voting_power: Power,
+
+    /// Time of the block at the common height
+    pub timestamp: Time,
+}
+
+impl Protobuf<RawLightClientAttackEvidence> for LightClientAttackEvidence {}
+
+impl TryFrom<RawLightClientAttackEvidence> for LightClientAttackEvidence {
+    type Error = Error;
+
+    fn try_from(value: RawLightClientAttackEvidence) -> Result<Self, Self::Error> {
+        Ok(Self {
+            conflicting_block: value
+                .conflicting_block
+                .ok_or(Kind::MissingEvidence)?
+                .try_into()?,
+            common_height: value.common_height.try_into()?,
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<_>, _>>()?,
+            total_voting_power: value.total_voting_power.try_into()?,
+            timestamp: value.timestamp.ok_or(Kind::MissingTimestamp)?.try_into()?,
+        })
+    }
+}
+
+impl From<LightClientAttackEvidence> for RawLightClientAttackEvidence {
+    fn from(value: LightClientAttackEvidence) -> Self {
+        RawLightClientAttackEvidence {
+            conflicting_block: Some(value.conflicting_block.into()),
+            common_height: value.common_height.into(),
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            total_voting_power: value.total_voting_power.into(),
+            timestamp: Some(value.timestamp.into()),
+        }
+    }
+}
+
+impl LightClientAttackEvidence {
+    /// Compute the hash of this evidence, as done by Tendermint: the SHA-256
+    /// of the conflicting header's hash followed by the varint-encoded
+    /// common height.
+    pub fn hash(&self) -> Hash {
+        let mut height = Vec::new();
+        let common_height: i64 = self.common_height.into();
+        // Go's binary.PutVarint uses zig-zag encoding.
+        prost::encoding::encode_varint(
+            ((common_height << 1) ^ (common_height >> 63)) as u64,
+            &mut height,
+        );
+
+        let mut bz = vec![0u8; SHA256_HASH_SIZE + height.len()];
+        // Tendermint only copies the first 31 bytes of the header hash.
+        let header_hash = self.conflicting_block.signed_header.header.hash();
+        let header_hash = header_hash.as_bytes();
+        let len = header_hash.len().min(SHA256_HASH_SIZE - 1);
+        bz[..len].copy_from_slice(&header_hash[..len]);
+        bz[SHA256_HASH_SIZE..].copy_from_slice(&height);
+
+        sha256(&bz)
+    }
+
+    /// Check that this evidence is consistent with the given validator set
+    /// (the one at the common height): the conflicting block must belong to
+    /// the given chain and be at or above the common height, its validator
+    /// set must match its header, the byzantine validators must be known and
+    /// the total voting power must match that of the validator set.
+    ///
+    /// As done by Tendermint's `VerifyLightClientAttack`, the conflicting
+    /// block must also have been signed by more than two thirds of its own
+    /// validator set and, if it is above the common height (i.e. a lunatic
+    /// attack), by validators holding at least a third of the voting power of
+    /// the given validator set.
+    pub fn verify(
+        &self,
+        validator_set: &validator::Set,
+        chain_id: &chain::Id,
+    ) -> Result<(), Error> {
+        let header = &self.conflicting_block.signed_header.header;
+        if &header.chain_id != chain_id {
+            return Err(Kind::InvalidEvidence
+                .context(format!(
+                    "conflicting block is from chain {}, expected {}",
+                    header.chain_id, chain_id
+                ))
+                .into());
+        }
+        if header.height < self.common_height {
+            return Err(Kind::InvalidEvidence
+                .context("conflicting block is below the common height")
+                .into());
+        }
+        if header.validators_hash != self.conflicting_block.validator_set.hash() {
+            return Err(Kind::InvalidEvidence
+                .context("conflicting block's validator set does not match its header")
+                .into());
+        }
+        if self.total_voting_power != validator_set.total_voting_power() {
+            return Err(Kind::InvalidEvidence
+                .context("total voting power does not match validator set")
+                .into());
+        }
+        for byzantine in &self.byzantine_validators {
+            let known = validator_set.validator(byzantine.address).is_some()
+                || self
+                    .conflicting_block
+                    .validator_set
+                    .validator(byzantine.address)
+                    .is_some();
+            if !known {
+                return Err(Kind::InvalidEvidence
+                    .context(format!("unknown byzantine validator {}", byzantine.address))
+                    .into());
+            }
+        }
+
+        let signed_header = &self.conflicting_block.signed_header;
+        signed_header
+            .validate(chain_id)
+            .map_err(|e| Kind::InvalidEvidence.context(e))?;
+        let commit = &signed_header.commit;
+        if header.height > self.common_height {
+            validator_set
+                .verify_commit_light_trusting(chain_id, commit, TrustThresholdFraction::default())
+                .map_err(|e| Kind::InvalidEvidence.context(e))?;
+        }
+        self.conflicting_block
+            .validator_set
+            .verify_commit(chain_id, &commit.block_id, header.height, commit)
+            .map_err(|e| Kind::InvalidEvidence.context(e))?;
+        Ok(())
+    }
+}
+
+/// A light block (signed header and validator set) included in a
+/// [`LightClientAttackEvidence`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawLightBlock", into = "RawLightBlock")]
+pub struct ConflictingBlock {
+    /// Signed header of the conflicting block
+    pub signed_header: SignedHeader,
+
+    /// Validator set of the conflicting block
+    pub validator_set: validator::Set,
+}
+
+impl Protobuf<RawLightBlock> for ConflictingBlock {}
+
+impl TryFrom<RawLightBlock> for ConflictingBlock {
+    type Error = Error;
+
+    fn try_from(value: RawLightBlock) -> Result<Self, Self::Error> {
+        Ok(Self {
+            signed_header: value
+                .signed_header
+                .ok_or(Kind::MissingEvidence)?
+                .try_into()?,
+            validator_set: value
+                .validator_set
+                .ok_or(Kind::MissingEvidence)?
+                .try_into()?,
+        })
+    }
 }
 
-impl ConflictingHeadersEvidence {
-    /// Create a new evidence of conflicting headers
-    pub fn new(h1: SignedHeader, h2: SignedHeader) -> Self {
-        Self { h1, h2 }
+impl From<ConflictingBlock> for RawLightBlock {
+    fn from(value: ConflictingBlock) -> Self {
+        RawLightBlock {
+            signed_header: Some(value.signed_header.into()),
+            validator_set: Some(value.validator_set.into()),
+        }
     }
 }
 
@@ -170,6 +548,14 @@ impl From<Data> for RawEvidenceList {
 }
 
 impl Data {
+    /// Compute the hash of the evidence data, i.e. the Merkle root of the
+    /// encoded evidence, as found in a block's `Header.evidence_hash`.
+    pub fn hash(&self) -> Hash {
+        Hash::Sha256(merkle::simple_hash_from_byte_vectors(
+            self.iter().map(Evidence::bytes).collect(),
+        ))
+    }
+
     /// Create a new evidence data collection
     pub fn new<I>(into_evidence: I) -> Data
     where
@@ -197,6 +583,13 @@ impl AsRef<[Evidence]> for Data {
     }
 }
 
+fn sha256(bytes: &[u8]) -> Hash {
+    let digest = Sha256::digest(bytes);
+    let mut hash_bytes = [0u8; SHA256_HASH_SIZE];
+    hash_bytes.copy_from_slice(&digest);
+    Hash::Sha256(hash_bytes)
+}
+
 /// Evidence collection parameters
 #[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
 // Todo: This struct is ready to be converted through tendermint_proto::types::EvidenceParams.
@@ -214,6 +607,54 @@ pub struct Params {
     pub max_bytes: i64,
 }
 
+impl Params {
+    /// Check that these parameters are valid, as done by Tendermint's
+    /// `ValidateConsensusParams` (save for the bound of `max_bytes` by the
+    /// maximum size of a block, which is checked by
+    /// [`consensus::Params::validate`](crate::consensus::Params::validate)).
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.max_age_num_blocks == 0 {
+            return Err(Kind::InvalidConsensusParams
+                .context("evidence.max_age_num_blocks must be greater than 0")
+                .into());
+        }
+        if self.max_age_duration.0.as_nanos() == 0 {
+            return Err(Kind::InvalidConsensusParams
+                .context("evidence.max_age_duration must be greater than 0")
+                .into());
+        }
+        if self.max_bytes < 0 {
+            return Err(format_err!(
+                Kind::InvalidConsensusParams,
+                "evidence.max_bytes must not be negative, got {}",
+                self.max_bytes
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Whether the given evidence has expired, given the height and time of
+    /// the latest committed block.
+    ///
+    /// Evidence expires once it is older than both `max_age_num_blocks` and
+    /// `max_age_duration`. Evidence from the future is never expired.
+    pub fn is_expired(
+        &self,
+        evidence: &Evidence,
+        current_height: Height,
+        current_time: Time,
+    ) -> bool {
+        let age_num_blocks = current_height
+            .value()
+            .saturating_sub(evidence.height().value());
+        let age_duration = current_time
+            .duration_since(evidence.time())
+            .unwrap_or_default();
+        age_num_blocks > self.max_age_num_blocks && age_duration > self.max_age_duration.0
+    }
+}
+
 impl Protobuf<RawEvidenceParams> for Params {}
 
 impl TryFrom<RawEvidenceParams> for Params {
@@ -245,16 +686,16 @@ impl From<Params> for RawEvidenceParams {
     }
 }
 
-/// Duration is a wrapper around std::time::Duration
+/// Duration is a wrapper around core::time::Duration
 /// essentially, to keep the usages look cleaner
 /// i.e. you can avoid using serde annotations everywhere
-/// Todo: harmonize google::protobuf::Duration, std::time::Duration and this. Too many structs.
+/// Todo: harmonize google::protobuf::Duration, core::time::Duration and this. Too many structs.
 /// <https://github.com/informalsystems/tendermint-rs/issues/741>
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
-pub struct Duration(#[serde(with = "serializers::time_duration")] pub std::time::Duration);
+pub struct Duration(#[serde(with = "serializers::time_duration")] pub core::time::Duration);
 
-impl From<Duration> for std::time::Duration {
-    fn from(d: Duration) -> std::time::Duration {
+impl From<Duration> for core::time::Duration {
+    fn from(d: Duration) -> core::time::Duration {
         d.0
     }
 }
@@ -265,7 +706,7 @@ impl TryFrom<RawDuration> for Duration {
     type Error = Error;
 
     fn try_from(value: RawDuration) -> Result<Self, Self::Error> {
-        Ok(Self(std::time::Duration::new(
+        Ok(Self(core::time::Duration::new(
             value
                 .seconds
                 .try_into()
@@ -287,3 +728,256 @@ impl From<Duration> for RawDuration {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{Commit, CommitSig, Header};
+    use crate::hash::Algorithm;
+    use crate::signature::Signer;
+    use crate::test::signing_key;
+    use crate::{account, private_key, PublicKey};
+    use core::time::Duration as StdDuration;
+
+    fn light_client_attack_evidence() -> LightClientAttackEvidence {
+        let header: Header = serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/header_with_known_hash.json"
+        ))
+        .unwrap();
+        let commit = Commit {
+            height: header.height,
+            ..Commit::default()
+        };
+        LightClientAttackEvidence {
+            conflicting_block: ConflictingBlock {
+                signed_header: SignedHeader::new(header, commit).unwrap(),
+                validator_set: validator::Set::without_proposer(vec![]),
+            },
+            common_height: Height::from(1607_u32),
+            byzantine_validators: vec![],
+            total_voting_power: Power::from(10_u32),
+            timestamp: Time::parse_from_rfc3339("2020-09-14T16:33:50Z").unwrap(),
+        }
+    }
+
+    #[test]
+    fn light_client_attack_evidence_roundtrip() {
+        let evidence = Evidence::LightClientAttack(Box::new(light_client_attack_evidence()));
+
+        let raw = RawEvidence::from(evidence.clone());
+        assert!(raw.sum.is_some());
+        assert_eq!(evidence, Evidence::try_from(raw).unwrap());
+
+        let json = serde_json::to_string(&evidence).unwrap();
+        assert_eq!(evidence, serde_json::from_str::<Evidence>(&json).unwrap());
+    }
+
+    #[test]
+    fn light_client_attack_evidence_hash() {
+        let expected = Hash::from_hex_upper(
+            Algorithm::Sha256,
+            "955F142DD9B7E039AF548776F5CE585A38BE45ACD34CD12BEDC1834302187789",
+        )
+        .unwrap();
+        assert_eq!(expected, light_client_attack_evidence().hash());
+    }
+
+    fn params() -> Params {
+        Params {
+            max_age_num_blocks: 100,
+            max_age_duration: Duration(StdDuration::from_secs(3600)),
+            max_bytes: 1_048_576,
+        }
+    }
+
+    fn signed_vote(key: &private_key::Ed25519, chain_id: &chain::Id, block_hash: u8) -> Vote {
+        let mut vote = Vote {
+            vote_type: crate::vote::Type::Precommit,
+            height: Height::from(10_u32),
+            block_id: Some(crate::block::Id {
+                hash: Hash::Sha256([block_hash; SHA256_HASH_SIZE]),
+                part_set_header: Default::default(),
+            }),
+            validator_address: account::Id::from(key.public),
+            ..Vote::default()
+        };
+        let sign_bytes = vote.to_signable_vec(chain_id.clone()).unwrap();
+        vote.signature = key.sign(&sign_bytes).into();
+        vote
+    }
+
+    #[test]
+    fn duplicate_vote_evidence_verification() {
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+        let key = signing_key(7);
+        let validator = validator::Info::new(PublicKey::from(key.public), Power::from(10_u32));
+        let validator_set = validator::Set::without_proposer(vec![validator]);
+        let latest_time = Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap();
+
+        let evidence = DuplicateVoteEvidence {
+            vote_a: signed_vote(&key, &chain_id, 1),
+            vote_b: signed_vote(&key, &chain_id, 2),
+            total_voting_power: Power::from(10_u32),
+            validator_power: Power::from(10_u32),
+            timestamp: latest_time,
+        };
+        let verify = |ev: &DuplicateVoteEvidence, latest_height: u32| {
+            Evidence::DuplicateVote(ev.clone()).verify(
+                &validator_set,
+                &chain_id,
+                &params(),
+                Height::from(latest_height),
+                latest_time,
+            )
+        };
+        verify(&evidence, 20).unwrap();
+
+        // Not yet expired, as it is within the maximum duration.
+        verify(&evidence, 1000).unwrap();
+        let mut old = evidence.clone();
+        old.timestamp = latest_time - StdDuration::from_secs(7200);
+        assert!(verify(&old, 1000).is_err());
+
+        // Evidence from the future.
+        assert!(verify(&evidence, 5).is_err());
+
+        let mut same_block = evidence.clone();
+        same_block.vote_b = same_block.vote_a.clone();
+        assert!(verify(&same_block, 20).is_err());
+
+        let mut bad_signature = evidence.clone();
+        bad_signature.vote_b.round = crate::block::Round::from(1_u8);
+        bad_signature.vote_a.round = crate::block::Round::from(1_u8);
+        assert!(verify(&bad_signature, 20).is_err());
+
+        let mut wrong_power = evidence;
+        wrong_power.validator_power = Power::from(5_u32);
+        assert!(verify(&wrong_power, 20).is_err());
+    }
+
+    // Evidence of a lunatic attack at height 1610, by the validators with the
+    // given keys, which also form the validator set at the common height.
+    fn signed_light_client_attack_evidence(
+        chain_id: &chain::Id,
+        keys: &[private_key::Ed25519],
+    ) -> (LightClientAttackEvidence, validator::Set) {
+        let validator_set = validator::Set::without_proposer(
+            keys.iter()
+                .map(|key| validator::Info::new(key.public.into(), Power::from(10_u32)))
+                .collect(),
+        );
+        let mut evidence = light_client_attack_evidence();
+        let header = &mut evidence.conflicting_block.signed_header.header;
+        header.chain_id = chain_id.clone();
+        header.height = Height::from(1610_u32);
+        header.validators_hash = validator_set.hash();
+        let commit = Commit::builder()
+            .height(header.height)
+            .block_id(crate::block::Id {
+                hash: header.hash(),
+                part_set_header: Default::default(),
+            })
+            .timestamp(header.time)
+            .sign(chain_id.clone(), &validator_set, keys)
+            .unwrap();
+        evidence.conflicting_block = ConflictingBlock {
+            signed_header: SignedHeader::new(header.clone(), commit).unwrap(),
+            validator_set: validator_set.clone(),
+        };
+        evidence.total_voting_power = validator_set.total_voting_power();
+        (evidence, validator_set)
+    }
+
+    #[test]
+    fn light_client_attack_evidence_verification() {
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+        let keys: Vec<_> = (1..=3).map(signing_key).collect();
+        let (evidence, validator_set) = signed_light_client_attack_evidence(&chain_id, &keys);
+        evidence.verify(&validator_set, &chain_id).unwrap();
+
+        let mut tampered = evidence.clone();
+        if let CommitSig::BlockIdFlagCommit { signature, .. } =
+            &mut tampered.conflicting_block.signed_header.commit.signatures[0]
+        {
+            let mut bytes = signature.as_bytes().to_vec();
+            bytes[0] ^= 1;
+            *signature = crate::Signature::try_from(bytes).unwrap();
+        }
+        assert_eq!(
+            tampered
+                .verify(&validator_set, &chain_id)
+                .unwrap_err()
+                .kind(),
+            &Kind::InvalidEvidence
+        );
+
+        // The validators at the common height didn't take part in a lunatic
+        // attack signed by other validators.
+        let other_keys: Vec<_> = (4..=6).map(signing_key).collect();
+        let (lunatic, _) = signed_light_client_attack_evidence(&chain_id, &other_keys);
+        assert!(lunatic.verify(&validator_set, &chain_id).is_err());
+
+        // Only a third of the conflicting validator set signed.
+        let (mut partial, _) = signed_light_client_attack_evidence(&chain_id, &keys);
+        let commit = &mut partial.conflicting_block.signed_header.commit;
+        commit.signatures[1] = CommitSig::BlockIdFlagAbsent;
+        commit.signatures[2] = CommitSig::BlockIdFlagAbsent;
+        assert!(partial.verify(&validator_set, &chain_id).is_err());
+    }
+
+    #[test]
+    fn evidence_data_hash() {
+        // The hash of empty evidence data is the hash of an empty Merkle tree.
+        let expected = Hash::from_hex_upper(
+            Algorithm::Sha256,
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855",
+        )
+        .unwrap();
+        assert_eq!(expected, Data::default().hash());
+        assert_eq!(expected, Data::new(vec![]).hash());
+
+        let evidence = Evidence::LightClientAttack(Box::new(light_client_attack_evidence()));
+        let data = Data::new(vec![evidence.clone()]);
+        let leaf = merkle::leaf_hash(&evidence.bytes());
+        assert_eq!(Hash::Sha256(leaf), data.hash());
+        assert_ne!(evidence.hash(), data.hash());
+    }
+
+    #[test]
+    fn params_validation() {
+        params().validate().unwrap();
+
+        let mut invalid = params();
+        invalid.max_age_num_blocks = 0;
+        assert_eq!(
+            invalid.validate().unwrap_err().kind(),
+            &Kind::InvalidConsensusParams
+        );
+
+        let mut invalid = params();
+        invalid.max_age_duration = Duration(StdDuration::from_secs(0));
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = params();
+        invalid.max_bytes = -1;
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn evidence_expiry() {
+        let evidence = Evidence::LightClientAttack(Box::new(light_client_attack_evidence()));
+        let params = params();
+        let height = |h: u32| Height::from(h);
+        let time = evidence.time();
+        let hours = |h: u64| StdDuration::from_secs(h * 3600);
+
+        // Expires only once it is too old in both blocks and time.
+        assert!(!params.is_expired(&evidence, height(1607), time));
+        assert!(!params.is_expired(&evidence, height(1800), time + hours(1)));
+        assert!(!params.is_expired(&evidence, height(1707), time + hours(2)));
+        assert!(params.is_expired(&evidence, height(1708), time + hours(2)));
+
+        // Evidence from the future isn't expired.
+        assert!(!params.is_expired(&evidence, height(1), time + hours(2)));
+    }
+}
@@ -1,13 +1,24 @@
 //! Genesis data
 
-use crate::{chain, consensus, validator, Time};
+use crate::prelude::*;
+use crate::{account, chain, consensus, validator, Hash, Time};
+use crate::{Error, Kind};
 use chrono::DateTime;
-use serde::de::Error;
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
+use serde::de::DeserializeOwned;
+use serde::de::Error as _;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::convert::TryFrom;
+use sha2::{Digest, Sha256};
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
 use tendermint_proto::google::protobuf::Timestamp;
 
 /// Genesis data
+///
+/// The application state is kept as a [`serde_json::Value`] by default, but
+/// can be deserialized into any type, including types borrowing from the
+/// JSON document (see [`Genesis::parse_json`]).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Genesis<AppState = serde_json::Value> {
     /// Time of genesis
@@ -32,6 +43,100 @@ pub struct Genesis<AppState = serde_json::Value> {
     pub app_state: AppState,
 }
 
+impl<'a, AppState> Genesis<AppState>
+where
+    AppState: Deserialize<'a> + Default,
+{
+    /// Parse a genesis document, e.g. the contents of `genesis.json`.
+    ///
+    /// The application state may borrow from the given JSON string.
+    pub fn parse_json(json_string: &'a str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json_string).map_err(|e| Kind::Parse.context(e))?)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<AppState> Genesis<AppState>
+where
+    AppState: DeserializeOwned + Default,
+{
+    /// Load `genesis.json` from a file
+    pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string = fs::read_to_string(path).map_err(|e| {
+            format_err!(
+                Kind::Parse,
+                "couldn't open {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
+
+        Ok(serde_json::from_str(&json_string).map_err(|e| Kind::Parse.context(e))?)
+    }
+}
+
+impl<AppState> Genesis<AppState> {
+    /// Check that this genesis document is valid, as done by Tendermint's
+    /// `ValidateAndComplete`: its consensus parameters must be valid, and
+    /// its validators must have some voting power, an address matching their
+    /// public key, and a key type allowed by the consensus parameters.
+    ///
+    /// Applications with an unbonding period should also check it against
+    /// the evidence parameters with
+    /// [`consensus::Params::validate_unbonding_period`].
+    pub fn validate(&self) -> Result<(), Error> {
+        self.consensus_params.validate()?;
+
+        for validator in &self.validators {
+            if validator.power() == 0 {
+                return Err(format_err!(
+                    Kind::InvalidGenesis,
+                    "validator {} has no voting power",
+                    validator.address
+                )
+                .into());
+            }
+            if validator.address != account::Id::from(validator.pub_key) {
+                return Err(format_err!(
+                    Kind::InvalidGenesis,
+                    "validator address {} does not match its public key",
+                    validator.address
+                )
+                .into());
+            }
+            let algorithm = validator.pub_key.algorithm();
+            if !self
+                .consensus_params
+                .validator
+                .pub_key_types
+                .contains(&algorithm)
+            {
+                return Err(format_err!(
+                    Kind::InvalidGenesis,
+                    "validator {} has a {} key, which is not allowed by the consensus parameters",
+                    validator.address,
+                    algorithm
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Hash of a genesis document, as stored by Tendermint nodes to detect
+    /// a change of genesis: the SHA-256 of the given contents of
+    /// `genesis.json`.
+    pub fn hash_json(json: &[u8]) -> Hash {
+        let mut hash_bytes = [0u8; 32];
+        hash_bytes.copy_from_slice(&Sha256::digest(json));
+        Hash::Sha256(hash_bytes)
+    }
+}
+
 /// Deserialize string into Time through Timestamp
 pub fn deserialize_time<'de, D>(deserializer: D) -> Result<Time, D::Error>
 where
@@ -46,3 +151,88 @@ where
     })
     .map_err(|e| D::Error::custom(format!("{}", e)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::signing_key;
+    use crate::vote;
+
+    const GENESIS: &str = r#"{
+        "genesis_time": "2021-01-01T00:00:00Z",
+        "chain_id": "test-chain",
+        "consensus_params": {
+            "block": { "max_bytes": "22020096", "max_gas": "-1", "time_iota_ms": "1000" },
+            "evidence": {
+                "max_age_num_blocks": "100000",
+                "max_age_duration": "172800000000000",
+                "max_bytes": "1048576"
+            },
+            "validator": { "pub_key_types": ["ed25519"] }
+        },
+        "app_hash": "",
+        "app_state": { "name": "test" }
+    }"#;
+
+    #[derive(Debug, Default, Deserialize)]
+    struct AppState<'a> {
+        name: &'a str,
+    }
+
+    #[test]
+    fn borrowed_app_state() {
+        let genesis = Genesis::<AppState<'_>>::parse_json(GENESIS).unwrap();
+        assert_eq!("test", genesis.app_state.name);
+    }
+
+    #[test]
+    fn validation() {
+        let mut genesis = Genesis::<serde_json::Value>::parse_json(GENESIS).unwrap();
+        genesis.validate().unwrap();
+
+        let pub_key = signing_key(1).public.into();
+        genesis
+            .validators
+            .push(validator::Info::new(pub_key, vote::Power::from(10_u32)));
+        genesis.validate().unwrap();
+
+        let mut invalid = genesis.clone();
+        invalid.validators[0].voting_power = vote::Power::from(0_u32);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis.clone();
+        invalid.validators[0].address = account::Id::new([0; account::LENGTH]);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis.clone();
+        invalid.consensus_params.validator.pub_key_types =
+            vec![crate::public_key::Algorithm::Secp256k1];
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis.clone();
+        invalid.consensus_params.evidence.max_bytes = 22020097;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis.clone();
+        invalid.consensus_params.block.max_bytes = consensus::params::MAX_BLOCK_SIZE_BYTES + 1;
+        assert!(invalid.validate().is_err());
+
+        let unbonding_period = core::time::Duration::from_secs(21 * 24 * 3600);
+        genesis
+            .consensus_params
+            .validate_unbonding_period(unbonding_period)
+            .unwrap();
+        assert!(genesis
+            .consensus_params
+            .validate_unbonding_period(unbonding_period / 21)
+            .is_err());
+    }
+
+    #[test]
+    fn genesis_hash() {
+        assert_eq!(
+            Genesis::<()>::hash_json(b"").to_string(),
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+        );
+    }
+}
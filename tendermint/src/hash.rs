@@ -1,13 +1,14 @@
 //! Hash functions and their outputs
 
 use crate::error::{Error, Kind};
-use serde::de::Error as _;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::convert::TryFrom;
-use std::{
+use crate::prelude::*;
+use core::convert::TryFrom;
+use core::{
     fmt::{self, Debug, Display},
     str::FromStr,
 };
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use subtle_encoding::{Encoding, Hex};
 use tendermint_proto::Protobuf;
 
@@ -74,6 +75,11 @@ impl Hash {
         }
     }
 
+    /// Create a SHA-256 `Hash` from the given digest
+    pub const fn sha256(bytes: [u8; SHA256_HASH_SIZE]) -> Hash {
+        Hash::Sha256(bytes)
+    }
+
     /// Decode a `Hash` from upper-case hexadecimal
     pub fn from_hex_upper(alg: Algorithm, s: &str) -> Result<Hash, Error> {
         if s.is_empty() {
@@ -82,12 +88,34 @@ impl Hash {
         match alg {
             Algorithm::Sha256 => {
                 let mut h = [0u8; SHA256_HASH_SIZE];
-                Hex::upper_case().decode_to_slice(s.as_bytes(), &mut h)?;
+                Hex::upper_case()
+                    .decode_to_slice(s.as_bytes(), &mut h)
+                    .map_err(|e| Kind::Parse.context(e))?;
                 Ok(Hash::Sha256(h))
             }
         }
     }
 
+    /// Decode a `Hash` from hexadecimal, in either upper or lower case
+    ///
+    /// Unlike [`Hash::from_hex_upper`], any non-empty input must be the full
+    /// length of the algorithm's digest.
+    pub fn from_hex(alg: Algorithm, s: &str) -> Result<Hash, Error> {
+        match alg {
+            Algorithm::Sha256 => {
+                if !s.is_empty() && s.len() != SHA256_HASH_SIZE * 2 {
+                    fail!(
+                        Kind::Parse,
+                        "hash invalid length: expected {} hex characters, got {}",
+                        SHA256_HASH_SIZE * 2,
+                        s.len()
+                    );
+                }
+            }
+        }
+        Self::from_hex_upper(alg, &s.to_ascii_uppercase())
+    }
+
     /// Return the digest algorithm used to produce this hash
     pub fn algorithm(self) -> Algorithm {
         match self {
@@ -140,7 +168,7 @@ impl FromStr for Hash {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Error> {
-        Self::from_hex_upper(Algorithm::Sha256, s)
+        Self::from_hex(Algorithm::Sha256, s)
     }
 }
 
@@ -217,7 +245,9 @@ impl AppHash {
             return Err(Kind::InvalidAppHashLength.into());
         }
         let mut h = vec![0; s.len() / 2];
-        Hex::upper_case().decode_to_slice(s.as_bytes(), &mut h)?;
+        Hex::upper_case()
+            .decode_to_slice(s.as_bytes(), &mut h)
+            .map_err(|e| Kind::Parse.context(e))?;
         Ok(AppHash(h))
     }
 }
@@ -257,3 +287,34 @@ impl FromStr for AppHash {
         Self::from_hex_upper(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_HASH: &str = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+
+    #[test]
+    fn parses_hex_in_any_case() {
+        let upper = Hash::from_str(EXAMPLE_HASH).unwrap();
+        assert_eq!(Hash::from_str(&EXAMPLE_HASH.to_lowercase()).unwrap(), upper);
+        assert_eq!(upper.to_string(), EXAMPLE_HASH);
+        assert!(Hash::from_hex_upper(Algorithm::Sha256, &EXAMPLE_HASH.to_lowercase()).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_hex() {
+        let err = Hash::from_str(&EXAMPLE_HASH[..32]).unwrap_err();
+        assert_eq!(err.kind(), &Kind::Parse);
+        assert!(Hash::from_str(&format!("{}00", EXAMPLE_HASH)).is_err());
+        assert_eq!(Hash::from_str("").unwrap(), Hash::None);
+
+        // Short upper-case hex is still zero-padded
+        let mut padded = [0; SHA256_HASH_SIZE];
+        padded[..16].copy_from_slice(&Hash::from_str(EXAMPLE_HASH).unwrap().as_bytes()[..16]);
+        assert_eq!(
+            Hash::from_hex_upper(Algorithm::Sha256, &EXAMPLE_HASH[..32]).unwrap(),
+            Hash::Sha256(padded)
+        );
+    }
+}
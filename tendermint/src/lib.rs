@@ -3,7 +3,24 @@
 //! This crate provides core types for representing information about Tendermint
 //! blockchain networks, including chain information types, secret connections,
 //! and remote procedure calls (JSON-RPC).
+//!
+//! ## `no_std` support
+//!
+//! The core domain types (hashes, headers, validators, votes and commit
+//! verification) only require `alloc`, so that they can be used on targets
+//! without the standard library, such as `wasm32` and embedded signers.
+//! Everything which needs the standard library (config and key files,
+//! private validators, network addresses, wall clock time, error backtraces,
+//! ...) is gated behind the `std` feature, which is enabled by default.
+//!
+//! ## Protobuf versions
+//!
+//! The core types (public keys, block IDs, headers, commits and validators)
+//! convert to and from the Tendermint v0.34 protobuf types, as well as to and
+//! from the CometBFT v0.37 and v0.38 ones when the `v0_37` and `v0_38`
+//! features are enabled. All the other types only support Tendermint v0.34.
 
+#![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![deny(
     warnings,
@@ -19,14 +36,27 @@
     html_logo_url = "https://raw.githubusercontent.com/informalsystems/tendermint-rs/master/img/logo-tendermint-rs_3961x4001.png"
 )]
 
+extern crate alloc;
+
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+mod prelude;
+
+#[macro_use]
+mod proto_macros;
+
 #[macro_use]
 pub mod error;
 
 pub mod abci;
 pub mod account;
+pub mod bech32;
 pub mod block;
 pub mod chain;
 pub mod channel;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub mod config;
 pub mod consensus;
 pub mod evidence;
@@ -34,13 +64,20 @@ pub mod genesis;
 pub mod hash;
 pub mod merkle;
 mod moniker;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub mod net;
 pub mod node;
 pub mod private_key;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod privval;
 pub mod proposal;
 pub mod public_key;
+pub mod secret;
 pub mod serializers;
 pub mod signature;
+pub mod store;
 pub mod time;
 mod timeout;
 pub mod trust_threshold;
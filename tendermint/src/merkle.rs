@@ -1,6 +1,14 @@
 //! Merkle tree used in Tendermint networks
 
 pub mod proof;
+pub mod simple_proof;
+
+#[cfg(feature = "ics23")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ics23")))]
+pub mod ics23;
+
+use crate::prelude::*;
+pub use simple_proof::{proofs_from_byte_vectors, SimpleProof};
 
 use sha2::{Digest, Sha256};
 
@@ -17,6 +25,53 @@ pub fn simple_hash_from_byte_vectors(byte_vecs: Vec<Vec<u8>>) -> Hash {
     simple_hash_from_byte_slices_inner(byte_vecs.as_slice())
 }
 
+/// Compute the Merkle root hash implied by an inclusion proof for the leaf
+/// with the given `leaf_hash` at position `index` in a tree with `total`
+/// leaves, given the hashes of its `aunts` (ordered from the bottom of the
+/// tree to the top).
+///
+/// Returns `None` if the proof is malformed, e.g. if it has more than
+/// [`MAX_AUNTS`](simple_proof::MAX_AUNTS) aunts or if `total` doesn't fit in
+/// the `int64` of Tendermint's proofs.
+pub fn compute_hash_from_aunts(
+    index: u64,
+    total: u64,
+    leaf_hash: Hash,
+    aunts: &[Hash],
+) -> Option<Hash> {
+    if index >= total || total > i64::MAX as u64 || aunts.len() > simple_proof::MAX_AUNTS {
+        return None;
+    }
+    match total {
+        0 => None,
+        1 => {
+            if aunts.is_empty() {
+                Some(leaf_hash)
+            } else {
+                None
+            }
+        }
+        _ => {
+            let (last, rest) = aunts.split_last()?;
+            let num_left = split_point(total);
+            if index < num_left {
+                let left = compute_hash_from_aunts(index, num_left, leaf_hash, rest)?;
+                Some(inner_hash(&left, last))
+            } else {
+                let right =
+                    compute_hash_from_aunts(index - num_left, total - num_left, leaf_hash, rest)?;
+                Some(inner_hash(last, &right))
+            }
+        }
+    }
+}
+
+/// Verify that the given `leaf` is included at position `index` in the Merkle
+/// tree with `total` leaves and the given `root` hash.
+pub fn verify_inclusion(root: &Hash, leaf: &[u8], index: u64, total: u64, aunts: &[Hash]) -> bool {
+    compute_hash_from_aunts(index, total, leaf_hash(leaf), aunts).as_ref() == Some(root)
+}
+
 // recurse into subtrees
 fn simple_hash_from_byte_slices_inner(byte_slices: &[Vec<u8>]) -> Hash {
     let length = byte_slices.len();
@@ -24,7 +79,7 @@ fn simple_hash_from_byte_slices_inner(byte_slices: &[Vec<u8>]) -> Hash {
         0 => empty_hash(),
         1 => leaf_hash(byte_slices[0].as_slice()),
         _ => {
-            let k = get_split_point(length);
+            let k = split_point(length as u64) as usize;
             let left = simple_hash_from_byte_slices_inner(&byte_slices[..k]);
             let right = simple_hash_from_byte_slices_inner(&byte_slices[k..]);
             inner_hash(&left, &right)
@@ -32,14 +87,9 @@ fn simple_hash_from_byte_slices_inner(byte_slices: &[Vec<u8>]) -> Hash {
     }
 }
 
-// returns the largest power of 2 less than length
-fn get_split_point(length: usize) -> usize {
-    match length {
-        0 => panic!("tree is empty!"),
-        1 => panic!("tree has only one element!"),
-        2 => 1,
-        _ => length.next_power_of_two() / 2,
-    }
+// returns the largest power of 2 less than total, which must be at least 2
+fn split_point(total: u64) -> u64 {
+    1 << (63 - (total - 1).leading_zeros())
 }
 
 // tmhash({})
@@ -56,8 +106,8 @@ fn empty_hash() -> Hash {
     hash_bytes
 }
 
-// tmhash(0x00 || leaf)
-fn leaf_hash(bytes: &[u8]) -> Hash {
+/// Compute the hash of a leaf of a Merkle tree, i.e. `tmhash(0x00 || leaf)`.
+pub fn leaf_hash(bytes: &[u8]) -> Hash {
     // make a new array starting with 0 and copy in the bytes
     let mut leaf_bytes = Vec::with_capacity(bytes.len() + 1);
     leaf_bytes.push(0x00);
@@ -72,6 +122,19 @@ fn leaf_hash(bytes: &[u8]) -> Hash {
     hash_bytes
 }
 
+/// Encode a key/value pair as a leaf of a simple Merkle map, as Tendermint's
+/// `KVPair`: the key followed by the SHA-256 digest of the value, each
+/// prefixed with its length as an unsigned varint.
+pub fn kv_pair_leaf(key: &[u8], value: &[u8]) -> Vec<u8> {
+    let value_hash = Sha256::digest(value);
+    let mut leaf = Vec::with_capacity(key.len() + value_hash.len() + 2 * 10);
+    prost::encoding::encode_varint(key.len() as u64, &mut leaf);
+    leaf.extend_from_slice(key);
+    prost::encoding::encode_varint(value_hash.len() as u64, &mut leaf);
+    leaf.extend_from_slice(&value_hash);
+    leaf
+}
+
 // tmhash(0x01 || left || right)
 fn inner_hash(left: &[u8], right: &[u8]) -> Hash {
     // make a new array starting with 0x1 and copy in the bytes
@@ -95,17 +158,19 @@ mod tests {
     use subtle_encoding::hex; // TODO: use non-subtle ?
 
     #[test]
-    fn test_get_split_point() {
-        assert_eq!(get_split_point(2), 1);
-        assert_eq!(get_split_point(3), 2);
-        assert_eq!(get_split_point(4), 2);
-        assert_eq!(get_split_point(5), 4);
-        assert_eq!(get_split_point(10), 8);
-        assert_eq!(get_split_point(20), 16);
-        assert_eq!(get_split_point(100), 64);
-        assert_eq!(get_split_point(255), 128);
-        assert_eq!(get_split_point(256), 128);
-        assert_eq!(get_split_point(257), 256);
+    fn test_split_point() {
+        assert_eq!(split_point(2), 1);
+        assert_eq!(split_point(3), 2);
+        assert_eq!(split_point(4), 2);
+        assert_eq!(split_point(5), 4);
+        assert_eq!(split_point(10), 8);
+        assert_eq!(split_point(20), 16);
+        assert_eq!(split_point(100), 64);
+        assert_eq!(split_point(255), 128);
+        assert_eq!(split_point(256), 128);
+        assert_eq!(split_point(257), 256);
+        assert_eq!(split_point(1 << 40), 1 << 39);
+        assert_eq!(split_point(u64::MAX), 1 << 63);
     }
 
     #[test]
@@ -142,6 +207,50 @@ mod tests {
         assert_eq!(leaf_root, &root);
     }
 
+    #[test]
+    fn test_verify_inclusion() {
+        let leaves: Vec<Vec<u8>> = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+        let root = simple_hash_from_byte_vectors(leaves.clone());
+        let (l0, l1, l2) = (
+            leaf_hash(&leaves[0]),
+            leaf_hash(&leaves[1]),
+            leaf_hash(&leaves[2]),
+        );
+
+        assert!(verify_inclusion(&root, &leaves[0], 0, 3, &[l1, l2]));
+        assert!(verify_inclusion(&root, &leaves[1], 1, 3, &[l0, l2]));
+        assert!(verify_inclusion(
+            &root,
+            &leaves[2],
+            2,
+            3,
+            &[inner_hash(&l0, &l1)]
+        ));
+
+        assert!(!verify_inclusion(&root, &leaves[1], 0, 3, &[l1, l2]));
+        assert!(!verify_inclusion(&root, &leaves[0], 3, 3, &[l1, l2]));
+        assert!(!verify_inclusion(&root, &leaves[0], 0, 3, &[l1]));
+    }
+
+    #[test]
+    fn test_malformed_proofs() {
+        let leaf = leaf_hash(b"a");
+        // Totals which don't fit in 32 bits
+        assert_eq!(compute_hash_from_aunts(0, 1 << 32, leaf, &[leaf]), None);
+        assert_eq!(
+            compute_hash_from_aunts(0, u64::MAX, leaf, &[leaf; 64]),
+            None
+        );
+        assert_eq!(
+            compute_hash_from_aunts(1 << 32, (1 << 32) + 1, leaf, &[leaf]),
+            Some(inner_hash(&leaf, &leaf))
+        );
+
+        // Too many aunts
+        let aunts = vec![leaf; simple_proof::MAX_AUNTS + 1];
+        assert_eq!(compute_hash_from_aunts(0, 1 << 62, leaf, &aunts), None);
+    }
+
     #[test]
     fn test_rfc6962_node() {
         let node_hash_hex = "aa217fe888e47007fa15edab33c2b492a722cb106c64667fc2b044444de66bbb";
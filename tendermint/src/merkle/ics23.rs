@@ -0,0 +1,127 @@
+//! Verification of ICS 23 commitment proofs, as returned by Cosmos SDK
+//! applications in response to `/abci_query` requests with `prove = true`.
+
+use crate::prelude::*;
+use ::ics23::commitment_proof::Proof as Ics23Proof;
+use ::ics23::{CommitmentProof, ExistenceProof, ProofSpec};
+use prost::Message;
+
+use super::proof::{Proof, ProofOp};
+use crate::hash::AppHash;
+use crate::{Error, Kind};
+
+/// Type of proof operations proving the inclusion of a key in an IAVL tree
+/// (i.e. in an application's store).
+pub const PROOF_OP_IAVL: &str = "ics23:iavl";
+
+/// Type of proof operations proving the inclusion of a store's root hash in
+/// a simple Merkle tree (i.e. in the application's multistore).
+pub const PROOF_OP_SIMPLE: &str = "ics23:simple";
+
+impl Proof {
+    /// Verify that this chain of ICS 23 proof operations proves that `key`
+    /// maps to `value` in the application state with the given app hash.
+    ///
+    /// The first operation must prove the key/value pair against the root of
+    /// a store, and each subsequent operation must prove the root computed
+    /// by the previous one, until the last one is anchored at the app hash.
+    #[cfg_attr(docsrs, doc(cfg(feature = "ics23")))]
+    pub fn verify_membership(
+        &self,
+        app_hash: &AppHash,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), Error> {
+        let (first, rest) = self.first_op(key)?;
+        let (proof, spec) = decode(first)?;
+        let root = match &proof.proof {
+            Some(Ics23Proof::Exist(existence)) => existence_root(existence)?,
+            _ => return Err(invalid("expected an existence proof")),
+        };
+        if !::ics23::verify_membership(&proof, &spec, &root, key, value) {
+            return Err(invalid("failed to verify membership of key"));
+        }
+        verify_chain(rest, root, app_hash)
+    }
+
+    /// Verify that this chain of ICS 23 proof operations proves that `key`
+    /// is absent from the application state with the given app hash.
+    ///
+    /// The first operation must prove the absence of the key from a store,
+    /// and the subsequent ones the inclusion of the store's root, as for
+    /// [`Proof::verify_membership`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "ics23")))]
+    pub fn verify_non_membership(&self, app_hash: &AppHash, key: &[u8]) -> Result<(), Error> {
+        let (first, rest) = self.first_op(key)?;
+        let (proof, spec) = decode(first)?;
+        let root = match &proof.proof {
+            Some(Ics23Proof::Nonexist(non_existence)) => {
+                match non_existence
+                    .left
+                    .as_ref()
+                    .or_else(|| non_existence.right.as_ref())
+                {
+                    Some(existence) => existence_root(existence)?,
+                    None => return Err(invalid("empty non-existence proof")),
+                }
+            }
+            _ => return Err(invalid("expected a non-existence proof")),
+        };
+        if !::ics23::verify_non_membership(&proof, &spec, &root, key) {
+            return Err(invalid("failed to verify non-membership of key"));
+        }
+        verify_chain(rest, root, app_hash)
+    }
+
+    fn first_op(&self, key: &[u8]) -> Result<(&ProofOp, &[ProofOp]), Error> {
+        let (first, rest) = self
+            .ops
+            .split_first()
+            .ok_or_else(|| invalid("no proof operations"))?;
+        if first.key != key {
+            return Err(invalid("proof is for a different key"));
+        }
+        Ok((first, rest))
+    }
+}
+
+// Verify the inclusion of each root in the next proof, up to the app hash.
+fn verify_chain(ops: &[ProofOp], mut root: Vec<u8>, app_hash: &AppHash) -> Result<(), Error> {
+    for op in ops {
+        let (proof, spec) = decode(op)?;
+        let next_root = match &proof.proof {
+            Some(Ics23Proof::Exist(existence)) => existence_root(existence)?,
+            _ => return Err(invalid("expected an existence proof")),
+        };
+        if !::ics23::verify_membership(&proof, &spec, &next_root, &op.key, &root) {
+            return Err(invalid(format!(
+                "failed to verify membership of root in {} proof",
+                op.field_type
+            )));
+        }
+        root = next_root;
+    }
+    if root.as_slice() != app_hash.as_ref() {
+        return Err(invalid("computed root does not match app hash"));
+    }
+    Ok(())
+}
+
+fn decode(op: &ProofOp) -> Result<(CommitmentProof, ProofSpec), Error> {
+    let spec = match op.field_type.as_str() {
+        PROOF_OP_IAVL => ::ics23::iavl_spec(),
+        PROOF_OP_SIMPLE => ::ics23::tendermint_spec(),
+        other => return Err(invalid(format!("unsupported proof operation {}", other))),
+    };
+    let proof = CommitmentProof::decode(op.data.as_slice())
+        .map_err(|e| Kind::InvalidMerkleProof.context(e))?;
+    Ok((proof, spec))
+}
+
+fn existence_root(proof: &ExistenceProof) -> Result<Vec<u8>, Error> {
+    ::ics23::calculate_existence_root(proof).map_err(|e| invalid(e.to_string()))
+}
+
+fn invalid(reason: impl Into<String>) -> Error {
+    Kind::InvalidMerkleProof.context(reason.into()).into()
+}
@@ -1,13 +1,20 @@
 //! Merkle proofs
+use crate::prelude::*;
+use core::convert::TryFrom;
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
 
 use tendermint_proto::crypto::ProofOp as RawProofOp;
 use tendermint_proto::crypto::ProofOps as RawProofOps;
+use tendermint_proto::crypto::ValueOp as RawValueOp;
 use tendermint_proto::Protobuf;
 
+use super::{kv_pair_leaf, Hash, SimpleProof};
 use crate::serializers;
-use crate::Error;
+use crate::{Error, Kind};
+
+/// Type of the proof operations proving that a key maps to a value in a
+/// simple Merkle map (Tendermint's `ValueOp`).
+pub const VALUE_OP_TYPE: &str = "simple:v";
 
 /// Proof is Merkle proof defined by the list of ProofOps
 /// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/merkle.proto#L26>
@@ -34,6 +41,51 @@ pub struct ProofOp {
     pub data: Vec<u8>,
 }
 
+impl ProofOp {
+    /// Proof operation proving that `key` maps to a value in a simple Merkle
+    /// map, given the inclusion proof of the corresponding key/value pair
+    /// leaf (see [`kv_pair_leaf`]).
+    pub fn value(key: Vec<u8>, proof: SimpleProof) -> Self {
+        let data = RawValueOp {
+            key: key.clone(),
+            proof: Some(proof.into()),
+        };
+        let mut buf = Vec::new();
+        // Encoding into a `Vec` cannot fail
+        prost::Message::encode(&data, &mut buf).unwrap();
+        Self {
+            field_type: VALUE_OP_TYPE.to_string(),
+            key,
+            data: buf,
+        }
+    }
+}
+
+impl Proof {
+    /// Verify that this proof, made of a single [`VALUE_OP_TYPE`] operation,
+    /// proves that `key` maps to `value` in the simple Merkle map with the
+    /// given root hash.
+    pub fn verify_value(&self, root_hash: &Hash, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        let op = match self.ops.as_slice() {
+            [op] if op.field_type == VALUE_OP_TYPE => op,
+            _ => {
+                return Err(Kind::InvalidMerkleProof
+                    .context(format!("expected a single {} operation", VALUE_OP_TYPE))
+                    .into())
+            }
+        };
+        let data = <RawValueOp as prost::Message>::decode(op.data.as_slice())
+            .map_err(|e| Kind::InvalidMerkleProof.context(e))?;
+        if op.key != key || data.key != key {
+            return Err(Kind::InvalidMerkleProof.context("key mismatch").into());
+        }
+        let proof = data
+            .proof
+            .ok_or_else(|| Kind::InvalidMerkleProof.context("missing proof"))?;
+        SimpleProof::try_from(proof)?.verify(root_hash, &kv_pair_leaf(key, value))
+    }
+}
+
 impl Protobuf<RawProofOp> for ProofOp {}
 
 impl TryFrom<RawProofOp> for ProofOp {
@@ -80,9 +132,28 @@ impl From<Proof> for RawProofOps {
 
 #[cfg(test)]
 mod test {
-    use super::Proof;
+    use super::{Proof, ProofOp};
+    use crate::merkle::{kv_pair_leaf, proofs_from_byte_vectors};
+    use crate::prelude::*;
     use crate::test::test_serialization_roundtrip;
 
+    #[test]
+    fn value_op_round_trip() {
+        let pairs = [("a", "1"), ("b", "2"), ("c", "3")];
+        let leaves: Vec<Vec<u8>> = pairs
+            .iter()
+            .map(|(k, v)| kv_pair_leaf(k.as_bytes(), v.as_bytes()))
+            .collect();
+        let (root, proofs) = proofs_from_byte_vectors(&leaves);
+        let proof = Proof {
+            ops: vec![ProofOp::value(b"b".to_vec(), proofs[1].clone())],
+        };
+
+        assert!(proof.verify_value(&root, b"b", b"2").is_ok());
+        assert!(proof.verify_value(&root, b"b", b"3").is_err());
+        assert!(proof.verify_value(&root, b"a", b"1").is_err());
+    }
+
     #[test]
     fn serialization_roundtrip() {
         let payload = r#"
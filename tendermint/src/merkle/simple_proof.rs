@@ -0,0 +1,183 @@
+//! Inclusion proofs for simple Merkle trees
+
+use crate::prelude::*;
+use core::convert::{TryFrom, TryInto};
+use serde::{Deserialize, Serialize};
+
+use tendermint_proto::crypto::Proof as RawProof;
+use tendermint_proto::Protobuf;
+
+use super::{compute_hash_from_aunts, empty_hash, inner_hash, leaf_hash, split_point, Hash};
+use crate::{Error, Kind};
+
+/// Maximum number of aunts in a proof, bounding the size of the trees which
+/// can be proven (as in Tendermint).
+pub const MAX_AUNTS: usize = 100;
+
+/// Proof that a leaf is included in a simple Merkle tree, compatible with
+/// Tendermint's `merkle.Proof`.
+///
+/// <https://github.com/tendermint/tendermint/blob/v0.34.0/crypto/merkle/proof.go>
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawProof", into = "RawProof")]
+pub struct SimpleProof {
+    /// Total number of leaves in the tree
+    pub total: u64,
+    /// Index of the leaf
+    pub index: u64,
+    /// Hash of the leaf
+    pub leaf_hash: Hash,
+    /// Hashes of the siblings of the nodes on the path from the leaf to the
+    /// root, from the bottom of the tree to the top
+    pub aunts: Vec<Hash>,
+}
+
+impl SimpleProof {
+    /// Compute the root hash implied by this proof.
+    ///
+    /// Returns `None` if the proof is malformed.
+    pub fn compute_root_hash(&self) -> Option<Hash> {
+        compute_hash_from_aunts(self.index, self.total, self.leaf_hash, &self.aunts)
+    }
+
+    /// Verify that this proof proves the inclusion of the given leaf in the
+    /// tree with the given root hash.
+    pub fn verify(&self, root_hash: &Hash, leaf: &[u8]) -> Result<(), Error> {
+        if self.aunts.len() > MAX_AUNTS {
+            return Err(Kind::InvalidMerkleProof
+                .context(format!(
+                    "expected no more than {} aunts, got {}",
+                    MAX_AUNTS,
+                    self.aunts.len()
+                ))
+                .into());
+        }
+        if self.leaf_hash != leaf_hash(leaf) {
+            return Err(Kind::InvalidMerkleProof
+                .context("leaf hash mismatch")
+                .into());
+        }
+        match self.compute_root_hash() {
+            Some(computed) if &computed == root_hash => Ok(()),
+            Some(_) => Err(Kind::InvalidMerkleProof
+                .context("root hash mismatch")
+                .into()),
+            None => Err(Kind::InvalidMerkleProof.context("malformed proof").into()),
+        }
+    }
+}
+
+impl Protobuf<RawProof> for SimpleProof {}
+
+impl TryFrom<RawProof> for SimpleProof {
+    type Error = Error;
+
+    fn try_from(value: RawProof) -> Result<Self, Self::Error> {
+        Ok(Self {
+            total: value
+                .total
+                .try_into()
+                .map_err(|_| Kind::InvalidMerkleProof.context("negative total"))?,
+            index: value
+                .index
+                .try_into()
+                .map_err(|_| Kind::InvalidMerkleProof.context("negative index"))?,
+            leaf_hash: hash_from_vec(value.leaf_hash)?,
+            aunts: value
+                .aunts
+                .into_iter()
+                .map(hash_from_vec)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<SimpleProof> for RawProof {
+    // Totals and indices beyond `i64::MAX` saturate: such proofs never verify.
+    fn from(value: SimpleProof) -> Self {
+        RawProof {
+            total: value.total.try_into().unwrap_or(i64::MAX),
+            index: value.index.try_into().unwrap_or(i64::MAX),
+            leaf_hash: value.leaf_hash.to_vec(),
+            aunts: value.aunts.iter().map(|aunt| aunt.to_vec()).collect(),
+        }
+    }
+}
+
+fn hash_from_vec(bytes: Vec<u8>) -> Result<Hash, Error> {
+    bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| Kind::InvalidHashSize.into())
+}
+
+/// Compute the Merkle root of the given leaves, along with an inclusion
+/// proof for each of them (in the same order).
+pub fn proofs_from_byte_vectors(leaves: &[Vec<u8>]) -> (Hash, Vec<SimpleProof>) {
+    let (root, aunts) = aunts_from_byte_slices(leaves);
+    let total = leaves.len() as u64;
+    let proofs = leaves
+        .iter()
+        .zip(aunts)
+        .enumerate()
+        .map(|(index, (leaf, aunts))| SimpleProof {
+            total,
+            index: index as u64,
+            leaf_hash: leaf_hash(leaf),
+            aunts,
+        })
+        .collect();
+    (root, proofs)
+}
+
+// Returns the root hash of the tree, and the aunts of each leaf.
+fn aunts_from_byte_slices(leaves: &[Vec<u8>]) -> (Hash, Vec<Vec<Hash>>) {
+    match leaves.len() {
+        0 => (empty_hash(), vec![]),
+        1 => (leaf_hash(&leaves[0]), vec![vec![]]),
+        length => {
+            let k = split_point(length as u64) as usize;
+            let (left, mut left_aunts) = aunts_from_byte_slices(&leaves[..k]);
+            let (right, mut right_aunts) = aunts_from_byte_slices(&leaves[k..]);
+            left_aunts.iter_mut().for_each(|aunts| aunts.push(right));
+            right_aunts.iter_mut().for_each(|aunts| aunts.push(left));
+            left_aunts.append(&mut right_aunts);
+            (inner_hash(&left, &right), left_aunts)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::simple_hash_from_byte_vectors;
+
+    #[test]
+    fn proofs_verify() {
+        for total in 0..=20_u8 {
+            let leaves: Vec<Vec<u8>> = (0..total).map(|i| vec![i; i as usize]).collect();
+            let (root, proofs) = proofs_from_byte_vectors(&leaves);
+            assert_eq!(simple_hash_from_byte_vectors(leaves.clone()), root);
+            assert_eq!(leaves.len(), proofs.len());
+
+            for (leaf, proof) in leaves.iter().zip(&proofs) {
+                proof.verify(&root, leaf).unwrap();
+                assert!(proof.verify(&root, b"not a leaf").is_err());
+                assert!(proof.verify(&[0; 32], leaf).is_err());
+
+                let raw = RawProof::from(proof.clone());
+                assert_eq!(proof, &SimpleProof::try_from(raw).unwrap());
+            }
+            if total > 1 {
+                let mut proof = proofs[0].clone();
+                proof.total = u64::MAX;
+                assert_eq!(RawProof::from(proof.clone()).total, i64::MAX);
+                assert!(proof.verify(&root, &leaves[0]).is_err());
+
+                let mut proof = proofs[0].clone();
+                proof.index = 1;
+                assert!(proof.verify(&root, &leaves[0]).is_err());
+            }
+        }
+    }
+}
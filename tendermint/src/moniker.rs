@@ -1,11 +1,12 @@
 //! Monikers: names associated with validators
 
 use crate::error::Error;
-use serde::{Deserialize, Serialize};
-use std::{
+use crate::prelude::*;
+use core::{
     fmt::{self, Display},
     str::FromStr,
 };
+use serde::{Deserialize, Serialize};
 
 /// Validator display names
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
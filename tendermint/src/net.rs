@@ -1,5 +1,6 @@
 //! Remote addresses (`tcp://` or `unix://`)
 
+use crate::prelude::*;
 use crate::{
     error::{Error, Kind},
     node,
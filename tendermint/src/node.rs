@@ -1,7 +1,12 @@
 //! Nodes in Tendermint blockchain networks
 
 mod id;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub mod info;
+mod key;
 
 pub use self::id::Id;
+#[cfg(feature = "std")]
 pub use self::info::Info;
+pub use self::key::Key;
@@ -1,16 +1,17 @@
 //! Tendermint node IDs
 
+use crate::prelude::*;
 use crate::{
     error::{Error, Kind},
     public_key::Ed25519,
 };
 
-use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use sha2::{Digest, Sha256};
-use std::{
+use core::{
     fmt::{self, Debug, Display},
     str::FromStr,
 };
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
 use subtle::{self, ConstantTimeEq};
 use subtle_encoding::hex;
 
@@ -1,8 +1,9 @@
 //! Node information (used in RPC responses)
 
+use crate::prelude::*;
 use crate::{chain, channel::Channels, net, node, serializers, Moniker, Version};
+use core::fmt::{self, Display};
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Display};
 
 /// Node information
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
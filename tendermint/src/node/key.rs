@@ -0,0 +1,127 @@
+//! Node keys
+
+#[cfg(feature = "std")]
+use crate::secret::Secret;
+use crate::{
+    error::{Error, Kind},
+    node,
+    private_key::{self, PrivateKey},
+    public_key::PublicKey,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+/// P2P node private keys, as stored in `node_key.json`
+#[derive(Serialize, Deserialize)]
+pub struct Key {
+    /// Private key
+    #[serde(serialize_with = "PrivateKey::serialize_exposed")]
+    pub priv_key: PrivateKey,
+}
+
+impl Key {
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    /// Generate a new random Ed25519 node key
+    pub fn generate() -> Result<Self, Error> {
+        let mut bytes = Secret::new([0_u8; ed25519_dalek::SECRET_KEY_LENGTH]);
+        getrandom::getrandom(bytes.expose_secret_mut()).map_err(|e| Kind::Crypto.context(e))?;
+        let secret = ed25519_dalek::SecretKey::from_bytes(bytes.expose_secret())
+            .map_err(|e| Kind::Crypto.context(e))?;
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        Ok(Key {
+            priv_key: PrivateKey::Ed25519(private_key::Ed25519 { public, secret }),
+        })
+    }
+
+    /// Parse `node_key.json`
+    pub fn parse_json<T: AsRef<str>>(json_string: T) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json_string.as_ref()).map_err(|e| Kind::Parse.context(e))?)
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    /// Load `node_key.json` from a file
+    pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string = fs::read_to_string(path).map_err(|e| {
+            format_err!(
+                Kind::Parse,
+                "couldn't open {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
+
+        Self::parse_json(json_string)
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    /// Save `node_key.json` to a file
+    pub fn save_json_file<P>(&self, path: &P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string =
+            Secret::new(serde_json::to_string(self).map_err(|e| Kind::Io.context(e))?);
+        fs::write(path, json_string.expose_secret()).map_err(|e| {
+            format_err!(
+                Kind::Io,
+                "couldn't write {}: {}",
+                path.as_ref().display(),
+                e
+            )
+            .into()
+        })
+    }
+
+    /// Borrow the Ed25519 keypair
+    pub fn ed25519_keypair(&self) -> Option<&private_key::Ed25519> {
+        self.priv_key.ed25519_keypair()
+    }
+
+    /// Get the public key for this keypair
+    pub fn public_key(&self) -> PublicKey {
+        match &self.priv_key {
+            PrivateKey::Ed25519(keypair) => keypair.public.into(),
+        }
+    }
+
+    /// Get node ID for this keypair
+    pub fn node_id(&self) -> node::Id {
+        #[allow(unreachable_patterns)]
+        match &self.public_key() {
+            PublicKey::Ed25519(pubkey) => node::Id::from(*pubkey),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn generate_and_reload() {
+        let key = Key::generate().unwrap();
+        let other = Key::generate().unwrap();
+        assert_ne!(key.node_id(), other.node_id());
+
+        let path =
+            std::env::temp_dir().join(format!("tendermint-node-key-{}.json", std::process::id()));
+        key.save_json_file(&path).unwrap();
+        let loaded = Key::load_json_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(key.node_id(), loaded.node_id());
+        assert_eq!(
+            key.ed25519_keypair().unwrap().to_bytes()[..],
+            loaded.ed25519_keypair().unwrap().to_bytes()[..]
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Re-exports of the items of the standard prelude which live in `alloc`,
+//! since the crate is `no_std`
+
+pub use alloc::borrow::ToOwned;
+pub use alloc::boxed::Box;
+pub use alloc::string::{String, ToString};
+pub use alloc::vec::Vec;
+
+pub use alloc::format;
+pub use alloc::vec;
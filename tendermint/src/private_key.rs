@@ -1,26 +1,43 @@
 //! Cryptographic private keys
 
+use crate::prelude::*;
 pub use ed25519_dalek::{Keypair as Ed25519, EXPANDED_SECRET_KEY_LENGTH as ED25519_KEYPAIR_SIZE};
 
 use crate::public_key::PublicKey;
+use crate::secret::Secret;
+use core::fmt;
 use serde::{de, ser, Deserialize, Serialize};
 use subtle_encoding::{Base64, Encoding};
-use zeroize::Zeroizing;
 
 /// Private keys as parsed from configuration files
-#[derive(Serialize, Deserialize)]
+///
+/// The secret key material is zeroized when dropped and redacted from the
+/// [`Debug`](fmt::Debug) output. `PrivateKey` doesn't implement [`Serialize`],
+/// so that it can't be serialized by accident: key files opt into it with
+/// `#[serde(serialize_with = "PrivateKey::serialize_exposed")]`.
+#[derive(Deserialize)]
 #[non_exhaustive]
 #[serde(tag = "type", content = "value")] // JSON custom serialization for priv_validator_key.json
 pub enum PrivateKey {
     /// Ed25519 keys
     #[serde(
         rename = "tendermint/PrivKeyEd25519",
-        serialize_with = "serialize_ed25519_keypair",
         deserialize_with = "deserialize_ed25519_keypair"
     )]
     Ed25519(Ed25519),
 }
 
+/// Borrowed counterpart of [`PrivateKey`], used to serialize it
+#[derive(Serialize)]
+#[serde(tag = "type", content = "value")]
+enum ExposedPrivateKey<'a> {
+    #[serde(
+        rename = "tendermint/PrivKeyEd25519",
+        serialize_with = "serialize_ed25519_keypair"
+    )]
+    Ed25519(&'a Ed25519),
+}
+
 impl PrivateKey {
     /// Get the public key associated with this private key
     pub fn public_key(&self) -> PublicKey {
@@ -35,6 +52,29 @@ impl PrivateKey {
             PrivateKey::Ed25519(keypair) => Some(keypair),
         }
     }
+
+    /// Serialize this private key, exposing the secret key material
+    pub fn serialize_exposed<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            PrivateKey::Ed25519(keypair) => ExposedPrivateKey::Ed25519(keypair),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrivateKey::Ed25519(keypair) => f
+                .debug_struct("Ed25519")
+                .field("public", &keypair.public)
+                .field("secret", &format_args!("[REDACTED]"))
+                .finish(),
+        }
+    }
 }
 
 /// Serialize an Ed25519 keypair as Base64
@@ -42,9 +82,12 @@ fn serialize_ed25519_keypair<S>(keypair: &Ed25519, serializer: S) -> Result<S::O
 where
     S: ser::Serializer,
 {
-    let keypair_bytes = Zeroizing::new(keypair.to_bytes());
-    Zeroizing::new(String::from_utf8(Base64::default().encode(&keypair_bytes[..])).unwrap())
-        .serialize(serializer)
+    let keypair_bytes = Secret::new(keypair.to_bytes());
+    Secret::new(
+        String::from_utf8(Base64::default().encode(&keypair_bytes.expose_secret()[..])).unwrap(),
+    )
+    .expose_secret()
+    .serialize(serializer)
 }
 
 /// Deserialize an Ed25519 keypair from Base64
@@ -53,15 +96,32 @@ where
     D: de::Deserializer<'de>,
 {
     use de::Error;
-    let string = Zeroizing::new(String::deserialize(deserializer)?);
-    let mut keypair_bytes = Zeroizing::new([0u8; ED25519_KEYPAIR_SIZE]);
+    let string = Secret::<String>::deserialize(deserializer)?;
+    let mut keypair_bytes = Secret::new([0u8; ED25519_KEYPAIR_SIZE]);
     let decoded_len = Base64::default()
-        .decode_to_slice(string.as_bytes(), &mut *keypair_bytes)
+        .decode_to_slice(
+            string.expose_secret().as_bytes(),
+            keypair_bytes.expose_secret_mut(),
+        )
         .map_err(D::Error::custom)?;
 
     if decoded_len != ED25519_KEYPAIR_SIZE {
         return Err(D::Error::custom("invalid Ed25519 keypair size"));
     }
 
-    Ed25519::from_bytes(&*keypair_bytes).map_err(D::Error::custom)
+    Ed25519::from_bytes(keypair_bytes.expose_secret()).map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::signing_key;
+
+    #[test]
+    fn debug_is_redacted() {
+        let private_key = PrivateKey::Ed25519(signing_key(1));
+        let debug = format!("{:?}", private_key);
+        assert!(debug.contains("REDACTED"));
+        assert!(!debug.contains(&format!("{:?}", &[1_u8; 32][..])));
+    }
 }
@@ -0,0 +1,27 @@
+//! Private validators
+//!
+//! This module contains the building blocks of validator signers, and of
+//! the remote signer protocol spoken between validator nodes and signers:
+//!
+//! - [`LastSignState`]: the height, round and step at which the validator
+//!   last signed, as stored in `priv_validator_state.json`, used to prevent
+//!   double signing
+//! - [`FilePrivValidator`]: a signer backed by `priv_validator_key.json`
+//!   and `priv_validator_state.json`, which refuses to sign votes and
+//!   proposals conflicting with what it has already signed
+//! - [`SignerClient`] and [`SignerServer`]: the validator node and signer
+//!   sides of the remote signer protocol, over any connection (e.g. a
+//!   SecretConnection or a Unix domain socket)
+
+mod client;
+mod file;
+mod last_sign_state;
+mod message;
+mod server;
+
+pub use self::client::SignerClient;
+pub use self::file::FilePrivValidator;
+pub use self::last_sign_state::{LastSignState, Step};
+pub use self::message::{Request, Response, MAX_MSG_SIZE};
+pub use self::server::{PrivValidator, SignerServer};
+pub use crate::config::PrivValidatorKey;
@@ -0,0 +1,120 @@
+//! Remote signer client
+
+use super::message::{Request, Response};
+use crate::prelude::*;
+use crate::proposal::SignProposalRequest;
+use crate::public_key::PubKeyRequest;
+use crate::vote::SignVoteRequest;
+use crate::{chain, Error, Kind, Proposal, PublicKey, Vote};
+use std::io::{Read, Write};
+use tendermint_proto::privval::RemoteSignerError;
+
+/// Client side of the remote signer protocol, used by a validator node to
+/// request signatures from its signer (e.g. a KMS) over the given
+/// connection.
+///
+/// The connection is usually a [SecretConnection] or a Unix domain socket,
+/// and may have been established by either side.
+///
+/// [SecretConnection]: https://docs.rs/tendermint-p2p/
+pub struct SignerClient<IoHandler> {
+    io: IoHandler,
+    chain_id: chain::Id,
+}
+
+impl<IoHandler> SignerClient<IoHandler>
+where
+    IoHandler: Read + Write,
+{
+    /// Create a client requesting signatures for the given chain over the
+    /// given connection.
+    pub fn new(io: IoHandler, chain_id: chain::Id) -> Self {
+        SignerClient { io, chain_id }
+    }
+
+    /// Check that the signer is still reachable.
+    pub fn ping(&mut self) -> Result<(), Error> {
+        match self.request(Request::Ping)? {
+            Response::Ping => Ok(()),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    /// Fetch the public key of the validator.
+    pub fn pub_key(&mut self) -> Result<PublicKey, Error> {
+        let request = Request::PubKey(PubKeyRequest {
+            chain_id: self.chain_id.clone(),
+        });
+        match self.request(request)? {
+            Response::PubKey(response) => {
+                check_error(response.error)?;
+                response
+                    .pub_key
+                    .ok_or_else(|| Kind::Protocol.context("missing public key").into())
+            }
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    /// Have the given vote signed, replacing it with the signed vote.
+    pub fn sign_vote(&mut self, vote: &mut Vote) -> Result<(), Error> {
+        let request = Request::SignVote(SignVoteRequest {
+            vote: vote.clone(),
+            chain_id: self.chain_id.clone(),
+        });
+        match self.request(request)? {
+            Response::SignedVote(response) => {
+                check_error(response.error)?;
+                *vote = response
+                    .vote
+                    .ok_or_else(|| Kind::Protocol.context("missing signed vote"))?;
+                Ok(())
+            }
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    /// Have the given proposal signed, replacing it with the signed
+    /// proposal.
+    pub fn sign_proposal(&mut self, proposal: &mut Proposal) -> Result<(), Error> {
+        let request = Request::SignProposal(SignProposalRequest {
+            proposal: proposal.clone(),
+            chain_id: self.chain_id.clone(),
+        });
+        match self.request(request)? {
+            Response::SignedProposal(response) => {
+                check_error(response.error)?;
+                *proposal = response
+                    .proposal
+                    .ok_or_else(|| Kind::Protocol.context("missing signed proposal"))?;
+                Ok(())
+            }
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    /// Close the connection to the signer, returning it.
+    pub fn into_inner(self) -> IoHandler {
+        self.io
+    }
+
+    fn request(&mut self, request: Request) -> Result<Response, Error> {
+        request.write_to(&mut self.io)?;
+        Response::read_from(&mut self.io)
+    }
+}
+
+fn check_error(error: Option<RemoteSignerError>) -> Result<(), Error> {
+    match error {
+        Some(error) => Err(Kind::RemoteSigner
+            .context(format!("{} (code {})", error.description, error.code))
+            .into()),
+        None => Ok(()),
+    }
+}
+
+fn unexpected_response() -> Error {
+    Kind::InvalidMessageType
+        .context("unexpected response from remote signer")
+        .into()
+}
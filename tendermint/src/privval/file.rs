@@ -0,0 +1,233 @@
+//! File-based private validator
+
+use super::{LastSignState, PrivValidatorKey, Step};
+use crate::prelude::*;
+use crate::proposal::CanonicalProposal;
+use crate::signature::Signer;
+use crate::vote::CanonicalVote;
+use crate::{
+    block, chain,
+    error::{Error, Kind},
+    Proposal, PublicKey, Signature, Vote,
+};
+use std::path::{Path, PathBuf};
+use tendermint_proto::Protobuf;
+
+/// A private validator backed by a `priv_validator_key.json` and a
+/// `priv_validator_state.json` file, as Tendermint's `FilePV`.
+///
+/// The last sign state is persisted before any signature is returned, and
+/// signing is refused for any height, round and step lower than the last
+/// one. Signing again at the same height, round and step is only allowed for
+/// the same data, in which case the previous signature is returned. Votes and
+/// proposals which only differ from the last signed one by their timestamp
+/// are given the previous timestamp and signature.
+pub struct FilePrivValidator {
+    key: PrivValidatorKey,
+    last_sign_state: LastSignState,
+    state_file: PathBuf,
+}
+
+impl FilePrivValidator {
+    /// Create a private validator from the given key and last sign state,
+    /// which will be saved to the given state file.
+    pub fn new<P>(key: PrivValidatorKey, last_sign_state: LastSignState, state_file: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        FilePrivValidator {
+            key,
+            last_sign_state,
+            state_file: state_file.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Load a private validator from the given key and state files.
+    pub fn load<P, Q>(key_file: &P, state_file: &Q) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let key = PrivValidatorKey::load_json_file(key_file)?;
+        let last_sign_state = LastSignState::load_json_file(state_file)?;
+        Ok(Self::new(key, last_sign_state, state_file))
+    }
+
+    /// Public key of this validator
+    pub fn pub_key(&self) -> PublicKey {
+        self.key.priv_key.public_key()
+    }
+
+    /// What this validator last signed
+    pub fn last_sign_state(&self) -> &LastSignState {
+        &self.last_sign_state
+    }
+
+    /// Sign the given vote for the given chain, unless doing so could amount
+    /// to double signing.
+    pub fn sign_vote(&mut self, chain_id: &chain::Id, vote: &mut Vote) -> Result<(), Error> {
+        let step = Step::from(vote.vote_type);
+        if self
+            .last_sign_state
+            .check_hrs(vote.height, vote.round, step)?
+        {
+            let last_vote =
+                CanonicalVote::decode_length_delimited_vec(&self.last_sign_state.sign_bytes)
+                    .map_err(|e| Kind::DoubleSign.context(e))?;
+            let mut unchanged = vote.clone();
+            unchanged.timestamp = last_vote.timestamp;
+            let sign_bytes = unchanged
+                .to_signable_vec(chain_id.clone())
+                .map_err(|e| Kind::Protocol.context(e))?;
+            vote.signature = self.previous_signature(&sign_bytes)?;
+            vote.timestamp = last_vote.timestamp;
+            return Ok(());
+        }
+
+        let sign_bytes = vote
+            .to_signable_vec(chain_id.clone())
+            .map_err(|e| Kind::Protocol.context(e))?;
+        vote.signature = self.sign(vote.height, vote.round, step, sign_bytes)?;
+        Ok(())
+    }
+
+    /// Sign the given proposal for the given chain, unless doing so could
+    /// amount to double signing.
+    pub fn sign_proposal(
+        &mut self,
+        chain_id: &chain::Id,
+        proposal: &mut Proposal,
+    ) -> Result<(), Error> {
+        if self
+            .last_sign_state
+            .check_hrs(proposal.height, proposal.round, Step::Propose)?
+        {
+            let last_proposal =
+                CanonicalProposal::decode_length_delimited_vec(&self.last_sign_state.sign_bytes)
+                    .map_err(|e| Kind::DoubleSign.context(e))?;
+            let mut unchanged = proposal.clone();
+            unchanged.timestamp = last_proposal.timestamp;
+            let sign_bytes = unchanged
+                .to_signable_vec(chain_id.clone())
+                .map_err(|e| Kind::Protocol.context(e))?;
+            proposal.signature = self.previous_signature(&sign_bytes)?;
+            proposal.timestamp = last_proposal.timestamp;
+            return Ok(());
+        }
+
+        let sign_bytes = proposal
+            .to_signable_vec(chain_id.clone())
+            .map_err(|e| Kind::Protocol.context(e))?;
+        proposal.signature =
+            self.sign(proposal.height, proposal.round, Step::Propose, sign_bytes)?;
+        Ok(())
+    }
+
+    // Signs the given bytes, persisting the new last sign state before
+    // returning the signature.
+    fn sign(
+        &mut self,
+        height: block::Height,
+        round: block::Round,
+        step: Step,
+        sign_bytes: Vec<u8>,
+    ) -> Result<Signature, Error> {
+        let keypair = self
+            .key
+            .priv_key
+            .ed25519_keypair()
+            .ok_or_else(|| Kind::InvalidKey.context("unsupported validator key type"))?;
+        let signature: Signature = keypair
+            .try_sign(&sign_bytes)
+            .map_err(|e| Kind::Crypto.context(e))?
+            .into();
+
+        let last_sign_state = LastSignState {
+            height,
+            round,
+            step,
+            signature: Some(signature),
+            sign_bytes,
+        };
+        last_sign_state.save_json_file(&self.state_file)?;
+        self.last_sign_state = last_sign_state;
+        Ok(signature)
+    }
+
+    // The last signature, provided the given bytes are the ones last signed.
+    fn previous_signature(&self, sign_bytes: &[u8]) -> Result<Signature, Error> {
+        match self.last_sign_state.signature {
+            Some(signature) if sign_bytes == self.last_sign_state.sign_bytes.as_slice() => {
+                Ok(signature)
+            }
+            _ => Err(Kind::DoubleSign
+                .context("conflicting data at the same height, round and step")
+                .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::signing_key;
+    use crate::{account, private_key::PrivateKey, vote, Time};
+
+    fn validator(state_file: &Path) -> FilePrivValidator {
+        let keypair = signing_key(3);
+        let key = PrivValidatorKey {
+            address: account::Id::from(keypair.public),
+            pub_key: keypair.public.into(),
+            priv_key: PrivateKey::Ed25519(keypair),
+        };
+        FilePrivValidator::new(key, LastSignState::default(), state_file)
+    }
+
+    #[test]
+    fn double_sign_protection() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("priv_validator_state.json");
+        let mut pv = validator(&state_file);
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+        let vote = Vote {
+            vote_type: vote::Type::Prevote,
+            height: block::Height::from(10_u32),
+            round: block::Round::from(1_u16),
+            timestamp: Some(Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap()),
+            ..Vote::default()
+        };
+
+        let mut signed = vote.clone();
+        pv.sign_vote(&chain_id, &mut signed).unwrap();
+        signed.verify(chain_id.clone(), &pv.pub_key()).unwrap();
+        assert_eq!(
+            pv.last_sign_state(),
+            &LastSignState::load_json_file(&state_file).unwrap()
+        );
+
+        // Signing the same vote again, or with another timestamp, yields the
+        // same signature.
+        let mut again = vote.clone();
+        again.timestamp = Some(Time::parse_from_rfc3339("2021-01-01T00:00:01Z").unwrap());
+        pv.sign_vote(&chain_id, &mut again).unwrap();
+        assert_eq!(signed, again);
+
+        // Voting for another block at the same height, round and step is
+        // double signing, as is going back to a previous round.
+        let mut conflicting = vote.clone();
+        conflicting.block_id = Some(block::Id {
+            hash: crate::Hash::Sha256([1; 32]),
+            part_set_header: block::parts::Header::new(1, crate::Hash::Sha256([2; 32])).unwrap(),
+        });
+        assert!(pv.sign_vote(&chain_id, &mut conflicting).is_err());
+        let mut previous_round = vote.clone();
+        previous_round.round = block::Round::from(0_u16);
+        assert!(pv.sign_vote(&chain_id, &mut previous_round).is_err());
+
+        // Moving on to the next step is fine.
+        let mut precommit = vote;
+        precommit.vote_type = vote::Type::Precommit;
+        pv.sign_vote(&chain_id, &mut precommit).unwrap();
+        assert_eq!(Step::Precommit, pv.last_sign_state().step);
+    }
+}
@@ -0,0 +1,374 @@
+//! Last sign state (`priv_validator_state.json`)
+
+use crate::prelude::*;
+use crate::{
+    block,
+    error::{Error, Kind},
+    serializers, vote, Signature,
+};
+use serde::{Deserialize, Serialize};
+use std::convert::{TryFrom, TryInto};
+use std::io::{self, Write};
+use std::{fmt, fs, path::Path};
+
+/// Step of the consensus protocol at which a validator signs
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Step {
+    /// Nothing has been signed yet
+    None = 0,
+
+    /// Proposal
+    Propose = 1,
+
+    /// Prevote
+    Prevote = 2,
+
+    /// Precommit
+    Precommit = 3,
+}
+
+impl Default for Step {
+    fn default() -> Self {
+        Step::None
+    }
+}
+
+impl From<vote::Type> for Step {
+    fn from(vote_type: vote::Type) -> Self {
+        match vote_type {
+            vote::Type::Prevote => Step::Prevote,
+            vote::Type::Precommit => Step::Precommit,
+        }
+    }
+}
+
+impl TryFrom<i8> for Step {
+    type Error = Error;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Step::None),
+            1 => Ok(Step::Propose),
+            2 => Ok(Step::Prevote),
+            3 => Ok(Step::Precommit),
+            _ => Err(format_err!(Kind::Parse, "invalid step: {}", value).into()),
+        }
+    }
+}
+
+impl From<Step> for i8 {
+    fn from(step: Step) -> i8 {
+        step as i8
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", i8::from(*self))
+    }
+}
+
+/// The last height, round and step at which a validator signed, along with
+/// what it signed.
+///
+/// This is the contents of `priv_validator_state.json`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawLastSignState", into = "RawLastSignState")]
+pub struct LastSignState {
+    /// Height
+    pub height: block::Height,
+
+    /// Round
+    pub round: block::Round,
+
+    /// Step
+    pub step: Step,
+
+    /// Last signature
+    pub signature: Option<Signature>,
+
+    /// Last signed bytes
+    pub sign_bytes: Vec<u8>,
+}
+
+/// The state of a validator which has never signed anything, as in a freshly
+/// initialized `priv_validator_state.json` (note this is at height 0).
+impl Default for LastSignState {
+    fn default() -> Self {
+        LastSignState {
+            height: block::Height::from(0_u32),
+            round: block::Round::default(),
+            step: Step::default(),
+            signature: None,
+            sign_bytes: Vec::new(),
+        }
+    }
+}
+
+impl LastSignState {
+    /// Parse `priv_validator_state.json`
+    pub fn parse_json<T: AsRef<str>>(json_string: T) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json_string.as_ref()).map_err(|e| Kind::Parse.context(e))?)
+    }
+
+    /// Load `priv_validator_state.json` from a file
+    pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string = fs::read_to_string(path).map_err(|e| {
+            format_err!(
+                Kind::Parse,
+                "couldn't open {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
+
+        Self::parse_json(json_string)
+    }
+
+    /// Save `priv_validator_state.json` to a file.
+    ///
+    /// The file is replaced atomically and synced to disk before returning,
+    /// like Tendermint's `WriteFileAtomic`, so that a crash cannot leave it
+    /// truncated or lose a state the validator has signed with.
+    pub fn save_json_file<P>(&self, path: &P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let json_string = serde_json::to_string_pretty(self).map_err(|e| Kind::Io.context(e))?;
+
+        write_file_atomic(path, json_string.as_bytes())
+            .map_err(|e| format_err!(Kind::Io, "couldn't write {}: {}", path.display(), e).into())
+    }
+
+    /// Check whether signing at the given height, round and step could
+    /// amount to double signing, as done by Tendermint's `CheckHRS`.
+    ///
+    /// Signing is refused if the given height, round and step are lower than
+    /// the last ones. Returns `true` if they are the same as the last ones, in
+    /// which case the caller must make sure the data to sign is the same as
+    /// what was last signed.
+    pub fn check_hrs(
+        &self,
+        height: block::Height,
+        round: block::Round,
+        step: Step,
+    ) -> Result<bool, Error> {
+        if self.height > height {
+            return Err(format_err!(
+                Kind::DoubleSign,
+                "height regression: got {}, last height {}",
+                height,
+                self.height
+            )
+            .into());
+        }
+        if self.height < height {
+            return Ok(false);
+        }
+
+        if self.round > round {
+            return Err(format_err!(
+                Kind::DoubleSign,
+                "round regression at height {}: got {}, last round {}",
+                height,
+                round,
+                self.round
+            )
+            .into());
+        }
+        if self.round < round {
+            return Ok(false);
+        }
+
+        if self.step > step {
+            return Err(format_err!(
+                Kind::DoubleSign,
+                "step regression at height {} round {}: got {}, last step {}",
+                height,
+                round,
+                step,
+                self.step
+            )
+            .into());
+        }
+        if self.step < step {
+            return Ok(false);
+        }
+
+        if self.sign_bytes.is_empty() || self.signature.is_none() {
+            return Err(Kind::DoubleSign
+                .context("no previously signed data found for the same height, round and step")
+                .into());
+        }
+        Ok(true)
+    }
+}
+
+// Go's JSON encoding of the last sign state, where only the height is
+// encoded as a string.
+#[derive(Serialize, Deserialize)]
+struct RawLastSignState {
+    #[serde(with = "serializers::from_str")]
+    height: i64,
+    round: i32,
+    step: i8,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "serializers::bytes::base64string"
+    )]
+    signature: Vec<u8>,
+    #[serde(
+        rename = "signbytes",
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "serializers::bytes::hexstring"
+    )]
+    sign_bytes: Vec<u8>,
+}
+
+impl TryFrom<RawLastSignState> for LastSignState {
+    type Error = Error;
+
+    fn try_from(value: RawLastSignState) -> Result<Self, Self::Error> {
+        let signature = if value.signature.is_empty() {
+            None
+        } else {
+            Some(Signature::try_from(value.signature)?)
+        };
+        Ok(LastSignState {
+            height: value.height.try_into()?,
+            round: value.round.try_into()?,
+            step: value.step.try_into()?,
+            signature,
+            sign_bytes: value.sign_bytes,
+        })
+    }
+}
+
+impl From<LastSignState> for RawLastSignState {
+    fn from(value: LastSignState) -> Self {
+        RawLastSignState {
+            height: value.height.into(),
+            round: value.round.into(),
+            step: value.step.into(),
+            signature: value
+                .signature
+                .map(|signature| signature.as_bytes().to_vec())
+                .unwrap_or_default(),
+            sign_bytes: value.sign_bytes,
+        }
+    }
+}
+
+/// Write `contents` to a temporary file next to `path`, sync it, rename it to
+/// `path` and sync the parent directory so that the rename is durable too.
+fn write_file_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+
+    let result = fs::File::create(&tmp_path).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = result.and_then(|_| fs::rename(&tmp_path, path)) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    // Directories can't be opened as files on all platforms
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        fs::File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_roundtrip() {
+        let state = LastSignState::parse_json(
+            r#"{
+                "height": "0",
+                "round": 0,
+                "step": 0
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(LastSignState::default(), state);
+
+        let json = r#"{
+  "height": "12",
+  "round": 1,
+  "step": 3,
+  "signature": "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQ==",
+  "signbytes": "0A0B"
+}"#;
+        let state = LastSignState::parse_json(json).unwrap();
+        assert_eq!(block::Height::from(12_u32), state.height);
+        assert_eq!(block::Round::from(1_u16), state.round);
+        assert_eq!(Step::Precommit, state.step);
+        assert_eq!(&[1; 64][..], state.signature.unwrap().as_bytes());
+        assert_eq!(vec![0x0a, 0x0b], state.sign_bytes);
+        assert_eq!(json, serde_json::to_string_pretty(&state).unwrap());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("priv_validator_state.json");
+        let state = LastSignState {
+            height: block::Height::from(7_u32),
+            round: block::Round::from(1_u16),
+            step: Step::Precommit,
+            signature: None,
+            sign_bytes: vec![1, 2, 3],
+        };
+
+        state.save_json_file(&path).unwrap();
+        assert_eq!(state, LastSignState::load_json_file(&path).unwrap());
+
+        let state = LastSignState {
+            height: block::Height::from(8_u32),
+            ..state
+        };
+        state.save_json_file(&path).unwrap();
+        assert_eq!(state, LastSignState::load_json_file(&path).unwrap());
+        assert_eq!(1, fs::read_dir(dir.path()).unwrap().count());
+    }
+
+    #[test]
+    fn hrs_regressions() {
+        let state = LastSignState {
+            height: block::Height::from(10_u32),
+            round: block::Round::from(2_u16),
+            step: Step::Prevote,
+            signature: None,
+            sign_bytes: vec![],
+        };
+        let check = |height: u32, round: u16, step| {
+            state.check_hrs(block::Height::from(height), block::Round::from(round), step)
+        };
+
+        assert!(check(9, 5, Step::Precommit).is_err());
+        assert!(check(10, 1, Step::Precommit).is_err());
+        assert!(check(10, 2, Step::Propose).is_err());
+        assert!(!check(10, 2, Step::Precommit).unwrap());
+        assert!(!check(10, 3, Step::Propose).unwrap());
+        assert!(!check(11, 0, Step::Propose).unwrap());
+        // Same height, round and step, but nothing to compare against
+        assert!(check(10, 2, Step::Prevote).is_err());
+    }
+}
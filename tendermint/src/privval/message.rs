@@ -0,0 +1,207 @@
+//! Messages of the remote signer protocol
+
+use crate::prelude::*;
+use crate::proposal::{SignProposalRequest, SignedProposalResponse};
+use crate::public_key::{PubKeyRequest, PubKeyResponse};
+use crate::vote::{SignVoteRequest, SignedVoteResponse};
+use crate::{Error, Kind};
+use std::convert::{TryFrom, TryInto};
+use std::io::{Read, Write};
+use tendermint_proto::privval::message::Sum;
+use tendermint_proto::privval::{Message as RawMessage, PingRequest, PingResponse};
+use tendermint_proto::Protobuf;
+
+/// Maximum size of a remote signer message, as in Tendermint.
+pub const MAX_MSG_SIZE: usize = 1024 * 10;
+
+/// Requests sent by a validator node to its signer
+#[derive(Clone, Debug, PartialEq)]
+pub enum Request {
+    /// Request for the validator's public key
+    PubKey(PubKeyRequest),
+
+    /// Request to sign a vote
+    SignVote(SignVoteRequest),
+
+    /// Request to sign a proposal
+    SignProposal(SignProposalRequest),
+
+    /// Request to check that the connection is alive
+    Ping,
+}
+
+impl Request {
+    /// Read a length-delimited request from the given reader.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        Self::decode_vec(&read_message(reader)?).map_err(|e| Kind::Protocol.context(e).into())
+    }
+
+    /// Write this request, length-delimited, to the given writer.
+    pub fn write_to<W: Write>(self, writer: &mut W) -> Result<(), Error> {
+        write_message(writer, self.encode_length_delimited_vec())
+    }
+}
+
+impl Protobuf<RawMessage> for Request {}
+
+impl TryFrom<RawMessage> for Request {
+    type Error = Error;
+
+    fn try_from(value: RawMessage) -> Result<Self, Self::Error> {
+        match value.sum {
+            Some(Sum::PubKeyRequest(req)) => Ok(Request::PubKey(req.try_into()?)),
+            Some(Sum::SignVoteRequest(req)) => Ok(Request::SignVote(req.try_into()?)),
+            Some(Sum::SignProposalRequest(req)) => Ok(Request::SignProposal(req.try_into()?)),
+            Some(Sum::PingRequest(_)) => Ok(Request::Ping),
+            _ => Err(Kind::InvalidMessageType
+                .context("expected a remote signer request")
+                .into()),
+        }
+    }
+}
+
+impl From<Request> for RawMessage {
+    fn from(value: Request) -> Self {
+        let sum = match value {
+            Request::PubKey(req) => Sum::PubKeyRequest(req.into()),
+            Request::SignVote(req) => Sum::SignVoteRequest(req.into()),
+            Request::SignProposal(req) => Sum::SignProposalRequest(req.into()),
+            Request::Ping => Sum::PingRequest(PingRequest {}),
+        };
+        RawMessage { sum: Some(sum) }
+    }
+}
+
+/// Responses sent by a signer to a validator node
+#[derive(Clone, Debug, PartialEq)]
+pub enum Response {
+    /// The validator's public key
+    PubKey(PubKeyResponse),
+
+    /// A signed vote
+    SignedVote(SignedVoteResponse),
+
+    /// A signed proposal
+    SignedProposal(SignedProposalResponse),
+
+    /// Response to a ping request
+    Ping,
+}
+
+impl Response {
+    /// Read a length-delimited response from the given reader.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        Self::decode_vec(&read_message(reader)?).map_err(|e| Kind::Protocol.context(e).into())
+    }
+
+    /// Write this response, length-delimited, to the given writer.
+    pub fn write_to<W: Write>(self, writer: &mut W) -> Result<(), Error> {
+        write_message(writer, self.encode_length_delimited_vec())
+    }
+}
+
+impl Protobuf<RawMessage> for Response {}
+
+impl TryFrom<RawMessage> for Response {
+    type Error = Error;
+
+    fn try_from(value: RawMessage) -> Result<Self, Self::Error> {
+        match value.sum {
+            Some(Sum::PubKeyResponse(res)) => Ok(Response::PubKey(res.try_into()?)),
+            Some(Sum::SignedVoteResponse(res)) => Ok(Response::SignedVote(res.try_into()?)),
+            Some(Sum::SignedProposalResponse(res)) => Ok(Response::SignedProposal(res.try_into()?)),
+            Some(Sum::PingResponse(_)) => Ok(Response::Ping),
+            _ => Err(Kind::InvalidMessageType
+                .context("expected a remote signer response")
+                .into()),
+        }
+    }
+}
+
+impl From<Response> for RawMessage {
+    fn from(value: Response) -> Self {
+        let sum = match value {
+            Response::PubKey(res) => Sum::PubKeyResponse(res.into()),
+            Response::SignedVote(res) => Sum::SignedVoteResponse(res.into()),
+            Response::SignedProposal(res) => Sum::SignedProposalResponse(res.into()),
+            Response::Ping => Sum::PingResponse(PingResponse {}),
+        };
+        RawMessage { sum: Some(sum) }
+    }
+}
+
+// Reads a message prefixed by its length as a varint.
+fn read_message<R: Read>(reader: &mut R) -> Result<Vec<u8>, Error> {
+    let mut len_bytes = Vec::with_capacity(10);
+    loop {
+        let mut byte = [0_u8];
+        reader
+            .read_exact(&mut byte)
+            .map_err(|e| Kind::Io.context(e))?;
+        len_bytes.push(byte[0]);
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+        if len_bytes.len() == 10 {
+            return Err(Kind::Protocol.context("malformed message length").into());
+        }
+    }
+    let len = prost::encoding::decode_varint(&mut len_bytes.as_slice())
+        .map_err(|e| Kind::Protocol.context(e))?;
+    if len > MAX_MSG_SIZE as u64 {
+        return Err(format_err!(
+            Kind::Protocol,
+            "message of {} bytes exceeds the maximum of {} bytes",
+            len,
+            MAX_MSG_SIZE
+        )
+        .into());
+    }
+
+    let mut msg = vec![0; len as usize];
+    reader
+        .read_exact(&mut msg)
+        .map_err(|e| Kind::Io.context(e))?;
+    Ok(msg)
+}
+
+fn write_message<W: Write>(
+    writer: &mut W,
+    msg: Result<Vec<u8>, tendermint_proto::Error>,
+) -> Result<(), Error> {
+    let msg = msg.map_err(|e| Kind::Protocol.context(e))?;
+    writer
+        .write_all(&msg)
+        .and_then(|_| writer.flush())
+        .map_err(|e| Kind::Io.context(e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain;
+
+    #[test]
+    fn request_roundtrip() {
+        let request = Request::PubKey(PubKeyRequest {
+            chain_id: "test-chain".parse::<chain::Id>().unwrap(),
+        });
+        let mut buf = vec![];
+        request.clone().write_to(&mut buf).unwrap();
+        request.clone().write_to(&mut buf).unwrap();
+
+        let mut reader = buf.as_slice();
+        assert_eq!(request, Request::read_from(&mut reader).unwrap());
+        assert_eq!(request, Request::read_from(&mut reader).unwrap());
+        assert!(Request::read_from(&mut reader).is_err());
+
+        // A response is not a request.
+        let mut buf = vec![];
+        Response::Ping.write_to(&mut buf).unwrap();
+        assert!(Request::read_from(&mut buf.as_slice()).is_err());
+        assert_eq!(
+            Response::Ping,
+            Response::read_from(&mut buf.as_slice()).unwrap()
+        );
+    }
+}
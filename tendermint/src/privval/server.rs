@@ -0,0 +1,212 @@
+//! Remote signer server
+
+use super::message::{Request, Response};
+use super::FilePrivValidator;
+use crate::prelude::*;
+use crate::proposal::SignedProposalResponse;
+use crate::public_key::PubKeyResponse;
+use crate::vote::SignedVoteResponse;
+use crate::{chain, Error, Kind, Proposal, PublicKey, Vote};
+use std::io::{Read, Write};
+use tendermint_proto::privval::RemoteSignerError;
+
+/// A validator signer, as Tendermint's `PrivValidator`.
+pub trait PrivValidator {
+    /// Public key of the validator
+    fn pub_key(&self) -> PublicKey;
+
+    /// Sign the given vote for the given chain.
+    fn sign_vote(&mut self, chain_id: &chain::Id, vote: &mut Vote) -> Result<(), Error>;
+
+    /// Sign the given proposal for the given chain.
+    fn sign_proposal(&mut self, chain_id: &chain::Id, proposal: &mut Proposal)
+        -> Result<(), Error>;
+}
+
+impl PrivValidator for FilePrivValidator {
+    fn pub_key(&self) -> PublicKey {
+        FilePrivValidator::pub_key(self)
+    }
+
+    fn sign_vote(&mut self, chain_id: &chain::Id, vote: &mut Vote) -> Result<(), Error> {
+        FilePrivValidator::sign_vote(self, chain_id, vote)
+    }
+
+    fn sign_proposal(
+        &mut self,
+        chain_id: &chain::Id,
+        proposal: &mut Proposal,
+    ) -> Result<(), Error> {
+        FilePrivValidator::sign_proposal(self, chain_id, proposal)
+    }
+}
+
+/// Server side of the remote signer protocol, answering the requests of a
+/// validator node over the given connection with the given validator.
+///
+/// Requests for any other chain than the one the server was created for
+/// are answered with an error.
+pub struct SignerServer<V, IoHandler> {
+    validator: V,
+    chain_id: chain::Id,
+    io: IoHandler,
+}
+
+impl<V, IoHandler> SignerServer<V, IoHandler>
+where
+    V: PrivValidator,
+    IoHandler: Read + Write,
+{
+    /// Create a server signing for the given chain with the given
+    /// validator, over the given connection.
+    pub fn new(validator: V, chain_id: chain::Id, io: IoHandler) -> Self {
+        SignerServer {
+            validator,
+            chain_id,
+            io,
+        }
+    }
+
+    /// Answer requests until the connection fails or is closed.
+    pub fn run(&mut self) -> Result<(), Error> {
+        loop {
+            self.handle_request()?;
+        }
+    }
+
+    /// Read a single request and answer it.
+    pub fn handle_request(&mut self) -> Result<(), Error> {
+        let response = match Request::read_from(&mut self.io)? {
+            Request::PubKey(request) => {
+                Response::PubKey(match self.check_chain_id(&request.chain_id) {
+                    Ok(()) => PubKeyResponse {
+                        pub_key: Some(self.validator.pub_key()),
+                        error: None,
+                    },
+                    Err(error) => PubKeyResponse {
+                        pub_key: None,
+                        error: Some(error),
+                    },
+                })
+            }
+            Request::SignVote(mut request) => Response::SignedVote(
+                match self.check_chain_id(&request.chain_id).and_then(|_| {
+                    self.validator
+                        .sign_vote(&request.chain_id, &mut request.vote)
+                        .map_err(signer_error)
+                }) {
+                    Ok(()) => SignedVoteResponse {
+                        vote: Some(request.vote),
+                        error: None,
+                    },
+                    Err(error) => SignedVoteResponse {
+                        vote: None,
+                        error: Some(error),
+                    },
+                },
+            ),
+            Request::SignProposal(mut request) => Response::SignedProposal(
+                match self.check_chain_id(&request.chain_id).and_then(|_| {
+                    self.validator
+                        .sign_proposal(&request.chain_id, &mut request.proposal)
+                        .map_err(signer_error)
+                }) {
+                    Ok(()) => SignedProposalResponse {
+                        proposal: Some(request.proposal),
+                        error: None,
+                    },
+                    Err(error) => SignedProposalResponse {
+                        proposal: None,
+                        error: Some(error),
+                    },
+                },
+            ),
+            Request::Ping => Response::Ping,
+        };
+        response.write_to(&mut self.io)
+    }
+
+    /// The validator, returned once the server is no longer needed.
+    pub fn into_validator(self) -> V {
+        self.validator
+    }
+
+    fn check_chain_id(&self, chain_id: &chain::Id) -> Result<(), RemoteSignerError> {
+        if chain_id == &self.chain_id {
+            Ok(())
+        } else {
+            Err(signer_error(
+                Kind::RemoteSigner
+                    .context(format!("want chain ID {}, got {}", self.chain_id, chain_id))
+                    .into(),
+            ))
+        }
+    }
+}
+
+fn signer_error(error: Error) -> RemoteSignerError {
+    RemoteSignerError {
+        code: 0,
+        description: error.to_string(),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::privval::{LastSignState, PrivValidatorKey, SignerClient};
+    use crate::test::signing_key;
+    use crate::{account, block, private_key::PrivateKey, vote, Time};
+    use std::os::unix::net::UnixStream;
+    use std::thread;
+
+    #[test]
+    fn remote_signing() {
+        let keypair = signing_key(4);
+        let key = PrivValidatorKey {
+            address: account::Id::from(keypair.public),
+            pub_key: keypair.public.into(),
+            priv_key: PrivateKey::Ed25519(keypair),
+        };
+        let public_key = key.pub_key;
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("priv_validator_state.json");
+        let validator = FilePrivValidator::new(key, LastSignState::default(), &state_file);
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+
+        let (client_io, server_io) = UnixStream::pair().unwrap();
+        let server_chain_id = chain_id.clone();
+        let server = thread::spawn(move || {
+            SignerServer::new(validator, server_chain_id, server_io)
+                .run()
+                .unwrap_err()
+        });
+
+        let mut client = SignerClient::new(client_io, chain_id.clone());
+        client.ping().unwrap();
+        let pub_key = client.pub_key().unwrap();
+        assert_eq!(public_key, pub_key);
+
+        let mut vote = Vote {
+            vote_type: vote::Type::Prevote,
+            height: block::Height::from(3_u32),
+            timestamp: Some(Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap()),
+            ..Vote::default()
+        };
+        client.sign_vote(&mut vote).unwrap();
+        vote.verify(chain_id, &pub_key).unwrap();
+
+        // Signing an earlier vote is refused by the signer.
+        let mut earlier = vote;
+        earlier.height = block::Height::from(2_u32);
+        assert!(client.sign_vote(&mut earlier).is_err());
+
+        // As is signing for another chain.
+        let io = client.into_inner();
+        let mut client = SignerClient::new(io, "other-chain".parse().unwrap());
+        assert!(client.pub_key().is_err());
+
+        drop(client);
+        server.join().unwrap();
+    }
+}
@@ -5,17 +5,19 @@ mod msg_type;
 mod sign_proposal;
 
 pub use self::canonical_proposal::CanonicalProposal;
+use crate::prelude::*;
 pub use msg_type::Type;
 pub use sign_proposal::{SignProposalRequest, SignedProposalResponse};
 
 use crate::block::{Height, Id as BlockId, Round};
 use crate::chain::Id as ChainId;
 use crate::consensus::State;
-use crate::Signature;
+use crate::signature::Signer;
 use crate::Time;
 use crate::{Error, Kind};
+use crate::{PublicKey, Signature};
 use bytes::BufMut;
-use std::convert::{TryFrom, TryInto};
+use core::convert::{TryFrom, TryInto};
 use tendermint_proto::types::Proposal as RawProposal;
 use tendermint_proto::{Error as ProtobufError, Protobuf};
 
@@ -79,6 +81,9 @@ impl From<Proposal> for RawProposal {
 
 impl Proposal {
     /// Create signable bytes from Proposal.
+    ///
+    /// These are the length-delimited protobuf encoding of the
+    /// [`CanonicalProposal`] for the given chain, as signed by validators.
     pub fn to_signable_bytes<B>(
         &self,
         chain_id: ChainId,
@@ -96,6 +101,30 @@ impl Proposal {
         CanonicalProposal::new(self.clone(), chain_id).encode_length_delimited_vec()
     }
 
+    /// Sign this proposal for the given chain, replacing its signature.
+    pub fn sign<S>(&mut self, chain_id: ChainId, signer: &S) -> Result<(), Error>
+    where
+        S: Signer<ed25519::Signature>,
+    {
+        let sign_bytes = self
+            .to_signable_vec(chain_id)
+            .map_err(|e| Kind::Protocol.context(e))?;
+        let signature = signer
+            .try_sign(&sign_bytes)
+            .map_err(|e| Kind::Crypto.context(e))?;
+        self.signature = signature.into();
+        Ok(())
+    }
+
+    /// Verify the signature of this proposal for the given chain with the
+    /// given public key.
+    pub fn verify(&self, chain_id: ChainId, pub_key: &PublicKey) -> Result<(), Error> {
+        let sign_bytes = self
+            .to_signable_vec(chain_id)
+            .map_err(|e| Kind::Protocol.context(e))?;
+        pub_key.verify(&sign_bytes, &self.signature)
+    }
+
     /// Consensus state from this proposal - This doesn't seem to be used anywhere.
     #[deprecated(
         since = "0.17.0",
@@ -118,11 +147,13 @@ mod tests {
     use crate::block::{Height, Round};
     use crate::chain::Id as ChainId;
     use crate::hash::{Algorithm, Hash};
+    use crate::prelude::*;
     use crate::proposal::SignProposalRequest;
     use crate::signature::{Ed25519Signature, ED25519_SIGNATURE_SIZE};
+    use crate::test::signing_key;
     use crate::{proposal::Type, Proposal, Signature};
     use chrono::{DateTime, Utc};
-    use std::str::FromStr;
+    use core::str::FromStr;
     use tendermint_proto::Protobuf;
 
     #[test]
@@ -140,7 +171,7 @@ mod tests {
                 )
                 .unwrap(),
                 part_set_header: Header::new(
-                    65535,
+                    1000,
                     Hash::from_hex_upper(
                         Algorithm::Sha256,
                         "0022446688AACCEE1133557799BBDDFF0022446688AACCEE1133557799BBDDFF",
@@ -186,7 +217,7 @@ mod tests {
                         Hash: block_hash,
                         PartSetHeader: types.PartSetHeader{
                             Hash:  part_hash,
-                            Total: 65535,
+                            Total: 1000,
                         },
                     },
                     Timestamp: stamp,
@@ -197,14 +228,14 @@ mod tests {
         */
 
         let want = vec![
-            136, 1, 8, 32, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 160, 91, 0, 0, 0, 0, 0, 0, 32, 255,
-            255, 255, 255, 255, 255, 255, 255, 255, 1, 42, 74, 10, 32, 222, 173, 190, 239, 222,
+            135, 1, 8, 32, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 160, 91, 0, 0, 0, 0, 0, 0, 32, 255,
+            255, 255, 255, 255, 255, 255, 255, 255, 1, 42, 73, 10, 32, 222, 173, 190, 239, 222,
             173, 190, 239, 186, 251, 175, 186, 251, 175, 186, 250, 222, 173, 190, 239, 222, 173,
-            190, 239, 186, 251, 175, 186, 251, 175, 186, 250, 18, 38, 8, 255, 255, 3, 18, 32, 0,
-            34, 68, 102, 136, 170, 204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 0, 34, 68, 102,
-            136, 170, 204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 50, 12, 8, 162, 216, 255, 211,
-            5, 16, 192, 242, 227, 236, 2, 58, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110,
-            95, 105, 100,
+            190, 239, 186, 251, 175, 186, 251, 175, 186, 250, 18, 37, 8, 232, 7, 18, 32, 0, 34, 68,
+            102, 136, 170, 204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 0, 34, 68, 102, 136, 170,
+            204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 50, 12, 8, 162, 216, 255, 211, 5, 16,
+            192, 242, 227, 236, 2, 58, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95, 105,
+            100,
         ];
 
         assert_eq!(got, want)
@@ -222,7 +253,7 @@ mod tests {
             block_id: Some(BlockId {
                 hash: Hash::from_hex_upper(Algorithm::Sha256, "").unwrap(),
                 part_set_header: Header::new(
-                    65535,
+                    1000,
                     Hash::from_hex_upper(
                         Algorithm::Sha256,
                         "0022446688AACCEE1133557799BBDDFF0022446688AACCEE1133557799BBDDFF",
@@ -268,7 +299,7 @@ mod tests {
                         Hash: block_hash,
                         PartSetHeader: types.PartSetHeader{
                             Hash:  part_hash,
-                            Total: 65535,
+                            Total: 1000,
                         },
                     },
                     Timestamp: stamp,
@@ -279,12 +310,12 @@ mod tests {
         */
 
         let want = vec![
-            102, 8, 32, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 160, 91, 0, 0, 0, 0, 0, 0, 32, 255, 255,
-            255, 255, 255, 255, 255, 255, 255, 1, 42, 40, 18, 38, 8, 255, 255, 3, 18, 32, 0, 34,
-            68, 102, 136, 170, 204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 0, 34, 68, 102, 136,
-            170, 204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 50, 12, 8, 162, 216, 255, 211, 5,
-            16, 192, 242, 227, 236, 2, 58, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95,
-            105, 100,
+            101, 8, 32, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 160, 91, 0, 0, 0, 0, 0, 0, 32, 255, 255,
+            255, 255, 255, 255, 255, 255, 255, 1, 42, 39, 18, 37, 8, 232, 7, 18, 32, 0, 34, 68,
+            102, 136, 170, 204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 0, 34, 68, 102, 136, 170,
+            204, 238, 17, 51, 85, 119, 153, 187, 221, 255, 50, 12, 8, 162, 216, 255, 211, 5, 16,
+            192, 242, 227, 236, 2, 58, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95, 105,
+            100,
         ];
 
         assert_eq!(got, want)
@@ -307,7 +338,7 @@ mod tests {
                 )
                 .unwrap(),
                 part_set_header: Header::new(
-                    65535,
+                    1000,
                     Hash::from_hex_upper(
                         Algorithm::Sha256,
                         "0022446688AACCEE1133557799BBDDFF0022446688AACCEE1133557799BBDDFF",
@@ -324,19 +355,41 @@ mod tests {
         };
 
         let data = vec![
-            10, 176, 1, 8, 32, 16, 185, 96, 24, 160, 183, 1, 32, 255, 255, 255, 255, 255, 255, 255,
-            255, 255, 1, 42, 74, 10, 32, 222, 173, 190, 239, 222, 173, 190, 239, 186, 251, 175,
+            10, 175, 1, 8, 32, 16, 185, 96, 24, 160, 183, 1, 32, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 1, 42, 73, 10, 32, 222, 173, 190, 239, 222, 173, 190, 239, 186, 251, 175,
             186, 251, 175, 186, 250, 222, 173, 190, 239, 222, 173, 190, 239, 186, 251, 175, 186,
-            251, 175, 186, 250, 18, 38, 8, 255, 255, 3, 18, 32, 0, 34, 68, 102, 136, 170, 204, 238,
-            17, 51, 85, 119, 153, 187, 221, 255, 0, 34, 68, 102, 136, 170, 204, 238, 17, 51, 85,
-            119, 153, 187, 221, 255, 50, 12, 8, 162, 216, 255, 211, 5, 16, 192, 242, 227, 236, 2,
-            58, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            251, 175, 186, 250, 18, 37, 8, 232, 7, 18, 32, 0, 34, 68, 102, 136, 170, 204, 238, 17,
+            51, 85, 119, 153, 187, 221, 255, 0, 34, 68, 102, 136, 170, 204, 238, 17, 51, 85, 119,
+            153, 187, 221, 255, 50, 12, 8, 162, 216, 255, 211, 5, 16, 192, 242, 227, 236, 2, 58,
+            64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 18, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95,
-            105, 100,
+            0, 0, 0, 0, 0, 0, 0, 18, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95, 105,
+            100,
         ];
 
         let have = SignProposalRequest::decode_vec(&data).unwrap();
         assert_eq!(have, want);
     }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = signing_key(1);
+        let pub_key = crate::PublicKey::from(signer.public);
+        let chain_id = ChainId::from_str("test_chain_id").unwrap();
+
+        let mut proposal = Proposal {
+            msg_type: Type::Proposal,
+            height: Height::from(10_u32),
+            round: Round::from(1_u16),
+            pol_round: None,
+            block_id: None,
+            timestamp: None,
+            signature: Signature::None,
+        };
+        proposal.sign(chain_id.clone(), &signer).unwrap();
+        proposal.verify(chain_id.clone(), &pub_key).unwrap();
+
+        proposal.pol_round = Some(Round::from(0_u16));
+        assert!(proposal.verify(chain_id, &pub_key).is_err());
+    }
 }
@@ -3,9 +3,10 @@
 use super::Type;
 use crate::block::{Height, Id as BlockId, Round};
 use crate::chain::Id as ChainId;
+use crate::prelude::*;
 use crate::Time;
 use crate::{Error, Kind};
-use std::convert::{TryFrom, TryInto};
+use core::convert::{TryFrom, TryInto};
 use tendermint_proto::types::CanonicalProposal as RawCanonicalProposal;
 use tendermint_proto::Protobuf;
 
@@ -98,9 +99,10 @@ impl CanonicalProposal {
 
 #[cfg(test)]
 mod tests {
+    use crate::prelude::*;
     use crate::proposal::canonical_proposal::CanonicalProposal;
     use crate::proposal::Type;
-    use std::convert::TryFrom;
+    use core::convert::TryFrom;
     use tendermint_proto::types::CanonicalBlockId as RawCanonicalBlockId;
     use tendermint_proto::types::CanonicalPartSetHeader as RawCanonicalPartSetHeader;
     use tendermint_proto::types::CanonicalProposal as RawCanonicalProposal;
@@ -1,7 +1,8 @@
+use crate::prelude::*;
 use crate::{Error, Kind};
+use core::convert::TryFrom;
 use serde::de::Error as _;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::convert::TryFrom;
 use tendermint_proto::Protobuf;
 
 /// Types of proposals
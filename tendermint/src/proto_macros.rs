@@ -0,0 +1,45 @@
+//! Macros to implement conversions for several versions of the protobuf
+//! definitions at once.
+
+/// Expand the given items in a module for each supported version of the
+/// Tendermint protobuf definitions.
+///
+/// In each module, `pb` refers to the protobuf definitions of that version,
+/// e.g. `tendermint_proto::v0_34`, so that the items can implement the
+/// conversions to and from `pb::types::Header` once for all the versions.
+/// The versions other than v0.34 are only enabled by their feature.
+macro_rules! tendermint_pb_modules {
+    {
+        $($contents:item)*
+    } => {
+        mod v0_34 {
+            use tendermint_proto::v0_34 as pb;
+            #[allow(unused_imports)]
+            use crate::prelude::*;
+            #[allow(unused_imports)]
+            use tendermint_proto::Protobuf;
+
+            $($contents)*
+        }
+        #[cfg(feature = "v0_37")]
+        mod v0_37 {
+            use tendermint_proto::v0_37 as pb;
+            #[allow(unused_imports)]
+            use crate::prelude::*;
+            #[allow(unused_imports)]
+            use tendermint_proto::Protobuf;
+
+            $($contents)*
+        }
+        #[cfg(feature = "v0_38")]
+        mod v0_38 {
+            use tendermint_proto::v0_38 as pb;
+            #[allow(unused_imports)]
+            use crate::prelude::*;
+            #[allow(unused_imports)]
+            use tendermint_proto::Protobuf;
+
+            $($contents)*
+        }
+    };
+}
@@ -1,27 +1,49 @@
 //! Public keys used in Tendermint networks
 
+use crate::prelude::*;
 pub use ed25519_dalek::PublicKey as Ed25519;
 #[cfg(feature = "secp256k1")]
 pub use k256::EncodedPoint as Secp256k1;
+#[cfg(feature = "sr25519")]
+pub use schnorrkel::PublicKey as Sr25519;
 
+#[cfg(feature = "bls12381")]
+mod bls12381;
 mod pub_key_request;
 mod pub_key_response;
+#[cfg(feature = "bls12381")]
+pub use bls12381::Bls12381;
 pub use pub_key_request::PubKeyRequest;
 pub use pub_key_response::PubKeyResponse;
 
 use crate::{
+    account,
     error::{self, Error},
     signature::Signature,
 };
-use anomaly::{fail, format_err};
+use core::convert::TryFrom;
+use core::{cmp::Ordering, fmt, ops::Deref, str::FromStr};
 use serde::{de, ser, Deserialize, Serialize};
 use signature::Verifier as _;
-use std::convert::TryFrom;
-use std::{cmp::Ordering, fmt, ops::Deref, str::FromStr};
+use subtle::{self, ConstantTimeEq};
 use subtle_encoding::{base64, bech32, hex};
-use tendermint_proto::crypto::public_key::Sum;
-use tendermint_proto::crypto::PublicKey as RawPublicKey;
-use tendermint_proto::Protobuf;
+
+/// Length of the Amino prefix of an encoded key: the 4-byte type prefix,
+/// followed by the length of the key
+const AMINO_PREFIX_LENGTH: usize = 5;
+
+const AMINO_PREFIX_ED25519: [u8; AMINO_PREFIX_LENGTH] = [0x16, 0x24, 0xDE, 0x64, 0x20];
+#[cfg(feature = "secp256k1")]
+const AMINO_PREFIX_SECP256K1: [u8; AMINO_PREFIX_LENGTH] = [0xEB, 0x5A, 0xE9, 0x87, 0x21];
+#[cfg(feature = "sr25519")]
+const AMINO_PREFIX_SR25519: [u8; AMINO_PREFIX_LENGTH] = [0x0D, 0xFB, 0x10, 0x05, 0x20];
+#[cfg(feature = "bls12381")]
+const AMINO_PREFIX_BLS12381: [u8; AMINO_PREFIX_LENGTH] = [0x6C, 0x33, 0x80, 0x95, 0x30];
+
+/// Size of a compressed secp256k1 public key, the only encoding of these keys
+/// used by Tendermint
+#[cfg(feature = "secp256k1")]
+const SECP256K1_PUBLIC_KEY_SIZE: usize = 33;
 
 // Note:On the golang side this is generic in the sense that it could everything that implements
 // github.com/tendermint/tendermint/crypto.PubKey
@@ -34,7 +56,9 @@ use tendermint_proto::Protobuf;
 //          All changes to the serialization should check both the JSON and protobuf conversions.
 // Todo: Merge JSON serialization with #[serde(try_from = "RawPublicKey", into = "RawPublicKey)]
 /// Public keys allowed in Tendermint protocols
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Keys are compared in constant time.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 #[non_exhaustive]
 #[serde(tag = "type", content = "value")] // JSON custom serialization for priv_validator_key.json
 pub enum PublicKey {
@@ -55,55 +79,99 @@ pub enum PublicKey {
         deserialize_with = "deserialize_secp256k1_base64"
     )]
     Secp256k1(Secp256k1),
-}
 
-impl Protobuf<RawPublicKey> for PublicKey {}
+    /// Sr25519 keys
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    #[serde(
+        rename = "tendermint/PubKeySr25519",
+        serialize_with = "serialize_sr25519_base64",
+        deserialize_with = "deserialize_sr25519_base64"
+    )]
+    Sr25519(Sr25519),
 
-impl TryFrom<RawPublicKey> for PublicKey {
-    type Error = Error;
+    /// BLS12-381 keys
+    #[cfg(feature = "bls12381")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bls12381")))]
+    #[serde(
+        rename = "cometbft/PubKeyBls12_381",
+        serialize_with = "serialize_bls12381_base64",
+        deserialize_with = "deserialize_bls12381_base64"
+    )]
+    Bls12381(Bls12381),
+}
 
-    fn try_from(value: RawPublicKey) -> Result<Self, Self::Error> {
-        let sum = &value
-            .sum
-            .ok_or_else(|| format_err!(error::Kind::InvalidKey, "empty sum"))?;
-        if let Sum::Ed25519(b) = sum {
-            return Self::from_raw_ed25519(b).ok_or_else(|| {
-                format_err!(error::Kind::InvalidKey, "malformed ed25519 key").into()
-            });
-        }
-        #[cfg(feature = "secp256k1")]
-        if let Sum::Secp256k1(b) = sum {
-            return Self::from_raw_secp256k1(b)
-                .ok_or_else(|| format_err!(error::Kind::InvalidKey, "malformed key").into());
+tendermint_pb_modules! {
+    use super::PublicKey;
+    use crate::error::{self, Error};
+    use core::convert::TryFrom;
+    use pb::crypto::{public_key::Sum, PublicKey as RawPublicKey};
+
+    impl Protobuf<RawPublicKey> for PublicKey {}
+
+    impl TryFrom<RawPublicKey> for PublicKey {
+        type Error = Error;
+
+        fn try_from(value: RawPublicKey) -> Result<Self, Self::Error> {
+            let sum = &value
+                .sum
+                .ok_or_else(|| format_err!(error::Kind::InvalidKey, "empty sum"))?;
+            if let Sum::Ed25519(b) = sum {
+                return Self::from_raw_ed25519(b).ok_or_else(|| {
+                    format_err!(error::Kind::InvalidKey, "malformed ed25519 key").into()
+                });
+            }
+            #[cfg(feature = "secp256k1")]
+            if let Sum::Secp256k1(b) = sum {
+                return Self::from_raw_secp256k1(b)
+                    .ok_or_else(|| format_err!(error::Kind::InvalidKey, "malformed key").into());
+            }
+            #[cfg(feature = "sr25519")]
+            if let Sum::Sr25519(b) = sum {
+                return Self::from_raw_sr25519(b).ok_or_else(|| {
+                    format_err!(error::Kind::InvalidKey, "malformed sr25519 key").into()
+                });
+            }
+            #[cfg(feature = "bls12381")]
+            if let Sum::Bls12381(b) = sum {
+                return Self::from_raw_bls12381(b).ok_or_else(|| {
+                    format_err!(error::Kind::InvalidKey, "malformed BLS12-381 key").into()
+                });
+            }
+            Err(format_err!(error::Kind::InvalidKey, "unsupported key type").into())
         }
-        Err(format_err!(error::Kind::InvalidKey, "not an ed25519 key").into())
     }
-}
 
-impl From<PublicKey> for RawPublicKey {
-    fn from(value: PublicKey) -> Self {
-        match value {
-            PublicKey::Ed25519(ref pk) => RawPublicKey {
-                sum: Some(tendermint_proto::crypto::public_key::Sum::Ed25519(
-                    pk.as_bytes().to_vec(),
-                )),
-            },
-            #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(ref pk) => RawPublicKey {
-                sum: Some(tendermint_proto::crypto::public_key::Sum::Secp256k1(
-                    pk.as_bytes().to_vec(),
-                )),
-            },
+    impl From<PublicKey> for RawPublicKey {
+        fn from(value: PublicKey) -> Self {
+            match value {
+                PublicKey::Ed25519(ref pk) => RawPublicKey {
+                    sum: Some(Sum::Ed25519(pk.as_bytes().to_vec())),
+                },
+                #[cfg(feature = "secp256k1")]
+                PublicKey::Secp256k1(ref pk) => RawPublicKey {
+                    sum: Some(Sum::Secp256k1(pk.as_bytes().to_vec())),
+                },
+                #[cfg(feature = "sr25519")]
+                PublicKey::Sr25519(ref pk) => RawPublicKey {
+                    sum: Some(Sum::Sr25519(pk.to_bytes().to_vec())),
+                },
+                #[cfg(feature = "bls12381")]
+                PublicKey::Bls12381(ref pk) => RawPublicKey {
+                    sum: Some(Sum::Bls12381(pk.as_bytes().to_vec())),
+                },
+            }
         }
     }
 }
 
 impl PublicKey {
-    /// From raw secp256k1 public key bytes
+    /// From raw secp256k1 public key bytes, which must be a compressed point
+    /// of the curve
     #[cfg(feature = "secp256k1")]
     #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
     pub fn from_raw_secp256k1(bytes: &[u8]) -> Option<PublicKey> {
-        Secp256k1::from_bytes(bytes).ok().map(PublicKey::Secp256k1)
+        secp256k1_from_bytes(bytes).map(PublicKey::Secp256k1)
     }
 
     /// From raw Ed25519 public key bytes
@@ -111,6 +179,33 @@ impl PublicKey {
         Ed25519::from_bytes(bytes).map(Into::into).ok()
     }
 
+    /// From raw sr25519 public key bytes
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    pub fn from_raw_sr25519(bytes: &[u8]) -> Option<PublicKey> {
+        Sr25519::from_bytes(bytes).ok().map(PublicKey::Sr25519)
+    }
+
+    /// From raw (compressed) BLS12-381 public key bytes
+    #[cfg(feature = "bls12381")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bls12381")))]
+    pub fn from_raw_bls12381(bytes: &[u8]) -> Option<PublicKey> {
+        Bls12381::from_bytes(bytes).ok().map(PublicKey::Bls12381)
+    }
+
+    /// Get the algorithm of this public key
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            PublicKey::Ed25519(_) => Algorithm::Ed25519,
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(_) => Algorithm::Secp256k1,
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Algorithm::Sr25519,
+            #[cfg(feature = "bls12381")]
+            PublicKey::Bls12381(_) => Algorithm::Bls12381,
+        }
+    }
+
     /// Get Ed25519 public key
     pub fn ed25519(self) -> Option<Ed25519> {
         #[allow(unreachable_patterns)]
@@ -130,6 +225,26 @@ impl PublicKey {
         }
     }
 
+    /// Get Sr25519 public key
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    pub fn sr25519(self) -> Option<Sr25519> {
+        match self {
+            PublicKey::Sr25519(pk) => Some(pk),
+            _ => None,
+        }
+    }
+
+    /// Get BLS12-381 public key
+    #[cfg(feature = "bls12381")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bls12381")))]
+    pub fn bls12381(self) -> Option<Bls12381> {
+        match self {
+            PublicKey::Bls12381(pk) => Some(pk),
+            _ => None,
+        }
+    }
+
     /// Verify the given [`Signature`] using this public key
     pub fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), Error> {
         match self {
@@ -144,21 +259,74 @@ impl PublicKey {
                 Signature::None => {
                     Err(format_err!(error::Kind::SignatureInvalid, "missing signature").into())
                 }
+                #[allow(unreachable_patterns)]
+                _ => Err(format_err!(
+                    error::Kind::SignatureInvalid,
+                    "expected an Ed25519 signature"
+                )
+                .into()),
             },
             #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(_) => fail!(
-                error::Kind::InvalidKey,
-                "unsupported signature algorithm (ECDSA/secp256k1)"
-            ),
+            PublicKey::Secp256k1(pk) => {
+                let verifying_key = k256::ecdsa::VerifyingKey::from_encoded_point(pk)
+                    .map_err(|_| format_err!(error::Kind::InvalidKey, "malformed secp256k1 key"))?;
+                // Tendermint encodes secp256k1 signatures as 64-byte `r || s`
+                // values, which are decoded as Ed25519 signatures since both
+                // have the same length.
+                let sig = k256::ecdsa::Signature::try_from(signature.as_bytes()).map_err(|_| {
+                    format_err!(
+                        error::Kind::SignatureInvalid,
+                        "malformed secp256k1 signature"
+                    )
+                })?;
+                // High-S signatures are rejected, as in Tendermint.
+                verifying_key.verify(msg, &sig).map_err(|_| {
+                    format_err!(
+                        error::Kind::SignatureInvalid,
+                        "Secp256k1 signature verification failed"
+                    )
+                    .into()
+                })
+            }
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => {
+                // Sr25519 signatures are 64 bytes long too, so they are also
+                // decoded as Ed25519 signatures.
+                let sig =
+                    schnorrkel::Signature::from_bytes(signature.as_bytes()).map_err(|_| {
+                        format_err!(error::Kind::SignatureInvalid, "malformed sr25519 signature")
+                    })?;
+                // Tendermint signs with an empty signing context
+                pk.verify_simple(b"", msg, &sig).map_err(|_| {
+                    format_err!(
+                        error::Kind::SignatureInvalid,
+                        "Sr25519 signature verification failed"
+                    )
+                    .into()
+                })
+            }
+            #[cfg(feature = "bls12381")]
+            PublicKey::Bls12381(pk) => pk.verify(msg, signature.as_bytes()),
         }
     }
 
+    /// The account ID (i.e. address) derived from this key: the first 20
+    /// bytes of `SHA256(pubkey)` for Ed25519, sr25519 and BLS12-381 keys,
+    /// and `RIPEMD160(SHA256(pubkey))` for secp256k1 keys (as used by Cosmos).
+    pub fn account_id(&self) -> account::Id {
+        account::Id::from(*self)
+    }
+
     /// View this key as a byte slice
     pub fn as_bytes(&self) -> &[u8] {
         match self {
             PublicKey::Ed25519(pk) => pk.as_bytes(),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(pk) => pk.as_bytes(),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => pk.as_compressed().as_bytes(),
+            #[cfg(feature = "bls12381")]
+            PublicKey::Bls12381(pk) => pk.as_bytes(),
         }
     }
 
@@ -167,22 +335,62 @@ impl PublicKey {
         self.as_bytes().to_vec()
     }
 
-    /// Serialize this key as Bech32 with the given human readable prefix
+    /// Serialize this key as Bech32 with the given human readable prefix,
+    /// e.g. `cosmosvalconspub` for a consensus key (see
+    /// [`bech32::Prefixes`](crate::bech32::Prefixes))
     pub fn to_bech32(self, hrp: &str) -> String {
-        let backward_compatible_amino_prefixed_pubkey = match self {
-            PublicKey::Ed25519(ref pk) => {
-                let mut key_bytes = vec![0x16, 0x24, 0xDE, 0x64, 0x20];
-                key_bytes.extend(pk.as_bytes());
-                key_bytes
-            }
+        let mut backward_compatible_amino_prefixed_pubkey = self.amino_prefix().to_vec();
+        backward_compatible_amino_prefixed_pubkey.extend(self.as_bytes());
+        bech32::encode(hrp, backward_compatible_amino_prefixed_pubkey)
+    }
+
+    /// Parse a key serialized as Bech32, checking that it has the given
+    /// human readable prefix
+    pub fn from_bech32(s: &str, hrp: &str) -> Result<Self, Error> {
+        let (actual_hrp, bytes) = bech32::decode(s).map_err(|e| error::Kind::Parse.context(e))?;
+        if actual_hrp != hrp {
+            fail!(
+                error::Kind::Parse,
+                "expected bech32 prefix {:?}, got {:?}",
+                hrp,
+                actual_hrp
+            );
+        }
+        if bytes.len() < AMINO_PREFIX_LENGTH {
+            fail!(error::Kind::InvalidKey, "bech32 public key is too short");
+        }
+
+        let (amino_prefix, key_bytes) = bytes.split_at(AMINO_PREFIX_LENGTH);
+        let mut prefix = [0u8; AMINO_PREFIX_LENGTH];
+        prefix.copy_from_slice(amino_prefix);
+        let public_key = match prefix {
+            AMINO_PREFIX_ED25519 => Self::from_raw_ed25519(key_bytes),
             #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(ref pk) => {
-                let mut key_bytes = vec![0xEB, 0x5A, 0xE9, 0x87, 0x21];
-                key_bytes.extend(pk.as_bytes());
-                key_bytes
-            }
+            AMINO_PREFIX_SECP256K1 => Self::from_raw_secp256k1(key_bytes),
+            #[cfg(feature = "sr25519")]
+            AMINO_PREFIX_SR25519 => Self::from_raw_sr25519(key_bytes),
+            #[cfg(feature = "bls12381")]
+            AMINO_PREFIX_BLS12381 => Self::from_raw_bls12381(key_bytes),
+            _ => fail!(
+                error::Kind::InvalidKey,
+                "unsupported bech32 public key type"
+            ),
         };
-        bech32::encode(hrp, backward_compatible_amino_prefixed_pubkey)
+        public_key.ok_or_else(|| format_err!(error::Kind::InvalidKey, "malformed key").into())
+    }
+
+    /// The prefix of this key's (legacy) Amino encoding, which is kept in its
+    /// Bech32 encoding for backward compatibility
+    fn amino_prefix(&self) -> [u8; AMINO_PREFIX_LENGTH] {
+        match self {
+            PublicKey::Ed25519(_) => AMINO_PREFIX_ED25519,
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(_) => AMINO_PREFIX_SECP256K1,
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => AMINO_PREFIX_SR25519,
+            #[cfg(feature = "bls12381")]
+            PublicKey::Bls12381(_) => AMINO_PREFIX_BLS12381,
+        }
     }
 
     /// Serialize this key as hexadecimal
@@ -204,27 +412,46 @@ impl From<Secp256k1> for PublicKey {
     }
 }
 
+#[cfg(feature = "sr25519")]
+impl From<Sr25519> for PublicKey {
+    fn from(pk: Sr25519) -> PublicKey {
+        PublicKey::Sr25519(pk)
+    }
+}
+
+#[cfg(feature = "bls12381")]
+impl From<Bls12381> for PublicKey {
+    fn from(pk: Bls12381) -> PublicKey {
+        PublicKey::Bls12381(pk)
+    }
+}
+
+impl ConstantTimeEq for PublicKey {
+    #[inline]
+    fn ct_eq(&self, other: &PublicKey) -> subtle::Choice {
+        (self.algorithm() as u8).ct_eq(&(other.algorithm() as u8))
+            & self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &PublicKey) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for PublicKey {}
+
 impl PartialOrd for PublicKey {
     fn partial_cmp(&self, other: &PublicKey) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
+/// Keys are ordered by algorithm first, then by their bytes.
 impl Ord for PublicKey {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self {
-            PublicKey::Ed25519(a) => match other {
-                PublicKey::Ed25519(b) => a.as_bytes().cmp(b.as_bytes()),
-                #[cfg(feature = "secp256k1")]
-                PublicKey::Secp256k1(_) => Ordering::Less,
-            },
-            #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(a) => match other {
-                PublicKey::Ed25519(_) => Ordering::Greater,
-                #[cfg(feature = "secp256k1")]
-                PublicKey::Secp256k1(b) => a.as_bytes().cmp(b.as_bytes()),
-            },
-        }
+        (self.algorithm() as u8, self.as_bytes()).cmp(&(other.algorithm() as u8, other.as_bytes()))
     }
 }
 
@@ -245,6 +472,13 @@ impl TendermintKey {
             PublicKey::Ed25519(_) => Ok(TendermintKey::AccountKey(public_key)),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "bls12381")]
+            PublicKey::Bls12381(_) => fail!(
+                error::Kind::InvalidKey,
+                "BLS12-381 keys can only be used as consensus keys"
+            ),
         }
     }
 
@@ -252,10 +486,14 @@ impl TendermintKey {
     pub fn new_consensus_key(public_key: PublicKey) -> Result<TendermintKey, Error> {
         #[allow(unreachable_patterns)]
         match public_key {
-            PublicKey::Ed25519(_) => Ok(TendermintKey::AccountKey(public_key)),
+            PublicKey::Ed25519(_) => Ok(TendermintKey::ConsensusKey(public_key)),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Ok(TendermintKey::ConsensusKey(public_key)),
+            #[cfg(feature = "bls12381")]
+            PublicKey::Bls12381(_) => Ok(TendermintKey::ConsensusKey(public_key)),
             _ => fail!(
                 error::Kind::InvalidKey,
-                "only ed25519 consensus keys are supported"
+                "secp256k1 keys can't be used as consensus keys"
             ),
         }
     }
@@ -286,6 +524,12 @@ pub enum Algorithm {
 
     /// secp256k1
     Secp256k1,
+
+    /// sr25519
+    Sr25519,
+
+    /// BLS12-381
+    Bls12381,
 }
 
 impl Algorithm {
@@ -294,6 +538,8 @@ impl Algorithm {
         match self {
             Algorithm::Ed25519 => "ed25519",
             Algorithm::Secp256k1 => "secp256k1",
+            Algorithm::Sr25519 => "sr25519",
+            Algorithm::Bls12381 => "bls12_381",
         }
     }
 }
@@ -311,6 +557,8 @@ impl FromStr for Algorithm {
         match s {
             "ed25519" => Ok(Algorithm::Ed25519),
             "secp256k1" => Ok(Algorithm::Secp256k1),
+            "sr25519" => Ok(Algorithm::Sr25519),
+            "bls12_381" => Ok(Algorithm::Bls12381),
             _ => Err(error::Kind::Parse.into()),
         }
     }
@@ -361,6 +609,28 @@ where
     Ed25519::from_bytes(&bytes).map_err(D::Error::custom)
 }
 
+/// Serialize the bytes of an Sr25519 public key as Base64. Used for serializing JSON
+#[cfg(feature = "sr25519")]
+fn serialize_sr25519_base64<S>(pk: &Sr25519, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    String::from_utf8(base64::encode(pk.to_bytes()))
+        .unwrap()
+        .serialize(serializer)
+}
+
+/// Serialize the bytes of a BLS12-381 public key as Base64. Used for serializing JSON
+#[cfg(feature = "bls12381")]
+fn serialize_bls12381_base64<S>(pk: &Bls12381, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    String::from_utf8(base64::encode(pk.as_bytes()))
+        .unwrap()
+        .serialize(serializer)
+}
+
 #[cfg(feature = "secp256k1")]
 fn deserialize_secp256k1_base64<'de, D>(deserializer: D) -> Result<Secp256k1, D::Error>
 where
@@ -369,12 +639,49 @@ where
     use de::Error;
     let encoded = String::deserialize(deserializer)?;
     let bytes = base64::decode(&encoded).map_err(D::Error::custom)?;
-    Secp256k1::from_bytes(&bytes).map_err(|_| D::Error::custom("invalid secp256k1 key"))
+    secp256k1_from_bytes(&bytes).ok_or_else(|| D::Error::custom("invalid secp256k1 key"))
+}
+
+/// Decode a secp256k1 public key, rejecting anything but a compressed point
+/// of the curve: uncompressed points and the identity are valid encodings of
+/// `Secp256k1`, but not of Tendermint keys.
+#[cfg(feature = "secp256k1")]
+fn secp256k1_from_bytes(bytes: &[u8]) -> Option<Secp256k1> {
+    if bytes.len() != SECP256K1_PUBLIC_KEY_SIZE || !matches!(bytes[0], 0x02 | 0x03) {
+        return None;
+    }
+    let pk = Secp256k1::from_bytes(bytes).ok()?;
+    k256::ecdsa::VerifyingKey::from_encoded_point(&pk).ok()?;
+    Some(pk)
+}
+
+#[cfg(feature = "sr25519")]
+fn deserialize_sr25519_base64<'de, D>(deserializer: D) -> Result<Sr25519, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    use de::Error;
+    let encoded = String::deserialize(deserializer)?;
+    let bytes = base64::decode(&encoded).map_err(D::Error::custom)?;
+    Sr25519::from_bytes(&bytes).map_err(|_| D::Error::custom("invalid sr25519 key"))
+}
+
+#[cfg(feature = "bls12381")]
+fn deserialize_bls12381_base64<'de, D>(deserializer: D) -> Result<Bls12381, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    use de::Error;
+    let encoded = String::deserialize(deserializer)?;
+    let bytes = base64::decode(&encoded).map_err(D::Error::custom)?;
+    Bls12381::from_bytes(&bytes).map_err(D::Error::custom)
 }
 
 #[cfg(test)]
 mod tests {
     use super::{PublicKey, TendermintKey};
+    use crate::bech32::Prefixes;
+    use crate::prelude::*;
     use crate::public_key::PubKeyResponse;
     use subtle_encoding::hex;
     use tendermint_proto::Protobuf;
@@ -409,6 +716,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_consensus_bech32_parsing() {
+        let prefixes = Prefixes::cosmos();
+        let bech32_key =
+            "cosmosvalconspub1zcjduepqfgjuveq2raetnjt4xwpffm63kmguxv2chdhvhf5lhslmtgeunh8qmf7exk";
+        let key = PublicKey::from_bech32(bech32_key, &prefixes.consensus_public_key).unwrap();
+        assert_eq!(key.to_hex(), EXAMPLE_CONSENSUS_KEY);
+        assert_eq!(
+            key.account_id().to_bech32(&prefixes.consensus_address),
+            "cosmosvalcons1uvr5swsgcw25ga9zvq8najxun9sn4c2zr5g5yu"
+        );
+
+        // Wrong human readable part
+        assert!(PublicKey::from_bech32(bech32_key, &prefixes.account_public_key).is_err());
+        // Bad checksum
+        assert!(PublicKey::from_bech32(
+            "cosmosvalconspub1zcjduepqfgjuveq2raetnjt4xwpffm63kmguxv2chdhvhf5lhslmtgeunh8qmf7exq",
+            &prefixes.consensus_public_key
+        )
+        .is_err());
+    }
+
     #[test]
     #[cfg(feature = "secp256k1")]
     fn test_account_serialization() {
@@ -424,6 +753,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn constant_time_equality() {
+        use subtle::ConstantTimeEq;
+
+        let bytes = hex::decode_upper(EXAMPLE_CONSENSUS_KEY).unwrap();
+        let key = PublicKey::from_raw_ed25519(&bytes).unwrap();
+        let other = PublicKey::from_raw_ed25519(&[
+            215, 90, 152, 1, 130, 177, 10, 183, 213, 75, 254, 211, 201, 100, 7, 58, 14, 225, 114,
+            243, 218, 166, 35, 37, 175, 2, 26, 104, 247, 7, 81, 26,
+        ])
+        .unwrap();
+
+        assert_eq!(key.ct_eq(&key).unwrap_u8(), 1);
+        assert_eq!(key.ct_eq(&other).unwrap_u8(), 0);
+        assert_eq!(key, PublicKey::from_raw_ed25519(&bytes).unwrap());
+        assert_ne!(key, other);
+
+        // Keys must have exactly the length of their algorithm
+        assert!(PublicKey::from_raw_ed25519(&bytes[..31]).is_none());
+        assert!(PublicKey::from_raw_ed25519(&[bytes.as_slice(), &[0]].concat()).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "secp256k1")]
+    fn secp256k1_strict_parsing() {
+        use k256::ecdsa::SigningKey;
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+        let verifying_key = SigningKey::from_bytes(&[7; 32]).unwrap().verify_key();
+        let verifying_key = k256::PublicKey::from(&verifying_key);
+        let compressed = verifying_key.to_encoded_point(true);
+        assert!(PublicKey::from_raw_secp256k1(compressed.as_bytes()).is_some());
+
+        // Uncompressed points and the identity aren't Tendermint keys
+        let uncompressed = verifying_key.to_encoded_point(false);
+        assert!(PublicKey::from_raw_secp256k1(uncompressed.as_bytes()).is_none());
+        assert!(PublicKey::from_raw_secp256k1(&[0]).is_none());
+
+        // Nor are compressed points with a bad prefix, or off the curve
+        let mut bytes = compressed.as_bytes().to_vec();
+        bytes[0] = 0x04;
+        assert!(PublicKey::from_raw_secp256k1(&bytes).is_none());
+        let mut bytes = [0xFF; 33];
+        bytes[0] = 0x02;
+        assert!(PublicKey::from_raw_secp256k1(&bytes).is_none());
+    }
+
     #[test]
     fn json_parsing() {
         let json_string = "{\"type\":\"tendermint/PubKeyEd25519\",\"value\":\"RblzMO4is5L1hZz6wo4kPbptzOyue6LTk4+lPhD1FRk=\"}";
@@ -488,4 +864,50 @@ mod tests {
         assert_eq!(got, encoded);
         assert_eq!(PubKeyResponse::decode_vec(&encoded).unwrap(), msg);
     }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn secp256k1_signature_verification() {
+        use crate::signature::Signature;
+        use k256::ecdsa::{signature::Signer, SigningKey};
+
+        let signing_key = SigningKey::from_bytes(&[7; 32]).unwrap();
+        let public_key = PublicKey::from(k256::EncodedPoint::from(&signing_key.verify_key()));
+        let msg = b"sign me";
+        let sig: k256::ecdsa::Signature = signing_key.sign(msg);
+        let sig = Signature::from(sig);
+
+        public_key.verify(msg, &sig).unwrap();
+        assert!(public_key.verify(b"not signed", &sig).is_err());
+        assert!(public_key.verify(msg, &Signature::None).is_err());
+        assert_eq!(20, public_key.account_id().as_bytes().len());
+    }
+
+    #[test]
+    #[cfg(feature = "sr25519")]
+    fn sr25519_json_and_proto() {
+        // The Ristretto basepoint
+        let json_string = "{\"type\":\"tendermint/PubKeySr25519\",\"value\":\"4vKuCmq8TnGohKlhxQBRX1jjC2qlgt2NtqZZReCNLXY=\"}";
+        let pubkey: PublicKey = serde_json::from_str(json_string).unwrap();
+        assert!(pubkey.sr25519().is_some());
+        assert_eq!(serde_json::to_string(&pubkey).unwrap(), json_string);
+
+        let encoded = pubkey.encode_vec().unwrap();
+        assert_eq!(PublicKey::decode_vec(&encoded).unwrap(), pubkey);
+    }
+
+    #[test]
+    #[cfg(feature = "bls12381")]
+    fn bls12381_json_and_proto() {
+        // The G1 generator
+        let json_string = "{\"type\":\"cometbft/PubKeyBls12_381\",\"value\":\"l/HTpzGX15QmlWOMT6msD8NojE+XdLkFoU46PxcbrFhsVeg/+Xoa7/s68ArbIsa7\"}";
+        let pubkey: PublicKey = serde_json::from_str(json_string).unwrap();
+        assert_eq!(pubkey.as_bytes().len(), 48);
+        assert_eq!(serde_json::to_string(&pubkey).unwrap(), json_string);
+
+        let encoded = pubkey.encode_vec().unwrap();
+        assert_eq!(PublicKey::decode_vec(&encoded).unwrap(), pubkey);
+        assert!(TendermintKey::new_account_key(pubkey).is_err());
+        assert!(TendermintKey::new_consensus_key(pubkey).is_ok());
+    }
 }
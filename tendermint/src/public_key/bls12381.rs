@@ -0,0 +1,119 @@
+//! BLS12-381 public keys, as used by CometBFT validators
+
+use crate::error::{self, Error};
+use blst::min_pk;
+use blst::BLST_ERROR;
+use sha2::{Digest, Sha256};
+
+/// Size of a compressed BLS12-381 public key (a G1 point), in bytes
+pub const PUBLIC_KEY_SIZE: usize = 48;
+
+/// Domain separation tag of the signature scheme: minimal-pubkey-size
+/// signatures in G2, with proof of possession, as in CometBFT
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Messages longer than this are signed by their SHA-256 digest instead
+const MAX_MSG_LEN: usize = 32;
+
+/// BLS12-381 public key, in its compressed form
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Bls12381([u8; PUBLIC_KEY_SIZE]);
+
+impl Bls12381 {
+    /// Decode a compressed public key, checking that it is a valid point of
+    /// the G1 subgroup
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != PUBLIC_KEY_SIZE {
+            fail!(
+                error::Kind::InvalidKey,
+                "BLS12-381 public keys must be {} bytes long, got {}",
+                PUBLIC_KEY_SIZE,
+                bytes.len()
+            );
+        }
+        min_pk::PublicKey::key_validate(bytes).map_err(|e| {
+            format_err!(
+                error::Kind::InvalidKey,
+                "malformed BLS12-381 public key: {:?}",
+                e
+            )
+        })?;
+
+        let mut key = [0u8; PUBLIC_KEY_SIZE];
+        key.copy_from_slice(bytes);
+        Ok(Bls12381(key))
+    }
+
+    /// Borrow the compressed public key
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Verify a (compressed) signature of the given message
+    pub fn verify(&self, msg: &[u8], signature: &[u8]) -> Result<(), Error> {
+        let pk = min_pk::PublicKey::uncompress(&self.0).map_err(|e| {
+            format_err!(
+                error::Kind::InvalidKey,
+                "malformed BLS12-381 public key: {:?}",
+                e
+            )
+        })?;
+        let sig = min_pk::Signature::sig_validate(signature, true).map_err(|e| {
+            format_err!(
+                error::Kind::SignatureInvalid,
+                "malformed BLS12-381 signature: {:?}",
+                e
+            )
+        })?;
+
+        let digest;
+        let msg = if msg.len() > MAX_MSG_LEN {
+            digest = Sha256::digest(msg);
+            &digest[..]
+        } else {
+            msg
+        };
+
+        match sig.verify(true, msg, DST, &[], &pk, false) {
+            BLST_ERROR::BLST_SUCCESS => Ok(()),
+            e => Err(format_err!(
+                error::Kind::SignatureInvalid,
+                "BLS12-381 signature verification failed: {:?}",
+                e
+            )
+            .into()),
+        }
+    }
+}
+
+impl AsRef<[u8]> for Bls12381 {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_signatures() {
+        let sk = min_pk::SecretKey::key_gen(&[7; 32], &[]).unwrap();
+        let pk = Bls12381::from_bytes(&sk.sk_to_pk().compress()).unwrap();
+
+        let msg = [42; MAX_MSG_LEN];
+        let sig = sk.sign(&msg, DST, &[]).compress();
+        assert!(pk.verify(&msg, &sig).is_ok());
+        assert!(pk.verify(&[43; MAX_MSG_LEN], &sig).is_err());
+
+        let long_msg = [42; 100];
+        let sig = sk.sign(&Sha256::digest(&long_msg), DST, &[]).compress();
+        assert!(pk.verify(&long_msg, &sig).is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert!(Bls12381::from_bytes(&[0; PUBLIC_KEY_SIZE]).is_err());
+        assert!(Bls12381::from_bytes(&[0; 96]).is_err());
+    }
+}
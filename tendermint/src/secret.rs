@@ -0,0 +1,112 @@
+//! Wrapper for secret values, such as private key material
+
+use crate::prelude::*;
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+/// A secret value which is zeroized when dropped.
+///
+/// The value is kept on the heap, so that moving a `Secret` around doesn't
+/// leave copies of it behind on the stack. Its [`Debug`](fmt::Debug) output
+/// is redacted, and it deliberately doesn't implement [`Serialize`]:
+/// serializing the secret has to be opted into with [`serialize_exposed`].
+pub struct Secret<T: Zeroize>(Box<T>);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wrap the given secret value
+    pub fn new(value: T) -> Self {
+        Secret(Box::new(value))
+    }
+
+    /// Borrow the secret value.
+    ///
+    /// Care should be taken not to copy it anywhere it won't be zeroized.
+    pub fn expose_secret(&self) -> &T {
+        &self.0
+    }
+
+    /// Mutably borrow the secret value
+    pub fn expose_secret_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret::new(value)
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Secret::new(self.expose_secret().clone())
+    }
+}
+
+impl<T: Zeroize + Default> Default for Secret<T> {
+    fn default() -> Self {
+        Secret::new(T::default())
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret([REDACTED {}])", core::any::type_name::<T>())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Secret<T>
+where
+    T: Zeroize + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Secret::new)
+    }
+}
+
+/// Serialize a [`Secret`], exposing its value.
+///
+/// For use with `#[serde(serialize_with = "...")]` on fields which are meant
+/// to be persisted, such as the private key in a key file.
+pub fn serialize_exposed<T, S>(secret: &Secret<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Zeroize + Serialize,
+    S: Serializer,
+{
+    secret.expose_secret().serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Exposed {
+        #[serde(serialize_with = "serialize_exposed")]
+        secret: Secret<String>,
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let secret = Secret::new("hunter2".to_string());
+        assert!(!format!("{:?}", secret).contains("hunter2"));
+        assert_eq!(secret.expose_secret(), "hunter2");
+    }
+
+    #[test]
+    fn serialization_is_opt_in() {
+        let secret: Secret<String> = serde_json::from_str("\"hunter2\"").unwrap();
+        let exposed = serde_json::to_string(&Exposed { secret }).unwrap();
+        assert_eq!(exposed, r#"{"secret":"hunter2"}"#);
+    }
+}
@@ -7,6 +7,9 @@
 //! risk.
 pub use tendermint_proto::serializers::*;
 
+#[cfg(feature = "compat-amino")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-amino")))]
+pub mod amino;
 pub mod apphash;
 pub mod hash;
 pub mod option_hash;
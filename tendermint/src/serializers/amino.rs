@@ -0,0 +1,87 @@
+//! Amino JSON encodings of Tendermint 0.33
+//!
+//! Tendermint 0.33 nodes encode their responses with (go-)amino instead of
+//! protobuf-derived JSON: public keys, votes and evidence are wrapped
+//! differently, and some integers are encoded as strings. These modules
+//! provide the legacy encodings of those types, to be used with
+//! `#[serde(with = "...")]` when talking to such nodes.
+
+pub mod evidence;
+pub mod public_key;
+pub mod vote;
+
+#[cfg(test)]
+mod tests {
+    use crate::evidence::Evidence;
+    use crate::prelude::*;
+    use crate::{PublicKey, Vote};
+    use serde::{Deserialize, Serialize};
+
+    const PUB_KEY: &str = r#"{"type":"tendermint/PubKeyEd25519","value":"SiXGZAofcrnJdTOClO9RttHDMVi7bsumn7w/taM8nc4="}"#;
+
+    fn vote(block_hash: &str) -> String {
+        format!(
+            r#"{{"type":2,"height":"10","round":"1","block_id":{{"hash":"{}","parts":{{"total":"1","hash":"{}"}}}},"timestamp":"2020-01-01T00:00:00Z","validator_address":"E307483A08C3954474A2600F3EC8DC99613AE142","validator_index":"3","signature":"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=="}}"#,
+            block_hash, block_hash
+        )
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Amino {
+        #[serde(with = "super::public_key")]
+        pub_key: PublicKey,
+        #[serde(with = "super::vote")]
+        vote: Vote,
+    }
+
+    #[test]
+    fn public_keys_and_votes() {
+        let json = format!(
+            r#"{{"pub_key":{},"vote":{}}}"#,
+            PUB_KEY,
+            vote(&"AB".repeat(32))
+        );
+        let amino: Amino = serde_json::from_str(&json).unwrap();
+        assert_eq!(amino.pub_key.account_id(), amino.vote.validator_address);
+        assert_eq!(amino.vote.height.value(), 10);
+        assert_eq!(amino.vote.round.value(), 1);
+        assert_eq!(amino.vote.validator_index.value(), 3);
+        assert_eq!(serde_json::to_string(&amino).unwrap(), json);
+
+        // Keys of ABCI validator updates
+        let json = format!(
+            r#"{{"pub_key":{},"vote":{}}}"#,
+            r#"{"type":"ed25519","data":"SiXGZAofcrnJdTOClO9RttHDMVi7bsumn7w/taM8nc4="}"#,
+            vote("").replace(r#""total":"1""#, r#""total":"0""#)
+        );
+        let amino: Amino = serde_json::from_str(&json).unwrap();
+        assert!(amino.pub_key.ed25519().is_some());
+        assert_eq!(amino.vote.block_id, None);
+    }
+
+    #[derive(Deserialize)]
+    struct AminoEvidence(#[serde(deserialize_with = "super::evidence::deserialize")] Evidence);
+
+    #[test]
+    fn duplicate_vote_evidence() {
+        let json = format!(
+            r#"{{"type":"tendermint/DuplicateVoteEvidence","value":{{"PubKey":{},"VoteA":{},"VoteB":{}}}}}"#,
+            PUB_KEY,
+            vote(&"AB".repeat(32)),
+            vote(&"CD".repeat(32))
+        );
+        let AminoEvidence(evidence) = serde_json::from_str(&json).unwrap();
+        match evidence {
+            Evidence::DuplicateVote(evidence) => {
+                let (vote_a, vote_b) = evidence.votes();
+                assert_ne!(vote_a.block_id, vote_b.block_id);
+            }
+            _ => panic!("expected duplicate vote evidence"),
+        }
+
+        // The votes must be from the validator with the given key
+        let other_key = r#"{"type":"tendermint/PubKeyEd25519","value":"11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo="}"#;
+        let json = json.replace(PUB_KEY, other_key);
+        assert!(serde_json::from_str::<AminoEvidence>(&json).is_err());
+    }
+}
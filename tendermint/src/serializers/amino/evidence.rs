@@ -0,0 +1,60 @@
+//! Amino JSON encoding of evidence, which only knows of duplicate votes:
+//! `{"type": "tendermint/DuplicateVoteEvidence", "value": {"PubKey": ...,
+//! "VoteA": ..., "VoteB": ...}}`.
+//!
+//! Instead of the voting powers and the time recorded by later versions, this
+//! evidence records the public key of the validator, which is checked against
+//! the votes and then dropped: the voting powers are left at zero, and the
+//! time of the evidence is the one of its first vote. Evidence can thus only
+//! be deserialized, e.g. with `#[serde(deserialize_with = "...")]`.
+
+use crate::evidence::{DuplicateVoteEvidence, Evidence};
+use crate::{PublicKey, Vote};
+use core::convert::TryFrom;
+use serde::{de, Deserialize, Deserializer};
+use tendermint_proto::types::DuplicateVoteEvidence as RawDuplicateVoteEvidence;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum AminoEvidence {
+    #[serde(rename = "tendermint/DuplicateVoteEvidence")]
+    DuplicateVote(AminoDuplicateVoteEvidence),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AminoDuplicateVoteEvidence {
+    #[serde(with = "super::public_key")]
+    pub_key: PublicKey,
+    #[serde(with = "super::vote")]
+    vote_a: Vote,
+    #[serde(with = "super::vote")]
+    vote_b: Vote,
+}
+
+/// Deserialize amino-encoded evidence
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Evidence, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use de::Error;
+    let AminoEvidence::DuplicateVote(evidence) = AminoEvidence::deserialize(deserializer)?;
+
+    let address = evidence.pub_key.account_id();
+    if evidence.vote_a.validator_address != address || evidence.vote_b.validator_address != address
+    {
+        return Err(D::Error::custom(
+            "duplicate votes are not from the validator with the given public key",
+        ));
+    }
+    let timestamp = evidence.vote_a.timestamp.map(Into::into);
+    DuplicateVoteEvidence::try_from(RawDuplicateVoteEvidence {
+        vote_a: Some(evidence.vote_a.into()),
+        vote_b: Some(evidence.vote_b.into()),
+        total_voting_power: 0,
+        validator_power: 0,
+        timestamp,
+    })
+    .map(Evidence::DuplicateVote)
+    .map_err(D::Error::custom)
+}
@@ -0,0 +1,77 @@
+//! Amino JSON encoding of public keys:
+//! `{"type": "tendermint/PubKeyEd25519", "value": "<base64>"}`.
+//!
+//! The encoding of the keys of ABCI validator updates, which are named after
+//! their algorithm and whose value is called `data`, is accepted as well.
+
+use crate::prelude::*;
+use crate::PublicKey;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use subtle_encoding::base64;
+
+const ED25519: &str = "tendermint/PubKeyEd25519";
+#[cfg(feature = "secp256k1")]
+const SECP256K1: &str = "tendermint/PubKeySecp256k1";
+#[cfg(feature = "sr25519")]
+const SR25519: &str = "tendermint/PubKeySr25519";
+
+#[derive(Serialize, Deserialize)]
+struct AminoPublicKey {
+    #[serde(rename = "type")]
+    name: String,
+    #[serde(alias = "data")]
+    value: String,
+}
+
+/// Deserialize an amino-encoded public key
+pub fn deserialize<'de, D>(deserializer: D) -> Result<PublicKey, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use de::Error;
+    let key = AminoPublicKey::deserialize(deserializer)?;
+    let bytes = base64::decode(&key.value).map_err(D::Error::custom)?;
+    let public_key = match key.name.as_str() {
+        ED25519 | "ed25519" => PublicKey::from_raw_ed25519(&bytes),
+        #[cfg(feature = "secp256k1")]
+        SECP256K1 | "secp256k1" => PublicKey::from_raw_secp256k1(&bytes),
+        #[cfg(feature = "sr25519")]
+        SR25519 | "sr25519" => PublicKey::from_raw_sr25519(&bytes),
+        name => {
+            return Err(D::Error::custom(format!(
+                "unsupported public key type: {}",
+                name
+            )))
+        }
+    };
+    public_key.ok_or_else(|| D::Error::custom(format!("malformed {} key", key.name)))
+}
+
+/// Serialize a public key with its amino encoding.
+///
+/// Fails for keys of algorithms unknown to Tendermint 0.33, such as BLS12-381.
+pub fn serialize<S>(value: &PublicKey, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use ser::Error;
+    let name = match value {
+        PublicKey::Ed25519(_) => ED25519,
+        #[cfg(feature = "secp256k1")]
+        PublicKey::Secp256k1(_) => SECP256K1,
+        #[cfg(feature = "sr25519")]
+        PublicKey::Sr25519(_) => SR25519,
+        #[allow(unreachable_patterns)]
+        _ => {
+            return Err(S::Error::custom(format!(
+                "{} keys have no amino encoding",
+                value.algorithm()
+            )))
+        }
+    };
+    AminoPublicKey {
+        name: name.to_string(),
+        value: String::from_utf8(base64::encode(value.as_bytes())).unwrap(),
+    }
+    .serialize(serializer)
+}
@@ -0,0 +1,105 @@
+//! Amino JSON encoding of votes, in which the round and the validator index
+//! are strings, and the part set header of the block ID is called `parts`.
+
+use crate::prelude::*;
+use crate::{serializers, Error, Time, Vote};
+use core::convert::TryFrom;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use tendermint_proto::types::{
+    BlockId as RawBlockId, PartSetHeader as RawPartSetHeader, Vote as RawVote,
+};
+
+#[derive(Serialize, Deserialize)]
+struct AminoVote {
+    #[serde(rename = "type")]
+    vote_type: i32,
+    #[serde(with = "serializers::from_str")]
+    height: i64,
+    #[serde(with = "serializers::from_str")]
+    round: i32,
+    block_id: AminoBlockId,
+    timestamp: Time,
+    #[serde(with = "serializers::bytes::hexstring")]
+    validator_address: Vec<u8>,
+    #[serde(with = "serializers::from_str")]
+    validator_index: i32,
+    #[serde(with = "serializers::bytes::base64string")]
+    signature: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AminoBlockId {
+    #[serde(with = "serializers::bytes::hexstring")]
+    hash: Vec<u8>,
+    parts: AminoPartSetHeader,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AminoPartSetHeader {
+    #[serde(with = "serializers::from_str")]
+    total: u32,
+    #[serde(with = "serializers::bytes::hexstring")]
+    hash: Vec<u8>,
+}
+
+/// Deserialize an amino-encoded vote
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vote, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let vote = AminoVote::deserialize(deserializer)?;
+    from_amino(vote).map_err(de::Error::custom)
+}
+
+/// Serialize a vote with its amino encoding
+pub fn serialize<S>(value: &Vote, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    to_amino(value.clone())
+        .ok_or_else(|| ser::Error::custom("vote without timestamp"))?
+        .serialize(serializer)
+}
+
+fn from_amino(vote: AminoVote) -> Result<Vote, Error> {
+    Vote::try_from(RawVote {
+        r#type: vote.vote_type,
+        height: vote.height,
+        round: vote.round,
+        block_id: Some(RawBlockId {
+            hash: vote.block_id.hash,
+            part_set_header: Some(RawPartSetHeader {
+                total: vote.block_id.parts.total,
+                hash: vote.block_id.parts.hash,
+            }),
+        }),
+        timestamp: Some(vote.timestamp.into()),
+        validator_address: vote.validator_address,
+        validator_index: vote.validator_index,
+        signature: vote.signature,
+        ..Default::default()
+    })
+}
+
+fn to_amino(vote: Vote) -> Option<AminoVote> {
+    let timestamp = vote.timestamp?;
+    let raw = RawVote::from(vote);
+    let block_id = raw.block_id.unwrap_or_default();
+    let parts = block_id.part_set_header.unwrap_or_default();
+    Some(AminoVote {
+        vote_type: raw.r#type,
+        height: raw.height,
+        round: raw.round,
+        block_id: AminoBlockId {
+            hash: block_id.hash,
+            parts: AminoPartSetHeader {
+                total: parts.total,
+                hash: parts.hash,
+            },
+        },
+        timestamp,
+        validator_address: raw.validator_address,
+        validator_index: raw.validator_index,
+        signature: raw.signature,
+    })
+}
@@ -1,21 +1,41 @@
 //! Cryptographic (a.k.a. digital) signatures
 
+use crate::prelude::*;
 pub use ed25519::{Signature as Ed25519Signature, SIGNATURE_LENGTH as ED25519_SIGNATURE_SIZE};
 pub use signature::{Signer, Verifier};
 
 #[cfg(feature = "secp256k1")]
 pub use k256::ecdsa::Signature as Secp256k1;
 
+/// Size of a (compressed) BLS12-381 signature
+pub const BLS12381_SIGNATURE_SIZE: usize = 96;
+
 use crate::{Error, Kind};
-use std::convert::TryFrom;
+use core::convert::TryFrom;
+use subtle::{self, ConstantTimeEq};
 use tendermint_proto::Protobuf;
 
 /// Signatures
-#[derive(Copy, Clone, Debug, PartialEq)]
+///
+/// Signatures are compared in constant time.
+#[derive(Copy, Clone, Debug)]
 #[non_exhaustive]
 pub enum Signature {
     /// Ed25519 block signature
     Ed25519(Ed25519Signature),
+    /// Secp256k1 (ECDSA) signature
+    ///
+    /// Note that signatures decoded from their byte representation are
+    /// always Ed25519 signatures, since both kinds have the same length.
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
+    Secp256k1(Secp256k1),
+    /// BLS12-381 signature
+    ///
+    /// Signatures of this length are decoded as such.
+    #[cfg(feature = "bls12381")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bls12381")))]
+    Bls12381(Bls12381),
     /// No signature present
     None, /* This could have been implemented as an `Option<>` but then handling it would be
            * outside the scope of this enum. */
@@ -30,6 +50,12 @@ impl TryFrom<Vec<u8>> for Signature {
         if value.is_empty() {
             return Ok(Self::default());
         }
+        #[cfg(feature = "bls12381")]
+        if value.len() == BLS12381_SIGNATURE_SIZE {
+            let mut slice = [0; BLS12381_SIGNATURE_SIZE];
+            slice.copy_from_slice(&value[..]);
+            return Ok(Signature::Bls12381(Bls12381::new(slice)));
+        }
         if value.len() != ED25519_SIGNATURE_SIZE {
             return Err(Kind::InvalidSignatureIdLength.into());
         }
@@ -56,6 +82,10 @@ impl Signature {
     pub fn algorithm(&self) -> Algorithm {
         match self {
             Signature::Ed25519(_) => Algorithm::Ed25519,
+            #[cfg(feature = "secp256k1")]
+            Signature::Secp256k1(_) => Algorithm::EcdsaSecp256k1,
+            #[cfg(feature = "bls12381")]
+            Signature::Bls12381(_) => Algorithm::Bls12381,
             Signature::None => Algorithm::Ed25519, /* It doesn't matter what algorithm an empty
                                                     * signature has. */
         }
@@ -65,7 +95,7 @@ impl Signature {
     pub fn ed25519(self) -> Option<Ed25519Signature> {
         match self {
             Signature::Ed25519(sig) => Some(sig),
-            Signature::None => None,
+            _ => None,
         }
     }
 
@@ -84,17 +114,42 @@ impl AsRef<[u8]> for Signature {
     fn as_ref(&self) -> &[u8] {
         match self {
             Signature::Ed25519(sig) => sig.as_ref(),
+            #[cfg(feature = "secp256k1")]
+            Signature::Secp256k1(sig) => sig.as_ref(),
+            #[cfg(feature = "bls12381")]
+            Signature::Bls12381(sig) => sig.as_ref(),
             Signature::None => &[],
         }
     }
 }
 
+impl ConstantTimeEq for Signature {
+    #[inline]
+    fn ct_eq(&self, other: &Signature) -> subtle::Choice {
+        (self.algorithm() as u8).ct_eq(&(other.algorithm() as u8))
+            & self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+impl PartialEq for Signature {
+    fn eq(&self, other: &Signature) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 impl From<Ed25519Signature> for Signature {
     fn from(pk: Ed25519Signature) -> Signature {
         Signature::Ed25519(pk)
     }
 }
 
+#[cfg(feature = "secp256k1")]
+impl From<Secp256k1> for Signature {
+    fn from(sig: Secp256k1) -> Signature {
+        Signature::Secp256k1(sig)
+    }
+}
+
 /// Digital signature algorithms
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Algorithm {
@@ -103,4 +158,76 @@ pub enum Algorithm {
 
     /// EdDSA over Curve25519
     Ed25519,
+
+    /// BLS over BLS12-381
+    Bls12381,
+}
+
+/// BLS12-381 signature, in its compressed form
+#[cfg(feature = "bls12381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12381")))]
+#[derive(Copy, Clone, Debug)]
+pub struct Bls12381([u8; BLS12381_SIGNATURE_SIZE]);
+
+#[cfg(feature = "bls12381")]
+impl Bls12381 {
+    /// Create a signature from its compressed bytes
+    pub fn new(bytes: [u8; BLS12381_SIGNATURE_SIZE]) -> Self {
+        Bls12381(bytes)
+    }
+}
+
+#[cfg(feature = "bls12381")]
+impl AsRef<[u8]> for Bls12381 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "bls12381")]
+impl ConstantTimeEq for Bls12381 {
+    #[inline]
+    fn ct_eq(&self, other: &Bls12381) -> subtle::Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+#[cfg(feature = "bls12381")]
+impl PartialEq for Bls12381 {
+    fn eq(&self, other: &Bls12381) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "bls12381")]
+impl Eq for Bls12381 {}
+
+#[cfg(feature = "bls12381")]
+impl From<Bls12381> for Signature {
+    fn from(sig: Bls12381) -> Signature {
+        Signature::Bls12381(sig)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_time_equality() {
+        let sig = Signature::try_from(vec![1; ED25519_SIGNATURE_SIZE]).unwrap();
+        let mut bytes = vec![1; ED25519_SIGNATURE_SIZE];
+        bytes[63] = 2;
+        let other = Signature::try_from(bytes).unwrap();
+
+        assert_eq!(sig.ct_eq(&sig).unwrap_u8(), 1);
+        assert_eq!(sig.ct_eq(&other).unwrap_u8(), 0);
+        assert_ne!(sig, other);
+        assert_ne!(sig, Signature::None);
+        assert_eq!(Signature::None, Signature::default());
+
+        // Signatures must have exactly the length of their algorithm
+        assert!(Signature::try_from(vec![1; ED25519_SIGNATURE_SIZE - 1]).is_err());
+        assert!(Signature::try_from(vec![1; ED25519_SIGNATURE_SIZE + 1]).is_err());
+    }
 }
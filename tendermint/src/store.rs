@@ -0,0 +1,69 @@
+//! Readers of the block and state stores of Tendermint nodes
+//!
+//! Tendermint nodes persist the blocks they committed in their `blockstore`
+//! database, and their state (validator sets, consensus parameters...) in
+//! their `state` database. [`BlockStore`] and [`StateStore`] decode the
+//! entries of those databases, as written by Tendermint v0.34, into domain
+//! types.
+//!
+//! The stores only ever read from a database, through the [`Database`]
+//! trait, which can be implemented on top of any binding to the key-value
+//! store backing the node (e.g. GoLevelDB databases can be opened read-only
+//! with a LevelDB binding).
+
+pub mod block_store;
+pub mod state_store;
+
+pub use self::{
+    block_store::BlockStore,
+    state_store::{State, StateStore},
+};
+
+use crate::prelude::*;
+use crate::{Error, Kind};
+use alloc::collections::BTreeMap;
+
+/// Read-only access to a key-value database of a Tendermint node.
+pub trait Database {
+    /// The value stored under the given key, if any.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
+}
+
+impl<D: Database + ?Sized> Database for &D {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        (**self).get(key)
+    }
+}
+
+/// An in-memory database, e.g. a dump of the entries of a node's database.
+impl Database for BTreeMap<Vec<u8>, Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(BTreeMap::get(self, key).cloned())
+    }
+}
+
+/// Read and decode the protobuf message stored under the given key.
+fn get_raw<D, T>(db: &D, key: &str) -> Result<Option<T>, Error>
+where
+    D: Database + ?Sized,
+    T: prost::Message + Default,
+{
+    db.get(key.as_bytes())?
+        .map(|value| decode_raw(key, &value))
+        .transpose()
+}
+
+/// Decode the protobuf message stored under the given key.
+fn decode_raw<T>(key: &str, value: &[u8]) -> Result<T, Error>
+where
+    T: prost::Message + Default,
+{
+    T::decode(value).map_err(|e| corrupted(key, e))
+}
+
+/// Report the entry stored under the given key as corrupted.
+fn corrupted(key: &str, e: impl core::fmt::Display) -> Error {
+    Kind::CorruptedStore
+        .context(format!("entry {}: {}", key, e))
+        .into()
+}
@@ -0,0 +1,287 @@
+//! Reader of the block store of a Tendermint node
+
+use super::{corrupted, decode_raw, get_raw, Database};
+use crate::block::{parts::Part, signed_header::SignedHeader, Commit, Height, Meta};
+use crate::prelude::*;
+use crate::{Block, Error, Hash, Kind};
+use core::convert::{TryFrom, TryInto};
+use core::ops::RangeInclusive;
+use subtle_encoding::hex;
+use tendermint_proto::store::BlockStoreState as RawBlockStoreState;
+use tendermint_proto::types::{
+    Block as RawBlock, BlockMeta as RawMeta, Commit as RawCommit, Part as RawPart,
+};
+
+/// Key of the range of heights held by the block store
+const BLOCK_STORE_KEY: &str = "blockStore";
+
+/// Reads the blocks, commits and block metadata committed by a Tendermint
+/// node from its `blockstore` database.
+#[derive(Clone, Debug)]
+pub struct BlockStore<D> {
+    db: D,
+}
+
+impl<D: Database> BlockStore<D> {
+    /// Read the block store held by the given database.
+    pub fn new(db: D) -> Self {
+        Self { db }
+    }
+
+    /// The range of heights of the blocks held by the store, from its base
+    /// (the lowest height, which is above 1 if the node pruned blocks) to
+    /// its latest height, or `None` if it holds no block.
+    pub fn heights(&self) -> Result<Option<RangeInclusive<Height>>, Error> {
+        let state: RawBlockStoreState = match get_raw(&self.db, BLOCK_STORE_KEY)? {
+            Some(state) => state,
+            None => return Ok(None),
+        };
+        if state.height == 0 {
+            return Ok(None);
+        }
+        let height = Height::try_from(state.height)?;
+        // Stores written before pruning was introduced have no base
+        let base = match state.base {
+            0 => Height::from(1_u32),
+            base => Height::try_from(base)?,
+        };
+        Ok(Some(base..=height))
+    }
+
+    /// The metadata of the block at the given height.
+    pub fn block_meta(&self, height: Height) -> Result<Option<Meta>, Error> {
+        let key = format!("H:{}", height);
+        get_raw::<_, RawMeta>(&self.db, &key)?
+            .map(|meta| meta.try_into().map_err(|e| corrupted(&key, e)))
+            .transpose()
+    }
+
+    /// The block at the given height, reassembled from its parts.
+    pub fn block(&self, height: Height) -> Result<Option<Block>, Error> {
+        let meta = match self.block_meta(height)? {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+
+        let mut bytes = Vec::new();
+        for index in 0..meta.block_id.part_set_header.total {
+            let part = self.block_part(height, index)?.ok_or_else(|| {
+                Error::from(
+                    Kind::CorruptedStore
+                        .context(format!("part {} of block {} is missing", index, height)),
+                )
+            })?;
+            bytes.extend_from_slice(&part.bytes);
+        }
+
+        let key = format!("P:{}", height);
+        decode_raw::<RawBlock>(&key, &bytes)?
+            .try_into()
+            .map(Some)
+            .map_err(|e| corrupted(&key, e))
+    }
+
+    /// The block with the given hash.
+    pub fn block_by_hash(&self, hash: Hash) -> Result<Option<Block>, Error> {
+        match self.height_by_hash(hash)? {
+            Some(height) => self.block(height),
+            None => Ok(None),
+        }
+    }
+
+    /// The height of the block with the given hash.
+    pub fn height_by_hash(&self, hash: Hash) -> Result<Option<Height>, Error> {
+        let key = format!(
+            "BH:{}",
+            String::from_utf8(hex::encode(hash.as_bytes())).unwrap()
+        );
+        self.db
+            .get(key.as_bytes())?
+            .map(|value| {
+                String::from_utf8(value)
+                    .map_err(|e| corrupted(&key, e))?
+                    .parse()
+                    .map_err(|e| corrupted(&key, e))
+            })
+            .transpose()
+    }
+
+    /// The part of the block at the given height with the given index.
+    pub fn block_part(&self, height: Height, index: u32) -> Result<Option<Part>, Error> {
+        let key = format!("P:{}:{}", height, index);
+        get_raw::<_, RawPart>(&self.db, &key)?
+            .map(|part| part.try_into().map_err(|e| corrupted(&key, e)))
+            .transpose()
+    }
+
+    /// The commit for the block at the given height, as included in the
+    /// block at the next height.
+    pub fn commit(&self, height: Height) -> Result<Option<Commit>, Error> {
+        self.get_commit(&format!("C:{}", height))
+    }
+
+    /// The commit for the block at the given height seen by the node, which
+    /// may include other signatures than the canonical [`commit`].
+    ///
+    /// [`commit`]: BlockStore::commit
+    pub fn seen_commit(&self, height: Height) -> Result<Option<Commit>, Error> {
+        self.get_commit(&format!("SC:{}", height))
+    }
+
+    /// The header of the block at the given height with the commit for it:
+    /// the canonical one if available, else the one seen by the node.
+    pub fn signed_header(&self, height: Height) -> Result<Option<SignedHeader>, Error> {
+        let header = match self.block_meta(height)? {
+            Some(meta) => meta.header,
+            None => return Ok(None),
+        };
+        let commit = match self.commit(height)? {
+            Some(commit) => commit,
+            None => match self.seen_commit(height)? {
+                Some(commit) => commit,
+                None => return Ok(None),
+            },
+        };
+        SignedHeader::new(header, commit).map(Some)
+    }
+
+    /// Unwrap the underlying database.
+    pub fn into_inner(self) -> D {
+        self.db
+    }
+
+    fn get_commit(&self, key: &str) -> Result<Option<Commit>, Error> {
+        get_raw::<_, RawCommit>(&self.db, key)?
+            .map(|commit| commit.try_into().map_err(|e| corrupted(key, e)))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abci::transaction;
+    use crate::block::{parts, Header, Id};
+    use crate::{evidence, Hash};
+    use alloc::collections::BTreeMap;
+    use prost::Message;
+    use tendermint_proto::crypto::Proof as RawProof;
+    use tendermint_proto::Protobuf;
+
+    fn block() -> Block {
+        let mut header: Header = serde_json::from_str(include_str!(
+            "../../tests/support/serialization/block/header.json"
+        ))
+        .unwrap();
+        header.height = Height::from(1_u32);
+        header.last_block_id = None;
+        Block::new(
+            header,
+            transaction::Data::default(),
+            evidence::Data::default(),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn commit(round: u16) -> Commit {
+        Commit {
+            height: Height::from(1_u32),
+            round: round.into(),
+            block_id: Id {
+                hash: Hash::Sha256([1; 32]),
+                part_set_header: parts::Header::new(2, Hash::Sha256([2; 32])).unwrap(),
+            },
+            signatures: vec![],
+        }
+    }
+
+    fn insert(db: &mut BTreeMap<Vec<u8>, Vec<u8>>, key: &str, value: impl Message) {
+        let mut bytes = Vec::new();
+        value.encode(&mut bytes).unwrap();
+        db.insert(key.as_bytes().to_vec(), bytes);
+    }
+
+    fn store() -> BlockStore<BTreeMap<Vec<u8>, Vec<u8>>> {
+        let block = block();
+        let block_id = commit(0).block_id;
+        let bytes = Protobuf::<RawBlock>::encode_vec(&block).unwrap();
+        let (first, second) = bytes.split_at(bytes.len() / 2);
+
+        let mut db = BTreeMap::new();
+        insert(
+            &mut db,
+            BLOCK_STORE_KEY,
+            RawBlockStoreState { base: 1, height: 1 },
+        );
+        insert(
+            &mut db,
+            "H:1",
+            RawMeta::from(Meta {
+                block_id,
+                block_size: bytes.len() as i64,
+                header: block.header,
+                num_txs: 0,
+            }),
+        );
+        for (index, bytes) in [first, second].iter().enumerate() {
+            insert(
+                &mut db,
+                &format!("P:1:{}", index),
+                RawPart {
+                    index: index as u32,
+                    bytes: bytes.to_vec(),
+                    proof: Some(RawProof {
+                        total: 2,
+                        index: index as i64,
+                        leaf_hash: vec![0; 32],
+                        aunts: vec![],
+                    }),
+                },
+            );
+        }
+        insert(&mut db, "C:1", RawCommit::from(commit(0)));
+        insert(&mut db, "SC:1", RawCommit::from(commit(1)));
+        db.insert(
+            format!("BH:{}", "01".repeat(32)).into_bytes(),
+            b"1".to_vec(),
+        );
+        BlockStore::new(db)
+    }
+
+    #[test]
+    fn reads_blocks_and_commits() {
+        let store = store();
+        let height = Height::from(1_u32);
+        assert_eq!(store.heights().unwrap(), Some(height..=height));
+        assert_eq!(store.block(height).unwrap(), Some(block()));
+        assert_eq!(
+            store.block_by_hash(Hash::Sha256([1; 32])).unwrap(),
+            Some(block())
+        );
+        assert_eq!(store.block_by_hash(Hash::Sha256([2; 32])).unwrap(), None);
+        assert_eq!(store.commit(height).unwrap(), Some(commit(0)));
+        assert_eq!(store.seen_commit(height).unwrap(), Some(commit(1)));
+        assert_eq!(
+            store.signed_header(height).unwrap().unwrap().commit,
+            commit(0)
+        );
+        assert_eq!(store.block(Height::from(2_u32)).unwrap(), None);
+    }
+
+    #[test]
+    fn reports_corrupted_entries() {
+        let mut db = store().into_inner();
+        db.remove(b"P:1:1".as_ref());
+        db.insert(b"C:1".to_vec(), b"not a commit".to_vec());
+        let store = BlockStore::new(db);
+
+        let height = Height::from(1_u32);
+        for error in &[
+            store.block(height).unwrap_err(),
+            store.commit(height).unwrap_err(),
+        ] {
+            assert_eq!(error.kind(), &Kind::CorruptedStore);
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Reader of the state store of a Tendermint node
+
+use super::{corrupted, get_raw, Database};
+use crate::block::{self, header::Version, Height};
+use crate::prelude::*;
+use crate::{chain, consensus, validator, AppHash, Error, Hash, Kind, Time};
+use core::convert::{TryFrom, TryInto};
+use tendermint_proto::abci::{
+    BlockParams as RawAbciBlockParams, ConsensusParams as RawAbciConsensusParams,
+};
+use tendermint_proto::state::{
+    ConsensusParamsInfo as RawConsensusParamsInfo, State as RawState,
+    ValidatorsInfo as RawValidatorsInfo,
+};
+use tendermint_proto::types::ConsensusParams as RawConsensusParams;
+
+/// Key of the latest state
+const STATE_KEY: &str = "stateKey";
+
+/// Interval between the heights at which the validator set is stored even
+/// if it didn't change, as in Tendermint.
+const VALIDATOR_SET_CHECKPOINT_INTERVAL: u64 = 100_000;
+
+/// The state of a Tendermint node after committing a block, as stored in its
+/// `state` database.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    /// Versions of the protocols in use
+    pub version: Version,
+
+    /// Version of the node's software
+    pub software: String,
+
+    /// Chain ID
+    pub chain_id: chain::Id,
+
+    /// Height of the first block of the chain
+    pub initial_height: Height,
+
+    /// Height of the latest block, `0` before the first block is committed
+    pub last_block_height: Height,
+
+    /// ID of the latest block, if any
+    pub last_block_id: Option<block::Id>,
+
+    /// Time of the latest block
+    pub last_block_time: Time,
+
+    /// Validators of the next block
+    pub next_validators: validator::Set,
+
+    /// Validators of the current block
+    pub validators: validator::Set,
+
+    /// Validators of the latest block, which signed its commit
+    pub last_validators: validator::Set,
+
+    /// Height at which the validator set last changed
+    pub last_height_validators_changed: Height,
+
+    /// Consensus parameters in effect
+    pub consensus_params: consensus::Params,
+
+    /// Height at which the consensus parameters last changed
+    pub last_height_consensus_params_changed: Height,
+
+    /// Merkle root of the results of the transactions of the latest block
+    pub last_results_hash: Option<Hash>,
+
+    /// Hash returned by the application after committing the latest block
+    pub app_hash: AppHash,
+}
+
+impl TryFrom<RawState> for State {
+    type Error = Error;
+
+    fn try_from(value: RawState) -> Result<Self, Self::Error> {
+        let version = value.version.ok_or(Kind::MissingVersion)?;
+        let consensus_version = version.consensus.ok_or(Kind::MissingVersion)?;
+        Ok(State {
+            version: Version {
+                block: consensus_version.block,
+                app: consensus_version.app,
+            },
+            software: version.software,
+            chain_id: value.chain_id.try_into()?,
+            initial_height: value.initial_height.try_into()?,
+            last_block_height: value.last_block_height.try_into()?,
+            // An empty block ID is considered nil by Go
+            last_block_id: value
+                .last_block_id
+                .filter(|id| !id.hash.is_empty())
+                .map(TryInto::try_into)
+                .transpose()?,
+            last_block_time: value
+                .last_block_time
+                .ok_or(Kind::MissingTimestamp)?
+                .try_into()?,
+            next_validators: value.next_validators.unwrap_or_default().try_into()?,
+            validators: value.validators.unwrap_or_default().try_into()?,
+            last_validators: value.last_validators.unwrap_or_default().try_into()?,
+            last_height_validators_changed: value.last_height_validators_changed.try_into()?,
+            consensus_params: consensus_params(
+                value.consensus_params.ok_or(Kind::InvalidConsensusParams)?,
+            )?,
+            last_height_consensus_params_changed: value
+                .last_height_consensus_params_changed
+                .try_into()?,
+            last_results_hash: if value.last_results_hash.is_empty() {
+                None
+            } else {
+                Some(value.last_results_hash.try_into()?)
+            },
+            app_hash: value.app_hash.try_into()?,
+        })
+    }
+}
+
+/// Reads the state of a Tendermint node, and the validator sets and
+/// consensus parameters of past heights, from its `state` database.
+#[derive(Clone, Debug)]
+pub struct StateStore<D> {
+    db: D,
+}
+
+impl<D: Database> StateStore<D> {
+    /// Read the state store held by the given database.
+    pub fn new(db: D) -> Self {
+        Self { db }
+    }
+
+    /// The latest state of the node, or `None` if the node never started.
+    pub fn state(&self) -> Result<Option<State>, Error> {
+        get_raw::<_, RawState>(&self.db, STATE_KEY)?
+            .map(|state| state.try_into().map_err(|e| corrupted(STATE_KEY, e)))
+            .transpose()
+    }
+
+    /// The validator set of the block at the given height.
+    ///
+    /// As in Tendermint, the validator set is only stored at the heights at
+    /// which it changed and at regular checkpoints: for other heights, the
+    /// proposer priorities of the stored set are incremented once per height
+    /// since it was stored.
+    pub fn validators(&self, height: Height) -> Result<Option<validator::Set>, Error> {
+        let key = validators_key(height);
+        let info: RawValidatorsInfo = match get_raw(&self.db, &key)? {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+        if let Some(validator_set) = info.validator_set {
+            return validator_set
+                .try_into()
+                .map(Some)
+                .map_err(|e| corrupted(&key, e));
+        }
+
+        let last_height_changed =
+            Height::try_from(info.last_height_changed).map_err(|e| corrupted(&key, e))?;
+        let checkpoint = height.value() - height.value() % VALIDATOR_SET_CHECKPOINT_INTERVAL;
+        let stored_height = Height::try_from(checkpoint)?.max(last_height_changed);
+
+        let stored_key = validators_key(stored_height);
+        let mut validator_set: validator::Set =
+            get_raw::<_, RawValidatorsInfo>(&self.db, &stored_key)?
+                .and_then(|info| info.validator_set)
+                .ok_or_else(|| {
+                    Error::from(Kind::CorruptedStore.context(format!(
+                        "validators of height {} are missing, as needed for height {}",
+                        stored_height, height
+                    )))
+                })?
+                .try_into()
+                .map_err(|e| corrupted(&stored_key, e))?;
+        let times = (height.value() - stored_height.value())
+            .try_into()
+            .map_err(|_| Kind::IntegerOverflow)?;
+        validator_set.increment_proposer_priority(times);
+        Ok(Some(validator_set))
+    }
+
+    /// The consensus parameters in effect at the given height.
+    pub fn consensus_params(&self, height: Height) -> Result<Option<consensus::Params>, Error> {
+        let key = consensus_params_key(height);
+        let info: RawConsensusParamsInfo = match get_raw(&self.db, &key)? {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+
+        // The parameters are only stored at the heights at which they changed
+        let params = match info.consensus_params {
+            Some(params) if params != RawConsensusParams::default() => params,
+            _ => {
+                let last_height_changed =
+                    Height::try_from(info.last_height_changed).map_err(|e| corrupted(&key, e))?;
+                let stored_key = consensus_params_key(last_height_changed);
+                get_raw::<_, RawConsensusParamsInfo>(&self.db, &stored_key)?
+                    .and_then(|info| info.consensus_params)
+                    .ok_or_else(|| {
+                        Error::from(Kind::CorruptedStore.context(format!(
+                            "consensus parameters of height {} are missing, as needed for height {}",
+                            last_height_changed, height
+                        )))
+                    })?
+            }
+        };
+        consensus_params(params)
+            .map(Some)
+            .map_err(|e| corrupted(&key, e))
+    }
+
+    /// Unwrap the underlying database.
+    pub fn into_inner(self) -> D {
+        self.db
+    }
+}
+
+fn validators_key(height: Height) -> String {
+    format!("validatorsKey:{}", height)
+}
+
+fn consensus_params_key(height: Height) -> String {
+    format!("consensusParamsKey:{}", height)
+}
+
+/// Convert the consensus parameters stored by a node, whose block parameters
+/// include the (ignored) minimum time increment between blocks.
+fn consensus_params(params: RawConsensusParams) -> Result<consensus::Params, Error> {
+    RawAbciConsensusParams {
+        block: params.block.map(|block| RawAbciBlockParams {
+            max_bytes: block.max_bytes,
+            max_gas: block.max_gas,
+        }),
+        evidence: params.evidence,
+        validator: params.validator,
+        version: params.version,
+    }
+    .try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::signing_key;
+    use crate::PublicKey;
+    use alloc::collections::BTreeMap;
+    use prost::Message;
+    use tendermint_proto::google::protobuf::{Duration as RawDuration, Timestamp};
+    use tendermint_proto::state::Version as RawVersion;
+    use tendermint_proto::types::{
+        BlockParams as RawBlockParams, EvidenceParams as RawEvidenceParams,
+        ValidatorParams as RawValidatorParams, ValidatorSet as RawValidatorSet,
+    };
+    use tendermint_proto::version::Consensus as RawConsensusVersion;
+
+    fn validators() -> validator::Set {
+        validator::Set::without_proposer(
+            (1..=3)
+                .map(|seed| {
+                    validator::Info::new(
+                        PublicKey::from(signing_key(seed).public),
+                        u64::from(seed).try_into().unwrap(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn raw_consensus_params() -> RawConsensusParams {
+        RawConsensusParams {
+            block: Some(RawBlockParams {
+                max_bytes: 22_020_096,
+                max_gas: -1,
+                time_iota_ms: 1000,
+            }),
+            evidence: Some(RawEvidenceParams {
+                max_age_num_blocks: 100_000,
+                max_age_duration: Some(RawDuration {
+                    seconds: 172_800,
+                    nanos: 0,
+                }),
+                max_bytes: 1_048_576,
+            }),
+            validator: Some(RawValidatorParams {
+                pub_key_types: vec!["ed25519".to_string()],
+            }),
+            version: None,
+        }
+    }
+
+    fn insert(db: &mut BTreeMap<Vec<u8>, Vec<u8>>, key: &str, value: impl Message) {
+        let mut bytes = Vec::new();
+        value.encode(&mut bytes).unwrap();
+        db.insert(key.as_bytes().to_vec(), bytes);
+    }
+
+    fn store() -> StateStore<BTreeMap<Vec<u8>, Vec<u8>>> {
+        let mut db = BTreeMap::new();
+        insert(
+            &mut db,
+            STATE_KEY,
+            RawState {
+                version: Some(RawVersion {
+                    consensus: Some(RawConsensusVersion { block: 11, app: 1 }),
+                    software: "0.34.9".to_string(),
+                }),
+                chain_id: "test-chain".to_string(),
+                initial_height: 1,
+                last_block_height: 0,
+                last_block_id: Some(Default::default()),
+                last_block_time: Some(Timestamp {
+                    seconds: 1_600_000_000,
+                    nanos: 0,
+                }),
+                next_validators: Some(validators().into()),
+                validators: Some(validators().into()),
+                last_validators: Some(RawValidatorSet::default()),
+                last_height_validators_changed: 1,
+                consensus_params: Some(raw_consensus_params()),
+                last_height_consensus_params_changed: 1,
+                last_results_hash: vec![],
+                app_hash: vec![],
+            },
+        );
+        insert(
+            &mut db,
+            "validatorsKey:3",
+            RawValidatorsInfo {
+                validator_set: Some(validators().into()),
+                last_height_changed: 3,
+            },
+        );
+        insert(
+            &mut db,
+            "validatorsKey:5",
+            RawValidatorsInfo {
+                validator_set: None,
+                last_height_changed: 3,
+            },
+        );
+        insert(
+            &mut db,
+            "consensusParamsKey:1",
+            RawConsensusParamsInfo {
+                consensus_params: Some(raw_consensus_params()),
+                last_height_changed: 1,
+            },
+        );
+        insert(
+            &mut db,
+            "consensusParamsKey:2",
+            RawConsensusParamsInfo {
+                consensus_params: None,
+                last_height_changed: 1,
+            },
+        );
+        StateStore::new(db)
+    }
+
+    #[test]
+    fn reads_state() {
+        let state = store().state().unwrap().unwrap();
+        assert_eq!(state.chain_id.as_str(), "test-chain");
+        assert_eq!(state.software, "0.34.9");
+        assert_eq!(state.last_block_height.value(), 0);
+        assert_eq!(state.last_block_id, None);
+        assert_eq!(state.validators, validators());
+        assert!(state.last_validators.validators().is_empty());
+        assert_eq!(state.consensus_params.block.max_bytes, 22_020_096);
+    }
+
+    #[test]
+    fn reads_validators_of_unchanged_heights() {
+        let store = store();
+        assert_eq!(
+            store.validators(Height::from(3_u32)).unwrap(),
+            Some(validators())
+        );
+
+        let mut expected = validators();
+        expected.increment_proposer_priority(2);
+        assert_eq!(
+            store.validators(Height::from(5_u32)).unwrap(),
+            Some(expected)
+        );
+        assert_eq!(store.validators(Height::from(6_u32)).unwrap(), None);
+    }
+
+    #[test]
+    fn reads_consensus_params_of_unchanged_heights() {
+        let store = store();
+        let params = store.consensus_params(Height::from(1_u32)).unwrap();
+        assert!(params.is_some());
+        assert_eq!(store.consensus_params(Height::from(2_u32)).unwrap(), params);
+
+        let mut db = store.into_inner();
+        db.remove(b"consensusParamsKey:1".as_ref());
+        let error = StateStore::new(db)
+            .consensus_params(Height::from(2_u32))
+            .unwrap_err();
+        assert_eq!(error.kind(), &Kind::CorruptedStore);
+    }
+}
@@ -1,5 +1,6 @@
+use crate::private_key;
+use core::fmt::Debug;
 use serde::{de::DeserializeOwned, Serialize};
-use std::fmt::Debug;
 
 /// Test that a struct `T` can be:
 ///
@@ -25,3 +26,13 @@ where
 
     assert_eq!(parsed0, parsed1);
 }
+
+/// Ed25519 key pair whose secret key is the given seed repeated, to sign test
+/// fixtures deterministically.
+pub fn signing_key(seed: u8) -> private_key::Ed25519 {
+    let secret = ed25519_dalek::SecretKey::from_bytes(&[seed; 32]).unwrap();
+    private_key::Ed25519 {
+        public: ed25519_dalek::PublicKey::from(&secret),
+        secret,
+    }
+}
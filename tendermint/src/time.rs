@@ -1,67 +1,115 @@
 //! Timestamps used by Tendermint blockchains
 
 use crate::error::{Error, Kind};
+use crate::prelude::*;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
-use std::convert::{Infallible, TryFrom};
-use std::fmt;
-use std::ops::{Add, Sub};
-use std::str::FromStr;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, Sub};
+use core::str::FromStr;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
 use tendermint_proto::google::protobuf::Timestamp;
 use tendermint_proto::serializers::timestamp;
 use tendermint_proto::Protobuf;
 
+/// Seconds since the Unix epoch of the earliest valid [`Time`],
+/// "0001-01-01T00:00:00Z"
+const MIN_UNIX_TIMESTAMP: i64 = -62_135_596_800;
+
+/// Seconds since the Unix epoch of the latest valid [`Time`],
+/// "9999-12-31T23:59:59.999999999Z"
+const MAX_UNIX_TIMESTAMP: i64 = 253_402_300_799;
+
 /// Tendermint timestamps
+///
+/// Valid timestamps range from year 1 to year 9999 (inclusive), as required
+/// by the spec. Timestamps are kept at nanosecond precision, so comparing two
+/// times gives the same result before and after a roundtrip through either
+/// their protobuf or JSON encoding.
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#time>
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(try_from = "Timestamp", into = "Timestamp")]
 pub struct Time(DateTime<Utc>);
 
 impl Protobuf<Timestamp> for Time {}
 
 impl TryFrom<Timestamp> for Time {
-    type Error = Infallible;
+    type Error = Error;
 
     fn try_from(value: Timestamp) -> Result<Self, Self::Error> {
-        // prost_types::Timestamp has a SystemTime converter but
-        // tendermint_proto::Timestamp can be JSON-encoded
-        let prost_value = prost_types::Timestamp {
-            seconds: value.seconds,
-            nanos: value.nanos,
-        };
-
-        Ok(SystemTime::from(prost_value).into())
+        if value.nanos < 0 {
+            return Err(Kind::OutOfRange
+                .context("timestamp nanoseconds must not be negative")
+                .into());
+        }
+        Time::from_unix_timestamp(value.seconds, value.nanos as u32)
     }
 }
 
 impl From<Time> for Timestamp {
     fn from(value: Time) -> Self {
-        // prost_types::Timestamp has a SystemTime converter but
-        // tendermint_proto::Timestamp can be JSON-encoded
-        let prost_value = prost_types::Timestamp::from(SystemTime::from(value));
         Timestamp {
-            seconds: prost_value.seconds,
-            nanos: prost_value.nanos,
+            seconds: value.unix_timestamp(),
+            nanos: value.0.timestamp_subsec_nanos() as i32,
         }
     }
 }
 
 impl Time {
     /// Get [`Time`] value representing the current wall clock time
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn now() -> Self {
         Time(Utc::now())
     }
 
-    /// Get the [`UNIX_EPOCH`] time ("1970-01-01 00:00:00 UTC") as a [`Time`]
+    /// Get the Unix epoch ("1970-01-01 00:00:00 UTC") as a [`Time`]
     pub fn unix_epoch() -> Self {
-        UNIX_EPOCH.into()
+        Time(Utc.timestamp(0, 0))
+    }
+
+    /// Create a [`Time`] from the number of seconds and nanoseconds since
+    /// the Unix epoch, checking that it lies within the valid range
+    pub fn from_unix_timestamp(secs: i64, nanos: u32) -> Result<Self, Error> {
+        if nanos >= 1_000_000_000 {
+            return Err(Kind::OutOfRange
+                .context(format!("invalid timestamp nanoseconds: {}", nanos))
+                .into());
+        }
+        if !(MIN_UNIX_TIMESTAMP..=MAX_UNIX_TIMESTAMP).contains(&secs) {
+            return Err(Kind::OutOfRange
+                .context(format!("timestamp not in years 1 through 9999: {}", secs))
+                .into());
+        }
+        Ok(Time(Utc.timestamp(secs, nanos)))
+    }
+
+    /// Number of whole seconds since the Unix epoch (negative for times
+    /// before it)
+    pub fn unix_timestamp(&self) -> i64 {
+        self.0.timestamp()
+    }
+
+    /// Number of nanoseconds since the Unix epoch (negative for times
+    /// before it)
+    pub fn unix_timestamp_nanos(&self) -> i128 {
+        i128::from(self.0.timestamp()) * 1_000_000_000 + i128::from(self.0.timestamp_subsec_nanos())
+    }
+
+    /// Check that this time lies within the range of valid timestamps, i.e.
+    /// between years 1 and 9999 (inclusive)
+    pub fn validate(&self) -> Result<(), Error> {
+        Time::from_unix_timestamp(self.unix_timestamp(), self.0.timestamp_subsec_nanos())
+            .map(|_| ())
     }
 
     /// Calculate the amount of time which has passed since another [`Time`]
-    /// as a [`std::time::Duration`]
+    /// as a [`core::time::Duration`]
     pub fn duration_since(&self, other: Time) -> Result<Duration, Error> {
         self.0
             .signed_duration_since(other.0)
@@ -69,9 +117,66 @@ impl Time {
             .map_err(|_| Kind::OutOfRange.into())
     }
 
+    /// Add the given duration to this time, returning `None` if the result
+    /// is not a valid [`Time`]
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        let duration = chrono::Duration::from_std(duration).ok()?;
+        let t = Time(self.0.checked_add_signed(duration)?);
+        t.validate().ok().map(|_| t)
+    }
+
+    /// Subtract the given duration from this time, returning `None` if the
+    /// result is not a valid [`Time`]
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        let duration = chrono::Duration::from_std(duration).ok()?;
+        let t = Time(self.0.checked_sub_signed(duration)?);
+        t.validate().ok().map(|_| t)
+    }
+
+    /// Returns this time if it is at least `min_increment` after `previous`,
+    /// or `previous + min_increment` otherwise.
+    ///
+    /// This keeps BFT time monotonic: e.g. a validator votes with the later
+    /// of its local clock and the locked block's time plus one millisecond.
+    pub fn monotonic_after(self, previous: Time, min_increment: Duration) -> Result<Self, Error> {
+        let min = previous
+            .checked_add(min_increment)
+            .ok_or_else(|| Kind::OutOfRange.context("time overflow"))?;
+        Ok(self.max(min))
+    }
+
+    /// Compute the median of the given times, weighted by the associated
+    /// voting powers, as done for BFT time.
+    ///
+    /// Returns `None` if the total weight is zero.
+    pub fn weighted_median(weighted_times: &[(Time, u64)]) -> Option<Self> {
+        let mut weighted_times = weighted_times.to_vec();
+        weighted_times.sort_by_key(|(time, _)| *time);
+        let total = weighted_times
+            .iter()
+            .fold(0_u64, |sum, (_, weight)| sum.saturating_add(*weight));
+        if total == 0 {
+            return None;
+        }
+        let mut median = total / 2;
+        for (time, weight) in weighted_times {
+            if median <= weight {
+                return Some(time);
+            }
+            median -= weight;
+        }
+        None
+    }
+
     /// Parse [`Time`] from an RFC 3339 date
     pub fn parse_from_rfc3339(s: &str) -> Result<Time, Error> {
-        Ok(Time(DateTime::parse_from_rfc3339(s)?.with_timezone(&Utc)))
+        let t = Time(
+            DateTime::parse_from_rfc3339(s)
+                .map_err(|e| Kind::Parse.context(e))?
+                .with_timezone(&Utc),
+        );
+        t.validate()?;
+        Ok(t)
     }
 
     /// Return an RFC 3339 and ISO 8601 date and time string with 6 subseconds digits and Z.
@@ -106,12 +211,14 @@ impl From<Time> for DateTime<Utc> {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<SystemTime> for Time {
     fn from(t: SystemTime) -> Time {
         Time(t.into())
     }
 }
 
+#[cfg(feature = "std")]
 impl From<Time> for SystemTime {
     fn from(t: Time) -> SystemTime {
         t.0.into()
@@ -121,18 +228,26 @@ impl From<Time> for SystemTime {
 impl Add<Duration> for Time {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if the result is not a valid [`Time`]. See
+    /// [`Time::checked_add`] for a version without panic.
     fn add(self, rhs: Duration) -> Self::Output {
-        let st: SystemTime = self.into();
-        (st + rhs).into()
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to time")
     }
 }
 
 impl Sub<Duration> for Time {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if the result is not a valid [`Time`]. See
+    /// [`Time::checked_sub`] for a version without panic.
     fn sub(self, rhs: Duration) -> Self::Output {
-        let st: SystemTime = self.into();
-        (st - rhs).into()
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from time")
     }
 }
 
@@ -175,6 +290,70 @@ mod tests {
         select(strs)
     }
 
+    #[test]
+    fn rejects_out_of_range_timestamps() {
+        assert!("0001-01-01T00:00:00Z".parse::<Time>().is_ok());
+        assert!("9999-12-31T23:59:59.999999999Z".parse::<Time>().is_ok());
+        assert!("0000-12-31T23:59:59Z".parse::<Time>().is_err());
+        assert!("0001-01-01T00:00:00+01:00".parse::<Time>().is_err());
+
+        assert!(Time::try_from(Timestamp {
+            seconds: MAX_UNIX_TIMESTAMP + 1,
+            nanos: 0,
+        })
+        .is_err());
+        assert!(Time::try_from(Timestamp {
+            seconds: 0,
+            nanos: -1,
+        })
+        .is_err());
+        assert!(Time::from_unix_timestamp(0, 1_000_000_000).is_err());
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let max: Time = "9999-12-31T23:59:59.999999999Z".parse().unwrap();
+        assert_eq!(max.checked_add(Duration::from_nanos(1)), None);
+        assert_eq!(
+            max.checked_sub(Duration::from_nanos(999_999_999)),
+            Some("9999-12-31T23:59:59Z".parse().unwrap())
+        );
+
+        let min: Time = "0001-01-01T00:00:00Z".parse().unwrap();
+        assert_eq!(min.checked_sub(Duration::from_nanos(1)), None);
+        assert_eq!(min + Duration::from_secs(1) - Duration::from_secs(1), min);
+
+        let t = Time::from_unix_timestamp(-1, 500_000_000).unwrap();
+        assert_eq!(t.unix_timestamp_nanos(), -500_000_000);
+        let encoded: Timestamp = t.into();
+        assert_eq!((encoded.seconds, encoded.nanos), (-1, 500_000_000));
+        assert_eq!(Time::try_from(encoded).unwrap(), t);
+    }
+
+    #[test]
+    fn bft_time_helpers() {
+        let t = |secs| Time::from_unix_timestamp(secs, 0).unwrap();
+        let iota = Duration::from_millis(1);
+        assert_eq!(t(10).monotonic_after(t(5), iota).unwrap(), t(10));
+        assert_eq!(
+            t(5).monotonic_after(t(5), iota).unwrap(),
+            t(5) + Duration::from_millis(1)
+        );
+
+        assert_eq!(Time::weighted_median(&[]), None);
+        assert_eq!(Time::weighted_median(&[(t(1), 0)]), None);
+        assert_eq!(
+            Time::weighted_median(&[(t(3), 10), (t(1), 10), (t(2), 10)]),
+            Some(t(2))
+        );
+        // A single validator with most of the voting power determines the
+        // median
+        assert_eq!(
+            Time::weighted_median(&[(t(1), 1), (t(2), 1), (t(9), 100)]),
+            Some(t(9))
+        );
+    }
+
     proptest! {
         #[test]
         fn can_parse_rfc3339_timestamps(stamp in pbt::time::arb_rfc3339_timestamp()) {
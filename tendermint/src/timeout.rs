@@ -1,8 +1,8 @@
+use crate::prelude::*;
 use crate::{Error, Kind};
-use anomaly::{fail, format_err};
 
+use core::{fmt, ops::Deref, str::FromStr, time::Duration};
 use serde::{de, de::Error as _, ser, Deserialize, Serialize};
-use std::{fmt, ops::Deref, str::FromStr, time::Duration};
 
 /// Timeout durations
 #[derive(Copy, Clone, Debug)]
@@ -84,8 +84,8 @@ impl Serialize for Timeout {
 #[cfg(test)]
 mod tests {
     use super::Timeout;
+    use crate::prelude::*;
     use crate::Kind;
-    use anomaly::format_err;
 
     #[test]
     fn parse_seconds() {
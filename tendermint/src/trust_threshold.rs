@@ -1,6 +1,6 @@
 //! Define traits and instances for dealing with trust thresholds.
 
-use std::fmt::{self, Debug, Display};
+use core::fmt::{self, Debug, Display};
 
 use crate::serializers;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -1,16 +1,28 @@
 //! Tendermint validators
 
+use crate::prelude::*;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 use subtle_encoding::base64;
 
-use crate::{account, hash::Hash, merkle, vote, Error, Kind, PublicKey, Signature};
+use crate::block::{self, Commit, CommitSig};
+use crate::trust_threshold::{TrustThreshold, TrustThresholdFraction};
+use crate::vote::{ValidatorIndex, Vote};
+use crate::{account, chain, hash::Hash, merkle, vote, Error, Kind, PublicKey, Signature};
 
-use std::convert::{TryFrom, TryInto};
+use alloc::collections::BTreeSet;
+use core::convert::{TryFrom, TryInto};
+use tendermint_proto::abci::ValidatorUpdate as RawValidatorUpdate;
 use tendermint_proto::types::SimpleValidator as RawSimpleValidator;
-use tendermint_proto::types::Validator as RawValidator;
-use tendermint_proto::types::ValidatorSet as RawValidatorSet;
 use tendermint_proto::Protobuf;
 
+/// The maximum total voting power of a validator set, which leaves room for
+/// proposer priority computations not to overflow.
+pub const MAX_TOTAL_VOTING_POWER: u64 = (i64::MAX / 8) as u64;
+
+/// The proposer priorities of a validator set are kept within a window of
+/// this factor times the set's total voting power.
+pub const PRIORITY_WINDOW_SIZE_FACTOR: i64 = 2;
+
 /// Validator set contains a vector of validators
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Set {
@@ -19,45 +31,6 @@ pub struct Set {
     total_voting_power: vote::Power,
 }
 
-impl Protobuf<RawValidatorSet> for Set {}
-
-impl TryFrom<RawValidatorSet> for Set {
-    type Error = Error;
-
-    fn try_from(value: RawValidatorSet) -> Result<Self, Self::Error> {
-        let validators = value
-            .validators
-            .into_iter()
-            .map(TryInto::try_into)
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let proposer = value.proposer.map(TryInto::try_into).transpose()?;
-        let validator_set = Self::new(validators, proposer);
-
-        // Ensure that the raw voting power matches the computed one
-        let raw_voting_power = value.total_voting_power.try_into()?;
-        if raw_voting_power != validator_set.total_voting_power() {
-            return Err(Kind::RawVotingPowerMismatch {
-                raw: raw_voting_power,
-                computed: validator_set.total_voting_power(),
-            }
-            .into());
-        }
-
-        Ok(validator_set)
-    }
-}
-
-impl From<Set> for RawValidatorSet {
-    fn from(value: Set) -> Self {
-        RawValidatorSet {
-            validators: value.validators.into_iter().map(Into::into).collect(),
-            proposer: value.proposer.map(Into::into),
-            total_voting_power: value.total_voting_power.into(),
-        }
-    }
-}
-
 impl Set {
     /// Constructor
     pub fn new(mut validators: Vec<Info>, proposer: Option<Info>) -> Set {
@@ -118,7 +91,7 @@ impl Set {
     /// Sort the validators according to the current Tendermint requirements
     /// (v. 0.34 -> first by validator power, descending, then by address, ascending)
     fn sort_validators(vals: &mut Vec<Info>) {
-        vals.sort_by_key(|v| (std::cmp::Reverse(v.voting_power), v.address));
+        vals.sort_by_key(|v| (core::cmp::Reverse(v.voting_power), v.address));
     }
 
     /// Returns the validator with the given Id if its in the Set.
@@ -129,16 +102,500 @@ impl Set {
             .cloned()
     }
 
+    /// Returns the leaves of the Merkle tree whose root is the hash of this
+    /// validator set, i.e. the `validators_hash` or `next_validators_hash`
+    /// of a block header: the canonical encoding of each validator, in order.
+    pub fn hash_bytes(&self) -> Vec<Vec<u8>> {
+        self.validators()
+            .iter()
+            .map(|validator| validator.hash_bytes())
+            .collect()
+    }
+
     /// Compute the hash of this validator set
     pub fn hash(&self) -> Hash {
-        let validator_bytes: Vec<Vec<u8>> = self
-            .validators()
+        Hash::Sha256(merkle::simple_hash_from_byte_vectors(self.hash_bytes()))
+    }
+}
+
+/// Proposer selection
+impl Set {
+    /// Apply the given validator updates to this validator set, as done by
+    /// Tendermint's `UpdateWithChangeSet`.
+    ///
+    /// Updates with a voting power of zero remove the corresponding
+    /// validator. Validators which are added get a proposer priority of
+    /// `-1.125 * total_voting_power`, so that they cannot reset a negative
+    /// priority by leaving and rejoining the set. The priorities are then
+    /// rescaled and centered around zero.
+    ///
+    /// The set is left unchanged if any of the updates is invalid.
+    pub fn update_with_change_set(&mut self, changes: &[Update]) -> Result<(), Error> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let mut changes: Vec<(account::Id, &Update)> = changes
             .iter()
-            .map(|validator| validator.hash_bytes())
+            .map(|update| (account::Id::from(update.pub_key), update))
             .collect();
+        changes.sort_by_key(|(address, _)| *address);
+        if let Some(w) = changes.windows(2).find(|w| w[0].0 == w[1].0) {
+            return Err(Kind::DuplicateValidatorUpdate(w[0].0).into());
+        }
+        if let Some((address, _)) = changes
+            .iter()
+            .find(|(_, update)| update.power.value() > MAX_TOTAL_VOTING_POWER)
+        {
+            return Err(Kind::InvalidValidatorUpdate
+                .context(format!(
+                    "voting power of validator {} is too large",
+                    address
+                ))
+                .into());
+        }
+        let (removals, updates): (Vec<_>, Vec<_>) = changes
+            .into_iter()
+            .partition(|(_, update)| update.power.value() == 0);
+
+        let mut removed_power = 0_u64;
+        for (address, _) in &removals {
+            match self.validator(*address) {
+                Some(validator) => removed_power += validator.power(),
+                None => return Err(Kind::ValidatorNotFound(*address).into()),
+            }
+        }
+
+        let new_validators = updates
+            .iter()
+            .filter(|(address, _)| self.validator(*address).is_none())
+            .count();
+        if new_validators == 0 && removals.len() == self.validators.len() {
+            return Err(Kind::InvalidValidatorUpdate
+                .context("applying the validator changes would result in an empty set")
+                .into());
+        }
+
+        // The total voting power after applying the updates, but before
+        // applying the removals.
+        let updated_total = updates.iter().fold(
+            i128::from(self.total_voting_power.value()),
+            |total, (address, update)| {
+                let current = self.validator(*address).map_or(0, |v| v.power());
+                total + i128::from(update.power.value()) - i128::from(current)
+            },
+        );
+        if updated_total - i128::from(removed_power) > i128::from(MAX_TOTAL_VOTING_POWER) {
+            return Err(Kind::InvalidValidatorUpdate
+                .context("total voting power would exceed the maximum")
+                .into());
+        }
+        let updated_total = updated_total as i64;
+
+        for (address, update) in updates {
+            match self.validators.iter_mut().find(|v| v.address == address) {
+                Some(validator) => validator.voting_power = update.power,
+                None => {
+                    let mut validator = Info::new(update.pub_key, update.power);
+                    validator.proposer_priority =
+                        ProposerPriority::from(-(updated_total + (updated_total >> 3)));
+                    self.validators.push(validator);
+                }
+            }
+        }
+        self.validators
+            .retain(|v| !removals.iter().any(|(address, _)| *address == v.address));
+
+        self.total_voting_power = self
+            .validators
+            .iter()
+            .map(Info::power)
+            .sum::<u64>()
+            .try_into()?;
+        self.rescale_priorities(PRIORITY_WINDOW_SIZE_FACTOR * self.total_power());
+        self.shift_by_avg_proposer_priority();
+        Self::sort_validators(&mut self.validators);
+        Ok(())
+    }
+
+    /// The validator set resulting from applying the given updates to this
+    /// one, following the rules of [`update_with_change_set`]: an update
+    /// cannot remove a validator which isn't in the set, nor empty it, nor
+    /// make the total voting power exceed [`MAX_TOTAL_VOTING_POWER`], and a
+    /// validator can only be updated once.
+    ///
+    /// [`update_with_change_set`]: Set::update_with_change_set
+    pub fn apply_updates(&self, updates: Vec<Update>) -> Result<Set, Error> {
+        let mut set = self.clone();
+        set.update_with_change_set(&updates)?;
+        Ok(set)
+    }
+
+    /// The minimal updates turning the validators of this set into those of
+    /// `other`, ordered by address: the validators which `other` adds or
+    /// whose voting power it changes, and the validators it removes, with a
+    /// voting power of zero.
+    ///
+    /// Applying these updates results in the validators and voting powers of
+    /// `other`, but not necessarily in its proposer priorities, which depend
+    /// on the history of the set.
+    pub fn diff(&self, other: &Set) -> Vec<Update> {
+        let mut updates: Vec<Update> = other
+            .validators
+            .iter()
+            .filter(|v| {
+                self.validator(v.address)
+                    .map(|current| current.voting_power)
+                    != Some(v.voting_power)
+            })
+            .map(|v| Update {
+                pub_key: v.pub_key,
+                power: v.voting_power,
+            })
+            .chain(
+                self.validators
+                    .iter()
+                    .filter(|v| other.validator(v.address).is_none())
+                    .map(|v| Update {
+                        pub_key: v.pub_key,
+                        power: vote::Power::default(),
+                    }),
+            )
+            .collect();
+        updates.sort_by_key(|update| account::Id::from(update.pub_key));
+        updates
+    }
+
+    /// Increment the proposer priorities of the validators the given number
+    /// of times (i.e. rounds), selecting a new proposer each time, as done
+    /// by Tendermint's `IncrementProposerPriority`. The set's proposer is
+    /// the one selected last.
+    pub fn increment_proposer_priority(&mut self, times: u32) {
+        if times == 0 || self.validators.is_empty() {
+            return;
+        }
+        self.rescale_priorities(PRIORITY_WINDOW_SIZE_FACTOR * self.total_power());
+        self.shift_by_avg_proposer_priority();
+
+        for _ in 0..times {
+            let total = self.total_power();
+            for validator in &mut self.validators {
+                validator.proposer_priority = validator
+                    .proposer_priority
+                    .value()
+                    .saturating_add(validator.power() as i64)
+                    .into();
+            }
+            let proposer = self.validator_with_most_priority();
+            proposer.proposer_priority = proposer
+                .proposer_priority
+                .value()
+                .saturating_sub(total)
+                .into();
+            self.proposer = Some(*proposer);
+        }
+    }
+
+    /// The proposer for the given round, `heights` heights after the one
+    /// for which the proposer priorities of this set are current, assuming
+    /// that the set does not change in between.
+    ///
+    /// `proposer_for(0, Round::default())` is the set's current proposer
+    /// (or, if none is recorded, the validator with the highest priority).
+    pub fn proposer_for(&self, heights: u64, round: block::Round) -> Option<Info> {
+        if self.validators.is_empty() {
+            return None;
+        }
+        let mut set = self.clone();
+        for _ in 0..heights {
+            set.increment_proposer_priority(1);
+        }
+        set.increment_proposer_priority(round.value());
+        match set.proposer {
+            Some(proposer) => Some(proposer),
+            None => Some(*set.validator_with_most_priority()),
+        }
+    }
+
+    fn total_power(&self) -> i64 {
+        // Bounded by `MAX_TOTAL_VOTING_POWER` for valid sets.
+        self.total_voting_power.value() as i64
+    }
+
+    // Scale down the priorities such that the distance between the
+    // highest and the lowest one is at most `diff_max`.
+    fn rescale_priorities(&mut self, diff_max: i64) {
+        if diff_max <= 0 || self.validators.is_empty() {
+            return;
+        }
+        let priorities = self.validators.iter().map(|v| v.proposer_priority.value());
+        let max = i128::from(priorities.clone().max().unwrap_or_default());
+        let min = i128::from(priorities.min().unwrap_or_default());
+        let diff = max - min;
+        let diff_max = i128::from(diff_max);
+        if diff > diff_max {
+            let ratio = (diff + diff_max - 1) / diff_max;
+            for validator in &mut self.validators {
+                let priority = i128::from(validator.proposer_priority.value()) / ratio;
+                validator.proposer_priority = (priority as i64).into();
+            }
+        }
+    }
+
+    fn shift_by_avg_proposer_priority(&mut self) {
+        if self.validators.is_empty() {
+            return;
+        }
+        let sum: i128 = self
+            .validators
+            .iter()
+            .map(|v| i128::from(v.proposer_priority.value()))
+            .sum();
+        let avg = sum.div_euclid(self.validators.len() as i128) as i64;
+        for validator in &mut self.validators {
+            validator.proposer_priority = validator
+                .proposer_priority
+                .value()
+                .saturating_sub(avg)
+                .into();
+        }
+    }
+
+    // Ties are broken in favor of the validator with the lowest address.
+    fn validator_with_most_priority(&mut self) -> &mut Info {
+        self.validators
+            .iter_mut()
+            .max_by(|a, b| {
+                a.proposer_priority
+                    .cmp(&b.proposer_priority)
+                    .then_with(|| b.address.cmp(&a.address))
+            })
+            .expect("validator set is empty")
+    }
+}
+
+/// Commit verification
+impl Set {
+    /// Verify that more than 2/3 of the voting power of this validator set
+    /// signed the given commit for the given block ID and height, as done
+    /// by Tendermint's `VerifyCommit`.
+    ///
+    /// The commit must contain exactly one signature slot per validator, in
+    /// the order of the validator set. All signatures, including those for
+    /// nil, are verified.
+    pub fn verify_commit(
+        &self,
+        chain_id: &chain::Id,
+        block_id: &block::Id,
+        height: block::Height,
+        commit: &Commit,
+    ) -> Result<(), Error> {
+        if self.validators.len() != commit.signatures.len() {
+            return Err(Kind::SignatureCountMismatch {
+                validators: self.validators.len(),
+                signatures: commit.signatures.len(),
+            }
+            .into());
+        }
+        if height != commit.height {
+            return Err(Kind::HeightMismatch {
+                header: height,
+                commit: commit.height,
+            }
+            .into());
+        }
+        if block_id != &commit.block_id {
+            return Err(Kind::BlockIdMismatch {
+                expected: *block_id,
+                got: commit.block_id,
+            }
+            .into());
+        }
+
+        let mut tallied = 0_u64;
+        let mut votes = Vec::with_capacity(commit.signatures.len());
+        for (idx, (validator, sig)) in self.validators.iter().zip(&commit.signatures).enumerate() {
+            if let Some(vote) = commit_vote(commit, idx, sig)? {
+                if sig.is_commit() {
+                    tallied += validator.power();
+                }
+                votes.push((validator, vote));
+            }
+        }
+        verify_votes(&votes, chain_id)?;
+
+        let total = self.total_voting_power.value();
+        if TrustThresholdFraction::TWO_THIRDS.is_enough_power(tallied, total) {
+            Ok(())
+        } else {
+            Err(Kind::InsufficientVotingPower { tallied, total }.into())
+        }
+    }
+
+    /// Verify that validators of this (trusted) validator set holding more
+    /// than `trust_threshold` of its voting power signed the given commit,
+    /// as done by Tendermint's `VerifyCommitLightTrusting`.
+    ///
+    /// Validators are matched by address, so the commit may come from a
+    /// different validator set. Only signatures for the committed block are
+    /// verified, and verification stops as soon as enough voting power has
+    /// been tallied.
+    pub fn verify_commit_light_trusting<T>(
+        &self,
+        chain_id: &chain::Id,
+        commit: &Commit,
+        trust_threshold: T,
+    ) -> Result<(), Error>
+    where
+        T: TrustThreshold,
+    {
+        let total = self.total_voting_power.value();
+        let mut tallied = 0_u64;
+        let mut seen_validators = BTreeSet::new();
+
+        for (idx, sig) in commit.signatures.iter().enumerate() {
+            if !sig.is_commit() {
+                continue;
+            }
+            let vote = match commit_vote(commit, idx, sig)? {
+                Some(vote) => vote,
+                None => continue,
+            };
+            let validator = match self.validator(vote.validator_address) {
+                Some(validator) => validator,
+                None => continue,
+            };
+            if !seen_validators.insert(validator.address) {
+                return Err(Kind::DuplicateVote(validator.address).into());
+            }
+
+            verify_vote(&validator, &vote, chain_id)?;
+            tallied += validator.power();
+            if trust_threshold.is_enough_power(tallied, total) {
+                return Ok(());
+            }
+        }
+
+        Err(Kind::InsufficientVotingPower { tallied, total }.into())
+    }
+}
+
+// The vote signed by the validator at the given index of the commit, if
+// the validator's vote is not absent.
+fn commit_vote(commit: &Commit, idx: usize, sig: &CommitSig) -> Result<Option<Vote>, Error> {
+    let (validator_address, timestamp, signature, block_id) = match sig {
+        CommitSig::BlockIdFlagAbsent => return Ok(None),
+        CommitSig::BlockIdFlagCommit {
+            validator_address,
+            timestamp,
+            signature,
+        } => (
+            validator_address,
+            timestamp,
+            signature,
+            Some(commit.block_id),
+        ),
+        CommitSig::BlockIdFlagNil {
+            validator_address,
+            timestamp,
+            signature,
+        } => (validator_address, timestamp, signature, None),
+    };
+    Ok(Some(Vote {
+        vote_type: vote::Type::Precommit,
+        height: commit.height,
+        round: commit.round,
+        block_id,
+        timestamp: Some(*timestamp),
+        validator_address: *validator_address,
+        validator_index: ValidatorIndex::try_from(idx)?,
+        signature: *signature,
+        extension: vec![],
+        extension_signature: None,
+    }))
+}
+
+fn verify_votes(votes: &[(&Info, Vote)], chain_id: &chain::Id) -> Result<(), Error> {
+    let sign_bytes = votes
+        .iter()
+        .map(|(_, vote)| vote_sign_bytes(vote, chain_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let signatures: Vec<_> = votes
+        .iter()
+        .zip(&sign_bytes)
+        .map(|((validator, vote), sign_bytes)| (*validator, sign_bytes.as_slice(), &vote.signature))
+        .collect();
+    verify_signatures(&signatures)
+}
+
+/// Verify the signatures of the given validators over the given messages,
+/// e.g. over the sign bytes of their votes in a commit.
+///
+/// With the `batch-verify` feature, Ed25519 signatures are first verified as
+/// a single batch, which is considerably faster than verifying them one by
+/// one for large validator sets. If the batch does not verify, its
+/// signatures are verified one by one, and that outcome is final, so that
+/// signatures are never rejected because of batch verification alone.
+pub fn verify_signatures(signatures: &[(&Info, &[u8], &Signature)]) -> Result<(), Error> {
+    #[cfg(feature = "batch-verify")]
+    {
+        if verify_batch(signatures) {
+            return Ok(());
+        }
+    }
+    signatures
+        .iter()
+        .try_for_each(|(validator, sign_bytes, signature)| {
+            verify_signature(validator, sign_bytes, signature)
+        })
+}
+
+// Whether all the given signatures verify, the Ed25519 ones as a batch and
+// the others one by one.
+#[cfg(feature = "batch-verify")]
+fn verify_batch(signatures: &[(&Info, &[u8], &Signature)]) -> bool {
+    use ed25519_consensus::{batch, VerificationKeyBytes};
 
-        Hash::Sha256(merkle::simple_hash_from_byte_vectors(validator_bytes))
+    let mut verifier = batch::Verifier::new();
+    for (validator, sign_bytes, signature) in signatures {
+        match (validator.pub_key.ed25519(), signature.ed25519()) {
+            (Some(pub_key), Some(signature)) => verifier.queue((
+                VerificationKeyBytes::from(pub_key.to_bytes()),
+                ed25519_consensus::Signature::from(signature.to_bytes()),
+                sign_bytes,
+            )),
+            _ => {
+                if verify_signature(validator, sign_bytes, signature).is_err() {
+                    return false;
+                }
+            }
+        }
     }
+    verifier.verify(rand_core::OsRng).is_ok()
+}
+
+fn vote_sign_bytes(vote: &Vote, chain_id: &chain::Id) -> Result<Vec<u8>, Error> {
+    vote.to_signable_vec(chain_id.clone())
+        .map_err(|e| Kind::InvalidCommit.context(e).into())
+}
+
+fn verify_vote(validator: &Info, vote: &Vote, chain_id: &chain::Id) -> Result<(), Error> {
+    let sign_bytes = vote_sign_bytes(vote, chain_id)?;
+    verify_signature(validator, &sign_bytes, &vote.signature)
+}
+
+fn verify_signature(
+    validator: &Info,
+    sign_bytes: &[u8],
+    signature: &Signature,
+) -> Result<(), Error> {
+    validator
+        .verify_signature(sign_bytes, signature)
+        .map_err(|e| {
+            Kind::InvalidCommitSignature(validator.address)
+                .context(e)
+                .into()
+        })
 }
 
 /// Validator information
@@ -161,26 +618,72 @@ pub struct Info {
     pub proposer_priority: ProposerPriority,
 }
 
-impl TryFrom<RawValidator> for Info {
-    type Error = Error;
+tendermint_pb_modules! {
+    use super::{Info, Set};
+    use crate::{Error, Kind};
+    use core::convert::{TryFrom, TryInto};
+    use pb::types::{Validator as RawValidator, ValidatorSet as RawValidatorSet};
 
-    fn try_from(value: RawValidator) -> Result<Self, Self::Error> {
-        Ok(Info {
-            address: value.address.try_into()?,
-            pub_key: value.pub_key.ok_or(Kind::MissingPublicKey)?.try_into()?,
-            voting_power: value.voting_power.try_into()?,
-            proposer_priority: value.proposer_priority.try_into()?,
-        })
+    impl Protobuf<RawValidatorSet> for Set {}
+
+    impl TryFrom<RawValidatorSet> for Set {
+        type Error = Error;
+
+        fn try_from(value: RawValidatorSet) -> Result<Self, Self::Error> {
+            let validators = value
+                .validators
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let proposer = value.proposer.map(TryInto::try_into).transpose()?;
+            let validator_set = Self::new(validators, proposer);
+
+            // Ensure that the raw voting power matches the computed one
+            let raw_voting_power = value.total_voting_power.try_into()?;
+            if raw_voting_power != validator_set.total_voting_power() {
+                return Err(Kind::RawVotingPowerMismatch {
+                    raw: raw_voting_power,
+                    computed: validator_set.total_voting_power(),
+                }
+                .into());
+            }
+
+            Ok(validator_set)
+        }
     }
-}
 
-impl From<Info> for RawValidator {
-    fn from(value: Info) -> Self {
-        RawValidator {
-            address: value.address.into(),
-            pub_key: Some(value.pub_key.into()),
-            voting_power: value.voting_power.into(),
-            proposer_priority: value.proposer_priority.into(),
+    impl From<Set> for RawValidatorSet {
+        fn from(value: Set) -> Self {
+            RawValidatorSet {
+                validators: value.validators.into_iter().map(Into::into).collect(),
+                proposer: value.proposer.map(Into::into),
+                total_voting_power: value.total_voting_power.into(),
+            }
+        }
+    }
+
+    impl TryFrom<RawValidator> for Info {
+        type Error = Error;
+
+        fn try_from(value: RawValidator) -> Result<Self, Self::Error> {
+            Ok(Info {
+                address: value.address.try_into()?,
+                pub_key: value.pub_key.ok_or(Kind::MissingPublicKey)?.try_into()?,
+                voting_power: value.voting_power.try_into()?,
+                proposer_priority: value.proposer_priority.into(),
+            })
+        }
+    }
+
+    impl From<Info> for RawValidator {
+        fn from(value: Info) -> Self {
+            RawValidator {
+                address: value.address.into(),
+                pub_key: Some(value.pub_key.into()),
+                voting_power: value.voting_power.into(),
+                proposer_priority: value.proposer_priority.into(),
+            }
         }
     }
 }
@@ -204,6 +707,10 @@ impl From<PublicKey> for account::Id {
             PublicKey::Ed25519(pk) => account::Id::from(pk),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(pk) => account::Id::from(pk),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => account::Id::from(pk),
+            #[cfg(feature = "bls12381")]
+            PublicKey::Bls12381(pk) => account::Id::from(pk),
         }
     }
 }
@@ -224,7 +731,6 @@ impl Info {
 /// It does not include the address, as that is redundant with the pubkey,
 /// nor the proposer priority, as that changes with every block even if the validator set didn't.
 /// It contains only the pubkey and the voting power.
-/// TODO: currently only works for Ed25519 pubkeys
 #[derive(Clone, PartialEq)]
 pub struct SimpleValidator {
     /// Public key
@@ -258,17 +764,8 @@ impl From<SimpleValidator> for RawSimpleValidator {
 /// Info -> SimpleValidator
 impl From<&Info> for SimpleValidator {
     fn from(info: &Info) -> SimpleValidator {
-        let sum = match &info.pub_key {
-            PublicKey::Ed25519(pk) => Some(tendermint_proto::crypto::public_key::Sum::Ed25519(
-                pk.as_bytes().to_vec(),
-            )),
-            #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(pk) => Some(tendermint_proto::crypto::public_key::Sum::Secp256k1(
-                pk.as_bytes().to_vec(),
-            )),
-        };
         SimpleValidator {
-            pub_key: Some(tendermint_proto::crypto::PublicKey { sum }),
+            pub_key: Some(info.pub_key.into()),
             voting_power: info.voting_power,
         }
     }
@@ -318,6 +815,26 @@ pub struct Update {
     pub power: vote::Power,
 }
 
+impl TryFrom<RawValidatorUpdate> for Update {
+    type Error = Error;
+
+    fn try_from(value: RawValidatorUpdate) -> Result<Self, Self::Error> {
+        Ok(Update {
+            pub_key: value.pub_key.ok_or(Kind::MissingPublicKey)?.try_into()?,
+            power: value.power.try_into()?,
+        })
+    }
+}
+
+impl From<Update> for RawValidatorUpdate {
+    fn from(value: Update) -> Self {
+        RawValidatorUpdate {
+            pub_key: Some(value.pub_key.into()),
+            power: value.power.into(),
+        }
+    }
+}
+
 /// Validator updates use a slightly different public key format than the one
 /// implemented in `tendermint::PublicKey`.
 ///
@@ -350,6 +867,8 @@ where
 mod tests {
 
     use super::*;
+    use crate::signature::Signer;
+    use crate::test::signing_key;
 
     // make a validator
     fn make_validator(pk: Vec<u8>, vp: u64) -> Info {
@@ -430,4 +949,273 @@ mod tests {
             148_151_478_422_287_875 + 158_095_448_483_785_107 + 770_561_664_770_006_272
         );
     }
+
+    // Builds a commit for the given validator set, signed by the validators
+    // whose key is given (all others are absent).
+    fn signed_commit(
+        chain_id: &chain::Id,
+        val_set: &Set,
+        keys: &[crate::private_key::Ed25519],
+    ) -> Commit {
+        Commit::builder()
+            .height(block::Height::from(5_u32))
+            .block_id(block::Id {
+                hash: Hash::Sha256([1; 32]),
+                part_set_header: block::parts::Header::new(1, Hash::Sha256([2; 32])).unwrap(),
+            })
+            .timestamp(crate::Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap())
+            .sign(chain_id.clone(), val_set, keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn commit_verification() {
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+        let keys: Vec<_> = (1..=3).map(signing_key).collect();
+        let val_set = Set::without_proposer(
+            keys.iter()
+                .map(|key| Info::new(key.public.into(), vote::Power::from(10_u32)))
+                .collect(),
+        );
+
+        let commit = signed_commit(&chain_id, &val_set, &keys);
+        val_set
+            .verify_commit(&chain_id, &commit.block_id, commit.height, &commit)
+            .unwrap();
+        assert!(val_set
+            .verify_commit(
+                &chain_id,
+                &commit.block_id,
+                block::Height::from(6_u32),
+                &commit
+            )
+            .is_err());
+        assert!(val_set
+            .verify_commit(
+                &"other-chain".parse().unwrap(),
+                &commit.block_id,
+                commit.height,
+                &commit
+            )
+            .is_err());
+
+        let mut tampered = commit.clone();
+        if let CommitSig::BlockIdFlagCommit { signature, .. } = &mut tampered.signatures[1] {
+            let mut bytes = signature.as_bytes().to_vec();
+            bytes[0] ^= 1;
+            *signature = Signature::try_from(bytes).unwrap();
+        }
+        assert!(val_set
+            .verify_commit(&chain_id, &commit.block_id, commit.height, &tampered)
+            .is_err());
+
+        // 2/3 of the voting power is not enough for a commit, but is more
+        // than enough to be trusted.
+        let commit = signed_commit(&chain_id, &val_set, &keys[..2]);
+        assert!(val_set
+            .verify_commit(&chain_id, &commit.block_id, commit.height, &commit)
+            .is_err());
+        val_set
+            .verify_commit_light_trusting(&chain_id, &commit, TrustThresholdFraction::default())
+            .unwrap();
+
+        let commit = signed_commit(&chain_id, &val_set, &keys[..1]);
+        assert!(val_set
+            .verify_commit_light_trusting(&chain_id, &commit, TrustThresholdFraction::default())
+            .is_err());
+    }
+
+    #[test]
+    fn signature_verification() {
+        let keys: Vec<_> = (1..=3).map(signing_key).collect();
+        let validators: Vec<_> = keys
+            .iter()
+            .map(|key| Info::new(key.public.into(), vote::Power::from(10_u32)))
+            .collect();
+        let messages: Vec<_> = (0..3_u8).map(|i| vec![i; 8]).collect();
+        let mut signatures: Vec<_> = keys
+            .iter()
+            .zip(&messages)
+            .map(|(key, message)| Signature::from(key.sign(message)))
+            .collect();
+
+        fn batch<'a>(
+            validators: &'a [Info],
+            messages: &'a [Vec<u8>],
+            signatures: &'a [Signature],
+        ) -> Vec<(&'a Info, &'a [u8], &'a Signature)> {
+            validators
+                .iter()
+                .zip(messages)
+                .zip(signatures)
+                .map(|((validator, message), signature)| (validator, message.as_slice(), signature))
+                .collect()
+        }
+        verify_signatures(&batch(&validators, &messages, &signatures)).unwrap();
+
+        let mut bytes = signatures[2].as_bytes().to_vec();
+        bytes[0] ^= 1;
+        signatures[2] = Signature::try_from(bytes).unwrap();
+        let err = verify_signatures(&batch(&validators, &messages, &signatures)).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &Kind::InvalidCommitSignature(validators[2].address)
+        );
+    }
+
+    #[test]
+    fn bft_time() {
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+        let keys: Vec<_> = (1..=3).map(signing_key).collect();
+        let val_set = Set::without_proposer(
+            keys.iter()
+                .zip(1_u32..)
+                .map(|(key, power)| Info::new(key.public.into(), vote::Power::from(power)))
+                .collect(),
+        );
+        let time = |secs| crate::Time::from_unix_timestamp(secs, 0).unwrap();
+        // Each validator votes at 10 times its voting power, in seconds
+        let with_timestamps = |mut commit: Commit| {
+            for (validator, sig) in val_set.validators().iter().zip(&mut commit.signatures) {
+                if let CommitSig::BlockIdFlagCommit { timestamp, .. } = sig {
+                    *timestamp = time(10 * validator.power() as i64);
+                }
+            }
+            commit
+        };
+
+        let commit = with_timestamps(signed_commit(&chain_id, &val_set, &keys));
+        assert_eq!(commit.median_time(&val_set).unwrap(), time(20));
+        // Absent validators don't count
+        let partial = with_timestamps(signed_commit(&chain_id, &val_set, &keys[..2]));
+        assert_eq!(partial.median_time(&val_set).unwrap(), time(10));
+        let absent = signed_commit(&chain_id, &val_set, &[]);
+        assert!(absent.median_time(&val_set).is_err());
+
+        let mut header: block::Header = serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/header.json"
+        ))
+        .unwrap();
+        header.height = commit.height.increment();
+        header.time = time(20);
+        header.validate_bft_time(&commit, &val_set).unwrap();
+
+        header.time = time(30);
+        assert_eq!(
+            header
+                .validate_bft_time(&commit, &val_set)
+                .unwrap_err()
+                .kind(),
+            &Kind::BftTimeMismatch {
+                header: time(30),
+                median: time(20),
+            }
+        );
+
+        header.time = time(20);
+        header.height = header.height.increment();
+        assert!(header.validate_bft_time(&commit, &val_set).is_err());
+    }
+
+    #[test]
+    fn proposer_selection() {
+        let keys: Vec<_> = (1..=3).map(signing_key).collect();
+        let mut val_set = Set::without_proposer(
+            keys.iter()
+                .zip(1_u32..)
+                .map(|(key, power)| Info::new(key.public.into(), vote::Power::from(power)))
+                .collect(),
+        );
+
+        // Proposers are selected in proportion to their voting power.
+        let mut proposed = std::collections::HashMap::new();
+        for _ in 0..60 {
+            val_set.increment_proposer_priority(1);
+            let proposer = val_set.proposer().unwrap();
+            *proposed.entry(proposer.power()).or_insert(0) += 1;
+        }
+        assert_eq!(10, proposed[&1]);
+        assert_eq!(20, proposed[&2]);
+        assert_eq!(30, proposed[&3]);
+
+        assert_eq!(
+            val_set.proposer_for(0, block::Round::default()),
+            *val_set.proposer()
+        );
+        let mut next = val_set.clone();
+        next.increment_proposer_priority(1);
+        next.increment_proposer_priority(2);
+        assert_eq!(
+            val_set.proposer_for(1, block::Round::from(2_u8)),
+            *next.proposer()
+        );
+    }
+
+    #[test]
+    fn validator_set_updates() {
+        let keys: Vec<_> = (1..=3).map(signing_key).collect();
+        let update = |key: &crate::private_key::Ed25519, power: u32| Update {
+            pub_key: key.public.into(),
+            power: vote::Power::from(power),
+        };
+        let mut val_set = Set::without_proposer(vec![
+            Info::new(keys[0].public.into(), vote::Power::from(10_u32)),
+            Info::new(keys[1].public.into(), vote::Power::from(10_u32)),
+        ]);
+
+        val_set
+            .update_with_change_set(&[update(&keys[1], 0), update(&keys[2], 20)])
+            .unwrap();
+        assert_eq!(30, val_set.total_voting_power().value());
+        assert_eq!(2, val_set.validators().len());
+        // The new validator starts with a priority of -1.125 * 40, shifted by
+        // the average priority.
+        let added = val_set
+            .validator(account::Id::from(keys[2].public))
+            .unwrap();
+        assert_eq!(20, added.power());
+        assert_eq!(-22, added.proposer_priority.value());
+        assert!(val_set
+            .validator(account::Id::from(keys[1].public))
+            .is_none());
+
+        // Invalid updates leave the set unchanged.
+        let before = val_set.clone();
+        assert!(val_set
+            .update_with_change_set(&[update(&keys[1], 0)])
+            .is_err());
+        assert!(val_set
+            .update_with_change_set(&[update(&keys[0], 1), update(&keys[0], 2)])
+            .is_err());
+        assert!(val_set
+            .update_with_change_set(&[update(&keys[0], 0), update(&keys[2], 0)])
+            .is_err());
+        assert_eq!(before, val_set);
+    }
+
+    #[test]
+    fn validator_set_diffs() {
+        let keys: Vec<_> = (1..=3).map(signing_key).collect();
+        let validator =
+            |i: usize, power: u32| Info::new(keys[i].public.into(), vote::Power::from(power));
+        let old = Set::without_proposer(vec![validator(0, 10), validator(1, 10)]);
+        let new = Set::without_proposer(vec![validator(0, 10), validator(1, 5), validator(2, 20)]);
+
+        let updates = old.diff(&new);
+        assert_eq!(updates.len(), 2);
+        assert!(old.diff(&old).is_empty());
+        let applied = old.apply_updates(updates).unwrap();
+        assert!(applied.diff(&new).is_empty());
+        assert_eq!(applied.total_voting_power(), new.total_voting_power());
+
+        let removals = new.diff(&old);
+        assert!(removals.iter().any(|update| update.power.value() == 0
+            && account::Id::from(update.pub_key) == account::Id::from(keys[2].public)));
+        assert!(new.apply_updates(removals).unwrap().diff(&old).is_empty());
+
+        // Invalid updates are rejected
+        assert!(old
+            .apply_updates(old.diff(&Set::without_proposer(vec![])))
+            .is_err());
+    }
 }
@@ -1,30 +1,38 @@
 //! Votes from validators
 
+mod builder;
 mod canonical_vote;
+mod canonical_vote_extension;
 mod power;
 mod sign_vote;
 mod validator_index;
+mod vote_set;
 
+pub use self::builder::VoteBuilder;
 pub use self::canonical_vote::CanonicalVote;
+pub use self::canonical_vote_extension::CanonicalVoteExtension;
 pub use self::power::Power;
 pub use self::sign_vote::*;
 pub use self::validator_index::ValidatorIndex;
+pub use self::vote_set::{AddedVote, VoteSet};
 use crate::chain::Id as ChainId;
 use crate::consensus::State;
 use crate::hash;
-use crate::{account, block, Signature, Time};
+use crate::prelude::*;
+use crate::signature::Signer;
+use crate::{account, block, PublicKey, Signature, Time};
 use crate::{Error, Kind::*};
 use bytes::BufMut;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
 use ed25519::Signature as ed25519Signature;
 use ed25519::SIGNATURE_LENGTH as ed25519SignatureLength;
 use serde::{Deserialize, Serialize};
-use std::convert::{TryFrom, TryInto};
-use std::fmt;
 use tendermint_proto::types::Vote as RawVote;
 use tendermint_proto::{Error as ProtobufError, Protobuf};
 
 use crate::signature::Signature::Ed25519;
-use std::str::FromStr;
+use core::str::FromStr;
 
 /// Votes are signed messages from validators for a particular block which
 /// include information about the validator signing it.
@@ -56,6 +64,13 @@ pub struct Vote {
 
     /// Signature
     pub signature: Signature,
+
+    /// Vote extension provided by the application (ABCI++), only allowed in
+    /// precommits for a block
+    pub extension: Vec<u8>,
+
+    /// Signature of the vote extension, if any
+    pub extension_signature: Option<Signature>,
 }
 
 impl Protobuf<RawVote> for Vote {}
@@ -81,6 +96,12 @@ impl TryFrom<RawVote> for Vote {
             validator_address: value.validator_address.try_into()?,
             validator_index: value.validator_index.try_into()?,
             signature: value.signature.try_into()?,
+            extension: value.extension,
+            extension_signature: if value.extension_signature.is_empty() {
+                None
+            } else {
+                Some(value.extension_signature.try_into()?)
+            },
         })
     }
 }
@@ -96,11 +117,22 @@ impl From<Vote> for RawVote {
             validator_address: value.validator_address.into(),
             validator_index: value.validator_index.into(),
             signature: value.signature.into(),
+            extension: value.extension,
+            extension_signature: value
+                .extension_signature
+                .map(|signature| signature.as_bytes().to_vec())
+                .unwrap_or_default(),
         }
     }
 }
 
 impl Vote {
+    /// Start building a vote, which can then be signed in one go with
+    /// [`VoteBuilder::sign`].
+    pub fn builder() -> VoteBuilder {
+        VoteBuilder::default()
+    }
+
     /// Is this vote a prevote?
     pub fn is_prevote(&self) -> bool {
         match self.vote_type {
@@ -123,6 +155,9 @@ impl Vote {
     }
 
     /// Create signable bytes from Vote.
+    ///
+    /// These are the length-delimited protobuf encoding of the
+    /// [`CanonicalVote`] for the given chain, as signed by validators.
     pub fn to_signable_bytes<B>(
         &self,
         chain_id: ChainId,
@@ -140,6 +175,92 @@ impl Vote {
         CanonicalVote::new(self.clone(), chain_id).encode_length_delimited_vec()
     }
 
+    /// Sign this vote for the given chain, replacing its signature.
+    pub fn sign<S>(&mut self, chain_id: ChainId, signer: &S) -> Result<(), Error>
+    where
+        S: Signer<ed25519Signature>,
+    {
+        let sign_bytes = self
+            .to_signable_vec(chain_id)
+            .map_err(|e| Protocol.context(e))?;
+        let signature = signer
+            .try_sign(&sign_bytes)
+            .map_err(|e| Crypto.context(e))?;
+        self.signature = signature.into();
+        Ok(())
+    }
+
+    /// Verify the signature of this vote for the given chain with the given
+    /// public key.
+    pub fn verify(&self, chain_id: ChainId, pub_key: &PublicKey) -> Result<(), Error> {
+        let sign_bytes = self
+            .to_signable_vec(chain_id)
+            .map_err(|e| Protocol.context(e))?;
+        pub_key.verify(&sign_bytes, &self.signature)
+    }
+
+    /// Create the signable vector of this vote's extension.
+    ///
+    /// This is the length-delimited protobuf encoding of the
+    /// [`CanonicalVoteExtension`] for the given chain.
+    pub fn extension_sign_bytes(&self, chain_id: ChainId) -> Result<Vec<u8>, ProtobufError> {
+        CanonicalVoteExtension::new(self.clone(), chain_id).encode_length_delimited_vec()
+    }
+
+    /// Sign the extension of this vote for the given chain, replacing its
+    /// extension signature.
+    ///
+    /// Only precommits for a block may carry an extension.
+    pub fn sign_extension<S>(&mut self, chain_id: ChainId, signer: &S) -> Result<(), Error>
+    where
+        S: Signer<ed25519Signature>,
+    {
+        self.ensure_extendable()?;
+        let sign_bytes = self
+            .extension_sign_bytes(chain_id)
+            .map_err(|e| Protocol.context(e))?;
+        let signature = signer
+            .try_sign(&sign_bytes)
+            .map_err(|e| Crypto.context(e))?;
+        self.extension_signature = Some(signature.into());
+        Ok(())
+    }
+
+    /// Verify the extension signature of this vote for the given chain with
+    /// the given public key.
+    ///
+    /// Prevotes and precommits for nil must not carry an extension or an
+    /// extension signature, whereas precommits for a block must carry an
+    /// extension signature (even if the extension itself is empty).
+    pub fn verify_extension(&self, chain_id: ChainId, pub_key: &PublicKey) -> Result<(), Error> {
+        if !self.is_precommit() || self.block_id.is_none() {
+            if !self.extension.is_empty() || self.extension_signature.is_some() {
+                return Err(InvalidVote
+                    .context("vote extensions are only allowed in precommits for a block")
+                    .into());
+            }
+            return Ok(());
+        }
+        let signature = self
+            .extension_signature
+            .as_ref()
+            .ok_or_else(|| InvalidVote.context("missing vote extension signature"))?;
+        let sign_bytes = self
+            .extension_sign_bytes(chain_id)
+            .map_err(|e| Protocol.context(e))?;
+        pub_key.verify(&sign_bytes, signature)
+    }
+
+    fn ensure_extendable(&self) -> Result<(), Error> {
+        if self.is_precommit() && self.block_id.is_some() {
+            Ok(())
+        } else {
+            Err(InvalidVote
+                .context("vote extensions are only allowed in precommits for a block")
+                .into())
+        }
+    }
+
     /// Consensus state from this vote - This doesn't seem to be used anywhere.
     #[deprecated(
         since = "0.17.0",
@@ -167,6 +288,8 @@ impl Default for Vote {
             validator_address: account::Id::new([0; account::LENGTH]),
             validator_index: ValidatorIndex::try_from(0_i32).unwrap(),
             signature: Ed25519(ed25519Signature::new([0; ed25519SignatureLength])),
+            extension: vec![],
+            extension_signature: None,
         }
     }
 }
@@ -0,0 +1,145 @@
+//! Builder for votes, mostly useful in tests
+
+use super::{Type, ValidatorIndex, Vote};
+use crate::chain::Id as ChainId;
+use crate::prelude::*;
+use crate::signature::Signer;
+use crate::{account, block, Error, Time};
+use ed25519::Signature as ed25519Signature;
+
+/// Builder for a [`Vote`], as returned by [`Vote::builder`].
+///
+/// Starts from a precommit for nil at the default height and round, with the
+/// UNIX epoch as its timestamp.
+#[derive(Clone, Debug)]
+pub struct VoteBuilder {
+    vote: Vote,
+}
+
+impl Default for VoteBuilder {
+    fn default() -> Self {
+        VoteBuilder {
+            vote: Vote {
+                vote_type: Type::Precommit,
+                ..Vote::default()
+            },
+        }
+    }
+}
+
+impl VoteBuilder {
+    /// Set the type of the vote
+    pub fn vote_type(mut self, vote_type: Type) -> Self {
+        self.vote.vote_type = vote_type;
+        self
+    }
+
+    /// Set the height of the vote
+    pub fn height(mut self, height: block::Height) -> Self {
+        self.vote.height = height;
+        self
+    }
+
+    /// Set the round of the vote
+    pub fn round(mut self, round: block::Round) -> Self {
+        self.vote.round = round;
+        self
+    }
+
+    /// Vote for the given block
+    pub fn block_id(mut self, block_id: block::Id) -> Self {
+        self.vote.block_id = Some(block_id);
+        self
+    }
+
+    /// Vote for nil
+    pub fn nil(mut self) -> Self {
+        self.vote.block_id = None;
+        self
+    }
+
+    /// Vote for the block with the given header, at its height and time.
+    ///
+    /// The part set header of the block ID is left empty.
+    pub fn header(mut self, header: &block::Header) -> Self {
+        self.vote.height = header.height;
+        self.vote.timestamp = Some(header.time);
+        self.vote.block_id = Some(block::Id::new(header.hash(), Default::default()));
+        self
+    }
+
+    /// Set the timestamp of the vote
+    pub fn timestamp(mut self, timestamp: Time) -> Self {
+        self.vote.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Set the address and index in the validator set of the voting validator
+    pub fn validator(mut self, address: account::Id, index: ValidatorIndex) -> Self {
+        self.vote.validator_address = address;
+        self.vote.validator_index = index;
+        self
+    }
+
+    /// Set the vote extension
+    pub fn extension(mut self, extension: Vec<u8>) -> Self {
+        self.vote.extension = extension;
+        self
+    }
+
+    /// Build the vote, leaving it unsigned (i.e. with a zero signature)
+    pub fn build(self) -> Vote {
+        self.vote
+    }
+
+    /// Build the vote and sign it for the given chain.
+    ///
+    /// Precommits for a block get their extension signed as well.
+    pub fn sign<S>(self, chain_id: ChainId, signer: &S) -> Result<Vote, Error>
+    where
+        S: Signer<ed25519Signature>,
+    {
+        let mut vote = self.vote;
+        vote.sign(chain_id.clone(), signer)?;
+        if vote.is_precommit() && vote.block_id.is_some() {
+            vote.sign_extension(chain_id, signer)?;
+        }
+        Ok(vote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::signing_key;
+    use crate::Hash;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn builds_signed_votes() {
+        let key = signing_key(1);
+        let chain_id: ChainId = "test-chain".parse().unwrap();
+        let block_id = block::Id::new(Hash::Sha256([1; 32]), Default::default());
+
+        let vote = Vote::builder()
+            .height(block::Height::from(3_u32))
+            .block_id(block_id)
+            .validator(key.public.into(), ValidatorIndex::try_from(0_u32).unwrap())
+            .extension(b"extension".to_vec())
+            .sign(chain_id.clone(), &key)
+            .unwrap();
+        assert!(vote.is_precommit());
+        assert_eq!(vote.block_id, Some(block_id));
+        vote.verify(chain_id.clone(), &key.public.into()).unwrap();
+        vote.verify_extension(chain_id.clone(), &key.public.into())
+            .unwrap();
+
+        let vote = Vote::builder()
+            .vote_type(Type::Prevote)
+            .sign(chain_id.clone(), &key)
+            .unwrap();
+        assert_eq!(vote.block_id, None);
+        vote.verify(chain_id.clone(), &key.public.into()).unwrap();
+        vote.verify_extension(chain_id, &key.public.into()).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use crate::block;
+use crate::chain::Id as ChainId;
+use crate::prelude::*;
+use crate::{Error, Kind::*};
+use core::convert::{TryFrom, TryInto};
+use serde::{Deserialize, Serialize};
+use tendermint_proto::types::CanonicalVoteExtension as RawCanonicalVoteExtension;
+use tendermint_proto::Protobuf;
+
+/// CanonicalVoteExtension is used for protobuf encoding the vote extension
+/// of a precommit, as signed by the validator
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(
+    try_from = "RawCanonicalVoteExtension",
+    into = "RawCanonicalVoteExtension"
+)]
+pub struct CanonicalVoteExtension {
+    /// Vote extension provided by the application
+    pub extension: Vec<u8>,
+
+    /// Block height
+    pub height: block::Height,
+
+    /// Round
+    pub round: block::Round,
+
+    /// Chain ID
+    pub chain_id: ChainId,
+}
+
+impl Protobuf<RawCanonicalVoteExtension> for CanonicalVoteExtension {}
+
+impl TryFrom<RawCanonicalVoteExtension> for CanonicalVoteExtension {
+    type Error = Error;
+
+    fn try_from(value: RawCanonicalVoteExtension) -> Result<Self, Self::Error> {
+        if value.round > i32::MAX as i64 {
+            // CanonicalVoteExtension uses sfixed64, Vote uses int32.
+            return Err(IntegerOverflow.into());
+        }
+        Ok(CanonicalVoteExtension {
+            extension: value.extension,
+            height: value.height.try_into()?,
+            round: (value.round as i32).try_into()?,
+            chain_id: ChainId::try_from(value.chain_id)?,
+        })
+    }
+}
+
+impl From<CanonicalVoteExtension> for RawCanonicalVoteExtension {
+    fn from(value: CanonicalVoteExtension) -> Self {
+        RawCanonicalVoteExtension {
+            extension: value.extension,
+            height: value.height.into(),
+            round: value.round.value().into(),
+            chain_id: value.chain_id.to_string(),
+        }
+    }
+}
+
+impl CanonicalVoteExtension {
+    /// Create CanonicalVoteExtension from Vote
+    pub fn new(vote: super::Vote, chain_id: ChainId) -> CanonicalVoteExtension {
+        CanonicalVoteExtension {
+            extension: vote.extension,
+            height: vote.height,
+            round: vote.round,
+            chain_id,
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! Voting power
 
-use std::convert::{TryFrom, TryInto};
-use std::fmt;
+use crate::prelude::*;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
 
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
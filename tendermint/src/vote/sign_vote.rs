@@ -1,8 +1,9 @@
 use crate::chain;
+use crate::prelude::*;
 use crate::Vote;
 use crate::{Error, Kind};
 use bytes::BufMut;
-use std::convert::TryFrom;
+use core::convert::TryFrom;
 use tendermint_proto::privval::SignedVoteResponse as RawSignedVoteResponse;
 use tendermint_proto::privval::{RemoteSignerError, SignVoteRequest as RawSignVoteRequest};
 use tendermint_proto::Error as ProtobufError;
@@ -98,14 +99,16 @@ mod tests {
     use crate::block::Round;
     use crate::chain::Id as ChainId;
     use crate::hash::Algorithm;
+    use crate::prelude::*;
     use crate::signature::{Signature, ED25519_SIGNATURE_SIZE};
+    use crate::test::signing_key;
     use crate::vote::{CanonicalVote, ValidatorIndex};
     use crate::vote::{SignVoteRequest, Type};
     use crate::Hash;
     use crate::Vote;
     use chrono::{DateTime, Utc};
-    use std::convert::TryFrom;
-    use std::str::FromStr;
+    use core::convert::TryFrom;
+    use core::str::FromStr;
     use tendermint_proto::Protobuf;
 
     #[test]
@@ -119,7 +122,7 @@ mod tests {
             block_id: Some(BlockId {
                 hash: Hash::try_from(b"DEADBEEFDEADBEEFBAFBAFBAFBAFBAFA".to_vec()).unwrap(),
                 part_set_header: Header::new(
-                    1_000_000,
+                    1000,
                     Hash::try_from(b"0022446688AACCEE1133557799BBDDFF".to_vec()).unwrap(),
                 )
                 .unwrap(),
@@ -137,6 +140,8 @@ mod tests {
                 192, 133, 130, 193, 115, 32, 206, 152, 91, 173, 10,
             ])
             .unwrap(),
+            extension: vec![],
+            extension_signature: None,
         };
         let mut got = vec![];
 
@@ -169,7 +174,7 @@ mod tests {
                    BlockID: types.BlockID{
                        Hash: []byte("DEADBEEFDEADBEEFBAFBAFBAFBAFBAFA"),
                        PartSetHeader: types.PartSetHeader{
-                           Total: 1000000,
+                           Total: 1000,
                            Hash:  []byte("0022446688AACCEE1133557799BBDDFF"),
                        },
                    },
@@ -181,12 +186,12 @@ mod tests {
         */
 
         let want = vec![
-            124, 8, 1, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 2, 0, 0, 0, 0, 0, 0, 0, 34, 74, 10, 32,
+            123, 8, 1, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 2, 0, 0, 0, 0, 0, 0, 0, 34, 73, 10, 32,
             68, 69, 65, 68, 66, 69, 69, 70, 68, 69, 65, 68, 66, 69, 69, 70, 66, 65, 70, 66, 65, 70,
-            66, 65, 70, 66, 65, 70, 66, 65, 70, 65, 18, 38, 8, 192, 132, 61, 18, 32, 48, 48, 50,
-            50, 52, 52, 54, 54, 56, 56, 65, 65, 67, 67, 69, 69, 49, 49, 51, 51, 53, 53, 55, 55, 57,
-            57, 66, 66, 68, 68, 70, 70, 42, 11, 8, 177, 211, 129, 210, 5, 16, 128, 157, 202, 111,
-            50, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95, 105, 100,
+            66, 65, 70, 66, 65, 70, 66, 65, 70, 65, 18, 37, 8, 232, 7, 18, 32, 48, 48, 50, 50, 52,
+            52, 54, 54, 56, 56, 65, 65, 67, 67, 69, 69, 49, 49, 51, 51, 53, 53, 55, 55, 57, 57, 66,
+            66, 68, 68, 70, 70, 42, 11, 8, 177, 211, 129, 210, 5, 16, 128, 157, 202, 111, 50, 13,
+            116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95, 105, 100,
         ];
         assert_eq!(got, want);
         assert_eq!(got2, want);
@@ -204,7 +209,7 @@ mod tests {
             block_id: Some(BlockId {
                 hash: Hash::try_from(b"".to_vec()).unwrap(),
                 part_set_header: Header::new(
-                    1_000_000,
+                    1000,
                     Hash::try_from(b"0022446688AACCEE1133557799BBDDFF".to_vec()).unwrap(),
                 )
                 .unwrap(),
@@ -222,6 +227,8 @@ mod tests {
                 192, 133, 130, 193, 115, 32, 206, 152, 91, 173, 10,
             ])
             .unwrap(),
+            extension: vec![],
+            extension_signature: None,
         };
 
         let request = SignVoteRequest {
@@ -250,7 +257,7 @@ mod tests {
                    BlockID: types.BlockID{
                        Hash: []byte(""),
                        PartSetHeader: types.PartSetHeader{
-                           Total: 1000000,
+                           Total: 1000,
                            Hash:  []byte("0022446688AACCEE1133557799BBDDFF"),
                        },
                    },
@@ -262,11 +269,11 @@ mod tests {
         */
 
         let want = vec![
-            90, 8, 1, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 2, 0, 0, 0, 0, 0, 0, 0, 34, 40, 18, 38, 8,
-            192, 132, 61, 18, 32, 48, 48, 50, 50, 52, 52, 54, 54, 56, 56, 65, 65, 67, 67, 69, 69,
-            49, 49, 51, 51, 53, 53, 55, 55, 57, 57, 66, 66, 68, 68, 70, 70, 42, 11, 8, 177, 211,
-            129, 210, 5, 16, 128, 157, 202, 111, 50, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105,
-            110, 95, 105, 100,
+            89, 8, 1, 17, 57, 48, 0, 0, 0, 0, 0, 0, 25, 2, 0, 0, 0, 0, 0, 0, 0, 34, 39, 18, 37, 8,
+            232, 7, 18, 32, 48, 48, 50, 50, 52, 52, 54, 54, 56, 56, 65, 65, 67, 67, 69, 69, 49, 49,
+            51, 51, 53, 53, 55, 55, 57, 57, 66, 66, 68, 68, 70, 70, 42, 11, 8, 177, 211, 129, 210,
+            5, 16, 128, 157, 202, 111, 50, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95,
+            105, 100,
         ];
         assert_eq!(got, want);
     }
@@ -291,7 +298,6 @@ mod tests {
                 vote_type: Type::Precommit,
                 ..Default::default()
             };
-            println!("{:?}", vt_precommit);
             let cv_precommit = CanonicalVote::new(vt_precommit, ChainId::try_from("A").unwrap());
             let got = cv_precommit.encode_vec().unwrap();
             let want = vec![
@@ -358,7 +364,7 @@ mod tests {
                     .unwrap(), //Hash::new(Algorithm::Sha256,
                 // b"hash".to_vec().as_slice()).unwrap(),
                 part_set_header: Header::new(
-                    1_000_000,
+                    1000,
                     Hash::from_hex_upper(Algorithm::Sha256, "DEADBEEFDEADBEEFBAFBAFBAFBAFBAFA")
                         .unwrap(),
                 )
@@ -372,6 +378,8 @@ mod tests {
                 192, 133, 130, 193, 115, 32, 206, 152, 91, 173, 10,
             ])
             .unwrap(),
+            extension: vec![],
+            extension_signature: None,
         };
         let got = vote.encode_vec().unwrap();
         let v = Vote::decode_vec(&got).unwrap();
@@ -394,16 +402,15 @@ mod tests {
     #[test]
     fn test_deserialization() {
         let encoded = vec![
-            10, 188, 1, 8, 1, 16, 185, 96, 24, 2, 34, 74, 10, 32, 222, 173, 190, 239, 222, 173,
+            10, 187, 1, 8, 1, 16, 185, 96, 24, 2, 34, 73, 10, 32, 222, 173, 190, 239, 222, 173,
             190, 239, 186, 251, 175, 186, 251, 175, 186, 250, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 18, 38, 8, 192, 132, 61, 18, 32, 0, 34, 68, 102, 136, 170, 204, 238, 17,
-            51, 85, 119, 153, 187, 221, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 42,
-            11, 8, 177, 211, 129, 210, 5, 16, 128, 157, 202, 111, 50, 20, 163, 178, 204, 221, 113,
-            134, 241, 104, 95, 33, 242, 72, 42, 244, 251, 52, 70, 168, 75, 53, 56, 213, 187, 3, 66,
-            64, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+            0, 0, 0, 0, 18, 37, 8, 232, 7, 18, 32, 0, 34, 68, 102, 136, 170, 204, 238, 17, 51, 85,
+            119, 153, 187, 221, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 42, 11, 8,
+            177, 211, 129, 210, 5, 16, 128, 157, 202, 111, 50, 20, 163, 178, 204, 221, 113, 134,
+            241, 104, 95, 33, 242, 72, 42, 244, 251, 52, 70, 168, 75, 53, 56, 213, 187, 3, 66, 64,
             1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
-            1, 1, 1, 1, 1, 1, 1, 18, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95, 105,
-            100,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 18, 13, 116, 101, 115, 116, 95, 99, 104, 97, 105, 110, 95, 105, 100,
         ]; // Todo: Double-check the Go implementation, this was self-generated.
         let dt = "2017-12-25T03:00:01.234Z".parse::<DateTime<Utc>>().unwrap();
         let vote = Vote {
@@ -421,13 +428,15 @@ mod tests {
                 hash: Hash::from_hex_upper(Algorithm::Sha256, "DEADBEEFDEADBEEFBAFBAFBAFBAFBAFA")
                     .unwrap(),
                 part_set_header: Header::new(
-                    1_000_000,
+                    1000,
                     Hash::from_hex_upper(Algorithm::Sha256, "0022446688AACCEE1133557799BBDDFF")
                         .unwrap(),
                 )
                 .unwrap(),
             }),
             signature: Signature::try_from(vec![1; ED25519_SIGNATURE_SIZE]).unwrap(),
+            extension: vec![],
+            extension_signature: None,
         };
         let want = SignVoteRequest {
             vote,
@@ -436,4 +445,63 @@ mod tests {
         let got = SignVoteRequest::decode_vec(&encoded).unwrap();
         assert_eq!(got, want);
     }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = signing_key(1);
+        let pub_key = crate::PublicKey::from(signer.public);
+        let chain_id = ChainId::from_str("test_chain_id").unwrap();
+
+        let mut vote = Vote {
+            vote_type: Type::Precommit,
+            height: Height::from(10_u32),
+            ..Vote::default()
+        };
+        vote.sign(chain_id.clone(), &signer).unwrap();
+        vote.verify(chain_id.clone(), &pub_key).unwrap();
+        assert!(vote
+            .verify(ChainId::from_str("other_chain_id").unwrap(), &pub_key)
+            .is_err());
+
+        vote.round = Round::from(1_u16);
+        assert!(vote.verify(chain_id, &pub_key).is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify_extension() {
+        let signer = signing_key(1);
+        let pub_key = crate::PublicKey::from(signer.public);
+        let chain_id = ChainId::from_str("test_chain_id").unwrap();
+
+        // Extensions are only allowed in precommits for a block
+        let mut vote = Vote {
+            vote_type: Type::Prevote,
+            height: Height::from(10_u32),
+            block_id: Some(BlockId {
+                hash: Hash::try_from(b"DEADBEEFDEADBEEFBAFBAFBAFBAFBAFA".to_vec()).unwrap(),
+                part_set_header: Header::new(
+                    1,
+                    Hash::try_from(b"0022446688AACCEE1133557799BBDDFF".to_vec()).unwrap(),
+                )
+                .unwrap(),
+            }),
+            ..Vote::default()
+        };
+        vote.verify_extension(chain_id.clone(), &pub_key).unwrap();
+        assert!(vote.sign_extension(chain_id.clone(), &signer).is_err());
+        vote.extension = b"extension".to_vec();
+        assert!(vote.verify_extension(chain_id.clone(), &pub_key).is_err());
+
+        vote.vote_type = Type::Precommit;
+        assert!(vote.verify_extension(chain_id.clone(), &pub_key).is_err());
+        vote.sign_extension(chain_id.clone(), &signer).unwrap();
+        vote.verify_extension(chain_id.clone(), &pub_key).unwrap();
+
+        // The extension signature survives a protobuf roundtrip
+        let decoded = Vote::decode_vec(&vote.encode_vec().unwrap()).unwrap();
+        assert_eq!(decoded, vote);
+
+        vote.extension = b"tampered".to_vec();
+        assert!(vote.verify_extension(chain_id, &pub_key).is_err());
+    }
 }
@@ -1,6 +1,6 @@
 use crate::error::{Error, Kind};
-use std::convert::TryInto;
-use std::{
+use core::convert::TryInto;
+use core::{
     convert::TryFrom,
     fmt::{self, Debug, Display},
     str::FromStr,
@@ -0,0 +1,355 @@
+//! Sets of votes for a given height, round and vote type
+
+use super::{Type, ValidatorIndex, Vote};
+use crate::block::{self, Commit, CommitSig};
+use crate::evidence::DuplicateVoteEvidence;
+use crate::prelude::*;
+use crate::trust_threshold::{TrustThreshold, TrustThresholdFraction};
+use crate::{chain, validator, Error, Kind};
+use alloc::collections::BTreeMap;
+
+/// Outcome of adding a vote to a [`VoteSet`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AddedVote {
+    /// The vote was added
+    Added,
+
+    /// The validator already cast the same vote, which was left unchanged
+    Duplicate,
+
+    /// The validator already voted for another block: the vote was not
+    /// added, and the conflicting votes are evidence of misbehaviour
+    Conflicting(Box<DuplicateVoteEvidence>),
+}
+
+/// The votes of a validator set for a given height, round and vote type, as
+/// Tendermint's `VoteSet`.
+///
+/// Votes are verified as they are added, and tallied by the block they are
+/// for, so as to detect when more than 2/3 of the voting power agrees on a
+/// block (or on nil). A set of precommits with such a majority for a block
+/// yields the commit for that block.
+#[derive(Clone, Debug)]
+pub struct VoteSet {
+    chain_id: chain::Id,
+    height: block::Height,
+    round: block::Round,
+    vote_type: Type,
+    validators: validator::Set,
+    // Votes by validator index
+    votes: Vec<Option<Vote>>,
+    sum: u64,
+    // Voting power by block voted for (`None` for nil)
+    power_by_block: BTreeMap<Option<block::Id>, u64>,
+    maj23: Option<Option<block::Id>>,
+}
+
+impl VoteSet {
+    /// Create an empty set of votes of the given validator set.
+    pub fn new(
+        chain_id: chain::Id,
+        height: block::Height,
+        round: block::Round,
+        vote_type: Type,
+        validators: validator::Set,
+    ) -> Self {
+        let votes = vec![None; validators.validators().len()];
+        VoteSet {
+            chain_id,
+            height,
+            round,
+            vote_type,
+            validators,
+            votes,
+            sum: 0,
+            power_by_block: BTreeMap::new(),
+            maj23: None,
+        }
+    }
+
+    /// Height of the votes in this set
+    pub fn height(&self) -> block::Height {
+        self.height
+    }
+
+    /// Round of the votes in this set
+    pub fn round(&self) -> block::Round {
+        self.round
+    }
+
+    /// Type of the votes in this set
+    pub fn vote_type(&self) -> Type {
+        self.vote_type
+    }
+
+    /// The validators voting
+    pub fn validators(&self) -> &validator::Set {
+        &self.validators
+    }
+
+    /// Verify the given vote and add it to this set.
+    ///
+    /// Fails if the vote is not for the height, round and type of this set,
+    /// if it was not validly signed by the validator at its index, or if the
+    /// validator already signed the same block with a different signature.
+    pub fn add_vote(&mut self, vote: Vote) -> Result<AddedVote, Error> {
+        if vote.height != self.height
+            || vote.round != self.round
+            || vote.vote_type != self.vote_type
+        {
+            return Err(format_err!(
+                Kind::InvalidVote,
+                "expected {:?} for {}/{}, got {:?} for {}/{}",
+                self.vote_type,
+                self.height,
+                self.round,
+                vote.vote_type,
+                vote.height,
+                vote.round
+            )
+            .into());
+        }
+        let index = usize::from(vote.validator_index);
+        let validator = match self.validators.validators().get(index) {
+            Some(validator) if validator.address == vote.validator_address => *validator,
+            _ => {
+                return Err(format_err!(
+                    Kind::InvalidVote,
+                    "no validator {} at index {}",
+                    vote.validator_address,
+                    vote.validator_index
+                )
+                .into())
+            }
+        };
+        vote.verify(self.chain_id.clone(), &validator.pub_key)?;
+
+        if let Some(existing) = &self.votes[index] {
+            if existing.block_id == vote.block_id {
+                if existing.signature == vote.signature {
+                    return Ok(AddedVote::Duplicate);
+                }
+                // Signatures are deterministic, so the validator signed
+                // something else for the same block, e.g. another timestamp
+                return Err(format_err!(
+                    Kind::InvalidVote,
+                    "conflicting signatures from {} for the same block",
+                    vote.validator_address
+                )
+                .into());
+            }
+            let timestamp = existing
+                .timestamp
+                .or(vote.timestamp)
+                .ok_or(Kind::NoTimestamp)?;
+            let evidence = DuplicateVoteEvidence::from_conflicting_votes(
+                existing.clone(),
+                vote,
+                &self.validators,
+                timestamp,
+            )?;
+            return Ok(AddedVote::Conflicting(Box::new(evidence)));
+        }
+
+        let power = validator.power();
+        self.sum += power;
+        let block_power = self.power_by_block.entry(vote.block_id).or_insert(0);
+        *block_power += power;
+        let block_power = *block_power;
+        if self.maj23.is_none() && self.is_two_thirds_majority(block_power) {
+            self.maj23 = Some(vote.block_id);
+        }
+        self.votes[index] = Some(vote);
+        Ok(AddedVote::Added)
+    }
+
+    /// Vote of the validator at the given index, if any
+    pub fn vote(&self, index: ValidatorIndex) -> Option<&Vote> {
+        self.votes.get(usize::from(index)).and_then(Option::as_ref)
+    }
+
+    /// Total voting power of the votes in this set
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    /// The block voted for by more than 2/3 of the voting power, if any:
+    /// `Some(None)` if the majority voted for nil.
+    pub fn two_thirds_majority(&self) -> Option<Option<block::Id>> {
+        self.maj23
+    }
+
+    /// Whether more than 2/3 of the voting power voted, for any blocks
+    pub fn has_two_thirds_any(&self) -> bool {
+        self.is_two_thirds_majority(self.sum)
+    }
+
+    /// Whether all validators voted
+    pub fn has_all(&self) -> bool {
+        self.sum == self.validators.total_voting_power().value()
+    }
+
+    /// Build the commit for the block voted for by more than 2/3 of the
+    /// voting power.
+    ///
+    /// Only sets of precommits with such a majority for a block (rather than
+    /// nil) yield a commit. Votes for other blocks are recorded as absent.
+    pub fn make_commit(&self) -> Result<Commit, Error> {
+        if self.vote_type != Type::Precommit {
+            return Err(Kind::InvalidCommit
+                .context("cannot make a commit from prevotes")
+                .into());
+        }
+        let block_id = match self.maj23 {
+            Some(Some(block_id)) => block_id,
+            _ => {
+                return Err(Kind::InvalidCommit
+                    .context("no 2/3 majority for a block")
+                    .into())
+            }
+        };
+
+        let signatures = self
+            .votes
+            .iter()
+            .map(|vote| match vote {
+                Some(vote) if vote.block_id == Some(block_id) => Ok(CommitSig::BlockIdFlagCommit {
+                    validator_address: vote.validator_address,
+                    timestamp: vote.timestamp.ok_or(Kind::NoTimestamp)?,
+                    signature: vote.signature,
+                }),
+                Some(vote) if vote.block_id.is_none() => Ok(CommitSig::BlockIdFlagNil {
+                    validator_address: vote.validator_address,
+                    timestamp: vote.timestamp.ok_or(Kind::NoTimestamp)?,
+                    signature: vote.signature,
+                }),
+                _ => Ok(CommitSig::BlockIdFlagAbsent),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Commit {
+            height: self.height,
+            round: self.round,
+            block_id,
+            signatures,
+        })
+    }
+
+    fn is_two_thirds_majority(&self, power: u64) -> bool {
+        TrustThresholdFraction::TWO_THIRDS
+            .is_enough_power(power, self.validators.total_voting_power().value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::signing_key;
+    use crate::{account, private_key, vote, Hash, Time};
+    use core::convert::TryFrom;
+
+    fn block_id(seed: u8) -> block::Id {
+        block::Id {
+            hash: Hash::Sha256([seed; 32]),
+            part_set_header: block::parts::Header::new(1, Hash::Sha256([seed; 32])).unwrap(),
+        }
+    }
+
+    fn precommit(
+        chain_id: &chain::Id,
+        validators: &validator::Set,
+        key: &private_key::Ed25519,
+        block_id: Option<block::Id>,
+    ) -> Vote {
+        let address = account::Id::from(key.public);
+        let index = validators
+            .validators()
+            .iter()
+            .position(|v| v.address == address)
+            .unwrap();
+        let mut builder = Vote::builder()
+            .height(block::Height::from(3_u32))
+            .timestamp(Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap())
+            .validator(address, ValidatorIndex::try_from(index).unwrap());
+        if let Some(block_id) = block_id {
+            builder = builder.block_id(block_id);
+        }
+        builder.sign(chain_id.clone(), key).unwrap()
+    }
+
+    #[test]
+    fn vote_aggregation() {
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+        let keys: Vec<_> = (1..=4).map(signing_key).collect();
+        let validators = validator::Set::without_proposer(
+            keys.iter()
+                .map(|key| validator::Info::new(key.public.into(), vote::Power::from(10_u32)))
+                .collect(),
+        );
+        let mut votes = VoteSet::new(
+            chain_id.clone(),
+            block::Height::from(3_u32),
+            block::Round::from(0_u8),
+            Type::Precommit,
+            validators.clone(),
+        );
+        let block = Some(block_id(1));
+
+        let vote = precommit(&chain_id, &validators, &keys[0], block);
+        assert_eq!(AddedVote::Added, votes.add_vote(vote.clone()).unwrap());
+        assert_eq!(AddedVote::Duplicate, votes.add_vote(vote).unwrap());
+        votes
+            .add_vote(precommit(&chain_id, &validators, &keys[1], block))
+            .unwrap();
+        assert_eq!(None, votes.two_thirds_majority());
+        assert!(votes.make_commit().is_err());
+
+        // A vote for nil is counted, but does not make a majority for the
+        // block.
+        votes
+            .add_vote(precommit(&chain_id, &validators, &keys[2], None))
+            .unwrap();
+        assert!(votes.has_two_thirds_any());
+        assert_eq!(None, votes.two_thirds_majority());
+
+        votes
+            .add_vote(precommit(&chain_id, &validators, &keys[3], block))
+            .unwrap();
+        assert!(votes.has_all());
+        assert_eq!(Some(block), votes.two_thirds_majority());
+
+        let commit = votes.make_commit().unwrap();
+        validators
+            .verify_commit(&chain_id, &block_id(1), commit.height, &commit)
+            .unwrap();
+
+        // Voting for another block is misbehaviour.
+        let conflicting = precommit(&chain_id, &validators, &keys[0], Some(block_id(2)));
+        match votes.add_vote(conflicting).unwrap() {
+            AddedVote::Conflicting(evidence) => evidence.verify(&validators, &chain_id).unwrap(),
+            added => panic!("expected conflicting votes, got {:?}", added),
+        }
+        assert_eq!(40, votes.sum());
+
+        // Signing the same block again with another timestamp is rejected,
+        // leaving the first vote in place.
+        let first = precommit(&chain_id, &validators, &keys[0], block);
+        let mut resigned = first.clone();
+        resigned.timestamp = Some(Time::parse_from_rfc3339("2021-01-01T00:00:01Z").unwrap());
+        resigned.sign(chain_id.clone(), &keys[0]).unwrap();
+        assert!(votes.add_vote(resigned).is_err());
+        assert_eq!(
+            Some(&first),
+            votes.vote(ValidatorIndex::try_from(0_u32).unwrap())
+        );
+
+        // Votes must be validly signed, for the right height.
+        let mut forged = precommit(&chain_id, &validators, &keys[1], None);
+        forged.validator_index = ValidatorIndex::try_from(0_u32).unwrap();
+        forged.validator_address = validators.validators()[0].address;
+        assert!(votes.add_vote(forged).is_err());
+        let mut other_height = precommit(&chain_id, &validators, &keys[1], block);
+        other_height.height = block::Height::from(4_u32);
+        assert!(votes.add_vote(other_height).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use gumdrop::Options;
 use simple_error::SimpleError;
-use tendermint_testgen::{helpers::*, Commit, Generator, Header, Time, Validator, Vote};
+use tendermint_testgen::{
+    helpers::*, Commit, Evidence, Generator, HashVector, Header, LightClientTest, RpcResponse,
+    Time, Validator, ValidatorSet, Vote,
+};
 
 const USAGE: &str = r#"
 This is a small utility for producing tendermint datastructures
@@ -59,6 +62,8 @@ struct CliOptions {
 enum Command {
     #[options(help = "produce validator from identifier and other parameters")]
     Validator(Validator),
+    #[options(help = "produce validator set from validator array and other parameters")]
+    ValidatorSet(ValidatorSet),
     #[options(help = "produce header from validator array and other parameters")]
     Header(Header),
     #[options(help = "produce vote from validator and other parameters")]
@@ -67,6 +72,14 @@ enum Command {
     Commit(Commit),
     #[options(help = "produce timestamp from number of seconds since epoch")]
     Time(Time),
+    #[options(help = "produce light client test case from height to verify and other parameters")]
+    LightClientTest(LightClientTest),
+    #[options(help = "produce evidence of misbehavior from validator array and other parameters")]
+    Evidence(Evidence),
+    #[options(help = "produce JSON-RPC response from RPC method and other parameters")]
+    RpcResponse(RpcResponse),
+    #[options(help = "produce hash test vectors of headers, validator sets and consensus params")]
+    HashVector(HashVector),
 }
 
 fn encode_with_stdin<Opts: Generator<T> + Options, T: serde::Serialize>(
@@ -128,9 +141,14 @@ fn main() {
             std::process::exit(1);
         }
         Some(Command::Validator(cli)) => run_command(cli, opts.stdin),
+        Some(Command::ValidatorSet(cli)) => run_command(cli, opts.stdin),
         Some(Command::Header(cli)) => run_command(cli, opts.stdin),
         Some(Command::Vote(cli)) => run_command(cli, opts.stdin),
         Some(Command::Commit(cli)) => run_command(cli, opts.stdin),
         Some(Command::Time(cli)) => run_command(cli, opts.stdin),
+        Some(Command::LightClientTest(cli)) => run_command(cli, opts.stdin),
+        Some(Command::Evidence(cli)) => run_command(cli, opts.stdin),
+        Some(Command::RpcResponse(cli)) => run_command(cli, opts.stdin),
+        Some(Command::HashVector(cli)) => run_command(cli, opts.stdin),
     }
 }
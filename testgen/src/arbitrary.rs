@@ -0,0 +1,200 @@
+//! [proptest](https://github.com/AltSysrq/proptest) integration, enabled with
+//! the "proptest" feature: the companion objects implement `Arbitrary`, so that
+//! property-based tests can take them as input and shrink the failing ones.
+//!
+//! The companion objects produced by the strategies always generate
+//! successfully: e.g. the validators of a header are never empty, and the
+//! validator of a vote is one of the validators of its header.
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use tendermint_testgen::{Generator, LightBlock};
+//!
+//! proptest! {
+//!     fn light_blocks_generate(light_block in any::<LightBlock>()) {
+//!         prop_assert!(light_block.generate().is_ok());
+//!     }
+//! }
+//!
+//! light_blocks_generate();
+//! ```
+
+use crate::{Commit, Header, LightBlock, Validator, Vote};
+use proptest::collection::btree_map;
+use proptest::prelude::*;
+use tendermint::Hash;
+
+/// The maximum number of validators in the validator sets produced by the
+/// strategies of the companion objects
+pub const MAX_VALIDATORS: usize = 4;
+
+/// Strategy for validator identifiers
+pub fn validator_id() -> impl Strategy<Value = String> {
+    "[a-z0-9]{1,8}"
+}
+
+/// Strategy for validators with distinct identifiers and non-zero voting
+/// powers, at most `max_len` of them
+pub fn validators(max_len: usize) -> impl Strategy<Value = Vec<Validator>> {
+    btree_map(validator_id(), 1..=1_000u64, 1..=max_len).prop_map(|vals| {
+        vals.into_iter()
+            .map(|(id, power)| Validator::new(&id).voting_power(power))
+            .collect()
+    })
+}
+
+/// Strategy for chain identifiers
+pub fn chain_id() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9-]{0,19}"
+}
+
+impl Arbitrary for Validator {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (validator_id(), 1..=1_000u64)
+            .prop_map(|(id, power)| Validator::new(&id).voting_power(power))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Header {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            validators(MAX_VALIDATORS),
+            prop::option::of(validators(MAX_VALIDATORS)),
+            chain_id(),
+            1..=1_000_000u64,
+            0..=1_000_000_000u64,
+            prop::option::of(any::<[u8; 32]>()),
+        )
+            .prop_flat_map(
+                |(vals, next_vals, chain_id, height, time, last_block_id_hash)| {
+                    (0..vals.len()).prop_map(move |proposer| {
+                        let mut header = Header::new(&vals)
+                            .chain_id(&chain_id)
+                            .height(height)
+                            .time(time)
+                            .proposer(proposer);
+                        header.next_validators = next_vals.clone();
+                        header.last_block_id_hash = last_block_id_hash.map(Hash::Sha256);
+                        header
+                    })
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Vote {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<Header>()
+            .prop_flat_map(|header| {
+                let num_validators = header.validators.as_ref().unwrap().len();
+                (
+                    Just(header),
+                    0..num_validators,
+                    0..=10u32,
+                    any::<bool>(),
+                    any::<bool>(),
+                )
+            })
+            .prop_map(|(header, index, round, prevote, nil)| {
+                let validator = header.validators.as_ref().unwrap()[index].clone();
+                Vote::new(validator, header)
+                    .round(round)
+                    .prevote(prevote)
+                    .nil(nil)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Commit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Header>(), 0..=10u32)
+            .prop_map(|(header, round)| Commit::new(header, round))
+            .boxed()
+    }
+}
+
+impl Arbitrary for LightBlock {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Header>(), 0..=10u32)
+            .prop_map(|(header, round)| {
+                let validators = header.validators.clone().unwrap();
+                let next_validators = header
+                    .next_validators
+                    .clone()
+                    .unwrap_or_else(|| validators.clone());
+                let commit = Commit::new(header.clone(), round);
+                LightBlock::new(header, commit)
+                    .validators(&validators)
+                    .next_validators(&next_validators)
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Generator;
+
+    proptest! {
+        #[test]
+        fn test_arbitrary_validators(vals in validators(MAX_VALIDATORS)) {
+            let generated = crate::validator::generate_validators(&vals).unwrap();
+            prop_assert_eq!(generated.len(), vals.len());
+            prop_assert!(generated.iter().all(|v| v.power() > 0));
+        }
+
+        #[test]
+        fn test_arbitrary_header(header in any::<Header>()) {
+            let generated = header.generate().unwrap();
+            prop_assert_eq!(generated.height.value(), header.height.unwrap());
+            prop_assert_eq!(generated.chain_id.as_str(), header.chain_id.unwrap());
+        }
+
+        #[test]
+        fn test_arbitrary_vote(vote in any::<Vote>()) {
+            let generated = vote.generate().unwrap();
+            let header = vote.header.unwrap().generate().unwrap();
+            prop_assert_eq!(generated.height, header.height);
+            prop_assert_eq!(generated.block_id.is_none(), vote.nil.is_some());
+        }
+
+        #[test]
+        fn test_arbitrary_commit(commit in any::<Commit>()) {
+            let generated = commit.generate().unwrap();
+            let num_validators = commit.header.unwrap().validators.unwrap().len();
+            prop_assert_eq!(generated.signatures.len(), num_validators);
+        }
+
+        #[test]
+        fn test_arbitrary_light_block(light_block in any::<LightBlock>()) {
+            let generated = light_block.generate().unwrap();
+            prop_assert_eq!(
+                generated.signed_header.header.validators_hash,
+                generated.validators.hash()
+            );
+            prop_assert_eq!(
+                generated.signed_header.header.next_validators_hash,
+                generated.next_validators.hash()
+            );
+        }
+    }
+}
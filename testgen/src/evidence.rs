@@ -0,0 +1,506 @@
+use gumdrop::Options;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+use std::convert::{TryFrom, TryInto};
+
+use crate::light_block::generate_signed_header;
+use crate::validator::generate_validators;
+use crate::{helpers::*, Commit, Generator, Header, Validator, Vote};
+use tendermint::evidence::{
+    self, ConflictingBlock, DuplicateVoteEvidence, LightClientAttackEvidence,
+};
+use tendermint::{block, validator};
+use tendermint_proto::types::DuplicateVoteEvidence as RawDuplicateVoteEvidence;
+use tendermint_proto::types::Vote as RawVote;
+
+/// The misbehavior that evidence is produced of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Misbehavior {
+    /// A validator signs votes for two blocks at the same height and round
+    DuplicateVote,
+    /// Validators sign a conflicting block at the common height, in the same
+    /// round as the block of the main chain
+    Equivocation,
+    /// Validators sign a conflicting block above the common height, whose
+    /// header is invalid, e.g. by having another validator set
+    Lunatic,
+    /// Validators sign a conflicting block at the common height, in another
+    /// round than the block of the main chain
+    Amnesia,
+}
+
+impl std::str::FromStr for Misbehavior {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "duplicate-vote" => Ok(Misbehavior::DuplicateVote),
+            "equivocation" => Ok(Misbehavior::Equivocation),
+            "lunatic" => Ok(Misbehavior::Lunatic),
+            "amnesia" => Ok(Misbehavior::Amnesia),
+            _ => bail!("unknown misbehavior: {}", s),
+        }
+    }
+}
+
+/// The way in which otherwise valid evidence is made invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Invalidity {
+    /// Both votes are for the same block (duplicate vote)
+    SameBlock,
+    /// The votes are for different heights (duplicate vote)
+    DifferentHeights,
+    /// The votes are for different rounds (duplicate vote)
+    DifferentRounds,
+    /// The votes are from different validators (duplicate vote)
+    DifferentValidators,
+    /// The second vote carries the signature of the first one (duplicate vote)
+    BadSignature,
+    /// The voting power of the validator is off by one (duplicate vote)
+    WrongValidatorPower,
+    /// The total voting power is off by one
+    WrongTotalPower,
+    /// The conflicting block is from another chain (light client attack)
+    WrongChain,
+    /// The common height is above the conflicting block (light client attack)
+    BelowCommonHeight,
+    /// The validator set of the conflicting block doesn't match its header
+    /// (light client attack)
+    ValidatorSetMismatch,
+    /// A byzantine validator is in neither validator set (light client attack)
+    UnknownByzantineValidator,
+}
+
+impl std::str::FromStr for Invalidity {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_as::<Invalidity>(&format!("\"{}\"", s))
+            .map_err(|_| SimpleError::new(format!("unknown invalidity: {}", s)))
+    }
+}
+
+/// A companion object for generating evidence of misbehavior by the
+/// validators of the given validator set. The votes and commits in the
+/// evidence are validly signed by the byzantine validators, unless the
+/// evidence is made invalid on purpose.
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct Evidence {
+    #[options(
+        help = "validators at the (common) height of the misbehavior (required; can be passed \
+                via STDIN), encoded as array of 'validator' parameters",
+        parse(try_from_str = "parse_as::<Vec<Validator>>")
+    )]
+    pub validators: Option<Vec<Validator>>,
+    #[options(
+        help = "misbehavior: duplicate-vote, equivocation, lunatic or amnesia \
+                (default: duplicate-vote)"
+    )]
+    pub misbehavior: Option<Misbehavior>,
+    #[options(
+        help = "indices of the byzantine validators (default: the first one for duplicate votes, \
+                all but the last one for lunatic attacks, all for other attacks), encoded as array",
+        parse(try_from_str = "parse_as::<Vec<usize>>")
+    )]
+    pub byzantine: Option<Vec<usize>>,
+    #[options(help = "chain id (default: test-chain)")]
+    pub chain_id: Option<String>,
+    #[options(
+        help = "height of the misbehavior (default: common height + 1 for lunatic attacks, \
+                2 otherwise)"
+    )]
+    pub height: Option<u64>,
+    #[options(
+        help = "common height of a light client attack (default: height - 1 for lunatic \
+                attacks, height otherwise)"
+    )]
+    pub common_height: Option<u64>,
+    #[options(help = "time of the block at the (common) height (default: its height)")]
+    pub time: Option<u64>,
+    #[options(help = "way in which the evidence is made invalid (default: none)")]
+    pub invalid: Option<Invalidity>,
+}
+
+impl Evidence {
+    pub fn new(validators: &[Validator]) -> Self {
+        Evidence {
+            validators: Some(validators.to_vec()),
+            misbehavior: None,
+            byzantine: None,
+            chain_id: None,
+            height: None,
+            common_height: None,
+            time: None,
+            invalid: None,
+        }
+    }
+    set_option!(validators, &[Validator], Some(validators.to_vec()));
+    set_option!(misbehavior, Misbehavior);
+    set_option!(byzantine, &[usize], Some(byzantine.to_vec()));
+    set_option!(chain_id, &str, Some(chain_id.to_string()));
+    set_option!(height, u64);
+    set_option!(common_height, u64);
+    set_option!(time, u64);
+    set_option!(invalid, Invalidity);
+}
+
+impl std::str::FromStr for Evidence {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let evidence = match parse_as::<Evidence>(s) {
+            Ok(input) => input,
+            Err(_) => Evidence::new(&parse_as::<Vec<Validator>>(s)?),
+        };
+        Ok(evidence)
+    }
+}
+
+impl Generator<evidence::Evidence> for Evidence {
+    fn merge_with_default(self, default: Self) -> Self {
+        Evidence {
+            validators: self.validators.or(default.validators),
+            misbehavior: self.misbehavior.or(default.misbehavior),
+            byzantine: self.byzantine.or(default.byzantine),
+            chain_id: self.chain_id.or(default.chain_id),
+            height: self.height.or(default.height),
+            common_height: self.common_height.or(default.common_height),
+            time: self.time.or(default.time),
+            invalid: self.invalid.or(default.invalid),
+        }
+    }
+
+    fn generate(&self) -> Result<evidence::Evidence, SimpleError> {
+        let validators = match &self.validators {
+            None => bail!("validator array is missing"),
+            Some(vals) if vals.is_empty() => bail!("validator array is empty"),
+            Some(vals) => vals,
+        };
+        let misbehavior = self.misbehavior.unwrap_or(Misbehavior::DuplicateVote);
+        let byzantine = match &self.byzantine {
+            Some(indices) => indices.clone(),
+            None => match misbehavior {
+                Misbehavior::DuplicateVote => vec![0],
+                Misbehavior::Lunatic => (0..validators.len() - 1).collect(),
+                _ => (0..validators.len()).collect(),
+            },
+        };
+        if byzantine.is_empty() {
+            bail!("there must be at least one byzantine validator")
+        }
+        let byzantine = byzantine
+            .iter()
+            .map(|&i| match validators.get(i) {
+                Some(v) => Ok(v.clone()),
+                None => bail!("byzantine validator {} out of bounds", i),
+            })
+            .collect::<Result<Vec<_>, SimpleError>>()?;
+
+        match misbehavior {
+            Misbehavior::DuplicateVote => self.duplicate_vote(validators, &byzantine[0]),
+            _ => self.light_client_attack(misbehavior, validators, &byzantine),
+        }
+    }
+}
+
+impl Evidence {
+    fn chain_id_or_default(&self) -> String {
+        self.chain_id
+            .clone()
+            .unwrap_or_else(|| "test-chain".to_string())
+    }
+
+    fn duplicate_vote(
+        &self,
+        validators: &[Validator],
+        byzantine: &Validator,
+    ) -> Result<evidence::Evidence, SimpleError> {
+        let height = self.height.unwrap_or(2);
+        let time = self.time.unwrap_or(height);
+        let validator_set = validator::Set::without_proposer(generate_validators(validators)?);
+
+        // Two blocks at the same height, which differ by their time
+        let header_a = Header::new(validators)
+            .chain_id(&self.chain_id_or_default())
+            .height(height)
+            .time(time);
+        let header_b = match self.invalid {
+            Some(Invalidity::SameBlock) => header_a.clone(),
+            Some(Invalidity::DifferentHeights) => header_a.clone().height(height + 1),
+            _ => header_a.clone().time(time + 1),
+        };
+        let vote_a = Vote::new(byzantine.clone(), header_a);
+        let vote_b = match self.invalid {
+            Some(Invalidity::DifferentRounds) => Vote::new(byzantine.clone(), header_b).round(2),
+            _ => Vote::new(byzantine.clone(), header_b),
+        };
+
+        let evidence = try_with!(
+            DuplicateVoteEvidence::from_conflicting_votes(
+                vote_a.generate()?,
+                vote_b.generate()?,
+                &validator_set,
+                get_time(time),
+            ),
+            "failed to generate duplicate vote evidence"
+        );
+
+        let mut raw = RawDuplicateVoteEvidence::from(evidence);
+        match self.invalid {
+            None
+            | Some(Invalidity::SameBlock)
+            | Some(Invalidity::DifferentHeights)
+            | Some(Invalidity::DifferentRounds) => {}
+            Some(Invalidity::DifferentValidators) => {
+                let other = match validators.iter().find(|&v| v != byzantine) {
+                    None => bail!("different validators require at least two validators"),
+                    Some(v) => v,
+                };
+                let header = Header::new(validators)
+                    .chain_id(&self.chain_id_or_default())
+                    .height(height)
+                    .time(time + 1);
+                raw.vote_b = Some(RawVote::from(Vote::new(other.clone(), header).generate()?));
+            }
+            Some(Invalidity::BadSignature) => {
+                let signature = raw.vote_a.as_ref().unwrap().signature.clone();
+                raw.vote_b.as_mut().unwrap().signature = signature;
+            }
+            Some(Invalidity::WrongValidatorPower) => raw.validator_power += 1,
+            Some(Invalidity::WrongTotalPower) => raw.total_voting_power += 1,
+            Some(invalid) => bail!("{:?} does not apply to duplicate votes", invalid),
+        }
+        let evidence = try_with!(
+            DuplicateVoteEvidence::try_from(raw),
+            "failed to generate duplicate vote evidence"
+        );
+        Ok(evidence::Evidence::DuplicateVote(evidence))
+    }
+
+    fn light_client_attack(
+        &self,
+        misbehavior: Misbehavior,
+        validators: &[Validator],
+        byzantine: &[Validator],
+    ) -> Result<evidence::Evidence, SimpleError> {
+        let (height, common_height) = match (misbehavior, self.height, self.common_height) {
+            (Misbehavior::Lunatic, Some(h), Some(c)) => (h, c),
+            (Misbehavior::Lunatic, Some(h), None) => (h, h.saturating_sub(1)),
+            (Misbehavior::Lunatic, None, Some(c)) => (c + 1, c),
+            (Misbehavior::Lunatic, None, None) => (3, 2),
+            (_, h, c) => {
+                let height = h.or(c).unwrap_or(2);
+                (height, c.unwrap_or(height))
+            }
+        };
+        if misbehavior == Misbehavior::Lunatic && height <= common_height {
+            bail!("the conflicting block of a lunatic attack must be above the common height")
+        }
+        if misbehavior != Misbehavior::Lunatic && height != common_height {
+            bail!("the conflicting block of an equivocation or amnesia attack must be at the common height")
+        }
+        if common_height < 1 {
+            bail!("the common height must be at least 1")
+        }
+        let time = self.time.unwrap_or(common_height);
+        let validator_set = validator::Set::without_proposer(generate_validators(validators)?);
+        let chain_id = match self.invalid {
+            Some(Invalidity::WrongChain) => format!("{}-fork", self.chain_id_or_default()),
+            _ => self.chain_id_or_default(),
+        };
+
+        // The conflicting block differs from the block of the main chain at
+        // the same height by its time or, for lunatic attacks, its validators
+        let (header, round) = match misbehavior {
+            Misbehavior::Lunatic => (
+                Header::new(byzantine)
+                    .chain_id(&chain_id)
+                    .height(height)
+                    .time(time + (height - common_height)),
+                1,
+            ),
+            Misbehavior::Equivocation => (
+                Header::new(validators)
+                    .chain_id(&chain_id)
+                    .height(height)
+                    .time(time + 1),
+                1,
+            ),
+            _ => (
+                Header::new(validators)
+                    .chain_id(&chain_id)
+                    .height(height)
+                    .time(time + 1),
+                2,
+            ),
+        };
+        let mut commit = Commit::new(header.clone(), round);
+        commit.votes = commit.votes.map(|votes| {
+            votes
+                .into_iter()
+                .filter(|vote| byzantine.contains(vote.validator.as_ref().unwrap()))
+                .collect()
+        });
+        let signed_header = generate_signed_header(&header, &commit)?;
+        let conflicting_validators = match self.invalid {
+            Some(Invalidity::ValidatorSetMismatch) => {
+                let mut vals = header.validators.clone().unwrap();
+                vals.push(outsider());
+                vals
+            }
+            _ => header.validators.clone().unwrap(),
+        };
+
+        // The validators who signed both blocks can't be told apart from the
+        // others in the case of amnesia, as they signed in different rounds
+        let mut byzantine_validators = match misbehavior {
+            Misbehavior::Amnesia => vec![],
+            _ => generate_validators(byzantine)?,
+        };
+        let mut total_voting_power = validator_set.total_voting_power();
+        let mut common_height = common_height;
+        match self.invalid {
+            None | Some(Invalidity::WrongChain) | Some(Invalidity::ValidatorSetMismatch) => {}
+            Some(Invalidity::WrongTotalPower) => {
+                total_voting_power = (total_voting_power.value() + 1).try_into().unwrap()
+            }
+            Some(Invalidity::BelowCommonHeight) => common_height = height + 1,
+            Some(Invalidity::UnknownByzantineValidator) => {
+                byzantine_validators.push(outsider().generate()?)
+            }
+            Some(invalid) => bail!("{:?} does not apply to light client attacks", invalid),
+        }
+
+        Ok(evidence::Evidence::LightClientAttack(Box::new(
+            LightClientAttackEvidence {
+                conflicting_block: ConflictingBlock {
+                    signed_header,
+                    validator_set: validator::Set::without_proposer(generate_validators(
+                        &conflicting_validators,
+                    )?),
+                },
+                common_height: block::Height::try_from(common_height)
+                    .map_err(|_| SimpleError::new("height out of bounds"))?,
+                byzantine_validators,
+                total_voting_power,
+                timestamp: get_time(time),
+            },
+        )))
+    }
+}
+
+/// A validator which isn't in any validator set that evidence is produced for
+fn outsider() -> Validator {
+    Validator::new("outsider").voting_power(50)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint::chain;
+
+    fn validators() -> Vec<Validator> {
+        vec![
+            Validator::new("a").voting_power(50),
+            Validator::new("b").voting_power(50),
+            Validator::new("c").voting_power(50),
+        ]
+    }
+
+    fn verify(evidence: &evidence::Evidence) -> Result<(), tendermint::Error> {
+        let validator_set =
+            validator::Set::without_proposer(generate_validators(&validators()).unwrap());
+        let chain_id = "test-chain".parse::<chain::Id>().unwrap();
+        match evidence {
+            evidence::Evidence::DuplicateVote(ev) => ev.verify(&validator_set, &chain_id),
+            evidence::Evidence::LightClientAttack(ev) => ev.verify(&validator_set, &chain_id),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_vote() {
+        let evidence = Evidence::new(&validators()).byzantine(&[1]);
+        let generated = evidence.generate().unwrap();
+        assert!(verify(&generated).is_ok());
+        match &generated {
+            evidence::Evidence::DuplicateVote(ev) => {
+                let (a, b) = ev.votes();
+                assert_eq!(
+                    a.validator_address,
+                    validators()[1].generate().unwrap().address
+                );
+                assert_ne!(a.block_id, b.block_id);
+            }
+            _ => panic!("expected duplicate vote evidence"),
+        }
+
+        for invalid in &[
+            Invalidity::SameBlock,
+            Invalidity::DifferentHeights,
+            Invalidity::DifferentRounds,
+            Invalidity::DifferentValidators,
+            Invalidity::BadSignature,
+            Invalidity::WrongValidatorPower,
+            Invalidity::WrongTotalPower,
+        ] {
+            let generated = evidence.clone().invalid(*invalid).generate().unwrap();
+            assert!(verify(&generated).is_err(), "{:?} verified", invalid);
+        }
+        assert!(evidence.invalid(Invalidity::WrongChain).generate().is_err());
+    }
+
+    #[test]
+    fn test_light_client_attacks() {
+        for misbehavior in &[
+            Misbehavior::Equivocation,
+            Misbehavior::Lunatic,
+            Misbehavior::Amnesia,
+        ] {
+            let evidence = Evidence::new(&validators()).misbehavior(*misbehavior);
+            let generated = evidence.generate().unwrap();
+            assert!(verify(&generated).is_ok(), "{:?} failed", misbehavior);
+            let ev = match &generated {
+                evidence::Evidence::LightClientAttack(ev) => ev,
+                _ => panic!("expected light client attack evidence"),
+            };
+            let header = &ev.conflicting_block.signed_header.header;
+            match misbehavior {
+                Misbehavior::Lunatic => {
+                    assert_eq!(ev.common_height.value(), 2);
+                    assert_eq!(header.height.value(), 3);
+                    assert_eq!(ev.byzantine_validators.len(), 2);
+                    assert_ne!(
+                        header.validators_hash,
+                        validator::Set::without_proposer(
+                            generate_validators(&validators()).unwrap()
+                        )
+                        .hash()
+                    );
+                }
+                Misbehavior::Equivocation => {
+                    assert_eq!(header.height, ev.common_height);
+                    assert_eq!(ev.byzantine_validators.len(), 3);
+                }
+                _ => {
+                    assert_eq!(ev.conflicting_block.signed_header.commit.round.value(), 2);
+                    assert!(ev.byzantine_validators.is_empty());
+                }
+            }
+
+            for invalid in &[
+                Invalidity::WrongTotalPower,
+                Invalidity::WrongChain,
+                Invalidity::BelowCommonHeight,
+                Invalidity::ValidatorSetMismatch,
+                Invalidity::UnknownByzantineValidator,
+            ] {
+                let generated = evidence.clone().invalid(*invalid).generate().unwrap();
+                assert!(
+                    verify(&generated).is_err(),
+                    "{:?} {:?} verified",
+                    misbehavior,
+                    invalid
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use gumdrop::Options;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+
+use crate::consensus::default_consensus_params;
+use crate::{helpers::*, Command, Generator, Header, Validator, ValidatorSet};
+use tendermint::{consensus, hash::Algorithm, Hash};
+
+/// The structures whose hashes are produced as test vectors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Structure {
+    /// A block header, hashed by `Header::hash()`
+    Header,
+    /// A validator set, hashed by `Set::hash()`
+    ValidatorSet,
+    /// Consensus parameters, hashed by `Params::hash()`
+    ConsensusParams,
+}
+
+impl Structure {
+    /// The name of the structure, as passed to the Go hasher
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Structure::Header => "header",
+            Structure::ValidatorSet => "validator-set",
+            Structure::ConsensusParams => "consensus-params",
+        }
+    }
+}
+
+impl std::str::FromStr for Structure {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_as::<Structure>(&format!("\"{}\"", s))
+            .map_err(|_| SimpleError::new(format!("unknown structure: {}", s)))
+    }
+}
+
+/// A hash test vector: the JSON encoding of a structure, and its expected hash.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmHashVector {
+    pub structure: Structure,
+    pub value: serde_json::Value,
+    pub hash: Hash,
+}
+
+/// A companion object for generating hash test vectors, which protect the
+/// hashing of the structures against silently diverging from Go.
+///
+/// If a Go hasher is given, each hash is confirmed by running it with the name
+/// and the JSON encoding of the structure as arguments, e.g.
+/// `go-hasher header '{"version": ...}'`: the hasher is expected to print the
+/// hash in hex, and generating the vectors fails if it differs.
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct HashVector {
+    #[options(
+        help = "structures to hash: header, validator-set or consensus-params \
+                (default: all; can be passed via STDIN), encoded as array",
+        parse(try_from_str = "parse_as::<Vec<Structure>>")
+    )]
+    pub structures: Option<Vec<Structure>>,
+    #[options(
+        help = "validators of the header and the validator set (default: two validators of \
+                voting power 50), encoded as array of 'validator' parameters",
+        parse(try_from_str = "parse_as::<Vec<Validator>>")
+    )]
+    pub validators: Option<Vec<Validator>>,
+    #[options(
+        help = "header (default: at height 1 and time 0 of the validators), encoded as \
+                'header' parameters",
+        parse(try_from_str = "parse_as::<Header>")
+    )]
+    pub header: Option<Header>,
+    #[options(
+        help = "consensus parameters (default: those of Go), encoded as JSON",
+        parse(try_from_str = "parse_as::<consensus::Params>")
+    )]
+    pub consensus_params: Option<consensus::Params>,
+    #[options(help = "Go hasher confirming the hashes (default: none)")]
+    pub go_hasher: Option<String>,
+}
+
+impl HashVector {
+    pub fn new(structures: &[Structure]) -> Self {
+        HashVector {
+            structures: Some(structures.to_vec()),
+            validators: None,
+            header: None,
+            consensus_params: None,
+            go_hasher: None,
+        }
+    }
+    set_option!(structures, &[Structure], Some(structures.to_vec()));
+    set_option!(validators, &[Validator], Some(validators.to_vec()));
+    set_option!(header, Header);
+    set_option!(consensus_params, consensus::Params);
+    set_option!(go_hasher, &str, Some(go_hasher.to_string()));
+}
+
+impl std::str::FromStr for HashVector {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vector = match parse_as::<HashVector>(s) {
+            Ok(input) => input,
+            Err(_) => HashVector::new(&[Structure::from_str(s)?]),
+        };
+        Ok(vector)
+    }
+}
+
+impl Generator<Vec<TmHashVector>> for HashVector {
+    fn merge_with_default(self, default: Self) -> Self {
+        HashVector {
+            structures: self.structures.or(default.structures),
+            validators: self.validators.or(default.validators),
+            header: self.header.or(default.header),
+            consensus_params: self.consensus_params.or(default.consensus_params),
+            go_hasher: self.go_hasher.or(default.go_hasher),
+        }
+    }
+
+    fn generate(&self) -> Result<Vec<TmHashVector>, SimpleError> {
+        let structures = self.structures.clone().unwrap_or_else(|| {
+            vec![
+                Structure::Header,
+                Structure::ValidatorSet,
+                Structure::ConsensusParams,
+            ]
+        });
+        let validators = self.validators.clone().unwrap_or_else(|| {
+            vec![
+                Validator::new("1").voting_power(50),
+                Validator::new("2").voting_power(50),
+            ]
+        });
+
+        structures
+            .into_iter()
+            .map(|structure| {
+                let (value, hash) = match structure {
+                    Structure::Header => {
+                        let default = Header::new(&validators).time(0);
+                        let header = match &self.header {
+                            Some(header) => header.clone().merge_with_default(default),
+                            None => default,
+                        };
+                        let header = header.generate()?;
+                        (to_value(&header)?, header.hash())
+                    }
+                    Structure::ValidatorSet => {
+                        let set = ValidatorSet::new(vec![])
+                            .validators(&validators)
+                            .generate()?;
+                        (to_value(&set)?, set.hash())
+                    }
+                    Structure::ConsensusParams => {
+                        let params = self
+                            .consensus_params
+                            .clone()
+                            .unwrap_or_else(default_consensus_params);
+                        (to_value(&params)?, params.hash())
+                    }
+                };
+                if let Some(go_hasher) = &self.go_hasher {
+                    confirm_hash(go_hasher, structure, &value, hash)?;
+                }
+                Ok(TmHashVector {
+                    structure,
+                    value,
+                    hash,
+                })
+            })
+            .collect()
+    }
+}
+
+fn to_value<T: Serialize>(structure: &T) -> Result<serde_json::Value, SimpleError> {
+    Ok(try_with!(
+        serde_json::to_value(structure),
+        "failed to encode structure"
+    ))
+}
+
+/// Runs the Go hasher on the given structure, and checks that its hash is the
+/// expected one.
+fn confirm_hash(
+    go_hasher: &str,
+    structure: Structure,
+    value: &serde_json::Value,
+    hash: Hash,
+) -> Result<(), SimpleError> {
+    let run = try_with!(
+        Command::new()
+            .program(go_hasher)
+            .arg(structure.as_str())
+            .arg(&value.to_string())
+            .spawn(),
+        "failed to run the Go hasher"
+    );
+    if !run.status.success() {
+        bail!("the Go hasher failed: {}", run.stderr.trim())
+    }
+    let go_hash = try_with!(
+        Hash::from_hex_upper(Algorithm::Sha256, &run.stdout.trim().to_uppercase()),
+        "the Go hasher printed an invalid hash"
+    );
+    if go_hash != hash {
+        bail!(
+            "the {} hashes diverge: {} in Rust, {} in Go",
+            structure.as_str(),
+            hash,
+            go_hash
+        )
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_of(structure: Structure) -> String {
+        let vectors = HashVector::new(&[structure]).generate().unwrap();
+        assert_eq!(vectors.len(), 1);
+        assert_eq!(vectors[0].structure, structure);
+        vectors[0].hash.to_string()
+    }
+
+    #[test]
+    fn test_hash_vectors() {
+        // As computed by Go for its default consensus parameters
+        assert_eq!(
+            hash_of(Structure::ConsensusParams),
+            "048091BC7DDC283F77BFBF91D73C44DA58C3DF8A9CBC867405D8B7F3DAADA22F"
+        );
+        // Regression vectors
+        assert_eq!(
+            hash_of(Structure::Header),
+            "64BCF09517A8A4217ED28DB0745E9DE7AEDC9EEA88D31631E5F85E0CC5544387"
+        );
+        assert_eq!(
+            hash_of(Structure::ValidatorSet),
+            "E4D2147E1C5994DAF958EAFA8413706F1C75E1A2813A2CD0D32876A25D9BCF98"
+        );
+
+        let vectors = HashVector::new(&[Structure::Header])
+            .header(Header::new(&[Validator::new("a")]).height(10).time(1))
+            .generate()
+            .unwrap();
+        let header: tendermint::block::Header =
+            serde_json::from_value(vectors[0].value.clone()).unwrap();
+        assert_eq!(header.height.value(), 10);
+        assert_eq!(header.hash(), vectors[0].hash);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_go_hasher() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let go_hasher = std::env::temp_dir().join("tendermint-testgen-go-hasher");
+        std::fs::write(
+            &go_hasher,
+            "#!/bin/sh\n\
+             echo 048091bc7ddc283f77bfbf91d73c44da58c3df8a9cbc867405d8b7f3daada22f\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&go_hasher, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let go_hasher = go_hasher.to_str().unwrap();
+
+        assert!(HashVector::new(&[Structure::ConsensusParams])
+            .go_hasher(go_hasher)
+            .generate()
+            .is_ok());
+        let err = HashVector::new(&[Structure::ValidatorSet])
+            .go_hasher(go_hasher)
+            .generate()
+            .unwrap_err();
+        assert!(err.as_str().contains("hashes diverge"));
+        assert!(HashVector::new(&[Structure::ConsensusParams])
+            .go_hasher("/nonexistent/go-hasher")
+            .generate()
+            .is_err());
+    }
+}
@@ -43,13 +43,7 @@ pub fn read_stdin() -> Result<String, SimpleError> {
 }
 
 pub fn get_vote_sign_bytes(chain_id: chain::Id, vote: &vote::Vote) -> Vec<u8> {
-    let signed_vote = vote::SignedVote::new(
-        vote.clone(),
-        chain_id,
-        vote.validator_address,
-        vote.signature,
-    );
-    signed_vote.sign_bytes()
+    vote.to_signable_vec(chain_id).unwrap()
 }
 
 pub fn verify_signature(verifier: &public_key::Ed25519, msg: &[u8], signature: &Signature) -> bool {
@@ -11,20 +11,28 @@ pub mod helpers;
 /// Helper types for generating Tendermint datastructures
 pub mod commit;
 pub mod consensus;
+pub mod evidence;
 pub mod generator;
+pub mod hash_vector;
 pub mod header;
 pub mod light_block;
 pub mod light_chain;
+pub mod light_client_test;
+pub mod rpc;
 pub mod time;
 pub mod validator;
 pub mod validator_set;
 pub mod vote;
 
 pub use commit::Commit;
+pub use evidence::Evidence;
 pub use generator::Generator;
+pub use hash_vector::HashVector;
 pub use header::Header;
 pub use light_block::LightBlock;
 pub use light_chain::LightChain;
+pub use light_client_test::LightClientTest;
+pub use rpc::RpcResponse;
 pub use time::Time;
 pub use validator::Validator;
 pub use validator_set::ValidatorSet;
@@ -39,3 +47,7 @@ pub mod tester;
 pub use command::Command;
 pub use tester::TestEnv;
 pub use tester::Tester;
+
+/// Enabled with the "proptest" feature:
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
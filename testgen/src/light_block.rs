@@ -37,8 +37,10 @@ pub struct TmLightBlock {
     /// Header and commit of this block
     pub signed_header: SignedHeader,
     /// Validator set at the block height
+    #[serde(rename = "validator_set")]
     pub validators: ValidatorSet,
     /// Validator set at the next block height
+    #[serde(rename = "next_validator_set")]
     pub next_validators: ValidatorSet,
     /// The peer ID of the node that provided this block
     pub provider: PeerId,
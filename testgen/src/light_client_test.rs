@@ -0,0 +1,590 @@
+use gumdrop::Options;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+use std::convert::TryFrom;
+
+use crate::helpers::*;
+use crate::light_block::{default_peer_id, TmLightBlock};
+use crate::{Commit, Generator, Header, LightBlock, Validator};
+use tendermint::evidence::Duration as DurationStr;
+use tendermint::node::Id as PeerId;
+use tendermint::trust_threshold::TrustThresholdFraction;
+use tendermint::{block, Time};
+
+/// A light client test case, with a primary and witnesses, as consumed by the
+/// bisection and fork detection tests of the light client.
+/// NOTE: This struct & the ones below are a copy of light-client's `LightClientTest`
+/// and the types it is made of, for the reason explained in `TmLightBlock`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmLightClientTest {
+    pub description: String,
+    pub trust_options: TmTrustOptions,
+    pub primary: TmProvider,
+    pub witnesses: Vec<TmWitnessProvider>,
+    pub height_to_verify: block::Height,
+    pub now: Time,
+    pub expected_output: Option<String>,
+    pub expected_num_of_bisections: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmWitnessProvider {
+    pub value: TmProvider,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmProvider {
+    pub chain_id: String,
+    pub lite_blocks: Vec<TmLightBlock>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmTrustOptions {
+    pub period: DurationStr,
+    pub height: block::Height,
+    pub trust_level: TrustThresholdFraction,
+}
+
+/// A companion object for generating light client test cases: the primary
+/// serves a chain whose validator set may change at each height, while the
+/// witnesses serve either the same chain or, if they are faulty, a fork of it.
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct LightClientTest {
+    #[options(help = "description of the test case (default: from the other parameters)")]
+    pub description: Option<String>,
+    #[options(
+        help = "validators at heights 1, 2, ..., the last ones validating the remaining heights \
+                (default: two validators of voting power 50), encoded as array of arrays of \
+                'validator' parameters",
+        parse(try_from_str = "parse_as::<Vec<Vec<Validator>>>")
+    )]
+    pub validators: Option<Vec<Vec<Validator>>>,
+    #[options(help = "chain id (default: test-chain)")]
+    pub chain_id: Option<String>,
+    #[options(help = "height of the chains (default: height to verify)")]
+    pub length: Option<u64>,
+    #[options(help = "trusted height (default: 1)")]
+    pub trusted_height: Option<u64>,
+    #[options(help = "height to verify (required; can be passed via STDIN)")]
+    pub height_to_verify: Option<u64>,
+    #[options(
+        help = "times of the blocks at heights 1, 2, ... in seconds passed since UNIX EPOCH \
+                (default: none), the remaining heights following the last one at the block \
+                interval, encoded as array",
+        parse(try_from_str = "parse_as::<Vec<u64>>")
+    )]
+    pub times: Option<Vec<u64>>,
+    #[options(help = "seconds between the blocks following the given times (default: 1)")]
+    pub block_interval: Option<u64>,
+    #[options(help = "trusting period in seconds (default: 10 days)")]
+    pub trusting_period: Option<u64>,
+    #[options(
+        help = "clock drift tolerated by the light client in seconds, which determines the \
+                expected output (default: 10, as in Go)"
+    )]
+    pub clock_drift: Option<u64>,
+    #[options(help = "current time in seconds passed since UNIX EPOCH \
+                (default: time of the latest block + 1)")]
+    pub now: Option<u64>,
+    #[options(help = "number of witnesses (default: 1)")]
+    pub witnesses: Option<usize>,
+    #[options(
+        help = "indices of the faulty witnesses, which serve a fork of the chain (default: none), \
+                encoded as array",
+        parse(try_from_str = "parse_as::<Vec<usize>>")
+    )]
+    pub faulty_witnesses: Option<Vec<usize>>,
+    #[options(help = "height of the first block of the fork (default: height to verify)")]
+    pub fork_height: Option<u64>,
+}
+
+impl LightClientTest {
+    pub fn new(height_to_verify: u64) -> Self {
+        LightClientTest {
+            description: None,
+            validators: None,
+            chain_id: None,
+            length: None,
+            trusted_height: None,
+            height_to_verify: Some(height_to_verify),
+            times: None,
+            block_interval: None,
+            trusting_period: None,
+            clock_drift: None,
+            now: None,
+            witnesses: None,
+            faulty_witnesses: None,
+            fork_height: None,
+        }
+    }
+    set_option!(description, &str, Some(description.to_string()));
+    set_option!(validators, &[Vec<Validator>], Some(validators.to_vec()));
+    set_option!(chain_id, &str, Some(chain_id.to_string()));
+    set_option!(length, u64);
+    set_option!(trusted_height, u64);
+    set_option!(height_to_verify, u64);
+    set_option!(times, &[u64], Some(times.to_vec()));
+    set_option!(block_interval, u64);
+    set_option!(trusting_period, u64);
+    set_option!(clock_drift, u64);
+    set_option!(now, u64);
+    set_option!(witnesses, usize);
+    set_option!(faulty_witnesses, &[usize], Some(faulty_witnesses.to_vec()));
+    set_option!(fork_height, u64);
+}
+
+impl std::str::FromStr for LightClientTest {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let test = match parse_as::<LightClientTest>(s) {
+            Ok(input) => input,
+            Err(_) => LightClientTest::new(try_with!(
+                u64::from_str(s),
+                "failed to parse height to verify"
+            )),
+        };
+        Ok(test)
+    }
+}
+
+impl Generator<TmLightClientTest> for LightClientTest {
+    fn merge_with_default(self, default: Self) -> Self {
+        LightClientTest {
+            description: self.description.or(default.description),
+            validators: self.validators.or(default.validators),
+            chain_id: self.chain_id.or(default.chain_id),
+            length: self.length.or(default.length),
+            trusted_height: self.trusted_height.or(default.trusted_height),
+            height_to_verify: self.height_to_verify.or(default.height_to_verify),
+            times: self.times.or(default.times),
+            block_interval: self.block_interval.or(default.block_interval),
+            trusting_period: self.trusting_period.or(default.trusting_period),
+            clock_drift: self.clock_drift.or(default.clock_drift),
+            now: self.now.or(default.now),
+            witnesses: self.witnesses.or(default.witnesses),
+            faulty_witnesses: self.faulty_witnesses.or(default.faulty_witnesses),
+            fork_height: self.fork_height.or(default.fork_height),
+        }
+    }
+
+    fn generate(&self) -> Result<TmLightClientTest, SimpleError> {
+        let height_to_verify = match self.height_to_verify {
+            None => bail!("height to verify is missing"),
+            Some(height) => height,
+        };
+        let trusted_height = self.trusted_height.unwrap_or(1);
+        let length = self.length.unwrap_or(height_to_verify);
+        if trusted_height < 1 || trusted_height >= height_to_verify || height_to_verify > length {
+            bail!("heights must satisfy 1 <= trusted height < height to verify <= length")
+        }
+        let validators = match &self.validators {
+            Some(vals) if vals.is_empty() => bail!("validators are empty"),
+            Some(vals) => vals.clone(),
+            None => vec![vec![
+                Validator::new("1").voting_power(50),
+                Validator::new("2").voting_power(50),
+            ]],
+        };
+        let chain_id = self
+            .chain_id
+            .clone()
+            .unwrap_or_else(|| "test-chain".to_string());
+        let times = block_times(
+            self.times.as_deref().unwrap_or_default(),
+            self.block_interval.unwrap_or(1),
+            length,
+        );
+        let trusting_period = self.trusting_period.unwrap_or(60 * 60 * 24 * 10);
+        let clock_drift = self.clock_drift.unwrap_or(10);
+        let now = self.now.unwrap_or(times[length as usize - 1] + 1);
+        let num_witnesses = self.witnesses.unwrap_or(1);
+        let faulty_witnesses = self.faulty_witnesses.clone().unwrap_or_default();
+        if let Some(&index) = faulty_witnesses.iter().find(|&&i| i >= num_witnesses) {
+            bail!("faulty witness {} out of bounds", index)
+        }
+        let fork_height = self.fork_height.unwrap_or(height_to_verify);
+        if !faulty_witnesses.is_empty() && (fork_height <= trusted_height || fork_height > length) {
+            bail!("fork height must be above the trusted height and at most the length")
+        }
+
+        let primary = generate_chain(&chain_id, &validators, &times, None)?;
+        let fork = if faulty_witnesses.is_empty() {
+            None
+        } else {
+            Some(generate_chain(
+                &chain_id,
+                &validators,
+                &times,
+                Some(fork_height),
+            )?)
+        };
+
+        let provide = |chain: &[LightBlock], provider: PeerId| {
+            let lite_blocks = chain
+                .iter()
+                .map(|lb| {
+                    let mut lb = lb.generate()?;
+                    lb.provider = provider;
+                    Ok(lb)
+                })
+                .collect::<Result<Vec<_>, SimpleError>>()?;
+            Ok(TmProvider {
+                chain_id: chain_id.clone(),
+                lite_blocks,
+            })
+        };
+        let witnesses = (0..num_witnesses)
+            .map(|i| {
+                let chain = match &fork {
+                    Some(fork) if faulty_witnesses.contains(&i) => fork,
+                    _ => &primary,
+                };
+                provide(chain, witness_peer_id(i)).map(|value| TmWitnessProvider { value })
+            })
+            .collect::<Result<Vec<_>, SimpleError>>()?;
+        let primary = provide(&primary, default_peer_id())?;
+
+        let trust_level = TrustThresholdFraction::default();
+        let (expected_num_of_bisections, verified) = verify_bisection(
+            &primary.lite_blocks,
+            &times,
+            trusted_height,
+            height_to_verify,
+            trust_level,
+            trusting_period,
+            clock_drift,
+            now,
+        );
+        // A fork is detected if any witness serves another block at the height to verify
+        let forked = !faulty_witnesses.is_empty() && fork_height <= height_to_verify;
+        let expected_output = if !verified || forked {
+            Some("error".to_string())
+        } else {
+            None
+        };
+
+        let description = self.description.clone().unwrap_or_else(|| {
+            format!(
+                "Case: Trusted height={}, bisecting to verify height={} with {} witness(es), \
+                 faulty witnesses={:?}, should {}",
+                trusted_height,
+                height_to_verify,
+                num_witnesses,
+                faulty_witnesses,
+                if expected_output.is_some() {
+                    "expect error"
+                } else {
+                    "verify successfully"
+                }
+            )
+        });
+
+        Ok(TmLightClientTest {
+            description,
+            trust_options: TmTrustOptions {
+                period: DurationStr(std::time::Duration::from_secs(trusting_period)),
+                height: height_from(trusted_height)?,
+                trust_level,
+            },
+            primary,
+            witnesses,
+            height_to_verify: height_from(height_to_verify)?,
+            now: get_time(now),
+            expected_output,
+            expected_num_of_bisections,
+        })
+    }
+}
+
+/// The peer ID of the witness at the given index
+pub fn witness_peer_id(index: usize) -> PeerId {
+    format!("{:040X}", index + 1).parse().unwrap()
+}
+
+/// The times of the blocks of a chain of the given length: the given times for
+/// the first heights, followed by times increasing by the given interval.
+/// Without times, the block at each height `h` is timestamped `h * interval`.
+pub(crate) fn block_times(times: &[u64], interval: u64, length: u64) -> Vec<u64> {
+    let last = times.last().copied().unwrap_or(0);
+    (1..=length)
+        .map(|height| match times.get(height as usize - 1) {
+            Some(&time) => time,
+            None => last + (height - times.len() as u64) * interval,
+        })
+        .collect()
+}
+
+/// Generates a chain with a block for each of the given times, in which the
+/// block at each height `h` is validated by `validators[h - 1]`, or by the last
+/// validators for the heights above them, and is timestamped `times[h - 1]`
+/// seconds after UNIX EPOCH. The times aren't required to increase, so that
+/// chains violating BFT time can be generated.
+/// If a fork height is given, the blocks from it on are proposed by another
+/// validator, so that they conflict with those of the chain without a fork.
+pub(crate) fn generate_chain(
+    chain_id: &str,
+    validators: &[Vec<Validator>],
+    times: &[u64],
+    fork_height: Option<u64>,
+) -> Result<Vec<LightBlock>, SimpleError> {
+    let length = times.len() as u64;
+    let validators_at = |height: u64| {
+        let index = std::cmp::min(height as usize, validators.len()) - 1;
+        &validators[index]
+    };
+    let mut chain: Vec<LightBlock> = Vec::with_capacity(length as usize);
+    for height in 1..=length {
+        let vals = validators_at(height);
+        let next_vals = validators_at(height + 1);
+        let proposer = match fork_height {
+            Some(fork_height) if height >= fork_height => {
+                if vals.len() < 2 {
+                    bail!("forking requires at least two validators")
+                }
+                1
+            }
+            _ => 0,
+        };
+        let mut header = Header::new(vals)
+            .next_validators(next_vals)
+            .chain_id(chain_id)
+            .height(height)
+            .time(times[height as usize - 1])
+            .proposer(proposer);
+        if let Some(prev) = chain.last() {
+            let prev_header = prev.header.as_ref().unwrap().generate()?;
+            header = header.last_block_id_hash(prev_header.hash());
+        }
+        let commit = Commit::new(header.clone(), 1);
+        chain.push(
+            LightBlock::new(header, commit)
+                .validators(vals)
+                .next_validators(next_vals),
+        );
+    }
+    Ok(chain)
+}
+
+/// Follows the light client verifying the given height from the trusted one
+/// with its basic bisecting schedule: each time the trusted validators don't
+/// have enough voting power in the validators of the block at the current
+/// height, it bisects to the midpoint of both. Verification fails as soon as
+/// the trusted block expired, the current block is from the future (beyond
+/// the clock drift), or it isn't more recent than the trusted one.
+/// Returns the number of bisections done, and whether verification succeeds.
+#[allow(clippy::too_many_arguments)]
+fn verify_bisection(
+    chain: &[TmLightBlock],
+    times: &[u64],
+    trusted_height: u64,
+    height_to_verify: u64,
+    trust_level: TrustThresholdFraction,
+    trusting_period: u64,
+    clock_drift: u64,
+    now: u64,
+) -> (usize, bool) {
+    let block_at = |height: u64| &chain[height as usize - 1];
+    let time_at = |height: u64| times[height as usize - 1];
+    let mut trusted = trusted_height;
+    let mut current = height_to_verify;
+    let mut bisections = 0;
+    while trusted < height_to_verify {
+        if time_at(trusted) + trusting_period <= now
+            || time_at(current) >= now + clock_drift
+            || time_at(current) <= time_at(trusted)
+        {
+            return (bisections, false);
+        }
+        if current == trusted + 1
+            || has_sufficient_overlap(block_at(trusted), block_at(current), trust_level)
+        {
+            trusted = current;
+            current = height_to_verify;
+        } else {
+            bisections += 1;
+            current = trusted + (current + 1 - trusted) / 2;
+        }
+    }
+    (bisections, true)
+}
+
+/// Whether the next validators of the trusted block have more voting power
+/// than the trust level in the validators of the untrusted one, which all
+/// sign its commit.
+fn has_sufficient_overlap(
+    trusted: &TmLightBlock,
+    untrusted: &TmLightBlock,
+    trust_level: TrustThresholdFraction,
+) -> bool {
+    let total = trusted.next_validators.total_voting_power().value();
+    let overlap: u64 = trusted
+        .next_validators
+        .validators()
+        .iter()
+        .filter(|v| {
+            untrusted
+                .validators
+                .validators()
+                .iter()
+                .any(|u| u.address == v.address)
+        })
+        .map(|v| v.power())
+        .sum();
+    overlap * trust_level.denominator > total * trust_level.numerator
+}
+
+fn height_from(height: u64) -> Result<block::Height, SimpleError> {
+    block::Height::try_from(height).map_err(|_| SimpleError::new("height out of bounds"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_honest_witnesses() {
+        let test = LightClientTest::new(5).witnesses(2).generate().unwrap();
+
+        assert_eq!(test.primary.lite_blocks.len(), 5);
+        assert_eq!(test.witnesses.len(), 2);
+        for witness in &test.witnesses {
+            for (w, p) in witness
+                .value
+                .lite_blocks
+                .iter()
+                .zip(&test.primary.lite_blocks)
+            {
+                assert_eq!(w.signed_header, p.signed_header);
+                assert_ne!(w.provider, p.provider);
+            }
+        }
+        for (i, lb) in test.primary.lite_blocks.iter().enumerate().skip(1) {
+            let prev = &test.primary.lite_blocks[i - 1];
+            assert_eq!(
+                lb.signed_header.header.last_block_id.map(|id| id.hash),
+                Some(prev.signed_header.header.hash())
+            );
+        }
+        assert_eq!(test.expected_output, None);
+        assert_eq!(test.expected_num_of_bisections, 0);
+    }
+
+    #[test]
+    fn test_faulty_witness() {
+        let test = LightClientTest::new(5)
+            .witnesses(2)
+            .faulty_witnesses(&[1])
+            .fork_height(3)
+            .generate()
+            .unwrap();
+
+        let primary = &test.primary.lite_blocks;
+        let honest = &test.witnesses[0].value.lite_blocks;
+        let faulty = &test.witnesses[1].value.lite_blocks;
+        for height in 0..5 {
+            assert_eq!(honest[height].signed_header, primary[height].signed_header);
+            if height < 2 {
+                assert_eq!(faulty[height].signed_header, primary[height].signed_header);
+            } else {
+                assert_ne!(faulty[height].signed_header, primary[height].signed_header);
+            }
+        }
+        assert_eq!(test.expected_output, Some("error".to_string()));
+
+        // A fork above the height to verify goes unnoticed
+        let test = LightClientTest::new(4)
+            .length(5)
+            .faulty_witnesses(&[0])
+            .fork_height(5)
+            .generate()
+            .unwrap();
+        assert_eq!(test.expected_output, None);
+
+        assert!(LightClientTest::new(5)
+            .faulty_witnesses(&[1])
+            .generate()
+            .is_err());
+        assert!(LightClientTest::new(5)
+            .faulty_witnesses(&[0])
+            .fork_height(1)
+            .generate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_bisections() {
+        let vals = |ids: &[&str]| -> Vec<Validator> {
+            ids.iter()
+                .map(|id| Validator::new(id).voting_power(50))
+                .collect()
+        };
+        // The validators are replaced gradually, so that the validators at
+        // height 1 have no voting power left from height 5 on
+        let test = LightClientTest::new(8)
+            .validators(&[
+                vals(&["a", "b", "c"]),
+                vals(&["a", "b", "c"]),
+                vals(&["b", "c", "d"]),
+                vals(&["c", "d", "e"]),
+                vals(&["d", "e", "f"]),
+            ])
+            .generate()
+            .unwrap();
+        // 1 -> 8 fails, 1 -> 5 fails, 1 -> 3 succeeds, 3 -> 8 succeeds
+        assert_eq!(test.expected_num_of_bisections, 2);
+        assert_eq!(test.expected_output, None);
+
+        let test = LightClientTest::new(8)
+            .trusting_period(5)
+            .generate()
+            .unwrap();
+        assert_eq!(test.expected_output, Some("error".to_string()));
+    }
+
+    #[test]
+    fn test_block_times() {
+        assert_eq!(block_times(&[], 1, 3), vec![1, 2, 3]);
+        assert_eq!(block_times(&[], 10, 3), vec![10, 20, 30]);
+        assert_eq!(block_times(&[5, 3], 10, 4), vec![5, 3, 13, 23]);
+
+        let test = LightClientTest::new(3)
+            .times(&[100])
+            .block_interval(60)
+            .generate()
+            .unwrap();
+        let time_at = |height: usize| test.primary.lite_blocks[height].signed_header.header.time;
+        assert_eq!(time_at(0), get_time(100));
+        assert_eq!(time_at(2), get_time(220));
+        assert_eq!(test.now, get_time(221));
+        assert_eq!(test.expected_output, None);
+
+        // The trusted block expires while the chain grows
+        let test = LightClientTest::new(3)
+            .block_interval(60)
+            .trusting_period(100)
+            .generate()
+            .unwrap();
+        assert_eq!(test.expected_output, Some("error".to_string()));
+
+        // The clock of the light client is behind the chain, within the clock drift or not
+        let test = LightClientTest::new(3)
+            .times(&[100, 110, 120])
+            .now(111)
+            .generate()
+            .unwrap();
+        assert_eq!(test.expected_output, None);
+        let test = LightClientTest::new(3)
+            .times(&[100, 110, 120])
+            .now(110)
+            .generate()
+            .unwrap();
+        assert_eq!(test.expected_output, Some("error".to_string()));
+
+        // BFT time doesn't increase
+        let test = LightClientTest::new(3)
+            .times(&[100, 110, 100])
+            .generate()
+            .unwrap();
+        assert_eq!(test.expected_output, Some("error".to_string()));
+    }
+}
@@ -0,0 +1,332 @@
+use gumdrop::Options;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+
+use crate::light_block::default_peer_id;
+use crate::light_client_test::{block_times, generate_chain};
+use crate::{helpers::*, Generator, Validator};
+use tendermint::block::{self, parts::Header as PartSetHeader, signed_header::SignedHeader};
+use tendermint::{evidence, node, serializers, validator, AppHash, Block, Hash, Time};
+
+/// The RPC endpoints whose responses can be generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcMethod {
+    /// `/block`
+    Block,
+    /// `/commit`
+    Commit,
+    /// `/validators`
+    Validators,
+    /// `/status`
+    Status,
+}
+
+impl std::str::FromStr for RpcMethod {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "block" => Ok(RpcMethod::Block),
+            "commit" => Ok(RpcMethod::Commit),
+            "validators" => Ok(RpcMethod::Validators),
+            "status" => Ok(RpcMethod::Status),
+            _ => bail!("unsupported RPC method: {}", s),
+        }
+    }
+}
+
+/// A JSON-RPC response envelope, as returned by a Tendermint node.
+/// NOTE: This struct & the ones below are copies of the response types of the
+/// rpc crate, which can't be depended upon here, as it depends on this crate
+/// for its tests.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmRpcResponse {
+    pub jsonrpc: String,
+    pub id: String,
+    pub result: TmRpcResult,
+}
+
+/// The result of a JSON-RPC response, serialized as the response of its endpoint
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TmRpcResult {
+    Block(TmBlockResponse),
+    Commit(TmCommitResponse),
+    Validators(TmValidatorsResponse),
+    Status(Box<TmStatusResponse>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmBlockResponse {
+    pub block_id: block::Id,
+    pub block: Block,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmCommitResponse {
+    pub signed_header: SignedHeader,
+    pub canonical: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmValidatorsResponse {
+    pub block_height: block::Height,
+    pub validators: Vec<validator::Info>,
+    #[serde(with = "serializers::from_str")]
+    pub count: usize,
+    #[serde(with = "serializers::from_str")]
+    pub total: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmStatusResponse {
+    pub node_info: node::Info,
+    pub sync_info: TmSyncInfo,
+    pub validator_info: validator::Info,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TmSyncInfo {
+    #[serde(with = "serializers::hash")]
+    pub latest_block_hash: Hash,
+    #[serde(with = "serializers::apphash")]
+    pub latest_app_hash: AppHash,
+    pub latest_block_height: block::Height,
+    pub latest_block_time: Time,
+    pub catching_up: bool,
+}
+
+/// A companion object for generating the JSON-RPC responses of a node serving
+/// a chain. All the responses generated for the same chain are consistent with
+/// each other, e.g. the block ID of a block is the one its commit is for.
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct RpcResponse {
+    #[options(
+        help = "RPC method: block, commit, validators or status (required; can be passed via STDIN)"
+    )]
+    pub method: Option<RpcMethod>,
+    #[options(
+        help = "validators at heights 1, 2, ..., the last ones validating the remaining heights \
+                (default: two validators of voting power 50), encoded as array of arrays of \
+                'validator' parameters",
+        parse(try_from_str = "parse_as::<Vec<Vec<Validator>>>")
+    )]
+    pub validators: Option<Vec<Vec<Validator>>>,
+    #[options(help = "chain id (default: test-chain)")]
+    pub chain_id: Option<String>,
+    #[options(help = "height of the chain (default: height, or 1)")]
+    pub length: Option<u64>,
+    #[options(help = "height queried, ignored by status (default: length)")]
+    pub height: Option<u64>,
+    #[options(help = "JSON-RPC request id (default: empty)")]
+    pub id: Option<String>,
+}
+
+impl RpcResponse {
+    pub fn new(method: RpcMethod) -> Self {
+        RpcResponse {
+            method: Some(method),
+            validators: None,
+            chain_id: None,
+            length: None,
+            height: None,
+            id: None,
+        }
+    }
+    set_option!(method, RpcMethod);
+    set_option!(validators, &[Vec<Validator>], Some(validators.to_vec()));
+    set_option!(chain_id, &str, Some(chain_id.to_string()));
+    set_option!(length, u64);
+    set_option!(height, u64);
+    set_option!(id, &str, Some(id.to_string()));
+}
+
+impl std::str::FromStr for RpcResponse {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let response = match parse_as::<RpcResponse>(s) {
+            Ok(input) => input,
+            Err(_) => RpcResponse::new(RpcMethod::from_str(s)?),
+        };
+        Ok(response)
+    }
+}
+
+impl Generator<TmRpcResponse> for RpcResponse {
+    fn merge_with_default(self, default: Self) -> Self {
+        RpcResponse {
+            method: self.method.or(default.method),
+            validators: self.validators.or(default.validators),
+            chain_id: self.chain_id.or(default.chain_id),
+            length: self.length.or(default.length),
+            height: self.height.or(default.height),
+            id: self.id.or(default.id),
+        }
+    }
+
+    fn generate(&self) -> Result<TmRpcResponse, SimpleError> {
+        let method = match self.method {
+            None => bail!("RPC method is missing"),
+            Some(method) => method,
+        };
+        let length = self.length.or(self.height).unwrap_or(1);
+        let height = match method {
+            RpcMethod::Status => length,
+            _ => self.height.unwrap_or(length),
+        };
+        if height < 1 || height > length {
+            bail!("heights must satisfy 1 <= height <= length")
+        }
+        let validators = match &self.validators {
+            Some(vals) if vals.is_empty() => bail!("validators are empty"),
+            Some(vals) => vals.clone(),
+            None => vec![vec![
+                Validator::new("1").voting_power(50),
+                Validator::new("2").voting_power(50),
+            ]],
+        };
+        let chain_id = self
+            .chain_id
+            .clone()
+            .unwrap_or_else(|| "test-chain".to_string());
+
+        let chain = generate_chain(&chain_id, &validators, &block_times(&[], 1, length), None)?;
+        let light_block = chain[height as usize - 1].generate()?;
+        let header = light_block.signed_header.header.clone();
+
+        let result = match method {
+            RpcMethod::Block => {
+                let last_commit = match height {
+                    1 => None,
+                    _ => Some(chain[height as usize - 2].generate()?.signed_header.commit),
+                };
+                let block = try_with!(
+                    Block::new(
+                        header.clone(),
+                        Default::default(),
+                        evidence::Data::new(vec![]),
+                        last_commit,
+                    ),
+                    "failed to generate block"
+                );
+                TmRpcResult::Block(TmBlockResponse {
+                    block_id: block::Id {
+                        hash: header.hash(),
+                        part_set_header: PartSetHeader::new(1, header.hash()).unwrap(),
+                    },
+                    block,
+                })
+            }
+            RpcMethod::Commit => TmRpcResult::Commit(TmCommitResponse {
+                signed_header: light_block.signed_header,
+                canonical: true,
+            }),
+            RpcMethod::Validators => {
+                let validators = light_block.validators.validators().clone();
+                TmRpcResult::Validators(TmValidatorsResponse {
+                    block_height: header.height,
+                    count: validators.len(),
+                    total: validators.len(),
+                    validators,
+                })
+            }
+            RpcMethod::Status => TmRpcResult::Status(Box::new(TmStatusResponse {
+                node_info: node_info(&chain_id)?,
+                sync_info: TmSyncInfo {
+                    latest_block_hash: header.hash(),
+                    latest_app_hash: header.app_hash.clone(),
+                    latest_block_height: header.height,
+                    latest_block_time: header.time,
+                    catching_up: false,
+                },
+                validator_info: light_block.validators.validators()[0],
+            })),
+        };
+
+        Ok(TmRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: self.id.clone().unwrap_or_default(),
+            result,
+        })
+    }
+}
+
+/// The node information of the node serving the chain
+fn node_info(chain_id: &str) -> Result<node::Info, SimpleError> {
+    let info = serde_json::json!({
+        "protocol_version": {
+            "p2p": "8",
+            "block": "11",
+            "app": "0"
+        },
+        "id": default_peer_id(),
+        "listen_addr": "tcp://0.0.0.0:26656",
+        "network": chain_id,
+        "version": "0.34.0",
+        "channels": "40202122233038606100",
+        "moniker": "testgen",
+        "other": {
+            "tx_index": "on",
+            "rpc_address": "tcp://0.0.0.0:26657"
+        }
+    });
+    Ok(try_with!(
+        serde_json::from_value(info),
+        "failed to generate node info"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(method: RpcMethod) -> TmRpcResult {
+        RpcResponse::new(method)
+            .length(5)
+            .height(3)
+            .generate()
+            .unwrap()
+            .result
+    }
+
+    #[test]
+    fn test_rpc_responses_are_consistent() {
+        let block = match generate(RpcMethod::Block) {
+            TmRpcResult::Block(response) => response,
+            _ => panic!("expected a block response"),
+        };
+        let commit = match generate(RpcMethod::Commit) {
+            TmRpcResult::Commit(response) => response,
+            _ => panic!("expected a commit response"),
+        };
+        let validators = match generate(RpcMethod::Validators) {
+            TmRpcResult::Validators(response) => response,
+            _ => panic!("expected a validators response"),
+        };
+        let status = match generate(RpcMethod::Status) {
+            TmRpcResult::Status(response) => response,
+            _ => panic!("expected a status response"),
+        };
+
+        assert_eq!(block.block.header, commit.signed_header.header);
+        assert_eq!(block.block_id, commit.signed_header.commit.block_id);
+        assert_eq!(
+            block.block.last_commit.as_ref().unwrap().block_id.hash,
+            block.block.header.last_block_id.unwrap().hash
+        );
+        assert_eq!(validators.block_height.value(), 3);
+        assert_eq!(
+            validator::Set::without_proposer(validators.validators).hash(),
+            block.block.header.validators_hash
+        );
+        assert_eq!(status.sync_info.latest_block_height.value(), 5);
+        assert_eq!(status.node_info.network.as_str(), "test-chain");
+
+        let envelope = RpcResponse::new(RpcMethod::Block).id("1").encode().unwrap();
+        let envelope: serde_json::Value = serde_json::from_str(&envelope).unwrap();
+        assert_eq!(envelope["jsonrpc"], "2.0");
+        assert_eq!(envelope["id"], "1");
+        // As in Go, the first block has an empty last commit
+        assert_eq!(envelope["result"]["block"]["last_commit"]["height"], "0");
+    }
+}
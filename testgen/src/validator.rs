@@ -108,7 +108,10 @@ impl Generator<validator::Info> for Validator {
         let info = validator::Info {
             address: account::Id::from(keypair.public),
             pub_key: PublicKey::from(keypair.public),
-            voting_power: vote::Power::try_from(self.voting_power.unwrap_or(0)).unwrap(),
+            voting_power: try_with!(
+                vote::Power::try_from(self.voting_power.unwrap_or(0)),
+                "voting power is out of range"
+            ),
             proposer_priority: validator::ProposerPriority::from(
                 self.proposer_priority.unwrap_or_default(),
             ),
@@ -6,7 +6,70 @@ use crate::{
 use gumdrop::Options;
 use serde::{Deserialize, Serialize};
 use simple_error::*;
-use tendermint::validator;
+use tendermint::validator::{self, MAX_TOTAL_VOTING_POWER};
+
+/// Distributions of the voting power of a validator set, at the edges of the
+/// voting power arithmetic. The voting power of the validators is replaced by
+/// the one of the distribution, in the given order of the validators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Distribution {
+    /// The total voting power is the maximum one accepted by Tendermint
+    MaxTotalPower,
+    /// The total voting power is `i64::MAX`, far above the maximum one
+    /// accepted by Tendermint, so that multiplying it overflows
+    NearOverflow,
+    /// The first validator has exactly 1/3 of the total voting power
+    OneThird,
+    /// The first validator has 1/3 of the total voting power, plus one
+    OverOneThird,
+    /// The first validator has exactly 2/3 of the total voting power
+    TwoThirds,
+    /// The first validator has 2/3 of the total voting power, plus one
+    OverTwoThirds,
+}
+
+impl std::str::FromStr for Distribution {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_as::<Distribution>(&format!("\"{}\"", s))
+            .map_err(|_| SimpleError::new(format!("unknown distribution: {}", s)))
+    }
+}
+
+impl Distribution {
+    /// The voting powers of the given number of validators: the total voting
+    /// power is spread evenly over the validators, or over all but the first
+    /// one for the 1/3 and 2/3 boundaries, the last one getting the remainder.
+    pub fn voting_powers(self, num_validators: usize) -> Result<Vec<u64>, SimpleError> {
+        if num_validators < 2 {
+            bail!("distributing voting power requires at least two validators")
+        }
+        let n = num_validators as u64;
+        let spread = |total: u64, num: u64| {
+            let mut powers = vec![total / num; num as usize];
+            *powers.last_mut().unwrap() += total % num;
+            powers
+        };
+        // A total voting power of 300 for each of the other validators
+        // makes the boundaries exact
+        let third = 100 * (n - 1);
+        let boundary = |first: u64| {
+            let mut powers = vec![first];
+            powers.extend(spread(3 * third - first, n - 1));
+            powers
+        };
+        let powers = match self {
+            Distribution::MaxTotalPower => spread(MAX_TOTAL_VOTING_POWER, n),
+            Distribution::NearOverflow => spread(i64::MAX as u64, n),
+            Distribution::OneThird => boundary(third),
+            Distribution::OverOneThird => boundary(third + 1),
+            Distribution::TwoThirds => boundary(2 * third),
+            Distribution::OverTwoThirds => boundary(2 * third + 1),
+        };
+        Ok(powers)
+    }
+}
 
 #[derive(Debug, Options, Serialize, Deserialize, Clone)]
 pub struct ValidatorSet {
@@ -15,6 +78,17 @@ pub struct ValidatorSet {
         help = "validators (required)"
     )]
     pub validators: Option<Vec<Validator>>,
+    #[options(
+        help = "distribution of the voting power over the validators: max-total-power, \
+                near-overflow, one-third, over-one-third, two-thirds or over-two-thirds \
+                (default: the voting power of the validators)"
+    )]
+    pub distribution: Option<Distribution>,
+    #[options(
+        help = "number of copies of the first validator added to the set, so that their \
+                addresses are duplicate (default: 0)"
+    )]
+    pub duplicates: Option<usize>,
 }
 
 impl ValidatorSet {
@@ -25,8 +99,13 @@ impl ValidatorSet {
             .collect::<Vec<Validator>>();
         Self {
             validators: Some(validators),
+            distribution: None,
+            duplicates: None,
         }
     }
+    set_option!(validators, &[Validator], Some(validators.to_vec()));
+    set_option!(distribution, Distribution);
+    set_option!(duplicates, usize);
 }
 
 impl std::str::FromStr for ValidatorSet {
@@ -44,11 +123,40 @@ impl Generator<validator::Set> for ValidatorSet {
     fn merge_with_default(self, default: Self) -> Self {
         ValidatorSet {
             validators: self.validators.or(default.validators),
+            distribution: self.distribution.or(default.distribution),
+            duplicates: self.duplicates.or(default.duplicates),
         }
     }
 
     fn generate(&self) -> Result<validator::Set, SimpleError> {
-        let vals = generate_validators(&self.validators.as_ref().unwrap())?;
+        let mut vals = match &self.validators {
+            None => bail!("validators are missing"),
+            Some(vals) => vals.clone(),
+        };
+        if let Some(distribution) = self.distribution {
+            let powers = distribution.voting_powers(vals.len())?;
+            vals = vals
+                .into_iter()
+                .zip(powers)
+                .map(|(val, power)| val.voting_power(power))
+                .collect();
+        }
+        if let Some(duplicates) = self.duplicates {
+            let duplicate = vals.first().cloned();
+            if duplicate.is_none() && duplicates > 0 {
+                bail!("duplicating validators requires at least one validator")
+            }
+            vals.extend(duplicate.into_iter().cycle().take(duplicates));
+        }
+        let vals = generate_validators(&vals)?;
+        // The total voting power of a set must fit into an i64
+        let total = vals
+            .iter()
+            .try_fold(0u64, |total, val| total.checked_add(val.power()));
+        match total {
+            Some(total) if total <= i64::MAX as u64 => {}
+            _ => bail!("total voting power overflows"),
+        }
         Ok(validator::Set::without_proposer(vals))
     }
 }
@@ -85,4 +193,68 @@ mod tests {
         let valset5 = validator::Set::without_proposer(generate_validators(&vals2).unwrap());
         assert_ne!(valset2.hash(), valset5.hash());
     }
+    #[test]
+    fn test_voting_power_distributions() {
+        let ids = vec!["a", "b", "c", "d"];
+        let total = |set: &validator::Set| set.total_voting_power().value();
+        let first = |set: &validator::Set, id: &str| {
+            let address = Validator::new(id).generate().unwrap().address;
+            set.validator(address).unwrap().power()
+        };
+
+        let set = ValidatorSet::new(ids.clone())
+            .distribution(Distribution::MaxTotalPower)
+            .generate()
+            .unwrap();
+        assert_eq!(total(&set), MAX_TOTAL_VOTING_POWER);
+
+        let set = ValidatorSet::new(ids.clone())
+            .distribution(Distribution::NearOverflow)
+            .generate()
+            .unwrap();
+        assert_eq!(total(&set), i64::MAX as u64);
+
+        for (distribution, numerator, excess) in &[
+            (Distribution::OneThird, 1, 0),
+            (Distribution::OverOneThird, 1, 1),
+            (Distribution::TwoThirds, 2, 0),
+            (Distribution::OverTwoThirds, 2, 1),
+        ] {
+            let set = ValidatorSet::new(ids.clone())
+                .distribution(*distribution)
+                .generate()
+                .unwrap();
+            assert_eq!(total(&set) % 3, 0);
+            assert_eq!(first(&set, "a"), total(&set) / 3 * numerator + excess);
+        }
+
+        assert!(ValidatorSet::new(vec!["a"])
+            .distribution(Distribution::OneThird)
+            .generate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_duplicate_addresses() {
+        let set = ValidatorSet::new(vec!["a", "b"])
+            .duplicates(2)
+            .generate()
+            .unwrap();
+        let vals = set.validators();
+        assert_eq!(vals.len(), 4);
+        assert_eq!(total_power(vals), 200);
+        let address = Validator::new("a").generate().unwrap().address;
+        assert_eq!(vals.iter().filter(|v| v.address == address).count(), 3);
+
+        // Duplicating the validators of an overflowing set makes its total overflow
+        assert!(ValidatorSet::new(vec!["a", "b"])
+            .distribution(Distribution::NearOverflow)
+            .duplicates(1)
+            .generate()
+            .is_err());
+    }
+
+    fn total_power(vals: &[validator::Info]) -> u64 {
+        vals.iter().map(|v| v.power()).sum()
+    }
 }
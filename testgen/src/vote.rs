@@ -134,6 +134,8 @@ impl Generator<vote::Vote> for Vote {
                 signature::Ed25519Signature::try_from(&[0_u8; ED25519_SIGNATURE_SIZE][..]),
                 "failed to construct empty ed25519 signature"
             )),
+            extension: vec![],
+            extension_signature: None,
         };
         let sign_bytes = get_vote_sign_bytes(block_header.chain_id, &vote);
         vote.signature = signer.sign(sign_bytes.as_slice()).into();
@@ -24,7 +24,7 @@ use tendermint_light_client::{
     types::{Height, PeerId, Status, TrustThreshold},
 };
 
-use tendermint::abci::transaction::Hash as TxHash;
+use tendermint::Hash;
 use tendermint_rpc as rpc;
 
 use std::convert::TryFrom;
@@ -34,7 +34,7 @@ struct TestEvidenceReporter;
 
 #[contracts::contract_trait]
 impl EvidenceReporter for TestEvidenceReporter {
-    fn report(&self, evidence: Evidence, peer: PeerId) -> Result<TxHash, IoError> {
+    fn report(&self, evidence: Evidence, peer: PeerId) -> Result<Hash, IoError> {
         panic!(
             "unexpected fork detected for peer {} with evidence: {:?}",
             peer, evidence
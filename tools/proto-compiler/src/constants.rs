@@ -1,12 +1,42 @@
 /// Tendermint protobuf version
+pub struct TendermintVersion {
+    /// Name of the module of this version, e.g. `v0_34`
+    pub ident: &'static str,
+    /// Human-readable name of this version, used in the module documentation
+    pub name: &'static str,
+    /// Repository URL
+    pub repo: &'static str,
+    /// Tag or commit ID of the protobuf definitions
+    pub commitish: &'static str,
+}
 
-/// Tendermint repository URL.
-pub const TENDERMINT_REPO: &str = "https://github.com/tendermint/tendermint";
+/// All the Tendermint protobuf versions, each generated in its own module.
+///
+/// The protobuf definitions are pinned, so that anyone generates the same
+/// structs. Branches are rejected, since they move.
 // Commitish formats:
 // Tag: v0.34.0-rc4
-// Branch: master
 // Commit ID (full length): d7d0ffea13c60c98b812d243ba5a2c375f341c15
-pub const TENDERMINT_COMMITISH: &str = "v0.34.0";
+pub const TENDERMINT_VERSIONS: &[TendermintVersion] = &[
+    TendermintVersion {
+        ident: "v0_34",
+        name: "Tendermint v0.34",
+        repo: "https://github.com/tendermint/tendermint",
+        commitish: "v0.34.0",
+    },
+    TendermintVersion {
+        ident: "v0_37",
+        name: "CometBFT v0.37",
+        repo: "https://github.com/cometbft/cometbft",
+        commitish: "v0.37.2",
+    },
+    TendermintVersion {
+        ident: "v0_38",
+        name: "CometBFT v0.38",
+        repo: "https://github.com/cometbft/cometbft",
+        commitish: "v0.38.0",
+    },
+];
 
 /// Predefined custom attributes for message annotations
 const PRIMITIVE_ENUM: &str = r#"#[derive(::num_derive::FromPrimitive, ::num_derive::ToPrimitive)]"#;
@@ -21,7 +51,7 @@ const BASE64STRING: &str = r#"#[serde(with = "crate::serializers::bytes::base64s
 const VEC_BASE64STRING: &str = r#"#[serde(with = "crate::serializers::bytes::vec_base64string")]"#;
 const OPTIONAL: &str = r#"#[serde(with = "crate::serializers::optional")]"#;
 const VEC_SKIP_IF_EMPTY: &str =
-    r#"#[serde(skip_serializing_if = "Vec::is_empty", with = "serde_bytes")]"#;
+    r#"#[serde(skip_serializing_if = "::prost::alloc::vec::Vec::is_empty", with = "serde_bytes")]"#;
 const NULLABLEVECARRAY: &str = r#"#[serde(with = "crate::serializers::txs")]"#;
 const NULLABLE: &str = r#"#[serde(with = "crate::serializers::nullable")]"#;
 const ALIAS_POWER_QUOTED: &str =
@@ -30,11 +60,15 @@ const PART_SET_HEADER_TOTAL: &str =
     r#"#[serde(with = "crate::serializers::part_set_header_total")]"#;
 const RENAME_EDPUBKEY: &str = r#"#[serde(rename = "tendermint/PubKeyEd25519", with = "crate::serializers::bytes::base64string")]"#;
 const RENAME_SECPPUBKEY: &str = r#"#[serde(rename = "tendermint/PubKeySecp256k1", with = "crate::serializers::bytes::base64string")]"#;
+const RENAME_SRPUBKEY: &str = r#"#[serde(rename = "tendermint/PubKeySr25519", with = "crate::serializers::bytes::base64string")]"#;
+const RENAME_BLSPUBKEY: &str = r#"#[serde(rename = "cometbft/PubKeyBls12_381", with = "crate::serializers::bytes::base64string")]"#;
 const RENAME_DUPLICATEVOTE: &str = r#"#[serde(rename = "tendermint/DuplicateVoteEvidence")]"#;
 const RENAME_LIGHTCLIENTATTACK: &str =
     r#"#[serde(rename = "tendermint/LightClientAttackEvidence")]"#;
-const EVIDENCE_VARIANT: &str = r#"#[serde(from = "crate::serializers::evidence::EvidenceVariant", into = "crate::serializers::evidence::EvidenceVariant")]"#;
+// `{version}` is replaced by the module name of the version being compiled
+const EVIDENCE_VARIANT: &str = r#"#[serde(from = "crate::serializers::evidence::{version}::EvidenceVariant", into = "crate::serializers::evidence::{version}::EvidenceVariant")]"#;
 const ALIAS_PARTS: &str = r#"#[serde(alias = "parts")]"#;
+const BASE64STRING_SKIP_IF_EMPTY: &str = r#"#[serde(default, skip_serializing_if = "::prost::alloc::vec::Vec::is_empty", with = "crate::serializers::bytes::base64string")]"#;
 
 /// Custom type attributes applied on top of protobuf structs
 /// The first item in the tuple defines the message where the annotation should apply and
@@ -53,6 +87,7 @@ pub static CUSTOM_TYPE_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.types.Vote", SERIALIZED),
     (".tendermint.types.BlockID", SERIALIZED),
     (".tendermint.types.PartSetHeader", SERIALIZED),
+    (".tendermint.types.Part", SERIALIZED),
     (".tendermint.types.LightClientAttackEvidence", SERIALIZED),
     (".tendermint.types.LightBlock", SERIALIZED),
     (".tendermint.types.SignedHeader", SERIALIZED),
@@ -104,6 +139,7 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
         PART_SET_HEADER_TOTAL,
     ),
     (".tendermint.types.PartSetHeader.hash", HEXSTRING),
+    (".tendermint.types.Part.bytes", HEXSTRING),
     (".tendermint.types.Header.height", QUOTED),
     (".tendermint.types.Header.time", OPTIONAL),
     (".tendermint.types.Header.last_commit_hash", HEXSTRING),
@@ -128,6 +164,14 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.types.Vote.validator_address", HEXSTRING),
     (".tendermint.types.Vote.signature", BASE64STRING),
     (".tendermint.types.Vote.timestamp", OPTIONAL),
+    (
+        ".tendermint.types.Vote.extension",
+        BASE64STRING_SKIP_IF_EMPTY,
+    ),
+    (
+        ".tendermint.types.Vote.extension_signature",
+        BASE64STRING_SKIP_IF_EMPTY,
+    ),
     (".tendermint.types.Validator.address", HEXSTRING),
     (
         ".tendermint.types.Validator.voting_power",
@@ -139,8 +183,24 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
     ), // Default is for /genesis deserialization
     (".tendermint.types.BlockMeta.block_size", QUOTED),
     (".tendermint.types.BlockMeta.num_txs", QUOTED),
+    (
+        ".tendermint.types.LightClientAttackEvidence.common_height",
+        QUOTED,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.total_voting_power",
+        QUOTED,
+    ),
     (".tendermint.crypto.PublicKey.sum.ed25519", RENAME_EDPUBKEY),
-    (".tendermint.crypto.PublicKey.sum.secp256k1", RENAME_SECPPUBKEY),
+    (
+        ".tendermint.crypto.PublicKey.sum.secp256k1",
+        RENAME_SECPPUBKEY,
+    ),
+    (".tendermint.crypto.PublicKey.sum.sr25519", RENAME_SRPUBKEY),
+    (
+        ".tendermint.crypto.PublicKey.sum.bls12381",
+        RENAME_BLSPUBKEY,
+    ),
     (
         ".tendermint.types.Evidence.sum.duplicate_vote_evidence",
         RENAME_DUPLICATEVOTE,
@@ -156,3 +216,13 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.crypto.Proof.aunts", VEC_BASE64STRING),
     (".tendermint.crypto.Proof.leaf_hash", BASE64STRING),
 ];
+
+/// Enumerations whose protobuf value names start with a prefix, which prost
+/// strips from the names of their variants. The proto3 JSON names of the
+/// values of other enumerations are those of their variants in
+/// SCREAMING_SNAKE_CASE.
+pub static PROTO3_JSON_ENUM_PREFIXES: &[(&str, &str)] = &[
+    ("BlockIdFlag", "BLOCK_ID_FLAG_"),
+    ("SignedMsgType", "SIGNED_MSG_TYPE_"),
+    ("Errors", "ERRORS_"),
+];
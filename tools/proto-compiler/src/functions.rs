@@ -1,11 +1,13 @@
 use git2::build::{CheckoutBuilder, RepoBuilder};
 use git2::{AutotagOption, Commit, FetchOptions, Oid, Reference, Repository};
-use std::fs::{copy, create_dir_all, remove_dir_all, File};
+use std::fs::{copy, create_dir_all, read, read_to_string, remove_dir_all, File, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
 use subtle_encoding::hex;
 use walkdir::WalkDir;
 
+use crate::constants::{TendermintVersion, PROTO3_JSON_ENUM_PREFIXES, TENDERMINT_VERSIONS};
+
 /// Clone or open+fetch a repository and check out a specific commitish
 /// In case of an existing repository, the origin remote will be set to `url`.
 pub fn get_commitish(dir: &PathBuf, url: &str, commitish: &str) {
@@ -134,33 +136,40 @@ fn find_reference_or_commit<'a>(
         }
     }
 
-    let mut reference = try_reference.unwrap();
-    if reference.is_branch() {
-        if tried_origin {
-            panic!("[error] => local branch names with 'origin/' prefix not supported");
-        }
-        try_reference = repo.resolve_reference_from_short_name(&format!("origin/{}", commitish));
-        reference = try_reference.unwrap();
-        if reference.is_branch() {
-            panic!("[error] => local branch names with 'origin/' prefix not supported");
-        }
+    let reference = try_reference.unwrap();
+    // Branches move, so that the generated structs couldn't be reproduced
+    if tried_origin || reference.is_branch() || reference.is_remote() {
+        panic!(
+            "[error] => {} is a branch, pin a tag or a commit ID instead",
+            commitish
+        );
     }
 
     let commit = reference.peel_to_commit().unwrap();
     (Some(reference), commit)
 }
 
-/// Copy generated files to target folder
+/// Copy the generated files of a Tendermint version to its target folder
 pub fn copy_files(src_dir: &PathBuf, target_dir: &PathBuf) {
     // Remove old compiled files
     remove_dir_all(target_dir).unwrap_or_default();
     create_dir_all(target_dir).unwrap();
 
+    copy_matching_files(src_dir, target_dir, |name| name.starts_with("tendermint."));
+}
+
+/// Copy the generated files shared by all the Tendermint versions, i.e. the
+/// well-known types, to the target folder
+pub fn copy_shared_files(src_dir: &PathBuf, target_dir: &PathBuf) {
+    copy_matching_files(src_dir, target_dir, |name| !name.starts_with("tendermint."));
+}
+
+fn copy_matching_files(src_dir: &PathBuf, target_dir: &PathBuf, filter: impl Fn(&str) -> bool) {
     // Copy new compiled files (prost does not use folder structures)
     let errors = WalkDir::new(src_dir)
         .into_iter()
         .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.file_type().is_file() && filter(e.file_name().to_str().unwrap()))
         .map(|e| {
             copy(
                 e.path(),
@@ -182,6 +191,400 @@ pub fn copy_files(src_dir: &PathBuf, target_dir: &PathBuf) {
     }
 }
 
+/// Append the gRPC service stubs generated by tonic in `grpc_dir` to the
+/// structs of the same packages in `prost_dir`, gated behind the `grpc` feature
+pub fn append_grpc_services(grpc_dir: &PathBuf, prost_dir: &PathBuf) {
+    const CLIENT_DOC: &str = "#[doc = r\" Generated client implementations.\"]";
+    const SERVER_DOC: &str = "#[doc = r\" Generated server implementations.\"]";
+    const GRPC_CFG: &str =
+        "#[cfg(feature = \"grpc\")]\n#[cfg_attr(docsrs, doc(cfg(feature = \"grpc\")))]\n";
+
+    for entry in WalkDir::new(grpc_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let generated = read_to_string(entry.path()).unwrap();
+        let services = match generated.find(CLIENT_DOC) {
+            Some(start) => &generated[start..],
+            None => continue,
+        };
+        let services = services
+            .replace(CLIENT_DOC, &format!("{}{}", GRPC_CFG, CLIENT_DOC))
+            .replace(SERVER_DOC, &format!("{}{}", GRPC_CFG, SERVER_DOC));
+
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(prost_dir.join(entry.file_name()))
+            .expect("generated structs file open failed");
+        file.write_all(services.as_bytes())
+            .expect("generated structs file write failed");
+    }
+}
+
+/// Replace the `std` paths of the generated structs with their `core` and
+/// `alloc` equivalents, so that they build without `std`
+pub fn replace_std_paths(prost_dir: &PathBuf) {
+    const REPLACEMENTS: &[(&str, &str)] = &[
+        ("::std::option::Option", "::core::option::Option"),
+        ("::std::vec::Vec", "::prost::alloc::vec::Vec"),
+        ("::std::string::String", "::prost::alloc::string::String"),
+        ("std::vec::Vec", "::prost::alloc::vec::Vec"),
+        ("std::string::String", "::prost::alloc::string::String"),
+    ];
+
+    for entry in WalkDir::new(prost_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let mut generated = read_to_string(entry.path()).unwrap();
+        for (std_path, path) in REPLACEMENTS {
+            generated = generated.replace(std_path, path);
+        }
+        let mut file = File::create(entry.path()).expect("generated structs file create failed");
+        file.write_all(generated.as_bytes())
+            .expect("generated structs file write failed");
+    }
+}
+
+/// Append the proto3 JSON implementations of the messages and enumerations
+/// to the generated structs
+pub fn append_proto3_json(prost_dir: &PathBuf) {
+    for entry in WalkDir::new(prost_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file()
+                && e.file_name().to_str().unwrap().starts_with("tendermint.")
+                && e.file_name().to_str().unwrap().ends_with(".rs")
+        })
+    {
+        let generated = read_to_string(entry.path()).unwrap();
+        let implementations = generate_proto3_json(&generated);
+
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(entry.path())
+            .expect("generated structs file open failed");
+        file.write_all(implementations.as_bytes())
+            .expect("generated structs file write failed");
+    }
+}
+
+/// A block of the generated structs
+enum Block {
+    Module(String),
+    Message,
+    Oneof,
+    Enumeration(String),
+    Other,
+}
+
+/// A field of a generated message
+enum Field {
+    /// The `proto3_json!` description of the field
+    Single(String),
+    /// A oneof field, with the path of its enumeration
+    Oneof(String, String),
+}
+
+/// Generate the `proto3_json!` and `proto3_enum!` invocations for the
+/// messages and enumerations of a file of generated structs
+fn generate_proto3_json(generated: &str) -> String {
+    // Messages and enumerations, by path relative to the file's module
+    let mut messages: Vec<(String, Vec<Field>)> = vec![];
+    let mut oneofs: Vec<(String, Vec<String>)> = vec![];
+    let mut enumerations: Vec<(String, Vec<String>)> = vec![];
+
+    let mut blocks: Vec<Block> = vec![];
+    let mut derives = String::new();
+    let mut prost_attribute: Option<String> = None;
+
+    for line in generated.lines().map(str::trim) {
+        let modules: Vec<&str> = blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Module(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        let path = |name: &str| {
+            modules
+                .iter()
+                .copied()
+                .chain(std::iter::once(name))
+                .collect::<Vec<_>>()
+                .join("::")
+        };
+
+        if line.starts_with("#[derive(") {
+            derives.push_str(line);
+        } else if line.starts_with("#[prost(") {
+            prost_attribute = Some(line.to_string());
+        } else if let Some(name) = block_name(line, "pub mod ") {
+            blocks.push(Block::Module(name));
+        } else if let Some(name) = block_name(line, "pub struct ") {
+            messages.push((path(&name), vec![]));
+            blocks.push(Block::Message);
+            derives.clear();
+        } else if let Some(name) = block_name(line, "pub enum ") {
+            if derives.contains("::prost::Oneof") {
+                oneofs.push((path(&name), vec![]));
+                blocks.push(Block::Oneof);
+            } else if derives.contains("::prost::Enumeration") {
+                enumerations.push((path(&name), vec![]));
+                blocks.push(Block::Enumeration(name));
+            } else {
+                blocks.push(Block::Other);
+            }
+            derives.clear();
+        } else if line == "}" {
+            blocks.pop();
+        } else if line.starts_with("//") || line.starts_with("#[") {
+            continue;
+        } else {
+            match blocks.last() {
+                Some(Block::Message) => {
+                    if let Some(attribute) = prost_attribute.take() {
+                        let field = line
+                            .strip_prefix("pub ")
+                            .and_then(|f| f.split(':').next())
+                            .unwrap();
+                        messages
+                            .last_mut()
+                            .unwrap()
+                            .1
+                            .push(proto3_json_field(field, &attribute, &modules));
+                    }
+                }
+                Some(Block::Oneof) => {
+                    if let Some(attribute) = prost_attribute.take() {
+                        let variant = line.split('(').next().unwrap();
+                        let proto_name = to_snake_case(variant);
+                        oneofs.last_mut().unwrap().1.push(format!(
+                            "{}({}), \"{}\", \"{}\";",
+                            variant,
+                            proto3_json_kind(&attribute, &modules),
+                            to_json_name(&proto_name),
+                            proto_name
+                        ));
+                    }
+                }
+                Some(Block::Enumeration(name)) => {
+                    if let Some(variant) = line.split(" = ").next().filter(|_| line.contains(" = "))
+                    {
+                        let prefix = PROTO3_JSON_ENUM_PREFIXES
+                            .iter()
+                            .find(|(e, _)| e == name)
+                            .map_or("", |(_, prefix)| prefix);
+                        enumerations.last_mut().unwrap().1.push(format!(
+                            "{} = \"{}{}\",",
+                            variant,
+                            prefix,
+                            to_snake_case(variant).to_uppercase()
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut content = String::new();
+    if !messages.is_empty() {
+        content.push_str("\nproto3_json! {\n");
+        for (message, fields) in messages {
+            content.push_str(&format!("    {} {{\n", message));
+            for field in fields {
+                let field = match field {
+                    Field::Single(field) => field,
+                    Field::Oneof(field, oneof) => {
+                        // The oneofs follow the messages they belong to
+                        let variants = &oneofs
+                            .iter()
+                            .find(|(path, _)| *path == oneof)
+                            .expect("oneof not found")
+                            .1;
+                        let variants: String = variants
+                            .iter()
+                            .map(|v| format!("            {}\n", v))
+                            .collect();
+                        format!("(oneof {}: {} {{\n{}        }})", field, oneof, variants)
+                    }
+                };
+                content.push_str(&format!("        {}\n", field));
+            }
+            content.push_str("    }\n");
+        }
+        content.push_str("}\n");
+    }
+
+    if !enumerations.is_empty() {
+        content.push_str("\nproto3_enum! {\n");
+        for (enumeration, values) in enumerations {
+            content.push_str(&format!("    {} {{\n", enumeration));
+            for value in values {
+                content.push_str(&format!("        {}\n", value));
+            }
+            content.push_str("    }\n");
+        }
+        content.push_str("}\n");
+    }
+    content
+}
+
+/// The name of the item opened by the given line, e.g. `pub mod name {`
+fn block_name(line: &str, prefix: &str) -> Option<String> {
+    line.strip_prefix(prefix)
+        .and_then(|l| l.strip_suffix(" {"))
+        .map(String::from)
+}
+
+/// A field of a message, given its prost attribute
+fn proto3_json_field(field: &str, attribute: &str, modules: &[&str]) -> Field {
+    let proto_name = field.strip_prefix("r#").unwrap_or(field);
+    if let Some(oneof) = attribute_value(attribute, "oneof") {
+        Field::Oneof(field.to_string(), resolve_path(&oneof, modules))
+    } else {
+        Field::Single(format!(
+            "({}: {}, \"{}\", \"{}\")",
+            field,
+            proto3_json_kind(attribute, modules),
+            to_json_name(proto_name),
+            proto_name
+        ))
+    }
+}
+
+/// The kind of a field, given its prost attribute, e.g. `Optional<Message>`
+/// for `#[prost(message, optional, tag="1")]`
+fn proto3_json_kind(attribute: &str, modules: &[&str]) -> String {
+    let arguments = attribute
+        .strip_prefix("#[prost(")
+        .and_then(|a| a.strip_suffix(")]"))
+        .unwrap();
+    let mut arguments = arguments.split(", ");
+    let kind = arguments.next().unwrap();
+    let kind = match kind {
+        "bool" | "string" | "double" | "float" => "Plain".to_string(),
+        "int32" | "uint32" | "sint32" | "fixed32" | "sfixed32" => "Int32".to_string(),
+        "int64" | "uint64" | "sint64" | "fixed64" | "sfixed64" => "Int64".to_string(),
+        "bytes" => "Bytes".to_string(),
+        "message" => "Message".to_string(),
+        _ => match attribute_value(kind, "enumeration") {
+            Some(enumeration) => format!("Enum<{}>", resolve_path(&enumeration, modules)),
+            None => panic!("unsupported field kind: {}", kind),
+        },
+    };
+    match arguments.next() {
+        Some("optional") => format!("Optional<{}>", kind),
+        Some("repeated") => format!("Repeated<{}>", kind),
+        _ => kind,
+    }
+}
+
+/// The value of an argument of a prost attribute, e.g. `oneof="sum::Sum"`
+fn attribute_value(attribute: &str, name: &str) -> Option<String> {
+    let start = attribute.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = start + attribute[start..].find('"')?;
+    Some(attribute[start..end].to_string())
+}
+
+/// The path of an item relative to the file's module, given its path
+/// relative to the current module
+fn resolve_path(path: &str, modules: &[&str]) -> String {
+    let mut depth = modules.len();
+    let mut path = path;
+    while let Some(rest) = path.strip_prefix("super::") {
+        if depth == 0 {
+            break;
+        }
+        depth -= 1;
+        path = rest;
+    }
+    modules[..depth]
+        .iter()
+        .copied()
+        .chain(std::iter::once(path))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Convert an UpperCamelCase name to snake_case
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Convert a protobuf field name to its JSON name, as protoc does
+fn to_json_name(name: &str) -> String {
+    let mut json = String::new();
+    let mut capitalize = false;
+    for c in name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            json.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            json.push(c);
+        }
+    }
+    json
+}
+
+/// Copy a folder recursively, e.g. to generate the structs in a copy of the
+/// `tendermint-proto` sources
+pub fn copy_dir(src_dir: &PathBuf, target_dir: &PathBuf) {
+    for entry in WalkDir::new(src_dir).into_iter().filter_map(|e| e.ok()) {
+        let target = target_dir.join(entry.path().strip_prefix(src_dir).unwrap());
+        if entry.file_type().is_dir() {
+            create_dir_all(&target).unwrap();
+        } else {
+            copy(entry.path(), &target).unwrap();
+        }
+    }
+}
+
+/// List the files which differ between two folders, or exist in only one of
+/// them, relative to the folders
+pub fn diff_dirs(dir: &PathBuf, other_dir: &PathBuf) -> Vec<PathBuf> {
+    let files = |dir: &PathBuf| {
+        WalkDir::new(dir)
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.path().strip_prefix(dir).unwrap().to_path_buf())
+            .collect::<Vec<_>>()
+    };
+
+    let mut differences = vec![];
+    for file in files(dir) {
+        let other_file = other_dir.join(&file);
+        if !other_file.exists() || read(dir.join(&file)).unwrap() != read(other_file).unwrap() {
+            differences.push(file);
+        }
+    }
+    for file in files(other_dir) {
+        if !dir.join(&file).exists() {
+            differences.push(file);
+        }
+    }
+    differences
+}
+
 /// Walk through the list of directories and gather all *.proto files
 pub fn find_proto_files(proto_paths: Vec<PathBuf>) -> Vec<PathBuf> {
     let mut protos: Vec<PathBuf> = vec![];
@@ -202,8 +605,36 @@ pub fn find_proto_files(proto_paths: Vec<PathBuf>) -> Vec<PathBuf> {
     protos
 }
 
-/// Create tendermint.rs with library information
-pub fn generate_tendermint_lib(prost_dir: &PathBuf, tendermint_lib_target: &PathBuf) {
+/// Create tendermint.rs with the modules of all the Tendermint versions
+pub fn generate_tendermint_lib(tendermint_lib_target: &PathBuf) {
+    let mut content =
+        String::from("//! Tendermint-proto auto-generated sub-modules for Tendermint\n");
+
+    for (i, version) in TENDERMINT_VERSIONS.iter().enumerate() {
+        // The first version is always available, the others are behind features
+        if i == 0 {
+            content = format!("{}\npub mod {};\n", content, version.ident);
+        } else {
+            content = format!(
+                "{}\n#[cfg(feature = \"{ident}\")]\n#[cfg_attr(docsrs, doc(cfg(feature = \"{ident}\")))]\npub mod {ident};\n",
+                content,
+                ident = version.ident
+            );
+        }
+    }
+
+    let mut file =
+        File::create(tendermint_lib_target).expect("tendermint library file create failed");
+    file.write_all(content.as_bytes())
+        .expect("tendermint library file write failed");
+}
+
+/// Create the module of a Tendermint version with library information
+pub fn generate_tendermint_mod(
+    prost_dir: &PathBuf,
+    tendermint_mod_target: &PathBuf,
+    version: &TendermintVersion,
+) {
     let file_names = WalkDir::new(prost_dir)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -215,8 +646,10 @@ pub fn generate_tendermint_lib(prost_dir: &PathBuf, tendermint_lib_target: &Path
         .map(|d| d.file_name().to_str().unwrap().to_string())
         .collect::<Vec<_>>();
 
-    let mut content =
-        String::from("//! Tendermint-proto auto-generated sub-modules for Tendermint\n");
+    let mut content = format!(
+        "//! Tendermint-proto auto-generated sub-modules for {}\n",
+        version.name
+    );
     let tab = "    ".to_string();
 
     for file_name in file_names {
@@ -232,8 +665,9 @@ pub fn generate_tendermint_lib(prost_dir: &PathBuf, tendermint_lib_target: &Path
         let mut tab_count = parts.len();
 
         let mut inner_content = format!(
-            "{}include!(\"prost/{}\");",
+            "{}include!(\"../prost/{}/{}\");",
             tab.repeat(tab_count),
+            version.ident,
             file_name
         );
 
@@ -254,13 +688,13 @@ pub fn generate_tendermint_lib(prost_dir: &PathBuf, tendermint_lib_target: &Path
         "{}\npub mod meta {{\n{}pub const REPOSITORY: &str = \"{}\";\n{}pub const COMMITISH: &str = \"{}\";\n}}\n",
         content,
         tab,
-        crate::constants::TENDERMINT_REPO,
+        version.repo,
         tab,
-        crate::constants::TENDERMINT_COMMITISH,
+        version.commitish,
     );
 
     let mut file =
-        File::create(tendermint_lib_target).expect("tendermint library file create failed");
+        File::create(tendermint_mod_target).expect("tendermint library file create failed");
     file.write_all(content.as_bytes())
         .expect("tendermint library file write failed");
 }
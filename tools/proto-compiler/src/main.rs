@@ -3,27 +3,30 @@ use std::path::PathBuf;
 use tempdir::TempDir;
 
 mod functions;
-use functions::{copy_files, find_proto_files, generate_tendermint_lib, get_commitish};
+use functions::{
+    append_grpc_services, append_proto3_json, copy_dir, copy_files, copy_shared_files, diff_dirs,
+    find_proto_files, generate_tendermint_lib, generate_tendermint_mod, get_commitish,
+    replace_std_paths,
+};
 
 mod constants;
-use constants::{
-    CUSTOM_FIELD_ATTRIBUTES, CUSTOM_TYPE_ATTRIBUTES, TENDERMINT_COMMITISH, TENDERMINT_REPO,
-};
+use constants::{CUSTOM_FIELD_ATTRIBUTES, CUSTOM_TYPE_ATTRIBUTES, TENDERMINT_VERSIONS};
 
 fn main() {
     let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    let tendermint_lib_target = root
-        .join("..")
-        .join("..")
-        .join("proto")
-        .join("src")
-        .join("tendermint.rs");
-    let target_dir = root
-        .join("..")
-        .join("..")
-        .join("proto")
-        .join("src")
-        .join("prost");
+    let committed_src_dir = root.join("..").join("..").join("proto").join("src");
+    // With `--check`, the structs are generated in a copy of the sources of
+    // tendermint-proto, which must be identical to the committed ones
+    let check = std::env::args().any(|arg| arg == "--check");
+    let proto_src_dir = if check {
+        let dir = TempDir::new("tendermint_proto_src").unwrap().into_path();
+        copy_dir(&committed_src_dir, &dir);
+        dir
+    } else {
+        committed_src_dir.clone()
+    };
+    let tendermint_lib_target = proto_src_dir.join("tendermint.rs");
+    let target_dir = proto_src_dir.join("prost");
     let out_dir = var("OUT_DIR")
         .map(PathBuf::from)
         .or_else(|_| TempDir::new("tendermint_proto_out").map(|d| d.into_path()))
@@ -37,51 +40,103 @@ fn main() {
             .to_string()
     }));
 
-    println!(
-        "[info] => Fetching {} at {} into {:?}",
-        TENDERMINT_REPO, TENDERMINT_COMMITISH, tendermint_dir
-    );
-    get_commitish(
-        &PathBuf::from(&tendermint_dir),
-        TENDERMINT_REPO,
-        TENDERMINT_COMMITISH,
-    ); // This panics if it fails.
+    for version in TENDERMINT_VERSIONS {
+        println!(
+            "[info] => Fetching {} at {} into {:?}",
+            version.repo, version.commitish, tendermint_dir
+        );
+        get_commitish(&tendermint_dir, version.repo, version.commitish); // This panics if it fails.
 
-    let proto_paths = vec![tendermint_dir.join("proto")];
-    let proto_includes_paths = vec![
-        tendermint_dir.join("proto"),
-        tendermint_dir.join("third_party").join("proto"),
-    ];
-    // List available proto files
-    let protos = find_proto_files(proto_paths);
+        let proto_paths = vec![tendermint_dir.join("proto")];
+        let proto_includes_paths = vec![
+            tendermint_dir.join("proto"),
+            tendermint_dir.join("third_party").join("proto"),
+        ];
+        // List available proto files
+        let protos = find_proto_files(proto_paths);
 
-    // Compile proto files with added annotations, exchange prost_types to our own
-    let mut pb = prost_build::Config::new();
-    pb.out_dir(&out_dir);
-    for type_attribute in CUSTOM_TYPE_ATTRIBUTES {
-        pb.type_attribute(type_attribute.0, type_attribute.1);
-    }
-    for field_attribute in CUSTOM_FIELD_ATTRIBUTES {
-        pb.field_attribute(field_attribute.0, field_attribute.1);
+        let version_out_dir = out_dir.join(version.ident);
+        std::fs::create_dir_all(&version_out_dir).unwrap();
+
+        // Compile proto files with added annotations, exchange prost_types to our own
+        let mut pb = prost_build::Config::new();
+        pb.out_dir(&version_out_dir);
+        for type_attribute in CUSTOM_TYPE_ATTRIBUTES {
+            pb.type_attribute(
+                type_attribute.0,
+                type_attribute.1.replace("{version}", version.ident),
+            );
+        }
+        for field_attribute in CUSTOM_FIELD_ATTRIBUTES {
+            pb.field_attribute(field_attribute.0, field_attribute.1);
+        }
+        pb.compile_well_known_types();
+        // The below in-place path redirection removes the Duration and Timestamp structs from
+        // google.protobuf.rs. We replace them with our own versions that have valid doctest comments.
+        // See also https://github.com/danburkert/prost/issues/374 .
+        pb.extern_path(
+            ".google.protobuf.Duration",
+            "crate::google::protobuf::Duration",
+        );
+        pb.extern_path(
+            ".google.protobuf.Timestamp",
+            "crate::google::protobuf::Timestamp",
+        );
+        println!("[info] => Creating structs for {}.", version.name);
+        pb.compile_protos(&protos, &proto_includes_paths).unwrap();
+        replace_std_paths(&version_out_dir);
+        append_proto3_json(&version_out_dir);
+
+        // The gRPC services are compiled separately, so that only their stubs
+        // are appended to the structs, behind the `grpc` feature
+        let grpc_out_dir = out_dir.join(format!("{}_grpc", version.ident));
+        std::fs::create_dir_all(&grpc_out_dir).unwrap();
+        let mut grpc_pb = prost_build::Config::new();
+        grpc_pb.extern_path(
+            ".google.protobuf.Duration",
+            "crate::google::protobuf::Duration",
+        );
+        grpc_pb.extern_path(
+            ".google.protobuf.Timestamp",
+            "crate::google::protobuf::Timestamp",
+        );
+        println!("[info] => Creating gRPC services for {}.", version.name);
+        tonic_build::configure()
+            .build_client(true)
+            .build_server(true)
+            .out_dir(&grpc_out_dir)
+            .compile_with_config(grpc_pb, &protos, &proto_includes_paths)
+            .unwrap();
+        append_grpc_services(&grpc_out_dir, &version_out_dir);
+
+        println!("[info] => Removing old structs and copying new structs.");
+        copy_files(&version_out_dir, &target_dir.join(version.ident)); // This panics if it fails.
+        generate_tendermint_mod(
+            &version_out_dir,
+            &proto_src_dir
+                .join("tendermint")
+                .join(format!("{}.rs", version.ident)),
+            version,
+        );
     }
-    pb.compile_well_known_types();
-    // The below in-place path redirection removes the Duration and Timestamp structs from
-    // google.protobuf.rs. We replace them with our own versions that have valid doctest comments.
-    // See also https://github.com/danburkert/prost/issues/374 .
-    pb.extern_path(
-        ".google.protobuf.Duration",
-        "super::super::google::protobuf::Duration",
-    );
-    pb.extern_path(
-        ".google.protobuf.Timestamp",
-        "super::super::google::protobuf::Timestamp",
-    );
-    println!("[info] => Creating structs.");
-    pb.compile_protos(&protos, &proto_includes_paths).unwrap();
 
-    println!("[info] => Removing old structs and copying new structs.");
-    copy_files(&out_dir, &target_dir); // This panics if it fails.
-    generate_tendermint_lib(&out_dir, &tendermint_lib_target);
+    // The well-known types are the same for all the versions
+    let last_version = TENDERMINT_VERSIONS.last().unwrap();
+    copy_shared_files(&out_dir.join(last_version.ident), &target_dir);
+    generate_tendermint_lib(&tendermint_lib_target);
+
+    if check {
+        let differences = diff_dirs(&proto_src_dir, &committed_src_dir);
+        if !differences.is_empty() {
+            println!("[error] => The committed structs are out of date:");
+            for file in differences {
+                println!("    {}", file.display());
+            }
+            println!("[error] => Run `cargo run` in the compiler folder and commit the result.");
+            std::process::exit(1);
+        }
+        println!("[info] => The committed structs are up to date.");
+    }
 
     println!("[info] => Done!");
 }